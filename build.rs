@@ -24,7 +24,7 @@ fn main() {
     let git_commit = get_git_commit();
 
     // Get build time
-    let build_time = chrono::Utc::now().to_rfc3339();
+    let build_time = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
 
     // Get Rust version
     let rust_version = get_rust_version();
@@ -0,0 +1,2 @@
+ALTER TABLE student_deliverables_components DROP COLUMN IF EXISTS weight;
+ALTER TABLE group_deliverables_components DROP COLUMN IF EXISTS weight;
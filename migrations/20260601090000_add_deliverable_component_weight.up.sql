@@ -0,0 +1,5 @@
+ALTER TABLE group_deliverables_components
+    ADD COLUMN weight INTEGER NOT NULL DEFAULT 0 CHECK (weight BETWEEN 0 AND 100);
+
+ALTER TABLE student_deliverables_components
+    ADD COLUMN weight INTEGER NOT NULL DEFAULT 0 CHECK (weight BETWEEN 0 AND 100);
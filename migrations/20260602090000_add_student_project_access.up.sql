@@ -0,0 +1,8 @@
+CREATE TABLE student_project_access (
+    student_project_access_id SERIAL PRIMARY KEY,
+    student_id INTEGER NOT NULL REFERENCES students(student_id) ON DELETE CASCADE,
+    project_id INTEGER NOT NULL REFERENCES projects(project_id) ON DELETE CASCADE,
+    security_code_id INTEGER REFERENCES security_codes(security_code_id) ON DELETE SET NULL,
+    granted_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (student_id, project_id)
+);
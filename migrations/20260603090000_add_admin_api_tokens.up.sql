@@ -0,0 +1,11 @@
+CREATE TABLE admin_api_tokens (
+    api_token_id SERIAL PRIMARY KEY,
+    admin_id INTEGER NOT NULL REFERENCES admins(admin_id) ON DELETE CASCADE,
+    name VARCHAR NOT NULL,
+    token_hash VARCHAR NOT NULL UNIQUE,
+    -- comma separated list of the authorities granted to the token
+    permissions TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    revoked_at TIMESTAMPTZ,
+    UNIQUE (admin_id, name)
+);
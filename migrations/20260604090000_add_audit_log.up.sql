@@ -0,0 +1,15 @@
+CREATE TABLE audit_log (
+    audit_log_id SERIAL PRIMARY KEY,
+    actor_admin_id INTEGER REFERENCES admins(admin_id) ON DELETE SET NULL,
+    actor_student_id INTEGER REFERENCES students(student_id) ON DELETE SET NULL,
+    -- machine readable action name, e.g. impersonation_started
+    action VARCHAR NOT NULL,
+    -- project the action relates to, if any
+    project_id INTEGER REFERENCES projects(project_id) ON DELETE CASCADE,
+    target_type VARCHAR,
+    target_id INTEGER,
+    details TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX audit_log_project_created_at_idx ON audit_log (project_id, created_at DESC);
@@ -0,0 +1,8 @@
+CREATE TABLE impersonation_sessions (
+    impersonation_session_id SERIAL PRIMARY KEY,
+    admin_id INTEGER NOT NULL REFERENCES admins(admin_id) ON DELETE CASCADE,
+    student_id INTEGER NOT NULL REFERENCES students(student_id) ON DELETE CASCADE,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    ended_at TIMESTAMPTZ
+);
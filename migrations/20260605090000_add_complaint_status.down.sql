@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS complaints_created_at_idx;
+
+ALTER TABLE complaints
+    DROP COLUMN IF EXISTS status;
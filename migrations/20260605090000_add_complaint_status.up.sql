@@ -0,0 +1,4 @@
+ALTER TABLE complaints
+    ADD COLUMN status VARCHAR NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'resolved'));
+
+CREATE INDEX complaints_created_at_idx ON complaints (created_at DESC);
@@ -0,0 +1,9 @@
+CREATE TABLE fair_attendance (
+    fair_attendance_id SERIAL PRIMARY KEY,
+    fair_id INTEGER NOT NULL REFERENCES fairs(fair_id) ON DELETE CASCADE,
+    group_id INTEGER NOT NULL REFERENCES groups(group_id) ON DELETE CASCADE,
+    student_id INTEGER NOT NULL REFERENCES students(student_id) ON DELETE CASCADE,
+    recorded_by_admin_id INTEGER REFERENCES admins(admin_id) ON DELETE SET NULL,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT fair_attendance_unique_student UNIQUE (fair_id, student_id)
+);
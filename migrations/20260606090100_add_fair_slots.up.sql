@@ -0,0 +1,9 @@
+CREATE TABLE fair_slots (
+    fair_slot_id SERIAL PRIMARY KEY,
+    fair_id INTEGER NOT NULL REFERENCES fairs(fair_id) ON DELETE CASCADE,
+    group_id INTEGER NOT NULL REFERENCES groups(group_id) ON DELETE CASCADE,
+    starts_at TIMESTAMPTZ NOT NULL,
+    ends_at TIMESTAMPTZ NOT NULL,
+    CONSTRAINT fair_slots_unique_group UNIQUE (fair_id, group_id),
+    CONSTRAINT fair_slots_valid_range CHECK (ends_at > starts_at)
+);
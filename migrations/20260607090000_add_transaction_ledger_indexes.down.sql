@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS transactions_selection_timestamp_idx;
+DROP INDEX IF EXISTS transactions_buyer_timestamp_idx;
+DROP INDEX IF EXISTS group_members_student_id_idx;
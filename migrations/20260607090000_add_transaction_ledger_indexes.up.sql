@@ -0,0 +1,5 @@
+-- Transactions of a student are looked up through the groups they are members of,
+-- both as buyers and as sellers, and read in chronological order
+CREATE INDEX group_members_student_id_idx ON group_members (student_id);
+CREATE INDEX transactions_buyer_timestamp_idx ON transactions (buyer_group_id, timestamp, transaction_id);
+CREATE INDEX transactions_selection_timestamp_idx ON transactions (group_deliverable_selection_id, timestamp, transaction_id);
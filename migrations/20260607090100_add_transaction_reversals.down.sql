@@ -0,0 +1,9 @@
+DELETE FROM transactions WHERE reverses_transaction_id IS NOT NULL;
+
+DROP INDEX IF EXISTS transactions_unique_purchase;
+ALTER TABLE transactions
+    ADD CONSTRAINT transactions_unique_purchase UNIQUE (buyer_group_id, group_deliverable_selection_id, group_deliverable_component_id);
+
+ALTER TABLE transactions
+    DROP COLUMN IF EXISTS reversed_by_admin_id,
+    DROP COLUMN IF EXISTS reverses_transaction_id;
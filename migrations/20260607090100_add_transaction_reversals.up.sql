@@ -0,0 +1,11 @@
+-- A reversing entry points to the transaction it cancels, each transaction can be reversed once
+ALTER TABLE transactions
+    ADD COLUMN reverses_transaction_id INTEGER UNIQUE REFERENCES transactions (transaction_id) ON DELETE CASCADE,
+    ADD COLUMN reversed_by_admin_id INTEGER REFERENCES admins (admin_id) ON DELETE SET NULL;
+
+-- Reversing entries repeat the purchase they cancel, so only purchases must be unique
+ALTER TABLE transactions
+    DROP CONSTRAINT IF EXISTS transactions_unique_purchase;
+CREATE UNIQUE INDEX transactions_unique_purchase
+    ON transactions (buyer_group_id, group_deliverable_selection_id, group_deliverable_component_id)
+    WHERE reverses_transaction_id IS NULL;
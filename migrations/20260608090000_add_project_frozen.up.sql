@@ -0,0 +1,3 @@
+-- A frozen project rejects student mutations (groups, selections, uploads) until unfrozen
+ALTER TABLE projects
+    ADD COLUMN frozen BOOLEAN NOT NULL DEFAULT FALSE;
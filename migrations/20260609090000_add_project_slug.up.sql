@@ -0,0 +1,46 @@
+-- Short, URL friendly project identifier, generated from the name and editable
+ALTER TABLE projects
+    ADD COLUMN slug TEXT;
+
+-- Existing projects get a slug from their name the way new ones do: the oldest project keeps
+-- the plain slug and the next ones get the first free of -2, -3, ...
+-- Numeric slugs are prefixed, as they would be read as project ids.
+DO
+$$
+    DECLARE
+        project   RECORD;
+        base      TEXT;
+        suffix    TEXT;
+        candidate TEXT;
+        n         INT;
+    BEGIN
+        FOR project IN SELECT project_id, name FROM projects ORDER BY project_id
+            LOOP
+                base := RTRIM(LEFT(TRIM(BOTH '-' FROM
+                                        REGEXP_REPLACE(LOWER(project.name), '[^a-z0-9]+', '-', 'g')),
+                                   64), '-');
+                IF base = '' THEN
+                    base := 'project';
+                ELSIF base ~ '^[0-9]+$' THEN
+                    base := 'project-' || base;
+                END IF;
+
+                candidate := base;
+                n := 1;
+                WHILE EXISTS (SELECT 1 FROM projects WHERE slug = candidate)
+                    LOOP
+                        n := n + 1;
+                        suffix := '-' || n;
+                        candidate := RTRIM(LEFT(base, 64 - LENGTH(suffix)), '-') || suffix;
+                    END LOOP;
+
+                UPDATE projects SET slug = candidate WHERE project_id = project.project_id;
+            END LOOP;
+    END
+$$;
+
+ALTER TABLE projects
+    ALTER COLUMN slug SET NOT NULL;
+
+ALTER TABLE projects
+    ADD CONSTRAINT projects_slug_key UNIQUE (slug);
@@ -0,0 +1,5 @@
+-- Password reset tokens are stateless, the hash of each used one is kept so it cannot be replayed
+CREATE TABLE used_reset_tokens (
+    token_hash TEXT PRIMARY KEY,
+    used_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
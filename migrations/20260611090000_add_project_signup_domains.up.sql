@@ -0,0 +1,4 @@
+-- Comma separated email domains whose students can redeem the codes of the project,
+-- NULL keeps the global allowed_signup_domains
+ALTER TABLE projects
+    ADD COLUMN allowed_signup_domains TEXT;
@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS complaints_filed_by_student_idx;
+
+ALTER TABLE complaints
+    DROP COLUMN IF EXISTS resolution_notes,
+    DROP COLUMN IF EXISTS filed_by_student_id;
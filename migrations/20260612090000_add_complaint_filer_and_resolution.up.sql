@@ -0,0 +1,16 @@
+-- Student who filed the complaint, existing complaints are credited to the current leader
+-- of the filing group
+ALTER TABLE complaints
+    ADD COLUMN filed_by_student_id INTEGER REFERENCES students ON DELETE SET NULL;
+
+UPDATE complaints c
+SET filed_by_student_id = gm.student_id
+FROM group_members gm
+WHERE gm.group_id = c.from_group_id
+  AND gm.student_role_id = 1;
+
+CREATE INDEX complaints_filed_by_student_idx ON complaints (filed_by_student_id, created_at DESC);
+
+-- Notes shown to the student once the complaint is resolved
+ALTER TABLE complaints
+    ADD COLUMN resolution_notes TEXT;
@@ -0,0 +1,6 @@
+-- Students only see published projects, the existing ones stay visible
+ALTER TABLE projects
+    ADD COLUMN published BOOLEAN NOT NULL DEFAULT TRUE;
+
+ALTER TABLE projects
+    ALTER COLUMN published SET DEFAULT FALSE;
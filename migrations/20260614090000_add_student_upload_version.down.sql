@@ -0,0 +1,2 @@
+ALTER TABLE student_uploads
+    DROP COLUMN IF EXISTS version;
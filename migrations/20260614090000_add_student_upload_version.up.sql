@@ -0,0 +1,3 @@
+-- Every upload so far has a single stored file
+ALTER TABLE student_uploads
+    ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,3 @@
+-- Tokens issued before this time are rejected, forcing the student to log in again
+ALTER TABLE students
+    ADD COLUMN sessions_revoked_at TIMESTAMPTZ NULL;
@@ -0,0 +1,20 @@
+-- Path of every stored version of an upload, the current one included
+CREATE TABLE student_upload_versions (
+    upload_id   INTEGER     NOT NULL REFERENCES student_uploads ON DELETE CASCADE,
+    version     INTEGER     NOT NULL,
+    path        VARCHAR     NOT NULL,
+    uploaded_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (upload_id, version)
+);
+
+-- Earlier versions were named after the current file: <student>.zip first, then <student>_vN.zip
+INSERT INTO student_upload_versions (upload_id, version, path, uploaded_at)
+SELECT upload_id,
+       v,
+       CASE
+           WHEN v = version THEN path
+           WHEN v = 1 THEN regexp_replace(path, '_v[0-9]+\.zip$', '.zip')
+           ELSE regexp_replace(path, '(_v[0-9]+)?\.zip$', '_v' || v || '.zip')
+       END,
+       timestamp
+FROM student_uploads, generate_series(1, version) AS v;
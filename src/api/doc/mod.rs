@@ -1,20 +1,36 @@
+use crate::api::features::__path_features;
+use crate::api::health::migrations::__path_migrations_check;
 use crate::api::health::{__path_health_check, __path_liveness_check};
+use crate::api::status::__path_status;
 use crate::api::v1::admins::auth::forgot_password::__path_forgot_password_handler;
 use crate::api::v1::admins::auth::login::__path_admins_login_handler;
-use crate::api::v1::admins::auth::reset_password::__path_reset_password_handler;
+use crate::api::v1::admins::auth::reset_password::{
+    __path_reset_password_handler, __path_verify_reset_token_handler,
+};
 use crate::api::v1::admins::blacklist::create::__path_add_to_blacklist_handler;
 use crate::api::v1::admins::blacklist::delete::__path_delete_blacklist_handler;
 use crate::api::v1::admins::blacklist::get::__path_get_blacklist_handler;
 use crate::api::v1::admins::blacklist::list::__path_list_blacklist_handler;
 use crate::api::v1::admins::blacklist::update::__path_update_blacklist_handler;
+use crate::api::v1::admins::complaints::export::__path_export_complaints_handler;
+use crate::api::v1::admins::complaints::list::__path_get_complaints_feed;
+use crate::api::v1::admins::complaints::update_status::__path_update_complaint_status;
+use crate::api::v1::admins::dev::seed::__path_seed_demo_data_handler;
+use crate::api::v1::admins::emails::preview::__path_preview_email_handler;
+use crate::api::v1::admins::fairs::attendance::{
+    __path_list_attendance_handler, __path_record_attendance_handler,
+};
 use crate::api::v1::admins::fairs::create::__path_create_fair_handler;
+use crate::api::v1::admins::fairs::delete::__path_delete_fair_handler;
 use crate::api::v1::admins::fairs::disable::__path_disable_fair_handler;
 use crate::api::v1::admins::fairs::enable::__path_enable_fair_handler;
 use crate::api::v1::admins::fairs::read::{
     __path_get_fair_by_project_handler, __path_get_fair_handler,
 };
 use crate::api::v1::admins::fairs::report::__path_fair_report_handler;
+use crate::api::v1::admins::fairs::slots::__path_assign_slot_handler;
 use crate::api::v1::admins::fairs::update::__path_update_fair_handler;
+use crate::api::v1::admins::group_deliverable_components::batch_delete::__path_batch_delete_group_components_handler;
 use crate::api::v1::admins::group_deliverable_components::create::__path_create_group_component_handler;
 use crate::api::v1::admins::group_deliverable_components::delete::__path_delete_group_component_handler;
 use crate::api::v1::admins::group_deliverable_components::read::__path_get_all_group_components_handler;
@@ -23,8 +39,10 @@ use crate::api::v1::admins::group_deliverable_components::read::__path_get_group
 use crate::api::v1::admins::group_deliverable_components::read::__path_get_group_components_for_project_handler;
 use crate::api::v1::admins::group_deliverable_components::update::__path_update_group_component_handler;
 use crate::api::v1::admins::group_deliverable_selections::read::__path_get_group_deliverable_selections;
+use crate::api::v1::admins::group_deliverables::batch_delete::__path_batch_delete_group_deliverables_handler;
 use crate::api::v1::admins::group_deliverables::create::__path_create_group_deliverable_handler;
 use crate::api::v1::admins::group_deliverables::delete::__path_delete_group_deliverable_handler;
+use crate::api::v1::admins::group_deliverables::link_impact::__path_get_group_deliverable_link_impact_handler;
 use crate::api::v1::admins::group_deliverables::read::__path_get_all_group_deliverables_handler;
 use crate::api::v1::admins::group_deliverables::read::__path_get_components_for_group_deliverable_handler;
 use crate::api::v1::admins::group_deliverables::read::__path_get_group_deliverable_handler;
@@ -35,6 +53,7 @@ use crate::api::v1::admins::group_deliverables_and_components::delete::__path_de
 use crate::api::v1::admins::group_deliverables_and_components::read::__path_get_components_for_deliverable_handler as __path_get_group_components_for_group_deliverable_handler;
 use crate::api::v1::admins::group_deliverables_and_components::read::__path_get_deliverables_for_component_handler as __path_get_group_deliverables_for_group_component_handler;
 use crate::api::v1::admins::group_deliverables_and_components::update::__path_update_group_deliverable_component_handler;
+use crate::api::v1::admins::groups::batch_members::__path_add_members_batch;
 use crate::api::v1::admins::groups::complaints::__path_get_group_complaints;
 use crate::api::v1::admins::groups::details::__path_get_group_details;
 use crate::api::v1::admins::groups::members::{
@@ -42,6 +61,8 @@ use crate::api::v1::admins::groups::members::{
     __path_remove_member as __path_admin_remove_member, __path_transfer_leadership,
 };
 use crate::api::v1::admins::groups::read::__path_get_project_groups;
+use crate::api::v1::admins::groups::split::__path_split_group;
+use crate::api::v1::admins::maintenance::orphans::{__path_cleanup_orphans, __path_get_orphans};
 use crate::api::v1::admins::oral_exam::completions::{
     __path_bulk_set_group_completions, __path_set_student_completion,
 };
@@ -49,18 +70,32 @@ use crate::api::v1::admins::oral_exam::group_details::__path_get_oral_exam_group
 use crate::api::v1::admins::oral_exam::list_groups::__path_list_oral_exam_groups;
 use crate::api::v1::admins::oral_exam::notes::{__path_delete_note, __path_upsert_note};
 use crate::api::v1::admins::oral_exam::toggle::__path_toggle_oral_exam;
+use crate::api::v1::admins::projects::activity::__path_get_project_activity_handler;
 use crate::api::v1::admins::projects::coordinators::{
     __path_assign_coordinator, __path_list_coordinators, __path_remove_coordinator,
 };
 use crate::api::v1::admins::projects::create::__path_create_project_handler;
 use crate::api::v1::admins::projects::delete::__path_delete_project_handler;
+use crate::api::v1::admins::projects::deliverables_import::__path_import_deliverables_handler;
+use crate::api::v1::admins::projects::freeze::__path_freeze_project_handler;
+use crate::api::v1::admins::projects::import::__path_import_project_handler;
+use crate::api::v1::admins::projects::members::__path_list_project_members_handler;
+use crate::api::v1::admins::projects::nudge::__path_nudge_students_handler;
+use crate::api::v1::admins::projects::progress::__path_get_project_progress_handler;
+use crate::api::v1::admins::projects::publish::__path_publish_project_handler;
 use crate::api::v1::admins::projects::read::__path_get_all_projects_handler;
 use crate::api::v1::admins::projects::read::__path_get_one_project_handler;
+use crate::api::v1::admins::projects::revoke_sessions::__path_revoke_sessions_handler;
+use crate::api::v1::admins::projects::rotate_codes::__path_rotate_codes_handler;
+use crate::api::v1::admins::projects::selections_export::__path_export_selections_handler;
+use crate::api::v1::admins::projects::signup_domains::__path_set_signup_domains_handler;
+use crate::api::v1::admins::projects::ungrouped::__path_list_ungrouped_students_handler;
 use crate::api::v1::admins::projects::update::__path_update_project_handler;
 use crate::api::v1::admins::security_codes::create::__path_create_code_handler;
 use crate::api::v1::admins::security_codes::delete::__path_delete_code_handler;
 use crate::api::v1::admins::security_codes::read::__path_get_all_codes_handler;
 use crate::api::v1::admins::security_codes::update::__path_update_code_handler;
+use crate::api::v1::admins::student_deliverable_components::batch_delete::__path_batch_delete_student_components_handler;
 use crate::api::v1::admins::student_deliverable_components::create::__path_create_student_component_handler;
 use crate::api::v1::admins::student_deliverable_components::delete::__path_delete_student_component_handler;
 use crate::api::v1::admins::student_deliverable_components::read::__path_get_all_student_components_handler;
@@ -69,8 +104,10 @@ use crate::api::v1::admins::student_deliverable_components::read::__path_get_stu
 use crate::api::v1::admins::student_deliverable_components::read::__path_get_student_components_for_project_handler;
 use crate::api::v1::admins::student_deliverable_components::update::__path_update_student_component_handler;
 use crate::api::v1::admins::student_deliverable_selections::read::__path_get_student_deliverable_selections;
+use crate::api::v1::admins::student_deliverables::batch_delete::__path_batch_delete_student_deliverables_handler;
 use crate::api::v1::admins::student_deliverables::create::__path_create_student_deliverable_handler;
 use crate::api::v1::admins::student_deliverables::delete::__path_delete_student_deliverable_handler;
+use crate::api::v1::admins::student_deliverables::link_impact::__path_get_student_deliverable_link_impact_handler;
 use crate::api::v1::admins::student_deliverables::read::__path_get_all_student_deliverables_handler;
 use crate::api::v1::admins::student_deliverables::read::__path_get_components_for_student_deliverable_handler;
 use crate::api::v1::admins::student_deliverables::read::__path_get_student_deliverable_handler;
@@ -81,28 +118,46 @@ use crate::api::v1::admins::student_deliverables_and_components::delete::__path_
 use crate::api::v1::admins::student_deliverables_and_components::read::__path_get_components_for_deliverable_handler;
 use crate::api::v1::admins::student_deliverables_and_components::read::__path_get_deliverables_for_component_handler;
 use crate::api::v1::admins::student_deliverables_and_components::update::__path_update_student_deliverable_component_handler;
+use crate::api::v1::admins::students::confirm::__path_confirm_students_handler;
+use crate::api::v1::admins::transactions::list::__path_list_student_transactions_handler;
+use crate::api::v1::admins::transactions::reverse::__path_reverse_transaction_handler;
 use crate::api::v1::admins::uploads::download::__path_download_student_upload_handler;
 use crate::api::v1::admins::uploads::list::__path_list_project_uploads_handler;
+use crate::api::v1::admins::uploads::signed_url::__path_create_signed_url_handler;
+use crate::api::v1::admins::users::api_tokens::{
+    __path_create_api_token_handler, __path_get_api_tokens_handler, __path_revoke_api_token_handler,
+};
 use crate::api::v1::admins::users::create::__path_create_admin_handler;
 use crate::api::v1::admins::users::delete::__path_delete_admin_handler;
+use crate::api::v1::admins::users::impersonate::{
+    __path_end_impersonation_handler, __path_start_impersonation_handler,
+};
 use crate::api::v1::admins::users::me::__path_admins_me_handler;
+use crate::api::v1::admins::users::permissions::__path_admins_me_permissions_handler;
 use crate::api::v1::admins::users::read::__path_get_all_admins_handler;
 use crate::api::v1::admins::users::read::__path_get_one_admin_handler;
+use crate::api::v1::admins::users::reassign::__path_reassign_coordinations_handler;
+use crate::api::v1::admins::users::role::__path_change_admin_role_handler;
+use crate::api::v1::admins::users::roles::__path_get_admin_roles_handler;
 use crate::api::v1::admins::users::test_email::__path_test_email_handler;
 use crate::api::v1::admins::users::update::__path_update_admin_handler;
 use crate::api::v1::admins::users::update_me::__path_update_me_admin_handler;
 use crate::api::v1::public::fairs::leaderboard::__path_leaderboard_handler;
+use crate::api::v1::public::uploads::download::__path_signed_download_handler;
 use crate::api::v1::students::auth::{
     allowed_domains::__path_allowed_domains_handler, confirm::__path_confirm_student_handler,
     forgot_password::__path_forgot_password_handler as __path_students_forgot_password_handler,
-    login::__path_students_login_handler,
+    forgot_password::__path_resend_reset_email_handler, login::__path_students_login_handler,
     reset_password::__path_reset_password_handler as __path_students_reset_password_handler,
+    reset_password::__path_verify_reset_token_handler as __path_students_verify_reset_token_handler,
     signup::__path_student_signup_handler,
 };
 use crate::api::v1::students::complaints::list::__path_list_group_filed_complaints_handler;
+use crate::api::v1::students::complaints::list::__path_list_own_complaints_handler;
 use crate::api::v1::students::complaints::submit::__path_submit_complaint_handler;
 use crate::api::v1::students::fairs::list::__path_list_transactions_handler;
 use crate::api::v1::students::fairs::purchase::__path_purchase_handler;
+use crate::api::v1::students::fairs::read::__path_list_student_fairs_handler;
 use crate::api::v1::students::group_component_implementation_details::{
     create::__path_create_component_implementation_detail,
     delete::__path_delete_component_implementation_detail,
@@ -115,21 +170,28 @@ use crate::api::v1::students::group_deliverable_selections::{
 use crate::api::v1::students::groups::{
     check_name::__path_check_name, create::__path_create_group, delete::__path_delete_group,
     members::__path_add_member, members::__path_remove_member,
-    members_list::__path_list_group_members, read::__path_get_groups,
+    members_list::__path_list_group_members, read::__path_get_group, read::__path_get_groups,
+    selection_validation::__path_validate_group_selection,
 };
 use crate::api::v1::students::projects::read::__path_get_student_projects;
+use crate::api::v1::students::projects::requirements::__path_get_project_requirements;
 use crate::api::v1::students::security_codes::validate_code::__path_validate_code;
 use crate::api::v1::students::student_deliverable_selections::{
     create::__path_create_student_deliverable_selection,
     delete::__path_delete_student_deliverable_selection,
     read::__path_get_student_deliverable_selection,
     update::__path_update_student_deliverable_selection,
+    update_batch::__path_update_student_deliverable_selections_batch,
 };
+use crate::api::v1::students::uploads::download::__path_download_own_upload_handler;
+use crate::api::v1::students::uploads::list::__path_list_student_uploads_handler;
+use crate::api::v1::students::uploads::replace::__path_replace_upload_handler;
 use crate::api::v1::students::uploads::status::__path_get_upload_status_handler;
 use crate::api::v1::students::uploads::upload::__path_upload_project_zip_handler;
 use crate::api::v1::students::users::me::__path_students_me_handler;
 use crate::api::v1::students::users::update_me::__path_update_me_student_handler;
 use crate::api::version::__path_version_info;
+use crate::common::json_error::{JsonError, ValidationError};
 use crate::jwt::grants_extractor::{ADMIN_HEADER_NAME, STUDENT_HEADER_NAME};
 use utoipa::openapi::security::SecurityScheme;
 use utoipa::openapi::security::{ApiKey, ApiKeyValue};
@@ -142,21 +204,36 @@ use utoipa_swagger_ui::SwaggerUi;
     paths(
         health_check,
         liveness_check,
+        migrations_check,
+        status,
         version_info,
+        features,
         allowed_domains_handler,
         students_login_handler,
         confirm_student_handler,
         student_signup_handler,
         students_forgot_password_handler,
+        resend_reset_email_handler,
         students_reset_password_handler,
+        students_verify_reset_token_handler,
         students_me_handler,
         update_me_student_handler,
         admins_login_handler,
         forgot_password_handler,
         reset_password_handler,
+        verify_reset_token_handler,
         get_one_admin_handler,
+        get_admin_roles_handler,
+        change_admin_role_handler,
+        reassign_coordinations_handler,
+        create_api_token_handler,
+        get_api_tokens_handler,
+        revoke_api_token_handler,
+        start_impersonation_handler,
+        end_impersonation_handler,
         get_all_admins_handler,
         admins_me_handler,
+        admins_me_permissions_handler,
         update_me_admin_handler,
         create_admin_handler,
         update_admin_handler,
@@ -167,10 +244,23 @@ use utoipa_swagger_ui::SwaggerUi;
         update_blacklist_handler,
         delete_blacklist_handler,
         test_email_handler,
+        preview_email_handler,
+        seed_demo_data_handler,
         create_project_handler,
+        import_project_handler,
+        import_deliverables_handler,
         get_all_projects_handler,
         update_project_handler,
         get_one_project_handler,
+        get_project_progress_handler,
+        list_project_members_handler,
+        list_ungrouped_students_handler,
+        nudge_students_handler,
+        get_project_activity_handler,
+        export_selections_handler,
+        freeze_project_handler,
+        publish_project_handler,
+        set_signup_domains_handler,
         delete_project_handler,
         assign_coordinator,
         list_coordinators,
@@ -178,15 +268,27 @@ use utoipa_swagger_ui::SwaggerUi;
         get_project_groups,
         get_group_details,
         get_group_complaints,
+        get_complaints_feed,
+        update_complaint_status,
+        get_orphans,
+        cleanup_orphans,
+        export_complaints_handler,
+        list_student_transactions_handler,
+        reverse_transaction_handler,
         admin_remove_member,
         transfer_leadership,
         admin_add_member,
+        add_members_batch,
+        split_group,
         get_group_deliverable_selections,
         get_student_deliverable_selections,
         get_student_projects,
+        get_project_requirements,
         create_code_handler,
         get_all_codes_handler,
         update_code_handler,
+        rotate_codes_handler,
+        revoke_sessions_handler,
         delete_code_handler,
         create_group_component_handler,
         get_all_group_components_handler,
@@ -195,13 +297,16 @@ use utoipa_swagger_ui::SwaggerUi;
         get_deliverables_for_group_component_handler,
         update_group_component_handler,
         delete_group_component_handler,
+        batch_delete_group_components_handler,
         create_group_deliverable_handler,
         get_all_group_deliverables_handler,
         get_group_deliverable_handler,
         get_group_deliverables_for_project_handler,
         get_components_for_group_deliverable_handler,
+        get_group_deliverable_link_impact_handler,
         update_group_deliverable_handler,
         delete_group_deliverable_handler,
+        batch_delete_group_deliverables_handler,
         create_group_deliverable_component_handler,
         get_group_components_for_group_deliverable_handler,
         get_group_deliverables_for_group_component_handler,
@@ -214,13 +319,17 @@ use utoipa_swagger_ui::SwaggerUi;
         get_deliverables_for_student_component_handler,
         update_student_component_handler,
         delete_student_component_handler,
+        batch_delete_student_components_handler,
         create_student_deliverable_handler,
         get_all_student_deliverables_handler,
         get_student_deliverable_handler,
         get_student_deliverables_for_project_handler,
         get_components_for_student_deliverable_handler,
+        get_student_deliverable_link_impact_handler,
         update_student_deliverable_handler,
         delete_student_deliverable_handler,
+        batch_delete_student_deliverables_handler,
+        confirm_students_handler,
         create_student_deliverable_component_handler,
         get_components_for_deliverable_handler,
         get_deliverables_for_component_handler,
@@ -228,12 +337,14 @@ use utoipa_swagger_ui::SwaggerUi;
         delete_student_deliverable_component_handler,
         create_group,
         get_groups,
+        get_group,
         delete_group,
         validate_code,
         check_name,
         add_member,
         remove_member,
         list_group_members,
+        validate_group_selection,
         create_group_deliverable_selection,
         get_group_deliverable_selection,
         create_component_implementation_detail,
@@ -243,23 +354,35 @@ use utoipa_swagger_ui::SwaggerUi;
         create_student_deliverable_selection,
         get_student_deliverable_selection,
         update_student_deliverable_selection,
+        update_student_deliverable_selections_batch,
         delete_student_deliverable_selection,
         create_fair_handler,
         get_fair_handler,
         get_fair_by_project_handler,
         update_fair_handler,
+        delete_fair_handler,
         enable_fair_handler,
         disable_fair_handler,
         fair_report_handler,
+        record_attendance_handler,
+        list_attendance_handler,
+        assign_slot_handler,
         purchase_handler,
         list_transactions_handler,
+        list_student_fairs_handler,
         submit_complaint_handler,
         list_group_filed_complaints_handler,
+        list_own_complaints_handler,
         upload_project_zip_handler,
         get_upload_status_handler,
+        list_student_uploads_handler,
+        replace_upload_handler,
+        download_own_upload_handler,
         list_project_uploads_handler,
         download_student_upload_handler,
         leaderboard_handler,
+        create_signed_url_handler,
+        signed_download_handler,
         toggle_oral_exam,
         list_oral_exam_groups,
         get_oral_exam_group_details,
@@ -292,8 +415,11 @@ use utoipa_swagger_ui::SwaggerUi;
         (name = "Complaints management", description = "Student endpoints for complaints about purchased deliverables"),
         (name = "Student Uploads", description = "Student upload and professor download endpoints for project ZIP submissions"),
         (name = "Fairs leaderboard", description = "Public endpoint for the fair sales leaderboard"),
+        (name = "Admin complaints", description = "Cross-project complaints feed for triage and their resolution"),
         (name = "Admin Oral Exam", description = "Professor endpoints for oral exam mode: group listing, details, notes, and completion tracking"),
+        (name = "Maintenance", description = "Root endpoints finding and repairing inconsistent data"),
     ),
+    components(schemas(JsonError, ValidationError)),
     modifiers(&SecurityAddon),
     info(
         title = "Advanced Programming Application Backend API",
@@ -320,7 +446,10 @@ impl Modify for SecurityAddon {
         let components: &mut Components =
             openapi.components.get_or_insert_with(Components::default);
 
-        let admin = ApiKeyValue::with_description(ADMIN_HEADER_NAME, "Admin token authentication");
+        let admin = ApiKeyValue::with_description(
+            ADMIN_HEADER_NAME,
+            "Admin token authentication, either a login token or an `apt_` API token",
+        );
         components.security_schemes.insert(
             "AdminAuth".to_string(),
             SecurityScheme::ApiKey(ApiKey::Header(admin)),
@@ -333,3 +462,45 @@ impl Modify for SecurityAddon {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_error_schema_is_referenced_by_signup() {
+        let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+
+        let schema = &doc["components"]["schemas"]["ValidationError"];
+        assert_eq!(
+            schema["properties"]["fields"]["additionalProperties"]["type"],
+            "array"
+        );
+
+        let bad_request = &doc["paths"]["/v1/students/auth/signup"]["post"]["responses"]["400"];
+        assert_eq!(
+            bad_request["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/ValidationError"
+        );
+    }
+
+    #[test]
+    fn test_batch_endpoints_share_the_batch_result_schema() {
+        let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+
+        let multi_status =
+            &doc["paths"]["/v1/admins/groups/{group_id}/members/batch"]["post"]["responses"]["207"];
+        let reference = multi_status["content"]["application/json"]["schema"]["$ref"]
+            .as_str()
+            .unwrap();
+        assert_eq!(reference, "#/components/schemas/BatchResult_AddedMember");
+
+        for name in ["BatchResult_AddedMember", "BatchResult_UpdatedSelection"] {
+            let schema = &doc["components"]["schemas"][name];
+            assert_eq!(
+                schema["properties"]["failed"]["items"]["$ref"],
+                "#/components/schemas/BatchFailure"
+            );
+        }
+    }
+}
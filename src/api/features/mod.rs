@@ -0,0 +1,133 @@
+use crate::app_data::AppData;
+use crate::config::Config;
+use actix_web::http::header::{CacheControl, CacheDirective};
+use actix_web::web::Data;
+use actix_web::{HttpResponse, Result};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// How long clients may reuse the features, they only change with a restart
+const FEATURES_MAX_AGE_SECS: u32 = 300;
+
+/// Features and limits the frontend adapts to, never anything secret
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct FeaturesResponse {
+    /// Whether students can create their own account
+    #[schema(example = true)]
+    self_signup_enabled: bool,
+    /// Whether new student accounts must confirm their email before logging in
+    #[schema(example = true)]
+    email_confirmation_required: bool,
+    /// Email domains accepted at signup, projects may admit others through security codes
+    #[schema(example = json!(["unitn.it", "studenti.unitn.it"]))]
+    allowed_signup_domains: Vec<String>,
+    /// Largest file a student can upload
+    #[schema(example = 10485760)]
+    max_upload_size_bytes: u64,
+    /// Page size of list endpoints when none is asked for
+    #[schema(example = 20)]
+    default_per_page: u32,
+    /// Largest page size of list endpoints
+    #[schema(example = 100)]
+    max_per_page: u32,
+    /// How long a group name checked for availability stays reserved
+    #[schema(example = 120)]
+    group_name_reservation_seconds: u64,
+}
+
+impl From<&Config> for FeaturesResponse {
+    fn from(config: &Config) -> Self {
+        Self {
+            self_signup_enabled: config.self_signup_enabled(),
+            email_confirmation_required: !config.skip_email_confirmation(),
+            allowed_signup_domains: config.allowed_signup_domains().clone(),
+            max_upload_size_bytes: config.max_upload_size_bytes(),
+            default_per_page: config.default_per_page(),
+            max_per_page: config.max_per_page(),
+            group_name_reservation_seconds: config.group_name_reservation_seconds(),
+        }
+    }
+}
+
+/// Features endpoint
+///
+/// Describes which config driven features are enabled and the limits that apply, so the
+/// frontend does not have to hardcode them. The maximum group size is set per project and
+/// comes with the project.
+#[utoipa::path(
+    get,
+    path = "/features",
+    tag = "Version",
+    responses(
+        (status = 200, description = "Enabled features and limits", body = FeaturesResponse,
+            headers(("Cache-Control" = String, description = "Clients may cache the features for the given max-age")))
+    ),
+    summary = "Get the enabled features",
+    description = "Returns the runtime feature flags and limits of the deployment. This endpoint does not require authentication."
+)]
+pub async fn features(data: Data<AppData>) -> Result<HttpResponse> {
+    Ok(HttpResponse::Ok()
+        .insert_header(CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(FEATURES_MAX_AGE_SECS),
+        ]))
+        .json(FeaturesResponse::from(&data.config)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{
+        create_test_app_data, TEST_EMAIL_TOKEN_SECRET, TEST_JWT_SECRET, TEST_PASSWORD,
+    };
+    use actix_web::test::{call_service, init_service, read_body, TestRequest};
+    use actix_web::{web, App};
+
+    #[actix_web::test]
+    async fn test_features_expose_flags_and_no_secrets() {
+        let data = create_test_app_data().await;
+        let config = data.config.clone();
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(data))
+                .route("/features", web::get().to(features)),
+        )
+        .await;
+
+        let res = call_service(&app, TestRequest::get().uri("/features").to_request()).await;
+        assert!(res.status().is_success());
+        let body = String::from_utf8(read_body(res).await.to_vec()).unwrap();
+        let features: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+        assert_eq!(
+            features["self_signup_enabled"],
+            config.self_signup_enabled()
+        );
+        assert_eq!(
+            features["email_confirmation_required"],
+            !config.skip_email_confirmation()
+        );
+        assert_eq!(
+            features["max_upload_size_bytes"],
+            config.max_upload_size_bytes()
+        );
+        assert_eq!(
+            features["allowed_signup_domains"],
+            serde_json::json!(config.allowed_signup_domains())
+        );
+        assert_eq!(features["max_per_page"], config.max_per_page());
+
+        for secret in [
+            String::from_utf8_lossy(TEST_JWT_SECRET).to_string(),
+            TEST_EMAIL_TOKEN_SECRET.to_string(),
+            TEST_PASSWORD.to_string(),
+            "testpassword".to_string(),
+            "postgres://".to_string(),
+        ] {
+            assert!(!body.contains(&secret), "features leak `{}`", secret);
+        }
+        for field in ["secret", "password", "db_url", "smtp"] {
+            assert!(!body.contains(field), "features expose `{}`", field);
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use crate::app_data::AppData;
+use crate::preflight::MIGRATOR;
+use actix_web::http::StatusCode;
+use actix_web::web::Data;
+use actix_web::{HttpResponse, Result};
+use serde::Serialize;
+use sqlx::migrate::Migrator;
+use sqlx::Row;
+use utoipa::ToSchema;
+
+#[derive(Debug, PartialEq, Eq, Serialize, ToSchema)]
+pub(crate) struct DriftedMigration {
+    #[schema(example = 20260614090000_i64)]
+    pub version: i64,
+    #[schema(example = "add student upload version")]
+    pub description: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct MigrationsResponse {
+    /// `in_sync`, `drift`, or `unknown` when the applied migrations cannot be read
+    pub status: String,
+    /// Migrations embedded in this build but not applied to the database
+    pub pending: Vec<DriftedMigration>,
+    /// Migrations applied to the database but unknown to this build
+    pub extra: Vec<DriftedMigration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Compares the migrations embedded in `migrator` with the successfully `applied` ones
+pub(crate) fn migration_drift(
+    migrator: &Migrator, applied: &[(i64, String)],
+) -> MigrationsResponse {
+    let embedded: Vec<_> = migrator
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+        .collect();
+
+    let pending: Vec<DriftedMigration> = embedded
+        .iter()
+        .filter(|m| !applied.iter().any(|(version, _)| *version == m.version))
+        .map(|m| DriftedMigration {
+            version: m.version,
+            description: m.description.to_string(),
+        })
+        .collect();
+    let extra: Vec<DriftedMigration> = applied
+        .iter()
+        .filter(|(version, _)| !embedded.iter().any(|m| m.version == *version))
+        .map(|(version, description)| DriftedMigration {
+            version: *version,
+            description: description.clone(),
+        })
+        .collect();
+
+    MigrationsResponse {
+        status: if pending.is_empty() && extra.is_empty() {
+            "in_sync"
+        } else {
+            "drift"
+        }
+        .to_string(),
+        pending,
+        extra,
+        error: None,
+    }
+}
+
+/// Migrations successfully applied to the database, none when the table is missing
+async fn applied_migrations(data: &AppData) -> Result<Vec<(i64, String)>, sqlx::Error> {
+    let pool = data.db.as_sqlx_pool();
+    let exists: bool = sqlx::query("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+        .fetch_one(pool)
+        .await?
+        .get(0);
+    if !exists {
+        return Ok(Vec::new());
+    }
+
+    let rows = sqlx::query(
+        "SELECT version, description FROM _sqlx_migrations WHERE success ORDER BY version",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .iter()
+        .map(|row| (row.get("version"), row.get("description")))
+        .collect())
+}
+
+/// Migration drift check
+///
+/// Compares the migrations embedded in this build with the ones applied to the database.
+/// Pending migrations mean the build expects a schema the database does not have yet,
+/// extra ones that the database was migrated by a newer build; both leave the behavior
+/// undefined, so either answers 503.
+#[utoipa::path(
+    get,
+    path = "/health/migrations",
+    tag = "Health",
+    responses(
+        (status = 200, description = "The database schema matches this build", body = MigrationsResponse),
+        (status = 503, description = "Migration drift, or the applied migrations cannot be read", body = MigrationsResponse)
+    ),
+    summary = "Get the migration drift between this build and the database",
+    description = "Lists the migrations pending for this build and the applied ones it does not know"
+)]
+pub async fn migrations_check(data: Data<AppData>) -> Result<HttpResponse> {
+    let response = match applied_migrations(&data).await {
+        Ok(applied) => migration_drift(&MIGRATOR, &applied),
+        Err(e) => MigrationsResponse {
+            status: "unknown".to_string(),
+            pending: Vec::new(),
+            extra: Vec::new(),
+            error: Some(e.to_string()),
+        },
+    };
+
+    let status_code = if response.status == "in_sync" {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    Ok(HttpResponse::build(status_code).json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Successfully applied migrations of a database migrated up to this build
+    fn fully_migrated() -> Vec<(i64, String)> {
+        MIGRATOR
+            .iter()
+            .filter(|m| !m.migration_type.is_down_migration())
+            .map(|m| (m.version, m.description.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_database_missing_the_latest_migration_drifts() {
+        let mut applied = fully_migrated();
+        applied.sort();
+        let (latest, description) = applied.pop().unwrap();
+
+        let drift = migration_drift(&MIGRATOR, &applied);
+
+        assert_eq!(drift.status, "drift");
+        assert_eq!(
+            drift.pending,
+            vec![DriftedMigration {
+                version: latest,
+                description
+            }]
+        );
+        assert!(drift.extra.is_empty());
+    }
+
+    #[test]
+    fn test_migrations_unknown_to_the_build_drift() {
+        let mut applied = fully_migrated();
+        applied.push((99990101000000, "from a newer build".to_string()));
+
+        let drift = migration_drift(&MIGRATOR, &applied);
+
+        assert_eq!(drift.status, "drift");
+        assert!(drift.pending.is_empty());
+        assert_eq!(drift.extra[0].version, 99990101000000);
+    }
+
+    #[test]
+    fn test_fully_migrated_database_is_in_sync() {
+        let drift = migration_drift(&MIGRATOR, &fully_migrated());
+
+        assert_eq!(drift.status, "in_sync");
+        assert!(drift.pending.is_empty() && drift.extra.is_empty());
+        // A database never migrated has everything pending
+        assert_eq!(
+            migration_drift(&MIGRATOR, &[]).pending.len(),
+            fully_migrated().len()
+        );
+    }
+}
@@ -1,10 +1,14 @@
+use crate::app_data::cache::TtlCache;
 use crate::app_data::AppData;
 use actix_web::web::Data;
 use actix_web::{HttpResponse, Result};
 use serde::Serialize;
+use std::future::Future;
 use std::time::{SystemTime, UNIX_EPOCH};
 use utoipa::ToSchema;
 
+pub(crate) mod migrations;
+
 #[derive(Serialize, ToSchema)]
 struct HealthResponse {
     status: String,
@@ -14,13 +18,19 @@ struct HealthResponse {
     database: DatabaseStatus,
 }
 
-#[derive(Serialize, ToSchema)]
-struct DatabaseStatus {
+#[derive(Clone, Serialize, ToSchema)]
+pub(crate) struct DatabaseStatus {
     status: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     error: Option<String>,
 }
 
+impl DatabaseStatus {
+    pub(crate) fn is_healthy(&self) -> bool {
+        self.status == "healthy"
+    }
+}
+
 /// Health check endpoint for monitoring
 ///
 /// This endpoint provides:
@@ -29,6 +39,9 @@ struct DatabaseStatus {
 /// - Application version
 /// - Uptime in seconds
 /// - Database connectivity status
+///
+/// The database check result is reused for `health_cache_ms`, so frequent probes
+/// do not each hit the database.
 #[utoipa::path(
     get,
     path = "/health",
@@ -48,7 +61,7 @@ pub async fn health_check(data: Data<AppData>) -> Result<HttpResponse> {
         .as_secs();
 
     // Check database connectivity
-    let database_status = check_database_health(&data).await;
+    let database_status = database_health(&data).await;
 
     // Calculate uptime (simplified - in a real app you'd track start time)
     let uptime_seconds = timestamp; // This is a simplified uptime calculation
@@ -74,6 +87,28 @@ pub async fn health_check(data: Data<AppData>) -> Result<HttpResponse> {
     Ok(HttpResponse::build(status_code).json(health_response))
 }
 
+/// Database status shared by the health and status endpoints, cached for `health_cache_ms`
+pub(crate) async fn database_health(data: &AppData) -> DatabaseStatus {
+    cached_database_health(&data.health_cache, || check_database_health(data)).await
+}
+
+/// Returns the cached database status, running `probe` only when it is missing or expired
+async fn cached_database_health<F, Fut>(
+    cache: &TtlCache<DatabaseStatus>, probe: F,
+) -> DatabaseStatus
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = DatabaseStatus>,
+{
+    if let Some(status) = cache.get() {
+        return status;
+    }
+
+    let status = probe().await;
+    cache.set(status.clone());
+    status
+}
+
 /// Check database health by attempting a simple query
 async fn check_database_health(app_data: &AppData) -> DatabaseStatus {
     match sqlx::query("SELECT 1")
@@ -114,3 +149,47 @@ pub async fn liveness_check() -> Result<HttpResponse> {
             .as_secs()
     })))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    async fn probe_counting(
+        cache: &TtlCache<DatabaseStatus>, calls: &AtomicUsize, status: &str,
+    ) -> DatabaseStatus {
+        cached_database_health(cache, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            DatabaseStatus {
+                status: status.to_string(),
+                error: None,
+            }
+        })
+        .await
+    }
+
+    #[actix_web::test]
+    async fn test_rapid_health_calls_probe_database_once() {
+        let cache = TtlCache::new(Duration::from_secs(2));
+        let calls = AtomicUsize::new(0);
+
+        probe_counting(&cache, &calls, "healthy").await;
+        let second = probe_counting(&cache, &calls, "unhealthy").await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(second.status, "healthy");
+    }
+
+    #[actix_web::test]
+    async fn test_expired_result_reflects_new_status() {
+        let cache = TtlCache::new(Duration::ZERO);
+        let calls = AtomicUsize::new(0);
+
+        probe_counting(&cache, &calls, "healthy").await;
+        let second = probe_counting(&cache, &calls, "unhealthy").await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(second.status, "unhealthy");
+    }
+}
@@ -1,18 +1,38 @@
+use crate::api::features::features;
+use crate::api::health::migrations::migrations_check;
 use crate::api::health::{health_check, liveness_check};
+use crate::api::status::status;
 use crate::api::v1::v1_scope;
 use crate::api::version::version_info;
-use actix_web::web;
+use crate::common::json_error::{JsonError, ToJsonError};
+use crate::common::params::{path_config, query_config};
+use crate::database::availability::DbAvailabilityGuard;
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpResponse};
 use doc::open_api;
 
 pub(super) mod doc;
+pub(super) mod features;
 pub(super) mod health;
+pub(super) mod status;
 pub(super) mod v1;
 pub(super) mod version;
 
 pub(super) fn configure_endpoints(conf: &mut web::ServiceConfig) {
-    conf.service(v1_scope())
+    conf.app_data(path_config())
+        .app_data(query_config())
+        .service(v1_scope().wrap(DbAvailabilityGuard))
         .service(open_api())
         .route("/health", web::get().to(health_check))
         .route("/health/live", web::get().to(liveness_check))
-        .route("/version", web::get().to(version_info));
+        .route("/health/migrations", web::get().to(migrations_check))
+        .route("/status", web::get().to(status))
+        .route("/features", web::get().to(features))
+        .route("/version", web::get().to(version_info))
+        .default_service(web::to(not_found));
+}
+
+/// Answer of every route that does not exist
+pub(super) async fn not_found() -> Result<HttpResponse, JsonError> {
+    Err("Resource not found".to_json_error(StatusCode::NOT_FOUND))
 }
@@ -0,0 +1,171 @@
+use crate::api::health::{database_health, DatabaseStatus};
+use crate::api::version::{BUILD_TIME, GIT_COMMIT, GIT_TAG};
+use crate::app_data::project_cache::CacheStats;
+use crate::app_data::AppData;
+use crate::preflight::MIGRATOR;
+use actix_web::http::StatusCode;
+use actix_web::web::Data;
+use actix_web::{HttpResponse, Result};
+use log::warn;
+use serde::Serialize;
+use sqlx::migrate::Migrator;
+use sqlx::Row;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+struct MigrationInfo {
+    #[schema(example = 20260607090100_i64)]
+    version: i64,
+    #[schema(example = "add transaction reversals")]
+    name: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+struct SchemaStatus {
+    /// Latest migration applied to the database, absent when it cannot be read
+    latest_migration: Option<MigrationInfo>,
+    /// Latest migration embedded in this build
+    expected_migration: Option<MigrationInfo>,
+    up_to_date: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+struct DependenciesStatus {
+    database: DatabaseStatus,
+}
+
+/// Hits and misses of the in-memory caches since startup
+#[derive(Serialize, ToSchema)]
+struct CachesStatus {
+    deliverable_trees: CacheStats,
+}
+
+#[derive(Serialize, ToSchema)]
+struct StatusResponse {
+    status: String,
+    version: String,
+    git_tag: String,
+    git_commit: String,
+    build_time: String,
+    schema: SchemaStatus,
+    dependencies: DependenciesStatus,
+    caches: CachesStatus,
+}
+
+/// Compares the latest applied migration with the latest one this build knows about
+fn schema_status(migrator: &Migrator, latest_applied: Option<(i64, String)>) -> SchemaStatus {
+    let expected_migration = migrator
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+        .max_by_key(|m| m.version)
+        .map(|m| MigrationInfo {
+            version: m.version,
+            name: m.description.to_string(),
+        });
+    let latest_migration = latest_applied.map(|(version, name)| MigrationInfo { version, name });
+
+    SchemaStatus {
+        up_to_date: latest_migration.as_ref().map(|m| m.version)
+            == expected_migration.as_ref().map(|m| m.version),
+        latest_migration,
+        expected_migration,
+    }
+}
+
+/// Latest successfully applied migration
+async fn latest_applied_migration(data: &AppData) -> Option<(i64, String)> {
+    let row = sqlx::query(
+        "SELECT version, description FROM _sqlx_migrations WHERE success ORDER BY version DESC LIMIT 1",
+    )
+    .fetch_optional(data.db.as_sqlx_pool())
+    .await
+    .map_err(|e| warn!("unable to read the applied migrations: {}", e))
+    .ok()??;
+
+    Some((row.get("version"), row.get("description")))
+}
+
+/// Status endpoint for the status page
+///
+/// Aggregates in a single payload:
+/// - Application version and build information
+/// - Latest applied and expected database migration
+/// - Health of the dependencies
+/// - Hit and miss counts of the caches
+///
+/// Orchestrators should keep probing `/health`, which is cheaper.
+#[utoipa::path(
+    get,
+    path = "/status",
+    tag = "Health",
+    responses(
+        (status = 200, description = "Every subsystem is healthy", body = StatusResponse),
+        (status = 503, description = "A dependency is unhealthy", body = StatusResponse)
+    ),
+    summary = "Get the status of every subsystem",
+    description = "Version, database schema version and dependency health and cache counters in one call"
+)]
+pub async fn status(data: Data<AppData>) -> Result<HttpResponse> {
+    let database = database_health(&data).await;
+    let schema = schema_status(&MIGRATOR, latest_applied_migration(&data).await);
+
+    let healthy = database.is_healthy();
+    let response = StatusResponse {
+        status: if healthy { "healthy" } else { "unhealthy" }.to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        git_tag: GIT_TAG.to_string(),
+        git_commit: GIT_COMMIT.to_string(),
+        build_time: BUILD_TIME.to_string(),
+        schema,
+        dependencies: DependenciesStatus { database },
+        caches: CachesStatus {
+            deliverable_trees: data.deliverable_trees.stats(),
+        },
+    };
+
+    let status_code = if healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    Ok(HttpResponse::build(status_code).json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_reports_the_latest_migration_once_applied() {
+        let latest = MIGRATOR
+            .iter()
+            .filter(|m| !m.migration_type.is_down_migration())
+            .max_by_key(|m| m.version)
+            .unwrap();
+
+        let schema = schema_status(
+            &MIGRATOR,
+            Some((latest.version, latest.description.to_string())),
+        );
+
+        assert!(schema.up_to_date);
+        let payload = serde_json::to_value(&schema).unwrap();
+        assert_eq!(
+            payload["latest_migration"]["name"],
+            serde_json::json!(latest.description)
+        );
+        assert_eq!(
+            payload["latest_migration"]["version"],
+            serde_json::json!(latest.version)
+        );
+    }
+
+    #[test]
+    fn test_pending_migrations_are_not_up_to_date() {
+        let schema = schema_status(&MIGRATOR, Some((1, "initial".to_string())));
+
+        assert!(!schema.up_to_date);
+        assert!(schema.expected_migration.is_some());
+        assert!(!schema_status(&MIGRATOR, None).up_to_date);
+    }
+}
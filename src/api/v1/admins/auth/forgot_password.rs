@@ -1,10 +1,11 @@
 use crate::app_data::AppData;
+use crate::common::frontend_url::link_base_for;
 use crate::common::json_error::{error_with_log_id_and_payload, JsonError};
 use crate::database::repositories::admins_repository;
 use crate::mail::Mailer;
 use actix_web::http::StatusCode;
 use actix_web::web::{Data, Json};
-use actix_web::HttpResponse;
+use actix_web::{HttpRequest, HttpResponse};
 use confirm_email::generate_token;
 use log::error;
 use serde::{Deserialize, Serialize};
@@ -34,7 +35,7 @@ pub(crate) struct ForgotPasswordSchema {
     tag = "Admin authentication"
 )]
 pub(crate) async fn forgot_password_handler(
-    body: Json<ForgotPasswordSchema>, data: Data<AppData>,
+    req: HttpRequest, body: Json<ForgotPasswordSchema>, data: Data<AppData>,
 ) -> Result<HttpResponse, JsonError> {
     // Fetch the admin by email
     let admin_state = admins_repository::get_by_email(&data.db, &body.email)
@@ -72,7 +73,7 @@ pub(crate) async fn forgot_password_handler(
         // Create the reset URL with the token (frontend URL)
         let reset_url = format!(
             "{}/admin/password-reset?t={}",
-            data.config.frontend_base_url(),
+            link_base_for(&req, data.config.frontend_base_urls()).trim_end_matches('/'),
             token
         );
 
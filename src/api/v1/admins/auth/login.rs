@@ -1,7 +1,7 @@
 use crate::app_data::AppData;
 use crate::common::json_error::{error_with_log_id_and_payload, JsonError, ToJsonError};
 use crate::database::repositories::admins_repository;
-use crate::jwt::token::create_admin_token;
+use crate::jwt::token::{create_admin_token, TokenKeys};
 use actix_web::cookie::time::Duration;
 use actix_web::http::StatusCode;
 use actix_web::web::{Data, Json};
@@ -41,6 +41,7 @@ pub(crate) struct LoginAdminsResponse {
     responses(
         (status = 200, description = "Login successful", body = LoginAdminsResponse),
         (status = 401, description = "Wrong credentials", body = JsonError),
+        (status = 429, description = "Too many requests from this address (code RATE_LIMITED), see `Retry-After`", body = JsonError),
         (status = 500, description = "Internal server error", body = JsonError)
     ),
     tag = "Admin authentication"
@@ -79,8 +80,9 @@ pub(crate) async fn admins_login_handler(
     let token = create_admin_token(
         user.admin_id,
         user.admin_role_id,
-        data.config.jwt_secret().as_bytes(),
+        &TokenKeys::admin(&data.config),
         Duration::days(data.config.jwt_validity_days()).whole_seconds(),
+        data.clock.now(),
     )
     .map_err(|e| {
         error_with_log_id_and_payload(
@@ -1,6 +1,9 @@
 use crate::api::v1::admins::auth::forgot_password::forgot_password_handler;
 use crate::api::v1::admins::auth::login::admins_login_handler;
-use crate::api::v1::admins::auth::reset_password::reset_password_handler;
+use crate::api::v1::admins::auth::reset_password::{
+    reset_password_handler, verify_reset_token_handler,
+};
+use crate::database::unit_of_work::UnitOfWorkMiddleware;
 use actix_web::{web, Scope};
 
 pub(crate) mod forgot_password;
@@ -11,5 +14,38 @@ pub(super) fn auth_scope() -> Scope {
     web::scope("/auth")
         .route("/login", web::post().to(admins_login_handler))
         .route("/forgot-password", web::post().to(forgot_password_handler))
-        .route("/reset-password", web::post().to(reset_password_handler))
+        .service(
+            web::resource("/reset-password")
+                .wrap(UnitOfWorkMiddleware)
+                .route(web::post().to(reset_password_handler)),
+        )
+        .route(
+            "/reset-password/verify",
+            web::get().to(verify_reset_token_handler),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_app_data;
+    use actix_web::http::StatusCode;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::web::Data;
+    use actix_web::App;
+    use serde_json::json;
+
+    #[actix_web::test]
+    async fn test_reset_password_route_reaches_the_handler() {
+        let data = create_test_app_data().await;
+        let app = init_service(App::new().app_data(Data::new(data)).service(auth_scope())).await;
+
+        // A malformed token is rejected by the handler before any query
+        let req = TestRequest::post()
+            .uri("/auth/reset-password?t=not-a-token")
+            .set_json(json!({"new_password": "N3w-Password!"}))
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
 }
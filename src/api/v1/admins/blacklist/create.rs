@@ -33,6 +33,7 @@ pub(crate) struct BlacklistDto {
     pub first_name: String,
     pub last_name: String,
     #[schema(value_type = String, example = "2026-05-21T12:34:56Z")]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub banned_at: chrono::DateTime<Utc>,
 }
 
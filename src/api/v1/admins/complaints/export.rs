@@ -0,0 +1,231 @@
+use crate::api::v1::admins::complaints::list::{feed_filter, ComplaintsFeedQuery};
+use crate::app_data::AppData;
+use crate::common::csv::csv_field;
+use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::common::timestamps;
+use crate::database::repositories::complaints_repository::ComplaintsFilter;
+use crate::database::repositories::{
+    complaints_repository, groups_repository, students_repository,
+};
+use crate::jwt::get_user::LoggedUser;
+use crate::models::complaint::Complaint;
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::http::StatusCode;
+use actix_web::web::{Bytes, Data, Query};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use futures_util::{stream, StreamExt};
+use std::collections::{HashMap, HashSet};
+use welds::connections::postgres::PostgresClient;
+use welds::state::DbState;
+
+/// Complaints read from the database for each chunk of the export
+const EXPORT_BATCH: i64 = 500;
+
+const CSV_HEADER: &str = "complaint_id,project_id,from_group_id,to_group_id,transaction_id,\
+     filed_by_student_id,filed_by_email,status,created_at,resolution_notes\r\n";
+
+/// A complaint with the project of its groups and the email of the student who filed it
+struct ExportRow {
+    complaint: Complaint,
+    project_id: Option<i32>,
+    filer_email: Option<String>,
+}
+
+/// CSV line of a complaint, terminated by a line break
+fn csv_line(row: &ExportRow) -> String {
+    let c = &row.complaint;
+    let fields = [
+        c.complaint_id.to_string(),
+        row.project_id.map(|id| id.to_string()).unwrap_or_default(),
+        c.from_group_id.to_string(),
+        c.to_group_id.to_string(),
+        c.transaction_id.to_string(),
+        c.filed_by_student_id
+            .map(|id| id.to_string())
+            .unwrap_or_default(),
+        csv_field(row.filer_email.as_deref().unwrap_or("")),
+        csv_field(&c.status),
+        timestamps::format(&c.created_at),
+        csv_field(c.resolution_notes.as_deref().unwrap_or("")),
+    ];
+    format!("{}\r\n", fields.join(","))
+}
+
+/// CSV lines of one batch of complaints, with the number of complaints it holds
+async fn export_batch(
+    db: &PostgresClient, filter: &ComplaintsFilter, offset: i64,
+) -> welds::errors::Result<(String, i64)> {
+    let complaints: Vec<Complaint> =
+        complaints_repository::get_filtered(db, filter, false, EXPORT_BATCH, offset)
+            .await?
+            .into_iter()
+            .map(DbState::into_inner)
+            .collect();
+
+    let group_ids: Vec<i32> = complaints
+        .iter()
+        .map(|c| c.from_group_id)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let projects: HashMap<i32, i32> = groups_repository::get_by_ids(db, &group_ids)
+        .await?
+        .into_iter()
+        .map(DbState::into_inner)
+        .map(|g| (g.group_id, g.project_id))
+        .collect();
+
+    let student_ids: Vec<i32> = complaints
+        .iter()
+        .filter_map(|c| c.filed_by_student_id)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let emails: HashMap<i32, String> = students_repository::get_by_ids(db, &student_ids)
+        .await?
+        .into_iter()
+        .map(DbState::into_inner)
+        .map(|s| (s.student_id, s.email))
+        .collect();
+
+    let count = complaints.len() as i64;
+    let lines = complaints
+        .into_iter()
+        .map(|complaint| {
+            csv_line(&ExportRow {
+                project_id: projects.get(&complaint.from_group_id).copied(),
+                filer_email: complaint
+                    .filed_by_student_id
+                    .and_then(|id| emails.get(&id).cloned()),
+                complaint,
+            })
+        })
+        .collect();
+    Ok((lines, count))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/admins/complaints/export.csv",
+    params(ComplaintsFeedQuery),
+    responses(
+        (status = 200, description = "Complaints matching the filters as CSV, most recent first", content_type = "text/csv"),
+        (status = 400, description = "Invalid filters", body = JsonError),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Admin complaints",
+)]
+/// Export the complaints as CSV for reporting
+///
+/// Takes the same filters as the complaints feed. The file is streamed as an attachment,
+/// reading the complaints from the database in batches, one line per complaint.
+#[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
+pub(super) async fn export_complaints_handler(
+    req: HttpRequest, filters: Query<ComplaintsFeedQuery>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let filter = feed_filter(&filters, &admin)?;
+
+    let header = stream::once(async {
+        Ok::<_, actix_web::Error>(Bytes::from_static(CSV_HEADER.as_bytes()))
+    });
+    let rows = stream::try_unfold(Some(0), move |offset| {
+        let data = data.clone();
+        let filter = filter.clone();
+        async move {
+            let Some(offset) = offset else {
+                return Ok(None);
+            };
+            let (lines, count) = export_batch(&data.db, &filter, offset).await.map_err(|e| {
+                actix_web::Error::from(error_with_log_id(
+                    format!("unable to export complaints at offset {}: {}", offset, e),
+                    "Failed to export complaints",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                ))
+            })?;
+            let next = (count == EXPORT_BATCH).then_some(offset + EXPORT_BATCH);
+            Ok(Some((Bytes::from(lines), next)))
+        }
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/csv; charset=utf-8")
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename("complaints.csv".to_string())],
+        })
+        .streaming(header.chain(rows)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::complaint::STATUS_RESOLVED;
+    use chrono::TimeZone;
+    use chrono::Utc;
+
+    #[test]
+    fn test_csv_has_header_and_complaint_row() {
+        let row = ExportRow {
+            complaint: Complaint {
+                complaint_id: 12,
+                transaction_id: 40,
+                from_group_id: 3,
+                to_group_id: 4,
+                text: "Missing documentation".to_string(),
+                status: STATUS_RESOLVED.to_string(),
+                created_at: Utc.with_ymd_and_hms(2026, 6, 1, 10, 30, 0).unwrap(),
+                filed_by_student_id: Some(5),
+                resolution_notes: Some("Refunded, seller warned".to_string()),
+            },
+            project_id: Some(1),
+            filer_email: Some("mario.rossi@studenti.unitn.it".to_string()),
+        };
+
+        assert_eq!(
+            CSV_HEADER,
+            "complaint_id,project_id,from_group_id,to_group_id,transaction_id,\
+             filed_by_student_id,filed_by_email,status,created_at,resolution_notes\r\n"
+        );
+        assert_eq!(
+            csv_line(&row),
+            "12,1,3,4,40,5,mario.rossi@studenti.unitn.it,resolved,\
+             2026-06-01T10:30:00.000Z,\"Refunded, seller warned\"\r\n"
+        );
+    }
+
+    #[test]
+    fn test_unknown_filer_and_project_leave_empty_fields() {
+        let row = ExportRow {
+            complaint: Complaint {
+                complaint_id: 13,
+                transaction_id: 41,
+                from_group_id: 3,
+                to_group_id: 4,
+                text: "Late delivery".to_string(),
+                status: "open".to_string(),
+                created_at: Utc.with_ymd_and_hms(2026, 6, 2, 9, 0, 0).unwrap(),
+                filed_by_student_id: None,
+                resolution_notes: None,
+            },
+            project_id: None,
+            filer_email: None,
+        };
+
+        assert_eq!(
+            csv_line(&row),
+            "13,,3,4,41,,,open,2026-06-02T09:00:00.000Z,\r\n"
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use crate::app_data::AppData;
+use crate::common::fields::select_fields;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::common::list_params::{ListParams, ListQuery, ListSpec, SortDirection};
+use crate::common::pagination::{count_response, CountQuery};
+use crate::database::repositories::complaints_repository::ComplaintsFilter;
+use crate::database::repositories::{complaints_repository, groups_repository};
+use crate::jwt::get_user::LoggedUser;
+use crate::models::admin::Admin;
+use crate::models::admin_role::AvailableAdminRole;
+use crate::models::complaint::{STATUS_OPEN, STATUS_RESOLVED};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Query};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use utoipa::{IntoParams, ToSchema};
+use welds::state::DbState;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ComplaintStatus {
+    Open,
+    Resolved,
+}
+
+impl ComplaintStatus {
+    pub(super) fn as_str(self) -> &'static str {
+        match self {
+            ComplaintStatus::Open => STATUS_OPEN,
+            ComplaintStatus::Resolved => STATUS_RESOLVED,
+        }
+    }
+}
+
+/// Filters of the complaints feed
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub(crate) struct ComplaintsFeedQuery {
+    pub status: Option<ComplaintStatus>,
+    pub project_id: Option<i32>,
+    /// Group that filed the complaint
+    pub from_group_id: Option<i32>,
+    /// Only complaints filed at or after this time
+    #[param(value_type = Option<String>)]
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only complaints filed at or before this time
+    #[param(value_type = Option<String>)]
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ComplaintFeedItem {
+    pub complaint_id: i32,
+    pub transaction_id: i32,
+    /// Project of the groups involved, missing if the group was deleted meanwhile
+    pub project_id: Option<i32>,
+    pub from_group_id: i32,
+    pub to_group_id: i32,
+    pub text: String,
+    #[schema(example = "open")]
+    pub status: String,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// Sorting and fields of the complaints feed, filtered by [`ComplaintsFeedQuery`] instead
+#[derive(Debug)]
+pub(crate) struct ComplaintsList;
+
+impl ListSpec for ComplaintsList {
+    const SORTABLE: &'static [&'static str] = &["created_at"];
+    const FILTERABLE: &'static [&'static str] = &[];
+    const FIELDS: &'static [&'static str] = &[
+        "complaint_id",
+        "transaction_id",
+        "project_id",
+        "from_group_id",
+        "to_group_id",
+        "text",
+        "status",
+        "created_at",
+    ];
+}
+
+/// Whether the feed was asked for oldest complaints first, the default is most recent first
+fn oldest_first(params: &ListParams<ComplaintsList>) -> bool {
+    params
+        .sort
+        .first()
+        .is_some_and(|key| key.direction == SortDirection::Asc)
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ComplaintsFeedResponse {
+    /// Complaints with only the requested fields when `fields` is set
+    #[schema(value_type = Vec<ComplaintFeedItem>)]
+    pub complaints: Vec<serde_json::Value>,
+    /// Open complaints matching the other filters, regardless of `status`
+    pub unresolved: u64,
+}
+
+/// Builds the repository filter for `admin`, Coordinators only see their projects
+pub(super) fn feed_filter(
+    query: &ComplaintsFeedQuery, admin: &Admin,
+) -> Result<ComplaintsFilter, JsonError> {
+    if let (Some(after), Some(before)) = (query.created_after, query.created_before) {
+        if after > before {
+            return Err("created_after must not be after created_before"
+                .to_json_error(StatusCode::BAD_REQUEST));
+        }
+    }
+
+    let coordinator_id =
+        (admin.admin_role_id == AvailableAdminRole::Coordinator as i32).then_some(admin.admin_id);
+
+    Ok(ComplaintsFilter {
+        status: query.status.map(ComplaintStatus::as_str),
+        project_id: query.project_id,
+        from_group_id: query.from_group_id,
+        filed_by_student_id: None,
+        created_after: query.created_after,
+        created_before: query.created_before,
+        coordinator_id,
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/admins/complaints",
+    params(ComplaintsFeedQuery, ListQuery, CountQuery),
+    responses(
+        (status = 200, description = "Page of complaints, most recent first unless `sort=created_at:asc`. With `envelope=true` the body is `{ data, meta }` and the unresolved count is only in the header. With `count_only=true` the body is only `{ total }`", body = ComplaintsFeedResponse,
+            headers(
+                ("X-Total-Count" = u64, description = "Total number of complaints matching the filters"),
+                ("X-Page" = u32, description = "Returned page"),
+                ("X-Per-Page" = u32, description = "Page size"),
+                ("Link" = String, description = "RFC 5988 links to the first, prev, next and last pages"),
+                ("X-Unresolved-Count" = u64, description = "Open complaints matching the other filters"),
+            )
+        ),
+        (status = 400, description = "Invalid filters, pagination, sort or fields", body = JsonError),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Admin complaints",
+)]
+/// Complaints of every project, for triage
+///
+/// Filters can be combined and are applied together. Coordinators only see the complaints
+/// of the projects they are assigned to. With `count_only` the matching complaints are
+/// counted without being fetched.
+#[actix_web_grants::protect(any(
+    "ROLE_ADMIN_ROOT",
+    "ROLE_ADMIN_PROFESSOR",
+    "ROLE_ADMIN_COORDINATOR"
+))]
+pub(super) async fn get_complaints_feed(
+    req: HttpRequest, filters: Query<ComplaintsFeedQuery>, params: ListParams<ComplaintsList>,
+    count: Query<CountQuery>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let filter = feed_filter(&filters, &admin)?;
+    let pagination = &params.pagination;
+
+    let db_error = |what: &str, e: welds::WeldsError| {
+        error_with_log_id(
+            format!("unable to {} for admin {}: {}", what, admin.admin_id, e),
+            "Failed to retrieve complaints",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    };
+
+    let total = complaints_repository::count_filtered(&data.db, &filter)
+        .await
+        .map_err(|e| db_error("count complaints", e))?;
+    if count.is_set() {
+        return Ok(count_response(total));
+    }
+
+    let unresolved = complaints_repository::count_filtered(&data.db, &filter.unresolved())
+        .await
+        .map_err(|e| db_error("count unresolved complaints", e))?;
+
+    let complaints: Vec<_> = complaints_repository::get_filtered(
+        &data.db,
+        &filter,
+        oldest_first(&params),
+        pagination.limit(),
+        pagination.offset(),
+    )
+    .await
+    .map_err(|e| db_error("fetch complaints", e))?
+    .into_iter()
+    .map(DbState::into_inner)
+    .collect();
+
+    let group_ids: Vec<i32> = complaints
+        .iter()
+        .map(|c| c.from_group_id)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    let projects: HashMap<i32, i32> = groups_repository::get_by_ids(&data.db, &group_ids)
+        .await
+        .map_err(|e| db_error("fetch complaint groups", e))?
+        .into_iter()
+        .map(DbState::into_inner)
+        .map(|g| (g.group_id, g.project_id))
+        .collect();
+
+    let items: Vec<ComplaintFeedItem> = complaints
+        .into_iter()
+        .map(|c| ComplaintFeedItem {
+            complaint_id: c.complaint_id,
+            transaction_id: c.transaction_id,
+            project_id: projects.get(&c.from_group_id).copied(),
+            from_group_id: c.from_group_id,
+            to_group_id: c.to_group_id,
+            text: c.text,
+            status: c.status,
+            created_at: c.created_at,
+        })
+        .collect();
+
+    let items = select_fields(items, params.fields.as_ref());
+    let mut response =
+        pagination.respond(&req, items, total, |complaints| ComplaintsFeedResponse {
+            complaints,
+            unresolved,
+        });
+    response.headers_mut().insert(
+        HeaderName::from_static("x-unresolved-count"),
+        HeaderValue::from(unresolved),
+    );
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::FromRequest;
+
+    fn admin(role: AvailableAdminRole) -> Admin {
+        Admin {
+            admin_id: 7,
+            first_name: "Jane".to_string(),
+            last_name: "Doe".to_string(),
+            email: "jane.doe@admin.com".to_string(),
+            password_hash: String::new(),
+            admin_role_id: role as i32,
+        }
+    }
+
+    #[test]
+    fn test_status_filter_is_applied() {
+        let query = Query::<ComplaintsFeedQuery>::from_query("status=resolved&project_id=3")
+            .unwrap()
+            .into_inner();
+
+        let filter = feed_filter(&query, &admin(AvailableAdminRole::Root)).unwrap();
+
+        assert_eq!(filter.status, Some(STATUS_RESOLVED));
+        assert_eq!(filter.project_id, Some(3));
+        // The summary counts open complaints whatever status was asked for
+        assert_eq!(filter.unresolved().status, Some(STATUS_OPEN));
+        assert_eq!(filter.unresolved().project_id, Some(3));
+        assert!(Query::<ComplaintsFeedQuery>::from_query("status=lost").is_err());
+    }
+
+    #[test]
+    fn test_coordinators_are_scoped_to_their_projects() {
+        let query = ComplaintsFeedQuery::default();
+
+        let coordinator = feed_filter(&query, &admin(AvailableAdminRole::Coordinator)).unwrap();
+        let professor = feed_filter(&query, &admin(AvailableAdminRole::Professor)).unwrap();
+
+        assert_eq!(coordinator.coordinator_id, Some(7));
+        assert_eq!(coordinator.unresolved().coordinator_id, Some(7));
+        assert_eq!(professor.coordinator_id, None);
+    }
+
+    #[actix_web::test]
+    async fn test_feed_can_be_sorted_oldest_first() {
+        let params = |query: &str| {
+            let req = actix_web::test::TestRequest::get()
+                .uri(&format!("/v1/admins/complaints?{}", query))
+                .to_http_request();
+            ListParams::<ComplaintsList>::extract(&req)
+        };
+
+        assert!(!oldest_first(&params("").await.unwrap()));
+        assert!(!oldest_first(
+            &params("sort=created_at:desc").await.unwrap()
+        ));
+        assert!(oldest_first(&params("sort=created_at").await.unwrap()));
+        assert!(params("sort=text").await.is_err());
+        assert!(params("filter=status:open").await.is_err());
+    }
+
+    #[test]
+    fn test_inverted_date_range_is_rejected() {
+        let query = ComplaintsFeedQuery {
+            created_after: Some(Utc::now()),
+            created_before: Some(Utc::now() - chrono::Duration::days(1)),
+            ..Default::default()
+        };
+
+        assert!(feed_filter(&query, &admin(AvailableAdminRole::Root)).is_err());
+    }
+}
@@ -0,0 +1,15 @@
+use crate::api::v1::admins::complaints::export::export_complaints_handler;
+use crate::api::v1::admins::complaints::list::get_complaints_feed;
+use crate::api::v1::admins::complaints::update_status::update_complaint_status;
+use actix_web::{web, Scope};
+
+pub(crate) mod export;
+pub(crate) mod list;
+pub(crate) mod update_status;
+
+pub(super) fn complaints_scope() -> Scope {
+    web::scope("/complaints")
+        .route("", web::get().to(get_complaints_feed))
+        .route("/export.csv", web::get().to(export_complaints_handler))
+        .route("/{complaint_id}", web::patch().to(update_complaint_status))
+}
@@ -0,0 +1,165 @@
+use crate::api::v1::admins::complaints::list::ComplaintStatus;
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::repositories::{audit_log_repository, complaints_repository};
+use crate::jwt::get_user::LoggedUser;
+use crate::models::audit_log::{AuditLog, COMPLAINT_STATUS_CHANGED};
+use crate::models::complaint::Complaint;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use welds::state::DbState;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct UpdateComplaintStatusRequest {
+    pub status: ComplaintStatus,
+    /// Shown to the student, only accepted when resolving
+    #[schema(example = "Component refunded to the buying group")]
+    pub resolution_notes: Option<String>,
+}
+
+/// Moves a complaint to `status`
+///
+/// Resolving keeps the given notes, reopening clears them since they no longer apply.
+fn apply_status(
+    complaint: &mut Complaint, status: ComplaintStatus, resolution_notes: Option<String>,
+) -> Result<(), JsonError> {
+    let resolution_notes = resolution_notes
+        .map(|notes| notes.trim().to_string())
+        .filter(|notes| !notes.is_empty());
+    if status == ComplaintStatus::Open && resolution_notes.is_some() {
+        return Err(
+            "Resolution notes can only be given when resolving a complaint"
+                .to_json_error(StatusCode::BAD_REQUEST),
+        );
+    }
+
+    complaint.status = status.as_str().to_string();
+    complaint.resolution_notes = resolution_notes;
+    Ok(())
+}
+
+#[utoipa::path(
+    patch,
+    path = "/v1/admins/complaints/{complaint_id}",
+    params(("complaint_id" = i32, Path, description = "Complaint ID")),
+    request_body = UpdateComplaintStatusRequest,
+    responses(
+        (status = 200, description = "Complaint updated", body = Complaint),
+        (status = 400, description = "Invalid status or notes", body = JsonError),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 404, description = "Complaint not found", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Admin complaints",
+)]
+/// Resolve or reopen a complaint
+#[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
+pub(super) async fn update_complaint_status(
+    req: HttpRequest, path: Path<i32>, body: Json<UpdateComplaintStatusRequest>,
+    data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+    let complaint_id = path.into_inner();
+    let db_error = |e: welds::WeldsError| {
+        error_with_log_id(
+            format!("unable to update complaint {}: {}", complaint_id, e),
+            "Failed to update complaint",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    };
+
+    let mut state = complaints_repository::get_by_id(&data.db, complaint_id)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| "Complaint not found".to_json_error(StatusCode::NOT_FOUND))?;
+
+    let body = body.into_inner();
+    apply_status(state.as_mut(), body.status, body.resolution_notes)?;
+    let complaint = DbState::into_inner(
+        complaints_repository::update(&data.db, state)
+            .await
+            .map_err(db_error)?,
+    );
+
+    audit_log_repository::record_or_warn(
+        &data.db,
+        AuditLog::by_admin(admin.admin_id, COMPLAINT_STATUS_CHANGED)
+            .target("complaint", complaint_id)
+            .details(format!("status {}", complaint.status)),
+    )
+    .await;
+
+    Ok(HttpResponse::Ok().json(complaint))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::complaint::{STATUS_OPEN, STATUS_RESOLVED};
+    use actix_web::ResponseError;
+    use chrono::Utc;
+
+    fn open_complaint() -> Complaint {
+        Complaint {
+            complaint_id: 1,
+            transaction_id: 2,
+            from_group_id: 3,
+            to_group_id: 4,
+            text: "The parser does not build".to_string(),
+            status: STATUS_OPEN.to_string(),
+            created_at: Utc::now(),
+            filed_by_student_id: Some(5),
+            resolution_notes: None,
+        }
+    }
+
+    #[test]
+    fn test_open_complaint_can_be_resolved() {
+        let mut complaint = open_complaint();
+
+        apply_status(
+            &mut complaint,
+            ComplaintStatus::Resolved,
+            Some(" Refunded ".to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(complaint.status, STATUS_RESOLVED);
+        assert_eq!(complaint.resolution_notes.as_deref(), Some("Refunded"));
+    }
+
+    #[test]
+    fn test_reopening_clears_the_notes() {
+        let mut complaint = open_complaint();
+        apply_status(
+            &mut complaint,
+            ComplaintStatus::Resolved,
+            Some("Refunded".to_string()),
+        )
+        .unwrap();
+
+        apply_status(&mut complaint, ComplaintStatus::Open, None).unwrap();
+        assert_eq!(complaint.status, STATUS_OPEN);
+        assert_eq!(complaint.resolution_notes, None);
+
+        let err = apply_status(
+            &mut complaint,
+            ComplaintStatus::Open,
+            Some("Refunded".to_string()),
+        )
+        .unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+    }
+}
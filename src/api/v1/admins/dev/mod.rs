@@ -0,0 +1,47 @@
+use crate::api::v1::admins::dev::seed::seed_demo_data_handler;
+use crate::database::unit_of_work::UnitOfWorkMiddleware;
+use actix_web::{web, Scope};
+
+pub(crate) mod seed;
+
+pub(super) fn dev_scope() -> Scope {
+    web::scope("/dev").service(
+        web::resource("/seed")
+            .wrap(UnitOfWorkMiddleware)
+            .route(web::post().to(seed_demo_data_handler)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_app_data;
+    use actix_web::dev::ServiceRequest;
+    use actix_web::http::StatusCode;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::web::Data;
+    use actix_web::{App, Error};
+    use actix_web_grants::GrantsMiddleware;
+    use std::collections::HashSet;
+
+    async fn root_grants(_req: &ServiceRequest) -> Result<HashSet<String>, Error> {
+        Ok(HashSet::from(["ROLE_ADMIN_ROOT".to_string()]))
+    }
+
+    #[actix_web::test]
+    async fn test_seed_route_reaches_the_handler() {
+        let data = create_test_app_data().await;
+        assert!(!data.config.dev_seed_enabled());
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(data))
+                .wrap(GrantsMiddleware::with_extractor(root_grants))
+                .service(dev_scope()),
+        )
+        .await;
+
+        // Seeding is disabled, which only the handler itself can answer
+        let res = call_service(&app, TestRequest::post().uri("/dev/seed").to_request()).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::repositories::{projects_repository, students_repository};
+use crate::database::seed::{seed_demo_data, DemoData, SeededDemo, DEMO_STUDENT_PASSWORD};
+use crate::database::unit_of_work::UnitOfWork;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Query};
+use actix_web::HttpResponse;
+use chrono::Datelike;
+use log::info;
+use password_auth::generate_hash;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct SeedQuery {
+    /// Seed even if the database already holds projects or students
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct SeedResponse {
+    pub project_id: i32,
+    pub group_deliverable_ids: Vec<i32>,
+    pub group_deliverable_component_ids: Vec<i32>,
+    pub student_deliverable_ids: Vec<i32>,
+    pub student_deliverable_component_ids: Vec<i32>,
+    pub student_ids: Vec<i32>,
+    pub group_ids: Vec<i32>,
+    /// Password of every created student
+    #[schema(example = "demo-password")]
+    pub student_password: String,
+}
+
+impl From<SeededDemo> for SeedResponse {
+    fn from(seeded: SeededDemo) -> Self {
+        Self {
+            project_id: seeded.project_id,
+            group_deliverable_ids: seeded.group_deliverable_ids,
+            group_deliverable_component_ids: seeded.group_deliverable_component_ids,
+            student_deliverable_ids: seeded.student_deliverable_ids,
+            student_deliverable_component_ids: seeded.student_deliverable_component_ids,
+            student_ids: seeded.student_ids,
+            group_ids: seeded.group_ids,
+            student_password: DEMO_STUDENT_PASSWORD.to_string(),
+        }
+    }
+}
+
+/// The endpoint answers as if it did not exist unless `dev_seed_enabled` is set
+fn ensure_seed_enabled(enabled: bool) -> Result<(), JsonError> {
+    if enabled {
+        Ok(())
+    } else {
+        Err("Not found".to_json_error(StatusCode::NOT_FOUND))
+    }
+}
+
+/// Demo data only goes into an empty database unless forced
+fn can_seed(projects: u64, students: u64, force: bool) -> bool {
+    (projects == 0 && students == 0) || force
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/admins/dev/seed",
+    params(SeedQuery),
+    responses(
+        (status = 201, description = "Demo data created, with the ids of the new rows", body = SeedResponse),
+        (status = 403, description = "Insufficient permissions - root access required", body = JsonError),
+        (status = 404, description = "Seeding is disabled in this deployment", body = JsonError),
+        (status = 409, description = "The database is not empty; retry with force=true", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Admin users management",
+)]
+/// Fill the database with a demo project
+///
+/// Creates a sample project with group and student deliverables and their components,
+/// six confirmed students split into two groups, and their deliverable selections, all in
+/// one transaction. Only available when `dev_seed_enabled` is set in the configuration.
+///
+/// **Security**: Only users with ROLE_ADMIN_ROOT can access this endpoint.
+#[actix_web_grants::protect("ROLE_ADMIN_ROOT")]
+pub(super) async fn seed_demo_data_handler(
+    query: Query<SeedQuery>, data: Data<AppData>, unit_of_work: UnitOfWork,
+) -> Result<HttpResponse, JsonError> {
+    ensure_seed_enabled(data.config.dev_seed_enabled())?;
+
+    let count_error = |what: &str, e: welds::WeldsError| {
+        error_with_log_id(
+            format!("unable to count {} before seeding: {}", what, e),
+            "Database error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    };
+    let projects = projects_repository::count(&data.db)
+        .await
+        .map_err(|e| count_error("projects", e))?;
+    let students = students_repository::count(&data.db)
+        .await
+        .map_err(|e| count_error("students", e))?;
+
+    if !can_seed(projects, students, query.force) {
+        return Err(format!(
+            "The database already holds {} projects and {} students. Seed with force=true to add demo data anyway.",
+            projects, students
+        )
+        .to_json_error(StatusCode::CONFLICT));
+    }
+
+    let now = data.clock.now();
+    let plan = DemoData::sample(now.timestamp().rem_euclid(10_000_000) as u32, now.year());
+    let seeded = seed_demo_data(&unit_of_work, &plan, &generate_hash(DEMO_STUDENT_PASSWORD))
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to seed demo data: {}", e),
+                "Failed to seed demo data",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+    info!(
+        "demo data seeded into project {} ({} students)",
+        seeded.project_id,
+        seeded.student_ids.len()
+    );
+
+    Ok(HttpResponse::Created().json(SeedResponse::from(seeded)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::ResponseError;
+
+    #[test]
+    fn test_seed_is_hidden_unless_enabled() {
+        let err = ensure_seed_enabled(false).unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::NOT_FOUND);
+        assert!(ensure_seed_enabled(true).is_ok());
+    }
+
+    #[test]
+    fn test_non_empty_database_needs_force() {
+        assert!(can_seed(0, 0, false));
+        assert!(!can_seed(1, 0, false));
+        assert!(!can_seed(0, 12, false));
+        assert!(can_seed(3, 12, true));
+    }
+}
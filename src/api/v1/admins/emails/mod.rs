@@ -0,0 +1,8 @@
+use crate::api::v1::admins::emails::preview::preview_email_handler;
+use actix_web::{web, Scope};
+
+pub(crate) mod preview;
+
+pub(super) fn emails_scope() -> Scope {
+    web::scope("/emails").route("/preview", web::post().to(preview_email_handler))
+}
@@ -0,0 +1,71 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::mail::PreviewError;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Json};
+use actix_web::HttpResponse;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct PreviewEmailRequest {
+    /// Template name without extension: `confirm`, `reset`, `admin_welcome`, `existing_account`,
+    /// `university_id_taken`, `project_nudge` or `group_change`
+    #[schema(example = "confirm")]
+    pub template: String,
+    /// Sample values of the template variables, missing ones get a placeholder value
+    #[serde(default)]
+    #[schema(example = json!({"user_name": "Jane Doe", "url": "https://example.com/confirm?t=abc"}))]
+    pub context: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct PreviewEmailResponse {
+    pub html: String,
+    pub text: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/admins/emails/preview",
+    request_body = PreviewEmailRequest,
+    responses(
+        (status = 200, description = "Rendered email bodies, nothing is sent", body = PreviewEmailResponse),
+        (status = 400, description = "Sample context refused", body = JsonError),
+        (status = 403, description = "Insufficient permissions - root access required", body = JsonError),
+        (status = 404, description = "Unknown template", body = JsonError),
+        (status = 500, description = "Template failed to render", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Admin users management",
+)]
+/// Renders an email template with sample values, without sending it
+///
+/// Only the variables the template uses are accepted, as plain strings, and link
+/// variables must be http(s) URLs.
+///
+/// **Security**: Only users with ROLE_ADMIN_ROOT can access this endpoint.
+#[actix_web_grants::protect("ROLE_ADMIN_ROOT")]
+pub(super) async fn preview_email_handler(
+    body: Json<PreviewEmailRequest>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let preview = data
+        .mailer
+        .preview(&body.template, &body.context)
+        .map_err(|e| match e {
+            PreviewError::UnknownTemplate(_) => e.to_string().to_json_error(StatusCode::NOT_FOUND),
+            PreviewError::Render(_) => error_with_log_id(
+                format!("unable to preview template {}: {}", body.template, e),
+                "Failed to render template",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            ),
+            _ => e.to_string().to_json_error(StatusCode::BAD_REQUEST),
+        })?;
+
+    Ok(HttpResponse::Ok().json(PreviewEmailResponse {
+        html: preview.html,
+        text: preview.text,
+    }))
+}
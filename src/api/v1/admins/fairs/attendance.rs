@@ -0,0 +1,259 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{
+    error_with_log_id, error_with_log_id_and_payload, JsonError, ToJsonError,
+};
+use crate::database::repositories::{
+    fair_attendance_repository, fairs_repository, groups_repository,
+};
+use crate::jwt::get_user::LoggedUser;
+use crate::models::fair_attendance::FairAttendance;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use utoipa::ToSchema;
+use welds::state::DbState;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub(crate) struct RecordAttendanceRequest {
+    #[schema(example = 4)]
+    pub group_id: i32,
+    /// Members of the group who attended, when omitted the whole group is recorded
+    #[schema(example = json!([12, 15]))]
+    pub student_ids: Option<Vec<i32>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct AttendanceEntry {
+    pub group_id: i32,
+    pub student_id: i32,
+    pub recorded_by_admin_id: Option<i32>,
+    #[schema(value_type = String)]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl From<DbState<FairAttendance>> for AttendanceEntry {
+    fn from(state: DbState<FairAttendance>) -> Self {
+        let a = DbState::into_inner(state);
+        Self {
+            group_id: a.group_id,
+            student_id: a.student_id,
+            recorded_by_admin_id: a.recorded_by_admin_id,
+            recorded_at: a.recorded_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct AttendanceResponse {
+    pub fair_id: i32,
+    pub attendance: Vec<AttendanceEntry>,
+}
+
+/// Students to record for a group, every member when none are listed.
+/// Fails with the first listed student that is not a member of the group
+fn attendees(member_ids: &[i32], requested: Option<&[i32]>) -> Result<Vec<i32>, i32> {
+    let Some(requested) = requested else {
+        return Ok(member_ids.to_vec());
+    };
+
+    let members: HashSet<i32> = member_ids.iter().copied().collect();
+    let mut seen = HashSet::new();
+    let mut students = Vec::with_capacity(requested.len());
+    for &student_id in requested {
+        if !members.contains(&student_id) {
+            return Err(student_id);
+        }
+        if seen.insert(student_id) {
+            students.push(student_id);
+        }
+    }
+    Ok(students)
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/admins/fairs/{fair_id}/attendance",
+    params(("fair_id" = i32, Path, description = "Fair ID")),
+    request_body = RecordAttendanceRequest,
+    responses(
+        (status = 201, description = "Attendance recorded, returns the new entries", body = AttendanceResponse),
+        (status = 400, description = "Group not in the fair's project or student not in the group", body = JsonError),
+        (status = 404, description = "Fair or group not found", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError),
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Fairs management",
+)]
+/// Record which students of a group attended a fair
+///
+/// Students already recorded for the fair are skipped, so the call can be repeated safely.
+#[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
+pub(in crate::api::v1) async fn record_attendance_handler(
+    req: HttpRequest, path: Path<i32>, body: Json<RecordAttendanceRequest>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+    let fair_id = path.into_inner();
+
+    let fair = fairs_repository::get_by_id(&data.db, fair_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id_and_payload(
+                format!("DB error fetching fair {}: {}", fair_id, e),
+                "Failed to fetch fair",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+                &body,
+            )
+        })?
+        .ok_or_else(|| "Fair not found".to_json_error(StatusCode::NOT_FOUND))?;
+
+    let group = groups_repository::get_by_id(&data.db, body.group_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id_and_payload(
+                format!("DB error fetching group {}: {}", body.group_id, e),
+                "Failed to fetch group",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+                &body,
+            )
+        })?
+        .ok_or_else(|| "Group not found".to_json_error(StatusCode::NOT_FOUND))?;
+
+    if group.project_id != fair.project_id {
+        return Err(
+            "Group does not belong to the fair's project".to_json_error(StatusCode::BAD_REQUEST)
+        );
+    }
+
+    let members = groups_repository::get_group_members(&data.db, body.group_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id_and_payload(
+                format!(
+                    "DB error fetching members of group {}: {}",
+                    body.group_id, e
+                ),
+                "Failed to fetch group members",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+                &body,
+            )
+        })?;
+    let member_ids: Vec<i32> = members.iter().map(|m| m.student_id).collect();
+
+    let student_ids =
+        attendees(&member_ids, body.student_ids.as_deref()).map_err(|student_id| {
+            format!(
+                "Student {} is not a member of group {}",
+                student_id, body.group_id
+            )
+            .to_json_error(StatusCode::BAD_REQUEST)
+        })?;
+
+    let recorded = fair_attendance_repository::record(
+        &data.db,
+        fair_id,
+        body.group_id,
+        student_ids,
+        admin.admin_id,
+        data.clock.now(),
+    )
+    .await
+    .map_err(|e| {
+        error_with_log_id_and_payload(
+            format!("Failed to record attendance for fair {}: {}", fair_id, e),
+            "Failed to record attendance",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+            &body,
+        )
+    })?;
+
+    Ok(HttpResponse::Created().json(AttendanceResponse {
+        fair_id,
+        attendance: recorded.into_iter().map(AttendanceEntry::from).collect(),
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/admins/fairs/{fair_id}/attendance",
+    params(("fair_id" = i32, Path, description = "Fair ID")),
+    responses(
+        (status = 200, description = "Attendance recorded for the fair", body = AttendanceResponse),
+        (status = 404, description = "Fair not found", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError),
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Fairs management",
+)]
+#[actix_web_grants::protect(any(
+    "ROLE_ADMIN_ROOT",
+    "ROLE_ADMIN_PROFESSOR",
+    "ROLE_ADMIN_COORDINATOR"
+))]
+pub(in crate::api::v1) async fn list_attendance_handler(
+    path: Path<i32>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let fair_id = path.into_inner();
+
+    fairs_repository::get_by_id(&data.db, fair_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("DB error fetching fair {}: {}", fair_id, e),
+                "Failed to fetch fair",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?
+        .ok_or_else(|| "Fair not found".to_json_error(StatusCode::NOT_FOUND))?;
+
+    let attendance = fair_attendance_repository::get_by_fair(&data.db, fair_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("DB error fetching attendance of fair {}: {}", fair_id, e),
+                "Failed to fetch attendance",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+    Ok(HttpResponse::Ok().json(AttendanceResponse {
+        fair_id,
+        attendance: attendance.into_iter().map(AttendanceEntry::from).collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_whole_group_is_recorded_when_no_students_are_listed() {
+        assert_eq!(attendees(&[3, 7, 9], None), Ok(vec![3, 7, 9]));
+    }
+
+    #[test]
+    fn test_listed_students_are_recorded_once() {
+        assert_eq!(attendees(&[3, 7, 9], Some(&[9, 3, 9])), Ok(vec![9, 3]));
+    }
+
+    #[test]
+    fn test_student_outside_the_group_is_rejected() {
+        assert_eq!(attendees(&[3, 7, 9], Some(&[3, 12])), Err(12));
+    }
+}
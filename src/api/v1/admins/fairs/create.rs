@@ -16,8 +16,10 @@ pub(crate) struct CreateFairRequest {
     #[schema(example = "End-of-semester component fair")]
     pub details: String,
     #[schema(value_type = String, example = "2026-06-01T09:00:00Z")]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub start_date: DateTime<Utc>,
     #[schema(value_type = String, example = "2026-06-01T18:00:00Z")]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub end_date: DateTime<Utc>,
     #[schema(example = 3)]
     pub min_purchases: i32,
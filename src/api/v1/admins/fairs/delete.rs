@@ -0,0 +1,106 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::repositories::{fair_attendance_repository, fairs_repository};
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Path, Query};
+use actix_web::HttpResponse;
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct DeleteFairQuery {
+    /// Delete the fair even if attendance was recorded for it
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// A fair with recorded attendance is only deleted when forced
+fn can_delete(attendance_count: u64, force: bool) -> bool {
+    attendance_count == 0 || force
+}
+
+#[utoipa::path(
+    delete,
+    path = "/v1/admins/fairs/{fair_id}",
+    params(("fair_id" = i32, Path, description = "Fair ID"), DeleteFairQuery),
+    responses(
+        (status = 204, description = "Fair deleted with its transactions and attendance"),
+        (status = 404, description = "Fair not found", body = JsonError),
+        (status = 409, description = "Attendance was recorded for the fair; retry with force=true", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError),
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Fairs management",
+)]
+#[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
+pub(in crate::api::v1) async fn delete_fair_handler(
+    path: Path<i32>, query: Query<DeleteFairQuery>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let fair_id = path.into_inner();
+
+    fairs_repository::get_by_id(&data.db, fair_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("DB error fetching fair {}: {}", fair_id, e),
+                "Failed to fetch fair",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?
+        .ok_or_else(|| "Fair not found".to_json_error(StatusCode::NOT_FOUND))?;
+
+    let attendance_count = fair_attendance_repository::count_by_fair(&data.db, fair_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("DB error counting attendance of fair {}: {}", fair_id, e),
+                "Failed to check fair attendance",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+    if !can_delete(attendance_count, query.force) {
+        return Err(format!(
+            "Attendance was recorded for {} students of this fair. Delete with force=true to remove it anyway.",
+            attendance_count
+        )
+        .to_json_error(StatusCode::CONFLICT));
+    }
+
+    fairs_repository::delete(&data.db, fair_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("Failed to delete fair {}: {}", fair_id, e),
+                "Failed to delete fair",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fair_with_attendance_needs_force() {
+        assert!(!can_delete(4, false));
+        assert!(can_delete(4, true));
+    }
+
+    #[test]
+    fn test_fair_without_attendance_is_deleted() {
+        assert!(can_delete(0, false));
+    }
+
+    #[test]
+    fn test_force_defaults_to_false() {
+        let query = Query::<DeleteFairQuery>::from_query("").unwrap();
+        assert!(!query.force);
+    }
+}
@@ -36,7 +36,7 @@ pub(in crate::api::v1) async fn disable_fair_handler(
         })?
         .ok_or_else(|| "Fair not found".to_json_error(StatusCode::NOT_FOUND))?;
 
-    fairs_repository::disable(&data.db, fair_id)
+    fairs_repository::disable(&data.db, fair_id, data.clock.now())
         .await
         .map_err(|e| {
             error_with_log_id_and_payload(
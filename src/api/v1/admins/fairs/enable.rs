@@ -4,7 +4,6 @@ use crate::database::repositories::fairs_repository;
 use actix_web::http::StatusCode;
 use actix_web::web::{Data, Path};
 use actix_web::HttpResponse;
-use chrono::Utc;
 
 #[utoipa::path(
     post,
@@ -38,14 +37,15 @@ pub(in crate::api::v1) async fn enable_fair_handler(
         })?
         .ok_or_else(|| "Fair not found".to_json_error(StatusCode::NOT_FOUND))?;
 
-    if fair_state.end_date <= Utc::now() {
+    let now = data.clock.now();
+    if fair_state.end_date <= now {
         return Err(
             "The fair's end_date is in the past. Update the fair dates before enabling."
                 .to_json_error(StatusCode::BAD_REQUEST),
         );
     }
 
-    fairs_repository::enable(&data.db, fair_id)
+    fairs_repository::enable(&data.db, fair_id, now)
         .await
         .map_err(|e| {
             error_with_log_id_and_payload(
@@ -1,16 +1,24 @@
+use crate::api::v1::admins::fairs::attendance::{
+    list_attendance_handler, record_attendance_handler,
+};
 use crate::api::v1::admins::fairs::create::create_fair_handler;
+use crate::api::v1::admins::fairs::delete::delete_fair_handler;
 use crate::api::v1::admins::fairs::disable::disable_fair_handler;
 use crate::api::v1::admins::fairs::enable::enable_fair_handler;
 use crate::api::v1::admins::fairs::read::{get_fair_by_project_handler, get_fair_handler};
 use crate::api::v1::admins::fairs::report::fair_report_handler;
+use crate::api::v1::admins::fairs::slots::assign_slot_handler;
 use crate::api::v1::admins::fairs::update::update_fair_handler;
 use actix_web::{web, Scope};
 
+pub(crate) mod attendance;
 pub(crate) mod create;
+pub(crate) mod delete;
 pub(crate) mod disable;
 pub(crate) mod enable;
 pub(crate) mod read;
 pub(crate) mod report;
+pub(crate) mod slots;
 pub(crate) mod update;
 
 pub(super) fn fairs_scope() -> Scope {
@@ -18,9 +26,22 @@ pub(super) fn fairs_scope() -> Scope {
         .route("", web::post().to(create_fair_handler))
         .route("/{fair_id}", web::get().to(get_fair_handler))
         .route("/{fair_id}", web::patch().to(update_fair_handler))
+        .route("/{fair_id}", web::delete().to(delete_fair_handler))
         .route("/{fair_id}/enable", web::post().to(enable_fair_handler))
         .route("/{fair_id}/disable", web::post().to(disable_fair_handler))
         .route("/{fair_id}/report", web::get().to(fair_report_handler))
+        .route(
+            "/{fair_id}/attendance",
+            web::post().to(record_attendance_handler),
+        )
+        .route(
+            "/{fair_id}/attendance",
+            web::get().to(list_attendance_handler),
+        )
+        .route(
+            "/{fair_id}/slots/{group_id}",
+            web::put().to(assign_slot_handler),
+        )
         .route(
             "/project/{project_id}",
             web::get().to(get_fair_by_project_handler),
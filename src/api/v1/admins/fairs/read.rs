@@ -5,6 +5,7 @@ use crate::models::fair::Fair;
 use actix_web::http::StatusCode;
 use actix_web::web::{Data, Path};
 use actix_web::HttpResponse;
+use chrono::{DateTime, Utc};
 use serde::Serialize;
 use utoipa::ToSchema;
 use welds::state::DbState;
@@ -15,16 +16,19 @@ pub(crate) struct FairResponse {
     pub project_id: i32,
     pub details: String,
     #[schema(value_type = String)]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub start_date: chrono::DateTime<chrono::Utc>,
     #[schema(value_type = String)]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub end_date: chrono::DateTime<chrono::Utc>,
     pub min_purchases: i32,
     pub is_active: bool,
 }
 
-impl From<DbState<Fair>> for FairResponse {
-    fn from(state: DbState<Fair>) -> Self {
-        let active = fairs_repository::is_active(&state);
+impl FairResponse {
+    /// Describes the fair as it stands at `now`
+    fn new(state: DbState<Fair>, now: DateTime<Utc>) -> Self {
+        let active = fairs_repository::is_active(&state, now);
         let f = DbState::into_inner(state);
         Self {
             fair_id: f.fair_id,
@@ -73,7 +77,7 @@ pub(in crate::api::v1) async fn get_fair_handler(
         })?
         .ok_or_else(|| "Fair not found".to_json_error(StatusCode::NOT_FOUND))?;
 
-    Ok(HttpResponse::Ok().json(FairResponse::from(state)))
+    Ok(HttpResponse::Ok().json(FairResponse::new(state, data.clock.now())))
 }
 
 #[utoipa::path(
@@ -111,5 +115,5 @@ pub(in crate::api::v1) async fn get_fair_by_project_handler(
         })?
         .ok_or_else(|| "No fair found for this project".to_json_error(StatusCode::NOT_FOUND))?;
 
-    Ok(HttpResponse::Ok().json(FairResponse::from(state)))
+    Ok(HttpResponse::Ok().json(FairResponse::new(state, data.clock.now())))
 }
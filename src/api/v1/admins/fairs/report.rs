@@ -1,6 +1,7 @@
 use crate::app_data::AppData;
 use crate::common::json_error::{error_with_log_id_and_payload, JsonError, ToJsonError};
 use crate::database::repositories::fairs_repository;
+use crate::database::timing::timed;
 use actix_web::http::StatusCode;
 use actix_web::web::{Data, Path, Query};
 use actix_web::HttpResponse;
@@ -20,6 +21,7 @@ pub(crate) struct SaleEntry {
     pub component_name: String,
     pub buyer_group_name: String,
     #[schema(value_type = String)]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub timestamp: DateTime<Utc>,
 }
 
@@ -29,6 +31,7 @@ pub(crate) struct PurchaseEntry {
     pub component_name: String,
     pub seller_group_name: String,
     #[schema(value_type = String)]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub timestamp: DateTime<Utc>,
 }
 
@@ -84,23 +87,28 @@ pub(in crate::api::v1) async fn fair_report_handler(
 
     let pool = data.db.as_sqlx_pool();
 
-    let group_name = sqlx::query_scalar::<_, String>("SELECT name FROM groups WHERE group_id = $1")
-        .bind(group_id)
-        .fetch_optional(pool)
-        .await
-        .map_err(|e| {
-            error_with_log_id_and_payload(
-                format!("DB error fetching group {}: {}", group_id, e),
-                "Failed to fetch group",
-                StatusCode::INTERNAL_SERVER_ERROR,
-                log::Level::Error,
-                &group_id,
-            )
-        })?
-        .ok_or_else(|| "Group not found".to_json_error(StatusCode::NOT_FOUND))?;
+    let group_name = timed(
+        "fairs.report_group_name",
+        sqlx::query_scalar::<_, String>("SELECT name FROM groups WHERE group_id = $1")
+            .bind(group_id)
+            .fetch_optional(pool),
+    )
+    .await
+    .map_err(|e| {
+        error_with_log_id_and_payload(
+            format!("DB error fetching group {}: {}", group_id, e),
+            "Failed to fetch group",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+            &group_id,
+        )
+    })?
+    .ok_or_else(|| "Group not found".to_json_error(StatusCode::NOT_FOUND))?;
 
-    let sold_rows = sqlx::query(
-        r#"
+    let sold_rows = timed(
+        "fairs.report_sold",
+        sqlx::query(
+            r#"
         SELECT
             t.transaction_id,
             gdc.name AS component_name,
@@ -113,12 +121,17 @@ pub(in crate::api::v1) async fn fair_report_handler(
             ON t.group_deliverable_component_id = gdc.group_deliverable_component_id
         JOIN groups bg ON t.buyer_group_id = bg.group_id
         WHERE gds.group_id = $1 AND t.fair_id = $2
+          AND t.reverses_transaction_id IS NULL
+          AND NOT EXISTS (
+              SELECT 1 FROM transactions r WHERE r.reverses_transaction_id = t.transaction_id
+          )
         ORDER BY t.timestamp
         "#,
+        )
+        .bind(group_id)
+        .bind(fair_id)
+        .fetch_all(pool),
     )
-    .bind(group_id)
-    .bind(fair_id)
-    .fetch_all(pool)
     .await
     .map_err(|e| {
         error_with_log_id_and_payload(
@@ -130,8 +143,10 @@ pub(in crate::api::v1) async fn fair_report_handler(
         )
     })?;
 
-    let bought_rows = sqlx::query(
-        r#"
+    let bought_rows = timed(
+        "fairs.report_bought",
+        sqlx::query(
+            r#"
         SELECT
             t.transaction_id,
             gdc.name AS component_name,
@@ -144,12 +159,17 @@ pub(in crate::api::v1) async fn fair_report_handler(
             ON t.group_deliverable_component_id = gdc.group_deliverable_component_id
         JOIN groups sg ON gds.group_id = sg.group_id
         WHERE t.buyer_group_id = $1 AND t.fair_id = $2
+          AND t.reverses_transaction_id IS NULL
+          AND NOT EXISTS (
+              SELECT 1 FROM transactions r WHERE r.reverses_transaction_id = t.transaction_id
+          )
         ORDER BY t.timestamp
         "#,
+        )
+        .bind(group_id)
+        .bind(fair_id)
+        .fetch_all(pool),
     )
-    .bind(group_id)
-    .bind(fair_id)
-    .fetch_all(pool)
     .await
     .map_err(|e| {
         error_with_log_id_and_payload(
@@ -161,16 +181,23 @@ pub(in crate::api::v1) async fn fair_report_handler(
         )
     })?;
 
-    let distinct_row: (Option<i64>,) = sqlx::query_as(
-        r#"
+    let distinct_row: (Option<i64>,) = timed(
+        "fairs.report_distinct_buyers",
+        sqlx::query_as(
+            r#"
         SELECT COUNT(DISTINCT (t.group_deliverable_selection_id, t.group_deliverable_component_id))
         FROM transactions t
         WHERE t.fair_id = $1 AND t.buyer_group_id = $2
+          AND t.reverses_transaction_id IS NULL
+          AND NOT EXISTS (
+              SELECT 1 FROM transactions r WHERE r.reverses_transaction_id = t.transaction_id
+          )
         "#,
+        )
+        .bind(fair_id)
+        .bind(group_id)
+        .fetch_one(pool),
     )
-    .bind(fair_id)
-    .bind(group_id)
-    .fetch_one(pool)
     .await
     .map_err(|e| {
         error_with_log_id_and_payload(
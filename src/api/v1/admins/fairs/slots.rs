@@ -0,0 +1,135 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id_and_payload, JsonError, ToJsonError};
+use crate::database::repositories::fair_slots_repository::SlotAssignment;
+use crate::database::repositories::{fair_slots_repository, fairs_repository, groups_repository};
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Json, Path};
+use actix_web::HttpResponse;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use welds::state::DbState;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub(crate) struct AssignSlotRequest {
+    #[schema(value_type = String, example = "2026-06-01T10:00:00Z")]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub starts_at: DateTime<Utc>,
+    #[schema(value_type = String, example = "2026-06-01T10:20:00Z")]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub ends_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct FairSlotResponse {
+    pub fair_id: i32,
+    pub group_id: i32,
+    #[schema(value_type = String)]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub starts_at: DateTime<Utc>,
+    #[schema(value_type = String)]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub ends_at: DateTime<Utc>,
+}
+
+#[utoipa::path(
+    put,
+    path = "/v1/admins/fairs/{fair_id}/slots/{group_id}",
+    params(
+        ("fair_id" = i32, Path, description = "Fair ID"),
+        ("group_id" = i32, Path, description = "Group ID"),
+    ),
+    request_body = AssignSlotRequest,
+    responses(
+        (status = 200, description = "Slot assigned or rescheduled", body = FairSlotResponse),
+        (status = 400, description = "Invalid slot, outside the fair's dates or group not in the fair's project", body = JsonError),
+        (status = 404, description = "Fair or group not found", body = JsonError),
+        (status = 409, description = "The slot overlaps another group's slot", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError),
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Fairs management",
+)]
+/// Assign the time slot in which a group presents at a fair
+///
+/// A group has at most one slot per fair, assigning again reschedules it.
+#[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
+pub(in crate::api::v1) async fn assign_slot_handler(
+    path: Path<(i32, i32)>, body: Json<AssignSlotRequest>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let (fair_id, group_id) = path.into_inner();
+
+    let fair = fairs_repository::get_by_id(&data.db, fair_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id_and_payload(
+                format!("DB error fetching fair {}: {}", fair_id, e),
+                "Failed to fetch fair",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+                &body,
+            )
+        })?
+        .map(DbState::into_inner)
+        .ok_or_else(|| "Fair not found".to_json_error(StatusCode::NOT_FOUND))?;
+
+    let group = groups_repository::get_by_id(&data.db, group_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id_and_payload(
+                format!("DB error fetching group {}: {}", group_id, e),
+                "Failed to fetch group",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+                &body,
+            )
+        })?
+        .ok_or_else(|| "Group not found".to_json_error(StatusCode::NOT_FOUND))?;
+
+    if group.project_id != fair.project_id {
+        return Err(
+            "Group does not belong to the fair's project".to_json_error(StatusCode::BAD_REQUEST)
+        );
+    }
+
+    let assignment = fair_slots_repository::assign_locked(
+        &data.db,
+        &fair,
+        group_id,
+        body.starts_at,
+        body.ends_at,
+    )
+    .await
+    .map_err(|e| {
+        error_with_log_id_and_payload(
+            format!(
+                "Failed to assign slot of group {} in fair {}: {}",
+                group_id, fair_id, e
+            ),
+            "Failed to assign slot",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+            &body,
+        )
+    })?;
+
+    match assignment {
+        SlotAssignment::Assigned(slot) => Ok(HttpResponse::Ok().json(FairSlotResponse {
+            fair_id: slot.fair_id,
+            group_id: slot.group_id,
+            starts_at: slot.starts_at,
+            ends_at: slot.ends_at,
+        })),
+        SlotAssignment::InvalidRange => {
+            Err("ends_at must be after starts_at".to_json_error(StatusCode::BAD_REQUEST))
+        }
+        SlotAssignment::OutsideFair => {
+            Err("The slot must be within the fair's start and end dates"
+                .to_json_error(StatusCode::BAD_REQUEST))
+        }
+        SlotAssignment::Overlaps { group_id: other } => {
+            Err(format!("The slot overlaps the slot of group {}", other)
+                .to_json_error(StatusCode::CONFLICT))
+        }
+    }
+}
@@ -13,8 +13,10 @@ pub(crate) struct UpdateFairRequest {
     #[schema(example = "Updated fair description")]
     pub details: Option<String>,
     #[schema(value_type = Option<String>, example = "2026-06-01T09:00:00Z")]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339_opt")]
     pub start_date: Option<DateTime<Utc>>,
     #[schema(value_type = Option<String>, example = "2026-06-01T18:00:00Z")]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339_opt")]
     pub end_date: Option<DateTime<Utc>>,
     #[schema(example = 5)]
     pub min_purchases: Option<i32>,
@@ -92,6 +92,7 @@ pub(super) async fn create_group_component_handler(
                     &body,
                 )
             })?;
+    data.deliverable_trees.invalidate(body.project_id);
 
     Ok(HttpResponse::Ok().json(CreateGroupComponentResponse {
         group_deliverable_component_id: state.group_deliverable_component_id,
@@ -1,5 +1,6 @@
 use crate::app_data::AppData;
 use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::errors::delete_error;
 use crate::database::repositories::group_deliverable_components_repository;
 use actix_web::http::StatusCode;
 use actix_web::web::Data;
@@ -13,6 +14,7 @@ use actix_web::HttpResponse;
         (status = 200, description = "Group component deleted successfully"),
         (status = 401, description = "Authentication required", body = JsonError),
         (status = 404, description = "Group component not found", body = JsonError),
+        (status = 409, description = "Still referenced by another record (code RESOURCE_IN_USE)", body = JsonError),
         (status = 500, description = "Internal server error occurred", body = JsonError)
     ),
     security(("AdminAuth" = [])),
@@ -28,7 +30,7 @@ pub(super) async fn delete_group_component_handler(
     let id = path.into_inner();
 
     // Check if the component exists
-    let component_exists = group_deliverable_components_repository::get_by_id(&data.db, id)
+    let Some(component) = group_deliverable_components_repository::get_by_id(&data.db, id)
         .await
         .map_err(|e| {
             error_with_log_id(
@@ -38,23 +40,15 @@ pub(super) async fn delete_group_component_handler(
                 log::Level::Error,
             )
         })?
-        .is_some();
-
-    if !component_exists {
+    else {
         return Err("Group component not found".to_json_error(StatusCode::NOT_FOUND));
-    }
+    };
 
     // Delete the component using repository function
     group_deliverable_components_repository::delete_by_id(&data.db, id)
         .await
-        .map_err(|e| {
-            error_with_log_id(
-                format!("unable to delete group component: {}", e),
-                "Failed to delete component",
-                StatusCode::INTERNAL_SERVER_ERROR,
-                log::Level::Error,
-            )
-        })?;
+        .map_err(|e| delete_error(e, "group component", "Failed to delete component"))?;
+    data.deliverable_trees.invalidate(component.project_id);
 
     Ok(HttpResponse::Ok().finish())
 }
@@ -1,3 +1,4 @@
+use crate::api::v1::admins::group_deliverable_components::batch_delete::batch_delete_group_components_handler;
 use crate::api::v1::admins::group_deliverable_components::create::create_group_component_handler;
 use crate::api::v1::admins::group_deliverable_components::delete::delete_group_component_handler;
 use crate::api::v1::admins::group_deliverable_components::read::{
@@ -5,8 +6,10 @@ use crate::api::v1::admins::group_deliverable_components::read::{
     get_group_component_handler, get_group_components_for_project_handler,
 };
 use crate::api::v1::admins::group_deliverable_components::update::update_group_component_handler;
+use crate::database::unit_of_work::UnitOfWorkMiddleware;
 use actix_web::{web, Scope};
 
+pub(crate) mod batch_delete;
 pub(crate) mod create;
 pub(crate) mod delete;
 pub(crate) mod read;
@@ -16,6 +19,11 @@ pub(super) fn group_deliverable_components_scope() -> Scope {
     web::scope("/group-deliverable-components")
         .route("", web::get().to(get_all_group_components_handler))
         .route("", web::post().to(create_group_component_handler))
+        .service(
+            web::resource("/batch-delete")
+                .wrap(UnitOfWorkMiddleware)
+                .route(web::post().to(batch_delete_group_components_handler)),
+        )
         .route(
             "/project/{project_id}",
             web::get().to(get_group_components_for_project_handler),
@@ -40,6 +40,8 @@ pub(crate) struct GroupComponentDeliverableResponse {
     pub group_deliverable_component_id: i32,
     #[schema(example = "5")]
     pub quantity: i32,
+    #[schema(example = "30")]
+    pub weight: i32,
     #[schema(example = "Motor")]
     pub deliverable_name: String,
 }
@@ -252,6 +254,7 @@ pub(super) async fn get_deliverables_for_group_component_handler(
             group_deliverable_id: relationship_data.group_deliverable_id,
             group_deliverable_component_id: relationship_data.group_deliverable_component_id,
             quantity: relationship_data.quantity,
+            weight: relationship_data.weight,
             deliverable_name: deliverable.name,
         });
     }
@@ -81,6 +81,7 @@ pub(super) async fn update_group_component_handler(
     component_state.name = body.name.clone();
     component_state.sellable = body.sellable;
 
+    let project_id = component_state.project_id;
     group_deliverable_components_repository::update(&data.db, component_state)
         .await
         .map_err(|e| {
@@ -92,6 +93,7 @@ pub(super) async fn update_group_component_handler(
                 &body,
             )
         })?;
+    data.deliverable_trees.invalidate(project_id);
 
     Ok(HttpResponse::Ok().finish())
 }
@@ -27,7 +27,9 @@ pub(crate) struct ComponentImplementationDetailInfo {
     pub component_name: String,
     pub markdown_description: String,
     pub repository_link: String,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -57,6 +59,7 @@ pub(crate) struct GroupSelectionInfo {
 ///
 /// This endpoint allows admins to view all group deliverable selections for a specific project,
 /// including which deliverables each group has chosen and their submission details.
+/// A project without selections gets an empty list, 404 is only for a missing project.
 #[actix_web_grants::protect(any(
     "ROLE_ADMIN_ROOT",
     "ROLE_ADMIN_PROFESSOR",
@@ -0,0 +1,128 @@
+use crate::app_data::AppData;
+use crate::common::batch_delete::{
+    count_dependents, plan_deletes, validate_batch, BatchDeleteRequest, BatchDeleteResponse,
+};
+use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::database::errors::delete_error;
+use crate::database::repositories::group_deliverables_repository;
+use crate::database::unit_of_work::UnitOfWork;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Json};
+use actix_web::HttpResponse;
+
+/// Every requested id that exists, with the number of rows depending on it
+const DEPENDENTS: &str = "
+SELECT d.group_deliverable_id,
+       (SELECT COUNT(*) FROM group_deliverable_selections s
+         WHERE s.group_deliverable_id = d.group_deliverable_id)
+FROM group_deliverables d
+WHERE d.group_deliverable_id = ANY($1)";
+
+/// What the dependents counted by `DEPENDENTS` are
+const DEPENDENTS_NOUN: &str = "group selections";
+
+#[utoipa::path(
+    post,
+    path = "/v1/admins/group-deliverables/batch-delete",
+    request_body = BatchDeleteRequest,
+    responses(
+        (status = 200, description = "Batch processed, see per-id results", body = BatchDeleteResponse),
+        (status = 400, description = "No ids or too many ids", body = JsonError),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 409, description = "Still referenced by another record (code RESOURCE_IN_USE)", body = JsonError),
+        (status = 500, description = "Internal server error occurred", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Group deliverables management",
+)]
+/// Deletes several group deliverables at once
+///
+/// A deliverable already selected by some group is refused unless `cascade` is set, in
+/// which case the selections, their implementation details and purchases go with it.
+/// Everything is deleted in one transaction and each id gets its own outcome.
+#[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
+pub(super) async fn batch_delete_group_deliverables_handler(
+    body: Json<BatchDeleteRequest>, data: Data<AppData>, unit_of_work: UnitOfWork,
+) -> Result<HttpResponse, JsonError> {
+    validate_batch(&body.ids)?;
+
+    let existing = count_dependents(&unit_of_work, DEPENDENTS, &body.ids)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!(
+                    "unable to check the dependents of group deliverables: {}",
+                    e
+                ),
+                "Failed to delete group deliverables",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+    let response = BatchDeleteResponse::new(plan_deletes(
+        &body.ids,
+        &existing,
+        body.cascade,
+        DEPENDENTS_NOUN,
+    ));
+
+    group_deliverables_repository::delete_by_ids(&unit_of_work, &response.deleted_ids())
+        .await
+        .map_err(|e| {
+            delete_error(
+                e,
+                "group deliverables",
+                "Failed to delete group deliverables",
+            )
+        })?;
+    let deliverable_trees = data.deliverable_trees.clone();
+    unit_of_work.after_commit(move || deliverable_trees.invalidate_all());
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Deliverable 4 was selected by two groups, deliverable 5 by none
+    fn existing() -> HashMap<i32, i64> {
+        HashMap::from([(4, 2), (5, 0)])
+    }
+
+    #[test]
+    fn test_selected_deliverable_is_refused_by_default() {
+        let response =
+            BatchDeleteResponse::new(plan_deletes(&[4, 5], &existing(), false, DEPENDENTS_NOUN));
+
+        assert_eq!(response.deleted, 1);
+        assert_eq!(response.deleted_ids(), vec![5]);
+        let refused = &response.results[0];
+        assert!(!refused.deleted);
+        assert_eq!(refused.dependents, 2);
+        assert_eq!(
+            refused.message,
+            "Referenced by 2 group selections, retry with cascade=true"
+        );
+    }
+
+    #[test]
+    fn test_selected_deliverable_is_cascaded_with_the_flag() {
+        let response =
+            BatchDeleteResponse::new(plan_deletes(&[4, 5], &existing(), true, DEPENDENTS_NOUN));
+
+        assert_eq!(response.deleted_ids(), vec![4, 5]);
+        assert_eq!(
+            response.results[0].message,
+            "Deleted with 2 group selections"
+        );
+    }
+
+    #[test]
+    fn test_cascade_defaults_to_false() {
+        let body: BatchDeleteRequest = serde_json::from_str(r#"{"ids": [4]}"#).unwrap();
+        assert!(!body.cascade);
+    }
+}
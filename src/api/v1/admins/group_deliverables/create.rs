@@ -1,4 +1,5 @@
 use crate::app_data::AppData;
+use crate::common::created::created;
 use crate::common::json_error::{error_with_log_id_and_payload, JsonError, ToJsonError};
 use crate::database::repositories::group_deliverables_repository;
 use crate::models::group_deliverable::GroupDeliverable;
@@ -31,7 +32,9 @@ pub(crate) struct CreateGroupDeliverableResponse {
     path = "/v1/admins/group-deliverables",
     request_body = CreateGroupDeliverableScheme,
     responses(
-        (status = 200, description = "Group deliverable created successfully", body = CreateGroupDeliverableResponse),
+        (status = 201, description = "Group deliverable created successfully", body = CreateGroupDeliverableResponse,
+            headers(("Location" = String, description = "URL of the created group deliverable"))
+        ),
         (status = 400, description = "Invalid data in request", body = JsonError),
         (status = 401, description = "Authentication required", body = JsonError),
         (status = 409, description = "Deliverable with this name already exists for the project", body = JsonError),
@@ -83,10 +86,15 @@ pub(super) async fn create_group_deliverable_handler(
                 &body,
             )
         })?;
+    data.deliverable_trees.invalidate(body.project_id);
 
-    Ok(HttpResponse::Ok().json(CreateGroupDeliverableResponse {
-        group_deliverable_id: state.group_deliverable_id,
-        project_id: body.project_id,
-        name: body.name.clone(),
-    }))
+    Ok(created(
+        "/v1/admins/group-deliverables",
+        state.group_deliverable_id,
+        &CreateGroupDeliverableResponse {
+            group_deliverable_id: state.group_deliverable_id,
+            project_id: body.project_id,
+            name: body.name.clone(),
+        },
+    ))
 }
@@ -1,5 +1,6 @@
 use crate::app_data::AppData;
 use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::errors::delete_error;
 use crate::database::repositories::group_deliverables_repository;
 use actix_web::http::StatusCode;
 use actix_web::web::Data;
@@ -13,6 +14,7 @@ use actix_web::HttpResponse;
         (status = 200, description = "Group deliverable deleted successfully"),
         (status = 401, description = "Authentication required", body = JsonError),
         (status = 404, description = "Group deliverable not found", body = JsonError),
+        (status = 409, description = "Still referenced by another record (code RESOURCE_IN_USE)", body = JsonError),
         (status = 500, description = "Internal server error occurred", body = JsonError)
     ),
     security(("AdminAuth" = [])),
@@ -28,7 +30,7 @@ pub(super) async fn delete_group_deliverable_handler(
     let id = path.into_inner();
 
     // Check if the deliverable exists
-    let deliverable_exists = group_deliverables_repository::get_by_id(&data.db, id)
+    let Some(deliverable) = group_deliverables_repository::get_by_id(&data.db, id)
         .await
         .map_err(|e| {
             error_with_log_id(
@@ -38,23 +40,15 @@ pub(super) async fn delete_group_deliverable_handler(
                 log::Level::Error,
             )
         })?
-        .is_some();
-
-    if !deliverable_exists {
+    else {
         return Err("Group deliverable not found".to_json_error(StatusCode::NOT_FOUND));
-    }
+    };
 
     // Delete the deliverable using repository function
     group_deliverables_repository::delete_by_id(&data.db, id)
         .await
-        .map_err(|e| {
-            error_with_log_id(
-                format!("unable to delete group deliverable: {}", e),
-                "Failed to delete deliverable",
-                StatusCode::INTERNAL_SERVER_ERROR,
-                log::Level::Error,
-            )
-        })?;
+        .map_err(|e| delete_error(e, "group deliverable", "Failed to delete deliverable"))?;
+    data.deliverable_trees.invalidate(deliverable.project_id);
 
     Ok(HttpResponse::Ok().finish())
 }
@@ -0,0 +1,218 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError, INVALID_PARAM};
+use crate::database::repositories::{
+    group_component_implementation_details_repository, group_deliverable_selections_repository,
+    group_deliverables_components_repository, group_deliverables_repository,
+};
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Path, Query};
+use actix_web::HttpResponse;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct LinkImpactQuery {
+    /// Comma separated IDs of the components the deliverable would be linked to after the
+    /// change, empty to preview unlinking every component
+    #[param(example = "1,2,5")]
+    #[serde(default)]
+    pub component_ids: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, ToSchema)]
+pub(crate) struct LinkImpactResponse {
+    pub group_deliverable_id: i32,
+    /// Components linked now that the change would unlink
+    pub removed_component_ids: Vec<i32>,
+    /// Components the change would link, selections lack their implementation details
+    pub added_component_ids: Vec<i32>,
+    /// Groups that selected the deliverable
+    #[schema(example = 12)]
+    pub selections: usize,
+    /// Selections with implementation details on a component that would be unlinked
+    #[schema(example = 3)]
+    pub affected_selections: usize,
+    /// Implementation details written for the components that would be unlinked
+    #[schema(example = 4)]
+    pub affected_implementation_details: usize,
+}
+
+/// Reads the proposed components, ignoring blanks and repetitions
+pub(crate) fn parse_component_ids(raw: &str) -> Result<BTreeSet<i32>, JsonError> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| {
+            id.parse::<i32>().map_err(|_| {
+                format!("Invalid component ID in `component_ids`: {:?}", id)
+                    .to_json_error(StatusCode::BAD_REQUEST)
+                    .with_code(INVALID_PARAM)
+            })
+        })
+        .collect()
+}
+
+/// What changing the links of a deliverable from `current` to `proposed` does to its selections
+///
+/// `details` are the (selection, component) pairs of the implementation details written by
+/// the groups that selected the deliverable.
+fn link_impact(
+    group_deliverable_id: i32, current: &BTreeSet<i32>, proposed: &BTreeSet<i32>,
+    selections: usize, details: &[(i32, i32)],
+) -> LinkImpactResponse {
+    let removed: BTreeSet<i32> = current.difference(proposed).copied().collect();
+
+    let affected: Vec<&(i32, i32)> = details
+        .iter()
+        .filter(|(_, component_id)| removed.contains(component_id))
+        .collect();
+    let affected_selections = affected
+        .iter()
+        .map(|(selection_id, _)| *selection_id)
+        .collect::<HashSet<_>>()
+        .len();
+
+    LinkImpactResponse {
+        group_deliverable_id,
+        added_component_ids: proposed.difference(current).copied().collect(),
+        removed_component_ids: removed.into_iter().collect(),
+        selections,
+        affected_selections,
+        affected_implementation_details: affected.len(),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/admins/group-deliverables/{id}/link-impact",
+    params(("id" = i32, Path, description = "Group deliverable ID"), LinkImpactQuery),
+    responses(
+        (status = 200, description = "Impact of the proposed links on the existing selections", body = LinkImpactResponse),
+        (status = 400, description = "Invalid component IDs (code INVALID_PARAM)", body = JsonError),
+        (status = 404, description = "Group deliverable not found", body = JsonError),
+        (status = 500, description = "Internal server error occurred", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Group deliverables management",
+)]
+/// Preview the impact of changing the components of a group deliverable
+///
+/// Compares the components linked now with the proposed ones and counts the selections whose
+/// implementation details are on a component that would be unlinked. Nothing is changed.
+#[actix_web_grants::protect(any(
+    "ROLE_ADMIN_ROOT",
+    "ROLE_ADMIN_PROFESSOR",
+    "ROLE_ADMIN_COORDINATOR"
+))]
+pub(super) async fn get_group_deliverable_link_impact_handler(
+    path: Path<i32>, query: Query<LinkImpactQuery>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let deliverable_id = path.into_inner();
+    let proposed = parse_component_ids(&query.component_ids)?;
+
+    let db_error = |what: &str, e: welds::WeldsError| {
+        error_with_log_id(
+            format!(
+                "unable to {} of group deliverable {}: {}",
+                what, deliverable_id, e
+            ),
+            "Failed to preview the impact",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    };
+
+    group_deliverables_repository::get_by_id(&data.db, deliverable_id)
+        .await
+        .map_err(|e| db_error("fetch", e))?
+        .ok_or_else(|| "Group deliverable not found".to_json_error(StatusCode::NOT_FOUND))?;
+
+    let current: BTreeSet<i32> =
+        group_deliverables_components_repository::get_by_deliverable_id(&data.db, deliverable_id)
+            .await
+            .map_err(|e| db_error("fetch the component links", e))?
+            .into_iter()
+            .map(|link| link.group_deliverable_component_id)
+            .collect();
+
+    let selection_ids: Vec<i32> =
+        group_deliverable_selections_repository::get_by_deliverable_id(&data.db, deliverable_id)
+            .await
+            .map_err(|e| db_error("fetch the selections", e))?
+            .into_iter()
+            .map(|selection| selection.group_deliverable_selection_id)
+            .collect();
+
+    let details: Vec<(i32, i32)> =
+        group_component_implementation_details_repository::get_by_selection_ids(
+            &data.db,
+            &selection_ids,
+        )
+        .await
+        .map_err(|e| db_error("fetch the implementation details", e))?
+        .into_iter()
+        .map(|d| {
+            (
+                d.group_deliverable_selection_id,
+                d.group_deliverable_component_id,
+            )
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(link_impact(
+        deliverable_id,
+        &current,
+        &proposed,
+        selection_ids.len(),
+        &details,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::ResponseError;
+
+    fn ids(ids: &[i32]) -> BTreeSet<i32> {
+        ids.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_impact_counts_selections_depending_on_removed_components() {
+        // Three groups selected the deliverable linked to components 1, 2 and 3
+        let details = [(10, 1), (10, 2), (11, 2), (11, 3), (12, 1)];
+
+        let impact = link_impact(4, &ids(&[1, 2, 3]), &ids(&[1, 3, 5]), 3, &details);
+
+        assert_eq!(
+            impact,
+            LinkImpactResponse {
+                group_deliverable_id: 4,
+                removed_component_ids: vec![2],
+                added_component_ids: vec![5],
+                selections: 3,
+                affected_selections: 2,
+                affected_implementation_details: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_unchanged_links_have_no_impact() {
+        let impact = link_impact(4, &ids(&[1, 2]), &ids(&[2, 1]), 1, &[(10, 1), (10, 2)]);
+
+        assert!(impact.removed_component_ids.is_empty());
+        assert!(impact.added_component_ids.is_empty());
+        assert_eq!(impact.affected_selections, 0);
+    }
+
+    #[test]
+    fn test_component_ids_are_parsed() {
+        assert_eq!(parse_component_ids(" 3, 1,,3 ").unwrap(), ids(&[1, 3]));
+        assert!(parse_component_ids("").unwrap().is_empty());
+
+        let err = parse_component_ids("1,two").unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+    }
+}
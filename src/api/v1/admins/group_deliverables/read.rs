@@ -38,6 +38,8 @@ pub(crate) struct GroupDeliverableComponentResponse {
     pub group_deliverable_component_id: i32,
     #[schema(example = "5")]
     pub quantity: i32,
+    #[schema(example = "30")]
+    pub weight: i32,
     #[schema(example = "Resistor")]
     pub component_name: String,
 }
@@ -250,6 +252,7 @@ pub(super) async fn get_components_for_group_deliverable_handler(
             group_deliverable_id: relationship_data.group_deliverable_id,
             group_deliverable_component_id: relationship_data.group_deliverable_component_id,
             quantity: relationship_data.quantity,
+            weight: relationship_data.weight,
             component_name: component.name,
         });
     }
@@ -87,6 +87,8 @@ pub(super) async fn update_group_deliverable_handler(
                 &body,
             )
         })?;
+    data.deliverable_trees
+        .invalidate(deliverable_state.project_id);
 
     Ok(HttpResponse::Ok().finish())
 }
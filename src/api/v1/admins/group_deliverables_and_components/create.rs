@@ -1,5 +1,9 @@
 use crate::app_data::AppData;
-use crate::common::json_error::{error_with_log_id_and_payload, JsonError, ToJsonError};
+use crate::common::json_error::{
+    error_with_log_id_and_payload, JsonError, ToJsonError, LINK_EXISTS,
+};
+use crate::common::link_weights::validate_link;
+use crate::database::errors::is_unique_violation;
 use crate::database::repositories::group_deliverables_components_repository;
 use crate::models::group_deliverables_component::GroupDeliverablesComponent;
 use actix_web::http::StatusCode;
@@ -16,6 +20,10 @@ pub(crate) struct CreateGroupDeliverableComponentScheme {
     pub group_deliverable_component_id: i32,
     #[schema(example = "5")]
     pub quantity: i32,
+    /// Grading weight of the component within the deliverable, defaults to 0
+    #[serde(default)]
+    #[schema(example = "30")]
+    pub weight: i32,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -28,6 +36,15 @@ pub(crate) struct CreateGroupDeliverableComponentResponse {
     pub group_deliverable_component_id: i32,
     #[schema(example = "5")]
     pub quantity: i32,
+    #[schema(example = "30")]
+    pub weight: i32,
+}
+
+/// Conflict returned both by the pre-check and when the unique constraint catches a race
+fn link_exists_error() -> JsonError {
+    "Relationship already exists"
+        .to_json_error(StatusCode::CONFLICT)
+        .with_code(LINK_EXISTS)
 }
 
 #[utoipa::path(
@@ -38,7 +55,8 @@ pub(crate) struct CreateGroupDeliverableComponentResponse {
         (status = 200, description = "Group deliverable-component relationship created successfully", body = CreateGroupDeliverableComponentResponse),
         (status = 400, description = "Invalid data in request", body = JsonError),
         (status = 401, description = "Authentication required", body = JsonError),
-        (status = 409, description = "Relationship already exists", body = JsonError),
+        (status = 422, description = "Quantity or weight out of range, or weights summing over 100", body = JsonError),
+        (status = 409, description = "Relationship already exists (code LINK_EXISTS)", body = JsonError),
         (status = 500, description = "Internal server error occurred", body = JsonError)
     ),
     security(("AdminAuth" = [])),
@@ -69,20 +87,42 @@ pub(super) async fn create_group_deliverable_component_handler(
     })?;
 
     if exists {
-        return Err("Relationship already exists".to_json_error(StatusCode::CONFLICT));
+        return Err(link_exists_error());
     }
 
+    let other_weights = group_deliverables_components_repository::total_weight_for_deliverable(
+        &data.db,
+        body.group_deliverable_id,
+        None,
+    )
+    .await
+    .map_err(|e| {
+        error_with_log_id_and_payload(
+            format!("unable to sum component weights: {}", e),
+            "Failed to create relationship",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+            &body,
+        )
+    })?;
+
+    validate_link(body.quantity, body.weight, other_weights)?;
+
     let group_deliverables_component = GroupDeliverablesComponent {
         id: 0,
         group_deliverable_id: body.group_deliverable_id,
         group_deliverable_component_id: body.group_deliverable_component_id,
         quantity: body.quantity,
+        weight: body.weight,
     };
 
     let state =
         group_deliverables_components_repository::create(&data.db, group_deliverables_component)
             .await
             .map_err(|e| {
+                if is_unique_violation(&e) {
+                    return link_exists_error();
+                }
                 error_with_log_id_and_payload(
                     format!(
                         "unable to create group deliverable component relationship: {}",
@@ -94,6 +134,7 @@ pub(super) async fn create_group_deliverable_component_handler(
                     &body,
                 )
             })?;
+    data.deliverable_trees.invalidate_all();
 
     Ok(
         HttpResponse::Ok().json(CreateGroupDeliverableComponentResponse {
@@ -101,6 +142,24 @@ pub(super) async fn create_group_deliverable_component_handler(
             group_deliverable_id: body.group_deliverable_id,
             group_deliverable_component_id: body.group_deliverable_component_id,
             quantity: body.quantity,
+            weight: body.weight,
         }),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::ResponseError;
+
+    #[test]
+    fn test_duplicate_link_is_rejected_with_link_exists_code() {
+        let err = link_exists_error();
+
+        assert_eq!(err.status_code(), StatusCode::CONFLICT);
+        assert_eq!(
+            serde_json::to_value(&err).unwrap()["code"],
+            serde_json::json!(LINK_EXISTS)
+        );
+    }
+}
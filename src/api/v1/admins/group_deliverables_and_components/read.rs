@@ -21,6 +21,8 @@ pub(crate) struct GroupDeliverableComponentResponse {
     pub group_deliverable_component_id: i32,
     #[schema(example = "5")]
     pub quantity: i32,
+    #[schema(example = "30")]
+    pub weight: i32,
     #[schema(example = "Resistor")]
     pub component_name: String,
     #[schema(example = "10k")]
@@ -102,6 +104,7 @@ pub(super) async fn get_components_for_deliverable_handler(
             group_deliverable_id: relationship_data.group_deliverable_id,
             group_deliverable_component_id: relationship_data.group_deliverable_component_id,
             quantity: relationship_data.quantity,
+            weight: relationship_data.weight,
             component_name: component.name.clone(),
             deliverable_name: deliverable.name.clone(),
         });
@@ -176,6 +179,7 @@ pub(super) async fn get_deliverables_for_component_handler(
             group_deliverable_id: relationship_data.group_deliverable_id,
             group_deliverable_component_id: relationship_data.group_deliverable_component_id,
             quantity: relationship_data.quantity,
+            weight: relationship_data.weight,
             component_name: component.name.clone(),
             deliverable_name: deliverable.name.clone(),
         });
@@ -0,0 +1,411 @@
+use crate::app_data::AppData;
+use crate::common::access::{ensure_admin_sees_project, found_or_not_found};
+use crate::common::batch_result::BatchResult;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::repositories::{groups_repository, projects_repository, students_repository};
+use crate::jwt::get_user::LoggedUser;
+use crate::models::group_member::GroupMember;
+use crate::models::student::Student;
+use crate::models::student_role::AvailableStudentRole;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use utoipa::ToSchema;
+use welds::state::DbState;
+
+const GROUP_NOT_FOUND: &str = "Group not found";
+/// Upper bound on the number of students accepted in a single batch
+const MAX_BATCH_SIZE: usize = 100;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct AdminBatchAddMembersRequest {
+    /// Students to add to the group
+    #[schema(example = json!([12, 15, 18]))]
+    pub student_ids: Vec<i32>,
+    /// Optional student, among `student_ids`, to add as Group Leader
+    #[schema(example = 12)]
+    pub leader_student_id: Option<i32>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct AddedMember {
+    #[schema(example = 12)]
+    pub student_id: i32,
+    /// Role assigned to the student
+    #[schema(example = "Member")]
+    pub role: String,
+}
+
+/// What is known about a requested student before adding it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Candidate {
+    Missing,
+    Pending,
+    InProject,
+    Eligible,
+}
+
+/// Decision taken for a single requested student
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Add { as_leader: bool },
+    Reject(&'static str),
+}
+
+/// Decides, in request order, which students can join the group
+///
+/// Students are rejected when missing, not confirmed, already in a group of the
+/// project, repeated in the request or once the group has reached `max_group_size`.
+fn plan_batch(
+    candidates: &[(i32, Candidate)], current_members: i32, max_group_size: i32,
+    leader_student_id: Option<i32>,
+) -> Vec<Outcome> {
+    let mut seen = HashSet::new();
+    let mut size = current_members;
+
+    candidates
+        .iter()
+        .map(|(student_id, candidate)| {
+            if !seen.insert(*student_id) {
+                return Outcome::Reject("Student listed more than once");
+            }
+
+            match candidate {
+                Candidate::Missing => return Outcome::Reject("Student not found"),
+                Candidate::Pending => return Outcome::Reject("Student email not confirmed"),
+                Candidate::InProject => return Outcome::Reject("Student already in project group"),
+                Candidate::Eligible => {}
+            }
+
+            if size >= max_group_size {
+                return Outcome::Reject("Group size limit exceeded");
+            }
+
+            size += 1;
+            Outcome::Add {
+                as_leader: leader_student_id == Some(*student_id),
+            }
+        })
+        .collect()
+}
+
+/// Members to insert for the students the plan adds
+fn new_members(
+    candidates: &[(i32, Candidate)], outcomes: &[Outcome], group_id: i32, joined_at: DateTime<Utc>,
+) -> Vec<GroupMember> {
+    candidates
+        .iter()
+        .zip(outcomes)
+        .filter_map(|((student_id, _), outcome)| match outcome {
+            Outcome::Add { as_leader } => Some(GroupMember {
+                group_member_id: 0,
+                group_id,
+                student_id: *student_id,
+                student_role_id: if *as_leader {
+                    AvailableStudentRole::GroupLeader as i32
+                } else {
+                    AvailableStudentRole::Member as i32
+                },
+                joined_at,
+            }),
+            Outcome::Reject(_) => None,
+        })
+        .collect()
+}
+
+/// Reports the outcome of every requested student, in request order
+fn batch_result(
+    candidates: &[(i32, Candidate)], outcomes: Vec<Outcome>,
+) -> BatchResult<AddedMember> {
+    candidates
+        .iter()
+        .zip(outcomes)
+        .map(|((student_id, _), outcome)| match outcome {
+            Outcome::Add { as_leader } => Ok(AddedMember {
+                student_id: *student_id,
+                role: if as_leader { "Group Leader" } else { "Member" }.to_string(),
+            }),
+            Outcome::Reject(reason) => Err(reason.to_string()),
+        })
+        .collect()
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/admins/groups/{group_id}/members/batch",
+    request_body = AdminBatchAddMembersRequest,
+    responses(
+        (status = 200, description = "Every student added", body = BatchResult<AddedMember>),
+        (status = 207, description = "Some students added, see the failed ones", body = BatchResult<AddedMember>),
+        (status = 400, description = "Invalid request data or leader conflict", body = JsonError),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 404, description = "Group not found", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Admin Groups management",
+)]
+/// Add several members to a group at once (Admin/Coordinator)
+///
+/// Each student is validated independently against the same rules used when adding
+/// a single member. Valid students are inserted together in one transaction holding the
+/// group lock, so the size limit also holds against concurrent additions. Invalid ones
+/// are listed as failed with their index in `student_ids`.
+/// A leader can be designated among the added students if the group has none.
+#[actix_web_grants::protect(any(
+    "ROLE_ADMIN_ROOT",
+    "ROLE_ADMIN_PROFESSOR",
+    "ROLE_ADMIN_COORDINATOR"
+))]
+pub(super) async fn add_members_batch(
+    req: HttpRequest, path: Path<i32>, body: Json<AdminBatchAddMembersRequest>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let group_id = path.into_inner();
+    let body = body.into_inner();
+
+    if body.student_ids.is_empty() {
+        return Err("At least one student is required".to_json_error(StatusCode::BAD_REQUEST));
+    }
+
+    if body.student_ids.len() > MAX_BATCH_SIZE {
+        return Err(
+            format!("At most {} students can be added at once", MAX_BATCH_SIZE)
+                .to_json_error(StatusCode::BAD_REQUEST),
+        );
+    }
+
+    if let Some(leader_id) = body.leader_student_id {
+        if !body.student_ids.contains(&leader_id) {
+            return Err("Leader must be one of the students being added"
+                .to_json_error(StatusCode::BAD_REQUEST));
+        }
+    }
+
+    let group_state = groups_repository::get_by_id(&data.db, group_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch group {}: {}", group_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+    let group = DbState::into_inner(found_or_not_found(group_state, GROUP_NOT_FOUND)?);
+    ensure_admin_sees_project(&data.db, &admin, group.project_id, GROUP_NOT_FOUND).await?;
+
+    let project_state = projects_repository::get_by_id(&data.db, group.project_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch project {}: {}", group.project_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+    let project = match project_state {
+        Some(state) => DbState::into_inner(state),
+        None => {
+            return Err(error_with_log_id(
+                format!("project {} not found", group.project_id),
+                "Project not found",
+                StatusCode::NOT_FOUND,
+                log::Level::Warn,
+            ));
+        }
+    };
+
+    let members = groups_repository::get_members(&data.db, group_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch members of group {}: {}", group_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+    let has_leader = members
+        .iter()
+        .any(|m| m.student_role_id == AvailableStudentRole::GroupLeader as i32);
+
+    if has_leader && body.leader_student_id.is_some() {
+        return Err(error_with_log_id(
+            format!("group {} already has a leader", group_id),
+            "Group already has a leader",
+            StatusCode::BAD_REQUEST,
+            log::Level::Warn,
+        ));
+    }
+
+    let students: HashMap<i32, Student> =
+        students_repository::get_by_ids(&data.db, &body.student_ids)
+            .await
+            .map_err(|e| {
+                error_with_log_id(
+                    format!("unable to fetch students {:?}: {}", body.student_ids, e),
+                    "Database error",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                )
+            })?
+            .into_iter()
+            .map(DbState::into_inner)
+            .map(|s| (s.student_id, s))
+            .collect();
+
+    let in_project: HashSet<i32> =
+        groups_repository::students_in_project(&data.db, &body.student_ids, group.project_id)
+            .await
+            .map_err(|e| {
+                error_with_log_id(
+                    format!("unable to check existing memberships: {}", e),
+                    "Database error",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                )
+            })?
+            .into_iter()
+            .collect();
+
+    let candidates: Vec<(i32, Candidate)> = body
+        .student_ids
+        .iter()
+        .map(|id| {
+            let candidate = match students.get(id) {
+                None => Candidate::Missing,
+                Some(s) if s.is_pending => Candidate::Pending,
+                Some(_) if in_project.contains(id) => Candidate::InProject,
+                Some(_) => Candidate::Eligible,
+            };
+            (*id, candidate)
+        })
+        .collect();
+
+    // The size is checked on the locked members, so concurrent additions cannot overfill the group
+    let joined_at = Utc::now();
+    let outcomes = groups_repository::add_members_locked(&data.db, group_id, |members| {
+        let outcomes = plan_batch(
+            &candidates,
+            members.len() as i32,
+            project.max_group_size,
+            body.leader_student_id,
+        );
+        (
+            new_members(&candidates, &outcomes, group_id, joined_at),
+            outcomes,
+        )
+    })
+    .await
+    .map_err(|e| {
+        error_with_log_id(
+            format!("unable to add members to group {}: {}", group_id, e),
+            "Failed to add members",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    Ok(batch_result(&candidates, outcomes).respond())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_exceeding_max_size_rejects_overflow() {
+        let candidates = [
+            (1, Candidate::Eligible),
+            (2, Candidate::Eligible),
+            (3, Candidate::Eligible),
+        ];
+
+        let outcomes = plan_batch(&candidates, 2, 4, None);
+
+        assert_eq!(
+            outcomes,
+            vec![
+                Outcome::Add { as_leader: false },
+                Outcome::Add { as_leader: false },
+                Outcome::Reject("Group size limit exceeded"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_student_in_another_group_is_rejected() {
+        let candidates = [(1, Candidate::InProject), (2, Candidate::Eligible)];
+
+        let outcomes = plan_batch(&candidates, 0, 4, Some(2));
+
+        assert_eq!(
+            outcomes,
+            vec![
+                Outcome::Reject("Student already in project group"),
+                Outcome::Add { as_leader: true },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_duplicates_and_invalid_students_do_not_take_seats() {
+        let candidates = [
+            (1, Candidate::Missing),
+            (2, Candidate::Pending),
+            (3, Candidate::Eligible),
+            (3, Candidate::Eligible),
+        ];
+
+        let outcomes = plan_batch(&candidates, 0, 1, None);
+
+        assert_eq!(
+            outcomes,
+            vec![
+                Outcome::Reject("Student not found"),
+                Outcome::Reject("Student email not confirmed"),
+                Outcome::Add { as_leader: false },
+                Outcome::Reject("Student listed more than once"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mixed_batch_reports_failures_by_index() {
+        let candidates = [
+            (1, Candidate::Eligible),
+            (2, Candidate::InProject),
+            (3, Candidate::Eligible),
+        ];
+
+        let result = batch_result(&candidates, plan_batch(&candidates, 0, 4, Some(3)));
+
+        assert_eq!(result.status(), StatusCode::MULTI_STATUS);
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({
+                "succeeded": [
+                    {"student_id": 1, "role": "Member"},
+                    {"student_id": 3, "role": "Group Leader"}
+                ],
+                "failed": [{"index": 1, "error": "Student already in project group"}]
+            })
+        );
+    }
+}
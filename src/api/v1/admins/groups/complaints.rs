@@ -23,6 +23,7 @@ pub(crate) struct ComplaintsFiledItem {
     pub transaction_id: i32,
     pub to_group_id: i32,
     pub text: String,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub created_at: DateTime<Utc>,
 }
 
@@ -32,6 +33,7 @@ pub(crate) struct ComplaintsReceivedItem {
     pub transaction_id: i32,
     pub from_group_id: i32,
     pub text: String,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub created_at: DateTime<Utc>,
 }
 
@@ -1,4 +1,5 @@
 use crate::app_data::AppData;
+use crate::common::access::{ensure_admin_sees_project, found_or_not_found};
 use crate::common::json_error::{error_with_log_id, JsonError};
 use crate::database::repositories::{
     group_component_implementation_details_repository, group_deliverable_components_repository,
@@ -15,6 +16,8 @@ use serde::Serialize;
 use utoipa::ToSchema;
 use welds::state::DbState;
 
+const GROUP_NOT_FOUND: &str = "Group not found";
+
 #[derive(Debug, Serialize, ToSchema)]
 pub(crate) struct GroupDetailsResponse {
     pub group_id: i32,
@@ -57,7 +60,9 @@ pub(crate) struct ComponentImplementationDetailInfo {
     pub component_name: String,
     pub markdown_description: String,
     pub repository_link: String,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -94,7 +99,7 @@ pub(crate) struct GroupDeliverableDetail {
 pub(super) async fn get_group_details(
     req: HttpRequest, path: Path<i32>, data: Data<AppData>,
 ) -> Result<HttpResponse, JsonError> {
-    let _admin = match req.extensions().get_admin() {
+    let admin = match req.extensions().get_admin() {
         Ok(admin) => admin,
         Err(_) => {
             return Err(error_with_log_id(
@@ -120,17 +125,8 @@ pub(super) async fn get_group_details(
             )
         })?;
 
-    let group = match group_state {
-        Some(state) => DbState::into_inner(state),
-        None => {
-            return Err(error_with_log_id(
-                format!("group {} not found", group_id),
-                "Group not found",
-                StatusCode::NOT_FOUND,
-                log::Level::Warn,
-            ));
-        }
-    };
+    let group = DbState::into_inner(found_or_not_found(group_state, GROUP_NOT_FOUND)?);
+    ensure_admin_sees_project(&data.db, &admin, group.project_id, GROUP_NOT_FOUND).await?;
 
     // Get project details
     let project_state = projects_repository::get_by_id(&data.db, group.project_id)
@@ -1,24 +1,60 @@
 use crate::app_data::AppData;
-use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::common::access::{ensure_admin_sees_project, found_or_not_found};
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::repositories::groups_repository::{LeadershipTransfer, MemberRemoval};
 use crate::database::repositories::{
-    groups_repository, projects_repository, student_deliverable_selections_repository,
-    students_repository,
+    audit_log_repository, groups_repository, projects_repository,
+    student_deliverable_selections_repository, students_repository,
 };
 use crate::jwt::get_user::LoggedUser;
+use crate::mail::Mailer;
+use crate::models::audit_log::{
+    AuditLog, ADMIN_GROUP_MEMBER_REMOVED, GROUP_LEADERSHIP_TRANSFERRED,
+};
+use crate::models::group::Group;
 use crate::models::group_member::GroupMember;
+use crate::models::student::Student;
 use crate::models::student_role::AvailableStudentRole;
 use actix_web::http::StatusCode;
-use actix_web::web::{Data, Json, Path};
+use actix_web::web::{Bytes, Data, Json, Path};
 use actix_web::{HttpMessage, HttpRequest, HttpResponse};
 use chrono::Utc;
+use log::error;
 use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
 use utoipa::ToSchema;
 use welds::state::DbState;
 
+const GROUP_NOT_FOUND: &str = "Group not found";
+
+/// Longest reason accepted for a removal or a leadership transfer
+const MAX_REASON_LEN: usize = 500;
+
+const REMOVED_NOTICE: &str = "you were removed from the group";
+const DEMOTED_NOTICE: &str = "you are no longer its leader";
+const PROMOTED_NOTICE: &str = "you are now its leader";
+
 #[derive(Debug, Deserialize, ToSchema)]
 pub(crate) struct TransferLeadershipRequest {
     pub new_leader_student_id: i32,
     pub remove_old_leader: bool,
+    /// Why the leadership changes, kept in the audit log
+    #[schema(example = "The leader asked to step down")]
+    pub reason: Option<String>,
+    /// Email the old and the new leader about the change, with the reason if any
+    #[serde(default)]
+    pub notify_student: bool,
+}
+
+/// Optional body of a member removal
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub(crate) struct RemoveMemberRequest {
+    /// Why the member is removed, kept in the audit log
+    #[schema(example = "Moved to another group on request")]
+    pub reason: Option<String>,
+    /// Email the removed student about the removal, with the reason if any
+    #[serde(default)]
+    pub notify_student: bool,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -56,11 +92,73 @@ pub(crate) struct LeaderChangeInfo {
     pub status: String,
 }
 
+/// Reads the body of a removal, an empty one asks for neither a reason nor an email
+fn parse_removal(body: &[u8]) -> Result<RemoveMemberRequest, JsonError> {
+    if body.iter().all(u8::is_ascii_whitespace) {
+        return Ok(RemoveMemberRequest::default());
+    }
+    serde_json::from_slice(body)
+        .map_err(|e| format!("Invalid request body: {}", e).to_json_error(StatusCode::BAD_REQUEST))
+}
+
+/// Trims the reason of a group change, a blank one is dropped
+fn clean_reason(reason: Option<&str>) -> Result<Option<String>, JsonError> {
+    let Some(reason) = reason.map(str::trim).filter(|r| !r.is_empty()) else {
+        return Ok(None);
+    };
+    if reason.chars().count() > MAX_REASON_LEN {
+        return Err(
+            format!("Reason cannot exceed {} characters", MAX_REASON_LEN)
+                .to_json_error(StatusCode::BAD_REQUEST),
+        );
+    }
+    Ok(Some(reason.to_string()))
+}
+
+/// Audit log details of a group change, followed by its reason if any
+fn change_details(summary: String, reason: Option<&str>) -> String {
+    match reason {
+        Some(reason) => format!("{}, reason: {}", summary, reason),
+        None => summary,
+    }
+}
+
+/// Audit log entry of the removal of `student_id` from `group` by an admin
+fn removal_entry(admin_id: i32, group: &Group, student_id: i32, reason: Option<&str>) -> AuditLog {
+    AuditLog::by_admin(admin_id, ADMIN_GROUP_MEMBER_REMOVED)
+        .project(group.project_id)
+        .target("student", student_id)
+        .details(change_details(format!("group {}", group.group_id), reason))
+}
+
+/// Emails a student about a change to their group in the background
+///
+/// A failed email is only logged, the change already happened.
+fn queue_change_notice(
+    mailer: Mailer, student: Student, group_name: String, change: &'static str,
+    reason: Option<String>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let name = format!("{} {}", student.first_name, student.last_name);
+        if let Err(e) = mailer
+            .send_group_change_notice(student.email, name, &group_name, change, reason.as_deref())
+            .await
+        {
+            error!(
+                "failed to tell student {} about a change to group {}: {}",
+                student.student_id, group_name, e
+            );
+        }
+    })
+}
+
 #[utoipa::path(
     delete,
     path = "/v1/admins/groups/{group_id}/members/{student_id}",
+    request_body(content = Option<RemoveMemberRequest>, description = "Optional reason of the removal"),
     responses(
         (status = 200, description = "Member removed successfully", body = AdminMemberResponse),
+        (status = 400, description = "Invalid request body", body = JsonError),
         (status = 401, description = "Authentication required", body = JsonError),
         (status = 404, description = "Group or member not found", body = JsonError),
         (status = 500, description = "Internal server error", body = JsonError)
@@ -72,15 +170,17 @@ pub(crate) struct LeaderChangeInfo {
 ///
 /// This endpoint allows admins and coordinators to remove any member from a group,
 /// including the Group Leader. Must delete the student's deliverable selection when removed.
+/// The removal is recorded in the audit log with the reason given, and the student is
+/// emailed about it when `notify_student` is set.
 #[actix_web_grants::protect(any(
     "ROLE_ADMIN_ROOT",
     "ROLE_ADMIN_PROFESSOR",
     "ROLE_ADMIN_COORDINATOR"
 ))]
 pub(super) async fn remove_member(
-    req: HttpRequest, path: Path<(i32, i32)>, data: Data<AppData>,
+    req: HttpRequest, path: Path<(i32, i32)>, body: Bytes, data: Data<AppData>,
 ) -> Result<HttpResponse, JsonError> {
-    let _admin = match req.extensions().get_admin() {
+    let admin = match req.extensions().get_admin() {
         Ok(admin) => admin,
         Err(_) => {
             return Err(error_with_log_id(
@@ -93,6 +193,8 @@ pub(super) async fn remove_member(
     };
 
     let (group_id, student_id) = path.into_inner();
+    let removal_request = parse_removal(&body)?;
+    let reason = clean_reason(removal_request.reason.as_deref())?;
 
     // Verify the group exists
     let group_state = groups_repository::get_by_id(&data.db, group_id)
@@ -106,42 +208,25 @@ pub(super) async fn remove_member(
             )
         })?;
 
-    let group = match group_state {
-        Some(state) => DbState::into_inner(state),
-        None => {
-            return Err(error_with_log_id(
-                format!("group {} not found", group_id),
-                "Group not found",
-                StatusCode::NOT_FOUND,
-                log::Level::Warn,
-            ));
-        }
-    };
+    let group = DbState::into_inner(found_or_not_found(group_state, GROUP_NOT_FOUND)?);
+    ensure_admin_sees_project(&data.db, &admin, group.project_id, GROUP_NOT_FOUND).await?;
 
-    // Find the group member
-    let members = groups_repository::get_group_members(&data.db, group_id)
+    // Remove the member while holding the group lock, so a concurrent leadership
+    // transfer cannot promote the student being removed
+    let removal = groups_repository::remove_member_locked(&data.db, group_id, student_id, None)
         .await
         .map_err(|e| {
             error_with_log_id(
-                format!("unable to find group members: {}", e),
+                format!("unable to remove member from group: {}", e),
                 "Database error",
                 StatusCode::INTERNAL_SERVER_ERROR,
                 log::Level::Error,
             )
         })?;
 
-    let mut found_member = None;
-    for member_state in members {
-        let member_data = DbState::into_inner(member_state);
-        if member_data.student_id == student_id {
-            found_member = Some(member_data);
-            break;
-        }
-    }
-
-    let member = match found_member {
-        Some(member) => member,
-        None => {
+    let member = match removal {
+        MemberRemoval::Removed(member) => member,
+        _ => {
             return Ok(HttpResponse::Ok().json(AdminMemberResponse {
                 success: false,
                 message: "Member not found in this group".to_string(),
@@ -150,6 +235,23 @@ pub(super) async fn remove_member(
         }
     };
 
+    // Delete the student's deliverable selection for this project (MANDATORY - Q4)
+    if let Err(e) = student_deliverable_selections_repository::delete_by_student_and_project(
+        &data.db,
+        student_id,
+        group.project_id,
+    )
+    .await
+    {
+        // Log the error but don't fail the operation - the member is already removed
+        log::warn!(
+            "Failed to delete deliverable selection for student {} in project {}: {}",
+            student_id,
+            group.project_id,
+            e
+        );
+    }
+
     // Get student details for response
     let student_state = students_repository::get_by_id(&data.db, student_id)
         .await
@@ -174,53 +276,38 @@ pub(super) async fn remove_member(
         }
     };
 
-    // Delete the student's deliverable selection for this project (MANDATORY - Q4)
-    if let Err(e) = student_deliverable_selections_repository::delete_by_student_and_project(
+    audit_log_repository::record_or_warn(
         &data.db,
-        student_id,
-        group.project_id,
+        removal_entry(admin.admin_id, &group, student_id, reason.as_deref()),
     )
-    .await
-    {
-        // Log the error but don't fail the operation - the member should still be removed
-        log::warn!(
-            "Failed to delete deliverable selection for student {} in project {}: {}",
-            student_id,
-            group.project_id,
-            e
+    .await;
+
+    if removal_request.notify_student {
+        queue_change_notice(
+            data.mailer.clone(),
+            student.clone(),
+            group.name.clone(),
+            REMOVED_NOTICE,
+            reason,
         );
     }
 
-    // Remove the member
-    match GroupMember::where_col(|gm| gm.group_member_id.equal(member.group_member_id))
-        .delete(&data.db)
-        .await
-    {
-        Ok(_) => {
-            let role_name = if member.student_role_id == AvailableStudentRole::GroupLeader as i32 {
-                "Group Leader"
-            } else {
-                "Member"
-            };
-
-            Ok(HttpResponse::Ok().json(AdminMemberResponse {
-                success: true,
-                message: "Member removed successfully from the group".to_string(),
-                member: Some(AdminMemberInfo {
-                    student_id: student.student_id,
-                    name: format!("{} {}", student.first_name, student.last_name),
-                    email: student.email,
-                    role: role_name.to_string(),
-                }),
-            }))
-        }
-        Err(e) => Err(error_with_log_id(
-            format!("unable to remove member from group: {}", e),
-            "Database error",
-            StatusCode::INTERNAL_SERVER_ERROR,
-            log::Level::Error,
-        )),
-    }
+    let role_name = if member.student_role_id == AvailableStudentRole::GroupLeader as i32 {
+        "Group Leader"
+    } else {
+        "Member"
+    };
+
+    Ok(HttpResponse::Ok().json(AdminMemberResponse {
+        success: true,
+        message: "Member removed successfully from the group".to_string(),
+        member: Some(AdminMemberInfo {
+            student_id: student.student_id,
+            name: format!("{} {}", student.first_name, student.last_name),
+            email: student.email,
+            role: role_name.to_string(),
+        }),
+    }))
 }
 
 #[utoipa::path(
@@ -240,7 +327,9 @@ pub(super) async fn remove_member(
 /// Transfer group leadership (Admin/Coordinator)
 ///
 /// This endpoint allows admins and coordinators to change the Group Leader of a group.
-/// Can optionally remove the old leader or demote them to member.
+/// Can optionally remove the old leader or demote them to member. The transfer is recorded
+/// in the audit log with the reason given, and both leaders are emailed about it when
+/// `notify_student` is set.
 #[actix_web_grants::protect(any(
     "ROLE_ADMIN_ROOT",
     "ROLE_ADMIN_PROFESSOR",
@@ -249,7 +338,7 @@ pub(super) async fn remove_member(
 pub(super) async fn transfer_leadership(
     req: HttpRequest, path: Path<i32>, body: Json<TransferLeadershipRequest>, data: Data<AppData>,
 ) -> Result<HttpResponse, JsonError> {
-    let _admin = match req.extensions().get_admin() {
+    let admin = match req.extensions().get_admin() {
         Ok(admin) => admin,
         Err(_) => {
             return Err(error_with_log_id(
@@ -262,6 +351,7 @@ pub(super) async fn transfer_leadership(
     };
 
     let group_id = path.into_inner();
+    let reason = clean_reason(body.reason.as_deref())?;
 
     // Verify the group exists
     let group_state = groups_repository::get_by_id(&data.db, group_id)
@@ -275,46 +365,33 @@ pub(super) async fn transfer_leadership(
             )
         })?;
 
-    let _group = match group_state {
-        Some(state) => DbState::into_inner(state),
-        None => {
-            return Err(error_with_log_id(
-                format!("group {} not found", group_id),
-                "Group not found",
-                StatusCode::NOT_FOUND,
-                log::Level::Warn,
-            ));
-        }
-    };
-
-    // Get all group members
-    let members = groups_repository::get_group_members(&data.db, group_id)
-        .await
-        .map_err(|e| {
-            error_with_log_id(
-                format!("unable to fetch group members: {}", e),
-                "Database error",
-                StatusCode::INTERNAL_SERVER_ERROR,
-                log::Level::Error,
-            )
-        })?;
-
-    // Find current leader and new leader
-    let mut current_leader = None;
-    let mut new_leader = None;
-
-    for member_state in members {
-        let member = DbState::into_inner(member_state);
-        if member.student_role_id == AvailableStudentRole::GroupLeader as i32 {
-            current_leader = Some(member);
-        } else if member.student_id == body.new_leader_student_id {
-            new_leader = Some(member);
-        }
-    }
+    let group = DbState::into_inner(found_or_not_found(group_state, GROUP_NOT_FOUND)?);
+    ensure_admin_sees_project(&data.db, &admin, group.project_id, GROUP_NOT_FOUND).await?;
 
-    let current_leader = match current_leader {
-        Some(leader) => leader,
-        None => {
+    // Transfer while holding the group lock, so a concurrent removal cannot
+    // drop the new leader in between
+    let transfer = groups_repository::transfer_leadership_locked(
+        &data.db,
+        group_id,
+        body.new_leader_student_id,
+        body.remove_old_leader,
+    )
+    .await
+    .map_err(|e| {
+        error_with_log_id(
+            format!("unable to transfer leadership of group {}: {}", group_id, e),
+            "Database error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let (current_leader, new_leader) = match transfer {
+        LeadershipTransfer::Transferred {
+            old_leader,
+            new_leader,
+        } => (old_leader, new_leader),
+        LeadershipTransfer::NoLeader => {
             return Err(error_with_log_id(
                 "no current group leader found",
                 "Group has no leader",
@@ -322,11 +399,7 @@ pub(super) async fn transfer_leadership(
                 log::Level::Warn,
             ));
         }
-    };
-
-    let new_leader = match new_leader {
-        Some(leader) => leader,
-        None => {
+        LeadershipTransfer::NotMember => {
             return Err(error_with_log_id(
                 format!(
                     "student {} is not a member of this group",
@@ -386,86 +459,6 @@ pub(super) async fn transfer_leadership(
         }
     };
 
-    // Update roles in database
-    // Demote current leader to member (or remove if requested)
-    if body.remove_old_leader {
-        // Remove the old leader entirely
-        match GroupMember::where_col(|gm| gm.group_member_id.equal(current_leader.group_member_id))
-            .delete(&data.db)
-            .await
-        {
-            Ok(_) => {}
-            Err(e) => {
-                return Err(error_with_log_id(
-                    format!("unable to remove old leader: {}", e),
-                    "Database error",
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    log::Level::Error,
-                ));
-            }
-        }
-    } else {
-        // Demote to member - we need to get the current leader as DbState first
-        let current_leader_states =
-            GroupMember::where_col(|gm| gm.group_member_id.equal(current_leader.group_member_id))
-                .run(&data.db)
-                .await
-                .map_err(|e| {
-                    error_with_log_id(
-                        format!("unable to fetch current leader: {}", e),
-                        "Database error",
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        log::Level::Error,
-                    )
-                })?;
-
-        if let Some(mut current_leader_state) = current_leader_states.into_iter().next() {
-            current_leader_state.as_mut().student_role_id = AvailableStudentRole::Member as i32;
-
-            match current_leader_state.save(&data.db).await {
-                Ok(_) => {}
-                Err(e) => {
-                    return Err(error_with_log_id(
-                        format!("unable to demote old leader: {}", e),
-                        "Database error",
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        log::Level::Error,
-                    ));
-                }
-            }
-        }
-    }
-
-    // Promote new leader - we need to get the new leader as DbState first
-    let new_leader_states =
-        GroupMember::where_col(|gm| gm.group_member_id.equal(new_leader.group_member_id))
-            .run(&data.db)
-            .await
-            .map_err(|e| {
-                error_with_log_id(
-                    format!("unable to fetch new leader: {}", e),
-                    "Database error",
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    log::Level::Error,
-                )
-            })?;
-
-    if let Some(mut new_leader_state) = new_leader_states.into_iter().next() {
-        new_leader_state.as_mut().student_role_id = AvailableStudentRole::GroupLeader as i32;
-
-        match new_leader_state.save(&data.db).await {
-            Ok(_) => {}
-            Err(e) => {
-                return Err(error_with_log_id(
-                    format!("unable to promote new leader: {}", e),
-                    "Database error",
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    log::Level::Error,
-                ));
-            }
-        }
-    }
-
     let old_leader_info = if body.remove_old_leader {
         Some(LeaderChangeInfo {
             student_id: current_leader.student_id,
@@ -492,6 +485,49 @@ pub(super) async fn transfer_leadership(
         status: "promoted_to_leader".to_string(),
     };
 
+    audit_log_repository::record_or_warn(
+        &data.db,
+        AuditLog::by_admin(admin.admin_id, GROUP_LEADERSHIP_TRANSFERRED)
+            .project(group.project_id)
+            .target("group", group_id)
+            .details(change_details(
+                format!(
+                    "leader {} -> {}, old leader {}",
+                    current_leader.student_id,
+                    new_leader.student_id,
+                    if body.remove_old_leader {
+                        "removed"
+                    } else {
+                        "demoted"
+                    }
+                ),
+                reason.as_deref(),
+            )),
+    )
+    .await;
+
+    if body.notify_student {
+        let old_leader_change = if body.remove_old_leader {
+            REMOVED_NOTICE
+        } else {
+            DEMOTED_NOTICE
+        };
+        queue_change_notice(
+            data.mailer.clone(),
+            current_student,
+            group.name.clone(),
+            old_leader_change,
+            reason.clone(),
+        );
+        queue_change_notice(
+            data.mailer.clone(),
+            new_student,
+            group.name,
+            PROMOTED_NOTICE,
+            reason,
+        );
+    }
+
     Ok(HttpResponse::Ok().json(TransferLeadershipResponse {
         message: "Group leader updated successfully".to_string(),
         old_leader: old_leader_info,
@@ -525,7 +561,7 @@ pub(super) async fn transfer_leadership(
 pub(super) async fn add_member(
     req: HttpRequest, path: Path<i32>, body: Json<AdminAddMemberRequest>, data: Data<AppData>,
 ) -> Result<HttpResponse, JsonError> {
-    let _admin = match req.extensions().get_admin() {
+    let admin = match req.extensions().get_admin() {
         Ok(admin) => admin,
         Err(_) => {
             return Err(error_with_log_id(
@@ -551,17 +587,8 @@ pub(super) async fn add_member(
             )
         })?;
 
-    let group = match group_state {
-        Some(state) => DbState::into_inner(state),
-        None => {
-            return Err(error_with_log_id(
-                format!("group {} not found", group_id),
-                "Group not found",
-                StatusCode::NOT_FOUND,
-                log::Level::Warn,
-            ));
-        }
-    };
+    let group = DbState::into_inner(found_or_not_found(group_state, GROUP_NOT_FOUND)?);
+    ensure_admin_sees_project(&data.db, &admin, group.project_id, GROUP_NOT_FOUND).await?;
 
     // Find the student by email
     let student_state = students_repository::get_by_email(&data.db, &body.student_email)
@@ -646,30 +673,6 @@ pub(super) async fn add_member(
         }
     };
 
-    // Check group size limit
-    let current_member_count = groups_repository::count_members(&data.db, group_id)
-        .await
-        .map_err(|e| {
-            error_with_log_id(
-                format!("unable to count members for group {}: {}", group_id, e),
-                "Database error",
-                StatusCode::INTERNAL_SERVER_ERROR,
-                log::Level::Error,
-            )
-        })?;
-
-    if current_member_count >= project.max_group_size {
-        return Err(error_with_log_id(
-            format!(
-                "group has reached the maximum size of {} members",
-                project.max_group_size
-            ),
-            "Group size limit exceeded",
-            StatusCode::BAD_REQUEST,
-            log::Level::Warn,
-        ));
-    }
-
     // If adding as Group Leader, check if there's already a leader
     if body.role_id == AvailableStudentRole::GroupLeader as i32 {
         let is_leader = groups_repository::is_group_leader(&data.db, student.student_id, group_id)
@@ -693,39 +696,155 @@ pub(super) async fn add_member(
         }
     }
 
-    // Add the student as a group member
-    let mut member_state = DbState::new_uncreated(GroupMember {
+    // Add the student as a group member, checking the size limit on the locked members
+    let group_member = GroupMember {
         group_member_id: 0,
         group_id,
         student_id: student.student_id,
         student_role_id: body.role_id,
         joined_at: Utc::now(),
-    });
-
-    match member_state.save(&data.db).await {
-        Ok(_) => {
-            let role_name = if body.role_id == AvailableStudentRole::GroupLeader as i32 {
-                "Group Leader"
-            } else {
-                "Member"
-            };
-
-            Ok(HttpResponse::Created().json(AdminMemberResponse {
-                success: true,
-                message: "Member added successfully".to_string(),
-                member: Some(AdminMemberInfo {
-                    student_id: student.student_id,
-                    name: format!("{} {}", student.first_name, student.last_name),
-                    email: student.email,
-                    role: role_name.to_string(),
-                }),
-            }))
+    };
+
+    let added = groups_repository::add_members_locked(&data.db, group_id, |members| {
+        if members.len() as i32 >= project.max_group_size {
+            (Vec::new(), false)
+        } else {
+            (vec![group_member], true)
         }
-        Err(e) => Err(error_with_log_id(
+    })
+    .await
+    .map_err(|e| {
+        error_with_log_id(
             format!("unable to add student to group: {}", e),
             "Database error",
             StatusCode::INTERNAL_SERVER_ERROR,
             log::Level::Error,
-        )),
+        )
+    })?;
+
+    if !added {
+        return Err(error_with_log_id(
+            format!(
+                "group has reached the maximum size of {} members",
+                project.max_group_size
+            ),
+            "Group size limit exceeded",
+            StatusCode::BAD_REQUEST,
+            log::Level::Warn,
+        ));
+    }
+
+    let role_name = if body.role_id == AvailableStudentRole::GroupLeader as i32 {
+        "Group Leader"
+    } else {
+        "Member"
+    };
+
+    Ok(HttpResponse::Created().json(AdminMemberResponse {
+        success: true,
+        message: "Member added successfully".to_string(),
+        member: Some(AdminMemberInfo {
+            student_id: student.student_id,
+            name: format!("{} {}", student.first_name, student.last_name),
+            email: student.email,
+            role: role_name.to_string(),
+        }),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TEST_FRONTEND_URL;
+    use actix_web::ResponseError;
+
+    fn group() -> Group {
+        Group {
+            group_id: 3,
+            project_id: 1,
+            name: "Rustaceans".to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn student() -> Student {
+        Student {
+            student_id: 5,
+            first_name: "Mario".to_string(),
+            last_name: "Rossi".to_string(),
+            email: "mario.rossi@studenti.unitn.it".to_string(),
+            university_id: 123456,
+            password_hash: String::new(),
+            is_pending: false,
+            sessions_revoked_at: None,
+        }
+    }
+
+    #[test]
+    fn test_removal_body_is_optional() {
+        let empty = parse_removal(b"").unwrap();
+        assert_eq!(empty.reason, None);
+        assert!(!empty.notify_student);
+
+        let given = parse_removal(br#"{"reason": "Moved", "notify_student": true}"#).unwrap();
+        assert_eq!(given.reason.as_deref(), Some("Moved"));
+        assert!(given.notify_student);
+
+        let err = parse_removal(b"{not json").unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_reason_is_persisted_to_the_audit_log() {
+        let reason = clean_reason(Some("  Moved to another group on request ")).unwrap();
+
+        let entry = removal_entry(7, &group(), 5, reason.as_deref());
+
+        assert_eq!(entry.action, ADMIN_GROUP_MEMBER_REMOVED);
+        assert_eq!(entry.actor_admin_id, Some(7));
+        assert_eq!(entry.project_id, Some(1));
+        assert_eq!(entry.target_id, Some(5));
+        assert_eq!(
+            entry.details.as_deref(),
+            Some("group 3, reason: Moved to another group on request")
+        );
+        assert_eq!(
+            removal_entry(7, &group(), 5, None).details.as_deref(),
+            Some("group 3")
+        );
+    }
+
+    #[test]
+    fn test_blank_or_long_reasons() {
+        assert_eq!(clean_reason(Some("   ")).unwrap(), None);
+        assert_eq!(clean_reason(None).unwrap(), None);
+        let long = "a".repeat(MAX_REASON_LEN + 1);
+        assert_eq!(
+            clean_reason(Some(&long)).unwrap_err().status_code(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_notice_is_emailed_with_the_reason() {
+        let mailer =
+            Mailer::in_memory("Test Sender", "noreply@test.com", TEST_FRONTEND_URL).unwrap();
+
+        queue_change_notice(
+            mailer.clone(),
+            student(),
+            "Rustaceans".to_string(),
+            REMOVED_NOTICE,
+            Some("Moved to another group on request".to_string()),
+        )
+        .await
+        .unwrap();
+
+        let sent = mailer.sent().await;
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, vec!["mario.rossi@studenti.unitn.it".to_string()]);
+        assert!(sent[0].1.contains("Your group Rustaceans changed"));
+        assert!(sent[0].1.contains("Moved to another group on request"));
+        assert!(sent[0].1.contains(REMOVED_NOTICE));
     }
 }
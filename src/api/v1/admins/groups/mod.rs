@@ -1,13 +1,17 @@
+use crate::api::v1::admins::groups::batch_members::add_members_batch;
 use crate::api::v1::admins::groups::complaints::get_group_complaints;
 use crate::api::v1::admins::groups::details::get_group_details;
 use crate::api::v1::admins::groups::members::{add_member, remove_member, transfer_leadership};
 use crate::api::v1::admins::groups::read::get_project_groups;
+use crate::api::v1::admins::groups::split::split_group;
 use actix_web::{web, Scope};
 
+pub(crate) mod batch_members;
 pub(crate) mod complaints;
 pub(crate) mod details;
 pub(crate) mod members;
 pub(crate) mod read;
+pub(crate) mod split;
 
 pub(super) fn groups_scope() -> Scope {
     web::scope("/groups")
@@ -23,4 +27,9 @@ pub(super) fn groups_scope() -> Scope {
         )
         .route("/{group_id}/leader", web::patch().to(transfer_leadership))
         .route("/{group_id}/members", web::post().to(add_member))
+        .route("/{group_id}/split", web::post().to(split_group))
+        .route(
+            "/{group_id}/members/batch",
+            web::post().to(add_members_batch),
+        )
 }
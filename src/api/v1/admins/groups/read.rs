@@ -1,4 +1,5 @@
 use crate::app_data::AppData;
+use crate::common::access::{ensure_admin_sees_project, found_or_not_found};
 use crate::common::json_error::{error_with_log_id, JsonError};
 use crate::database::repositories::{
     group_deliverable_selections_repository, group_deliverables_repository, groups_repository,
@@ -8,11 +9,12 @@ use crate::jwt::get_user::LoggedUser;
 use actix_web::http::StatusCode;
 use actix_web::web::{Data, Path};
 use actix_web::{HttpMessage, HttpRequest, HttpResponse};
-use chrono::Utc;
 use serde::Serialize;
 use utoipa::ToSchema;
 use welds::state::DbState;
 
+const PROJECT_NOT_FOUND: &str = "Project not found";
+
 #[derive(Debug, Serialize, ToSchema)]
 pub(crate) struct ProjectGroupsResponse {
     pub groups: Vec<GroupInfo>,
@@ -66,7 +68,7 @@ pub(crate) struct DeliverableInfo {
 pub(super) async fn get_project_groups(
     req: HttpRequest, path: Path<i32>, data: Data<AppData>,
 ) -> Result<HttpResponse, JsonError> {
-    let _admin = match req.extensions().get_admin() {
+    let admin = match req.extensions().get_admin() {
         Ok(admin) => admin,
         Err(_) => {
             return Err(error_with_log_id(
@@ -92,17 +94,8 @@ pub(super) async fn get_project_groups(
             )
         })?;
 
-    let project = match project_state {
-        Some(state) => DbState::into_inner(state),
-        None => {
-            return Err(error_with_log_id(
-                format!("project with id {} not found", project_id),
-                "Project not found",
-                StatusCode::NOT_FOUND,
-                log::Level::Warn,
-            ));
-        }
-    };
+    let project = DbState::into_inner(found_or_not_found(project_state, PROJECT_NOT_FOUND)?);
+    ensure_admin_sees_project(&data.db, &admin, project_id, PROJECT_NOT_FOUND).await?;
 
     // Get all groups for this project
     let groups = groups_repository::get_by_project_id(&data.db, project_id)
@@ -225,7 +218,7 @@ pub(super) async fn get_project_groups(
 
         // Check if time has expired for deliverable selection
         let time_expired = if let Some(deadline) = project.deliverable_selection_deadline {
-            deliverable_selected.is_none() && Utc::now() > deadline
+            deliverable_selected.is_none() && data.clock.now() > deadline
         } else {
             false
         };
@@ -0,0 +1,210 @@
+use crate::app_data::AppData;
+use crate::common::access::{ensure_admin_sees_project, found_or_not_found};
+use crate::common::created::created;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::repositories::groups_repository::GroupSplit;
+use crate::database::repositories::{audit_log_repository, groups_repository};
+use crate::jwt::get_user::LoggedUser;
+use crate::models::audit_log::{AuditLog, GROUP_SPLIT};
+use crate::models::group::Group;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use welds::state::DbState;
+
+const GROUP_NOT_FOUND: &str = "Group not found";
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct SplitGroupRequest {
+    /// Members moving to the new group, by student id
+    #[schema(example = json!([14, 15]))]
+    pub student_ids: Vec<i32>,
+    /// Name of the new group, unique in the project
+    #[schema(example = "Rusty Robots B")]
+    pub name: String,
+    /// Leader of the new group, one of the moved members
+    #[schema(example = 14)]
+    pub leader_student_id: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct SplitGroupResponse {
+    /// Group the members were moved out of
+    pub group_id: i32,
+    /// Group created for the moved members
+    pub new_group_id: i32,
+    pub name: String,
+    pub project_id: i32,
+    pub leader_student_id: i32,
+    /// Members of the new group, its leader first
+    pub student_ids: Vec<i32>,
+}
+
+/// Error of a split the members of the group do not allow
+fn split_error(split: &GroupSplit) -> Option<JsonError> {
+    let message = match split {
+        GroupSplit::Split { .. } => return None,
+        GroupSplit::NotMember(student_id) => {
+            format!("Student {} is not a member of this group", student_id)
+        }
+        GroupSplit::LeaderNotMoved => {
+            "The leader of the new group must be one of the moved members".to_string()
+        }
+        GroupSplit::EmptyGroup => {
+            "Both groups must keep at least one member after the split".to_string()
+        }
+        GroupSplit::Leaderless => {
+            "The group leader cannot be moved, the group would be left without a leader".to_string()
+        }
+    };
+    Some(message.to_json_error(StatusCode::BAD_REQUEST))
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/admins/groups/{group_id}/split",
+    request_body = SplitGroupRequest,
+    responses(
+        (status = 201, description = "Group split, the new group is in the body", body = SplitGroupResponse),
+        (status = 400, description = "The split would leave a group empty or without a leader, or moves a student who is not a member", body = JsonError),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 404, description = "Group not found", body = JsonError),
+        (status = 409, description = "The name is already used in the project", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Admin Groups management",
+)]
+/// Split a group in two (Admin/Coordinator)
+///
+/// The listed members move to a new group of the same project, led by the given student.
+/// The current leader stays, and both groups must keep at least one member. Members are
+/// moved in one transaction holding the group lock, so the split never leaves a group
+/// half done. The new group starts without a deliverable selection. The split is recorded
+/// in the audit log.
+#[actix_web_grants::protect(any(
+    "ROLE_ADMIN_ROOT",
+    "ROLE_ADMIN_PROFESSOR",
+    "ROLE_ADMIN_COORDINATOR"
+))]
+pub(super) async fn split_group(
+    req: HttpRequest, path: Path<i32>, body: Json<SplitGroupRequest>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let group_id = path.into_inner();
+    let name = body.name.trim();
+    if name.is_empty() {
+        return Err("Group name cannot be empty".to_json_error(StatusCode::BAD_REQUEST));
+    }
+
+    let db_error = |what: &str, e: welds::WeldsError| {
+        error_with_log_id(
+            format!("unable to {} of group {}: {}", what, group_id, e),
+            "Database error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    };
+
+    let group_state = groups_repository::get_by_id(&data.db, group_id)
+        .await
+        .map_err(|e| db_error("fetch", e))?;
+    let group = DbState::into_inner(found_or_not_found(group_state, GROUP_NOT_FOUND)?);
+    ensure_admin_sees_project(&data.db, &admin, group.project_id, GROUP_NOT_FOUND).await?;
+
+    let exists = groups_repository::name_exists_for_project(&data.db, group.project_id, name)
+        .await
+        .map_err(|e| db_error("check the new name", e))?;
+    if exists {
+        return Err("A group with this name already exists in the project"
+            .to_json_error(StatusCode::CONFLICT));
+    }
+
+    let new_group = Group {
+        group_id: 0,
+        project_id: group.project_id,
+        name: name.to_string(),
+        created_at: Utc::now(),
+    };
+    let split = groups_repository::split_group_locked(
+        &data.db,
+        group_id,
+        new_group,
+        &body.student_ids,
+        body.leader_student_id,
+    )
+    .await
+    .map_err(|e| db_error("split", e))?;
+
+    if let Some(error) = split_error(&split) {
+        return Err(error);
+    }
+    let GroupSplit::Split {
+        group: new_group,
+        moved,
+    } = split
+    else {
+        unreachable!("every other outcome is an error");
+    };
+    let student_ids: Vec<i32> = moved.iter().map(|m| m.student_id).collect();
+
+    audit_log_repository::record_or_warn(
+        &data.db,
+        AuditLog::by_admin(admin.admin_id, GROUP_SPLIT)
+            .project(group.project_id)
+            .target("group", group_id)
+            .details(format!(
+                "students {:?} moved to group {}",
+                student_ids, new_group.group_id
+            )),
+    )
+    .await;
+
+    Ok(created(
+        "/v1/admins/groups",
+        new_group.group_id,
+        &SplitGroupResponse {
+            group_id,
+            new_group_id: new_group.group_id,
+            name: new_group.name,
+            project_id: new_group.project_id,
+            leader_student_id: body.leader_student_id,
+            student_ids,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::ResponseError;
+
+    #[test]
+    fn test_only_accepted_splits_succeed() {
+        let split = GroupSplit::Split {
+            group: Group {
+                group_id: 8,
+                project_id: 3,
+                name: "Rusty Robots B".to_string(),
+                created_at: Utc::now(),
+            },
+            moved: Vec::new(),
+        };
+        assert!(split_error(&split).is_none());
+
+        let empty: JsonError = split_error(&GroupSplit::EmptyGroup).unwrap();
+        assert_eq!(empty.status_code(), StatusCode::BAD_REQUEST);
+        assert!(split_error(&GroupSplit::Leaderless).is_some());
+    }
+}
@@ -0,0 +1,151 @@
+use crate::app_data::AppData;
+use crate::common::client_ip::IpRanges;
+use crate::common::json_error::{error_with_log_id, ToJsonError};
+use actix_web::body::EitherBody;
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::StatusCode;
+use actix_web::web::Data;
+use actix_web::Error;
+use futures_util::future::{ready, LocalBoxFuture, Ready};
+use log::warn;
+use std::net::IpAddr;
+use std::rc::Rc;
+
+/// Whether a client can reach the admin routes, an empty allowlist lets everyone in
+fn is_allowed(allowlist: &IpRanges, client: Option<IpAddr>) -> bool {
+    allowlist.is_empty() || client.is_some_and(|ip| allowlist.contains(ip))
+}
+
+/// Rejects requests to the wrapped scope from clients outside `admin_ip_allowlist`
+///
+/// The client address is resolved through the trusted proxies, so it must be configured
+/// alongside `trusted_proxies` when the server runs behind a reverse proxy.
+pub(crate) struct AdminIpAllowlist;
+
+impl<S, B> Transform<S, ServiceRequest> for AdminIpAllowlist
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = AdminIpAllowlistService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AdminIpAllowlistService {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub(crate) struct AdminIpAllowlistService<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for AdminIpAllowlistService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        Box::pin(async move {
+            let Some(data) = req.app_data::<Data<AppData>>().cloned() else {
+                let error = error_with_log_id(
+                    "AdminIpAllowlist used without application data",
+                    "Internal server error",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                );
+                return Ok(req.error_response(error).map_into_right_body());
+            };
+
+            let client = data.trusted_proxies.service_client_ip(&req);
+            if !is_allowed(&data.admin_ip_allowlist, client) {
+                warn!(
+                    "admin request from {} blocked by the ip allowlist: {} {}",
+                    client.map_or_else(|| "unknown address".to_string(), |ip| ip.to_string()),
+                    req.method(),
+                    req.path()
+                );
+                let error =
+                    "Access from this address is not allowed".to_json_error(StatusCode::FORBIDDEN);
+                return Ok(req.error_response(error).map_into_right_body());
+            }
+
+            service.call(req).await.map(|res| res.map_into_left_body())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_app_data;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::{web, App, HttpResponse};
+
+    async fn ok() -> HttpResponse {
+        HttpResponse::Ok().finish()
+    }
+
+    async fn status_from(peer: &str, uri: &str) -> StatusCode {
+        let mut data = create_test_app_data().await;
+        data.admin_ip_allowlist = IpRanges::parse(&["192.0.2.0/24".to_string()]).unwrap();
+
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(data))
+                .service(
+                    web::scope("/admins")
+                        .wrap(AdminIpAllowlist)
+                        .route("/me", web::get().to(ok)),
+                )
+                .route("/health", web::get().to(ok)),
+        )
+        .await;
+
+        let req = TestRequest::get()
+            .uri(uri)
+            .peer_addr(peer.parse().unwrap())
+            .to_request();
+        call_service(&app, req).await.status()
+    }
+
+    #[actix_web::test]
+    async fn test_allowed_ip_reaches_admin_routes() {
+        assert_eq!(
+            status_from("192.0.2.10:4000", "/admins/me").await,
+            StatusCode::OK
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_blocked_ip_is_forbidden_on_admin_routes_only() {
+        assert_eq!(
+            status_from("203.0.113.5:4000", "/admins/me").await,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            status_from("203.0.113.5:4000", "/health").await,
+            StatusCode::OK
+        );
+    }
+
+    #[test]
+    fn test_empty_allowlist_allows_everyone() {
+        assert!(is_allowed(&IpRanges::default(), None));
+        assert!(is_allowed(&IpRanges::default(), "203.0.113.5".parse().ok()));
+        let campus = IpRanges::parse(&["192.0.2.0/24".to_string()]).unwrap();
+        assert!(!is_allowed(&campus, None));
+    }
+}
@@ -0,0 +1,10 @@
+use crate::api::v1::admins::maintenance::orphans::{cleanup_orphans, get_orphans};
+use actix_web::{web, Scope};
+
+pub(crate) mod orphans;
+
+pub(super) fn maintenance_scope() -> Scope {
+    web::scope("/maintenance")
+        .route("/orphans", web::get().to(get_orphans))
+        .route("/orphans/cleanup", web::post().to(cleanup_orphans))
+}
@@ -0,0 +1,219 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::database::repositories::orphans_repository::{Orphan, OrphanKind};
+use crate::database::repositories::{audit_log_repository, orphans_repository};
+use crate::jwt::get_user::LoggedUser;
+use crate::models::audit_log::{AuditLog, ORPHANS_CLEANED};
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Query};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct OrphanItem {
+    pub kind: OrphanKind,
+    /// Primary key of the row in the table of its kind
+    pub id: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ProjectOrphans {
+    pub project_id: i32,
+    pub orphans: Vec<OrphanItem>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct OrphansResponse {
+    /// Number of orphaned rows of every project
+    pub total: usize,
+    /// Projects with at least one orphaned row, by id
+    pub projects: Vec<ProjectOrphans>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct CleanupOrphansResponse {
+    /// Whether nothing was actually deleted
+    pub dry_run: bool,
+    /// Rows deleted, or that would be deleted on a dry run
+    pub removed: OrphansResponse,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub(crate) struct CleanupOrphansQuery {
+    /// Report what would be deleted without deleting it
+    pub dry_run: Option<bool>,
+}
+
+/// Groups `orphans` by project, keeping the order of each project's rows
+fn report(orphans: Vec<Orphan>) -> OrphansResponse {
+    let total = orphans.len();
+    let mut projects: BTreeMap<i32, Vec<OrphanItem>> = BTreeMap::new();
+    for orphan in orphans {
+        projects
+            .entry(orphan.project_id)
+            .or_default()
+            .push(OrphanItem {
+                kind: orphan.kind,
+                id: orphan.id,
+            });
+    }
+
+    OrphansResponse {
+        total,
+        projects: projects
+            .into_iter()
+            .map(|(project_id, orphans)| ProjectOrphans {
+                project_id,
+                orphans,
+            })
+            .collect(),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/admins/maintenance/orphans",
+    responses(
+        (status = 200, description = "Orphaned selections and links, by project", body = OrphansResponse),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Maintenance",
+)]
+/// Report orphaned selections and links (Root only)
+///
+/// Lists rows earlier changes left inconsistent: deliverable/component links across
+/// projects, group selections of another project's deliverable, implementation details of
+/// components no longer in the selected deliverable, and selections of students who left
+/// every group of the project. Nothing is changed.
+#[actix_web_grants::protect("ROLE_ADMIN_ROOT")]
+pub(super) async fn get_orphans(data: Data<AppData>) -> Result<HttpResponse, JsonError> {
+    let orphans = orphans_repository::find_all(&data.db).await.map_err(|e| {
+        error_with_log_id(
+            format!("unable to look for orphaned rows: {}", e),
+            "Failed to look for orphaned rows",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    Ok(HttpResponse::Ok().json(report(orphans)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/admins/maintenance/orphans/cleanup",
+    params(CleanupOrphansQuery),
+    responses(
+        (status = 200, description = "Orphaned links and implementation details deleted, or those that would be on a dry run", body = CleanupOrphansResponse),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Maintenance",
+)]
+/// Delete orphaned links and implementation details (Root only)
+///
+/// Deletes the links and implementation details reported by the orphans report in one
+/// transaction. Orphaned selections are only reported: deleting one would take the
+/// transactions bought for it along, and a student moving between groups briefly looks
+/// orphaned. With `dry_run=true` the transaction is rolled back, so the response shows
+/// exactly what would be deleted. An actual cleanup is recorded in the audit log.
+#[actix_web_grants::protect("ROLE_ADMIN_ROOT")]
+pub(super) async fn cleanup_orphans(
+    req: HttpRequest, query: Query<CleanupOrphansQuery>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+    let dry_run = query.dry_run.unwrap_or(false);
+
+    let removed = orphans_repository::delete_all(&data.db, dry_run)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to delete orphaned rows: {}", e),
+                "Failed to delete orphaned rows",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+    if !dry_run && !removed.is_empty() {
+        audit_log_repository::record_or_warn(
+            &data.db,
+            AuditLog::by_admin(admin.admin_id, ORPHANS_CLEANED)
+                .details(format!("{} orphaned rows", removed.len())),
+        )
+        .await;
+    }
+
+    Ok(HttpResponse::Ok().json(CleanupOrphansResponse {
+        dry_run,
+        removed: report(removed),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_orphaned_selection_is_reported_under_its_project() {
+        let orphans = vec![
+            Orphan {
+                kind: OrphanKind::GroupSelection,
+                id: 4,
+                project_id: 2,
+            },
+            Orphan {
+                kind: OrphanKind::StudentSelection,
+                id: 17,
+                project_id: 1,
+            },
+            Orphan {
+                kind: OrphanKind::StudentSelection,
+                id: 9,
+                project_id: 2,
+            },
+        ];
+
+        let body = serde_json::to_value(report(orphans)).unwrap();
+
+        assert_eq!(
+            body,
+            json!({
+                "total": 3,
+                "projects": [
+                    { "project_id": 1, "orphans": [{ "kind": "student_selection", "id": 17 }] },
+                    { "project_id": 2, "orphans": [
+                        { "kind": "group_selection", "id": 4 },
+                        { "kind": "student_selection", "id": 9 }
+                    ] }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_nothing_to_clean_is_an_empty_report() {
+        let response = CleanupOrphansResponse {
+            dry_run: true,
+            removed: report(Vec::new()),
+        };
+
+        assert_eq!(
+            serde_json::to_value(response).unwrap(),
+            json!({ "dry_run": true, "removed": { "total": 0, "projects": [] } })
+        );
+    }
+}
@@ -1,11 +1,15 @@
 use crate::api::v1::admins::auth::auth_scope;
 use crate::api::v1::admins::blacklist::blacklist_scope;
+use crate::api::v1::admins::complaints::complaints_scope;
+use crate::api::v1::admins::dev::dev_scope;
+use crate::api::v1::admins::emails::emails_scope;
 use crate::api::v1::admins::fairs::fairs_scope;
 use crate::api::v1::admins::group_deliverable_components::group_deliverable_components_scope;
 use crate::api::v1::admins::group_deliverable_selections::group_deliverable_selections_scope;
 use crate::api::v1::admins::group_deliverables::group_deliverables_scope;
 use crate::api::v1::admins::group_deliverables_and_components::group_deliverables_components_scope;
 use crate::api::v1::admins::groups::groups_scope;
+use crate::api::v1::admins::maintenance::maintenance_scope;
 use crate::api::v1::admins::oral_exam::oral_exam_scope;
 use crate::api::v1::admins::projects::projects_scope;
 use crate::api::v1::admins::security_codes::security_codes_scope;
@@ -13,18 +17,26 @@ use crate::api::v1::admins::student_deliverable_components::student_deliverable_
 use crate::api::v1::admins::student_deliverable_selections::student_deliverable_selections_scope;
 use crate::api::v1::admins::student_deliverables::student_deliverables_scope;
 use crate::api::v1::admins::student_deliverables_and_components::student_deliverables_components_scope;
-use crate::api::v1::admins::uploads::uploads_scope;
+use crate::api::v1::admins::students::students_scope;
+use crate::api::v1::admins::transactions::transactions_scope;
+use crate::api::v1::admins::uploads::{upload_links_scope, uploads_scope};
 use crate::api::v1::admins::users::users_scope;
+use crate::common::rate_limit::RateLimit;
 use actix_web::{web, Scope};
 
 pub(crate) mod auth;
 pub(crate) mod blacklist;
+pub(crate) mod complaints;
+pub(crate) mod dev;
+pub(crate) mod emails;
 pub(crate) mod fairs;
 pub(crate) mod group_deliverable_components;
 pub(crate) mod group_deliverable_selections;
 pub(crate) mod group_deliverables;
 pub(crate) mod group_deliverables_and_components;
 pub(crate) mod groups;
+pub(crate) mod ip_allowlist;
+pub(crate) mod maintenance;
 pub(crate) mod oral_exam;
 pub(crate) mod projects;
 pub(crate) mod security_codes;
@@ -32,18 +44,21 @@ pub(crate) mod student_deliverable_components;
 pub(crate) mod student_deliverable_selections;
 pub(crate) mod student_deliverables;
 pub(crate) mod student_deliverables_and_components;
+pub(crate) mod students;
+pub(crate) mod transactions;
 pub(crate) mod uploads;
 pub(crate) mod users;
 
 pub(super) fn admins_scope() -> Scope {
     web::scope("/admins")
-        .service(auth_scope())
+        .service(auth_scope().wrap(RateLimit))
         .service(users_scope())
         .service(projects_scope())
         .service(blacklist_scope())
         .service(security_codes_scope())
         .service(groups_scope())
         .service(fairs_scope())
+        .service(transactions_scope())
         .service(group_deliverable_components_scope())
         .service(group_deliverable_selections_scope())
         .service(group_deliverables_scope())
@@ -52,6 +67,132 @@ pub(super) fn admins_scope() -> Scope {
         .service(student_deliverable_selections_scope())
         .service(student_deliverables_scope())
         .service(student_deliverables_components_scope())
+        .service(students_scope())
         .service(uploads_scope())
+        .service(upload_links_scope())
         .service(oral_exam_scope())
+        .service(complaints_scope())
+        .service(emails_scope())
+        .service(maintenance_scope())
+        .service(dev_scope())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::doc::ApiDoc;
+    use crate::test_utils::create_test_app_data;
+    use actix_web::dev::ServiceRequest;
+    use actix_web::http::StatusCode;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::web::Data;
+    use actix_web::{App, Error};
+    use actix_web_grants::GrantsMiddleware;
+    use std::collections::HashSet;
+    use utoipa::OpenApi;
+
+    async fn student(_req: &ServiceRequest) -> Result<HashSet<String>, Error> {
+        Ok(HashSet::from(["ROLE_STUDENT".to_string()]))
+    }
+
+    async fn root(_req: &ServiceRequest) -> Result<HashSet<String>, Error> {
+        Ok(HashSet::from(["ROLE_ADMIN_ROOT".to_string()]))
+    }
+
+    /// Status of a POST by a Root admin on the admin scope, without a database
+    async fn post_status(uri: &str, body: serde_json::Value) -> StatusCode {
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(create_test_app_data().await))
+                .wrap(GrantsMiddleware::with_extractor(root))
+                .service(admins_scope()),
+        )
+        .await;
+
+        call_service(
+            &app,
+            TestRequest::post().uri(uri).set_json(body).to_request(),
+        )
+        .await
+        .status()
+    }
+
+    /// Status of a GET on the admin scope, denied by the grants check when the route exists
+    async fn get_status(uri: &str) -> StatusCode {
+        let app = init_service(
+            App::new()
+                .wrap(GrantsMiddleware::with_extractor(student))
+                .service(admins_scope()),
+        )
+        .await;
+
+        call_service(&app, TestRequest::get().uri(uri).to_request())
+            .await
+            .status()
+    }
+
+    fn documented(path: &str) -> bool {
+        ApiDoc::openapi().paths.paths.contains_key(path)
+    }
+
+    #[actix_web::test]
+    async fn test_student_deliverables_routes_are_mounted() {
+        for uri in [
+            "/admins/student-deliverables",
+            "/admins/student-deliverable-components",
+        ] {
+            assert_ne!(get_status(uri).await, StatusCode::NOT_FOUND, "{}", uri);
+        }
+        assert_eq!(
+            get_status("/admins/student-parts").await,
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[test]
+    fn test_student_deliverables_routes_are_documented() {
+        assert!(documented("/v1/admins/student-deliverables"));
+        assert!(documented("/v1/admins/student-deliverable-components"));
+    }
+
+    #[actix_web::test]
+    async fn test_group_deliverables_routes_are_mounted() {
+        for uri in [
+            "/admins/group-deliverables",
+            "/admins/group-deliverable-components",
+        ] {
+            assert_ne!(get_status(uri).await, StatusCode::NOT_FOUND, "{}", uri);
+        }
+    }
+
+    #[test]
+    fn test_group_deliverables_routes_are_documented() {
+        assert!(documented("/v1/admins/group-deliverables"));
+        assert!(documented("/v1/admins/group-deliverable-components"));
+        assert!(documented(
+            "/v1/students/group-deliverable-selections/{group_id}"
+        ));
+    }
+
+    #[actix_web::test]
+    async fn test_batch_deletes_reach_their_handlers() {
+        for resource in [
+            "group-deliverables",
+            "group-deliverable-components",
+            "student-deliverables",
+            "student-deliverable-components",
+        ] {
+            // An empty batch is refused by the handler, once its unit of work is extracted
+            let uri = format!("/admins/{}/batch-delete", resource);
+            let status = post_status(&uri, serde_json::json!({ "ids": [] })).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_project_import_reaches_its_handler() {
+        // A document without any field is refused by the handler's own validation
+        let status = post_status("/admins/projects/import", serde_json::json!({})).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
 }
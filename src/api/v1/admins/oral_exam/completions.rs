@@ -21,6 +21,7 @@ pub(crate) struct CompletionResponse {
     pub student_id: i32,
     pub project_id: i32,
     pub completed: bool,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339_opt")]
     pub completed_at: Option<DateTime<Utc>>,
 }
 
@@ -6,7 +6,7 @@ use crate::database::repositories::{
     group_deliverables_repository, groups_repository, oral_exam_repository, projects_repository,
     student_deliverable_components_repository, student_deliverable_selections_repository,
     student_deliverables_components_repository, student_deliverables_repository,
-    student_uploads_repository, students_repository,
+    student_uploads_repository, students_repository, transactions_repository,
 };
 use crate::jwt::get_user::LoggedUser;
 use crate::models::transaction::Transaction;
@@ -44,8 +44,10 @@ pub(crate) struct OralExamMemberDetail {
     pub student_deliverable: Option<StudentDeliverableSummary>,
     pub upload_count: Option<i32>,
     pub oral_exam_note: Option<String>,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339_opt")]
     pub oral_exam_note_updated_at: Option<DateTime<Utc>>,
     pub oral_exam_completed: bool,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339_opt")]
     pub oral_exam_completed_at: Option<DateTime<Utc>>,
 }
 
@@ -84,6 +86,7 @@ pub(crate) struct ComplaintSummary {
     pub other_group_id: i32,
     pub other_group_name: String,
     pub text: String,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub created_at: DateTime<Utc>,
 }
 
@@ -93,6 +96,7 @@ pub(crate) struct FairSaleSummary {
     pub buyer_group_id: i32,
     pub buyer_group_name: String,
     pub component_name: String,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub timestamp: DateTime<Utc>,
 }
 
@@ -481,7 +485,7 @@ async fn fetch_sales_for_selection(
             .await;
 
     let transactions = match rows {
-        Ok(v) => v,
+        Ok(v) => transactions_repository::without_reversed(v),
         Err(_) => return Vec::new(),
     };
 
@@ -19,6 +19,7 @@ pub(crate) struct NoteResponse {
     pub student_id: i32,
     pub project_id: i32,
     pub text: String,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -0,0 +1,324 @@
+use crate::app_data::AppData;
+use crate::common::access::{ensure_admin_sees_project, found_or_not_found};
+use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::common::pagination::PaginationQuery;
+use crate::database::repositories::{
+    admins_repository, audit_log_repository, projects_repository, students_repository,
+};
+use crate::jwt::get_user::LoggedUser;
+use crate::models::audit_log::AuditLog;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Path, Query};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use utoipa::ToSchema;
+use welds::state::DbState;
+
+const PROJECT_NOT_FOUND: &str = "Project not found";
+/// Audit entries are cheap to list, so larger pages than usual are allowed
+const ACTIVITY_MAX_PER_PAGE: u32 = 500;
+
+/// Who performed an action
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ActivityActor {
+    /// `admin` or `student`
+    #[schema(example = "student")]
+    pub kind: String,
+    #[schema(example = 12)]
+    pub id: i32,
+    /// Missing when the account was deleted meanwhile
+    #[schema(example = "Mario Rossi")]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ActivityItem {
+    pub audit_log_id: i32,
+    #[schema(example = "group_member_added")]
+    pub action: String,
+    /// Missing when the account that performed the action was deleted
+    pub actor: Option<ActivityActor>,
+    #[schema(example = "student")]
+    pub target_type: Option<String>,
+    #[schema(example = 34)]
+    pub target_id: Option<i32>,
+    #[schema(example = "group 4")]
+    pub details: Option<String>,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ProjectActivityResponse {
+    pub project_id: i32,
+    pub activity: Vec<ActivityItem>,
+}
+
+/// Turns audit entries into feed items, keeping their order and naming their actors
+fn activity_items(
+    entries: Vec<AuditLog>, admin_names: &HashMap<i32, String>,
+    student_names: &HashMap<i32, String>,
+) -> Vec<ActivityItem> {
+    entries
+        .into_iter()
+        .map(|entry| {
+            let actor = match (entry.actor_admin_id, entry.actor_student_id) {
+                (Some(id), _) => Some(ActivityActor {
+                    kind: "admin".to_string(),
+                    id,
+                    name: admin_names.get(&id).cloned(),
+                }),
+                (None, Some(id)) => Some(ActivityActor {
+                    kind: "student".to_string(),
+                    id,
+                    name: student_names.get(&id).cloned(),
+                }),
+                (None, None) => None,
+            };
+            ActivityItem {
+                audit_log_id: entry.audit_log_id,
+                action: entry.action,
+                actor,
+                target_type: entry.target_type,
+                target_id: entry.target_id,
+                details: entry.details,
+                created_at: entry.created_at,
+            }
+        })
+        .collect()
+}
+
+fn distinct(ids: impl Iterator<Item = i32>) -> Vec<i32> {
+    ids.collect::<HashSet<_>>().into_iter().collect()
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/admins/projects/{id}/activity",
+    params(("id" = i32, Path, description = "Project ID"), PaginationQuery),
+    responses(
+        (status = 200, description = "Page of the project activity, most recent first. With `envelope=true` the body is `{ data, meta }`", body = ProjectActivityResponse,
+            headers(
+                ("X-Total-Count" = u64, description = "Total number of events of the project"),
+                ("X-Page" = u32, description = "Returned page"),
+                ("X-Per-Page" = u32, description = "Page size"),
+                ("Link" = String, description = "RFC 5988 links to the first, prev, next and last pages"),
+            )
+        ),
+        (status = 404, description = "Project not found or not visible to the caller", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Projects management",
+)]
+/// Get the activity timeline of a project
+///
+/// Lists the audit log entries of the project, such as groups formed, members added or
+/// removed and deliverables selected, with who performed them. Coordinators can only see
+/// the activity of projects they are assigned to.
+#[actix_web_grants::protect(any(
+    "ROLE_ADMIN_ROOT",
+    "ROLE_ADMIN_PROFESSOR",
+    "ROLE_ADMIN_COORDINATOR"
+))]
+pub(in crate::api::v1) async fn get_project_activity_handler(
+    req: HttpRequest, path: Path<i32>, pagination: Query<PaginationQuery>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let project_id = path.into_inner();
+    let pagination = pagination
+        .into_inner()
+        .within(data.page_limits.with_max(ACTIVITY_MAX_PER_PAGE));
+
+    ensure_admin_sees_project(&data.db, &admin, project_id, PROJECT_NOT_FOUND).await?;
+
+    let project = projects_repository::get_by_id(&data.db, project_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch project {}: {}", project_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+    found_or_not_found(project, PROJECT_NOT_FOUND)?;
+
+    let db_error = |what: &str, e: welds::WeldsError| {
+        error_with_log_id(
+            format!("unable to {} of project {}: {}", what, project_id, e),
+            "Failed to retrieve project activity",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    };
+
+    let total = audit_log_repository::count_for_project(&data.db, project_id)
+        .await
+        .map_err(|e| db_error("count the activity", e))?;
+
+    let entries: Vec<AuditLog> = audit_log_repository::get_for_project(
+        &data.db,
+        project_id,
+        pagination.limit(),
+        pagination.offset(),
+    )
+    .await
+    .map_err(|e| db_error("fetch the activity", e))?
+    .into_iter()
+    .map(DbState::into_inner)
+    .collect();
+
+    let admin_ids = distinct(entries.iter().filter_map(|e| e.actor_admin_id));
+    let admin_names: HashMap<i32, String> = admins_repository::get_by_ids(&data.db, &admin_ids)
+        .await
+        .map_err(|e| db_error("fetch the admins in the activity", e))?
+        .into_iter()
+        .map(DbState::into_inner)
+        .map(|a| (a.admin_id, format!("{} {}", a.first_name, a.last_name)))
+        .collect();
+
+    let student_ids = distinct(entries.iter().filter_map(|e| e.actor_student_id));
+    let student_names: HashMap<i32, String> =
+        students_repository::get_by_ids(&data.db, &student_ids)
+            .await
+            .map_err(|e| db_error("fetch the students in the activity", e))?
+            .into_iter()
+            .map(DbState::into_inner)
+            .map(|s| (s.student_id, format!("{} {}", s.first_name, s.last_name)))
+            .collect();
+
+    let activity = activity_items(entries, &admin_names, &student_names);
+
+    Ok(
+        pagination.respond(&req, activity, total, |activity| ProjectActivityResponse {
+            project_id,
+            activity,
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::admin::Admin;
+    use crate::models::admin_role::AvailableAdminRole;
+    use crate::models::audit_log::{GROUP_CREATED, GROUP_MEMBER_ADDED, IMPERSONATION_STARTED};
+    use crate::test_utils::{create_test_app_data, RecordingClient};
+    use actix_web::dev::ServiceRequest;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::{web, App, Error, ResponseError};
+    use actix_web_grants::GrantsMiddleware;
+    use chrono::TimeZone;
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 6, 10, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_seeded_events_keep_their_order_and_actors() {
+        let mut added = AuditLog::by_student(3, GROUP_MEMBER_ADDED)
+            .project(1)
+            .target("student", 4);
+        added.audit_log_id = 12;
+        added.created_at = at(11);
+        let mut created = AuditLog::by_student(3, GROUP_CREATED)
+            .project(1)
+            .target("group", 7);
+        created.audit_log_id = 10;
+        created.created_at = at(10);
+        let mut by_admin = AuditLog::by_admin(9, IMPERSONATION_STARTED).project(1);
+        by_admin.audit_log_id = 8;
+        by_admin.created_at = at(9);
+
+        let students = HashMap::from([(3, "Mario Rossi".to_string())]);
+        let items = activity_items(vec![added, created, by_admin], &HashMap::new(), &students);
+
+        let ids: Vec<i32> = items.iter().map(|i| i.audit_log_id).collect();
+        assert_eq!(ids, vec![12, 10, 8]);
+        assert!(items.windows(2).all(|w| w[0].created_at >= w[1].created_at));
+
+        let actor = items[0].actor.as_ref().unwrap();
+        assert_eq!(actor.kind, "student");
+        assert_eq!(actor.name.as_deref(), Some("Mario Rossi"));
+        assert_eq!(items[0].target_id, Some(4));
+        // The admin was deleted meanwhile, the entry still names its id
+        let actor = items[2].actor.as_ref().unwrap();
+        assert_eq!(
+            (actor.kind.as_str(), actor.id, actor.name.clone()),
+            ("admin", 9, None)
+        );
+    }
+
+    fn admin(role: AvailableAdminRole) -> Admin {
+        Admin {
+            admin_id: 5,
+            first_name: "Test".to_string(),
+            last_name: "Admin".to_string(),
+            email: "admin@test.com".to_string(),
+            password_hash: String::new(),
+            admin_role_id: role as i32,
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_coordinators_only_see_activity_of_their_projects() {
+        // No assignment is found, the project is hidden behind the same 404 as a missing one
+        let db = RecordingClient::default();
+        let coordinator = admin(AvailableAdminRole::Coordinator);
+        let err = ensure_admin_sees_project(&db, &coordinator, 1, PROJECT_NOT_FOUND)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::NOT_FOUND);
+        let statements = db.statements();
+        assert_eq!(statements.len(), 1);
+        assert!(
+            statements[0].contains(r#"t1."admin_id" = $1 AND t1."project_id" = $2"#),
+            "{}",
+            statements[0]
+        );
+
+        // Professors see every project without looking up assignments
+        let db = RecordingClient::default();
+        let professor = admin(AvailableAdminRole::Professor);
+        ensure_admin_sees_project(&db, &professor, 1, PROJECT_NOT_FOUND)
+            .await
+            .unwrap();
+        assert!(db.statements().is_empty());
+    }
+
+    async fn student(_req: &ServiceRequest) -> Result<HashSet<String>, Error> {
+        Ok(HashSet::from(["ROLE_STUDENT".to_string()]))
+    }
+
+    #[actix_web::test]
+    async fn test_students_cannot_see_project_activity() {
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(create_test_app_data().await))
+                .wrap(GrantsMiddleware::with_extractor(student))
+                .route(
+                    "/projects/{id}/activity",
+                    web::get().to(get_project_activity_handler),
+                ),
+        )
+        .await;
+
+        let res = call_service(
+            &app,
+            TestRequest::get().uri("/projects/1/activity").to_request(),
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::app_data::AppData;
-use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError, ALREADY_COORDINATOR};
+use crate::database::errors::is_unique_violation;
 use crate::database::repositories::{
     admins_repository, coordinator_projects_repository, projects_repository,
 };
@@ -52,6 +53,7 @@ pub(crate) struct CoordinatorDetail {
     pub first_name: String,
     pub last_name: String,
     pub email: String,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub assigned_at: DateTime<Utc>,
 }
 
@@ -60,6 +62,33 @@ pub(crate) struct RemoveCoordinatorResponse {
     pub message: String,
 }
 
+/// Message of the conflict returned when the admin already coordinates the project
+const ALREADY_ASSIGNED: &str = "This admin is already the coordinator of the project";
+
+/// Refuses an assignment to a project that already has a coordinator
+///
+/// Assigning the current coordinator again is a 409 `ALREADY_COORDINATOR`, so clients
+/// can tell a repeated request from a project taken by someone else.
+fn ensure_no_coordinator(
+    project_id: i32, existing_admin_id: Option<i32>, admin_id: i32,
+) -> Result<(), JsonError> {
+    match existing_admin_id {
+        None => Ok(()),
+        Some(existing) if existing == admin_id => Err(ALREADY_ASSIGNED
+            .to_json_error(StatusCode::CONFLICT)
+            .with_code(ALREADY_COORDINATOR)),
+        Some(existing) => Err(error_with_log_id(
+            format!(
+                "project {} already has a coordinator assigned (admin_id: {})",
+                project_id, existing
+            ),
+            "Project can only have one coordinator. Remove the existing coordinator first.",
+            StatusCode::BAD_REQUEST,
+            log::Level::Warn,
+        )),
+    }
+}
+
 #[utoipa::path(
     post,
     path = "/v1/admins/projects/{project_id}/coordinators",
@@ -69,6 +98,7 @@ pub(crate) struct RemoveCoordinatorResponse {
         (status = 400, description = "Invalid request or business rule violation", body = JsonError),
         (status = 401, description = "Authentication required", body = JsonError),
         (status = 404, description = "Project or admin not found", body = JsonError),
+        (status = 409, description = "The admin already coordinates the project (code ALREADY_COORDINATOR)", body = JsonError),
         (status = 500, description = "Internal server error", body = JsonError)
     ),
     security(("AdminAuth" = [])),
@@ -171,23 +201,22 @@ pub(super) async fn assign_coordinator(
                 )
             })?;
 
-    if !existing_coordinators.is_empty() {
-        let existing_coordinator = &existing_coordinators[0];
-        return Err(error_with_log_id(
-            format!(
-                "project {} already has a coordinator assigned (admin_id: {})",
-                project_id, existing_coordinator.admin_id
-            ),
-            "Project can only have one coordinator. Remove the existing coordinator first.",
-            StatusCode::BAD_REQUEST,
-            log::Level::Warn,
-        ));
-    }
+    ensure_no_coordinator(
+        project_id,
+        existing_coordinators.first().map(|c| c.admin_id),
+        body.admin_id,
+    )?;
 
-    // Create the assignment
+    // Create the assignment, the (admin_id, project_id) unique constraint catches
+    // a repeated request racing this one
     let assignment = coordinator_projects_repository::create(&data.db, body.admin_id, project_id)
         .await
         .map_err(|e| {
+            if is_unique_violation(&e) {
+                return ALREADY_ASSIGNED
+                    .to_json_error(StatusCode::CONFLICT)
+                    .with_code(ALREADY_COORDINATOR);
+            }
             error_with_log_id(
                 format!("unable to create coordinator assignment: {}", e),
                 "Database error",
@@ -228,7 +257,7 @@ pub(super) async fn assign_coordinator(
 /// Get the coordinator assigned to a project
 ///
 /// This endpoint allows admins to view the coordinator assigned to a specific project.
-/// Returns null if no coordinator is assigned.
+/// Returns null if no coordinator is assigned, 404 is only for a missing project.
 #[actix_web_grants::protect(any(
     "ROLE_ADMIN_ROOT",
     "ROLE_ADMIN_PROFESSOR",
@@ -419,3 +448,28 @@ pub(super) async fn remove_coordinator(
         message: "Coordinator removed from project successfully".to_string(),
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::ResponseError;
+
+    #[test]
+    fn test_repeated_assignment_is_a_conflict() {
+        let err = ensure_no_coordinator(1, Some(7), 7).unwrap_err();
+
+        assert_eq!(err.status_code(), StatusCode::CONFLICT);
+        assert_eq!(
+            serde_json::to_value(&err).unwrap()["code"],
+            ALREADY_COORDINATOR
+        );
+    }
+
+    #[test]
+    fn test_project_with_another_coordinator_is_refused() {
+        assert!(ensure_no_coordinator(1, None, 7).is_ok());
+
+        let err = ensure_no_coordinator(1, Some(8), 7).unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+    }
+}
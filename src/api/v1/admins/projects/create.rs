@@ -1,5 +1,11 @@
 use crate::app_data::AppData;
-use crate::common::json_error::{error_with_log_id_and_payload, JsonError, ToJsonError};
+use crate::common::created::created;
+use crate::common::deadlines::ProjectDeadlines;
+use crate::common::json_error::{
+    error_with_log_id, error_with_log_id_and_payload, JsonError, ToJsonError,
+};
+use crate::common::slug::{slugify, unique_slug, validate_slug};
+use crate::database::errors::is_unique_violation;
 use crate::database::repositories::projects_repository;
 use crate::models::project::Project;
 use actix_web::http::StatusCode;
@@ -8,18 +14,24 @@ use actix_web::HttpResponse;
 use chrono::{DateTime, Datelike, Local, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use welds::state::DbState;
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub(crate) struct CreateProjectScheme {
     #[schema(example = "Project Name")]
     pub name: String,
+    /// Generated from the name when missing
+    #[schema(example = "project-name")]
+    pub slug: Option<String>,
     #[schema(example = 10)]
     pub max_student_uploads: i32,
     #[schema(example = 4)]
     pub max_group_size: i32,
     #[schema(value_type = Option<String>, example = "2025-12-15T23:59:59Z")]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339_opt")]
     pub deliverable_selection_deadline: Option<DateTime<Utc>>,
     #[schema(value_type = Option<String>, example = "2025-12-20T23:59:59Z")]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339_opt")]
     pub upload_deadline: Option<DateTime<Utc>>,
     #[schema(example = true)]
     pub active: bool,
@@ -27,20 +39,63 @@ pub(crate) struct CreateProjectScheme {
 #[derive(Debug, Serialize, ToSchema)]
 pub(crate) struct CreateProjectResponse {
     project_id: i32,
+    slug: String,
+}
+
+pub(super) const SLUG_TAKEN: &str = "Slug already in use by another project";
+
+/// The slug asked by the client if free, otherwise one generated from the name
+pub(super) async fn pick_slug(
+    data: &AppData, name: &str, slug: Option<&str>,
+) -> Result<String, JsonError> {
+    let db_error = |e: welds::WeldsError| {
+        error_with_log_id(
+            format!("unable to check project slugs: {}", e),
+            "Failed to create project",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    };
+
+    if let Some(slug) = slug {
+        validate_slug(slug)?;
+        if projects_repository::get_by_slug(&data.db, slug)
+            .await
+            .map_err(db_error)?
+            .is_some()
+        {
+            return Err(SLUG_TAKEN.to_json_error(StatusCode::CONFLICT));
+        }
+        return Ok(slug.to_string());
+    }
+
+    let base = slugify(name);
+    let taken = projects_repository::slugs_starting_with(&data.db, &base)
+        .await
+        .map_err(db_error)?;
+    Ok(unique_slug(&base, &taken))
 }
 #[utoipa::path(
     post,
     path = "/v1/admins/projects",
     request_body = CreateProjectScheme,
     responses(
-        (status = 201, description = "Project created successfully", body = CreateProjectResponse),
+        (status = 201, description = "Project created successfully", body = CreateProjectResponse,
+            headers(("Location" = String, description = "URL of the created project"))
+        ),
         (status = 400, description = "Invalid data in request", body = JsonError),
+        (status = 409, description = "Slug already in use", body = JsonError),
+        (status = 422, description = "Deadlines out of order or in the past", body = JsonError),
         (status = 500, description = "Internal server error", body = JsonError)
     ),
     security(("AdminAuth" = [])),
     tag = "Projects management",
 )]
 /// Create a project
+///
+/// Deadlines must be in chronological order (deliverable selection, then upload)
+/// and not in the past. They are stored in UTC with second precision.
+/// Without a slug one is generated from the name, suffixed with a number when taken.
 #[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
 pub(in crate::api::v1) async fn create_project_handler(
     body: Json<CreateProjectScheme>, data: Data<AppData>,
@@ -55,21 +110,38 @@ pub(in crate::api::v1) async fn create_project_handler(
         return Err("Max group size must be greater than 1".to_json_error(StatusCode::BAD_REQUEST));
     }
 
+    let deadlines = ProjectDeadlines {
+        deliverable_selection_deadline: body.deliverable_selection_deadline,
+        upload_deadline: body.upload_deadline,
+    }
+    .normalized();
+    deadlines.validate_order()?;
+    deadlines.validate_not_past(data.clock.now())?;
+
+    let slug = pick_slug(&data, &body.name, body.slug.as_deref()).await?;
+
     let project = Project {
         project_id: 0,
         name: body.name.clone(),
+        slug,
         year: Local::now().year(),
         max_student_uploads: body.max_student_uploads,
         max_group_size: body.max_group_size,
-        deliverable_selection_deadline: body.deliverable_selection_deadline,
-        upload_deadline: body.upload_deadline,
+        deliverable_selection_deadline: deadlines.deliverable_selection_deadline,
+        upload_deadline: deadlines.upload_deadline,
         active: body.active,
         oral_exam_enabled: false,
+        frozen: false,
+        allowed_signup_domains: None,
+        published: data.config.publish_projects_on_create(),
     };
 
     let p = projects_repository::create(&data.db, project)
         .await
         .map_err(|e| {
+            if is_unique_violation(&e) {
+                return SLUG_TAKEN.to_json_error(StatusCode::CONFLICT);
+            }
             error_with_log_id_and_payload(
                 format!("unable to insert project in database: {}", e),
                 "Failed to create project",
@@ -79,7 +151,13 @@ pub(in crate::api::v1) async fn create_project_handler(
             )
         })?;
 
-    Ok(HttpResponse::Created().json(CreateProjectResponse {
-        project_id: p.project_id,
-    }))
+    let project_id = p.project_id;
+    Ok(created(
+        "/v1/admins/projects",
+        project_id,
+        &CreateProjectResponse {
+            project_id,
+            slug: DbState::into_inner(p).slug,
+        },
+    ))
 }
@@ -37,6 +37,7 @@ pub(in crate::api::v1) async fn delete_project_handler(
     if !deleted {
         return Err("Project not found".to_json_error(StatusCode::NOT_FOUND));
     }
+    data.deliverable_trees.invalidate(project_id);
 
     Ok(HttpResponse::Ok().finish())
 }
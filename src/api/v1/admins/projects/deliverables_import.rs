@@ -0,0 +1,933 @@
+use crate::app_data::AppData;
+use crate::common::access::found_or_not_found;
+use crate::common::csv::parse_csv;
+use crate::common::json_error::{error_with_log_id, JsonError, ValidationError};
+use crate::common::link_weights::validate_link;
+use crate::common::project_freeze::ensure_not_frozen;
+use crate::database::repositories::{
+    group_deliverable_components_repository, group_deliverables_components_repository,
+    group_deliverables_repository, projects_repository, student_deliverable_components_repository,
+    student_deliverables_components_repository, student_deliverables_repository,
+};
+use crate::database::unit_of_work::UnitOfWork;
+use crate::models::group_deliverable::GroupDeliverable;
+use crate::models::group_deliverable_component::GroupDeliverableComponent;
+use crate::models::group_deliverables_component::GroupDeliverablesComponent;
+use crate::models::student_deliverable::StudentDeliverable;
+use crate::models::student_deliverable_component::StudentDeliverableComponent;
+use crate::models::student_deliverables_component::StudentDeliverablesComponent;
+use actix_web::http::header::CONTENT_TYPE;
+use actix_web::http::StatusCode;
+use actix_web::mime;
+use actix_web::web::{Bytes, Data, Path};
+use actix_web::{HttpRequest, HttpResponse};
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use utoipa::ToSchema;
+use welds::state::DbState;
+
+/// Upper bound on the number of items imported in a single request
+pub(crate) const MAX_IMPORT_ITEMS: usize = 1000;
+
+const PROJECT_NOT_FOUND: &str = "Project not found";
+
+/// Columns a CSV import may have, `type` being the only mandatory one
+const CSV_COLUMNS: [&str; 6] = [
+    "type",
+    "deliverable",
+    "component",
+    "quantity",
+    "weight",
+    "sellable",
+];
+
+/// Level of the project an imported item belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DeliverableType {
+    Group,
+    Student,
+}
+
+/// Deliverables, components and links to add to a project, as JSON
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct DeliverablesImport {
+    pub items: Vec<DeliverableImportItem>,
+}
+
+/// One deliverable, component or link, told apart by the names it carries
+///
+/// A deliverable alone defines a deliverable, a component alone defines a component, and
+/// both define a link between them, which then needs a quantity and a weight. Names refer
+/// to items of the same import or to those the project already has, at the same level.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct DeliverableImportItem {
+    #[serde(rename = "type")]
+    pub level: DeliverableType,
+    #[schema(example = "Drone")]
+    pub deliverable: Option<String>,
+    #[schema(example = "Propeller")]
+    pub component: Option<String>,
+    #[schema(example = 4)]
+    pub quantity: Option<i32>,
+    #[schema(example = 40)]
+    pub weight: Option<i32>,
+    /// Whether the component can be bought at the fair, only for group components
+    pub sellable: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ImportedItemKind {
+    Deliverable,
+    Component,
+    Link,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ImportedItemResult {
+    /// Position of the item in the request, the first CSV row after the header being 0
+    pub index: usize,
+    #[serde(rename = "type")]
+    pub level: DeliverableType,
+    pub kind: ImportedItemKind,
+    /// Id of the created deliverable, component or link
+    pub id: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct DeliverablesImportResponse {
+    pub project_id: i32,
+    /// One entry per item, in request order
+    pub results: Vec<ImportedItemResult>,
+}
+
+/// Deliverables, components and links a project already has at one level
+#[derive(Debug, Default)]
+struct ExistingLevel {
+    deliverables: HashMap<String, i32>,
+    components: HashMap<String, i32>,
+    /// Linked deliverable and component ids
+    links: HashSet<(i32, i32)>,
+    /// Sum of the link weights of each deliverable id
+    weights: HashMap<i32, i32>,
+}
+
+impl ExistingLevel {
+    fn new(
+        deliverables: impl IntoIterator<Item = (String, i32)>,
+        components: impl IntoIterator<Item = (String, i32)>,
+        links: impl IntoIterator<Item = (i32, i32, i32)>,
+    ) -> Self {
+        let mut level = ExistingLevel {
+            deliverables: deliverables.into_iter().collect(),
+            components: components.into_iter().collect(),
+            ..Default::default()
+        };
+        for (deliverable_id, component_id, weight) in links {
+            level.links.insert((deliverable_id, component_id));
+            *level.weights.entry(deliverable_id).or_default() += weight;
+        }
+        level
+    }
+
+    fn is_linked(&self, deliverable: &str, component: &str) -> bool {
+        match (
+            self.deliverables.get(deliverable),
+            self.components.get(component),
+        ) {
+            (Some(&d), Some(&c)) => self.links.contains(&(d, c)),
+            _ => false,
+        }
+    }
+
+    fn weight_of(&self, deliverable: &str) -> i32 {
+        self.deliverables
+            .get(deliverable)
+            .and_then(|id| self.weights.get(id))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct PlannedComponent<'a> {
+    index: usize,
+    name: &'a str,
+    sellable: bool,
+}
+
+#[derive(Debug, PartialEq)]
+struct PlannedDeliverable<'a> {
+    index: usize,
+    name: &'a str,
+}
+
+#[derive(Debug, PartialEq)]
+struct PlannedLink<'a> {
+    index: usize,
+    deliverable: &'a str,
+    component: &'a str,
+    quantity: i32,
+    weight: i32,
+}
+
+/// Rows to insert at one level, components and deliverables before the links using them
+#[derive(Debug, Default, PartialEq)]
+struct LevelPlan<'a> {
+    components: Vec<PlannedComponent<'a>>,
+    deliverables: Vec<PlannedDeliverable<'a>>,
+    links: Vec<PlannedLink<'a>>,
+}
+
+/// Names the project and the import already define at one level, with the weights and
+/// links seen so far
+#[derive(Default)]
+struct LevelNames<'a> {
+    deliverables: HashSet<&'a str>,
+    components: HashSet<&'a str>,
+    links: HashSet<(&'a str, &'a str)>,
+    weights: HashMap<&'a str, i32>,
+}
+
+fn non_blank(name: &Option<String>) -> Option<&str> {
+    name.as_deref().filter(|n| !n.trim().is_empty())
+}
+
+/// Records a name defined by the import, rejecting those already taken at its level
+fn define<'a>(
+    errors: &mut ValidationError, field: &str, name: &'a str, existing: &HashMap<String, i32>,
+    defined: &mut HashSet<&'a str>,
+) -> bool {
+    if existing.contains_key(name) {
+        errors.add(field, format!("`{}` already exists in the project", name));
+        false
+    } else if !defined.insert(name) {
+        errors.add(field, format!("Duplicate name `{}`", name));
+        false
+    } else {
+        true
+    }
+}
+
+/// Checks every item against the others and the existing structure of the project
+///
+/// Nothing is written: the plan is only returned when the whole import is consistent.
+fn plan_import<'a>(
+    items: &'a [DeliverableImportItem], existing: &HashMap<DeliverableType, ExistingLevel>,
+) -> Result<HashMap<DeliverableType, LevelPlan<'a>>, ValidationError> {
+    let mut errors = ValidationError::default();
+    if items.is_empty() {
+        errors.add("items", "At least one item is required");
+    }
+    if items.len() > MAX_IMPORT_ITEMS {
+        errors.add(
+            "items",
+            format!("At most {} items can be imported at once", MAX_IMPORT_ITEMS),
+        );
+    }
+
+    let empty = ExistingLevel::default();
+    let mut names: HashMap<DeliverableType, LevelNames> = HashMap::new();
+    let mut plans: HashMap<DeliverableType, LevelPlan> = HashMap::new();
+
+    // Definitions first, so links may refer to items listed after them
+    for (index, item) in items.iter().enumerate() {
+        let field = format!("items[{}]", index);
+        let existing = existing.get(&item.level).unwrap_or(&empty);
+        let names = names.entry(item.level).or_default();
+        let plan = plans.entry(item.level).or_default();
+
+        if item.sellable.is_some()
+            && !(item.level == DeliverableType::Group
+                && item.deliverable.is_none()
+                && item.component.is_some())
+        {
+            errors.add(
+                &format!("{}.sellable", field),
+                "Only group components can be sellable",
+            );
+        }
+        let is_link = item.deliverable.is_some() && item.component.is_some();
+        if !is_link && (item.quantity.is_some() || item.weight.is_some()) {
+            errors.add(
+                &field,
+                "Only links between a deliverable and a component have a quantity and a weight",
+            );
+        }
+
+        match (non_blank(&item.deliverable), non_blank(&item.component)) {
+            (None, None) => errors.add(&field, "A deliverable or a component name is required"),
+            (Some(name), None) if item.component.is_none() => {
+                let field = format!("{}.deliverable", field);
+                if define(
+                    &mut errors,
+                    &field,
+                    name,
+                    &existing.deliverables,
+                    &mut names.deliverables,
+                ) {
+                    plan.deliverables.push(PlannedDeliverable { index, name });
+                }
+            }
+            (None, Some(name)) if item.deliverable.is_none() => {
+                let field = format!("{}.component", field);
+                if define(
+                    &mut errors,
+                    &field,
+                    name,
+                    &existing.components,
+                    &mut names.components,
+                ) {
+                    plan.components.push(PlannedComponent {
+                        index,
+                        name,
+                        sellable: item.sellable.unwrap_or(false),
+                    });
+                }
+            }
+            (Some(_), Some(_)) => {}
+            _ => errors.add(&field, "Names cannot be empty"),
+        }
+    }
+
+    for (index, item) in items.iter().enumerate() {
+        let (Some(deliverable), Some(component)) =
+            (non_blank(&item.deliverable), non_blank(&item.component))
+        else {
+            continue;
+        };
+        let field = format!("items[{}]", index);
+        let existing = existing.get(&item.level).unwrap_or(&empty);
+        let names = names.entry(item.level).or_default();
+        let level = match item.level {
+            DeliverableType::Group => "group",
+            DeliverableType::Student => "student",
+        };
+
+        let mut dangling = false;
+        if !existing.deliverables.contains_key(deliverable)
+            && !names.deliverables.contains(deliverable)
+        {
+            dangling = true;
+            errors.add(
+                &format!("{}.deliverable", field),
+                format!("Unknown {} deliverable `{}`", level, deliverable),
+            );
+        }
+        if !existing.components.contains_key(component) && !names.components.contains(component) {
+            dangling = true;
+            errors.add(
+                &format!("{}.component", field),
+                format!("Unknown {} component `{}`", level, component),
+            );
+        }
+        let (Some(quantity), Some(weight)) = (item.quantity, item.weight) else {
+            errors.add(&field, "A link needs a quantity and a weight");
+            continue;
+        };
+        if dangling {
+            continue;
+        }
+
+        if existing.is_linked(deliverable, component)
+            || !names.links.insert((deliverable, component))
+        {
+            errors.add(
+                &field,
+                format!("`{}` is already linked to `{}`", component, deliverable),
+            );
+            continue;
+        }
+        let other_weights = names
+            .weights
+            .entry(deliverable)
+            .or_insert_with(|| existing.weight_of(deliverable));
+        match validate_link(quantity, weight, *other_weights) {
+            Ok(()) => *other_weights += weight,
+            Err(e) => errors.add(&field, e.message()),
+        }
+        plans
+            .entry(item.level)
+            .or_default()
+            .links
+            .push(PlannedLink {
+                index,
+                deliverable,
+                component,
+                quantity,
+                weight,
+            });
+    }
+
+    errors
+        .into_result()
+        .map_err(|e| e.with_status(StatusCode::UNPROCESSABLE_ENTITY))?;
+    Ok(plans)
+}
+
+/// Reports a body that cannot be read as an import under `body`
+fn unreadable(msg: impl Into<String>) -> ValidationError {
+    let mut errors = ValidationError::default();
+    errors.add("body", msg);
+    errors.with_status(StatusCode::UNPROCESSABLE_ENTITY)
+}
+
+/// Reads a CSV import: a header naming the columns, then one item per row
+fn parse_csv_items(text: &str) -> Result<Vec<DeliverableImportItem>, ValidationError> {
+    let mut records = parse_csv(text).map_err(unreadable)?.into_iter();
+    let header = records
+        .next()
+        .ok_or_else(|| unreadable("The CSV header is missing"))?;
+    let header: Vec<String> = header.iter().map(|c| c.trim().to_lowercase()).collect();
+
+    let mut errors = ValidationError::default();
+    for column in &header {
+        if !CSV_COLUMNS.contains(&column.as_str()) {
+            errors.add(
+                "body",
+                format!(
+                    "Unknown column `{}`, expected {}",
+                    column,
+                    CSV_COLUMNS.join(", ")
+                ),
+            );
+        }
+    }
+    if !header.iter().any(|c| c == "type") {
+        errors.add("body", "The `type` column is mandatory");
+    }
+    errors
+        .into_result()
+        .map_err(|e| e.with_status(StatusCode::UNPROCESSABLE_ENTITY))?;
+
+    let mut errors = ValidationError::default();
+    let mut items = Vec::new();
+    for (index, record) in records.enumerate() {
+        let field = format!("items[{}]", index);
+        if record.len() != header.len() {
+            errors.add(
+                &field,
+                format!("Expected {} fields, found {}", header.len(), record.len()),
+            );
+            continue;
+        }
+        let cell = |column: &str| {
+            header
+                .iter()
+                .position(|c| c == column)
+                .map(|i| record[i].trim())
+                .filter(|v| !v.is_empty())
+        };
+        let mut number = |column: &str| {
+            let value = cell(column)?;
+            value
+                .parse::<i32>()
+                .map_err(|_| {
+                    errors.add(
+                        &format!("{}.{}", field, column),
+                        format!("Invalid number `{}`", value),
+                    )
+                })
+                .ok()
+        };
+        let quantity = number("quantity");
+        let weight = number("weight");
+
+        let level = match cell("type") {
+            Some("group") => DeliverableType::Group,
+            Some("student") => DeliverableType::Student,
+            other => {
+                errors.add(
+                    &format!("{}.type", field),
+                    format!(
+                        "Invalid type `{}`, expected group or student",
+                        other.unwrap_or_default()
+                    ),
+                );
+                continue;
+            }
+        };
+        let sellable = match cell("sellable") {
+            None => None,
+            Some("true") => Some(true),
+            Some("false") => Some(false),
+            Some(other) => {
+                errors.add(
+                    &format!("{}.sellable", field),
+                    format!("Invalid value `{}`, expected true or false", other),
+                );
+                continue;
+            }
+        };
+
+        items.push(DeliverableImportItem {
+            level,
+            deliverable: cell("deliverable").map(str::to_string),
+            component: cell("component").map(str::to_string),
+            quantity,
+            weight,
+            sellable,
+        });
+    }
+    errors
+        .into_result()
+        .map_err(|e| e.with_status(StatusCode::UNPROCESSABLE_ENTITY))?;
+    Ok(items)
+}
+
+/// Reads the items of a JSON or, with a `text/csv` content type, CSV body
+fn parse_items(
+    req: &HttpRequest, body: &[u8],
+) -> Result<Vec<DeliverableImportItem>, ValidationError> {
+    let is_csv = req
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<mime::Mime>().ok())
+        .is_some_and(|m| m.essence_str() == mime::TEXT_CSV.essence_str());
+
+    if is_csv {
+        let text = std::str::from_utf8(body).map_err(|_| unreadable("The CSV is not UTF-8"))?;
+        parse_csv_items(text)
+    } else {
+        serde_json::from_slice::<DeliverablesImport>(body)
+            .map(|import| import.items)
+            .map_err(|e| unreadable(e.to_string()))
+    }
+}
+
+/// Loads what the project already has at both levels, through `db` so the import sees
+/// the same rows it then writes next to
+async fn load_existing(
+    db: &UnitOfWork, project_id: i32,
+) -> welds::errors::Result<HashMap<DeliverableType, ExistingLevel>> {
+    let deliverables = group_deliverables_repository::get_by_project_id(db, project_id).await?;
+    let ids: Vec<i32> = deliverables
+        .iter()
+        .map(|d| d.group_deliverable_id)
+        .collect();
+    let links = group_deliverables_components_repository::get_by_deliverable_ids(db, &ids).await?;
+    let components =
+        group_deliverable_components_repository::get_by_project_id(db, project_id).await?;
+    let group = ExistingLevel::new(
+        deliverables
+            .iter()
+            .map(|d| (d.name.clone(), d.group_deliverable_id)),
+        components
+            .iter()
+            .map(|c| (c.name.clone(), c.group_deliverable_component_id)),
+        links.iter().map(|l| {
+            (
+                l.group_deliverable_id,
+                l.group_deliverable_component_id,
+                l.weight,
+            )
+        }),
+    );
+
+    let deliverables = student_deliverables_repository::get_by_project_id(db, project_id).await?;
+    let ids: Vec<i32> = deliverables
+        .iter()
+        .map(|d| d.student_deliverable_id)
+        .collect();
+    let links =
+        student_deliverables_components_repository::get_by_deliverable_ids(db, &ids).await?;
+    let components =
+        student_deliverable_components_repository::get_by_project_id(db, project_id).await?;
+    let student = ExistingLevel::new(
+        deliverables
+            .iter()
+            .map(|d| (d.name.clone(), d.student_deliverable_id)),
+        components
+            .iter()
+            .map(|c| (c.name.clone(), c.student_deliverable_component_id)),
+        links.iter().map(|l| {
+            (
+                l.student_deliverable_id,
+                l.student_deliverable_component_id,
+                l.weight,
+            )
+        }),
+    );
+
+    Ok(HashMap::from([
+        (DeliverableType::Group, group),
+        (DeliverableType::Student, student),
+    ]))
+}
+
+fn result(
+    index: usize, level: DeliverableType, kind: ImportedItemKind, id: i32,
+) -> ImportedItemResult {
+    ImportedItemResult {
+        index,
+        level,
+        kind,
+        id,
+    }
+}
+
+/// Inserts the planned group rows, resolving names against the existing ones
+async fn insert_group_level(
+    db: &UnitOfWork, project_id: i32, plan: &LevelPlan<'_>, existing: &ExistingLevel,
+    results: &mut Vec<ImportedItemResult>,
+) -> welds::errors::Result<()> {
+    let level = DeliverableType::Group;
+    let mut components = existing.components.clone();
+    for planned in &plan.components {
+        let mut state = DbState::new_uncreated(GroupDeliverableComponent {
+            group_deliverable_component_id: 0,
+            project_id,
+            name: planned.name.to_string(),
+            sellable: planned.sellable,
+        });
+        state.save(db).await?;
+        let id = state.group_deliverable_component_id;
+        components.insert(planned.name.to_string(), id);
+        results.push(result(
+            planned.index,
+            level,
+            ImportedItemKind::Component,
+            id,
+        ));
+    }
+    let mut deliverables = existing.deliverables.clone();
+    for planned in &plan.deliverables {
+        let mut state = DbState::new_uncreated(GroupDeliverable {
+            group_deliverable_id: 0,
+            project_id,
+            name: planned.name.to_string(),
+        });
+        state.save(db).await?;
+        let id = state.group_deliverable_id;
+        deliverables.insert(planned.name.to_string(), id);
+        results.push(result(
+            planned.index,
+            level,
+            ImportedItemKind::Deliverable,
+            id,
+        ));
+    }
+    for planned in &plan.links {
+        let mut state = DbState::new_uncreated(GroupDeliverablesComponent {
+            id: 0,
+            group_deliverable_id: deliverables[planned.deliverable],
+            group_deliverable_component_id: components[planned.component],
+            quantity: planned.quantity,
+            weight: planned.weight,
+        });
+        state.save(db).await?;
+        results.push(result(
+            planned.index,
+            level,
+            ImportedItemKind::Link,
+            state.id,
+        ));
+    }
+    Ok(())
+}
+
+/// Inserts the planned student rows, resolving names against the existing ones
+async fn insert_student_level(
+    db: &UnitOfWork, project_id: i32, plan: &LevelPlan<'_>, existing: &ExistingLevel,
+    results: &mut Vec<ImportedItemResult>,
+) -> welds::errors::Result<()> {
+    let level = DeliverableType::Student;
+    let mut components = existing.components.clone();
+    for planned in &plan.components {
+        let mut state = DbState::new_uncreated(StudentDeliverableComponent {
+            student_deliverable_component_id: 0,
+            project_id,
+            name: planned.name.to_string(),
+        });
+        state.save(db).await?;
+        let id = state.student_deliverable_component_id;
+        components.insert(planned.name.to_string(), id);
+        results.push(result(
+            planned.index,
+            level,
+            ImportedItemKind::Component,
+            id,
+        ));
+    }
+    let mut deliverables = existing.deliverables.clone();
+    for planned in &plan.deliverables {
+        let mut state = DbState::new_uncreated(StudentDeliverable {
+            student_deliverable_id: 0,
+            project_id,
+            name: planned.name.to_string(),
+        });
+        state.save(db).await?;
+        let id = state.student_deliverable_id;
+        deliverables.insert(planned.name.to_string(), id);
+        results.push(result(
+            planned.index,
+            level,
+            ImportedItemKind::Deliverable,
+            id,
+        ));
+    }
+    for planned in &plan.links {
+        let mut state = DbState::new_uncreated(StudentDeliverablesComponent {
+            id: 0,
+            student_deliverable_id: deliverables[planned.deliverable],
+            student_deliverable_component_id: components[planned.component],
+            quantity: planned.quantity,
+            weight: planned.weight,
+        });
+        state.save(db).await?;
+        results.push(result(
+            planned.index,
+            level,
+            ImportedItemKind::Link,
+            state.id,
+        ));
+    }
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/admins/projects/{id}/deliverables/import",
+    params(("id" = i32, Path, description = "Project id")),
+    request_body(
+        description = "Items as JSON, or as CSV with a header naming the columns `type`, `deliverable`, `component`, `quantity`, `weight` and `sellable`",
+        content(
+            (DeliverablesImport = "application/json"),
+            (String = "text/csv")
+        )
+    ),
+    responses(
+        (status = 201, description = "Every item created, with its id", body = DeliverablesImportResponse),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 404, description = "Project not found", body = JsonError),
+        (status = 409, description = "The project is frozen", body = JsonError),
+        (status = 422, description = "Inconsistent items, listed per field, nothing was created", body = ValidationError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Projects management",
+)]
+/// Import deliverables, components and links into a project
+///
+/// Each item is a group or student deliverable, component, or link between the two,
+/// referring by name to the other items or to those the project already has. The whole
+/// import is checked first: duplicate names, unknown names, repeated links and weights
+/// over the budget of a deliverable are all reported at once. Everything is then created
+/// in one transaction, with the id of each item in request order.
+#[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
+pub(in crate::api::v1) async fn import_deliverables_handler(
+    req: HttpRequest, path: Path<i32>, body: Bytes, data: Data<AppData>, unit_of_work: UnitOfWork,
+) -> actix_web::Result<HttpResponse> {
+    let project_id = path.into_inner();
+    let items = parse_items(&req, &body)?;
+
+    let db_error = |what: &str, e: welds::WeldsError| {
+        error_with_log_id(
+            format!(
+                "unable to {} of project {} to import: {}",
+                what, project_id, e
+            ),
+            "Failed to import deliverables",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    };
+
+    let project = projects_repository::get_by_id(&data.db, project_id)
+        .await
+        .map_err(|e| db_error("fetch the project", e))?;
+    let project = found_or_not_found(project, PROJECT_NOT_FOUND)?;
+    ensure_not_frozen(project.frozen)?;
+
+    let existing = load_existing(&unit_of_work, project_id)
+        .await
+        .map_err(|e| db_error("load the deliverables", e))?;
+    let plans = plan_import(&items, &existing)?;
+
+    let mut results = Vec::with_capacity(items.len());
+    if let Some(plan) = plans.get(&DeliverableType::Group) {
+        insert_group_level(
+            &unit_of_work,
+            project_id,
+            plan,
+            &existing[&DeliverableType::Group],
+            &mut results,
+        )
+        .await
+        .map_err(|e| db_error("insert the group deliverables", e))?;
+    }
+    if let Some(plan) = plans.get(&DeliverableType::Student) {
+        insert_student_level(
+            &unit_of_work,
+            project_id,
+            plan,
+            &existing[&DeliverableType::Student],
+            &mut results,
+        )
+        .await
+        .map_err(|e| db_error("insert the student deliverables", e))?;
+    }
+    results.sort_by_key(|r| r.index);
+    let deliverable_trees = data.deliverable_trees.clone();
+    unit_of_work.after_commit(move || deliverable_trees.invalidate(project_id));
+
+    info!(
+        "{} deliverables, components and links imported into project {}",
+        results.len(),
+        project_id
+    );
+
+    Ok(HttpResponse::Created().json(DeliverablesImportResponse {
+        project_id,
+        results,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn items(value: serde_json::Value) -> Vec<DeliverableImportItem> {
+        serde_json::from_value::<DeliverablesImport>(json!({ "items": value }))
+            .unwrap()
+            .items
+    }
+
+    fn existing() -> HashMap<DeliverableType, ExistingLevel> {
+        HashMap::from([(
+            DeliverableType::Group,
+            ExistingLevel::new(
+                [("Rover".to_string(), 1)],
+                [("Wheel".to_string(), 7)],
+                [(1, 7, 70)],
+            ),
+        )])
+    }
+
+    fn problems(items: &[DeliverableImportItem]) -> serde_json::Value {
+        let err = plan_import(items, &existing()).unwrap_err();
+        assert_eq!(
+            actix_web::ResponseError::status_code(&err),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+        serde_json::to_value(&err).unwrap()["fields"].clone()
+    }
+
+    #[test]
+    fn test_valid_import_plans_the_full_structure() {
+        let items = items(json!([
+            {"type": "group", "deliverable": "Drone", "component": "Propeller", "quantity": 4, "weight": 60},
+            {"type": "group", "deliverable": "Drone"},
+            {"type": "group", "component": "Propeller", "sellable": true},
+            {"type": "group", "deliverable": "Rover", "component": "Propeller", "quantity": 2, "weight": 30},
+            {"type": "student", "deliverable": "Report"},
+            {"type": "student", "component": "Essay"},
+            {"type": "student", "deliverable": "Report", "component": "Essay", "quantity": 1, "weight": 100}
+        ]));
+
+        let plans = plan_import(&items, &existing()).unwrap();
+
+        assert_eq!(
+            plans[&DeliverableType::Group],
+            LevelPlan {
+                components: vec![PlannedComponent {
+                    index: 2,
+                    name: "Propeller",
+                    sellable: true
+                }],
+                deliverables: vec![PlannedDeliverable {
+                    index: 1,
+                    name: "Drone"
+                }],
+                links: vec![
+                    PlannedLink {
+                        index: 0,
+                        deliverable: "Drone",
+                        component: "Propeller",
+                        quantity: 4,
+                        weight: 60
+                    },
+                    PlannedLink {
+                        index: 3,
+                        deliverable: "Rover",
+                        component: "Propeller",
+                        quantity: 2,
+                        weight: 30
+                    },
+                ],
+            }
+        );
+        let student = &plans[&DeliverableType::Student];
+        assert_eq!(student.deliverables.len(), 1);
+        assert_eq!(student.components.len(), 1);
+        assert_eq!(student.links[0].index, 6);
+    }
+
+    #[test]
+    fn test_inconsistent_import_is_rejected_before_writing() {
+        let items = items(json!([
+            {"type": "group", "deliverable": "Rover"},
+            {"type": "group", "component": "Propeller"},
+            {"type": "group", "component": "Propeller"},
+            {"type": "student", "deliverable": "Report", "component": "Propeller", "quantity": 1, "weight": 10},
+            {"type": "group", "deliverable": "Rover", "component": "Wheel", "quantity": 1, "weight": 10},
+            {"type": "group", "deliverable": "Rover", "component": "Propeller", "quantity": 1, "weight": 40},
+            {"type": "student", "component": "Essay", "sellable": true}
+        ]));
+
+        let fields = problems(&items);
+
+        assert_eq!(
+            fields["items[0].deliverable"],
+            json!(["`Rover` already exists in the project"])
+        );
+        assert_eq!(
+            fields["items[2].component"],
+            json!(["Duplicate name `Propeller`"])
+        );
+        assert_eq!(
+            fields["items[3].deliverable"],
+            json!(["Unknown student deliverable `Report`"])
+        );
+        assert_eq!(
+            fields["items[3].component"],
+            json!(["Unknown student component `Propeller`"])
+        );
+        assert_eq!(
+            fields["items[4]"],
+            json!(["`Wheel` is already linked to `Rover`"])
+        );
+        assert!(fields.get("items[5]").is_some());
+        assert!(fields.get("items[6].sellable").is_some());
+    }
+
+    #[test]
+    fn test_csv_rows_are_read_as_items() {
+        let items = parse_csv_items(
+            "type,deliverable,component,quantity,weight,sellable\n\
+             group,Drone,,,,\n\
+             group,,Propeller,,,true\n\
+             group,Drone,Propeller,4,60,\n",
+        )
+        .unwrap();
+
+        let plans = plan_import(&items, &HashMap::new()).unwrap();
+        let group = &plans[&DeliverableType::Group];
+        assert_eq!(group.deliverables[0].name, "Drone");
+        assert!(group.components[0].sellable);
+        assert_eq!(group.links[0].quantity, 4);
+
+        let err = parse_csv_items("type,deliverable,colour\nrobot,Drone,red\n").unwrap_err();
+        let fields = serde_json::to_value(&err).unwrap()["fields"].clone();
+        assert!(fields["body"][0]
+            .as_str()
+            .unwrap()
+            .starts_with("Unknown column `colour`"));
+    }
+}
@@ -0,0 +1,94 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::database::repositories::projects_repository;
+use crate::jwt::get_user::LoggedUser;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use log::info;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct FreezeProjectRequest {
+    pub frozen: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct FreezeProjectResponse {
+    pub project_id: i32,
+    pub frozen: bool,
+}
+
+#[utoipa::path(
+    patch,
+    path = "/v1/admins/projects/{id}/frozen",
+    params(("id" = i32, Path, description = "Project ID")),
+    request_body = FreezeProjectRequest,
+    responses(
+        (status = 200, description = "Project frozen or unfrozen", body = FreezeProjectResponse),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 404, description = "Project not found", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Projects management",
+)]
+/// Freeze or unfreeze a project
+///
+/// While frozen, students cannot change the groups, selections, implementation details or
+/// uploads of the project and get a 409 with code `PROJECT_FROZEN`. Reads stay allowed and
+/// the phase and deadlines of the project are left untouched.
+#[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
+pub(in crate::api::v1) async fn freeze_project_handler(
+    req: HttpRequest, path: Path<i32>, body: Json<FreezeProjectRequest>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+    let project_id = path.into_inner();
+
+    let mut project_state = projects_repository::get_by_id(&data.db, project_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch project {}: {}", project_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?
+        .ok_or_else(|| {
+            error_with_log_id(
+                format!("project {} not found", project_id),
+                "Project not found",
+                StatusCode::NOT_FOUND,
+                log::Level::Warn,
+            )
+        })?;
+
+    project_state.as_mut().frozen = body.frozen;
+    project_state.save(&data.db).await.map_err(|e| {
+        error_with_log_id(
+            format!("unable to save project {}: {}", project_id, e),
+            "Database error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    info!(
+        "admin {} set frozen={} on project {}",
+        admin.admin_id, body.frozen, project_id
+    );
+
+    Ok(HttpResponse::Ok().json(FreezeProjectResponse {
+        project_id,
+        frozen: body.frozen,
+    }))
+}
@@ -0,0 +1,519 @@
+use crate::api::v1::admins::projects::create::{pick_slug, SLUG_TAKEN};
+use crate::app_data::AppData;
+use crate::common::created::created;
+use crate::common::deadlines::ProjectDeadlines;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError, ValidationError};
+use crate::common::link_weights::validate_link;
+use crate::common::slug::validate_slug;
+use crate::database::errors::is_unique_violation;
+use crate::database::unit_of_work::UnitOfWork;
+use crate::models::group_deliverable::GroupDeliverable;
+use crate::models::group_deliverable_component::GroupDeliverableComponent;
+use crate::models::group_deliverables_component::GroupDeliverablesComponent;
+use crate::models::project::Project;
+use crate::models::student_deliverable::StudentDeliverable;
+use crate::models::student_deliverable_component::StudentDeliverableComponent;
+use crate::models::student_deliverables_component::StudentDeliverablesComponent;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Json};
+use actix_web::HttpResponse;
+use chrono::{DateTime, Datelike, Utc};
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use utoipa::ToSchema;
+use welds::state::DbState;
+
+/// Version of the import format this build reads
+pub(crate) const IMPORT_FORMAT_VERSION: u32 = 1;
+
+/// Project with its deliverables and components, as exported or written by hand
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ProjectImport {
+    /// Version of the format, only `1` is supported
+    #[schema(example = 1)]
+    pub version: u32,
+    pub project: ImportedProject,
+    #[serde(default)]
+    pub student: ImportedLevel,
+    #[serde(default)]
+    pub group: ImportedLevel,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ImportedProject {
+    #[schema(example = "Robotics")]
+    pub name: String,
+    /// Generated from the name when missing
+    #[schema(example = "robotics")]
+    pub slug: Option<String>,
+    #[schema(example = 10)]
+    pub max_student_uploads: i32,
+    #[schema(example = 4)]
+    pub max_group_size: i32,
+    #[schema(value_type = Option<String>, example = "2025-12-15T23:59:59Z")]
+    pub deliverable_selection_deadline: Option<DateTime<Utc>>,
+    #[schema(value_type = Option<String>, example = "2025-12-20T23:59:59Z")]
+    pub upload_deadline: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub active: bool,
+}
+
+/// Deliverables, components and the links between them at one level of the project
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ImportedLevel {
+    /// Names of the deliverables, unique in the level
+    #[schema(example = json!(["Drone"]))]
+    #[serde(default)]
+    pub deliverables: Vec<String>,
+    #[serde(default)]
+    pub components: Vec<ImportedComponent>,
+    #[serde(default)]
+    pub links: Vec<ImportedLink>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ImportedComponent {
+    /// Unique in the level
+    #[schema(example = "Propeller")]
+    pub name: String,
+    /// Whether the component can be bought at the fair, only for group components
+    pub sellable: Option<bool>,
+}
+
+/// Component required by a deliverable, both referred to by name
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ImportedLink {
+    #[schema(example = "Drone")]
+    pub deliverable: String,
+    #[schema(example = "Propeller")]
+    pub component: String,
+    #[schema(example = 4)]
+    pub quantity: i32,
+    #[schema(example = 40)]
+    pub weight: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ImportProjectResponse {
+    project_id: i32,
+    slug: String,
+    student_deliverables: usize,
+    group_deliverables: usize,
+}
+
+/// Records the empty and repeated names of a list under `{prefix}[index]`
+fn check_names<'a>(
+    errors: &mut ValidationError, prefix: &str, names: impl Iterator<Item = &'a str>,
+) -> HashSet<&'a str> {
+    let mut seen = HashSet::new();
+    for (i, name) in names.enumerate() {
+        let field = format!("{}[{}]", prefix, i);
+        if name.trim().is_empty() {
+            errors.add(&field, "Name cannot be empty");
+        } else if !seen.insert(name) {
+            errors.add(&field, format!("Duplicate name `{}`", name));
+        }
+    }
+    seen
+}
+
+/// Checks that every link of a level refers to a deliverable and a component of that level
+fn check_level(errors: &mut ValidationError, level: &str, imported: &ImportedLevel) {
+    let deliverables = check_names(
+        errors,
+        &format!("{}.deliverables", level),
+        imported.deliverables.iter().map(String::as_str),
+    );
+    let components = check_names(
+        errors,
+        &format!("{}.components", level),
+        imported.components.iter().map(|c| c.name.as_str()),
+    );
+    if level == "student" {
+        for (i, component) in imported.components.iter().enumerate() {
+            if component.sellable.is_some() {
+                errors.add(
+                    &format!("student.components[{}].sellable", i),
+                    "Student components cannot be sold",
+                );
+            }
+        }
+    }
+
+    let mut linked = HashSet::new();
+    let mut weights: HashMap<&str, i32> = HashMap::new();
+    for (i, link) in imported.links.iter().enumerate() {
+        let field = format!("{}.links[{}]", level, i);
+        let mut dangling = false;
+        if !deliverables.contains(link.deliverable.as_str()) {
+            dangling = true;
+            errors.add(
+                &format!("{}.deliverable", field),
+                format!("Unknown {} deliverable `{}`", level, link.deliverable),
+            );
+        }
+        if !components.contains(link.component.as_str()) {
+            dangling = true;
+            errors.add(
+                &format!("{}.component", field),
+                format!("Unknown {} component `{}`", level, link.component),
+            );
+        }
+        if dangling {
+            continue;
+        }
+
+        if !linked.insert((link.deliverable.as_str(), link.component.as_str())) {
+            errors.add(
+                &field,
+                format!(
+                    "`{}` is already linked to `{}`",
+                    link.component, link.deliverable
+                ),
+            );
+            continue;
+        }
+        let other_weights = weights.entry(link.deliverable.as_str()).or_default();
+        match validate_link(link.quantity, link.weight, *other_weights) {
+            Ok(()) => *other_weights += link.weight,
+            Err(e) => errors.add(&field, e.message()),
+        }
+    }
+}
+
+/// Every structural problem of an import, found before anything is written
+fn validate_import(import: &ProjectImport, now: DateTime<Utc>) -> Result<(), ValidationError> {
+    let mut errors = ValidationError::default();
+
+    if import.version != IMPORT_FORMAT_VERSION {
+        errors.add(
+            "version",
+            format!(
+                "Unsupported version {}, expected {}",
+                import.version, IMPORT_FORMAT_VERSION
+            ),
+        );
+    }
+
+    let project = &import.project;
+    if project.name.trim().is_empty() {
+        errors.add("project.name", "Name field is mandatory");
+    }
+    if let Some(Err(e)) = project.slug.as_deref().map(validate_slug) {
+        errors.add("project.slug", e.message());
+    }
+    if project.max_student_uploads < 1 {
+        errors.add(
+            "project.max_student_uploads",
+            "Max student uploads must be greater than 0",
+        );
+    }
+    if project.max_group_size < 2 {
+        errors.add(
+            "project.max_group_size",
+            "Max group size must be greater than 1",
+        );
+    }
+    let deadlines = ProjectDeadlines {
+        deliverable_selection_deadline: project.deliverable_selection_deadline,
+        upload_deadline: project.upload_deadline,
+    };
+    if let Err(e) = deadlines
+        .validate_order()
+        .and_then(|()| deadlines.validate_not_past(now))
+    {
+        errors.add("project", e.message());
+    }
+
+    check_level(&mut errors, "student", &import.student);
+    check_level(&mut errors, "group", &import.group);
+
+    errors
+        .into_result()
+        .map_err(|e| e.with_status(StatusCode::UNPROCESSABLE_ENTITY))
+}
+
+/// Reads the import, reporting a missing or mistyped field as a structural problem
+fn parse_import(body: serde_json::Value) -> Result<ProjectImport, ValidationError> {
+    serde_json::from_value(body).map_err(|e| {
+        let mut errors = ValidationError::default();
+        errors.add("body", e.to_string());
+        errors.with_status(StatusCode::UNPROCESSABLE_ENTITY)
+    })
+}
+
+/// Inserts a validated import, pass a transaction to keep either all of it or nothing
+async fn insert_import(
+    db: &impl welds::Client, import: &ProjectImport, slug: String, year: i32, published: bool,
+) -> welds::errors::Result<i32> {
+    let imported = &import.project;
+    let deadlines = ProjectDeadlines {
+        deliverable_selection_deadline: imported.deliverable_selection_deadline,
+        upload_deadline: imported.upload_deadline,
+    }
+    .normalized();
+    let mut project = DbState::new_uncreated(Project {
+        project_id: 0,
+        name: imported.name.clone(),
+        slug,
+        year,
+        max_student_uploads: imported.max_student_uploads,
+        max_group_size: imported.max_group_size,
+        deliverable_selection_deadline: deadlines.deliverable_selection_deadline,
+        upload_deadline: deadlines.upload_deadline,
+        active: imported.active,
+        oral_exam_enabled: false,
+        frozen: false,
+        allowed_signup_domains: None,
+        published,
+    });
+    project.save(db).await?;
+    let project_id = project.project_id;
+
+    let mut components = HashMap::new();
+    for component in &import.student.components {
+        let mut state = DbState::new_uncreated(StudentDeliverableComponent {
+            student_deliverable_component_id: 0,
+            project_id,
+            name: component.name.clone(),
+        });
+        state.save(db).await?;
+        components.insert(&component.name, state.student_deliverable_component_id);
+    }
+    let mut deliverables = HashMap::new();
+    for name in &import.student.deliverables {
+        let mut state = DbState::new_uncreated(StudentDeliverable {
+            student_deliverable_id: 0,
+            project_id,
+            name: name.clone(),
+        });
+        state.save(db).await?;
+        deliverables.insert(name, state.student_deliverable_id);
+    }
+    for link in &import.student.links {
+        let mut state = DbState::new_uncreated(StudentDeliverablesComponent {
+            id: 0,
+            student_deliverable_id: deliverables[&link.deliverable],
+            student_deliverable_component_id: components[&link.component],
+            quantity: link.quantity,
+            weight: link.weight,
+        });
+        state.save(db).await?;
+    }
+
+    let mut components = HashMap::new();
+    for component in &import.group.components {
+        let mut state = DbState::new_uncreated(GroupDeliverableComponent {
+            group_deliverable_component_id: 0,
+            project_id,
+            name: component.name.clone(),
+            sellable: component.sellable.unwrap_or(false),
+        });
+        state.save(db).await?;
+        components.insert(&component.name, state.group_deliverable_component_id);
+    }
+    let mut deliverables = HashMap::new();
+    for name in &import.group.deliverables {
+        let mut state = DbState::new_uncreated(GroupDeliverable {
+            group_deliverable_id: 0,
+            project_id,
+            name: name.clone(),
+        });
+        state.save(db).await?;
+        deliverables.insert(name, state.group_deliverable_id);
+    }
+    for link in &import.group.links {
+        let mut state = DbState::new_uncreated(GroupDeliverablesComponent {
+            id: 0,
+            group_deliverable_id: deliverables[&link.deliverable],
+            group_deliverable_component_id: components[&link.component],
+            quantity: link.quantity,
+            weight: link.weight,
+        });
+        state.save(db).await?;
+    }
+
+    Ok(project_id)
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/admins/projects/import",
+    request_body = ProjectImport,
+    responses(
+        (status = 201, description = "Project imported with its deliverables and components", body = ImportProjectResponse,
+            headers(("Location" = String, description = "URL of the created project"))
+        ),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 409, description = "Slug already in use", body = JsonError),
+        (status = 422, description = "Structural problems of the document, listed per field", body = ValidationError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Projects management",
+)]
+/// Import a project with its deliverables, components and links
+///
+/// The whole document is checked before anything is written: missing fields, unknown
+/// versions, duplicate names and links referring to a deliverable or component the
+/// document does not define are all reported at once. Everything is then inserted in
+/// one transaction.
+#[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
+pub(in crate::api::v1) async fn import_project_handler(
+    body: Json<serde_json::Value>, data: Data<AppData>, unit_of_work: UnitOfWork,
+) -> actix_web::Result<HttpResponse> {
+    let import = parse_import(body.into_inner())?;
+    let now = data.clock.now();
+    validate_import(&import, now)?;
+
+    let slug = pick_slug(&data, &import.project.name, import.project.slug.as_deref()).await?;
+    let project_id = insert_import(
+        &unit_of_work,
+        &import,
+        slug.clone(),
+        now.year(),
+        data.config.publish_projects_on_create(),
+    )
+    .await
+    .map_err(|e| {
+        if is_unique_violation(&e) {
+            return SLUG_TAKEN.to_json_error(StatusCode::CONFLICT);
+        }
+        error_with_log_id(
+            format!("unable to import project: {}", e),
+            "Failed to import project",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    info!(
+        "project {} imported with {} student and {} group deliverables",
+        project_id,
+        import.student.deliverables.len(),
+        import.group.deliverables.len()
+    );
+
+    Ok(created(
+        "/v1/admins/projects",
+        project_id,
+        &ImportProjectResponse {
+            project_id,
+            slug,
+            student_deliverables: import.student.deliverables.len(),
+            group_deliverables: import.group.deliverables.len(),
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn document() -> serde_json::Value {
+        json!({
+            "version": 1,
+            "project": {
+                "name": "Robotics",
+                "max_student_uploads": 5,
+                "max_group_size": 4
+            },
+            "student": {
+                "deliverables": ["Report"],
+                "components": [{"name": "Essay"}],
+                "links": [{"deliverable": "Report", "component": "Essay", "quantity": 1, "weight": 100}]
+            },
+            "group": {
+                "deliverables": ["Drone"],
+                "components": [{"name": "Propeller", "sellable": true}],
+                "links": [{"deliverable": "Drone", "component": "Propeller", "quantity": 4, "weight": 60}]
+            }
+        })
+    }
+
+    fn problems(document: serde_json::Value) -> serde_json::Value {
+        let err = parse_import(document)
+            .and_then(|import| validate_import(&import, Utc::now()))
+            .unwrap_err();
+        assert_eq!(
+            actix_web::ResponseError::status_code(&err),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+        serde_json::to_value(&err).unwrap()["fields"].clone()
+    }
+
+    #[test]
+    fn test_consistent_import_is_accepted() {
+        let import = parse_import(document()).unwrap();
+
+        assert!(validate_import(&import, Utc::now()).is_ok());
+    }
+
+    #[test]
+    fn test_link_to_a_missing_deliverable_is_rejected() {
+        let mut document = document();
+        document["group"]["links"][0]["deliverable"] = json!("Rover");
+        document["student"]["links"][0]["component"] = json!("Slides");
+
+        let fields = problems(document);
+
+        assert_eq!(
+            fields["group.links[0].deliverable"],
+            json!(["Unknown group deliverable `Rover`"])
+        );
+        assert_eq!(
+            fields["student.links[0].component"],
+            json!(["Unknown student component `Slides`"])
+        );
+    }
+
+    #[test]
+    fn test_missing_fields_and_duplicates_are_reported() {
+        let mut document = document();
+        document["project"]
+            .as_object_mut()
+            .unwrap()
+            .remove("max_group_size");
+
+        let fields = problems(document);
+        assert!(fields["body"][0]
+            .as_str()
+            .unwrap()
+            .contains("missing field `max_group_size`"));
+
+        let mut document = self::document();
+        document["version"] = json!(2);
+        document["group"]["deliverables"] = json!(["Drone", "Drone"]);
+        document["student"]["components"][0]["sellable"] = json!(true);
+
+        let fields = problems(document);
+        assert!(fields.get("version").is_some());
+        assert_eq!(
+            fields["group.deliverables[1]"],
+            json!(["Duplicate name `Drone`"])
+        );
+        assert!(fields.get("student.components[0].sellable").is_some());
+    }
+
+    #[test]
+    fn test_links_over_the_weight_budget_are_rejected() {
+        let mut document = document();
+        document["group"]["components"] = json!([{"name": "Propeller"}, {"name": "Frame"}]);
+        document["group"]["links"] = json!([
+            {"deliverable": "Drone", "component": "Propeller", "quantity": 4, "weight": 60},
+            {"deliverable": "Drone", "component": "Frame", "quantity": 1, "weight": 50}
+        ]);
+
+        let fields = problems(document);
+
+        assert!(fields.get("group.links[0]").is_none());
+        assert!(fields.get("group.links[1]").is_some());
+    }
+}
@@ -0,0 +1,205 @@
+use crate::app_data::AppData;
+use crate::common::access::{ensure_admin_sees_project, found_or_not_found};
+use crate::common::fields::{select_fields, FieldsQuery};
+use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::common::pagination::{count_response, CountQuery, PaginationQuery};
+use crate::database::repositories::projects_repository;
+use crate::jwt::get_user::LoggedUser;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Path, Query};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+const PROJECT_NOT_FOUND: &str = "Project not found";
+
+/// Filters of the project members listing
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub(crate) struct ProjectMembersQuery {
+    /// Case insensitive match on the first name, last name, full name or email
+    pub search: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ProjectMember {
+    #[schema(example = 12)]
+    pub student_id: i32,
+    #[schema(example = "Mario")]
+    pub first_name: String,
+    #[schema(example = "Rossi")]
+    pub last_name: String,
+    #[schema(example = "mario.rossi@studenti.unitn.it")]
+    pub email: String,
+    #[schema(example = 4)]
+    pub group_id: i32,
+    #[schema(example = "Team Rocket")]
+    pub group_name: String,
+    #[schema(example = 1)]
+    pub student_role_id: i32,
+}
+
+/// Fields of a member that can be selected with `fields`
+const MEMBER_FIELDS: &[&str] = &[
+    "student_id",
+    "first_name",
+    "last_name",
+    "email",
+    "group_id",
+    "group_name",
+    "student_role_id",
+];
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ProjectMembersResponse {
+    pub project_id: i32,
+    /// Members with only the requested fields when `fields` is set
+    #[schema(value_type = Vec<ProjectMember>)]
+    pub members: Vec<serde_json::Value>,
+}
+
+/// `ILIKE` pattern matching `search` anywhere, `None` when there is nothing to search
+///
+/// Wildcards typed by the client are matched literally.
+fn search_pattern(search: Option<&str>) -> Option<String> {
+    let search = search.map(str::trim).filter(|s| !s.is_empty())?;
+    let escaped = search
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    Some(format!("%{}%", escaped))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/admins/projects/{id}/members",
+    params(("id" = i32, Path, description = "Project ID"), ProjectMembersQuery, PaginationQuery, FieldsQuery, CountQuery),
+    responses(
+        (status = 200, description = "Page of the students in any group of the project, sorted by name. With `envelope=true` the body is `{ data, meta }`, with `count_only=true` only `{ total }`", body = ProjectMembersResponse,
+            headers(
+                ("X-Total-Count" = u64, description = "Total number of members matching the search"),
+                ("X-Page" = u32, description = "Returned page"),
+                ("X-Per-Page" = u32, description = "Page size"),
+                ("Link" = String, description = "RFC 5988 links to the first, prev, next and last pages"),
+            )
+        ),
+        (status = 400, description = "Unknown field requested", body = JsonError),
+        (status = 404, description = "Project not found or not visible to the caller", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Projects management",
+)]
+/// List the members of every group of a project
+///
+/// Members are searched and paginated across all the groups of the project, so large
+/// projects can be browsed without loading every group. Coordinators can only list the
+/// members of projects they are assigned to. With `fields` only the listed fields of each
+/// member are returned, and with `count_only` the members are only counted.
+#[allow(clippy::too_many_arguments)]
+#[actix_web_grants::protect(any(
+    "ROLE_ADMIN_ROOT",
+    "ROLE_ADMIN_PROFESSOR",
+    "ROLE_ADMIN_COORDINATOR"
+))]
+pub(in crate::api::v1) async fn list_project_members_handler(
+    req: HttpRequest, path: Path<i32>, filters: Query<ProjectMembersQuery>,
+    pagination: Query<PaginationQuery>, fields: Query<FieldsQuery>, count: Query<CountQuery>,
+    data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let fields = fields.parse(MEMBER_FIELDS)?;
+
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let project_id = path.into_inner();
+    let pagination = pagination.into_inner().within(data.page_limits);
+
+    ensure_admin_sees_project(&data.db, &admin, project_id, PROJECT_NOT_FOUND).await?;
+
+    let project = projects_repository::get_by_id(&data.db, project_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch project {}: {}", project_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+    found_or_not_found(project, PROJECT_NOT_FOUND)?;
+
+    let pattern = search_pattern(filters.search.as_deref());
+    let db_error = |what: &str, e: welds::WeldsError| {
+        error_with_log_id(
+            format!("unable to {} of project {}: {}", what, project_id, e),
+            "Failed to retrieve project members",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    };
+
+    let total = projects_repository::count_members(&data.db, project_id, pattern.clone())
+        .await
+        .map_err(|e| db_error("count the members", e))?;
+    if count.is_set() {
+        return Ok(count_response(total));
+    }
+
+    let members = projects_repository::get_members(
+        &data.db,
+        project_id,
+        pattern,
+        pagination.limit(),
+        pagination.offset(),
+    )
+    .await
+    .map_err(|e| db_error("fetch the members", e))?
+    .into_iter()
+    .map(|row| ProjectMember {
+        student_id: row.student_id,
+        first_name: row.first_name,
+        last_name: row.last_name,
+        email: row.email,
+        group_id: row.group_id,
+        group_name: row.group_name,
+        student_role_id: row.student_role_id,
+    })
+    .collect();
+
+    let members = select_fields(members, fields.as_ref());
+    Ok(
+        pagination.respond(&req, members, total, |members| ProjectMembersResponse {
+            project_id,
+            members,
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_matches_anywhere_and_escapes_wildcards() {
+        let query = Query::<ProjectMembersQuery>::from_query("search=%20Rossi%20")
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(
+            search_pattern(query.search.as_deref()).as_deref(),
+            Some("%Rossi%")
+        );
+        assert_eq!(
+            search_pattern(Some("100%_a\\b")).as_deref(),
+            Some("%100\\%\\_a\\\\b%")
+        );
+        assert_eq!(search_pattern(Some("   ")), None);
+        assert_eq!(search_pattern(None), None);
+    }
+}
@@ -1,25 +1,91 @@
+use crate::api::v1::admins::projects::activity::get_project_activity_handler;
 use crate::api::v1::admins::projects::coordinators::{
     assign_coordinator, list_coordinators, remove_coordinator,
 };
 use crate::api::v1::admins::projects::create::create_project_handler;
 use crate::api::v1::admins::projects::delete::delete_project_handler;
+use crate::api::v1::admins::projects::deliverables_import::import_deliverables_handler;
+use crate::api::v1::admins::projects::freeze::freeze_project_handler;
+use crate::api::v1::admins::projects::import::import_project_handler;
+use crate::api::v1::admins::projects::members::list_project_members_handler;
+use crate::api::v1::admins::projects::nudge::nudge_students_handler;
+use crate::api::v1::admins::projects::progress::get_project_progress_handler;
+use crate::api::v1::admins::projects::publish::publish_project_handler;
 use crate::api::v1::admins::projects::read::{get_all_projects_handler, get_one_project_handler};
+use crate::api::v1::admins::projects::revoke_sessions::revoke_sessions_handler;
+use crate::api::v1::admins::projects::rotate_codes::rotate_codes_handler;
+use crate::api::v1::admins::projects::selections_export::export_selections_handler;
+use crate::api::v1::admins::projects::signup_domains::set_signup_domains_handler;
+use crate::api::v1::admins::projects::ungrouped::list_ungrouped_students_handler;
 use crate::api::v1::admins::projects::update::update_project_handler;
+use crate::database::unit_of_work::UnitOfWorkMiddleware;
 use actix_web::{web, Scope};
 
+pub(crate) mod activity;
 pub(crate) mod coordinators;
 pub(crate) mod create;
 pub(crate) mod delete;
+pub(crate) mod deliverables_import;
+pub(crate) mod freeze;
+pub(crate) mod import;
+pub(crate) mod members;
+pub(crate) mod nudge;
+pub(crate) mod progress;
+pub(crate) mod publish;
 pub(crate) mod read;
+pub(crate) mod revoke_sessions;
+pub(crate) mod rotate_codes;
+pub(crate) mod selections_export;
+pub(crate) mod signup_domains;
+pub(crate) mod ungrouped;
 pub(crate) mod update;
 
 pub(super) fn projects_scope() -> Scope {
     web::scope("/projects")
         .route("", web::post().to(create_project_handler))
         .route("", web::get().to(get_all_projects_handler))
+        .service(
+            web::resource("/import")
+                .wrap(UnitOfWorkMiddleware)
+                .route(web::post().to(import_project_handler)),
+        )
         .route("/{id}", web::get().to(get_one_project_handler))
         .route("/{id}", web::patch().to(update_project_handler))
         .route("/{id}", web::delete().to(delete_project_handler))
+        .route(
+            "/{id}/progress",
+            web::get().to(get_project_progress_handler),
+        )
+        .route(
+            "/{id}/activity",
+            web::get().to(get_project_activity_handler),
+        )
+        .route("/{id}/members", web::get().to(list_project_members_handler))
+        .route(
+            "/{id}/ungrouped-students",
+            web::get().to(list_ungrouped_students_handler),
+        )
+        .route("/{id}/nudge", web::post().to(nudge_students_handler))
+        .route("/{id}/frozen", web::patch().to(freeze_project_handler))
+        .route("/{id}/publish", web::post().to(publish_project_handler))
+        .route("/{id}/rotate-codes", web::post().to(rotate_codes_handler))
+        .route(
+            "/{id}/revoke-sessions",
+            web::post().to(revoke_sessions_handler),
+        )
+        .route(
+            "/{id}/signup-domains",
+            web::put().to(set_signup_domains_handler),
+        )
+        .service(
+            web::resource("/{id}/deliverables/import")
+                .wrap(UnitOfWorkMiddleware)
+                .route(web::post().to(import_deliverables_handler)),
+        )
+        .route(
+            "/{id}/selections/export",
+            web::get().to(export_selections_handler),
+        )
         .route(
             "/{project_id}/coordinators",
             web::post().to(assign_coordinator),
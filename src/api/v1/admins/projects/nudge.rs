@@ -0,0 +1,414 @@
+use crate::app_data::AppData;
+use crate::common::access::found_or_not_found;
+use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::database::repositories::projects_repository;
+use crate::database::timing::timed;
+use crate::jwt::get_user::LoggedUser;
+use crate::mail::Mailer;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Path, Query};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use tokio::task::JoinHandle;
+use utoipa::{IntoParams, ToSchema};
+
+const PROJECT_NOT_FOUND: &str = "Project not found";
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct NudgeQuery {
+    /// Only list the students that would be emailed, without sending anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// What a nudged student still has to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum NudgeReason {
+    /// The student is in none of the groups of the project
+    Ungrouped,
+    /// The group of the student has not selected a deliverable yet
+    NoSelection,
+    /// The group selected a deliverable but did not detail all its components
+    IncompleteSelection,
+    /// The student has not selected one of the student deliverables of the project
+    NoStudentSelection,
+}
+
+impl NudgeReason {
+    /// Sentence completing the reminder of the email
+    fn reminder(self) -> &'static str {
+        match self {
+            NudgeReason::Ungrouped => "you are not in a group yet",
+            NudgeReason::NoSelection => "your group has not selected a deliverable yet",
+            NudgeReason::IncompleteSelection => {
+                "your group has not detailed all the components of its deliverable yet"
+            }
+            NudgeReason::NoStudentSelection => "you have not selected your own deliverable yet",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub(crate) struct NudgeRecipient {
+    #[schema(example = 12)]
+    pub student_id: i32,
+    #[schema(example = "Mario")]
+    pub first_name: String,
+    #[schema(example = "Rossi")]
+    pub last_name: String,
+    #[schema(example = "mario.rossi@studenti.unitn.it")]
+    pub email: String,
+    pub reason: NudgeReason,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct NudgeResponse {
+    pub project_id: i32,
+    #[schema(example = false)]
+    pub dry_run: bool,
+    /// Emails queued for sending, always 0 on a dry run
+    #[schema(example = 3)]
+    pub queued: usize,
+    /// Students targeted by the reminder
+    pub recipients: Vec<NudgeRecipient>,
+}
+
+/// Why a student with access to the project needs a reminder, if they do
+///
+/// `group` is `None` for ungrouped students, otherwise whether the group selected a
+/// deliverable with the number of required and completed components. `missing_student_selection`
+/// tells whether the project has student deliverables and the student selected none of them.
+/// The group comes first, so a student gets a single reminder.
+fn nudge_reason(
+    group: Option<(bool, i64, i64)>, missing_student_selection: bool,
+) -> Option<NudgeReason> {
+    match group {
+        None => Some(NudgeReason::Ungrouped),
+        Some((false, _, _)) => Some(NudgeReason::NoSelection),
+        Some((true, required, completed)) if completed < required => {
+            Some(NudgeReason::IncompleteSelection)
+        }
+        Some(_) if missing_student_selection => Some(NudgeReason::NoStudentSelection),
+        Some(_) => None,
+    }
+}
+
+/// Sends the reminders in the background, one after the other
+///
+/// A failed email is logged and does not stop the following ones.
+fn queue_nudges(
+    mailer: Mailer, project_name: String, recipients: Vec<NudgeRecipient>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        for recipient in recipients {
+            let name = format!("{} {}", recipient.first_name, recipient.last_name);
+            if let Err(e) = mailer
+                .send_project_nudge(
+                    recipient.email,
+                    name,
+                    &project_name,
+                    recipient.reason.reminder(),
+                )
+                .await
+            {
+                error!(
+                    "failed to send the {} reminder to student {}: {}",
+                    project_name, recipient.student_id, e
+                );
+            }
+        }
+    })
+}
+
+/// Queues the reminders unless on a dry run, returning the task sending them
+fn nudge(
+    mailer: &Mailer, project_id: i32, project_name: String, recipients: Vec<NudgeRecipient>,
+    dry_run: bool,
+) -> (NudgeResponse, Option<JoinHandle<()>>) {
+    let queue = (!dry_run).then(|| queue_nudges(mailer.clone(), project_name, recipients.clone()));
+
+    let response = NudgeResponse {
+        project_id,
+        dry_run,
+        queued: if dry_run { 0 } else { recipients.len() },
+        recipients,
+    };
+    (response, queue)
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/admins/projects/{id}/nudge",
+    params(("id" = i32, Path, description = "Project ID"), NudgeQuery),
+    responses(
+        (status = 202, description = "Reminders queued for sending", body = NudgeResponse),
+        (status = 200, description = "Dry run, students that would be reminded", body = NudgeResponse),
+        (status = 404, description = "Project not found", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Projects management",
+)]
+/// Email a reminder to the students of a project that are behind
+///
+/// Students with access to the project are reminded when they are in no group, when
+/// their group has not selected a deliverable or not detailed all its required
+/// components, or when they have not selected one of the student deliverables of the
+/// project. The emails are sent in the background and the response only tells how
+/// many were queued; with `dry_run=true` nothing is sent.
+#[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
+pub(in crate::api::v1) async fn nudge_students_handler(
+    req: HttpRequest, path: Path<i32>, query: Query<NudgeQuery>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let project_id = path.into_inner();
+    let dry_run = query.dry_run;
+
+    let project = projects_repository::get_by_id(&data.db, project_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch project {}: {}", project_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+    let project = found_or_not_found(project, PROJECT_NOT_FOUND)?;
+
+    let rows = timed(
+        "projects.nudge_recipients",
+        sqlx::query(
+            r#"
+        SELECT
+            s.student_id, s.first_name, s.last_name, s.email,
+            g.group_id,
+            gds.group_deliverable_id,
+            COUNT(DISTINCT gdc.group_deliverable_component_id) AS required_components,
+            COUNT(DISTINCT gcid.group_deliverable_component_id) AS completed_components,
+            EXISTS (SELECT 1 FROM student_deliverables sd WHERE sd.project_id = $1)
+                AND NOT EXISTS (SELECT 1
+                                FROM student_deliverable_selections sds
+                                JOIN student_deliverables sd
+                                    ON sd.student_deliverable_id = sds.student_deliverable_id
+                                WHERE sds.student_id = s.student_id
+                                  AND sd.project_id = $1) AS missing_student_selection
+        FROM student_project_access spa
+        JOIN students s ON s.student_id = spa.student_id
+        LEFT JOIN (group_members gm JOIN groups g ON g.group_id = gm.group_id)
+            ON gm.student_id = spa.student_id AND g.project_id = spa.project_id
+        LEFT JOIN group_deliverable_selections gds
+            ON gds.group_id = g.group_id
+        LEFT JOIN group_deliverables_components gdc
+            ON gdc.group_deliverable_id = gds.group_deliverable_id
+        LEFT JOIN group_component_implementation_details gcid
+            ON gcid.group_deliverable_selection_id = gds.group_deliverable_selection_id
+            AND gcid.group_deliverable_component_id = gdc.group_deliverable_component_id
+        WHERE spa.project_id = $1
+        GROUP BY s.student_id, s.first_name, s.last_name, s.email, g.group_id,
+                 gds.group_deliverable_id
+        ORDER BY s.last_name, s.first_name, s.student_id
+        "#,
+        )
+        .bind(project_id)
+        .fetch_all(data.db.as_sqlx_pool()),
+    )
+    .await
+    .map_err(|e| {
+        error_with_log_id(
+            format!(
+                "unable to find the students to remind of project {}: {}",
+                project_id, e
+            ),
+            "Failed to find the students to remind",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let recipients: Vec<NudgeRecipient> = rows
+        .into_iter()
+        .filter_map(|row| {
+            let group_id: Option<i32> = row.get("group_id");
+            let group_deliverable_id: Option<i32> = row.get("group_deliverable_id");
+            let group = group_id.map(|_| {
+                (
+                    group_deliverable_id.is_some(),
+                    row.get::<i64, _>("required_components"),
+                    row.get::<i64, _>("completed_components"),
+                )
+            });
+
+            nudge_reason(group, row.get("missing_student_selection")).map(|reason| NudgeRecipient {
+                student_id: row.get("student_id"),
+                first_name: row.get("first_name"),
+                last_name: row.get("last_name"),
+                email: row.get("email"),
+                reason,
+            })
+        })
+        .collect();
+
+    let (response, queue) = nudge(
+        &data.mailer,
+        project_id,
+        project.name.clone(),
+        recipients,
+        dry_run,
+    );
+    if queue.is_none() {
+        return Ok(HttpResponse::Ok().json(response));
+    }
+
+    info!(
+        "admin {} queued {} reminders for project {}",
+        admin.admin_id, response.queued, project_id
+    );
+    Ok(HttpResponse::Accepted().json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_app_data, TEST_FRONTEND_URL};
+    use actix_web::dev::ServiceRequest;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::{web, App, Error};
+    use actix_web_grants::GrantsMiddleware;
+    use std::collections::HashSet;
+
+    fn recipient(student_id: i32, reason: NudgeReason) -> NudgeRecipient {
+        NudgeRecipient {
+            student_id,
+            first_name: "Mario".to_string(),
+            last_name: "Rossi".to_string(),
+            email: format!("student{}@studenti.unitn.it", student_id),
+            reason,
+        }
+    }
+
+    fn memory_mailer() -> Mailer {
+        Mailer::in_memory("Test Sender", "noreply@test.com", TEST_FRONTEND_URL).unwrap()
+    }
+
+    #[test]
+    fn test_ungrouped_and_incomplete_students_are_targeted() {
+        assert_eq!(nudge_reason(None, false), Some(NudgeReason::Ungrouped));
+        assert_eq!(
+            nudge_reason(Some((false, 0, 0)), false),
+            Some(NudgeReason::NoSelection)
+        );
+        assert_eq!(
+            nudge_reason(Some((true, 4, 3)), false),
+            Some(NudgeReason::IncompleteSelection)
+        );
+    }
+
+    #[test]
+    fn test_students_without_their_own_selection_are_targeted() {
+        assert_eq!(
+            nudge_reason(Some((true, 4, 4)), true),
+            Some(NudgeReason::NoStudentSelection)
+        );
+        // The group reminder comes first
+        assert_eq!(nudge_reason(None, true), Some(NudgeReason::Ungrouped));
+        assert_eq!(
+            nudge_reason(Some((true, 4, 3)), true),
+            Some(NudgeReason::IncompleteSelection)
+        );
+    }
+
+    #[test]
+    fn test_complete_students_are_left_alone() {
+        assert_eq!(nudge_reason(Some((true, 4, 4)), false), None);
+        assert_eq!(nudge_reason(Some((true, 0, 0)), false), None);
+    }
+
+    #[actix_web::test]
+    async fn test_queued_reminders_reach_every_recipient() {
+        let mailer = memory_mailer();
+        let recipients = vec![
+            recipient(3, NudgeReason::Ungrouped),
+            recipient(5, NudgeReason::IncompleteSelection),
+        ];
+
+        queue_nudges(mailer.clone(), "Rusty Robots".to_string(), recipients)
+            .await
+            .unwrap();
+
+        let sent = mailer.sent().await;
+        let to: Vec<_> = sent.iter().map(|(to, _)| to.clone()).collect();
+        assert_eq!(
+            to,
+            vec![
+                vec!["student3@studenti.unitn.it".to_string()],
+                vec!["student5@studenti.unitn.it".to_string()],
+            ]
+        );
+        assert!(sent[0].1.contains("Reminder: Rusty Robots"));
+    }
+
+    #[actix_web::test]
+    async fn test_dry_run_lists_recipients_and_sends_nothing() {
+        let mailer = memory_mailer();
+        let recipients = vec![recipient(3, NudgeReason::Ungrouped)];
+
+        let (response, queue) = nudge(&mailer, 1, "Rusty Robots".to_string(), recipients, true);
+
+        assert!(queue.is_none());
+        assert_eq!(response.queued, 0);
+        assert_eq!(response.recipients[0].student_id, 3);
+        assert!(mailer.sent().await.is_empty());
+    }
+
+    #[actix_web::test]
+    async fn test_nudge_returns_the_count_queued() {
+        let mailer = memory_mailer();
+        let recipients = vec![
+            recipient(3, NudgeReason::Ungrouped),
+            recipient(4, NudgeReason::NoSelection),
+        ];
+
+        let (response, queue) = nudge(&mailer, 1, "Rusty Robots".to_string(), recipients, false);
+        queue.unwrap().await.unwrap();
+
+        assert_eq!(response.queued, 2);
+        assert_eq!(mailer.sent().await.len(), 2);
+    }
+
+    async fn coordinator_grants(_req: &ServiceRequest) -> Result<HashSet<String>, Error> {
+        Ok(HashSet::from(["ROLE_ADMIN_COORDINATOR".to_string()]))
+    }
+
+    #[actix_web::test]
+    async fn test_coordinators_cannot_nudge_students() {
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(create_test_app_data().await))
+                .wrap(GrantsMiddleware::with_extractor(coordinator_grants))
+                .route(
+                    "/projects/{id}/nudge",
+                    web::post().to(nudge_students_handler),
+                ),
+        )
+        .await;
+
+        let res = call_service(
+            &app,
+            TestRequest::post().uri("/projects/1/nudge").to_request(),
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    }
+}
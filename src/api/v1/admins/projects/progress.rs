@@ -0,0 +1,157 @@
+use crate::app_data::AppData;
+use crate::common::access::{ensure_admin_sees_project, found_or_not_found};
+use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::database::repositories::projects_repository;
+use crate::jwt::get_user::LoggedUser;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Path};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+const PROJECT_NOT_FOUND: &str = "Project not found";
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct GroupProgress {
+    #[schema(example = 4)]
+    pub group_id: i32,
+    #[schema(example = "Team Rocket")]
+    pub group_name: String,
+    /// Deliverable selected by the group, if any
+    #[schema(example = 2)]
+    pub group_deliverable_id: Option<i32>,
+    /// Components required by the selected deliverable
+    #[schema(example = 4)]
+    pub required_components: i64,
+    /// Required components the group has provided implementation details for
+    #[schema(example = 3)]
+    pub completed_components: i64,
+    #[schema(example = 75.0)]
+    pub completion_percentage: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ProjectProgressResponse {
+    pub project_id: i32,
+    pub groups: Vec<GroupProgress>,
+}
+
+/// Share of the required components a group has completed, from 0 to 100
+///
+/// Groups without a selected deliverable are at 0, a selected deliverable
+/// without required components counts as complete.
+fn completion_percentage(has_selection: bool, required: i64, completed: i64) -> f64 {
+    if !has_selection {
+        return 0.0;
+    }
+
+    if required == 0 {
+        return 100.0;
+    }
+
+    let ratio = completed.min(required) as f64 / required as f64;
+    (ratio * 10_000.0).round() / 100.0
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/admins/projects/{id}/progress",
+    params(("id" = i32, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Completion percentage of every group of the project", body = ProjectProgressResponse),
+        (status = 404, description = "Project not found or not visible to the caller", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Projects management",
+)]
+/// Get the completion progress of every group of a project
+///
+/// For each group the required components are the ones linked to its selected deliverable,
+/// a component is completed once the group has provided its implementation details.
+/// Coordinators can only see the progress of projects they are assigned to.
+#[actix_web_grants::protect(any(
+    "ROLE_ADMIN_ROOT",
+    "ROLE_ADMIN_PROFESSOR",
+    "ROLE_ADMIN_COORDINATOR"
+))]
+pub(in crate::api::v1) async fn get_project_progress_handler(
+    req: HttpRequest, path: Path<i32>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let project_id = path.into_inner();
+
+    ensure_admin_sees_project(&data.db, &admin, project_id, PROJECT_NOT_FOUND).await?;
+
+    let project = projects_repository::get_by_id(&data.db, project_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch project {}: {}", project_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+    found_or_not_found(project, PROJECT_NOT_FOUND)?;
+
+    let groups = projects_repository::progress(&data.db, project_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!(
+                    "unable to compute progress for project {}: {}",
+                    project_id, e
+                ),
+                "Failed to compute project progress",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?
+        .into_iter()
+        .map(|group| GroupProgress {
+            completion_percentage: completion_percentage(
+                group.group_deliverable_id.is_some(),
+                group.required_components,
+                group.completed_components,
+            ),
+            group_id: group.group_id,
+            group_name: group.group_name,
+            group_deliverable_id: group.group_deliverable_id,
+            required_components: group.required_components,
+            completed_components: group.completed_components,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(ProjectProgressResponse { project_id, groups }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_selections_give_partial_percentages() {
+        assert_eq!(completion_percentage(true, 4, 3), 75.0);
+        assert_eq!(completion_percentage(true, 3, 1), 33.33);
+        assert_eq!(completion_percentage(true, 4, 4), 100.0);
+    }
+
+    #[test]
+    fn test_groups_without_selection_are_at_zero() {
+        assert_eq!(completion_percentage(false, 0, 0), 0.0);
+    }
+
+    #[test]
+    fn test_deliverable_without_required_components_is_complete() {
+        assert_eq!(completion_percentage(true, 0, 0), 100.0);
+    }
+}
@@ -0,0 +1,176 @@
+use crate::app_data::AppData;
+use crate::common::access::found_or_not_found;
+use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::database::repositories::projects_repository;
+use crate::jwt::get_user::LoggedUser;
+use crate::models::project::Project;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Path};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use log::info;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct PublishProjectResponse {
+    pub project_id: i32,
+    pub published: bool,
+}
+
+/// Makes a draft visible to students, `false` when the project was already published
+fn publish(project: &mut Project) -> bool {
+    let draft = !project.published;
+    project.published = true;
+    draft
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/admins/projects/{id}/publish",
+    params(("id" = i32, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Project published, or already published", body = PublishProjectResponse),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 404, description = "Project not found", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Projects management",
+)]
+/// Publish a project
+///
+/// New projects are drafts hidden from students, unless `publish_projects_on_create` is set,
+/// so their deliverables and components can be prepared first. Once published, the project
+/// shows up in the student reads for the students who have access to it.
+#[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
+pub(in crate::api::v1) async fn publish_project_handler(
+    req: HttpRequest, path: Path<i32>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+    let project_id = path.into_inner();
+
+    let project_state = projects_repository::get_by_id(&data.db, project_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch project {}: {}", project_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+    let mut project_state = found_or_not_found(project_state, "Project not found")?;
+
+    if publish(project_state.as_mut()) {
+        project_state.save(&data.db).await.map_err(|e| {
+            error_with_log_id(
+                format!("unable to publish project {}: {}", project_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+        info!("admin {} published project {}", admin.admin_id, project_id);
+    }
+
+    Ok(HttpResponse::Ok().json(PublishProjectResponse {
+        project_id,
+        published: true,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::v1::students::fairs::read::upcoming_fairs;
+    use crate::models::fair::Fair;
+    use crate::models::group::Group;
+    use crate::test_utils::create_test_app_data;
+    use actix_web::dev::ServiceRequest;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::{web, App, Error};
+    use actix_web_grants::GrantsMiddleware;
+    use chrono::{Duration, TimeZone, Utc};
+    use std::collections::HashSet;
+
+    async fn coordinator_grants(_req: &ServiceRequest) -> Result<HashSet<String>, Error> {
+        Ok(HashSet::from(["ROLE_ADMIN_COORDINATOR".to_string()]))
+    }
+
+    #[actix_web::test]
+    async fn test_coordinators_cannot_publish_projects() {
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(create_test_app_data().await))
+                .wrap(GrantsMiddleware::with_extractor(coordinator_grants))
+                .route(
+                    "/projects/{id}/publish",
+                    web::post().to(publish_project_handler),
+                ),
+        )
+        .await;
+
+        let res = call_service(
+            &app,
+            TestRequest::post().uri("/projects/1/publish").to_request(),
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_draft_is_hidden_from_students_until_published() {
+        let now = Utc.with_ymd_and_hms(2026, 6, 1, 12, 0, 0).unwrap();
+        let group = Group {
+            group_id: 10,
+            project_id: 1,
+            name: "Group 10".to_string(),
+            created_at: now,
+        };
+        let mut project = Project {
+            project_id: 1,
+            name: "Robotics".to_string(),
+            slug: "robotics".to_string(),
+            year: 2026,
+            max_student_uploads: 5,
+            max_group_size: 4,
+            deliverable_selection_deadline: None,
+            upload_deadline: None,
+            active: true,
+            oral_exam_enabled: false,
+            frozen: false,
+            allowed_signup_domains: None,
+            published: false,
+        };
+        let fairs = || {
+            vec![Fair {
+                fair_id: 1,
+                project_id: 1,
+                details: String::new(),
+                start_date: now + Duration::hours(24),
+                end_date: now + Duration::hours(32),
+                min_purchases: 1,
+            }]
+        };
+
+        let before = upcoming_fairs(&[(group.clone(), project.clone())], fairs(), &[], now);
+        assert!(before.is_empty());
+
+        assert!(publish(&mut project));
+        let after = upcoming_fairs(&[(group, project.clone())], fairs(), &[], now);
+        assert_eq!(after.len(), 1);
+        assert_eq!(after[0].project_id, 1);
+
+        // Publishing again changes nothing
+        assert!(!publish(&mut project));
+        assert!(project.published);
+    }
+}
@@ -1,8 +1,14 @@
 use crate::app_data::AppData;
+use crate::common::access::{ensure_admin_sees_project, not_found};
 use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::common::list_params::{ListParams, ListQuery, ListSpec};
+use crate::common::pagination::{count_response, CountQuery};
+use crate::common::slug::ProjectRef;
 use crate::database::repositories::coordinator_projects_repository;
 use crate::database::repositories::projects_repository;
+use crate::database::repositories::projects_repository::ProjectsFilter;
 use crate::jwt::get_user::LoggedUser;
+use crate::models::admin::Admin;
 use crate::models::admin_role::AvailableAdminRole;
 use crate::models::group_deliverable::GroupDeliverable;
 use crate::models::group_deliverable_component::GroupDeliverableComponent;
@@ -10,22 +16,94 @@ use crate::models::project::Project;
 use crate::models::student_deliverable::StudentDeliverable;
 use crate::models::student_deliverable_component::StudentDeliverableComponent;
 use actix_web::http::StatusCode;
-use actix_web::web::{Data, Path};
+use actix_web::web::{Data, Path, Query};
 use actix_web::{HttpMessage, HttpRequest, HttpResponse};
 use log::error;
 use serde::Serialize;
+use std::cmp::Ordering;
 use utoipa::ToSchema;
 use welds::state::DbState;
+use welds::Client;
+
+const PROJECT_NOT_FOUND: &str = "Project not found";
+
+/// Fields of a project that can be selected with `fields`
+const PROJECT_FIELDS: &[&str] = &[
+    "project_id",
+    "name",
+    "slug",
+    "year",
+    "max_student_uploads",
+    "max_group_size",
+    "deliverable_selection_deadline",
+    "upload_deadline",
+    "active",
+    "oral_exam_enabled",
+    "frozen",
+    "allowed_signup_domains",
+];
+
+/// Sorting, filtering and fields of the project list
+#[derive(Debug)]
+pub(crate) struct ProjectsList;
+
+impl ListSpec for ProjectsList {
+    const SORTABLE: &'static [&'static str] = &["project_id", "name", "year", "upload_deadline"];
+    const FILTERABLE: &'static [&'static str] = &["year", "active", "frozen"];
+    const FIELDS: &'static [&'static str] = PROJECT_FIELDS;
+}
+
+/// Compares two projects on one of the sortable fields of [`ProjectsList`]
+fn compare_projects(field: &str, a: &Project, b: &Project) -> Ordering {
+    match field {
+        "name" => a.name.cmp(&b.name),
+        "year" => a.year.cmp(&b.year),
+        "upload_deadline" => a.upload_deadline.cmp(&b.upload_deadline),
+        _ => a.project_id.cmp(&b.project_id),
+    }
+}
+
+/// Repository filter of the `year`, `active` and `frozen` filters of the request
+fn projects_filter(
+    params: &ListParams<ProjectsList>, coordinator_id: Option<i32>,
+) -> Result<ProjectsFilter, JsonError> {
+    Ok(ProjectsFilter {
+        year: params.filter("year")?,
+        active: params.filter("active")?,
+        frozen: params.filter("frozen")?,
+        coordinator_id,
+    })
+}
+
+/// Projects matching the `year`, `active` and `frozen` filters, in the requested order
+fn list_projects(
+    params: &ListParams<ProjectsList>, projects: Vec<Project>,
+) -> Result<Vec<Project>, JsonError> {
+    let filter = projects_filter(params, None)?;
+
+    let mut projects: Vec<Project> = projects
+        .into_iter()
+        .filter(|p| filter.year.is_none_or(|year| p.year == year))
+        .filter(|p| filter.active.is_none_or(|active| p.active == active))
+        .filter(|p| filter.frozen.is_none_or(|frozen| p.frozen == frozen))
+        .collect();
+    params.sort_items(&mut projects, compare_projects);
+    Ok(projects)
+}
 
 #[derive(Debug, Serialize, ToSchema)]
 pub(crate) struct GetAllProjectsResponse {
-    projects: Vec<Project>,
+    /// Projects with only the requested fields when `fields` is set
+    #[schema(value_type = Vec<Project>)]
+    projects: Vec<serde_json::Value>,
 }
 #[utoipa::path(
     get,
     path = "/v1/admins/projects",
+    params(ListQuery, CountQuery),
     responses(
-        (status = 200, description = "Found projects", body = GetAllProjectsResponse),
+        (status = 200, description = "Found projects. Paginated with the usual headers when `page`, `per_page` or `envelope` is set, only `{ total }` with `count_only=true`", body = GetAllProjectsResponse),
+        (status = 400, description = "Invalid pagination, sort, filter or fields", body = JsonError),
         (status = 500, description = "Internal server error occurred", body = JsonError)
     ),
     security(("AdminAuth" = [])),
@@ -33,14 +111,18 @@ pub(crate) struct GetAllProjectsResponse {
 )]
 /// Get all projects details
 ///
-/// Returns all projects for Professors/Root, or only assigned projects for Coordinators
+/// Returns all projects for Professors/Root, or only assigned projects for Coordinators.
+/// Projects can be sorted by id, name, year or upload deadline and filtered by year,
+/// `active` and `frozen`. With `fields` only the listed fields of each project are returned.
+/// With `count_only` the matching projects are counted without being fetched.
 #[actix_web_grants::protect(any(
     "ROLE_ADMIN_ROOT",
     "ROLE_ADMIN_PROFESSOR",
     "ROLE_ADMIN_COORDINATOR"
 ))]
 pub(in crate::api::v1) async fn get_all_projects_handler(
-    req: HttpRequest, data: Data<AppData>,
+    req: HttpRequest, params: ListParams<ProjectsList>, count: Query<CountQuery>,
+    data: Data<AppData>,
 ) -> Result<HttpResponse, JsonError> {
     let user = match req.extensions().get_admin() {
         Ok(user) => user,
@@ -53,6 +135,21 @@ pub(in crate::api::v1) async fn get_all_projects_handler(
     // Check if user is a coordinator
     let is_coordinator = user.admin_role_id == AvailableAdminRole::Coordinator as i32;
 
+    if count.is_set() {
+        let filter = projects_filter(&params, is_coordinator.then_some(user.admin_id))?;
+        let total = projects_repository::count_filtered(&data.db, &filter)
+            .await
+            .map_err(|e| {
+                error_with_log_id(
+                    format!("unable to count projects: {}", e),
+                    "Failed to retrieve projects",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                )
+            })?;
+        return Ok(count_response(total));
+    }
+
     let projects: Vec<Project> = if is_coordinator {
         // Coordinators see only their assigned projects
         let project_ids =
@@ -103,7 +200,33 @@ pub(in crate::api::v1) async fn get_all_projects_handler(
             .collect()
     };
 
-    Ok(HttpResponse::Ok().json(GetAllProjectsResponse { projects }))
+    let projects = list_projects(&params, projects)?;
+    Ok(
+        params.respond_all(&req, projects, |projects| GetAllProjectsResponse {
+            projects,
+        }),
+    )
+}
+
+/// Id of the addressed project, a slug matching no project is reported as not found
+async fn resolve_project_id(data: &AppData, project: ProjectRef) -> Result<i32, JsonError> {
+    let slug = match project {
+        ProjectRef::Id(id) => return Ok(id),
+        ProjectRef::Slug(slug) => slug,
+    };
+
+    projects_repository::get_by_slug(&data.db, &slug)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to retrieve project with slug {}: {}", slug, e),
+                "Failed to retrieve project details",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?
+        .map(|p| p.project_id)
+        .ok_or_else(|| not_found(PROJECT_NOT_FOUND))
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -117,26 +240,28 @@ pub(crate) struct ProjectDetailsResponse {
 
 #[utoipa::path(
     get,
-    path = "/v1/admins/projects/{id}",
+    path = "/v1/admins/projects/{id_or_slug}",
+    params(("id_or_slug" = String, Path, description = "Project ID or slug")),
     responses(
         (status = 200, description = "Found project with deliverables and components", body = ProjectDetailsResponse),
-        (status = 403, description = "Access denied", body = JsonError),
-        (status = 404, description = "project not found", body = JsonError),
+        (status = 404, description = "Project not found or not visible to the caller", body = JsonError),
         (status = 500, description = "Internal server error", body = JsonError)
     ),
     security(("AdminAuth" = [])),
     tag = "Projects management",
 )]
-/// Get project details by id with deliverables and components
+/// Get project details by id or slug with deliverables and components
 ///
+/// Numeric path segments are read as ids, anything else as a slug.
 /// Coordinators can only view projects they are assigned to. Professors/Root can view any project.
+/// Projects a coordinator is not assigned to are reported as not found.
 #[actix_web_grants::protect(any(
     "ROLE_ADMIN_ROOT",
     "ROLE_ADMIN_PROFESSOR",
     "ROLE_ADMIN_COORDINATOR"
 ))]
 pub(in crate::api::v1) async fn get_one_project_handler(
-    req: HttpRequest, path: Path<i32>, data: Data<AppData>,
+    req: HttpRequest, path: Path<String>, data: Data<AppData>,
 ) -> Result<HttpResponse, JsonError> {
     let user = match req.extensions().get_admin() {
         Ok(user) => user,
@@ -146,30 +271,21 @@ pub(in crate::api::v1) async fn get_one_project_handler(
         }
     };
 
-    let id = path.into_inner();
+    let id = resolve_project_id(&data, ProjectRef::from(path.into_inner())).await?;
 
-    // Check if user is a coordinator and if they have access to this project
-    let is_coordinator = user.admin_role_id == AvailableAdminRole::Coordinator as i32;
-    if is_coordinator {
-        let is_assigned = coordinator_projects_repository::is_assigned(&data.db, user.admin_id, id)
-            .await
-            .map_err(|e| {
-                error_with_log_id(
-                    format!("unable to check coordinator assignment: {}", e),
-                    "Failed to retrieve project",
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    log::Level::Error,
-                )
-            })?;
+    let details = project_details(&data.db, &user, id).await?;
+    Ok(HttpResponse::Ok().json(details))
+}
 
-        if !is_assigned {
-            return Err("Access denied - you are not assigned to this project"
-                .to_json_error(StatusCode::FORBIDDEN));
-        }
-    }
+/// The project with its deliverables and components, not found when hidden from `admin`
+async fn project_details(
+    db: &impl Client, admin: &Admin, id: i32,
+) -> Result<ProjectDetailsResponse, JsonError> {
+    // Coordinators only see the projects they are assigned to
+    ensure_admin_sees_project(db, admin, id, PROJECT_NOT_FOUND).await?;
 
     // Fetch project details with all related entities using repository function
-    let project_details = projects_repository::get_project_details(&data.db, id)
+    let project_details = projects_repository::get_project_details(db, id)
         .await
         .map_err(|e| {
             error_with_log_id(
@@ -188,7 +304,7 @@ pub(in crate::api::v1) async fn get_one_project_handler(
         student_components_state,
     ) = match project_details {
         Some(details) => details,
-        None => return Err("Project not found".to_json_error(StatusCode::NOT_FOUND)),
+        None => return Err(not_found(PROJECT_NOT_FOUND)),
     };
 
     let project = DbState::into_inner(project_state);
@@ -209,11 +325,119 @@ pub(in crate::api::v1) async fn get_one_project_handler(
         .map(DbState::into_inner)
         .collect();
 
-    Ok(HttpResponse::Ok().json(ProjectDetailsResponse {
+    Ok(ProjectDetailsResponse {
         project,
         group_deliverables,
         group_components,
         student_deliverables,
         student_components,
-    }))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RecordingClient;
+    use actix_web::test::TestRequest;
+    use actix_web::FromRequest;
+
+    fn project(project_id: i32, name: &str, year: i32, active: bool) -> Project {
+        Project {
+            project_id,
+            name: name.to_string(),
+            slug: name.to_lowercase(),
+            year,
+            max_student_uploads: 5,
+            max_group_size: 4,
+            deliverable_selection_deadline: None,
+            upload_deadline: None,
+            active,
+            oral_exam_enabled: false,
+            frozen: false,
+            allowed_signup_domains: None,
+            published: true,
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_projects_are_filtered_and_sorted() {
+        let req = TestRequest::get()
+            .uri("/v1/admins/projects?sort=year:desc,name&filter=active:true")
+            .to_http_request();
+        let params = ListParams::<ProjectsList>::extract(&req).await.unwrap();
+        let projects = vec![
+            project(1, "Robotics", 2025, true),
+            project(2, "Compilers", 2026, true),
+            project(3, "Archived", 2026, false),
+            project(4, "Networks", 2026, true),
+        ];
+
+        let listed: Vec<i32> = list_projects(&params, projects)
+            .unwrap()
+            .iter()
+            .map(|p| p.project_id)
+            .collect();
+
+        assert_eq!(listed, vec![2, 4, 1]);
+    }
+
+    #[actix_web::test]
+    async fn test_count_uses_the_filters_of_the_listing() {
+        let req = TestRequest::get()
+            .uri("/v1/admins/projects?count_only=true&filter=year:2026,active:true")
+            .to_http_request();
+        let params = ListParams::<ProjectsList>::extract(&req).await.unwrap();
+
+        assert_eq!(
+            projects_filter(&params, Some(7)).unwrap(),
+            ProjectsFilter {
+                year: Some(2026),
+                active: Some(true),
+                frozen: None,
+                coordinator_id: Some(7),
+            }
+        );
+    }
+
+    fn admin(role: AvailableAdminRole) -> Admin {
+        Admin {
+            admin_id: 3,
+            first_name: "Test".to_string(),
+            last_name: "Admin".to_string(),
+            email: "admin@test.com".to_string(),
+            password_hash: String::new(),
+            admin_role_id: role as i32,
+        }
+    }
+
+    async fn render(err: JsonError) -> (StatusCode, actix_web::web::Bytes) {
+        let response = actix_web::ResponseError::error_response(&err);
+        let status = response.status();
+        let body = actix_web::body::to_bytes(response.into_body())
+            .await
+            .unwrap();
+        (status, body)
+    }
+
+    #[actix_web::test]
+    async fn test_coordinator_outside_the_project_gets_the_missing_project_response() {
+        // The recording client has no rows: no assignment and no project
+        let db = RecordingClient::default();
+
+        let hidden = project_details(&db, &admin(AvailableAdminRole::Coordinator), 7)
+            .await
+            .unwrap_err();
+        assert_eq!(db.statements().len(), 1, "stops at the assignment check");
+        assert!(db.statements()[0].contains("coordinator_projects"));
+
+        let missing = project_details(&db, &admin(AvailableAdminRole::Root), 7)
+            .await
+            .unwrap_err();
+
+        let (hidden_status, hidden_body) = render(hidden).await;
+        let (missing_status, missing_body) = render(missing).await;
+        assert_eq!(hidden_status, StatusCode::NOT_FOUND);
+        assert_eq!(hidden_status, missing_status);
+        assert_eq!(hidden_body, missing_body);
+    }
 }
@@ -0,0 +1,115 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::database::repositories::{
+    audit_log_repository, projects_repository, students_repository,
+};
+use crate::jwt::get_user::LoggedUser;
+use crate::models::audit_log::{AuditLog, STUDENT_SESSIONS_REVOKED};
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Path};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use log::warn;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct RevokeSessionsResponse {
+    pub project_id: i32,
+    /// Number of students who have to log in again
+    #[schema(example = 42)]
+    pub revoked: usize,
+    /// Tokens issued up to this time are rejected
+    #[schema(value_type = String, example = "2025-09-22T12:34:56Z")]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub revoked_at: DateTime<Utc>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/admins/projects/{id}/revoke-sessions",
+    params(("id" = i32, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Sessions revoked", body = RevokeSessionsResponse),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 404, description = "Project not found", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Projects management",
+)]
+/// Force every student with access to a project to log in again
+///
+/// Students in a group of the project or who redeemed one of its security codes have every
+/// token issued so far rejected with a 401, including impersonation tokens. Their tokens
+/// are revoked for every project, since a token is not bound to one.
+#[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
+pub(in crate::api::v1) async fn revoke_sessions_handler(
+    req: HttpRequest, path: Path<i32>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+    let project_id = path.into_inner();
+
+    projects_repository::get_by_id(&data.db, project_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch project {}: {}", project_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?
+        .ok_or_else(|| {
+            error_with_log_id(
+                format!("project {} not found", project_id),
+                "Project not found",
+                StatusCode::NOT_FOUND,
+                log::Level::Warn,
+            )
+        })?;
+
+    let revoked_at = data.clock.now();
+    let student_ids =
+        students_repository::revoke_sessions_by_project(&data.db, project_id, revoked_at)
+            .await
+            .map_err(|e| {
+                error_with_log_id(
+                    format!(
+                        "unable to revoke the sessions of project {}: {}",
+                        project_id, e
+                    ),
+                    "Failed to revoke sessions",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                )
+            })?;
+
+    warn!(
+        "admin {} revoked the sessions of {} students of project {}",
+        admin.admin_id,
+        student_ids.len(),
+        project_id
+    );
+
+    audit_log_repository::record_or_warn(
+        &data.db,
+        AuditLog::by_admin(admin.admin_id, STUDENT_SESSIONS_REVOKED)
+            .project(project_id)
+            .details(format!("{} students", student_ids.len())),
+    )
+    .await;
+
+    Ok(HttpResponse::Ok().json(RevokeSessionsResponse {
+        project_id,
+        revoked: student_ids.len(),
+        revoked_at,
+    }))
+}
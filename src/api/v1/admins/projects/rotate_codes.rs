@@ -0,0 +1,124 @@
+use crate::api::v1::admins::security_codes::create::generate_random_code;
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::repositories::security_codes;
+use crate::jwt::get_user::LoggedUser;
+use crate::models::security_code::SecurityCode;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct RotateCodesScheme {
+    /// Create a replacement for every invalidated code, with the same expiration
+    #[serde(default)]
+    #[schema(example = true)]
+    pub regenerate: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct RotatedCode {
+    #[schema(example = 12)]
+    pub security_code_id: i32,
+    #[schema(example = "D3K-Z9A")]
+    pub code: String,
+    #[schema(value_type = String, example = "2025-09-22T12:34:56Z")]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub expiration: DateTime<Utc>,
+}
+
+impl From<SecurityCode> for RotatedCode {
+    fn from(code: SecurityCode) -> Self {
+        Self {
+            security_code_id: code.security_code_id,
+            code: code.code,
+            expiration: code.expiration,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct RotateCodesResponse {
+    pub project_id: i32,
+    /// Number of codes that could still be redeemed and no longer can
+    #[schema(example = 2)]
+    pub invalidated: usize,
+    /// Replacement codes, empty unless `regenerate` was set
+    pub codes: Vec<RotatedCode>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/admins/projects/{id}/rotate-codes",
+    params(("id" = i32, Path, description = "Project ID")),
+    request_body = RotateCodesScheme,
+    responses(
+        (status = 200, description = "Codes invalidated, with their replacements", body = RotateCodesResponse),
+        (status = 404, description = "Project not found", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Security codes management",
+)]
+/// Rotate the security codes of a compromised project
+///
+/// Every code of the project that can still be redeemed expires immediately and, when
+/// `regenerate` is set, is replaced by a new one with the same expiration. Students who
+/// already redeemed a code keep their access.
+#[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
+pub(in crate::api::v1) async fn rotate_codes_handler(
+    req: HttpRequest, path: Path<i32>, body: Json<RotateCodesScheme>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+    let project_id = path.into_inner();
+
+    let rotation = security_codes::rotate_for_project(
+        &data.db,
+        project_id,
+        data.clock.now(),
+        body.regenerate,
+        generate_random_code,
+    )
+    .await
+    .map_err(|e| {
+        error_with_log_id(
+            format!(
+                "unable to rotate security codes of project {}: {}",
+                project_id, e
+            ),
+            "Failed to rotate security codes",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?
+    .ok_or_else(|| "Project not found".to_json_error(StatusCode::NOT_FOUND))?;
+
+    warn!(
+        "admin {} rotated the security codes of project {}: {} invalidated, {} created",
+        admin.admin_id,
+        project_id,
+        rotation.invalidated.len(),
+        rotation.created.len()
+    );
+
+    Ok(HttpResponse::Ok().json(RotateCodesResponse {
+        project_id,
+        invalidated: rotation.invalidated.len(),
+        codes: rotation
+            .created
+            .into_iter()
+            .map(RotatedCode::from)
+            .collect(),
+    }))
+}
@@ -0,0 +1,425 @@
+use crate::app_data::AppData;
+use crate::common::access::{ensure_admin_sees_project, found_or_not_found};
+use crate::common::csv::csv_field;
+use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::database::repositories::{group_deliverable_components_repository, projects_repository};
+use crate::database::timing::timed;
+use crate::jwt::get_user::LoggedUser;
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::http::StatusCode;
+use actix_web::web::{Bytes, Data, Path, Query};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use futures_util::stream;
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use std::collections::HashMap;
+use utoipa::{IntoParams, ToSchema};
+use welds::state::DbState;
+
+const PROJECT_NOT_FOUND: &str = "Project not found";
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct SelectionsExportQuery {
+    /// `json` (default) or `csv`
+    #[serde(default)]
+    #[param(value_type = Option<ExportFormat>)]
+    pub format: ExportFormat,
+}
+
+/// Where a group stands on a component of the project
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SelectionStatus {
+    /// The component is not part of the deliverable selected by the group
+    NotSelected,
+    /// The component is required by the selected deliverable, without implementation details yet
+    Selected,
+    /// The group has provided the implementation details of the component
+    Implemented,
+}
+
+impl SelectionStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            SelectionStatus::NotSelected => "not_selected",
+            SelectionStatus::Selected => "selected",
+            SelectionStatus::Implemented => "implemented",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub(crate) struct ExportComponent {
+    pub group_deliverable_component_id: i32,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ExportGroupRow {
+    pub group_id: i32,
+    pub group_name: String,
+    /// Name of the deliverable selected by the group, if any
+    pub group_deliverable: Option<String>,
+    /// One status per component, in the order of `components`
+    pub statuses: Vec<SelectionStatus>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct SelectionsExport {
+    pub project_id: i32,
+    pub components: Vec<ExportComponent>,
+    pub groups: Vec<ExportGroupRow>,
+}
+
+/// A group of the project with its selected deliverable
+struct ExportGroup {
+    group_id: i32,
+    group_name: String,
+    group_deliverable: Option<String>,
+}
+
+/// A component required by the deliverable a group selected
+struct RequiredComponent {
+    group_id: i32,
+    group_deliverable_component_id: i32,
+    implemented: bool,
+}
+
+/// Builds the groups × components matrix, every group gets a status for every component
+fn build_export(
+    project_id: i32, groups: Vec<ExportGroup>, components: Vec<ExportComponent>,
+    required: &[RequiredComponent],
+) -> SelectionsExport {
+    let statuses: HashMap<(i32, i32), SelectionStatus> = required
+        .iter()
+        .map(|r| {
+            let status = if r.implemented {
+                SelectionStatus::Implemented
+            } else {
+                SelectionStatus::Selected
+            };
+            ((r.group_id, r.group_deliverable_component_id), status)
+        })
+        .collect();
+
+    let groups = groups
+        .into_iter()
+        .map(|group| ExportGroupRow {
+            statuses: components
+                .iter()
+                .map(|c| {
+                    statuses
+                        .get(&(group.group_id, c.group_deliverable_component_id))
+                        .copied()
+                        .unwrap_or(SelectionStatus::NotSelected)
+                })
+                .collect(),
+            group_id: group.group_id,
+            group_name: group.group_name,
+            group_deliverable: group.group_deliverable,
+        })
+        .collect();
+
+    SelectionsExport {
+        project_id,
+        components,
+        groups,
+    }
+}
+
+/// CSV lines of the export, header first, each terminated by a line break
+fn csv_lines(export: &SelectionsExport) -> Vec<String> {
+    let mut header = vec![
+        "group_id".to_string(),
+        "group_name".to_string(),
+        "group_deliverable".to_string(),
+    ];
+    header.extend(export.components.iter().map(|c| csv_field(&c.name)));
+
+    let mut lines = Vec::with_capacity(export.groups.len() + 1);
+    lines.push(format!("{}\r\n", header.join(",")));
+    for group in &export.groups {
+        let mut fields = vec![
+            group.group_id.to_string(),
+            csv_field(&group.group_name),
+            csv_field(group.group_deliverable.as_deref().unwrap_or("")),
+        ];
+        fields.extend(group.statuses.iter().map(|s| s.as_str().to_string()));
+        lines.push(format!("{}\r\n", fields.join(",")));
+    }
+    lines
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/admins/projects/{id}/selections/export",
+    params(("id" = i32, Path, description = "Project ID"), SelectionsExportQuery),
+    responses(
+        (status = 200, description = "Selection status of every group for every component, as JSON or CSV", body = SelectionsExport),
+        (status = 404, description = "Project not found or not visible to the caller", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Projects management",
+)]
+/// Export the selections of every group of a project for grading
+///
+/// Rows are groups and columns are the deliverable components of the project.
+/// With `format=csv` the export is streamed as an attachment, one line per group.
+/// Coordinators can only export projects they are assigned to.
+#[actix_web_grants::protect(any(
+    "ROLE_ADMIN_ROOT",
+    "ROLE_ADMIN_PROFESSOR",
+    "ROLE_ADMIN_COORDINATOR"
+))]
+pub(in crate::api::v1) async fn export_selections_handler(
+    req: HttpRequest, path: Path<i32>, query: Query<SelectionsExportQuery>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let project_id = path.into_inner();
+
+    ensure_admin_sees_project(&data.db, &admin, project_id, PROJECT_NOT_FOUND).await?;
+
+    let project = projects_repository::get_by_id(&data.db, project_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch project {}: {}", project_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+    found_or_not_found(project, PROJECT_NOT_FOUND)?;
+
+    let export_error = |e: sqlx::Error| {
+        error_with_log_id(
+            format!(
+                "unable to export selections of project {}: {}",
+                project_id, e
+            ),
+            "Failed to export selections",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    };
+
+    let groups = timed(
+        "projects.export_groups",
+        sqlx::query(
+            r#"
+        SELECT g.group_id, g.name AS group_name, gd.name AS group_deliverable
+        FROM groups g
+        LEFT JOIN group_deliverable_selections gds
+            ON gds.group_id = g.group_id
+        LEFT JOIN group_deliverables gd
+            ON gd.group_deliverable_id = gds.group_deliverable_id
+        WHERE g.project_id = $1
+        ORDER BY g.name, g.group_id
+        "#,
+        )
+        .bind(project_id)
+        .fetch_all(data.db.as_sqlx_pool()),
+    )
+    .await
+    .map_err(export_error)?
+    .into_iter()
+    .map(|row| ExportGroup {
+        group_id: row.get("group_id"),
+        group_name: row.get("group_name"),
+        group_deliverable: row.get("group_deliverable"),
+    })
+    .collect();
+
+    let required: Vec<RequiredComponent> = timed(
+        "projects.export_required_components",
+        sqlx::query(
+            r#"
+        SELECT
+            gds.group_id,
+            gdc.group_deliverable_component_id,
+            gcid.id IS NOT NULL AS implemented
+        FROM group_deliverable_selections gds
+        JOIN groups g
+            ON g.group_id = gds.group_id
+        JOIN group_deliverables_components gdc
+            ON gdc.group_deliverable_id = gds.group_deliverable_id
+        LEFT JOIN group_component_implementation_details gcid
+            ON gcid.group_deliverable_selection_id = gds.group_deliverable_selection_id
+            AND gcid.group_deliverable_component_id = gdc.group_deliverable_component_id
+        WHERE g.project_id = $1
+        "#,
+        )
+        .bind(project_id)
+        .fetch_all(data.db.as_sqlx_pool()),
+    )
+    .await
+    .map_err(export_error)?
+    .into_iter()
+    .map(|row| RequiredComponent {
+        group_id: row.get("group_id"),
+        group_deliverable_component_id: row.get("group_deliverable_component_id"),
+        implemented: row.get("implemented"),
+    })
+    .collect();
+
+    let mut components: Vec<ExportComponent> =
+        group_deliverable_components_repository::get_by_project_id(&data.db, project_id)
+            .await
+            .map_err(|e| {
+                error_with_log_id(
+                    format!(
+                        "unable to fetch components of project {}: {}",
+                        project_id, e
+                    ),
+                    "Database error",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                )
+            })?
+            .into_iter()
+            .map(|state| {
+                let c = DbState::into_inner(state);
+                ExportComponent {
+                    group_deliverable_component_id: c.group_deliverable_component_id,
+                    name: c.name,
+                }
+            })
+            .collect();
+    components.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let export = build_export(project_id, groups, components, &required);
+
+    match query.format {
+        ExportFormat::Json => Ok(HttpResponse::Ok().json(export)),
+        ExportFormat::Csv => {
+            let lines = csv_lines(&export)
+                .into_iter()
+                .map(|line| Ok::<_, actix_web::Error>(Bytes::from(line)));
+            Ok(HttpResponse::Ok()
+                .content_type("text/csv; charset=utf-8")
+                .insert_header(ContentDisposition {
+                    disposition: DispositionType::Attachment,
+                    parameters: vec![DispositionParam::Filename(format!(
+                        "project_{}_selections.csv",
+                        project_id
+                    ))],
+                })
+                .streaming(stream::iter(lines)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(group_id: i32, name: &str, deliverable: Option<&str>) -> ExportGroup {
+        ExportGroup {
+            group_id,
+            group_name: name.to_string(),
+            group_deliverable: deliverable.map(str::to_string),
+        }
+    }
+
+    fn component(id: i32, name: &str) -> ExportComponent {
+        ExportComponent {
+            group_deliverable_component_id: id,
+            name: name.to_string(),
+        }
+    }
+
+    fn required(group_id: i32, component_id: i32, implemented: bool) -> RequiredComponent {
+        RequiredComponent {
+            group_id,
+            group_deliverable_component_id: component_id,
+            implemented,
+        }
+    }
+
+    /// Three groups and three components: one group complete, one halfway, one without selection
+    fn seeded_export() -> SelectionsExport {
+        build_export(
+            7,
+            vec![
+                group(1, "Alpha", Some("Basic")),
+                group(2, "Beta, the second", Some("Advanced")),
+                group(3, "Gamma", None),
+            ],
+            vec![
+                component(10, "Bank"),
+                component(11, "Drone"),
+                component(12, "Market"),
+            ],
+            &[
+                required(1, 10, true),
+                required(2, 10, true),
+                required(2, 11, false),
+                required(2, 12, false),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_export_covers_every_group_and_component() {
+        let export = seeded_export();
+
+        assert_eq!(export.groups.len(), 3);
+        assert!(export.groups.iter().all(|g| g.statuses.len() == 3));
+        assert_eq!(
+            export.groups[0].statuses,
+            vec![
+                SelectionStatus::Implemented,
+                SelectionStatus::NotSelected,
+                SelectionStatus::NotSelected
+            ]
+        );
+        assert_eq!(
+            export.groups[1].statuses,
+            vec![
+                SelectionStatus::Implemented,
+                SelectionStatus::Selected,
+                SelectionStatus::Selected
+            ]
+        );
+        assert_eq!(
+            export.groups[2].statuses,
+            vec![SelectionStatus::NotSelected; 3]
+        );
+    }
+
+    #[test]
+    fn test_csv_has_a_line_per_group_and_escapes_names() {
+        let lines = csv_lines(&seeded_export());
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[0],
+            "group_id,group_name,group_deliverable,Bank,Drone,Market\r\n"
+        );
+        assert_eq!(
+            lines[2],
+            "2,\"Beta, the second\",Advanced,implemented,selected,selected\r\n"
+        );
+        assert_eq!(
+            lines[3],
+            "3,Gamma,,not_selected,not_selected,not_selected\r\n"
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError, ValidationError};
+use crate::database::repositories::projects_repository;
+use crate::jwt::get_user::LoggedUser;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use log::info;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct SignupDomainsRequest {
+    /// Domains admitted when redeeming the codes of the project, `null` admits every domain
+    #[schema(example = json!(["studenti.unitn.it", "partner.org"]))]
+    pub allowed_signup_domains: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct SignupDomainsResponse {
+    pub project_id: i32,
+    /// `null` when every domain is admitted
+    pub allowed_signup_domains: Option<Vec<String>>,
+}
+
+/// Normalized domains of the override, reporting every malformed one at once
+fn normalize_domains(domains: &[String]) -> Result<Vec<String>, ValidationError> {
+    let mut errors = ValidationError::default();
+    let mut normalized: Vec<String> = Vec::new();
+    let mut malformed = false;
+    for domain in domains {
+        let domain = domain.trim().to_ascii_lowercase();
+        if domain.is_empty() || domain.contains(['@', ',']) || domain.contains(char::is_whitespace)
+        {
+            malformed = true;
+            errors.add(
+                "allowed_signup_domains",
+                format!("Invalid domain `{}`", domain),
+            );
+        } else if !normalized.contains(&domain) {
+            normalized.push(domain);
+        }
+    }
+    if normalized.is_empty() && !malformed {
+        errors.add(
+            "allowed_signup_domains",
+            "At least one domain is required, send null to admit every domain",
+        );
+    }
+
+    errors.into_result().map(|()| normalized)
+}
+
+#[utoipa::path(
+    put,
+    path = "/v1/admins/projects/{id}/signup-domains",
+    params(("id" = i32, Path, description = "Project ID")),
+    request_body = SignupDomainsRequest,
+    responses(
+        (status = 200, description = "Signup domains of the project set or cleared", body = SignupDomainsResponse),
+        (status = 400, description = "Malformed or empty domain list", body = ValidationError),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 404, description = "Project not found", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Projects management",
+)]
+/// Override the email domains admitted to a project
+///
+/// Students redeeming a security code of the project must have an email in one of these
+/// domains, which need not be in the global `allowed_signup_domains`, so external
+/// collaborators can be admitted. Without an override the codes are open to every student,
+/// and self signup keeps using the global list.
+#[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
+pub(in crate::api::v1) async fn set_signup_domains_handler(
+    req: HttpRequest, path: Path<i32>, body: Json<SignupDomainsRequest>, data: Data<AppData>,
+) -> actix_web::Result<HttpResponse> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+    let project_id = path.into_inner();
+
+    let domains = match &body.allowed_signup_domains {
+        Some(domains) => Some(normalize_domains(domains)?),
+        None => None,
+    };
+
+    let mut project_state = projects_repository::get_by_id(&data.db, project_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch project {}: {}", project_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?
+        .ok_or_else(|| {
+            error_with_log_id(
+                format!("project {} not found", project_id),
+                "Project not found",
+                StatusCode::NOT_FOUND,
+                log::Level::Warn,
+            )
+        })?;
+
+    project_state.as_mut().allowed_signup_domains = domains.as_ref().map(|d| d.join(","));
+    project_state.save(&data.db).await.map_err(|e| {
+        error_with_log_id(
+            format!("unable to save project {}: {}", project_id, e),
+            "Database error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    info!(
+        "admin {} set the signup domains of project {} to {:?}",
+        admin.admin_id, project_id, domains
+    );
+
+    Ok(HttpResponse::Ok().json(SignupDomainsResponse {
+        project_id,
+        allowed_signup_domains: domains,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn domains(list: &[&str]) -> Vec<String> {
+        list.iter().map(|d| d.to_string()).collect()
+    }
+
+    #[test]
+    fn test_domains_are_normalized() {
+        let normalized = normalize_domains(&domains(&[
+            " Partner.org ",
+            "studenti.unitn.it",
+            "partner.org",
+        ]))
+        .unwrap();
+
+        assert_eq!(normalized, domains(&["partner.org", "studenti.unitn.it"]));
+    }
+
+    #[test]
+    fn test_malformed_domains_are_rejected() {
+        let err = normalize_domains(&domains(&["mario@partner.org", "a,b", ""])).unwrap_err();
+
+        let payload = serde_json::to_value(&err).unwrap();
+        assert_eq!(
+            payload["fields"]["allowed_signup_domains"]
+                .as_array()
+                .unwrap()
+                .len(),
+            3
+        );
+        assert!(normalize_domains(&[]).is_err());
+    }
+}
@@ -0,0 +1,193 @@
+use crate::app_data::AppData;
+use crate::common::access::{ensure_admin_sees_project, found_or_not_found};
+use crate::common::fields::{select_fields, FieldsQuery};
+use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::common::pagination::PaginationQuery;
+use crate::database::repositories::projects_repository;
+use crate::jwt::get_user::LoggedUser;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Path, Query};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+const PROJECT_NOT_FOUND: &str = "Project not found";
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct UngroupedStudent {
+    #[schema(example = 12)]
+    pub student_id: i32,
+    #[schema(example = "Mario")]
+    pub first_name: String,
+    #[schema(example = "Rossi")]
+    pub last_name: String,
+    #[schema(example = "mario.rossi@studenti.unitn.it")]
+    pub email: String,
+    #[schema(example = 123456)]
+    pub university_id: i32,
+    /// When the student redeemed a security code of the project
+    #[schema(value_type = String, example = "2026-03-01T10:00:00.000Z")]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub granted_at: DateTime<Utc>,
+}
+
+/// Fields of an ungrouped student that can be selected with `fields`
+const UNGROUPED_STUDENT_FIELDS: &[&str] = &[
+    "student_id",
+    "first_name",
+    "last_name",
+    "email",
+    "university_id",
+    "granted_at",
+];
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct UngroupedStudentsResponse {
+    pub project_id: i32,
+    /// Students with only the requested fields when `fields` is set
+    #[schema(value_type = Vec<UngroupedStudent>)]
+    pub students: Vec<serde_json::Value>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/admins/projects/{id}/ungrouped-students",
+    params(("id" = i32, Path, description = "Project ID"), PaginationQuery, FieldsQuery),
+    responses(
+        (status = 200, description = "Page of the students with access to the project but in no group, sorted by name. With `envelope=true` the body is `{ data, meta }`", body = UngroupedStudentsResponse,
+            headers(
+                ("X-Total-Count" = u64, description = "Total number of ungrouped students"),
+                ("X-Page" = u32, description = "Returned page"),
+                ("X-Per-Page" = u32, description = "Page size"),
+                ("Link" = String, description = "RFC 5988 links to the first, prev, next and last pages"),
+            )
+        ),
+        (status = 400, description = "Unknown field requested", body = JsonError),
+        (status = 404, description = "Project not found or not visible to the caller", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Projects management",
+)]
+/// List the students of a project that are not in any group yet
+///
+/// Students get access to a project by redeeming one of its security codes; those who
+/// have not joined or created a group are returned, so they can be reminded to form a
+/// team. Coordinators can only list the students of projects they are assigned to. With
+/// `fields` only the listed fields of each student are returned.
+#[actix_web_grants::protect(any(
+    "ROLE_ADMIN_ROOT",
+    "ROLE_ADMIN_PROFESSOR",
+    "ROLE_ADMIN_COORDINATOR"
+))]
+pub(in crate::api::v1) async fn list_ungrouped_students_handler(
+    req: HttpRequest, path: Path<i32>, pagination: Query<PaginationQuery>,
+    fields: Query<FieldsQuery>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let fields = fields.parse(UNGROUPED_STUDENT_FIELDS)?;
+
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let project_id = path.into_inner();
+    let pagination = pagination.into_inner().within(data.page_limits);
+
+    ensure_admin_sees_project(&data.db, &admin, project_id, PROJECT_NOT_FOUND).await?;
+
+    let project = projects_repository::get_by_id(&data.db, project_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch project {}: {}", project_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+    found_or_not_found(project, PROJECT_NOT_FOUND)?;
+
+    let db_error = |what: &str, e: welds::WeldsError| {
+        error_with_log_id(
+            format!("unable to {} of project {}: {}", what, project_id, e),
+            "Failed to retrieve ungrouped students",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    };
+
+    let total = projects_repository::count_ungrouped(&data.db, project_id)
+        .await
+        .map_err(|e| db_error("count the ungrouped students", e))?;
+
+    let students = projects_repository::get_ungrouped(
+        &data.db,
+        project_id,
+        pagination.limit(),
+        pagination.offset(),
+    )
+    .await
+    .map_err(|e| db_error("fetch the ungrouped students", e))?
+    .into_iter()
+    .map(|row| UngroupedStudent {
+        student_id: row.student_id,
+        first_name: row.first_name,
+        last_name: row.last_name,
+        email: row.email,
+        university_id: row.university_id,
+        granted_at: row.granted_at,
+    })
+    .collect();
+    let students = select_fields(students, fields.as_ref());
+
+    Ok(pagination.respond(&req, students, total, |students| {
+        UngroupedStudentsResponse {
+            project_id,
+            students,
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_app_data;
+    use actix_web::dev::ServiceRequest;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::{web, App, Error};
+    use actix_web_grants::GrantsMiddleware;
+    use std::collections::HashSet;
+
+    async fn student_grants(_req: &ServiceRequest) -> Result<HashSet<String>, Error> {
+        Ok(HashSet::from(["ROLE_STUDENT".to_string()]))
+    }
+
+    #[actix_web::test]
+    async fn test_students_cannot_list_ungrouped_students() {
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(create_test_app_data().await))
+                .wrap(GrantsMiddleware::with_extractor(student_grants))
+                .route(
+                    "/projects/{id}/ungrouped-students",
+                    web::get().to(list_ungrouped_students_handler),
+                ),
+        )
+        .await;
+
+        let res = call_service(
+            &app,
+            TestRequest::get()
+                .uri("/projects/1/ungrouped-students")
+                .to_request(),
+        )
+        .await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -1,5 +1,8 @@
 use crate::app_data::AppData;
+use crate::common::deadlines::ProjectDeadlines;
 use crate::common::json_error::{error_with_log_id_and_payload, JsonError, ToJsonError};
+use crate::common::slug::validate_slug;
+use crate::database::errors::is_unique_violation;
 use crate::database::repositories::projects_repository;
 use actix_web::http::StatusCode;
 use actix_web::web::{Data, Json, Path};
@@ -8,11 +11,16 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+const SLUG_TAKEN: &str = "Slug already in use by another project";
+
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct UpdateProjectScheme {
     pub name: Option<String>,
+    /// Lowercase letters and digits separated by hyphens, unique among projects
+    pub slug: Option<String>,
     pub max_student_uploads: Option<i32>,
     pub max_group_size: Option<i32>,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339_opt")]
     pub upload_deadline: Option<DateTime<Utc>>,
     pub active: Option<bool>,
 }
@@ -25,12 +33,17 @@ pub struct UpdateProjectScheme {
         (status = 400, description = "Invalid data in request", body = JsonError),
         (status = 401, description = "Authentication required", body = JsonError),
         (status = 404, description = "Project not found", body = JsonError),
+        (status = 409, description = "Slug already in use", body = JsonError),
+        (status = 422, description = "Deadlines out of order", body = JsonError),
         (status = 500, description = "Internal server error occurred", body = JsonError)
     ),
     security(("AdminAuth" = [])),
     tag = "Projects management",
 )]
 /// Update a project details
+///
+/// The resulting deadlines must stay in chronological order (deliverable selection, then upload).
+/// Renaming a project keeps its slug, which only changes when sent.
 #[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
 pub(in crate::api::v1) async fn update_project_handler(
     path: Path<i32>, body: Json<UpdateProjectScheme>, data: Data<AppData>,
@@ -38,7 +51,7 @@ pub(in crate::api::v1) async fn update_project_handler(
     let id = path.into_inner();
 
     // Check if project exists
-    let project_exists = projects_repository::get_by_id(&data.db, id)
+    let project = projects_repository::get_by_id(&data.db, id)
         .await
         .map_err(|e| {
             error_with_log_id_and_payload(
@@ -48,11 +61,50 @@ pub(in crate::api::v1) async fn update_project_handler(
                 log::Level::Error,
                 &body,
             )
-        })?
-        .is_some();
+        })?;
 
-    if !project_exists {
+    let Some(project) = project else {
         return Err("Project not found".to_json_error(StatusCode::NOT_FOUND));
+    };
+
+    let deadlines = ProjectDeadlines {
+        deliverable_selection_deadline: project.deliverable_selection_deadline,
+        upload_deadline: body.upload_deadline.or(project.upload_deadline),
+    }
+    .normalized();
+    deadlines.validate_order()?;
+
+    if let Some(slug) = &body.slug {
+        validate_slug(slug)?;
+        let owner = projects_repository::get_by_slug(&data.db, slug)
+            .await
+            .map_err(|e| {
+                error_with_log_id_and_payload(
+                    format!("unable to check slug of project {}: {}", id, e),
+                    "Failed to update project",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                    &body,
+                )
+            })?;
+        if owner.is_some_and(|p| p.project_id != id) {
+            return Err(SLUG_TAKEN.to_json_error(StatusCode::CONFLICT));
+        }
+
+        projects_repository::update_slug(&data.db, id, slug.clone())
+            .await
+            .map_err(|e| {
+                if is_unique_violation(&e) {
+                    return SLUG_TAKEN.to_json_error(StatusCode::CONFLICT);
+                }
+                error_with_log_id_and_payload(
+                    format!("unable to update slug of project {}: {}", id, e),
+                    "Failed to update project",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                    &body,
+                )
+            })?;
     }
 
     // Update project using repository function
@@ -62,7 +114,7 @@ pub(in crate::api::v1) async fn update_project_handler(
         body.name.clone(),
         body.max_student_uploads,
         body.max_group_size,
-        body.upload_deadline,
+        body.upload_deadline.and(deadlines.upload_deadline),
         body.active,
     )
     .await
@@ -15,7 +15,7 @@ use log::error;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-fn generate_random_code() -> String {
+pub(crate) fn generate_random_code() -> String {
     use rand::RngExt;
 
     let mut rng = rand::rng();
@@ -38,6 +38,7 @@ pub(crate) struct CreateCodeScheme {
     #[schema(example = 10)]
     pub project_id: i32,
     #[schema(value_type = String, example = "2025-09-22T12:34:56Z")]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub expiration: DateTime<Utc>,
 }
 
@@ -80,7 +81,7 @@ pub(in crate::api::v1) async fn create_code_handler(
     };
 
     let skew = Duration::days(1);
-    let now = Utc::now() - skew;
+    let now = data.clock.now() - skew;
 
     if body.project_id <= 0 {
         return Err("Project id field is mandatory".to_json_error(StatusCode::BAD_REQUEST));
@@ -1,5 +1,6 @@
 use crate::app_data::AppData;
 use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::errors::delete_error;
 use crate::database::repositories::coordinator_projects_repository;
 use crate::database::repositories::security_codes::{delete as delete_security_code, get_by_id};
 use crate::jwt::get_user::LoggedUser;
@@ -25,6 +26,7 @@ pub(crate) struct DeleteCodeResponse {
         (status = 200, description = "Code deleted successfully", body = DeleteCodeResponse),
         (status = 403, description = "Access denied", body = JsonError),
         (status = 404, description = "Security code not found", body = JsonError),
+        (status = 409, description = "Still referenced by another record (code RESOURCE_IN_USE)", body = JsonError),
         (status = 500, description = "Internal server error", body = JsonError)
     ),
     security(("AdminAuth" = [])),
@@ -98,11 +100,10 @@ pub(in crate::api::v1) async fn delete_code_handler(
         Ok(_) => Ok(HttpResponse::Ok().json(DeleteCodeResponse {
             message: "Security code deleted successfully".to_string(),
         })),
-        Err(e) => Err(error_with_log_id(
-            format!("unable to delete security code from database: {}", e),
+        Err(e) => Err(delete_error(
+            e,
+            "security code",
             "Failed to delete security code",
-            StatusCode::INTERNAL_SERVER_ERROR,
-            log::Level::Error,
         )),
     }
 }
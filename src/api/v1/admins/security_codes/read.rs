@@ -14,6 +14,7 @@ use welds::prelude::DbState;
 pub struct SecurityCodeWithNames {
     pub security_code_id: i32,
     pub code: String,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub expiration: DateTime<Utc>,
     pub project_id: i32,
     pub project_name: String,
@@ -41,7 +42,7 @@ pub(crate) struct GetAllCodesResponse {
 pub(in crate::api::v1) async fn get_all_codes_handler(
     data: Data<AppData>,
 ) -> Result<HttpResponse, JsonError> {
-    let now: DateTime<Utc> = Utc::now();
+    let now: DateTime<Utc> = data.clock.now();
 
     let codes_with_projects = match security_codes::get_all_with_projects(&data.db).await {
         Ok(c) => c,
@@ -61,7 +62,7 @@ pub(in crate::api::v1) async fn get_all_codes_handler(
     // Filter for non-expired codes (Welds currently does not support filtering with datetime operators)
     let filtered_codes: Vec<(DbState<SecurityCode>, DbState<Project>)> = codes_with_projects
         .into_iter()
-        .filter(|(code, _)| code.expiration > now)
+        .filter(|(code, _)| !code.is_expired(now))
         .collect();
 
     let mut out = Vec::with_capacity(filtered_codes.len());
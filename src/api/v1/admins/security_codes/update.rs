@@ -40,6 +40,7 @@ pub(crate) struct UpdateCodeScheme {
     #[schema(example = "D3K-Z9A")]
     pub code: Option<String>,
     #[schema(value_type = String, example = "2025-09-22T12:34:56Z")]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339_opt")]
     pub expiration: Option<DateTime<Utc>>,
 }
 
@@ -50,6 +51,7 @@ pub(crate) struct UpdateCodeResponse {
     #[schema(example = "D3K-Z9A")]
     pub code: String,
     #[schema(value_type = String, example = "2025-09-22T12:34:56Z")]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub expiration: DateTime<Utc>,
     #[schema(example = 10)]
     pub project_id: i32,
@@ -136,7 +138,7 @@ pub(in crate::api::v1) async fn update_code_handler(
     // Validate expiration if provided
     if let Some(expiration) = body.expiration {
         let skew = Duration::days(1);
-        let now = Utc::now() - skew;
+        let now = data.clock.now() - skew;
 
         if expiration <= now {
             return Err(
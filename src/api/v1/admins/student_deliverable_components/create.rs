@@ -87,6 +87,7 @@ pub(super) async fn create_student_component_handler(
                     &body,
                 )
             })?;
+    data.deliverable_trees.invalidate(body.project_id);
 
     Ok(HttpResponse::Ok().json(CreateStudentComponentResponse {
         student_deliverable_component_id: state.student_deliverable_component_id,
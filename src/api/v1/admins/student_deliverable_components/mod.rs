@@ -1,3 +1,4 @@
+use crate::api::v1::admins::student_deliverable_components::batch_delete::batch_delete_student_components_handler;
 use crate::api::v1::admins::student_deliverable_components::create::create_student_component_handler;
 use crate::api::v1::admins::student_deliverable_components::delete::delete_student_component_handler;
 use crate::api::v1::admins::student_deliverable_components::read::{
@@ -5,8 +6,10 @@ use crate::api::v1::admins::student_deliverable_components::read::{
     get_student_component_handler, get_student_components_for_project_handler,
 };
 use crate::api::v1::admins::student_deliverable_components::update::update_student_component_handler;
+use crate::database::unit_of_work::UnitOfWorkMiddleware;
 use actix_web::{web, Scope};
 
+pub(crate) mod batch_delete;
 pub(crate) mod create;
 pub(crate) mod delete;
 pub(crate) mod read;
@@ -16,6 +19,11 @@ pub(super) fn student_deliverable_components_scope() -> Scope {
     web::scope("/student-deliverable-components")
         .route("", web::get().to(get_all_student_components_handler))
         .route("", web::post().to(create_student_component_handler))
+        .service(
+            web::resource("/batch-delete")
+                .wrap(UnitOfWorkMiddleware)
+                .route(web::post().to(batch_delete_student_components_handler)),
+        )
         .route(
             "/project/{project_id}",
             web::get().to(get_student_components_for_project_handler),
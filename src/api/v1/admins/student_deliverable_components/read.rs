@@ -40,6 +40,8 @@ pub(crate) struct StudentComponentDeliverableResponse {
     pub student_deliverable_component_id: i32,
     #[schema(example = "5")]
     pub quantity: i32,
+    #[schema(example = "30")]
+    pub weight: i32,
     #[schema(example = "Motor")]
     pub deliverable_name: String,
 }
@@ -249,6 +251,7 @@ pub(super) async fn get_deliverables_for_student_component_handler(
             student_deliverable_id: relationship_data.student_deliverable_id,
             student_deliverable_component_id: relationship_data.student_deliverable_component_id,
             quantity: relationship_data.quantity,
+            weight: relationship_data.weight,
             deliverable_name: deliverable.name,
         });
     }
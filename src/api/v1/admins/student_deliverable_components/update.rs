@@ -78,6 +78,7 @@ pub(super) async fn update_student_component_handler(
     // Update the name
     component_state.name = body.name.clone();
 
+    let project_id = component_state.project_id;
     student_deliverable_components_repository::update(&data.db, component_state)
         .await
         .map_err(|e| {
@@ -89,6 +90,7 @@ pub(super) async fn update_student_component_handler(
                 &body,
             )
         })?;
+    data.deliverable_trees.invalidate(project_id);
 
     Ok(HttpResponse::Ok().finish())
 }
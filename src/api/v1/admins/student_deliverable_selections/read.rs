@@ -45,6 +45,7 @@ pub(crate) struct StudentSelectionInfo {
 ///
 /// This endpoint allows admins to view all student deliverable selections for a specific project,
 /// including which deliverables each student has chosen.
+/// A project without selections gets an empty list, 404 is only for a missing project.
 #[actix_web_grants::protect(any(
     "ROLE_ADMIN_ROOT",
     "ROLE_ADMIN_PROFESSOR",
@@ -0,0 +1,83 @@
+use crate::app_data::AppData;
+use crate::common::batch_delete::{
+    count_dependents, plan_deletes, validate_batch, BatchDeleteRequest, BatchDeleteResponse,
+};
+use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::database::errors::delete_error;
+use crate::database::repositories::student_deliverables_repository;
+use crate::database::unit_of_work::UnitOfWork;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Json};
+use actix_web::HttpResponse;
+
+/// Every requested id that exists, with the number of rows depending on it
+const DEPENDENTS: &str = "
+SELECT d.student_deliverable_id,
+       (SELECT COUNT(*) FROM student_deliverable_selections s
+         WHERE s.student_deliverable_id = d.student_deliverable_id)
+FROM student_deliverables d
+WHERE d.student_deliverable_id = ANY($1)";
+
+/// What the dependents counted by `DEPENDENTS` are
+const DEPENDENTS_NOUN: &str = "student selections";
+
+#[utoipa::path(
+    post,
+    path = "/v1/admins/student-deliverables/batch-delete",
+    request_body = BatchDeleteRequest,
+    responses(
+        (status = 200, description = "Batch processed, see per-id results", body = BatchDeleteResponse),
+        (status = 400, description = "No ids or too many ids", body = JsonError),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 409, description = "Still referenced by another record (code RESOURCE_IN_USE)", body = JsonError),
+        (status = 500, description = "Internal server error occurred", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Student deliverables management",
+)]
+/// Deletes several student deliverables at once
+///
+/// A deliverable already selected by some student is refused unless `cascade` is set, in
+/// which case the selections go with it. Everything is deleted in one transaction and each
+/// id gets its own outcome.
+#[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
+pub(super) async fn batch_delete_student_deliverables_handler(
+    body: Json<BatchDeleteRequest>, data: Data<AppData>, unit_of_work: UnitOfWork,
+) -> Result<HttpResponse, JsonError> {
+    validate_batch(&body.ids)?;
+
+    let existing = count_dependents(&unit_of_work, DEPENDENTS, &body.ids)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!(
+                    "unable to check the dependents of student deliverables: {}",
+                    e
+                ),
+                "Failed to delete student deliverables",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+    let response = BatchDeleteResponse::new(plan_deletes(
+        &body.ids,
+        &existing,
+        body.cascade,
+        DEPENDENTS_NOUN,
+    ));
+
+    student_deliverables_repository::delete_by_ids(&unit_of_work, &response.deleted_ids())
+        .await
+        .map_err(|e| {
+            delete_error(
+                e,
+                "student deliverables",
+                "Failed to delete student deliverables",
+            )
+        })?;
+    let deliverable_trees = data.deliverable_trees.clone();
+    unit_of_work.after_commit(move || deliverable_trees.invalidate_all());
+
+    Ok(HttpResponse::Ok().json(response))
+}
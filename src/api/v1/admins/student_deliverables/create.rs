@@ -1,4 +1,5 @@
 use crate::app_data::AppData;
+use crate::common::created::created;
 use crate::common::json_error::{error_with_log_id_and_payload, JsonError, ToJsonError};
 use crate::database::repositories::student_deliverables_repository;
 use crate::models::student_deliverable::StudentDeliverable;
@@ -31,7 +32,9 @@ pub(crate) struct CreateStudentDeliverableResponse {
     path = "/v1/admins/student-deliverables",
     request_body = CreateStudentDeliverableScheme,
     responses(
-        (status = 200, description = "Student deliverable created successfully", body = CreateStudentDeliverableResponse),
+        (status = 201, description = "Student deliverable created successfully", body = CreateStudentDeliverableResponse,
+            headers(("Location" = String, description = "URL of the created student deliverable"))
+        ),
         (status = 400, description = "Invalid data in request", body = JsonError),
         (status = 401, description = "Authentication required", body = JsonError),
         (status = 409, description = "Deliverable with this name already exists for the project", body = JsonError),
@@ -83,10 +86,15 @@ pub(super) async fn create_student_deliverable_handler(
                 &body,
             )
         })?;
+    data.deliverable_trees.invalidate(body.project_id);
 
-    Ok(HttpResponse::Ok().json(CreateStudentDeliverableResponse {
-        student_deliverable_id: state.student_deliverable_id,
-        project_id: body.project_id,
-        name: body.name.clone(),
-    }))
+    Ok(created(
+        "/v1/admins/student-deliverables",
+        state.student_deliverable_id,
+        &CreateStudentDeliverableResponse {
+            student_deliverable_id: state.student_deliverable_id,
+            project_id: body.project_id,
+            name: body.name.clone(),
+        },
+    ))
 }
@@ -0,0 +1,146 @@
+use crate::api::v1::admins::group_deliverables::link_impact::{
+    parse_component_ids, LinkImpactQuery,
+};
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::repositories::{
+    student_deliverable_selections_repository, student_deliverables_components_repository,
+    student_deliverables_repository,
+};
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Path, Query};
+use actix_web::HttpResponse;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use utoipa::ToSchema;
+
+#[derive(Debug, PartialEq, Eq, Serialize, ToSchema)]
+pub(crate) struct StudentLinkImpactResponse {
+    pub student_deliverable_id: i32,
+    /// Components linked now that the change would unlink
+    pub removed_component_ids: Vec<i32>,
+    /// Components the change would link
+    pub added_component_ids: Vec<i32>,
+    /// Students who selected the deliverable
+    #[schema(example = 12)]
+    pub selections: u64,
+    /// Selections whose components would change, all of them unless the links stay the same
+    #[schema(example = 12)]
+    pub affected_selections: u64,
+}
+
+/// What changing the links of a student deliverable from `current` to `proposed` changes
+///
+/// Student selections keep nothing per component, so no data depends on a link: every
+/// selection sees the new components, none loses anything.
+fn link_impact(
+    student_deliverable_id: i32, current: &BTreeSet<i32>, proposed: &BTreeSet<i32>, selections: u64,
+) -> StudentLinkImpactResponse {
+    let changed = current != proposed;
+
+    StudentLinkImpactResponse {
+        student_deliverable_id,
+        removed_component_ids: current.difference(proposed).copied().collect(),
+        added_component_ids: proposed.difference(current).copied().collect(),
+        selections,
+        affected_selections: if changed { selections } else { 0 },
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/admins/student-deliverables/{id}/link-impact",
+    params(("id" = i32, Path, description = "Student deliverable ID"), LinkImpactQuery),
+    responses(
+        (status = 200, description = "Impact of the proposed links on the existing selections", body = StudentLinkImpactResponse),
+        (status = 400, description = "Invalid component IDs (code INVALID_PARAM)", body = JsonError),
+        (status = 404, description = "Student deliverable not found", body = JsonError),
+        (status = 500, description = "Internal server error occurred", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Student deliverables management",
+)]
+/// Preview the impact of changing the components of a student deliverable
+///
+/// Compares the components linked now with the proposed ones and counts the students whose
+/// selection would see its components change. Nothing is changed.
+#[actix_web_grants::protect(any(
+    "ROLE_ADMIN_ROOT",
+    "ROLE_ADMIN_PROFESSOR",
+    "ROLE_ADMIN_COORDINATOR"
+))]
+pub(super) async fn get_student_deliverable_link_impact_handler(
+    path: Path<i32>, query: Query<LinkImpactQuery>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let deliverable_id = path.into_inner();
+    let proposed = parse_component_ids(&query.component_ids)?;
+
+    let db_error = |what: &str, e: welds::WeldsError| {
+        error_with_log_id(
+            format!(
+                "unable to {} of student deliverable {}: {}",
+                what, deliverable_id, e
+            ),
+            "Failed to preview the impact",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    };
+
+    student_deliverables_repository::get_by_id(&data.db, deliverable_id)
+        .await
+        .map_err(|e| db_error("fetch", e))?
+        .ok_or_else(|| "Student deliverable not found".to_json_error(StatusCode::NOT_FOUND))?;
+
+    let current: BTreeSet<i32> =
+        student_deliverables_components_repository::get_by_deliverable_id(&data.db, deliverable_id)
+            .await
+            .map_err(|e| db_error("fetch the component links", e))?
+            .into_iter()
+            .map(|link| link.student_deliverable_component_id)
+            .collect();
+
+    let selections = student_deliverable_selections_repository::count_by_deliverable_id(
+        &data.db,
+        deliverable_id,
+    )
+    .await
+    .map_err(|e| db_error("count the selections", e))?;
+
+    Ok(HttpResponse::Ok().json(link_impact(deliverable_id, &current, &proposed, selections)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(ids: &[i32]) -> BTreeSet<i32> {
+        ids.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_changed_links_reach_every_selection() {
+        let impact = link_impact(4, &ids(&[1, 2, 3]), &ids(&[1, 3, 5]), 7);
+
+        assert_eq!(
+            impact,
+            StudentLinkImpactResponse {
+                student_deliverable_id: 4,
+                removed_component_ids: vec![2],
+                added_component_ids: vec![5],
+                selections: 7,
+                affected_selections: 7,
+            }
+        );
+    }
+
+    #[test]
+    fn test_unchanged_links_have_no_impact() {
+        let impact = link_impact(4, &ids(&[1, 2]), &ids(&[2, 1]), 7);
+
+        assert!(impact.removed_component_ids.is_empty());
+        assert!(impact.added_component_ids.is_empty());
+        assert_eq!(impact.selections, 7);
+        assert_eq!(impact.affected_selections, 0);
+    }
+}
@@ -40,6 +40,8 @@ pub(crate) struct StudentDeliverableComponentResponse {
     pub student_deliverable_component_id: i32,
     #[schema(example = "5")]
     pub quantity: i32,
+    #[schema(example = "30")]
+    pub weight: i32,
     #[schema(example = "Resistor")]
     pub component_name: String,
 }
@@ -253,6 +255,7 @@ pub(super) async fn get_components_for_student_deliverable_handler(
             student_deliverable_id: relationship_data.student_deliverable_id,
             student_deliverable_component_id: relationship_data.student_deliverable_component_id,
             quantity: relationship_data.quantity,
+            weight: relationship_data.weight,
             component_name: component.name,
         });
     }
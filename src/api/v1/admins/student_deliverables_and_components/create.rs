@@ -1,5 +1,9 @@
 use crate::app_data::AppData;
-use crate::common::json_error::{error_with_log_id_and_payload, JsonError, ToJsonError};
+use crate::common::json_error::{
+    error_with_log_id_and_payload, JsonError, ToJsonError, LINK_EXISTS,
+};
+use crate::common::link_weights::validate_link;
+use crate::database::errors::is_unique_violation;
 use crate::database::repositories::student_deliverables_components_repository;
 use crate::models::student_deliverables_component::StudentDeliverablesComponent;
 use actix_web::http::StatusCode;
@@ -16,6 +20,10 @@ pub(crate) struct CreateStudentDeliverableComponentScheme {
     pub student_deliverable_component_id: i32,
     #[schema(example = "5")]
     pub quantity: i32,
+    /// Grading weight of the component within the deliverable, defaults to 0
+    #[serde(default)]
+    #[schema(example = "30")]
+    pub weight: i32,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -28,6 +36,15 @@ pub(crate) struct CreateStudentDeliverableComponentResponse {
     pub student_deliverable_component_id: i32,
     #[schema(example = "5")]
     pub quantity: i32,
+    #[schema(example = "30")]
+    pub weight: i32,
+}
+
+/// Conflict returned both by the pre-check and when the unique constraint catches a race
+fn link_exists_error() -> JsonError {
+    "Relationship already exists"
+        .to_json_error(StatusCode::CONFLICT)
+        .with_code(LINK_EXISTS)
 }
 
 #[utoipa::path(
@@ -38,7 +55,8 @@ pub(crate) struct CreateStudentDeliverableComponentResponse {
         (status = 200, description = "Student deliverable-component relationship created successfully", body = CreateStudentDeliverableComponentResponse),
         (status = 400, description = "Invalid data in request", body = JsonError),
         (status = 401, description = "Authentication required", body = JsonError),
-        (status = 409, description = "Relationship already exists", body = JsonError),
+        (status = 422, description = "Quantity or weight out of range, or weights summing over 100", body = JsonError),
+        (status = 409, description = "Relationship already exists (code LINK_EXISTS)", body = JsonError),
         (status = 500, description = "Internal server error occurred", body = JsonError)
     ),
     security(("AdminAuth" = [])),
@@ -69,14 +87,33 @@ pub(super) async fn create_student_deliverable_component_handler(
     })?;
 
     if exists {
-        return Err("Relationship already exists".to_json_error(StatusCode::CONFLICT));
+        return Err(link_exists_error());
     }
 
+    let other_weights = student_deliverables_components_repository::total_weight_for_deliverable(
+        &data.db,
+        body.student_deliverable_id,
+        None,
+    )
+    .await
+    .map_err(|e| {
+        error_with_log_id_and_payload(
+            format!("unable to sum component weights: {}", e),
+            "Failed to create relationship",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+            &body,
+        )
+    })?;
+
+    validate_link(body.quantity, body.weight, other_weights)?;
+
     let student_deliverables_component = StudentDeliverablesComponent {
         id: 0,
         student_deliverable_id: body.student_deliverable_id,
         student_deliverable_component_id: body.student_deliverable_component_id,
         quantity: body.quantity,
+        weight: body.weight,
     };
 
     let state = student_deliverables_components_repository::create(
@@ -85,6 +122,9 @@ pub(super) async fn create_student_deliverable_component_handler(
     )
     .await
     .map_err(|e| {
+        if is_unique_violation(&e) {
+            return link_exists_error();
+        }
         error_with_log_id_and_payload(
             format!(
                 "unable to create student deliverable component relationship: {}",
@@ -96,6 +136,7 @@ pub(super) async fn create_student_deliverable_component_handler(
             &body,
         )
     })?;
+    data.deliverable_trees.invalidate_all();
 
     Ok(
         HttpResponse::Ok().json(CreateStudentDeliverableComponentResponse {
@@ -103,6 +144,7 @@ pub(super) async fn create_student_deliverable_component_handler(
             student_deliverable_id: body.student_deliverable_id,
             student_deliverable_component_id: body.student_deliverable_component_id,
             quantity: body.quantity,
+            weight: body.weight,
         }),
     )
 }
@@ -1,5 +1,6 @@
 use crate::app_data::AppData;
 use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::errors::delete_error;
 use crate::database::repositories::student_deliverables_components_repository;
 use actix_web::http::StatusCode;
 use actix_web::web::Data;
@@ -13,6 +14,7 @@ use actix_web::HttpResponse;
         (status = 200, description = "Student deliverable component relationship deleted successfully"),
         (status = 401, description = "Authentication required", body = JsonError),
         (status = 404, description = "Relationship not found", body = JsonError),
+        (status = 409, description = "Still referenced by another record (code RESOURCE_IN_USE)", body = JsonError),
         (status = 500, description = "Internal server error occurred", body = JsonError)
     ),
     security(("AdminAuth" = [])),
@@ -51,16 +53,13 @@ pub(super) async fn delete_student_deliverable_component_handler(
     student_deliverables_components_repository::delete_by_id(&data.db, id)
         .await
         .map_err(|e| {
-            error_with_log_id(
-                format!(
-                    "unable to delete student deliverable component relationship: {}",
-                    e
-                ),
+            delete_error(
+                e,
+                "student deliverable component relationship",
                 "Failed to delete relationship",
-                StatusCode::INTERNAL_SERVER_ERROR,
-                log::Level::Error,
             )
         })?;
+    data.deliverable_trees.invalidate_all();
 
     Ok(HttpResponse::Ok().finish())
 }
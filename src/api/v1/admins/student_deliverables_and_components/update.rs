@@ -1,5 +1,6 @@
 use crate::app_data::AppData;
 use crate::common::json_error::{error_with_log_id_and_payload, JsonError, ToJsonError};
+use crate::common::link_weights::validate_link;
 use crate::database::repositories::student_deliverables_components_repository;
 use actix_web::http::StatusCode;
 use actix_web::web::Path;
@@ -12,6 +13,9 @@ use utoipa::ToSchema;
 pub(crate) struct UpdateStudentDeliverableComponentScheme {
     #[schema(example = "10")]
     pub quantity: i32,
+    /// New grading weight, the current one is kept when omitted
+    #[schema(example = "30")]
+    pub weight: Option<i32>,
 }
 
 #[utoipa::path(
@@ -23,14 +27,15 @@ pub(crate) struct UpdateStudentDeliverableComponentScheme {
         (status = 400, description = "Invalid data in request", body = JsonError),
         (status = 401, description = "Authentication required", body = JsonError),
         (status = 404, description = "Relationship not found", body = JsonError),
+        (status = 422, description = "Quantity or weight out of range, or weights summing over 100", body = JsonError),
         (status = 500, description = "Internal server error occurred", body = JsonError)
     ),
     security(("AdminAuth" = [])),
     tag = "Student deliverables-components management",
 )]
-/// Updates the quantity of a component in a student deliverable.
+/// Updates the quantity and weight of a component in a student deliverable.
 ///
-/// This endpoint allows authenticated admins to modify the quantity of a component in a student deliverable by ID.
+/// This endpoint allows authenticated admins to modify the quantity and weight of a component in a student deliverable by ID.
 #[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
 pub(super) async fn update_student_deliverable_component_handler(
     path: Path<i32>, body: Json<UpdateStudentDeliverableComponentScheme>, data: Data<AppData>,
@@ -54,8 +59,27 @@ pub(super) async fn update_student_deliverable_component_handler(
             })?
             .ok_or_else(|| "Relationship not found".to_json_error(StatusCode::NOT_FOUND))?;
 
-    // Update the quantity
+    let weight = body.weight.unwrap_or(relationship_state.weight);
+    let other_weights = student_deliverables_components_repository::total_weight_for_deliverable(
+        &data.db,
+        relationship_state.student_deliverable_id,
+        Some(id),
+    )
+    .await
+    .map_err(|e| {
+        error_with_log_id_and_payload(
+            format!("unable to sum component weights: {}", e),
+            "Failed to update relationship",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+            &body,
+        )
+    })?;
+
+    validate_link(body.quantity, weight, other_weights)?;
+
     relationship_state.quantity = body.quantity;
+    relationship_state.weight = weight;
 
     student_deliverables_components_repository::update(&data.db, relationship_state)
         .await
@@ -71,6 +95,7 @@ pub(super) async fn update_student_deliverable_component_handler(
                 &body,
             )
         })?;
+    data.deliverable_trees.invalidate_all();
 
     Ok(HttpResponse::Ok().finish())
 }
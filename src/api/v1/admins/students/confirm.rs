@@ -0,0 +1,275 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::repositories::{audit_log_repository, students_repository};
+use crate::database::unit_of_work::UnitOfWork;
+use crate::jwt::get_user::LoggedUser;
+use crate::models::audit_log::{AuditLog, STUDENTS_CONFIRMED};
+use crate::models::student::Student;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Json};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use utoipa::ToSchema;
+use welds::state::DbState;
+
+/// Upper bound on the number of students confirmed in a single request
+pub(crate) const MAX_BULK_CONFIRM: usize = 500;
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub(crate) struct ConfirmStudentsRequest {
+    /// Students to confirm by id
+    #[serde(default)]
+    #[schema(example = json!([12, 15]))]
+    pub student_ids: Vec<i32>,
+    /// Students to confirm by email
+    #[serde(default)]
+    #[schema(example = json!(["mario.rossi@studenti.unitn.it"]))]
+    pub emails: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ConfirmOutcome {
+    Confirmed,
+    AlreadyConfirmed,
+    NotFound,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ConfirmStudentResult {
+    /// Requested id, or id of the student found by email
+    pub student_id: Option<i32>,
+    /// Requested email, or email of the student found by id
+    pub email: Option<String>,
+    pub outcome: ConfirmOutcome,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ConfirmStudentsResponse {
+    /// Number of students actually confirmed by this request
+    pub confirmed: usize,
+    /// One entry per requested id, then one per requested email, in request order
+    pub results: Vec<ConfirmStudentResult>,
+}
+
+impl ConfirmStudentsResponse {
+    /// Ids of the pending students the request confirms, each once
+    fn confirmed_ids(&self) -> Vec<i32> {
+        self.results
+            .iter()
+            .filter(|r| r.outcome == ConfirmOutcome::Confirmed)
+            .filter_map(|r| r.student_id)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Rejects empty and oversized requests
+fn validate_request(body: &ConfirmStudentsRequest) -> Result<(), JsonError> {
+    let requested = body.student_ids.len() + body.emails.len();
+    if requested == 0 {
+        return Err(
+            "At least one student id or email is required".to_json_error(StatusCode::BAD_REQUEST)
+        );
+    }
+    if requested > MAX_BULK_CONFIRM {
+        return Err(format!(
+            "At most {} students can be confirmed at once",
+            MAX_BULK_CONFIRM
+        )
+        .to_json_error(StatusCode::BAD_REQUEST));
+    }
+    Ok(())
+}
+
+fn outcome_of(student: Option<&Student>) -> ConfirmOutcome {
+    match student {
+        Some(s) if s.is_pending => ConfirmOutcome::Confirmed,
+        Some(_) => ConfirmOutcome::AlreadyConfirmed,
+        None => ConfirmOutcome::NotFound,
+    }
+}
+
+/// Outcome of each requested id and email, given the students `found` by either
+fn plan_confirmations(body: &ConfirmStudentsRequest, found: &[Student]) -> ConfirmStudentsResponse {
+    let by_id = body.student_ids.iter().map(|&id| {
+        let student = found.iter().find(|s| s.student_id == id);
+        ConfirmStudentResult {
+            student_id: Some(id),
+            email: student.map(|s| s.email.clone()),
+            outcome: outcome_of(student),
+        }
+    });
+    let by_email = body.emails.iter().map(|email| {
+        let student = found.iter().find(|s| &s.email == email);
+        ConfirmStudentResult {
+            student_id: student.map(|s| s.student_id),
+            email: Some(email.clone()),
+            outcome: outcome_of(student),
+        }
+    });
+
+    let mut response = ConfirmStudentsResponse {
+        confirmed: 0,
+        results: by_id.chain(by_email).collect(),
+    };
+    response.confirmed = response.confirmed_ids().len();
+    response
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/admins/students/confirm",
+    request_body = ConfirmStudentsRequest,
+    responses(
+        (status = 200, description = "Request processed, see per-student results", body = ConfirmStudentsResponse),
+        (status = 400, description = "No students or too many students", body = JsonError),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 500, description = "Internal server error occurred", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Student users management",
+)]
+/// Confirms several students at once, in place of their confirmation email
+///
+/// Meant for when email delivery is broken: the listed students can log in as if they had
+/// followed the link they were sent. Students are found by id or by email, and each one
+/// gets its own outcome; unknown ones are reported without failing the request. All
+/// confirmations happen in one transaction and are recorded in the audit log.
+#[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
+pub(super) async fn confirm_students_handler(
+    req: HttpRequest, body: Json<ConfirmStudentsRequest>, data: Data<AppData>,
+    unit_of_work: UnitOfWork,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    validate_request(&body)?;
+
+    let db_error = |e: welds::WeldsError| {
+        error_with_log_id(
+            format!("unable to confirm students in bulk: {}", e),
+            "Failed to confirm students",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    };
+
+    let mut found: Vec<Student> = students_repository::get_by_ids(&data.db, &body.student_ids)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(DbState::into_inner)
+        .collect();
+    found.extend(
+        students_repository::get_by_emails(&data.db, &body.emails)
+            .await
+            .map_err(db_error)?
+            .into_iter()
+            .map(DbState::into_inner),
+    );
+
+    let response = plan_confirmations(&body, &found);
+    let confirmed_ids = response.confirmed_ids();
+
+    students_repository::confirm_by_ids(&unit_of_work, &confirmed_ids)
+        .await
+        .map_err(db_error)?;
+
+    if !confirmed_ids.is_empty() {
+        audit_log_repository::record_or_warn(
+            &data.db,
+            AuditLog::by_admin(admin.admin_id, STUDENTS_CONFIRMED)
+                .details(format!("students {:?}", confirmed_ids)),
+        )
+        .await;
+    }
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::ResponseError;
+
+    fn student(student_id: i32, is_pending: bool) -> Student {
+        Student {
+            student_id,
+            first_name: "Mario".to_string(),
+            last_name: "Rossi".to_string(),
+            email: format!("student{}@studenti.unitn.it", student_id),
+            university_id: 200_000 + student_id,
+            password_hash: String::new(),
+            is_pending,
+            sessions_revoked_at: None,
+        }
+    }
+
+    #[test]
+    fn test_mix_of_existing_and_nonexistent_students() {
+        let body = ConfirmStudentsRequest {
+            student_ids: vec![1, 2, 99],
+            emails: vec![
+                "student3@studenti.unitn.it".to_string(),
+                "nobody@studenti.unitn.it".to_string(),
+            ],
+        };
+        let found = vec![student(1, true), student(2, false), student(3, true)];
+
+        let response = plan_confirmations(&body, &found);
+
+        let outcomes: Vec<_> = response.results.iter().map(|r| r.outcome).collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ConfirmOutcome::Confirmed,
+                ConfirmOutcome::AlreadyConfirmed,
+                ConfirmOutcome::NotFound,
+                ConfirmOutcome::Confirmed,
+                ConfirmOutcome::NotFound,
+            ]
+        );
+        assert_eq!(response.results[3].student_id, Some(3));
+        assert_eq!(response.results[2].email, None);
+        assert_eq!(response.confirmed_ids(), vec![1, 3]);
+        assert_eq!(response.confirmed, 2);
+    }
+
+    #[test]
+    fn test_student_requested_twice_is_confirmed_once() {
+        let body = ConfirmStudentsRequest {
+            student_ids: vec![4, 4],
+            emails: vec!["student4@studenti.unitn.it".to_string()],
+        };
+
+        let response = plan_confirmations(&body, &[student(4, true)]);
+
+        assert_eq!(response.results.len(), 3);
+        assert_eq!(response.confirmed_ids(), vec![4]);
+        assert_eq!(response.confirmed, 1);
+    }
+
+    #[test]
+    fn test_empty_and_oversized_requests_are_rejected() {
+        let empty = ConfirmStudentsRequest::default();
+        let oversized = ConfirmStudentsRequest {
+            student_ids: (0..MAX_BULK_CONFIRM as i32).collect(),
+            emails: vec!["one.more@studenti.unitn.it".to_string()],
+        };
+
+        assert_eq!(
+            validate_request(&empty).unwrap_err().status_code(),
+            StatusCode::BAD_REQUEST
+        );
+        assert!(validate_request(&oversized).is_err());
+    }
+}
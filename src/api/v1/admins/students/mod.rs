@@ -0,0 +1,13 @@
+use crate::api::v1::admins::students::confirm::confirm_students_handler;
+use crate::database::unit_of_work::UnitOfWorkMiddleware;
+use actix_web::{web, Scope};
+
+pub(crate) mod confirm;
+
+pub(super) fn students_scope() -> Scope {
+    web::scope("/students").service(
+        web::resource("/confirm")
+            .wrap(UnitOfWorkMiddleware)
+            .route(web::post().to(confirm_students_handler)),
+    )
+}
@@ -0,0 +1,369 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::common::pagination::PageLimits;
+use crate::database::repositories::students_repository;
+use crate::database::timing::timed;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Query};
+use actix_web::HttpResponse;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use utoipa::{IntoParams, ToSchema};
+
+/// Side of a transaction from the point of view of the student
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum TransactionType {
+    /// A group of the student sold a component
+    Credit,
+    /// A group of the student bought a component
+    Debit,
+}
+
+impl TransactionType {
+    /// Purchases of the student's groups are debits, reversing a purchase credits it back
+    fn of(is_purchase: bool, is_reversal: bool) -> Self {
+        if is_purchase != is_reversal {
+            TransactionType::Debit
+        } else {
+            TransactionType::Credit
+        }
+    }
+}
+
+/// Filters of the transactions list
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct TransactionsQuery {
+    /// Student whose transactions are listed, through the groups they are members of
+    pub student_id: i32,
+    #[serde(rename = "type")]
+    #[param(rename = "type")]
+    pub transaction_type: Option<TransactionType>,
+    /// Only transactions at or after this time
+    #[param(value_type = Option<String>)]
+    pub from: Option<DateTime<Utc>>,
+    /// Only transactions at or before this time
+    #[param(value_type = Option<String>)]
+    pub to: Option<DateTime<Utc>>,
+    /// Cursor, the `next_after` of the previous page
+    pub after: Option<i32>,
+    /// Number of entries per page
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub(crate) struct TransactionEntry {
+    pub transaction_id: i32,
+    pub fair_id: i32,
+    #[serde(rename = "type")]
+    pub transaction_type: TransactionType,
+    pub group_deliverable_component_id: i32,
+    pub component_name: String,
+    pub buyer_group_id: i32,
+    pub seller_group_id: i32,
+    #[schema(value_type = String)]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub timestamp: DateTime<Utc>,
+    /// Transaction cancelled by this entry, its type is the opposite of the cancelled one
+    pub reverses_transaction_id: Option<i32>,
+    /// Components sold minus components bought by the student's groups, up to this entry
+    pub balance: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct TransactionsResponse {
+    pub student_id: i32,
+    pub entries: Vec<TransactionEntry>,
+    /// Cursor of the next page, absent on the last one
+    pub next_after: Option<i32>,
+}
+
+/// Checks the filters, returning the page size within `limits`
+fn page_size(query: &TransactionsQuery, limits: PageLimits) -> Result<usize, JsonError> {
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        if from > to {
+            return Err("from must not be after to".to_json_error(StatusCode::BAD_REQUEST));
+        }
+    }
+    Ok(query
+        .limit
+        .unwrap_or(limits.default_per_page)
+        .clamp(1, limits.max_per_page) as usize)
+}
+
+/// Transactions of the student in `$1`, oldest first, with the running balance
+///
+/// The balance is computed over every transaction of the student, before the filters of the
+/// page apply, so filtered pages still show the true balance.
+const LEDGER: &str = r#"
+    WITH student_groups AS (
+        SELECT group_id FROM group_members WHERE student_id = $1
+    ),
+    ledger AS (
+        SELECT
+            t.transaction_id,
+            t.fair_id,
+            t.group_deliverable_component_id,
+            gdc.name AS component_name,
+            t.buyer_group_id,
+            gds.group_id AS seller_group_id,
+            t.timestamp,
+            t.reverses_transaction_id,
+            t.buyer_group_id IN (SELECT group_id FROM student_groups) AS is_purchase,
+            SUM(CASE
+                WHEN (t.buyer_group_id IN (SELECT group_id FROM student_groups))
+                    <> (t.reverses_transaction_id IS NOT NULL) THEN -1
+                ELSE 1
+            END) OVER (ORDER BY t.timestamp, t.transaction_id) AS balance
+        FROM transactions t
+        JOIN group_deliverable_selections gds
+            ON t.group_deliverable_selection_id = gds.group_deliverable_selection_id
+        JOIN group_deliverable_components gdc
+            ON t.group_deliverable_component_id = gdc.group_deliverable_component_id
+        WHERE t.buyer_group_id IN (SELECT group_id FROM student_groups)
+           OR gds.group_id IN (SELECT group_id FROM student_groups)
+    )
+"#;
+
+/// Position of the cursor `$2` in the ledger, none when it is not a transaction of the student
+fn cursor_query() -> String {
+    format!(
+        "{} SELECT timestamp, transaction_id FROM ledger WHERE transaction_id = $2",
+        LEDGER
+    )
+}
+
+/// Entries of the ledger after the cursor `($2, $3)` matching the filters, at most `$7`
+///
+/// `$4` keeps only debits when true and only credits when false.
+fn page_query() -> String {
+    format!(
+        r#"{}
+        SELECT * FROM ledger
+        WHERE ($2::timestamptz IS NULL OR (timestamp, transaction_id) > ($2, $3))
+          AND ($4::boolean IS NULL OR (is_purchase <> (reverses_transaction_id IS NOT NULL)) = $4)
+          AND ($5::timestamptz IS NULL OR timestamp >= $5)
+          AND ($6::timestamptz IS NULL OR timestamp <= $6)
+        ORDER BY timestamp, transaction_id
+        LIMIT $7
+        "#,
+        LEDGER
+    )
+}
+
+/// Cuts the `limit + 1` entries fetched for a page down to the page and the next cursor
+fn next_page(
+    mut entries: Vec<TransactionEntry>, limit: usize,
+) -> (Vec<TransactionEntry>, Option<i32>) {
+    let next_after = if entries.len() > limit {
+        entries.truncate(limit);
+        entries.last().map(|e| e.transaction_id)
+    } else {
+        None
+    };
+    (entries, next_after)
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/admins/transactions",
+    params(TransactionsQuery),
+    responses(
+        (status = 200, description = "Page of the student's transactions, oldest first, with the running balance", body = TransactionsResponse),
+        (status = 400, description = "Invalid filters or cursor", body = JsonError),
+        (status = 404, description = "Student not found", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError),
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Fairs management",
+)]
+/// List the fair transactions of a student
+///
+/// Sales of the student's groups are credits and purchases are debits. Pages are walked
+/// with the `next_after` cursor.
+#[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
+pub(super) async fn list_student_transactions_handler(
+    query: Query<TransactionsQuery>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let limit = page_size(&query, data.page_limits)?;
+    let student_id = query.student_id;
+
+    students_repository::get_by_id(&data.db, student_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("DB error fetching student {}: {}", student_id, e),
+                "Failed to fetch student",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?
+        .ok_or_else(|| "Student not found".to_json_error(StatusCode::NOT_FOUND))?;
+
+    let db_error = |e: sqlx::Error| {
+        error_with_log_id(
+            format!(
+                "DB error fetching transactions of student {}: {}",
+                student_id, e
+            ),
+            "Failed to fetch transactions",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    };
+
+    let cursor: Option<(DateTime<Utc>, i32)> = match query.after {
+        Some(after) => Some(
+            timed(
+                "transactions.ledger_cursor",
+                sqlx::query_as(&cursor_query())
+                    .bind(student_id)
+                    .bind(after)
+                    .fetch_optional(data.db.as_sqlx_pool()),
+            )
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| "Unknown cursor".to_json_error(StatusCode::BAD_REQUEST))?,
+        ),
+        None => None,
+    };
+
+    let rows = timed(
+        "transactions.ledger_page",
+        sqlx::query(&page_query())
+            .bind(student_id)
+            .bind(cursor.map(|(timestamp, _)| timestamp))
+            .bind(cursor.map(|(_, transaction_id)| transaction_id))
+            .bind(query.transaction_type.map(|t| t == TransactionType::Debit))
+            .bind(query.from)
+            .bind(query.to)
+            .bind(limit as i64 + 1)
+            .fetch_all(data.db.as_sqlx_pool()),
+    )
+    .await
+    .map_err(db_error)?;
+
+    let entries = rows
+        .into_iter()
+        .map(|r| TransactionEntry {
+            transaction_id: r.get("transaction_id"),
+            fair_id: r.get("fair_id"),
+            transaction_type: TransactionType::of(
+                r.get("is_purchase"),
+                r.get::<Option<i32>, _>("reverses_transaction_id").is_some(),
+            ),
+            group_deliverable_component_id: r.get("group_deliverable_component_id"),
+            component_name: r.get("component_name"),
+            buyer_group_id: r.get("buyer_group_id"),
+            seller_group_id: r.get("seller_group_id"),
+            timestamp: r.get("timestamp"),
+            reverses_transaction_id: r.get("reverses_transaction_id"),
+            balance: r.get("balance"),
+        })
+        .collect();
+
+    let (entries, next_after) = next_page(entries, limit);
+
+    Ok(HttpResponse::Ok().json(TransactionsResponse {
+        student_id,
+        entries,
+        next_after,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn at(hours: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 6, 1, 9, 0, 0).unwrap() + Duration::hours(hours)
+    }
+
+    fn entry(
+        transaction_id: i32, transaction_type: TransactionType, hours: i64,
+    ) -> TransactionEntry {
+        TransactionEntry {
+            transaction_id,
+            fair_id: 1,
+            transaction_type,
+            group_deliverable_component_id: 1,
+            component_name: "Parser".to_string(),
+            buyer_group_id: 1,
+            seller_group_id: 2,
+            timestamp: at(hours),
+            reverses_transaction_id: None,
+            balance: 0,
+        }
+    }
+
+    fn query() -> TransactionsQuery {
+        Query::<TransactionsQuery>::from_query("student_id=7")
+            .unwrap()
+            .into_inner()
+    }
+
+    fn ids(entries: &[TransactionEntry]) -> Vec<i32> {
+        entries.iter().map(|e| e.transaction_id).collect()
+    }
+
+    #[test]
+    fn test_cursor_filters_and_limit_run_in_the_database() {
+        let sql = page_query();
+
+        assert!(
+            sql.contains("(timestamp, transaction_id) > ($2, $3)"),
+            "{}",
+            sql
+        );
+        assert!(
+            sql.contains("ORDER BY timestamp, transaction_id"),
+            "{}",
+            sql
+        );
+        assert!(sql.trim_end().ends_with("LIMIT $7"), "{}", sql);
+        // The balance is summed over the whole ledger, before the page filters
+        let window = sql
+            .find("OVER (ORDER BY t.timestamp, t.transaction_id)")
+            .unwrap();
+        assert!(window < sql.find("SELECT * FROM ledger").unwrap());
+    }
+
+    #[test]
+    fn test_extra_entry_becomes_the_cursor() {
+        let fetched = vec![
+            entry(1, TransactionType::Credit, 0),
+            entry(2, TransactionType::Debit, 1),
+            entry(3, TransactionType::Credit, 2),
+            entry(4, TransactionType::Credit, 3),
+        ];
+
+        let (page, next_after) = next_page(fetched, 3);
+        assert_eq!(ids(&page), vec![1, 2, 3]);
+        assert_eq!(next_after, Some(3));
+
+        let (last, next_after) = next_page(vec![entry(4, TransactionType::Credit, 3)], 3);
+        assert_eq!(ids(&last), vec![4]);
+        assert_eq!(next_after, None);
+    }
+
+    #[test]
+    fn test_inverted_date_range_is_rejected() {
+        let query = TransactionsQuery {
+            from: Some(at(2)),
+            to: Some(at(1)),
+            ..query()
+        };
+
+        assert!(page_size(&query, PageLimits::default()).is_err());
+    }
+
+    #[test]
+    fn test_reversals_have_the_opposite_type() {
+        assert_eq!(TransactionType::of(true, false), TransactionType::Debit);
+        assert_eq!(TransactionType::of(true, true), TransactionType::Credit);
+        assert_eq!(TransactionType::of(false, false), TransactionType::Credit);
+        assert_eq!(TransactionType::of(false, true), TransactionType::Debit);
+    }
+}
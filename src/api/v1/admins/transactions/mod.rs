@@ -0,0 +1,15 @@
+use crate::api::v1::admins::transactions::list::list_student_transactions_handler;
+use crate::api::v1::admins::transactions::reverse::reverse_transaction_handler;
+use actix_web::{web, Scope};
+
+pub(crate) mod list;
+pub(crate) mod reverse;
+
+pub(super) fn transactions_scope() -> Scope {
+    web::scope("/transactions")
+        .route("", web::get().to(list_student_transactions_handler))
+        .route(
+            "/{transaction_id}/reverse",
+            web::post().to(reverse_transaction_handler),
+        )
+}
@@ -0,0 +1,94 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::repositories::transactions_repository;
+use crate::database::repositories::transactions_repository::Reversal;
+use crate::jwt::get_user::LoggedUser;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Path};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ReversalResponse {
+    /// The reversing entry
+    pub transaction_id: i32,
+    pub reverses_transaction_id: i32,
+    pub reversed_by_admin_id: i32,
+    #[schema(value_type = String)]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub timestamp: DateTime<Utc>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/admins/transactions/{transaction_id}/reverse",
+    params(("transaction_id" = i32, Path, description = "Transaction to reverse")),
+    responses(
+        (status = 201, description = "Reversing entry created", body = ReversalResponse),
+        (status = 400, description = "The transaction is itself a reversing entry", body = JsonError),
+        (status = 404, description = "Transaction not found", body = JsonError),
+        (status = 409, description = "The transaction was already reversed", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError),
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Fairs management",
+)]
+/// Reverse a fair transaction
+///
+/// Transactions are never changed, a reversing entry linked to the original is added instead
+/// and the purchase no longer counts in reports. A transaction can be reversed only once.
+#[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
+pub(super) async fn reverse_transaction_handler(
+    req: HttpRequest, path: Path<i32>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+    let transaction_id = path.into_inner();
+
+    let reversal = transactions_repository::reverse_locked(
+        &data.db,
+        transaction_id,
+        admin.admin_id,
+        data.clock.now(),
+    )
+    .await
+    .map_err(|e| {
+        error_with_log_id(
+            format!(
+                "Failed to reverse transaction {} by admin {}: {}",
+                transaction_id, admin.admin_id, e
+            ),
+            "Failed to reverse transaction",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    match reversal {
+        Reversal::Reversed(entry) => Ok(HttpResponse::Created().json(ReversalResponse {
+            transaction_id: entry.transaction_id,
+            reverses_transaction_id: transaction_id,
+            reversed_by_admin_id: admin.admin_id,
+            timestamp: entry.timestamp,
+        })),
+        Reversal::NotFound => Err("Transaction not found".to_json_error(StatusCode::NOT_FOUND)),
+        Reversal::AlreadyReversed {
+            reversing_transaction_id,
+        } => Err(format!(
+            "Transaction already reversed by transaction {}",
+            reversing_transaction_id
+        )
+        .to_json_error(StatusCode::CONFLICT)),
+        Reversal::ReversingEntry => {
+            Err("A reversing entry cannot be reversed".to_json_error(StatusCode::BAD_REQUEST))
+        }
+    }
+}
@@ -4,22 +4,89 @@ use crate::database::repositories::{
     projects_repository, student_deliverable_selections_repository, student_uploads_repository,
 };
 use crate::jwt::get_user::LoggedUser;
+use crate::models::student_upload::StudentUpload;
 use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
 use actix_web::http::StatusCode;
-use actix_web::web::{Data, Path};
+use actix_web::web::{Data, Path, Query};
 use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use welds::connections::postgres::PostgresClient;
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub(crate) struct DownloadUploadQuery {
+    /// Version of the file to download, the current one when omitted
+    pub version: Option<i32>,
+}
+
+/// File of the requested version of `upload`, `None` when the version does not exist
+///
+/// The current file is known from the upload itself, earlier ones are looked up.
+async fn version_file(
+    db: &PostgresClient, upload: &StudentUpload, requested: Option<i32>,
+) -> welds::errors::Result<Option<String>> {
+    match requested {
+        None => Ok(Some(upload.path.clone())),
+        Some(version) if version == upload.version => Ok(Some(upload.path.clone())),
+        Some(version) => {
+            student_uploads_repository::get_version_path(db, upload.upload_id, version).await
+        }
+    }
+}
+
+/// Answers with the file of the requested version of an upload as a ZIP attachment
+pub(crate) async fn serve_upload(
+    db: &PostgresClient, upload: &StudentUpload, version: Option<i32>, project_id: i32,
+    student_id: i32,
+) -> Result<HttpResponse, JsonError> {
+    let upload_path = version_file(db, upload, version)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!(
+                    "failed loading version {:?} of upload {}: {}",
+                    version, upload.upload_id, e
+                ),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?
+        .ok_or_else(|| "Upload version not found".to_json_error(StatusCode::NOT_FOUND))?;
+    let bytes = tokio::fs::read(&upload_path).await.map_err(|e| {
+        error_with_log_id(
+            format!("failed reading upload file {}: {}", upload_path, e),
+            "Stored upload file not available",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let filename = match version {
+        Some(version) => format!("{}_{}_v{}.zip", project_id, student_id, version),
+        None => format!("{}_{}.zip", project_id, student_id),
+    };
+    Ok(HttpResponse::Ok()
+        .content_type("application/zip")
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(filename)],
+        })
+        .body(bytes))
+}
 
 #[utoipa::path(
     get,
     path = "/v1/admins/projects/{project_id}/students/{student_id}/upload",
     params(
         ("project_id" = i32, Path, description = "Project id"),
-        ("student_id" = i32, Path, description = "Student id")
+        ("student_id" = i32, Path, description = "Student id"),
+        DownloadUploadQuery
     ),
     responses(
         (status = 200, description = "ZIP file downloaded", content_type = "application/zip"),
         (status = 401, description = "Authentication required", body = JsonError),
-        (status = 404, description = "Project or upload not found", body = JsonError),
+        (status = 404, description = "Project, upload or version not found", body = JsonError),
         (status = 500, description = "Internal server error", body = JsonError),
     ),
     security(("AdminAuth" = [])),
@@ -27,7 +94,8 @@ use actix_web::{HttpMessage, HttpRequest, HttpResponse};
 )]
 #[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
 pub(in crate::api::v1) async fn download_student_upload_handler(
-    req: HttpRequest, path: Path<(i32, i32)>, data: Data<AppData>,
+    req: HttpRequest, path: Path<(i32, i32)>, query: Query<DownloadUploadQuery>,
+    data: Data<AppData>,
 ) -> Result<HttpResponse, JsonError> {
     let _admin = req.extensions().get_admin().map_err(|_| {
         error_with_log_id(
@@ -94,22 +162,38 @@ pub(in crate::api::v1) async fn download_student_upload_handler(
         "Upload not found for student in project".to_json_error(StatusCode::NOT_FOUND)
     })?;
 
-    let upload_path = upload.as_ref().path.clone();
-    let bytes = tokio::fs::read(&upload_path).await.map_err(|e| {
-        error_with_log_id(
-            format!("failed reading upload file {}: {}", upload_path, e),
-            "Stored upload file not available",
-            StatusCode::INTERNAL_SERVER_ERROR,
-            log::Level::Error,
-        )
-    })?;
+    serve_upload(
+        &data.db,
+        upload.as_ref(),
+        query.version,
+        project_id,
+        student_id,
+    )
+    .await
+}
 
-    let filename = format!("{}_{}.zip", project_id, student_id);
-    Ok(HttpResponse::Ok()
-        .content_type("application/zip")
-        .insert_header(ContentDisposition {
-            disposition: DispositionType::Attachment,
-            parameters: vec![DispositionParam::Filename(filename)],
-        })
-        .body(bytes))
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_app_data;
+    use chrono::Utc;
+
+    #[actix_web::test]
+    async fn test_current_version_is_served_without_a_lookup() {
+        // The test pool has no database behind it, so a lookup would not answer
+        let data = create_test_app_data().await;
+        let upload = StudentUpload {
+            upload_id: 1,
+            student_deliverable_selection_id: 2,
+            path: "/uploads/3/5_v3.zip".to_string(),
+            upload_count: 3,
+            version: 3,
+            timestamp: Utc::now(),
+        };
+
+        for requested in [None, Some(3)] {
+            let file = version_file(&data.db, &upload, requested).await.unwrap();
+            assert_eq!(file.unwrap(), "/uploads/3/5_v3.zip");
+        }
+    }
 }
@@ -15,6 +15,9 @@ pub(crate) struct ProjectUploadItem {
     pub first_name: String,
     pub last_name: String,
     pub upload_count: i32,
+    /// Current version, earlier ones can be downloaded with `?version=`
+    pub version: i32,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub timestamp: DateTime<Utc>,
 }
 
@@ -86,6 +89,7 @@ pub(in crate::api::v1) async fn list_project_uploads_handler(
             first_name: student.first_name,
             last_name: student.last_name,
             upload_count: upload.upload_count,
+            version: upload.version,
             timestamp: upload.timestamp,
         })
         .collect();
@@ -1,9 +1,11 @@
 use crate::api::v1::admins::uploads::download::download_student_upload_handler;
 use crate::api::v1::admins::uploads::list::list_project_uploads_handler;
+use crate::api::v1::admins::uploads::signed_url::create_signed_url_handler;
 use actix_web::{web, Scope};
 
 pub(crate) mod download;
 pub(crate) mod list;
+pub(crate) mod signed_url;
 
 pub(super) fn uploads_scope() -> Scope {
     web::scope("/projects")
@@ -16,3 +18,10 @@ pub(super) fn uploads_scope() -> Scope {
             web::get().to(download_student_upload_handler),
         )
 }
+
+pub(super) fn upload_links_scope() -> Scope {
+    web::scope("/uploads").route(
+        "/{id}/signed-url",
+        web::post().to(create_signed_url_handler),
+    )
+}
@@ -0,0 +1,188 @@
+use crate::api::v1::admins::uploads::download::DownloadUploadQuery;
+use crate::app_data::AppData;
+use crate::common::client_ip::TrustedProxies;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::common::signed_url::{download_query, download_secret};
+use crate::database::repositories::{audit_log_repository, student_uploads_repository};
+use crate::jwt::get_user::LoggedUser;
+use crate::models::audit_log::{AuditLog, UPLOAD_LINK_SIGNED};
+use actix_web::http::header::HOST;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Path, Query};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct SignedUrlResponse {
+    /// Link downloading the upload without authentication until it expires
+    #[schema(
+        example = "https://backend.example.com/v1/uploads/12/download?expires=1767225599&signature=9f86d0"
+    )]
+    pub url: String,
+    #[schema(value_type = String)]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Scheme and host the links point to
+///
+/// `public_base_url` when configured. Otherwise the host `req` was sent to, taken from the
+/// `Forwarded` and `X-Forwarded-*` headers only when a trusted proxy sent the request, as any
+/// client can set them.
+fn link_base(
+    req: &HttpRequest, public_base_url: Option<&str>, trusted_proxies: &TrustedProxies,
+) -> String {
+    if let Some(base) = public_base_url {
+        return base.trim_end_matches('/').to_string();
+    }
+
+    if trusted_proxies.is_trusted_peer(req) {
+        let info = req.connection_info();
+        return format!("{}://{}", info.scheme(), info.host());
+    }
+
+    let scheme = if req.app_config().secure() {
+        "https"
+    } else {
+        "http"
+    };
+    let host = req
+        .headers()
+        .get(HOST)
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or_else(|| req.app_config().host());
+    format!("{}://{}", scheme, host)
+}
+
+/// Absolute URL of the public download route of an upload
+fn download_url(base: &str, upload_id: i32, query: &str) -> String {
+    format!("{}/v1/uploads/{}/download?{}", base, upload_id, query)
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/admins/uploads/{id}/signed-url",
+    params(("id" = i32, Path, description = "Upload id"), DownloadUploadQuery),
+    responses(
+        (status = 201, description = "Signed link created", body = SignedUrlResponse),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 404, description = "Upload not found, or signed links are not enabled", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError),
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Student Uploads",
+)]
+/// Create a shareable, expiring download link to an upload
+///
+/// The link carries an HMAC signature of the upload, version and expiry made with
+/// `download_url_secret`, so it can be handed to a co-grader without any token. It stays
+/// valid for `signed_url_minutes`; creating one is recorded in the audit log.
+#[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
+pub(in crate::api::v1) async fn create_signed_url_handler(
+    req: HttpRequest, path: Path<i32>, query: Query<DownloadUploadQuery>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without an admin loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+    let secret = download_secret(data.config.download_url_secret().as_deref())?;
+
+    let upload_id = path.into_inner();
+    let owned = student_uploads_repository::get_by_id_with_owner(&data.db, upload_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch upload {}: {}", upload_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?
+        .ok_or_else(|| "Upload not found".to_json_error(StatusCode::NOT_FOUND))?;
+    if query
+        .version
+        .is_some_and(|version| !(1..=owned.upload.version).contains(&version))
+    {
+        return Err("Upload version not found".to_json_error(StatusCode::NOT_FOUND));
+    }
+
+    let expires_at = data.clock.now() + Duration::minutes(data.config.signed_url_minutes());
+    let base = link_base(
+        &req,
+        data.config.public_base_url().as_deref(),
+        &data.trusted_proxies,
+    );
+    let url = download_url(
+        &base,
+        upload_id,
+        &download_query(secret, upload_id, query.version, expires_at),
+    );
+
+    audit_log_repository::record_or_warn(
+        &data.db,
+        AuditLog::by_admin(admin.admin_id, UPLOAD_LINK_SIGNED)
+            .project(owned.project_id)
+            .target("upload", upload_id)
+            .details(format!(
+                "version {}, expires at {}",
+                query
+                    .version
+                    .map_or("current".to_string(), |v| v.to_string()),
+                expires_at.to_rfc3339()
+            )),
+    )
+    .await;
+
+    Ok(HttpResponse::Created().json(SignedUrlResponse { url, expires_at }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::client_ip::IpRanges;
+    use actix_web::test::TestRequest;
+
+    fn request() -> HttpRequest {
+        TestRequest::post()
+            .uri("/v1/admins/uploads/12/signed-url")
+            .peer_addr("10.0.0.2:5000".parse().unwrap())
+            .insert_header(("Host", "backend.example.com"))
+            .insert_header(("X-Forwarded-Host", "public.example.com"))
+            .insert_header(("X-Forwarded-Proto", "https"))
+            .to_http_request()
+    }
+
+    #[test]
+    fn test_link_points_to_the_public_route_of_this_server() {
+        // Forwarding headers from an untrusted peer are ignored
+        assert_eq!(
+            download_url(
+                &link_base(&request(), None, &TrustedProxies::default()),
+                12,
+                "expires=1&signature=ab"
+            ),
+            "http://backend.example.com/v1/uploads/12/download?expires=1&signature=ab"
+        );
+    }
+
+    #[test]
+    fn test_forwarded_host_is_only_used_from_trusted_proxies() {
+        let proxies: TrustedProxies = IpRanges::parse(&["10.0.0.0/8".to_string()]).unwrap().into();
+        assert_eq!(
+            link_base(&request(), None, &proxies),
+            "https://public.example.com"
+        );
+
+        // The configured base wins over whatever the request says
+        assert_eq!(
+            link_base(&request(), Some("https://files.example.com/"), &proxies),
+            "https://files.example.com"
+        );
+    }
+}
@@ -0,0 +1,296 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::repositories::admin_api_tokens_repository;
+use crate::jwt::api_token::{generate_api_token, hash_api_token, validate_permissions};
+use crate::jwt::get_user::LoggedUser;
+use crate::models::admin_api_token::AdminApiToken;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use welds::state::DbState;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct CreateApiTokenScheme {
+    /// Unique name of the token among the admin's tokens
+    #[schema(example = "smoke-tests")]
+    pub name: String,
+    /// Authorities granted to requests using the token
+    #[schema(example = json!(["ROLE_ADMIN_PROFESSOR"]))]
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ApiTokenScheme {
+    #[schema(example = 1)]
+    pub id: i32,
+    #[schema(example = "smoke-tests")]
+    pub name: String,
+    #[schema(example = json!(["ROLE_ADMIN_PROFESSOR"]))]
+    pub permissions: Vec<String>,
+    #[schema(value_type = String)]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub created_at: DateTime<Utc>,
+    #[schema(value_type = Option<String>)]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339_opt")]
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<AdminApiToken> for ApiTokenScheme {
+    fn from(value: AdminApiToken) -> Self {
+        Self {
+            id: value.api_token_id,
+            permissions: value.permission_list(),
+            name: value.name,
+            created_at: value.created_at,
+            revoked_at: value.revoked_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct CreateApiTokenResponse {
+    /// Plain token value, send it in the `X-Admin-Token` header. It is not shown again.
+    #[schema(example = "apt_8fQ2...")]
+    pub token: String,
+    pub details: ApiTokenScheme,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct GetApiTokensResponse {
+    pub tokens: Vec<ApiTokenScheme>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/admins/users/me/tokens",
+    request_body = CreateApiTokenScheme,
+    responses(
+        (status = 201, description = "Token issued, the plain value is returned only once", body = CreateApiTokenResponse),
+        (status = 400, description = "Invalid name or permissions", body = JsonError),
+        (status = 409, description = "A token with this name already exists", body = JsonError),
+        (status = 500, description = "Internal server error occurred", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Admin users management",
+)]
+/// Issues a long-lived API token for the current admin
+///
+/// Meant for automation such as smoke tests. The token is accepted in the `X-Admin-Token`
+/// header in place of a login token and only carries the requested permissions.
+/// Only its hash is stored.
+#[actix_web_grants::protect("ROLE_ADMIN_ROOT")]
+pub(super) async fn create_api_token_handler(
+    req: HttpRequest, body: Json<CreateApiTokenScheme>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without a user loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let name = body.name.trim();
+    if name.is_empty() {
+        return Err("Token name cannot be empty".to_json_error(StatusCode::BAD_REQUEST));
+    }
+
+    let permissions = validate_permissions(&body.permissions)
+        .map_err(|e| e.to_json_error(StatusCode::BAD_REQUEST))?;
+
+    let name_taken = admin_api_tokens_repository::name_exists(&data.db, admin.admin_id, name)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to check api token names: {}", e),
+                "Failed to create token",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+    if name_taken {
+        return Err("A token with this name already exists".to_json_error(StatusCode::CONFLICT));
+    }
+
+    let token = generate_api_token();
+    let state = admin_api_tokens_repository::create(
+        &data.db,
+        AdminApiToken {
+            api_token_id: 0,
+            admin_id: admin.admin_id,
+            name: name.to_string(),
+            token_hash: hash_api_token(&token),
+            permissions: permissions.join(","),
+            created_at: Utc::now(),
+            revoked_at: None,
+        },
+    )
+    .await
+    .map_err(|e| {
+        error_with_log_id(
+            format!(
+                "unable to create api token for admin {}: {}",
+                admin.admin_id, e
+            ),
+            "Failed to create token",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    log::info!(
+        "admin {} issued api token '{}' with permissions {}",
+        admin.admin_id,
+        name,
+        permissions.join(",")
+    );
+
+    Ok(HttpResponse::Created().json(CreateApiTokenResponse {
+        token,
+        details: DbState::into_inner(state).into(),
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/admins/users/me/tokens",
+    responses(
+        (status = 200, description = "API tokens of the current admin, revoked ones included", body = GetApiTokensResponse),
+        (status = 500, description = "Internal server error occurred", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Admin users management",
+)]
+/// Lists the API tokens of the current admin
+///
+/// Token values are never returned, only their metadata.
+#[actix_web_grants::protect("ROLE_ADMIN_ROOT")]
+pub(super) async fn get_api_tokens_handler(
+    req: HttpRequest, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without a user loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let tokens = admin_api_tokens_repository::get_by_admin(&data.db, admin.admin_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!(
+                    "unable to list api tokens of admin {}: {}",
+                    admin.admin_id, e
+                ),
+                "Failed to retrieve tokens",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?
+        .into_iter()
+        .map(DbState::into_inner)
+        .map(ApiTokenScheme::from)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(GetApiTokensResponse { tokens }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/v1/admins/users/me/tokens/{id}",
+    params(("id" = i32, Path, description = "API token ID")),
+    responses(
+        (status = 204, description = "Token revoked"),
+        (status = 404, description = "Token not found or already revoked", body = JsonError),
+        (status = 500, description = "Internal server error occurred", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Admin users management",
+)]
+/// Revokes one of the API tokens of the current admin
+///
+/// Requests using the token are rejected from now on.
+#[actix_web_grants::protect("ROLE_ADMIN_ROOT")]
+pub(super) async fn revoke_api_token_handler(
+    req: HttpRequest, path: Path<i32>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without a user loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let api_token_id = path.into_inner();
+
+    let revoked = admin_api_tokens_repository::revoke(&data.db, admin.admin_id, api_token_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to revoke api token {}: {}", api_token_id, e),
+                "Failed to revoke token",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+    if !revoked {
+        return Err("Token not found".to_json_error(StatusCode::NOT_FOUND));
+    }
+
+    log::info!(
+        "admin {} revoked api token {}",
+        admin.admin_id,
+        api_token_id
+    );
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(revoked_at: Option<DateTime<Utc>>) -> AdminApiToken {
+        AdminApiToken {
+            api_token_id: 7,
+            admin_id: 1,
+            name: "smoke-tests".to_string(),
+            token_hash: hash_api_token("apt_test"),
+            permissions: "ROLE_ADMIN_PROFESSOR,ROLE_ADMIN_COORDINATOR".to_string(),
+            created_at: Utc::now(),
+            revoked_at,
+        }
+    }
+
+    #[test]
+    fn test_active_token_grants_its_permissions() {
+        let token = token(None);
+
+        assert!(token.is_active());
+        assert_eq!(
+            token.permission_list(),
+            vec!["ROLE_ADMIN_PROFESSOR", "ROLE_ADMIN_COORDINATOR"]
+        );
+    }
+
+    #[test]
+    fn test_revoked_token_is_no_longer_active() {
+        let token = token(Some(Utc::now()));
+
+        assert!(!token.is_active());
+
+        let listed = ApiTokenScheme::from(token);
+        assert!(listed.revoked_at.is_some());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::app_data::AppData;
+use crate::common::created::created;
 use crate::common::json_error::{error_with_log_id_and_payload, JsonError, ToJsonError};
 use crate::database::repositories::admins_repository;
 use crate::jwt::get_user::LoggedUser;
@@ -35,7 +36,9 @@ pub(crate) struct CreateAdminResponse {
     path = "/v1/admins/users",
     request_body = CreateAdminScheme,
     responses(
-        (status = 200, description = "Admin created successfully", body = CreateAdminResponse),
+        (status = 201, description = "Admin created successfully", body = CreateAdminResponse,
+            headers(("Location" = String, description = "URL of the created admin"))
+        ),
         (status = 400, description = "Invalid data in request", body = JsonError),
         (status = 401, description = "Authentication required", body = JsonError),
         (status = 500, description = "Internal server error occurred", body = JsonError)
@@ -109,7 +112,11 @@ pub(super) async fn create_admin_handler(
         // The professor can manually share credentials if needed
     }
 
-    Ok(HttpResponse::Ok().json(CreateAdminResponse {
-        admin_id: state.admin_id,
-    }))
+    Ok(created(
+        "/v1/admins/users",
+        state.admin_id,
+        &CreateAdminResponse {
+            admin_id: state.admin_id,
+        },
+    ))
 }
@@ -1,25 +1,31 @@
 use crate::app_data::AppData;
-use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::common::access::{admin_can_see_admin, ensure_visible, found_or_not_found, not_found};
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError, LAST_ROOT};
 use crate::database::repositories::admins_repository;
+use crate::database::repositories::admins_repository::RootGuarded;
 use crate::jwt::get_user::LoggedUser;
-use crate::models::admin_role::AvailableAdminRole;
 use actix_web::http::StatusCode;
 use actix_web::web::{Data, Path};
 use actix_web::{HttpMessage, HttpRequest, HttpResponse};
-use log::warn;
+use welds::state::DbState;
+
+const ADMIN_NOT_FOUND: &str = "Admin not found";
 
 #[utoipa::path(
     delete,
     path = "/v1/admins/users/{id}",
     responses(
         (status = 200, description = "Admin deleted successfully"),
-        (status = 404, description = "Admin not found", body = JsonError),
+        (status = 404, description = "Admin not found or not visible to the caller", body = JsonError),
+        (status = 409, description = "The admin is the last Root (code LAST_ROOT)", body = JsonError),
         (status = 500, description = "Internal server error occurred", body = JsonError)
     ),
     security(("AdminAuth" = [])),
     tag = "Admin users management",
 )]
 /// Delete an admin
+///
+/// The last Root admin cannot be deleted, so privileged operations stay reachable.
 #[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
 pub(super) async fn delete_admin_handler(
     req: HttpRequest, path: Path<i32>, data: Data<AppData>,
@@ -51,21 +57,12 @@ pub(super) async fn delete_admin_handler(
             )
         })?;
 
-    let admin_state = match admin_state {
-        Some(s) => s,
-        None => return Err("Admin not found".to_json_error(StatusCode::NOT_FOUND)),
-    };
+    let admin = DbState::into_inner(found_or_not_found(admin_state, ADMIN_NOT_FOUND)?);
 
-    // Only root can delete root users
-    if (user.admin_role_id != AvailableAdminRole::Root as i32)
-        && (admin_state.admin_role_id == AvailableAdminRole::Root as i32)
-    {
-        warn!("user {} tried to delete a root user", user.email);
-        return Err("Operation not permitted".to_json_error(StatusCode::FORBIDDEN));
-    }
+    // Only root can see, and therefore delete, root users
+    ensure_visible(admin_can_see_admin(&user, &admin), ADMIN_NOT_FOUND)?;
 
-    // Delete admin using repository function
-    admins_repository::delete_by_id(&data.db, admin_id)
+    let outcome = admins_repository::delete_unless_last_root(&data.db, admin_id)
         .await
         .map_err(|e| {
             error_with_log_id(
@@ -76,5 +73,12 @@ pub(super) async fn delete_admin_handler(
             )
         })?;
 
-    Ok(HttpResponse::Ok().finish())
+    match outcome {
+        RootGuarded::Applied => Ok(HttpResponse::Ok().finish()),
+        // deleted in the meantime
+        RootGuarded::NotFound => Err(not_found(ADMIN_NOT_FOUND)),
+        RootGuarded::LastRoot => Err("The last Root admin cannot be deleted"
+            .to_json_error(StatusCode::CONFLICT)
+            .with_code(LAST_ROOT)),
+    }
 }
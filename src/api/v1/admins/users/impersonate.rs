@@ -0,0 +1,295 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::repositories::{
+    blacklist_repository, impersonation_sessions_repository, students_repository,
+};
+use crate::jwt::get_user::LoggedUser;
+use crate::jwt::token::{create_impersonation_token, TokenKeys};
+use crate::models::impersonation_session::ImpersonationSession;
+use crate::models::student::Student;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Path};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+use welds::state::DbState;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ImpersonationResponse {
+    /// Student token to send in the `X-Student-Token` header while impersonating
+    #[schema(example = "eyJhbGc9...")]
+    pub token: String,
+    /// Session to end once done
+    #[schema(example = 12)]
+    pub session_id: i32,
+    #[schema(value_type = String)]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Reason a student cannot be impersonated, if any
+///
+/// Accounts pending confirmation and blacklisted students cannot log in,
+/// so they cannot be impersonated either.
+fn impersonation_blocker(student: &Student, blacklisted: bool) -> Option<&'static str> {
+    if student.is_pending {
+        Some("Student account is not confirmed")
+    } else if blacklisted {
+        Some("Student is blacklisted")
+    } else {
+        None
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/admins/users/impersonate/{student_id}",
+    params(("student_id" = i32, Path, description = "Student to impersonate")),
+    responses(
+        (status = 201, description = "Impersonation started", body = ImpersonationResponse),
+        (status = 403, description = "Caller is not Root or the student account is disabled", body = JsonError),
+        (status = 404, description = "Student not found", body = JsonError),
+        (status = 500, description = "Internal server error occurred", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Admin users management",
+)]
+/// Starts impersonating a student for support
+///
+/// Issues a short-lived student token bound to a new impersonation session,
+/// which is recorded in the audit log. Requests made with the token are logged
+/// with the impersonating admin. End the session with
+/// `DELETE /v1/admins/users/impersonations/{session_id}`.
+#[actix_web_grants::protect("ROLE_ADMIN_ROOT")]
+pub(super) async fn start_impersonation_handler(
+    req: HttpRequest, path: Path<i32>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without a user loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let student_id = path.into_inner();
+
+    let student = students_repository::get_by_id(&data.db, student_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch student {}: {}", student_id, e),
+                "Failed to start impersonation",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?
+        .map(DbState::into_inner)
+        .ok_or_else(|| "Student not found".to_json_error(StatusCode::NOT_FOUND))?;
+
+    let blacklisted = blacklist_repository::get_by_university_id(&data.db, student.university_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!(
+                    "unable to check blacklist for student {}: {}",
+                    student_id, e
+                ),
+                "Failed to start impersonation",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?
+        .is_some();
+
+    if let Some(reason) = impersonation_blocker(&student, blacklisted) {
+        return Err(reason.to_json_error(StatusCode::FORBIDDEN));
+    }
+
+    let now = data.clock.now();
+    let session = impersonation_sessions_repository::start(
+        &data.db,
+        ImpersonationSession {
+            impersonation_session_id: 0,
+            admin_id: admin.admin_id,
+            student_id,
+            started_at: now,
+            expires_at: now + Duration::minutes(data.config.impersonation_token_minutes()),
+            ended_at: None,
+        },
+    )
+    .await
+    .map_err(|e| {
+        error_with_log_id(
+            format!(
+                "unable to start impersonation of student {} by admin {}: {}",
+                student_id, admin.admin_id, e
+            ),
+            "Failed to start impersonation",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+    let session = DbState::into_inner(session);
+
+    let token = create_impersonation_token(
+        student_id,
+        session.impersonation_session_id,
+        &TokenKeys::student(&data.config),
+        data.config.impersonation_token_minutes(),
+        now,
+    )
+    .map_err(|e| {
+        error_with_log_id(
+            format!("unable to create impersonation token: {}", e),
+            "Failed to start impersonation",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    log::info!(
+        "admin {} started impersonating student {} (session {})",
+        admin.admin_id,
+        student_id,
+        session.impersonation_session_id
+    );
+
+    Ok(HttpResponse::Created().json(ImpersonationResponse {
+        token,
+        session_id: session.impersonation_session_id,
+        expires_at: session.expires_at,
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/v1/admins/users/impersonations/{session_id}",
+    params(("session_id" = i32, Path, description = "Impersonation session ID")),
+    responses(
+        (status = 204, description = "Impersonation ended, its token is rejected from now on"),
+        (status = 404, description = "Session not found or already ended", body = JsonError),
+        (status = 500, description = "Internal server error occurred", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Admin users management",
+)]
+/// Ends one of the impersonation sessions of the current admin
+///
+/// The end is recorded in the audit log.
+#[actix_web_grants::protect("ROLE_ADMIN_ROOT")]
+pub(super) async fn end_impersonation_handler(
+    req: HttpRequest, path: Path<i32>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let admin = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without a user loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let session_id = path.into_inner();
+
+    let ended = impersonation_sessions_repository::end(&data.db, admin.admin_id, session_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to end impersonation session {}: {}", session_id, e),
+                "Failed to end impersonation",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+    if !ended {
+        return Err("Impersonation session not found".to_json_error(StatusCode::NOT_FOUND));
+    }
+
+    log::info!(
+        "admin {} ended impersonation session {}",
+        admin.admin_id,
+        session_id
+    );
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::repositories::impersonation_sessions_repository::start_entry;
+    use crate::models::audit_log::IMPERSONATION_STARTED;
+    use crate::test_utils::create_test_app_data;
+    use actix_web::dev::ServiceRequest;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::{web, App, Error};
+    use actix_web_grants::GrantsMiddleware;
+    use std::collections::HashSet;
+
+    fn student(is_pending: bool) -> Student {
+        Student {
+            student_id: 5,
+            first_name: "Mario".to_string(),
+            last_name: "Rossi".to_string(),
+            email: "mario.rossi@test.com".to_string(),
+            university_id: 123456,
+            password_hash: String::new(),
+            is_pending,
+            sessions_revoked_at: None,
+        }
+    }
+
+    async fn professor(_req: &ServiceRequest) -> Result<HashSet<String>, Error> {
+        Ok(HashSet::from(["ROLE_ADMIN_PROFESSOR".to_string()]))
+    }
+
+    #[test]
+    fn test_disabled_students_cannot_be_impersonated() {
+        assert_eq!(impersonation_blocker(&student(false), false), None);
+        assert!(impersonation_blocker(&student(true), false).is_some());
+        assert!(impersonation_blocker(&student(false), true).is_some());
+    }
+
+    #[test]
+    fn test_impersonation_is_recorded_in_audit_log() {
+        let session = ImpersonationSession {
+            impersonation_session_id: 12,
+            admin_id: 1,
+            student_id: 5,
+            started_at: Utc::now(),
+            expires_at: Utc::now() + Duration::minutes(15),
+            ended_at: None,
+        };
+
+        let entry = start_entry(&session);
+
+        assert_eq!(entry.action, IMPERSONATION_STARTED);
+        assert_eq!(entry.actor_admin_id, Some(1));
+        assert_eq!(entry.target_type.as_deref(), Some("student"));
+        assert_eq!(entry.target_id, Some(5));
+        assert!(entry.details.unwrap().starts_with("session 12"));
+    }
+
+    #[actix_web::test]
+    async fn test_non_root_admin_cannot_impersonate() {
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(create_test_app_data().await))
+                .wrap(GrantsMiddleware::with_extractor(professor))
+                .route(
+                    "/impersonate/{student_id}",
+                    web::post().to(start_impersonation_handler),
+                ),
+        )
+        .await;
+
+        let req = TestRequest::post().uri("/impersonate/5").to_request();
+        let resp = call_service(&app, req).await;
+
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -1,7 +1,17 @@
+use crate::api::v1::admins::users::api_tokens::{
+    create_api_token_handler, get_api_tokens_handler, revoke_api_token_handler,
+};
 use crate::api::v1::admins::users::create::create_admin_handler;
 use crate::api::v1::admins::users::delete::delete_admin_handler;
+use crate::api::v1::admins::users::impersonate::{
+    end_impersonation_handler, start_impersonation_handler,
+};
 use crate::api::v1::admins::users::me::admins_me_handler;
+use crate::api::v1::admins::users::permissions::admins_me_permissions_handler;
 use crate::api::v1::admins::users::read::{get_all_admins_handler, get_one_admin_handler};
+use crate::api::v1::admins::users::reassign::reassign_coordinations_handler;
+use crate::api::v1::admins::users::role::change_admin_role_handler;
+use crate::api::v1::admins::users::roles::get_admin_roles_handler;
 use crate::api::v1::admins::users::test_email::test_email_handler;
 use crate::api::v1::admins::users::update::update_admin_handler;
 use crate::api::v1::admins::users::update_me::update_me_admin_handler;
@@ -10,10 +20,16 @@ use actix_web::{web, Scope};
 use serde::Serialize;
 use utoipa::ToSchema;
 
+pub(crate) mod api_tokens;
 pub(crate) mod create;
 pub(crate) mod delete;
+pub(crate) mod impersonate;
 pub(crate) mod me;
+pub(crate) mod permissions;
 pub(crate) mod read;
+pub(crate) mod reassign;
+pub(crate) mod role;
+pub(crate) mod roles;
 pub(crate) mod test_email;
 pub(crate) mod update;
 pub(crate) mod update_me;
@@ -22,12 +38,36 @@ pub(super) fn users_scope() -> Scope {
     web::scope("/users")
         .route("/me", web::get().to(admins_me_handler))
         .route("/me", web::patch().to(update_me_admin_handler))
+        .route(
+            "/me/permissions",
+            web::get().to(admins_me_permissions_handler),
+        )
+        .route("/me/tokens", web::post().to(create_api_token_handler))
+        .route("/me/tokens", web::get().to(get_api_tokens_handler))
+        .route(
+            "/me/tokens/{id}",
+            web::delete().to(revoke_api_token_handler),
+        )
+        .route("/roles", web::get().to(get_admin_roles_handler))
+        .route(
+            "/impersonate/{student_id}",
+            web::post().to(start_impersonation_handler),
+        )
+        .route(
+            "/impersonations/{session_id}",
+            web::delete().to(end_impersonation_handler),
+        )
         .route("/test-email", web::post().to(test_email_handler))
         .route("", web::get().to(get_all_admins_handler))
         .route("", web::post().to(create_admin_handler))
         .route("/{id}", web::patch().to(update_admin_handler))
         .route("/{id}", web::get().to(get_one_admin_handler))
         .route("/{id}", web::delete().to(delete_admin_handler))
+        .route("/{id}/role", web::patch().to(change_admin_role_handler))
+        .route(
+            "/{id}/reassign-coordinations",
+            web::post().to(reassign_coordinations_handler),
+        )
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -0,0 +1,82 @@
+use crate::common::json_error::JsonError;
+use crate::jwt::permissions::effective_permissions;
+use actix_web::HttpResponse;
+use actix_web_grants::authorities::AuthDetails;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct AdminPermissionsResponse {
+    /// Authorities of the request, sorted
+    #[schema(example = json!(["ROLE_ADMIN_COORDINATOR"]))]
+    pub authorities: Vec<String>,
+    /// Capabilities granted by those authorities
+    #[schema(example = json!(["projects:read", "groups:read", "groups:manage_members"]))]
+    pub permissions: Vec<&'static str>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/admins/users/me/permissions",
+    responses(
+        (status = 200, description = "Effective permissions of the authenticated admin", body = AdminPermissionsResponse),
+        (status = 401, description = "Authentication required", body = JsonError),
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Admin users management",
+)]
+/// Retrieves what the currently authenticated admin is allowed to do
+///
+/// Permissions are resolved from the authorities of the request, so they reflect both the
+/// admin role and the scoping of API tokens. No database access is needed.
+#[actix_web_grants::protect(any(
+    "ROLE_ADMIN_ROOT",
+    "ROLE_ADMIN_PROFESSOR",
+    "ROLE_ADMIN_COORDINATOR"
+))]
+pub(super) async fn admins_me_permissions_handler(details: AuthDetails) -> HttpResponse {
+    let mut authorities: Vec<String> = details.authorities.iter().cloned().collect();
+    authorities.sort();
+
+    HttpResponse::Ok().json(AdminPermissionsResponse {
+        permissions: effective_permissions(&details.authorities),
+        authorities,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::dev::ServiceRequest;
+    use actix_web::test::{call_and_read_body_json, init_service, TestRequest};
+    use actix_web::{web, App, Error};
+    use actix_web_grants::GrantsMiddleware;
+    use serde_json::Value;
+    use std::collections::HashSet;
+
+    async fn coordinator(_req: &ServiceRequest) -> Result<HashSet<String>, Error> {
+        Ok(HashSet::from(["ROLE_ADMIN_COORDINATOR".to_string()]))
+    }
+
+    #[actix_web::test]
+    async fn test_permissions_are_resolved_from_authorities() {
+        let app = init_service(
+            App::new()
+                .wrap(GrantsMiddleware::with_extractor(coordinator))
+                .route("/permissions", web::get().to(admins_me_permissions_handler)),
+        )
+        .await;
+
+        let body: Value =
+            call_and_read_body_json(&app, TestRequest::get().uri("/permissions").to_request())
+                .await;
+
+        assert_eq!(
+            body["authorities"],
+            serde_json::json!(["ROLE_ADMIN_COORDINATOR"])
+        );
+        let permissions = body["permissions"].as_array().unwrap();
+        assert!(permissions.contains(&Value::from("groups:read")));
+        assert!(!permissions.contains(&Value::from("projects:manage")));
+    }
+}
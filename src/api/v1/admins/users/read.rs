@@ -1,23 +1,56 @@
 use crate::api::v1::admins::users::AdminResponseScheme;
 use crate::app_data::AppData;
-use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::common::access::{admin_can_see_admin, ensure_visible, found_or_not_found};
+use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::common::list_params::{ListParams, ListQuery, ListSpec};
 use crate::database::repositories::admins_repository;
+use crate::jwt::get_user::LoggedUser;
 use actix_web::http::StatusCode;
 use actix_web::web::{Data, Path};
-use actix_web::HttpResponse;
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
 use serde::Serialize;
+use std::cmp::Ordering;
 use utoipa::ToSchema;
 use welds::state::DbState;
 
+const ADMIN_NOT_FOUND: &str = "Admin not found";
+
+const ADMIN_FIELDS: &[&str] = &["id", "first_name", "last_name", "email", "role_id"];
+
+/// Sorting, filtering and fields of the admin list
+#[derive(Debug)]
+pub(crate) struct AdminsList;
+
+impl ListSpec for AdminsList {
+    const SORTABLE: &'static [&'static str] = ADMIN_FIELDS;
+    const FILTERABLE: &'static [&'static str] = &["role_id"];
+    const FIELDS: &'static [&'static str] = ADMIN_FIELDS;
+}
+
+/// Compares two admins on one of the sortable fields of [`AdminsList`]
+fn compare_admins(field: &str, a: &AdminResponseScheme, b: &AdminResponseScheme) -> Ordering {
+    match field {
+        "first_name" => a.first_name.cmp(&b.first_name),
+        "last_name" => a.last_name.cmp(&b.last_name),
+        "email" => a.email.cmp(&b.email),
+        "role_id" => a.role_id.cmp(&b.role_id),
+        _ => a.id.cmp(&b.id),
+    }
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub(crate) struct GetAllAdminsResponse {
-    pub admins: Vec<AdminResponseScheme>,
+    /// Admins with only the requested fields when `fields` is set
+    #[schema(value_type = Vec<AdminResponseScheme>)]
+    pub admins: Vec<serde_json::Value>,
 }
 #[utoipa::path(
     get,
     path = "/v1/admins/users",
+    params(ListQuery),
     responses(
-        (status = 200, description = "Found admins", body = GetAllAdminsResponse),
+        (status = 200, description = "Found admins. Paginated with the usual headers when `page`, `per_page` or `envelope` is set", body = GetAllAdminsResponse),
+        (status = 400, description = "Invalid pagination, sort, filter or fields", body = JsonError),
         (status = 500, description = "Internal server error occurred", body = JsonError)
     ),
     security(("AdminAuth" = [])),
@@ -25,9 +58,22 @@ pub(crate) struct GetAllAdminsResponse {
 )]
 /// Handler for retrieving a list of admin users
 ///
-/// Returns array with all the data of the admins except passwords
+/// Returns array with all the data of the admins except passwords.
+/// Root accounts are listed only for Root callers. Admins can be sorted by any of their
+/// fields and filtered by `role_id`.
 #[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
-pub(super) async fn get_all_admins_handler(data: Data<AppData>) -> Result<HttpResponse, JsonError> {
+pub(super) async fn get_all_admins_handler(
+    req: HttpRequest, params: ListParams<AdminsList>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let user = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without a user loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
     let states = admins_repository::get_all(&data.db).await.map_err(|e| {
         error_with_log_id(
             format!("unable to retrieve admins from database: {}", e),
@@ -37,20 +83,24 @@ pub(super) async fn get_all_admins_handler(data: Data<AppData>) -> Result<HttpRe
         )
     })?;
 
-    let admins: Vec<AdminResponseScheme> = states
+    let role_id = params.filter::<i32>("role_id")?;
+    let mut admins: Vec<AdminResponseScheme> = states
         .into_iter()
         .map(DbState::into_inner)
+        .filter(|admin| admin_can_see_admin(&user, admin))
+        .filter(|admin| role_id.is_none_or(|role_id| admin.admin_role_id == role_id))
         .map(AdminResponseScheme::from)
         .collect();
+    params.sort_items(&mut admins, compare_admins);
 
-    Ok(HttpResponse::Ok().json(GetAllAdminsResponse { admins }))
+    Ok(params.respond_all(&req, admins, |admins| GetAllAdminsResponse { admins }))
 }
 #[utoipa::path(
     get,
     path = "/v1/admins/users/{id}",
     responses(
         (status = 200, description = "Found admin", body = AdminResponseScheme),
-        (status = 404, description = "Admin not found", body = JsonError),
+        (status = 404, description = "Admin not found or not visible to the caller", body = JsonError),
         (status = 500, description = "Internal server error", body = JsonError)
     ),
     security(("AdminAuth" = [])),
@@ -60,10 +110,20 @@ pub(super) async fn get_all_admins_handler(data: Data<AppData>) -> Result<HttpRe
 ///
 /// Returns detailed information about a specific admin user
 /// without including sensitive fields like passwords.
+/// Root accounts are reported as not found to non-Root callers.
 #[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
 pub(super) async fn get_one_admin_handler(
-    path: Path<i32>, data: Data<AppData>,
+    req: HttpRequest, path: Path<i32>, data: Data<AppData>,
 ) -> Result<HttpResponse, JsonError> {
+    let user = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without a user loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
     let id = path.into_inner();
 
     let admin_state = admins_repository::get_by_id(&data.db, id)
@@ -77,12 +137,10 @@ pub(super) async fn get_one_admin_handler(
             )
         })?;
 
-    let state = match admin_state {
-        Some(a) => a,
-        None => return Err("Admin not found".to_json_error(StatusCode::NOT_FOUND)),
-    };
+    let admin = DbState::into_inner(found_or_not_found(admin_state, ADMIN_NOT_FOUND)?);
+    ensure_visible(admin_can_see_admin(&user, &admin), ADMIN_NOT_FOUND)?;
 
-    let admin = AdminResponseScheme::from(DbState::into_inner(state));
+    let admin = AdminResponseScheme::from(admin);
 
     Ok(HttpResponse::Ok().json(admin))
 }
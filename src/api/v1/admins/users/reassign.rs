@@ -0,0 +1,168 @@
+use crate::app_data::AppData;
+use crate::common::access::found_or_not_found;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::repositories::{
+    admins_repository, audit_log_repository, coordinator_projects_repository,
+};
+use crate::jwt::get_user::LoggedUser;
+use crate::models::admin::Admin;
+use crate::models::admin_role::AvailableAdminRole;
+use crate::models::audit_log::{AuditLog, COORDINATIONS_REASSIGNED};
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use welds::state::DbState;
+
+const ADMIN_NOT_FOUND: &str = "Admin not found";
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct ReassignCoordinationsRequest {
+    /// Coordinator taking over the projects
+    #[schema(example = 5)]
+    pub target_admin_id: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ReassignCoordinationsResponse {
+    pub source_admin_id: i32,
+    pub target_admin_id: i32,
+    /// Projects now coordinated by the target
+    pub moved_project_ids: Vec<i32>,
+    /// Projects the target already coordinated, dropped from the source
+    pub skipped_project_ids: Vec<i32>,
+}
+
+/// Rejects a reassignment to the source itself or to an admin who cannot coordinate
+fn validate_target(source_id: i32, target: &Admin) -> Result<(), JsonError> {
+    if target.admin_id == source_id {
+        return Err("Coordinations cannot be reassigned to the same admin"
+            .to_json_error(StatusCode::BAD_REQUEST));
+    }
+    if target.admin_role_id != AvailableAdminRole::Coordinator as i32 {
+        return Err(
+            "Only Coordinators can be assigned to projects".to_json_error(StatusCode::BAD_REQUEST)
+        );
+    }
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/v1/admins/users/{id}/reassign-coordinations",
+    params(("id" = i32, Path, description = "Admin whose coordinations move")),
+    request_body = ReassignCoordinationsRequest,
+    responses(
+        (status = 200, description = "Coordinations moved to the target", body = ReassignCoordinationsResponse),
+        (status = 400, description = "The target is the source or not a Coordinator", body = JsonError),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 404, description = "Source or target admin not found", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Admin users management",
+)]
+/// Move every coordination of an admin to another Coordinator
+///
+/// Meant for departing admins, before deleting them. All assignments move in one
+/// transaction. Projects the target already coordinates are skipped and the source's
+/// assignment to them is dropped, so the source coordinates nothing afterwards. The
+/// change is recorded in the audit log.
+#[actix_web_grants::protect("ROLE_ADMIN_ROOT")]
+pub(super) async fn reassign_coordinations_handler(
+    req: HttpRequest, path: Path<i32>, body: Json<ReassignCoordinationsRequest>,
+    data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let user = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without a user loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+    let source_id = path.into_inner();
+    let target_id = body.target_admin_id;
+
+    let load = |id: i32| {
+        let db = &data.db;
+        async move {
+            let state = admins_repository::get_by_id(db, id).await.map_err(|e| {
+                error_with_log_id(
+                    format!("unable to load admin {}: {}", id, e),
+                    "Failed to reassign coordinations",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                )
+            })?;
+            found_or_not_found(state, ADMIN_NOT_FOUND).map(DbState::into_inner)
+        }
+    };
+    load(source_id).await?;
+    let target = load(target_id).await?;
+    validate_target(source_id, &target)?;
+
+    let reassignment = coordinator_projects_repository::reassign_all(
+        &data.db,
+        source_id,
+        target_id,
+        data.clock.now(),
+    )
+    .await
+    .map_err(|e| {
+        error_with_log_id(
+            format!(
+                "unable to reassign the coordinations of admin {} to admin {}: {}",
+                source_id, target_id, e
+            ),
+            "Failed to reassign coordinations",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    audit_log_repository::record_or_warn(
+        &data.db,
+        AuditLog::by_admin(user.admin_id, COORDINATIONS_REASSIGNED)
+            .target("admin", source_id)
+            .details(format!(
+                "to admin {}, moved projects {:?}, skipped {:?}",
+                target_id, reassignment.moved, reassignment.skipped
+            )),
+    )
+    .await;
+
+    Ok(HttpResponse::Ok().json(ReassignCoordinationsResponse {
+        source_admin_id: source_id,
+        target_admin_id: target_id,
+        moved_project_ids: reassignment.moved,
+        skipped_project_ids: reassignment.skipped,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::ResponseError;
+
+    fn admin(admin_id: i32, role: AvailableAdminRole) -> Admin {
+        Admin {
+            admin_id,
+            first_name: "Jane".to_string(),
+            last_name: "Doe".to_string(),
+            email: format!("admin{}@admin.com", admin_id),
+            password_hash: String::new(),
+            admin_role_id: role as i32,
+        }
+    }
+
+    #[test]
+    fn test_target_must_be_another_coordinator() {
+        assert!(validate_target(4, &admin(5, AvailableAdminRole::Coordinator)).is_ok());
+
+        let same = validate_target(4, &admin(4, AvailableAdminRole::Coordinator)).unwrap_err();
+        assert_eq!(same.status_code(), StatusCode::BAD_REQUEST);
+        assert!(validate_target(4, &admin(5, AvailableAdminRole::Professor)).is_err());
+    }
+}
@@ -0,0 +1,131 @@
+use crate::api::v1::admins::users::AdminResponseScheme;
+use crate::app_data::AppData;
+use crate::common::access::{found_or_not_found, not_found};
+use crate::common::json_error::{
+    error_with_log_id, JsonError, ToJsonError, ValidationError, LAST_ROOT,
+};
+use crate::database::repositories::admins_repository::RoleChange;
+use crate::database::repositories::{
+    admin_roles_repository, admins_repository, audit_log_repository,
+};
+use crate::jwt::get_user::LoggedUser;
+use crate::models::audit_log::{AuditLog, ADMIN_ROLE_CHANGED};
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use welds::state::DbState;
+
+const ADMIN_NOT_FOUND: &str = "Admin not found";
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct ChangeAdminRoleScheme {
+    /// One of the roles listed by `GET /v1/admins/users/roles`
+    #[schema(example = 2)]
+    pub admin_role_id: i32,
+}
+
+#[utoipa::path(
+    patch,
+    path = "/v1/admins/users/{id}/role",
+    params(("id" = i32, Path, description = "Admin ID")),
+    request_body = ChangeAdminRoleScheme,
+    responses(
+        (status = 200, description = "Role changed, the updated admin is returned", body = AdminResponseScheme),
+        (status = 400, description = "The role does not exist", body = ValidationError),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 404, description = "Admin not found", body = JsonError),
+        (status = 409, description = "The change would demote the last Root (code LAST_ROOT)", body = JsonError),
+        (status = 500, description = "Internal server error occurred", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Admin users management",
+)]
+/// Change the role of an admin
+///
+/// Only Root admins can change roles. The last Root cannot be demoted, and every change is
+/// recorded in the audit log.
+#[actix_web_grants::protect("ROLE_ADMIN_ROOT")]
+pub(super) async fn change_admin_role_handler(
+    req: HttpRequest, path: Path<i32>, body: Json<ChangeAdminRoleScheme>, data: Data<AppData>,
+) -> actix_web::Result<HttpResponse> {
+    let user = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without a user loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+    let id = path.into_inner();
+    let new_role = body.admin_role_id;
+
+    let roles = data
+        .admin_roles_cache
+        .get_or_try_load(|| async {
+            admin_roles_repository::get_all(&data.db)
+                .await
+                .map(|states| states.into_iter().map(DbState::into_inner).collect())
+        })
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to retrieve admin roles from database: {}", e),
+                "Failed to change role",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+    if !roles.iter().any(|r| r.admin_role_id == new_role) {
+        let mut errors = ValidationError::default();
+        errors.add("admin_role_id", format!("Unknown role {}", new_role));
+        errors.into_result()?;
+    }
+
+    let admin_state = admins_repository::get_by_id(&data.db, id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to load admin {}: {}", id, e),
+                "Failed to change role",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+    let mut admin = DbState::into_inner(found_or_not_found(admin_state, ADMIN_NOT_FOUND)?);
+
+    let outcome = admins_repository::set_role_unless_last_root(&data.db, id, new_role)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to change the role of admin {}: {}", id, e),
+                "Failed to change role",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+    let old_role = match outcome {
+        RoleChange::Changed { old_role } => old_role,
+        // deleted in the meantime
+        RoleChange::NotFound => return Err(not_found(ADMIN_NOT_FOUND).into()),
+        RoleChange::LastRoot => {
+            return Err("The last Root admin cannot be demoted"
+                .to_json_error(StatusCode::CONFLICT)
+                .with_code(LAST_ROOT)
+                .into())
+        }
+    };
+
+    audit_log_repository::record_or_warn(
+        &data.db,
+        AuditLog::by_admin(user.admin_id, ADMIN_ROLE_CHANGED)
+            .target("admin", id)
+            .details(format!("role {} -> {}", old_role, new_role)),
+    )
+    .await;
+
+    admin.admin_role_id = new_role;
+    Ok(HttpResponse::Ok().json(AdminResponseScheme::from(admin)))
+}
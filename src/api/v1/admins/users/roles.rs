@@ -0,0 +1,59 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::database::repositories::admin_roles_repository;
+use crate::models::admin_role::AdminRole;
+use actix_web::http::StatusCode;
+use actix_web::web::Data;
+use actix_web::HttpResponse;
+use serde::Serialize;
+use utoipa::ToSchema;
+use welds::state::DbState;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct GetAdminRolesResponse {
+    pub roles: Vec<AdminRole>,
+}
+#[utoipa::path(
+    get,
+    path = "/v1/admins/users/roles",
+    responses(
+        (status = 200, description = "Available admin roles", body = GetAdminRolesResponse,
+            headers(("Cache-Control" = String, description = "Clients may cache the list for the given max-age"))),
+        (status = 500, description = "Internal server error occurred", body = JsonError)
+    ),
+    security(("AdminAuth" = [])),
+    tag = "Admin users management",
+)]
+/// Handler for retrieving the list of admin roles
+///
+/// Roles rarely change, so the list is cached in memory and clients
+/// are allowed to cache it as well.
+#[actix_web_grants::protect(any(
+    "ROLE_ADMIN_ROOT",
+    "ROLE_ADMIN_PROFESSOR",
+    "ROLE_ADMIN_COORDINATOR"
+))]
+pub(super) async fn get_admin_roles_handler(
+    data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let cache = &data.admin_roles_cache;
+    let roles = cache
+        .get_or_try_load(|| async {
+            admin_roles_repository::get_all(&data.db)
+                .await
+                .map(|states| states.into_iter().map(DbState::into_inner).collect())
+        })
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to retrieve admin roles from database: {}", e),
+                "Failed to retrieve roles",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+    Ok(HttpResponse::Ok()
+        .insert_header(cache.cache_control())
+        .json(GetAdminRolesResponse { roles }))
+}
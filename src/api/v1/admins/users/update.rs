@@ -1,12 +1,17 @@
 use crate::app_data::AppData;
-use crate::common::json_error::{error_with_log_id_and_payload, JsonError, ToJsonError};
+use crate::common::access::{admin_can_see_admin, ensure_visible, found_or_not_found};
+use crate::common::json_error::{error_with_log_id, error_with_log_id_and_payload, JsonError};
 use crate::database::repositories::admins_repository;
+use crate::jwt::get_user::LoggedUser;
 use actix_web::http::StatusCode;
 use actix_web::web::{Data, Json, Path};
-use actix_web::HttpResponse;
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
 use password_auth::generate_hash;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use welds::state::DbState;
+
+const ADMIN_NOT_FOUND: &str = "Admin not found";
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub(crate) struct UpdateAdminScheme {
@@ -27,6 +32,7 @@ pub(crate) struct UpdateAdminScheme {
         (status = 200, description = "Admin updated successfully"),
         (status = 400, description = "Invalid data in request", body = JsonError),
         (status = 401, description = "Authentication required", body = JsonError),
+        (status = 404, description = "Admin not found or not visible to the caller", body = JsonError),
         (status = 500, description = "Internal server error occurred", body = JsonError)
     ),
     security(("AdminAuth" = [])),
@@ -35,14 +41,24 @@ pub(crate) struct UpdateAdminScheme {
 /// Updates an existing admin user.
 ///
 /// This endpoint allows authenticated admins to update their own or other admin's details. Only root admins can modify roles.
+/// Root accounts are reported as not found to non-Root callers.
 #[actix_web_grants::protect(any("ROLE_ADMIN_ROOT", "ROLE_ADMIN_PROFESSOR"))]
 pub(super) async fn update_admin_handler(
-    path: Path<i32>, body: Json<UpdateAdminScheme>, data: Data<AppData>,
+    req: HttpRequest, path: Path<i32>, body: Json<UpdateAdminScheme>, data: Data<AppData>,
 ) -> Result<HttpResponse, JsonError> {
+    let user = req.extensions().get_admin().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without a user loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
     let id = path.into_inner();
 
-    // Check if admin exists
-    let admin_exists = admins_repository::get_by_id(&data.db, id)
+    // Check if admin exists and is visible to the caller
+    let admin_state = admins_repository::get_by_id(&data.db, id)
         .await
         .map_err(|e| {
             error_with_log_id_and_payload(
@@ -52,12 +68,10 @@ pub(super) async fn update_admin_handler(
                 log::Level::Error,
                 &body,
             )
-        })?
-        .is_some();
+        })?;
 
-    if !admin_exists {
-        return Err("Admin not found".to_json_error(StatusCode::NOT_FOUND));
-    }
+    let admin = DbState::into_inner(found_or_not_found(admin_state, ADMIN_NOT_FOUND)?);
+    ensure_visible(admin_can_see_admin(&user, &admin), ADMIN_NOT_FOUND)?;
 
     // Update admin using repository function
     let password_hash = body.password.as_ref().map(generate_hash);
@@ -1,4 +1,5 @@
 use crate::api::v1::admins::admins_scope;
+use crate::api::v1::admins::ip_allowlist::AdminIpAllowlist;
 use crate::api::v1::public::public_scope;
 use crate::api::v1::students::students_scope;
 use actix_web::{web, Scope};
@@ -9,7 +10,7 @@ pub(crate) mod students;
 
 pub(super) fn v1_scope() -> Scope {
     web::scope("/v1")
-        .service(admins_scope())
+        .service(admins_scope().wrap(AdminIpAllowlist))
         .service(students_scope())
         .service(public_scope())
 }
@@ -1,6 +1,7 @@
 use crate::app_data::AppData;
 use crate::common::json_error::{error_with_log_id_and_payload, JsonError, ToJsonError};
 use crate::database::repositories::fairs_repository;
+use crate::database::timing::timed;
 use actix_web::http::StatusCode;
 use actix_web::web::{Data, Path};
 use actix_web::HttpResponse;
@@ -52,11 +53,13 @@ pub(in crate::api::v1) async fn leaderboard_handler(
         })?
         .ok_or_else(|| "Fair not found".to_json_error(StatusCode::NOT_FOUND))?;
 
-    let active = fairs_repository::is_active(&fair_state);
+    let active = fairs_repository::is_active(&fair_state, data.clock.now());
     let pool = data.db.as_sqlx_pool();
 
-    let rows = sqlx::query(
-        r#"
+    let rows = timed(
+        "fairs.leaderboard",
+        sqlx::query(
+            r#"
         SELECT
             g.group_id,
             g.name AS group_name,
@@ -66,13 +69,18 @@ pub(in crate::api::v1) async fn leaderboard_handler(
         LEFT JOIN transactions t
             ON t.group_deliverable_selection_id = gds.group_deliverable_selection_id
             AND t.fair_id = $1
+            AND t.reverses_transaction_id IS NULL
+            AND NOT EXISTS (
+                SELECT 1 FROM transactions r WHERE r.reverses_transaction_id = t.transaction_id
+            )
         WHERE g.project_id = (SELECT project_id FROM fairs WHERE fair_id = $1)
         GROUP BY g.group_id, g.name
         ORDER BY total_sales DESC, g.name ASC
         "#,
+        )
+        .bind(fair_id)
+        .fetch_all(pool),
     )
-    .bind(fair_id)
-    .fetch_all(pool)
     .await
     .map_err(|e| {
         error_with_log_id_and_payload(
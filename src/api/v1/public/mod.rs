@@ -1,8 +1,12 @@
 use crate::api::v1::public::fairs::public_fairs_scope;
+use crate::api::v1::public::uploads::public_uploads_scope;
 use actix_web::{web, Scope};
 
 pub(crate) mod fairs;
+pub(crate) mod uploads;
 
 pub(super) fn public_scope() -> Scope {
-    web::scope("").service(public_fairs_scope())
+    web::scope("")
+        .service(public_fairs_scope())
+        .service(public_uploads_scope())
 }
@@ -0,0 +1,101 @@
+use crate::api::v1::admins::uploads::download::serve_upload;
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::common::signed_url::{download_secret, verify_download, SignedDownloadQuery};
+use crate::database::repositories::student_uploads_repository;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Path, Query};
+use actix_web::HttpResponse;
+
+#[utoipa::path(
+    get,
+    path = "/v1/uploads/{id}/download",
+    params(("id" = i32, Path, description = "Upload id"), SignedDownloadQuery),
+    responses(
+        (status = 200, description = "ZIP file downloaded", content_type = "application/zip"),
+        (status = 403, description = "The signature does not match the link", body = JsonError),
+        (status = 404, description = "Upload or version not found, or signed links are not enabled", body = JsonError),
+        (status = 410, description = "The link has expired", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError),
+    ),
+    tag = "Student Uploads",
+)]
+/// Download an upload through a signed link
+///
+/// No session is needed: the link created by an admin is the authorization. The signature
+/// is checked before anything is read, so a forged link learns nothing about the upload.
+pub(in crate::api::v1) async fn signed_download_handler(
+    path: Path<i32>, query: Query<SignedDownloadQuery>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let secret = download_secret(data.config.download_url_secret().as_deref())?;
+    let upload_id = path.into_inner();
+    verify_download(secret, upload_id, &query, data.clock.now())?;
+
+    let owned = student_uploads_repository::get_by_id_with_owner(&data.db, upload_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch upload {}: {}", upload_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?
+        .ok_or_else(|| "Upload not found".to_json_error(StatusCode::NOT_FOUND))?;
+
+    serve_upload(
+        &data.db,
+        &owned.upload,
+        query.version,
+        owned.project_id,
+        owned.student_id,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::signed_url::download_query;
+    use crate::models::student_upload::StudentUpload;
+    use crate::test_utils::create_test_app_data;
+    use actix_web::body::to_bytes;
+    use chrono::{TimeDelta, Utc};
+
+    #[actix_web::test]
+    async fn test_valid_signed_link_downloads_the_upload() {
+        let dir = std::env::temp_dir().join(format!("signed-download-{}", uuid::Uuid::new_v4()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let path = dir.join("5.zip");
+        tokio::fs::write(&path, b"PK zip bytes").await.unwrap();
+        let upload = StudentUpload {
+            upload_id: 12,
+            student_deliverable_selection_id: 2,
+            path: path.to_string_lossy().to_string(),
+            upload_count: 1,
+            version: 1,
+            timestamp: Utc::now(),
+        };
+
+        let now = Utc::now();
+        let query = Query::<SignedDownloadQuery>::from_query(&download_query(
+            "secret",
+            12,
+            None,
+            now + TimeDelta::minutes(5),
+        ))
+        .unwrap();
+        verify_download("secret", 12, &query, now).unwrap();
+        let data = create_test_app_data().await;
+        let response = serve_upload(&data.db, &upload, query.version, 3, 5)
+            .await
+            .unwrap();
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            to_bytes(response.into_body()).await.unwrap().as_ref(),
+            b"PK zip bytes"
+        );
+    }
+}
@@ -0,0 +1,8 @@
+use crate::api::v1::public::uploads::download::signed_download_handler;
+use actix_web::{web, Scope};
+
+pub(crate) mod download;
+
+pub(super) fn public_uploads_scope() -> Scope {
+    web::scope("/uploads").route("/{id}/download", web::get().to(signed_download_handler))
+}
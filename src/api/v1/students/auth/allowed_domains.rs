@@ -9,6 +9,9 @@ pub(crate) struct AllowedDomainsResponse {
     /// List of email domains allowed for account creation
     #[schema(example = json!(["unitn.it", "studenti.unitn.it"]))]
     domains: Vec<String>,
+    /// Whether students can create their own account, the signup form should be hidden when false
+    #[schema(example = true)]
+    self_signup_enabled: bool,
 }
 
 /// Get allowed email domains for student registration
@@ -21,15 +24,26 @@ pub(crate) struct AllowedDomainsResponse {
     path = "/v1/students/auth/allowed-domains",
     tag = "Student authentication",
     responses(
-        (status = 200, description = "List of allowed email domains", body = AllowedDomainsResponse)
+        (status = 200, description = "List of allowed email domains", body = AllowedDomainsResponse,
+            headers(("Cache-Control" = String, description = "Clients may cache the list for the given max-age")))
     ),
     summary = "Get allowed email domains for registration",
     description = "Returns a list of email domains that can be used to create student accounts. This endpoint does not require authentication."
 )]
 pub(super) async fn allowed_domains_handler(data: Data<AppData>) -> Result<HttpResponse> {
-    let domains = data.config.allowed_signup_domains().clone();
+    let cache = &data.allowed_domains_cache;
+    let domains = cache.get().unwrap_or_else(|| {
+        let domains = data.config.allowed_signup_domains().clone();
+        cache.set(domains.clone());
+        domains
+    });
 
-    let response = AllowedDomainsResponse { domains };
+    let response = AllowedDomainsResponse {
+        domains,
+        self_signup_enabled: data.config.self_signup_enabled(),
+    };
 
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::Ok()
+        .insert_header(cache.cache_control())
+        .json(response))
 }
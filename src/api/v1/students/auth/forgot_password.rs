@@ -1,12 +1,13 @@
 use crate::app_data::AppData;
+use crate::common::frontend_url::link_base_for;
 use crate::common::json_error::{error_with_log_id_and_payload, JsonError};
 use crate::database::repositories::students_repository;
 use crate::mail::Mailer;
 use actix_web::http::StatusCode;
 use actix_web::web::{Data, Json};
-use actix_web::HttpResponse;
+use actix_web::{HttpRequest, HttpResponse};
 use confirm_email::generate_token;
-use log::error;
+use log::{error, info};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use welds::state::DbState;
@@ -19,23 +20,38 @@ pub(crate) struct ForgotPasswordSchema {
     email: String,
 }
 
-/// Requests a password reset for a student account
+/// Uniform answer of the resend endpoint, whether an email was sent or not
+#[derive(Serialize, ToSchema)]
+pub(crate) struct ResendResetEmailResponse {
+    #[schema(example = "If an account exists for this email, a reset link has been sent")]
+    pub message: String,
+}
+
+/// Sends the password reset email of `body.email`, unless one was sent within the cooldown
 ///
-/// This endpoint sends a password reset email to the specified email address if a student
-/// account with that email exists. The email contains a secure link to reset the password.
-#[utoipa::path(
-    post,
-    path = "/v1/students/auth/forgot-password",
-    request_body = ForgotPasswordSchema,
-    responses(
-        (status = 204, description = "Password reset email sent successfully (or email doesn't exist)"),
-        (status = 500, description = "Internal server error", body = JsonError)
-    ),
-    tag = "Student authentication"
-)]
-pub(crate) async fn forgot_password_handler(
-    body: Json<ForgotPasswordSchema>, data: Data<AppData>,
-) -> Result<HttpResponse, JsonError> {
+/// Unknown addresses and suppressed sends succeed silently to prevent email enumeration.
+async fn send_reset_email(
+    req: &HttpRequest, body: &ForgotPasswordSchema, data: &AppData,
+) -> Result<(), JsonError> {
+    if !data.reset_email_cooldowns.try_acquire(&body.email) {
+        info!(
+            "password reset email to {} suppressed, one was sent less than {}s ago",
+            body.email,
+            data.config.reset_email_cooldown_secs()
+        );
+        return Ok(());
+    }
+
+    let result = deliver_reset_email(req, body, data).await;
+    if result.is_err() {
+        data.reset_email_cooldowns.release(&body.email);
+    }
+    result
+}
+
+async fn deliver_reset_email(
+    req: &HttpRequest, body: &ForgotPasswordSchema, data: &AppData,
+) -> Result<(), JsonError> {
     // Fetch the student by email
     let student_state = students_repository::get_by_email(&data.db, &body.email)
         .await
@@ -45,7 +61,7 @@ pub(crate) async fn forgot_password_handler(
                 "Password reset request failed",
                 StatusCode::INTERNAL_SERVER_ERROR,
                 log::Level::Error,
-                &body,
+                body,
             )
         })?;
 
@@ -65,14 +81,14 @@ pub(crate) async fn forgot_password_handler(
                 "Password reset request failed",
                 StatusCode::INTERNAL_SERVER_ERROR,
                 log::Level::Error,
-                &body,
+                body,
             )
         })?;
 
         // Create the reset URL with the token (frontend URL)
         let reset_url = format!(
             "{}/password-reset?t={}",
-            data.config.frontend_base_url(),
+            link_base_for(req, data.config.frontend_base_urls()).trim_end_matches('/'),
             token
         );
 
@@ -85,7 +101,7 @@ pub(crate) async fn forgot_password_handler(
                     "Password reset request failed",
                     StatusCode::INTERNAL_SERVER_ERROR,
                     log::Level::Error,
-                    &body,
+                    body,
                 ));
             }
         };
@@ -102,11 +118,59 @@ pub(crate) async fn forgot_password_handler(
                 "Password reset request failed",
                 StatusCode::INTERNAL_SERVER_ERROR,
                 log::Level::Error,
-                &body,
+                body,
             ));
         }
     }
 
+    Ok(())
+}
+
+/// Requests a password reset for a student account
+///
+/// This endpoint sends a password reset email to the specified email address if a student
+/// account with that email exists. The email contains a secure link to reset the password.
+/// Only one email is sent per address within `reset_email_cooldown_secs`.
+#[utoipa::path(
+    post,
+    path = "/v1/students/auth/forgot-password",
+    request_body = ForgotPasswordSchema,
+    responses(
+        (status = 204, description = "Password reset email sent successfully (or email doesn't exist, or one was sent within the cooldown)"),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    tag = "Student authentication"
+)]
+pub(crate) async fn forgot_password_handler(
+    req: HttpRequest, body: Json<ForgotPasswordSchema>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    send_reset_email(&req, &body, &data).await?;
+
     // Always return success to prevent email enumeration
     Ok(HttpResponse::NoContent().finish())
 }
+
+/// Re-sends the password reset email of a student account
+///
+/// For students who lost the first email. Like the forgot password endpoint, only one email
+/// is sent per address within `reset_email_cooldown_secs`, and the answer is the same
+/// whether the account exists, the email was sent or it was suppressed.
+#[utoipa::path(
+    post,
+    path = "/v1/students/auth/forgot-password/resend",
+    request_body = ForgotPasswordSchema,
+    responses(
+        (status = 200, description = "Request accepted, an email is sent only if the account exists and the cooldown elapsed", body = ResendResetEmailResponse),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    tag = "Student authentication"
+)]
+pub(crate) async fn resend_reset_email_handler(
+    req: HttpRequest, body: Json<ForgotPasswordSchema>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    send_reset_email(&req, &body, &data).await?;
+
+    Ok(HttpResponse::Ok().json(ResendResetEmailResponse {
+        message: "If an account exists for this email, a reset link has been sent".to_string(),
+    }))
+}
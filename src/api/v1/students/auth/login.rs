@@ -1,7 +1,7 @@
 use crate::app_data::AppData;
 use crate::common::json_error::{error_with_log_id_and_payload, JsonError, ToJsonError};
 use crate::database::repositories::students_repository;
-use crate::jwt::token::create_student_token;
+use crate::jwt::token::{create_student_token, TokenKeys};
 use actix_web::cookie::time::Duration;
 use actix_web::http::StatusCode;
 use actix_web::web::Data;
@@ -43,6 +43,7 @@ pub(crate) struct LoginStudentsResponse {
         (status = 200, description = "Login successful", body = LoginStudentsResponse),
         (status = 401, description = "Wrong credentials", body = JsonError),
         (status = 403, description = "Account pending email confirmation", body = JsonError),
+        (status = 429, description = "Too many requests from this address (code RATE_LIMITED), see `Retry-After`", body = JsonError),
         (status = 500, description = "Internal server error", body = JsonError)
     ),
     tag = "Student authentication",
@@ -88,8 +89,9 @@ pub(crate) async fn students_login_handler(
     // create JWT
     let token = create_student_token(
         user.student_id,
-        data.config.jwt_secret().as_bytes(),
+        &TokenKeys::student(&data.config),
         Duration::days(data.config.jwt_validity_days()).whole_seconds(),
+        data.clock.now(),
     )
     .map_err(|e| {
         error_with_log_id_and_payload(
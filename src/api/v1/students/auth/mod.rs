@@ -6,10 +6,14 @@ pub(crate) mod reset_password;
 pub(crate) mod signup;
 
 use crate::api::v1::students::auth::{
-    allowed_domains::allowed_domains_handler, confirm::confirm_student_handler,
-    forgot_password::forgot_password_handler, login::students_login_handler,
-    reset_password::reset_password_handler, signup::student_signup_handler,
+    allowed_domains::allowed_domains_handler,
+    confirm::confirm_student_handler,
+    forgot_password::{forgot_password_handler, resend_reset_email_handler},
+    login::students_login_handler,
+    reset_password::{reset_password_handler, verify_reset_token_handler},
+    signup::student_signup_handler,
 };
+use crate::database::unit_of_work::UnitOfWorkMiddleware;
 use actix_web::{web, Scope};
 
 pub(super) fn auth_scope() -> Scope {
@@ -18,6 +22,43 @@ pub(super) fn auth_scope() -> Scope {
         .route("/confirm", web::get().to(confirm_student_handler))
         .route("/signup", web::post().to(student_signup_handler))
         .route("/forgot-password", web::post().to(forgot_password_handler))
-        .route("/reset-password", web::post().to(reset_password_handler))
+        .route(
+            "/forgot-password/resend",
+            web::post().to(resend_reset_email_handler),
+        )
+        .service(
+            web::resource("/reset-password")
+                .wrap(UnitOfWorkMiddleware)
+                .route(web::post().to(reset_password_handler)),
+        )
+        .route(
+            "/reset-password/verify",
+            web::get().to(verify_reset_token_handler),
+        )
         .route("/allowed-domains", web::get().to(allowed_domains_handler))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_app_data;
+    use actix_web::http::StatusCode;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::web::Data;
+    use actix_web::App;
+    use serde_json::json;
+
+    #[actix_web::test]
+    async fn test_reset_password_route_reaches_the_handler() {
+        let data = create_test_app_data().await;
+        let app = init_service(App::new().app_data(Data::new(data)).service(auth_scope())).await;
+
+        // A malformed token is rejected by the handler before any query
+        let req = TestRequest::post()
+            .uri("/auth/reset-password?t=not-a-token")
+            .set_json(json!({"new_password": "N3w-Password!"}))
+            .to_request();
+        let res = call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+}
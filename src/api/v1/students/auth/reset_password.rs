@@ -1,10 +1,11 @@
 use crate::app_data::AppData;
 use crate::common::json_error::{error_with_log_id_and_payload, JsonError, ToJsonError};
+use crate::common::reset_token::{check_reset_token, consume_reset_token};
 use crate::database::repositories::students_repository;
+use crate::database::unit_of_work::UnitOfWork;
 use actix_web::http::StatusCode;
 use actix_web::web::{Data, Json, Query};
 use actix_web::HttpResponse;
-use confirm_email::validate_token;
 use log::{error, info};
 use password_auth::generate_hash;
 use serde::{Deserialize, Serialize};
@@ -18,6 +19,14 @@ pub(crate) struct ResetPasswordQuery {
     pub t: String,
 }
 
+/// Query parameter of the token verification
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct VerifyResetTokenQuery {
+    /// The password reset token sent via email
+    #[schema(example = "eyJhbGciOiJIUzI1NiIsIn...")]
+    pub token: String,
+}
+
 /// Request body for resetting a password
 #[derive(Deserialize, Serialize, ToSchema)]
 pub(crate) struct ResetPasswordSchema {
@@ -29,7 +38,7 @@ pub(crate) struct ResetPasswordSchema {
 /// Resets a student's password using a valid reset token
 ///
 /// This endpoint validates the password reset token and updates the student's password.
-/// The token is sent to the student's email via the forgot-password endpoint.
+/// The token is sent to the student's email via the forgot-password endpoint and can be used once.
 #[utoipa::path(
     post,
     path = "/v1/students/auth/reset-password",
@@ -39,26 +48,20 @@ pub(crate) struct ResetPasswordSchema {
     request_body = ResetPasswordSchema,
     responses(
         (status = 204, description = "Password reset successfully"),
-        (status = 400, description = "Invalid or expired token", body = JsonError),
+        (status = 400, description = "Invalid token", body = JsonError),
+        (status = 410, description = "Token expired or already used", body = JsonError),
         (status = 500, description = "Internal server error", body = JsonError)
     ),
     tag = "Student authentication"
 )]
 pub(crate) async fn reset_password_handler(
     query: Query<ResetPasswordQuery>, body: Json<ResetPasswordSchema>, data: Data<AppData>,
+    unit_of_work: UnitOfWork,
 ) -> Result<HttpResponse, JsonError> {
     let token = &query.t;
 
     // Validate the token and extract the email
-    let email = match validate_token(token.clone(), data.config.email_token_secret().clone()) {
-        Ok(email) => email,
-        Err(e) => {
-            error!("invalid password reset token: {}", e);
-            return Err(
-                "Invalid or expired password reset token".to_json_error(StatusCode::BAD_REQUEST)
-            );
-        }
-    };
+    let email = check_reset_token(&data.db, token, data.config.email_token_secret()).await?;
 
     // Fetch the student by email
     let student_state = students_repository::get_by_email(&data.db, &email)
@@ -78,10 +81,13 @@ pub(crate) async fn reset_password_handler(
         "Student account not found".to_json_error(StatusCode::BAD_REQUEST)
     })?;
 
+    // Claim the token first, so that a concurrent reset with it cannot also write a password
+    consume_reset_token(&unit_of_work, token).await?;
+
     // Update the password hash using repository function
     let password_hash = generate_hash(&body.new_password);
 
-    students_repository::update_password_by_email(&data.db, &email, password_hash)
+    students_repository::update_password_by_email(&unit_of_work, &email, password_hash)
         .await
         .map_err(|e| {
             error_with_log_id_and_payload(
@@ -97,3 +103,28 @@ pub(crate) async fn reset_password_handler(
 
     Ok(HttpResponse::NoContent().finish())
 }
+
+/// Checks a password reset token without using it
+///
+/// Lets the reset page tell an expired or used link apart before asking for a new password.
+#[utoipa::path(
+    get,
+    path = "/v1/students/auth/reset-password/verify",
+    params(
+        ("token" = String, Query, description = "Password reset token from email")
+    ),
+    responses(
+        (status = 200, description = "Token can reset the password"),
+        (status = 400, description = "Invalid token", body = JsonError),
+        (status = 410, description = "Token expired or already used", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    tag = "Student authentication"
+)]
+pub(crate) async fn verify_reset_token_handler(
+    query: Query<VerifyResetTokenQuery>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    check_reset_token(&data.db, &query.token, data.config.email_token_secret()).await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
@@ -1,15 +1,21 @@
 use crate::app_data::AppData;
-use crate::common::json_error::{error_with_log_id_and_payload, JsonError, ToJsonError};
+use crate::common::frontend_url::link_base_for;
+use crate::common::json_error::{
+    error_with_log_id_and_payload, JsonError, ToJsonError, ValidationError, SIGNUP_DISABLED,
+};
+use crate::database::errors::is_unique_violation;
 use crate::database::repositories::students_repository;
 use crate::mail::Mailer;
 use crate::models::student::Student;
 use actix_web::http::StatusCode;
 use actix_web::web::{Data, Json};
-use actix_web::HttpResponse;
+use actix_web::{HttpRequest, HttpResponse};
 use log::info;
 use password_auth::generate_hash;
 use serde::{Deserialize, Serialize};
+use std::future::Future;
 use utoipa::ToSchema;
+use welds::state::DbState;
 
 #[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub(crate) struct StudentSignupScheme {
@@ -27,8 +33,75 @@ pub(crate) struct StudentSignupScheme {
 
 #[derive(Debug, Serialize, ToSchema)]
 pub(crate) struct StudentSignupResponse {
-    #[schema(example = "123")]
-    pub student_id: i32,
+    #[schema(example = "Check your email to complete the signup")]
+    pub message: String,
+}
+
+/// Email sent at the end of a signup
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SignupEmail {
+    /// The account was created, its email must be confirmed
+    Confirmation,
+    /// The email already belongs to an account, its owner is told instead
+    ExistingAccount,
+    /// The university ID already belongs to an account, the sender is told instead
+    UniversityIdTaken,
+}
+
+/// Account a signup ended up with
+enum SignupAccount {
+    Created(Student),
+    /// An account already has the email
+    Existing(Student),
+    /// No account has the email but one has the university ID, so none was created
+    UniversityIdTaken,
+}
+
+fn signup_email(account: &SignupAccount) -> SignupEmail {
+    match account {
+        SignupAccount::Created(_) => SignupEmail::Confirmation,
+        SignupAccount::Existing(_) => SignupEmail::ExistingAccount,
+        SignupAccount::UniversityIdTaken => SignupEmail::UniversityIdTaken,
+    }
+}
+
+/// Rejects signups when self-signup is turned off in the config
+fn ensure_signup_enabled(enabled: bool) -> Result<(), JsonError> {
+    if enabled {
+        Ok(())
+    } else {
+        Err("Self-signup is disabled"
+            .to_json_error(StatusCode::FORBIDDEN)
+            .with_code(SIGNUP_DISABLED))
+    }
+}
+
+/// Checks every field of a signup, reporting all the invalid ones at once
+fn validate_signup(
+    body: &StudentSignupScheme, allowed_domains: &[String],
+) -> Result<(), ValidationError> {
+    let mut errors = ValidationError::default();
+    if body.first_name.trim().is_empty() {
+        errors.add("first_name", "First name cannot be empty");
+    }
+    if body.last_name.trim().is_empty() {
+        errors.add("last_name", "Last name cannot be empty");
+    }
+    if body.password.trim().is_empty() {
+        errors.add("password", "Password cannot be empty");
+    }
+
+    if body.email.trim().is_empty() {
+        errors.add("email", "Email cannot be empty");
+    } else {
+        match body.email.split('@').nth(1) {
+            Some(domain) if allowed_domains.iter().any(|d| d == domain) => {}
+            Some(_) => errors.add("email", "Email domain not allowed for signup"),
+            None => errors.add("email", "Invalid email format"),
+        }
+    }
+
+    errors.into_result()
 }
 
 #[utoipa::path(
@@ -36,9 +109,9 @@ pub(crate) struct StudentSignupResponse {
     path = "/v1/students/auth/signup",
     request_body = StudentSignupScheme,
     responses(
-        (status = 202, description = "Account created successfully", body = StudentSignupResponse),
-        (status = 400, description = "Invalid data in request", body = JsonError),
-        (status = 409, description = "Student with this email or university ID already exists", body = JsonError),
+        (status = 202, description = "Signup accepted, returned whether or not the email or the university ID is already registered", body = StudentSignupResponse),
+        (status = 400, description = "Invalid data in request, every invalid field is listed in `fields`", body = ValidationError),
+        (status = 403, description = "Self-signup is disabled (code SIGNUP_DISABLED)", body = JsonError),
         (status = 500, description = "Internal server error occurred", body = JsonError),
         (status = 503, description = "Account created email was not sent", body = JsonError)
     ),
@@ -46,36 +119,18 @@ pub(crate) struct StudentSignupResponse {
 )]
 /// Creates a new student account
 ///
-/// This endpoint allows students to register to the app.
+/// This endpoint allows students to register to the app, unless self-signup is disabled.
+/// An email or a university ID that is already registered gets the same response, and a
+/// notice is sent instead of a confirmation so the response does not reveal existing accounts.
 pub(super) async fn student_signup_handler(
-    body: Json<StudentSignupScheme>, data: Data<AppData>,
-) -> Result<HttpResponse, JsonError> {
-    // Validate that all fields are not empty or default values
-    if body.first_name.trim().is_empty() {
-        return Err("First name cannot be empty".to_json_error(StatusCode::BAD_REQUEST));
-    } else if body.last_name.trim().is_empty() {
-        return Err("Last name cannot be empty".to_json_error(StatusCode::BAD_REQUEST));
-    } else if body.email.trim().is_empty() {
-        return Err("Email cannot be empty".to_json_error(StatusCode::BAD_REQUEST));
-    } else if body.password.trim().is_empty() {
-        return Err("Password cannot be empty".to_json_error(StatusCode::BAD_REQUEST));
-    }
+    req: HttpRequest, body: Json<StudentSignupScheme>, data: Data<AppData>,
+) -> actix_web::Result<HttpResponse> {
+    ensure_signup_enabled(data.config.self_signup_enabled())?;
 
-    // check that email domain is valid
-    let email_domain = body.email.split('@').nth(1);
-    if let Some(domain) = email_domain {
-        let allowed_domains = data.config.allowed_signup_domains();
-        if !allowed_domains.contains(&domain.to_string()) {
-            return Err(
-                "Email domain not allowed for signup".to_json_error(StatusCode::BAD_REQUEST)
-            );
-        }
-    } else {
-        return Err("Invalid email format".to_json_error(StatusCode::BAD_REQUEST));
-    }
+    validate_signup(&body, data.config.allowed_signup_domains())?;
 
-    // Check if email already exists
-    let email_exists = students_repository::email_exists(&data.db, &body.email)
+    // An existing account takes the same path as a new one, only the email differs
+    let existing = students_repository::get_by_email(&data.db, &body.email)
         .await
         .map_err(|e| {
             error_with_log_id_and_payload(
@@ -85,12 +140,50 @@ pub(super) async fn student_signup_handler(
                 log::Level::Error,
                 &body,
             )
-        })?;
+        })?
+        .map(DbState::into_inner);
 
-    if email_exists {
-        return Err("User with this email already exists".to_json_error(StatusCode::CONFLICT));
-    }
+    let account = match existing {
+        Some(student) => SignupAccount::Existing(student),
+        None => match create_student(&body, &data).await? {
+            Some(student) => SignupAccount::Created(student),
+            // Either the university ID is taken or a concurrent signup registered the email
+            None => match refetch_by_email(&body, &data).await? {
+                Some(student) => SignupAccount::Existing(student),
+                None => SignupAccount::UniversityIdTaken,
+            },
+        },
+    };
+
+    let response = finish_signup(signup_email(&account), |email| async move {
+        // Emails are only sent when email confirmation is not skipped
+        if data.config.skip_email_confirmation() {
+            return Ok(());
+        }
+        send_signup_email(&req, &data, &body, email, &account).await
+    })
+    .await?;
+    Ok(response)
+}
 
+/// Sends `email` with `send` and answers the same whatever the account the signup ended up with
+async fn finish_signup<F, Fut>(email: SignupEmail, send: F) -> Result<HttpResponse, JsonError>
+where
+    F: FnOnce(SignupEmail) -> Fut,
+    Fut: Future<Output = Result<(), JsonError>>,
+{
+    send(email).await?;
+
+    Ok(HttpResponse::Accepted().json(StudentSignupResponse {
+        message: "Check your email to complete the signup".to_string(),
+    }))
+}
+
+/// Creates the account, `None` when the university ID is taken or the email was registered
+/// in the meantime
+async fn create_student(
+    body: &Json<StudentSignupScheme>, data: &Data<AppData>,
+) -> Result<Option<Student>, JsonError> {
     // Check if university ID already exists
     let university_id_exists =
         students_repository::university_id_exists(&data.db, body.university_id)
@@ -101,14 +194,12 @@ pub(super) async fn student_signup_handler(
                     "Account creation failed",
                     StatusCode::INTERNAL_SERVER_ERROR,
                     log::Level::Error,
-                    &body,
+                    body,
                 )
             })?;
 
     if university_id_exists {
-        return Err(
-            "User with this university ID already exists".to_json_error(StatusCode::CONFLICT)
-        );
+        return Ok(None);
     }
 
     // Determine if account should be immediately active or pending confirmation
@@ -122,57 +213,248 @@ pub(super) async fn student_signup_handler(
         university_id: body.university_id,
         password_hash: generate_hash(body.password.clone()),
         is_pending,
+        sessions_revoked_at: None,
     };
 
-    let result = students_repository::create(&data.db, student)
+    match students_repository::create(&data.db, student).await {
+        Ok(result) => {
+            info!("new student account created: {:?}", result);
+            Ok(Some(DbState::into_inner(result)))
+        }
+        Err(e) if is_unique_violation(&e) => Ok(None),
+        Err(e) => Err(error_with_log_id_and_payload(
+            format!("unable to create student's account: {}", e),
+            "Account creation failed",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+            body,
+        )),
+    }
+}
+
+/// Loads the account that won a concurrent signup, the university ID is taken when none has the email
+async fn refetch_by_email(
+    body: &Json<StudentSignupScheme>, data: &Data<AppData>,
+) -> Result<Option<Student>, JsonError> {
+    let student = students_repository::get_by_email(&data.db, &body.email)
         .await
         .map_err(|e| {
             error_with_log_id_and_payload(
-                format!("unable to create student's account: {}", e),
+                format!("unable to fetch student after a duplicate signup: {}", e),
                 "Account creation failed",
                 StatusCode::INTERNAL_SERVER_ERROR,
                 log::Level::Error,
-                &body,
+                body,
             )
         })?;
 
-    // Only send confirmation email if email confirmation is not skipped
-    if !data.config.skip_email_confirmation() {
-        let mailer = match Mailer::from_config(&data.config) {
-            Ok(m) => m,
-            Err(e) => {
-                return Err(error_with_log_id_and_payload(
-                    format!("unable to create instance of Mailer: {}", e),
-                    "Account creation failed",
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    log::Level::Error,
-                    &body,
-                ));
-            }
-        };
+    Ok(student.map(DbState::into_inner))
+}
 
-        let name = format!("{} {}", &result.first_name, &result.last_name);
-        if let Err(e) = mailer
-            .send_account_confirmation(
-                result.email.clone(),
-                name,
-                data.config.email_token_secret().clone(),
-            )
-            .await
-        {
-            return Err(error_with_log_id_and_payload(
-                format!("failed to send confirmation email: {}", e),
-                "Account created but confirmation email could not be sent",
-                StatusCode::SERVICE_UNAVAILABLE,
+fn full_name(first_name: &str, last_name: &str) -> String {
+    format!("{} {}", first_name, last_name)
+}
+
+async fn send_signup_email(
+    req: &HttpRequest, data: &Data<AppData>, body: &Json<StudentSignupScheme>, email: SignupEmail,
+    account: &SignupAccount,
+) -> Result<(), JsonError> {
+    // Links point to the frontend the signup came from
+    let link_base = link_base_for(req, data.config.frontend_base_urls());
+    let mailer = Mailer::from_config(&data.config)
+        .and_then(|m| m.with_frontend_base_url(link_base))
+        .map_err(|e| {
+            error_with_log_id_and_payload(
+                format!("unable to create instance of Mailer: {}", e),
+                "Account creation failed",
+                StatusCode::INTERNAL_SERVER_ERROR,
                 log::Level::Error,
-                &body,
-            ));
+                body,
+            )
+        })?;
+
+    let sent = match account {
+        SignupAccount::Created(student) => {
+            mailer
+                .send_account_confirmation(
+                    student.email.clone(),
+                    full_name(&student.first_name, &student.last_name),
+                    data.config.email_token_secret().clone(),
+                )
+                .await
         }
+        SignupAccount::Existing(student) => {
+            info!(
+                "signup attempted with the email of student {}",
+                student.student_id
+            );
+            mailer
+                .send_existing_account_notice(
+                    student.email.clone(),
+                    full_name(&student.first_name, &student.last_name),
+                )
+                .await
+        }
+        SignupAccount::UniversityIdTaken => {
+            info!(
+                "signup attempted with the registered university ID {}",
+                body.university_id
+            );
+            mailer
+                .send_university_id_taken_notice(
+                    body.email.clone(),
+                    full_name(&body.first_name, &body.last_name),
+                )
+                .await
+        }
+    };
+
+    sent.map_err(|e| {
+        error_with_log_id_and_payload(
+            format!("failed to send {:?} email: {}", email, e),
+            "Signup email could not be sent",
+            StatusCode::SERVICE_UNAVAILABLE,
+            log::Level::Error,
+            body,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::ResponseError;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_signup_blocked_when_disabled() {
+        let err = ensure_signup_enabled(false).unwrap_err();
+
+        assert_eq!(err.status_code(), StatusCode::FORBIDDEN);
+        assert_eq!(
+            serde_json::to_value(&err).unwrap()["code"],
+            serde_json::json!(SIGNUP_DISABLED)
+        );
     }
 
-    info!("new student account created: {:?}", result);
+    #[test]
+    fn test_signup_allowed_when_enabled() {
+        assert!(ensure_signup_enabled(true).is_ok());
+    }
 
-    Ok(HttpResponse::Ok().json(StudentSignupResponse {
-        student_id: result.student_id,
-    }))
+    #[actix_web::test]
+    async fn test_new_and_existing_emails_get_identical_responses() {
+        let sent = RefCell::new(Vec::new());
+        let record = |email| {
+            sent.borrow_mut().push(email);
+            async { Ok(()) }
+        };
+
+        let new_email = finish_signup(SignupEmail::Confirmation, record)
+            .await
+            .unwrap();
+        let existing_email = finish_signup(SignupEmail::ExistingAccount, record)
+            .await
+            .unwrap();
+
+        // Only the email tells the two apart
+        assert_eq!(
+            *sent.borrow(),
+            vec![SignupEmail::Confirmation, SignupEmail::ExistingAccount]
+        );
+        assert_eq!(new_email.status(), StatusCode::ACCEPTED);
+        assert_eq!(new_email.status(), existing_email.status());
+        assert_eq!(
+            new_email.headers().get("content-type"),
+            existing_email.headers().get("content-type")
+        );
+
+        let new_body = actix_web::body::to_bytes(new_email.into_body())
+            .await
+            .unwrap();
+        let existing_body = actix_web::body::to_bytes(existing_email.into_body())
+            .await
+            .unwrap();
+        assert_eq!(new_body, existing_body);
+        assert!(!String::from_utf8_lossy(&new_body).contains("student_id"));
+    }
+
+    #[test]
+    fn test_every_invalid_field_is_reported() {
+        let domains = vec!["studenti.unitn.it".to_string()];
+        let mut body = StudentSignupScheme {
+            first_name: " ".to_string(),
+            last_name: "Doe".to_string(),
+            email: "john.doe@gmail.com".to_string(),
+            password: String::new(),
+            university_id: 123456,
+        };
+
+        let err = validate_signup(&body, &domains).unwrap_err();
+        let payload = serde_json::to_value(&err).unwrap();
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+        assert_eq!(payload["error"], "First name cannot be empty");
+        assert_eq!(
+            payload["fields"],
+            serde_json::json!({
+                "email": ["Email domain not allowed for signup"],
+                "first_name": ["First name cannot be empty"],
+                "password": ["Password cannot be empty"]
+            })
+        );
+
+        body.first_name = "John".to_string();
+        body.password = "SecureP@ss123".to_string();
+        body.email = "john.doe@studenti.unitn.it".to_string();
+        assert!(validate_signup(&body, &domains).is_ok());
+    }
+
+    #[actix_web::test]
+    async fn test_existing_email_and_taken_university_id_get_identical_responses() {
+        let existing_email = finish_signup(SignupEmail::ExistingAccount, |_| async { Ok(()) })
+            .await
+            .unwrap();
+        let taken_university_id =
+            finish_signup(SignupEmail::UniversityIdTaken, |_| async { Ok(()) })
+                .await
+                .unwrap();
+
+        assert_eq!(taken_university_id.status(), StatusCode::ACCEPTED);
+        assert_eq!(existing_email.status(), taken_university_id.status());
+        assert_eq!(
+            actix_web::body::to_bytes(existing_email.into_body())
+                .await
+                .unwrap(),
+            actix_web::body::to_bytes(taken_university_id.into_body())
+                .await
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_registered_accounts_get_a_notice() {
+        let student = || Student {
+            student_id: 1,
+            first_name: "John".to_string(),
+            last_name: "Doe".to_string(),
+            email: "john.doe@studenti.unitn.it".to_string(),
+            university_id: 123456,
+            password_hash: String::new(),
+            is_pending: false,
+            sessions_revoked_at: None,
+        };
+
+        assert_eq!(
+            signup_email(&SignupAccount::Created(student())),
+            SignupEmail::Confirmation
+        );
+        assert_eq!(
+            signup_email(&SignupAccount::Existing(student())),
+            SignupEmail::ExistingAccount
+        );
+        assert_eq!(
+            signup_email(&SignupAccount::UniversityIdTaken),
+            SignupEmail::UniversityIdTaken
+        );
+    }
 }
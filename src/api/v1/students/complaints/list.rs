@@ -1,9 +1,12 @@
 use crate::app_data::AppData;
 use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::common::pagination::PaginationQuery;
+use crate::database::repositories::complaints_repository::ComplaintsFilter;
 use crate::database::repositories::{complaints_repository, groups_repository};
 use crate::jwt::get_user::LoggedUser;
+use crate::models::complaint::Complaint;
 use actix_web::http::StatusCode;
-use actix_web::web::{Data, Path};
+use actix_web::web::{Data, Path, Query};
 use actix_web::{HttpMessage, HttpRequest, HttpResponse};
 use chrono::{DateTime, Utc};
 use serde::Serialize;
@@ -16,9 +19,128 @@ pub(crate) struct ComplaintItem {
     pub transaction_id: i32,
     pub to_group_id: i32,
     pub text: String,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub created_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct OwnComplaintItem {
+    pub complaint_id: i32,
+    pub transaction_id: i32,
+    pub from_group_id: i32,
+    pub to_group_id: i32,
+    pub text: String,
+    #[schema(example = "resolved")]
+    pub status: String,
+    /// Left by the admin who resolved the complaint, if any
+    pub resolution_notes: Option<String>,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Complaint> for OwnComplaintItem {
+    fn from(complaint: Complaint) -> Self {
+        Self {
+            complaint_id: complaint.complaint_id,
+            transaction_id: complaint.transaction_id,
+            from_group_id: complaint.from_group_id,
+            to_group_id: complaint.to_group_id,
+            text: complaint.text,
+            status: complaint.status,
+            resolution_notes: complaint.resolution_notes,
+            created_at: complaint.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct OwnComplaintsResponse {
+    pub complaints: Vec<OwnComplaintItem>,
+}
+
+/// Complaints filed by `student_id`, whatever group they were filed for
+fn own_complaints_filter(student_id: i32) -> ComplaintsFilter {
+    ComplaintsFilter {
+        filed_by_student_id: Some(student_id),
+        ..Default::default()
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/students/complaints",
+    params(PaginationQuery),
+    responses(
+        (status = 200, description = "Page of the complaints filed by the student, most recent first. With `envelope=true` the body is `{ data, meta }`", body = OwnComplaintsResponse,
+            headers(
+                ("X-Total-Count" = u64, description = "Total number of complaints filed by the student"),
+                ("X-Page" = u32, description = "Returned page"),
+                ("X-Per-Page" = u32, description = "Page size"),
+                ("Link" = String, description = "RFC 5988 links to the first, prev, next and last pages"),
+            )
+        ),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError),
+    ),
+    security(("StudentAuth" = [])),
+    tag = "Complaints management",
+)]
+/// Complaints filed by the authenticated student
+///
+/// Only the complaints the student filed as group leader are listed, with their current status
+/// and the resolution notes left by the admins.
+#[actix_web_grants::protect("ROLE_STUDENT")]
+pub(in crate::api::v1) async fn list_own_complaints_handler(
+    req: HttpRequest, pagination: Query<PaginationQuery>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let student = req.extensions().get_student().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without a student loaded in request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let filter = own_complaints_filter(student.student_id);
+    let pagination = pagination.into_inner().within(data.page_limits);
+    let db_error = |what: &str, e: welds::WeldsError| {
+        error_with_log_id(
+            format!(
+                "unable to {} for student {}: {}",
+                what, student.student_id, e
+            ),
+            "Failed to retrieve complaints",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    };
+
+    let total = complaints_repository::count_filtered(&data.db, &filter)
+        .await
+        .map_err(|e| db_error("count complaints", e))?;
+
+    let items: Vec<OwnComplaintItem> = complaints_repository::get_filtered(
+        &data.db,
+        &filter,
+        false,
+        pagination.limit(),
+        pagination.offset(),
+    )
+    .await
+    .map_err(|e| db_error("fetch complaints", e))?
+    .into_iter()
+    .map(DbState::into_inner)
+    .map(OwnComplaintItem::from)
+    .collect();
+
+    Ok(
+        pagination.respond(&req, items, total, |complaints| OwnComplaintsResponse {
+            complaints,
+        }),
+    )
+}
+
 #[utoipa::path(
     get,
     path = "/v1/students/groups/{group_id}/complaints",
@@ -97,3 +219,45 @@ pub(in crate::api::v1) async fn list_group_filed_complaints_handler(
 
     Ok(HttpResponse::Ok().json(items))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::complaint::STATUS_RESOLVED;
+
+    #[test]
+    fn test_own_feed_is_scoped_to_the_student() {
+        let filter = own_complaints_filter(4);
+
+        assert_eq!(filter.filed_by_student_id, Some(4));
+        // Any other filter would widen or narrow the feed beyond the student's own complaints
+        assert_eq!(filter.from_group_id, None);
+        assert_eq!(filter.project_id, None);
+        assert_eq!(filter.coordinator_id, None);
+        assert_eq!(filter.status, None);
+        assert_ne!(
+            own_complaints_filter(5).filed_by_student_id,
+            filter.filed_by_student_id
+        );
+    }
+
+    #[test]
+    fn test_item_carries_status_and_resolution_notes() {
+        let item = OwnComplaintItem::from(Complaint {
+            complaint_id: 1,
+            transaction_id: 2,
+            from_group_id: 3,
+            to_group_id: 4,
+            text: "Missing documentation".to_string(),
+            status: STATUS_RESOLVED.to_string(),
+            created_at: Utc::now(),
+            filed_by_student_id: Some(4),
+            resolution_notes: Some("Refunded".to_string()),
+        });
+
+        let json = serde_json::to_value(&item).unwrap();
+        assert_eq!(json["status"], "resolved");
+        assert_eq!(json["resolution_notes"], "Refunded");
+        assert!(json.get("filed_by_student_id").is_none());
+    }
+}
@@ -1,4 +1,6 @@
-use crate::api::v1::students::complaints::list::list_group_filed_complaints_handler;
+use crate::api::v1::students::complaints::list::{
+    list_group_filed_complaints_handler, list_own_complaints_handler,
+};
 use crate::api::v1::students::complaints::submit::submit_complaint_handler;
 use actix_web::{web, Scope};
 
@@ -8,6 +10,7 @@ pub(crate) mod submit;
 pub(super) fn complaints_scope() -> Scope {
     web::scope("")
         .route("/complaints", web::post().to(submit_complaint_handler))
+        .route("/complaints", web::get().to(list_own_complaints_handler))
         .route(
             "/groups/{group_id}/complaints",
             web::get().to(list_group_filed_complaints_handler),
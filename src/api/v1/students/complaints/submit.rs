@@ -5,7 +5,7 @@ use crate::database::repositories::{
     transactions_repository,
 };
 use crate::jwt::get_user::LoggedUser;
-use crate::models::complaint::Complaint;
+use crate::models::complaint::{Complaint, STATUS_OPEN};
 use actix_web::http::StatusCode;
 use actix_web::web::{Data, Json};
 use actix_web::{HttpMessage, HttpRequest, HttpResponse};
@@ -127,7 +127,10 @@ pub(in crate::api::v1) async fn submit_complaint_handler(
         from_group_id: body.from_group_id,
         to_group_id: seller_selection.group_id,
         text: body.text.trim().to_string(),
+        status: STATUS_OPEN.to_string(),
         created_at: Utc::now(),
+        filed_by_student_id: Some(student.student_id),
+        resolution_notes: None,
     };
 
     let created = complaints_repository::create(&data.db, complaint)
@@ -22,6 +22,7 @@ pub(crate) struct TransactionEntry {
     pub group_deliverable_selection_id: i32,
     pub group_deliverable_component_id: i32,
     #[schema(value_type = String)]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub timestamp: DateTime<Utc>,
 }
 
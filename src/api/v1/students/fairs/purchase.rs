@@ -75,7 +75,7 @@ pub(in crate::api::v1) async fn purchase_handler(
         })?
         .ok_or_else(|| "Fair not found".to_json_error(StatusCode::NOT_FOUND))?;
 
-    if !fairs_repository::is_active(&fair_state) {
+    if !fairs_repository::is_active(&fair_state, data.clock.now()) {
         return Err("The fair is not currently active".to_json_error(StatusCode::FORBIDDEN));
     }
 
@@ -231,6 +231,8 @@ pub(in crate::api::v1) async fn purchase_handler(
         group_deliverable_component_id: body.group_deliverable_component_id,
         fair_id,
         timestamp: Utc::now(),
+        reverses_transaction_id: None,
+        reversed_by_admin_id: None,
     };
 
     let created = transactions_repository::create(&data.db, transaction)
@@ -0,0 +1,265 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::database::repositories::{fair_slots_repository, fairs_repository, groups_repository};
+use crate::jwt::get_user::LoggedUser;
+use crate::models::fair::Fair;
+use crate::models::fair_slot::FairSlot;
+use crate::models::group::Group;
+use crate::models::project::Project;
+use actix_web::http::StatusCode;
+use actix_web::web::Data;
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+use welds::state::DbState;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct StudentFairEntry {
+    pub fair_id: i32,
+    pub project_id: i32,
+    pub project_name: String,
+    /// The student's group in the fair's project
+    pub group_id: i32,
+    pub group_name: String,
+    pub details: String,
+    #[schema(value_type = String)]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub start_date: DateTime<Utc>,
+    #[schema(value_type = String)]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub end_date: DateTime<Utc>,
+    pub is_active: bool,
+    /// When the student's group presents, if a slot was assigned
+    pub slot: Option<SlotTime>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct SlotTime {
+    #[schema(value_type = String)]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub starts_at: DateTime<Utc>,
+    #[schema(value_type = String)]
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub ends_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct StudentFairsResponse {
+    pub fairs: Vec<StudentFairEntry>,
+}
+
+/// Fairs that have not ended yet for the published projects of the student's groups, soonest
+/// first, with the slot of the student's group when one is in `slots`
+pub(crate) fn upcoming_fairs(
+    memberships: &[(Group, Project)], fairs: Vec<Fair>, slots: &[FairSlot], now: DateTime<Utc>,
+) -> Vec<StudentFairEntry> {
+    let mut entries: Vec<StudentFairEntry> = fairs
+        .into_iter()
+        .filter(|fair| fair.end_date >= now)
+        .filter_map(|fair| {
+            let (group, project) = memberships.iter().find(|(group, project)| {
+                group.project_id == fair.project_id && project.published
+            })?;
+            let slot = slots
+                .iter()
+                .find(|s| s.fair_id == fair.fair_id && s.group_id == group.group_id)
+                .map(|s| SlotTime {
+                    starts_at: s.starts_at,
+                    ends_at: s.ends_at,
+                });
+            Some(StudentFairEntry {
+                fair_id: fair.fair_id,
+                project_id: fair.project_id,
+                project_name: project.name.clone(),
+                group_id: group.group_id,
+                group_name: group.name.clone(),
+                is_active: fair.start_date <= now,
+                details: fair.details,
+                start_date: fair.start_date,
+                end_date: fair.end_date,
+                slot,
+            })
+        })
+        .collect();
+
+    entries.sort_by_key(|entry| entry.start_date);
+    entries
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/students/fairs",
+    responses(
+        (status = 200, description = "Upcoming and running fairs of the student's projects, with their group's slot", body = StudentFairsResponse),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError),
+    ),
+    security(("StudentAuth" = [])),
+    tag = "Fair transactions",
+)]
+/// List the fairs the authenticated student takes part in
+///
+/// Only fairs of projects where the student is in a group are returned, and ended fairs are left out.
+#[actix_web_grants::protect("ROLE_STUDENT")]
+pub(in crate::api::v1) async fn list_student_fairs_handler(
+    req: HttpRequest, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let student = req.extensions().get_student().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without a user loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let memberships: Vec<(Group, Project)> =
+        groups_repository::get_groups_with_projects_for_student(&data.db, student.student_id)
+            .await
+            .map_err(|e| {
+                error_with_log_id(
+                    format!(
+                        "unable to fetch groups of student {}: {}",
+                        student.student_id, e
+                    ),
+                    "Database error",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                )
+            })?
+            .into_iter()
+            .map(|(_, group, project)| (DbState::into_inner(group), DbState::into_inner(project)))
+            .collect();
+
+    let project_ids: Vec<i32> = memberships.iter().map(|(g, _)| g.project_id).collect();
+    let fairs = fairs_repository::get_by_project_ids(&data.db, &project_ids)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!(
+                    "unable to fetch fairs of student {}: {}",
+                    student.student_id, e
+                ),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?
+        .into_iter()
+        .map(DbState::into_inner)
+        .collect();
+
+    let group_ids: Vec<i32> = memberships.iter().map(|(g, _)| g.group_id).collect();
+    let slots: Vec<FairSlot> = fair_slots_repository::get_for_groups(&data.db, &group_ids)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!(
+                    "unable to fetch fair slots of student {}: {}",
+                    student.student_id, e
+                ),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?
+        .into_iter()
+        .map(DbState::into_inner)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(StudentFairsResponse {
+        fairs: upcoming_fairs(&memberships, fairs, &slots, data.clock.now()),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 6, 1, 12, 0, 0).unwrap()
+    }
+
+    fn membership(group_id: i32, project_id: i32) -> (Group, Project) {
+        let group = Group {
+            group_id,
+            project_id,
+            name: format!("Group {}", group_id),
+            created_at: now(),
+        };
+        let project = Project {
+            project_id,
+            name: format!("Project {}", project_id),
+            slug: format!("project-{}", project_id),
+            year: 2026,
+            max_student_uploads: 5,
+            max_group_size: 4,
+            deliverable_selection_deadline: None,
+            upload_deadline: None,
+            active: true,
+            oral_exam_enabled: false,
+            frozen: false,
+            allowed_signup_domains: None,
+            published: true,
+        };
+        (group, project)
+    }
+
+    fn fair(fair_id: i32, project_id: i32, start_in_hours: i64, hours: i64) -> Fair {
+        let start_date = now() + Duration::hours(start_in_hours);
+        Fair {
+            fair_id,
+            project_id,
+            details: String::new(),
+            start_date,
+            end_date: start_date + Duration::hours(hours),
+            min_purchases: 1,
+        }
+    }
+
+    #[test]
+    fn test_student_sees_only_fairs_of_their_projects() {
+        let memberships = [membership(10, 1)];
+        let fairs = vec![fair(1, 1, 24, 8), fair(2, 2, 24, 8)];
+
+        let entries = upcoming_fairs(&memberships, fairs, &[], now());
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].fair_id, 1);
+        assert_eq!(entries[0].group_id, 10);
+        assert!(!entries[0].is_active);
+    }
+
+    #[test]
+    fn test_ended_fairs_are_left_out_and_running_first() {
+        let memberships = [membership(10, 1), membership(20, 2), membership(30, 3)];
+        let fairs = vec![fair(1, 1, 48, 8), fair(2, 2, -2, 4), fair(3, 3, -10, 4)];
+
+        let entries = upcoming_fairs(&memberships, fairs, &[], now());
+
+        let ids: Vec<i32> = entries.iter().map(|e| e.fair_id).collect();
+        assert_eq!(ids, vec![2, 1]);
+        assert!(entries[0].is_active);
+    }
+
+    #[test]
+    fn test_group_slot_is_exposed() {
+        let memberships = [membership(10, 1), membership(20, 2)];
+        let fairs = vec![fair(1, 1, 24, 8), fair(2, 2, 24, 8)];
+        let starts_at = now() + Duration::hours(25);
+        let slots = [FairSlot {
+            fair_slot_id: 1,
+            fair_id: 1,
+            group_id: 10,
+            starts_at,
+            ends_at: starts_at + Duration::minutes(20),
+        }];
+
+        let entries = upcoming_fairs(&memberships, fairs, &slots, now());
+
+        assert_eq!(entries[0].slot.as_ref().unwrap().starts_at, starts_at);
+        assert!(entries[1].slot.is_none());
+    }
+}
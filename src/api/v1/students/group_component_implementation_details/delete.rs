@@ -1,5 +1,6 @@
 use crate::app_data::AppData;
 use crate::common::json_error::{error_with_log_id, error_with_log_id_and_payload, JsonError};
+use crate::common::project_freeze::ensure_group_not_frozen;
 use crate::database::repositories::{
     group_component_implementation_details_repository, group_deliverable_selections_repository,
     groups_repository,
@@ -31,6 +32,7 @@ pub(crate) struct DeleteComponentImplementationDetailResponse {
         (status = 400, description = "Invalid request", body = JsonError),
         (status = 403, description = "Not authorized - must be group leader", body = JsonError),
         (status = 404, description = "Group, selection, or implementation detail not found", body = JsonError),
+        (status = 409, description = "The project is frozen (code PROJECT_FROZEN)", body = JsonError),
         (status = 500, description = "Internal server error", body = JsonError)
     ),
     security(("StudentAuth" = [])),
@@ -78,6 +80,8 @@ pub(in crate::api::v1) async fn delete_component_implementation_detail(
         ));
     }
 
+    ensure_group_not_frozen(&data.db, group_id).await?;
+
     // 2. Verify the group has selected a deliverable
     let selection_state =
         group_deliverable_selections_repository::get_by_group_id(&data.db, group_id)
@@ -1,10 +1,12 @@
 use crate::app_data::AppData;
 use crate::common::json_error::{error_with_log_id, error_with_log_id_and_payload, JsonError};
+use crate::common::project_freeze::ensure_not_frozen;
 use crate::database::repositories::{
-    group_deliverable_selections_repository, group_deliverables_repository, groups_repository,
-    projects_repository,
+    audit_log_repository, group_deliverable_selections_repository, group_deliverables_repository,
+    groups_repository, projects_repository,
 };
 use crate::jwt::get_user::LoggedUser;
+use crate::models::audit_log::{AuditLog, GROUP_DELIVERABLE_SELECTED};
 use crate::models::group_deliverable_selection::GroupDeliverableSelection;
 use actix_web::http::StatusCode;
 use actix_web::web::{Data, Json, Path};
@@ -35,7 +37,7 @@ pub(crate) struct CreateGroupDeliverableSelectionResponse {
         (status = 400, description = "Invalid request", body = JsonError),
         (status = 403, description = "Not authorized - must be group leader", body = JsonError),
         (status = 404, description = "Group or deliverable not found", body = JsonError),
-        (status = 409, description = "Group already has a selection or link already in use", body = JsonError),
+        (status = 409, description = "Group already has a selection or link already in use, or the project is frozen (code PROJECT_FROZEN)", body = JsonError),
         (status = 500, description = "Internal server error", body = JsonError)
     ),
     security(("StudentAuth" = [])),
@@ -184,9 +186,10 @@ pub(in crate::api::v1) async fn create_group_deliverable_selection(
         })?;
 
     let project = DbState::into_inner(project_state);
+    ensure_not_frozen(project.frozen)?;
 
     if let Some(deadline) = project.deliverable_selection_deadline {
-        if Utc::now() > deadline {
+        if data.clock.now() > deadline {
             return Err(error_with_log_id(
                 format!(
                     "Deliverable selection deadline {} has passed for project {}",
@@ -221,6 +224,18 @@ pub(in crate::api::v1) async fn create_group_deliverable_selection(
         })?;
 
     let selection = DbState::into_inner(selection_state);
+    audit_log_repository::record_or_warn(
+        &data.db,
+        AuditLog::by_student(user.student_id, GROUP_DELIVERABLE_SELECTED)
+            .project(group.project_id)
+            .target("group", group_id)
+            .details(format!(
+                "group deliverable {}",
+                selection.group_deliverable_id
+            )),
+    )
+    .await;
+
     Ok(
         HttpResponse::Created().json(CreateGroupDeliverableSelectionResponse {
             group_deliverable_selection_id: selection.group_deliverable_selection_id,
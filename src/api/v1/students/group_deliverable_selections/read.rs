@@ -18,7 +18,9 @@ pub(crate) struct ComponentImplementationDetail {
     pub component_name: String,
     pub markdown_description: String,
     pub repository_link: String,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -1,12 +1,12 @@
 use crate::app_data::AppData;
-use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
 use crate::database::repositories::groups_repository;
 use crate::jwt::get_user::LoggedUser;
 use actix_web::http::StatusCode;
-use actix_web::web::{Data, Json};
+use actix_web::web::{Data, Json, Query};
 use actix_web::{HttpMessage, HttpRequest, HttpResponse};
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub(crate) struct CheckNameRequest {
@@ -14,18 +14,32 @@ pub(crate) struct CheckNameRequest {
     pub name: String,
 }
 
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct CheckNameQuery {
+    /// Reserve the name for the caller when it is available
+    pub reserve: Option<bool>,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub(crate) struct CheckNameResponse {
     pub exists: bool,
+    /// Whether another student currently holds a reservation on the name
+    pub reserved: bool,
+    /// Token to send when creating the group, only set when a reservation was requested
+    pub reservation_token: Option<String>,
+    /// Seconds the reservation stays valid
+    pub reservation_expires_in: Option<u64>,
 }
 
 #[utoipa::path(
     post,
     path = "/v1/students/groups/check-name",
+    params(CheckNameQuery),
     request_body = CheckNameRequest,
     responses(
         (status = 200, description = "A boolean indicating if name exists already", body = CheckNameResponse),
         (status = 401, description = "Authentication required", body = JsonError),
+        (status = 409, description = "The name is reserved by another student", body = JsonError),
         (status = 500, description = "Internal server error", body = JsonError)
     ),
     security(("StudentAuth" = [])),
@@ -35,11 +49,14 @@ pub(crate) struct CheckNameResponse {
 ///
 /// This endpoint allows students to check if a group name is already taken
 /// within a specific project before creating a group.
+/// With `reserve=true` an available name is held for the caller for a short time,
+/// the returned token must be sent when creating the group.
 #[actix_web_grants::protect("ROLE_STUDENT")]
 pub(super) async fn check_name(
-    req: HttpRequest, body: Json<CheckNameRequest>, data: Data<AppData>,
+    req: HttpRequest, query: Query<CheckNameQuery>, body: Json<CheckNameRequest>,
+    data: Data<AppData>,
 ) -> Result<HttpResponse, JsonError> {
-    let _user = match req.extensions().get_student() {
+    let user = match req.extensions().get_student() {
         Ok(user) => user,
         Err(_) => {
             return Err(error_with_log_id(
@@ -63,5 +80,31 @@ pub(super) async fn check_name(
             )
         })?;
 
-    Ok(HttpResponse::Ok().json(CheckNameResponse { exists }))
+    let reservations = &data.group_name_reservations;
+
+    if exists || !query.reserve.unwrap_or(false) {
+        let reserved = reservations
+            .check(body.project_id, &body.name, user.student_id, None)
+            .is_err();
+
+        return Ok(HttpResponse::Ok().json(CheckNameResponse {
+            exists,
+            reserved,
+            reservation_token: None,
+            reservation_expires_in: None,
+        }));
+    }
+
+    let token = reservations
+        .reserve(body.project_id, &body.name, user.student_id)
+        .map_err(|_| {
+            "Group name is reserved by another student".to_json_error(StatusCode::CONFLICT)
+        })?;
+
+    Ok(HttpResponse::Ok().json(CheckNameResponse {
+        exists,
+        reserved: false,
+        reservation_token: Some(token),
+        reservation_expires_in: Some(reservations.ttl().as_secs()),
+    }))
 }
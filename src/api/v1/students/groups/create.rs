@@ -1,7 +1,11 @@
 use crate::app_data::AppData;
-use crate::common::json_error::{error_with_log_id, JsonError};
-use crate::database::repositories::{groups_repository, security_codes};
+use crate::common::created::created;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::common::project_freeze::ensure_project_not_frozen;
+use crate::database::repositories::{audit_log_repository, groups_repository, security_codes};
+use crate::database::unit_of_work::UnitOfWork;
 use crate::jwt::get_user::LoggedUser;
+use crate::models::audit_log::{AuditLog, GROUP_CREATED};
 use crate::models::group::Group;
 use crate::models::group_member::GroupMember;
 use crate::models::student_role::AvailableStudentRole;
@@ -17,6 +21,8 @@ use welds::state::DbState;
 pub(crate) struct CreateGroupRequest {
     pub name: String,
     pub security_code: String,
+    /// Token returned by the name check when the name was reserved
+    pub reservation_token: Option<String>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -32,10 +38,12 @@ pub(crate) struct CreateGroupResponse {
     path = "/v1/students/groups",
     request_body = CreateGroupRequest,
     responses(
-        (status = 201, description = "Group created successfully", body = CreateGroupResponse),
+        (status = 201, description = "Group created successfully", body = CreateGroupResponse,
+            headers(("Location" = String, description = "URL of the created group"))
+        ),
         (status = 400, description = "Invalid request data", body = JsonError),
         (status = 401, description = "Authentication required", body = JsonError),
-        (status = 409, description = "User already has a group for this project", body = JsonError),
+        (status = 409, description = "User already has a group for this project or the name is reserved by another student, or the project is frozen (code PROJECT_FROZEN)", body = JsonError),
         (status = 500, description = "Internal server error", body = JsonError)
     ),
     security(("StudentAuth" = [])),
@@ -47,9 +55,11 @@ pub(crate) struct CreateGroupResponse {
 /// The security code must be valid and not expired for the specified project.
 /// Each student can only create one group per project.
 /// The group creator becomes the GroupLeader automatically.
+/// A name reserved through the name check can only be used with its reservation token.
+/// The group and its leader are created together: if either insert fails, neither is kept.
 #[actix_web_grants::protect("ROLE_STUDENT")]
 pub(crate) async fn create_group(
-    req: HttpRequest, body: Json<CreateGroupRequest>, data: Data<AppData>,
+    req: HttpRequest, body: Json<CreateGroupRequest>, data: Data<AppData>, unit_of_work: UnitOfWork,
 ) -> Result<HttpResponse, JsonError> {
     let user = match req.extensions().get_student() {
         Ok(user) => user,
@@ -88,7 +98,7 @@ pub(crate) async fn create_group(
     };
 
     // Validate security code expiration
-    if security_code.expiration <= Utc::now() {
+    if security_code.is_expired(data.clock.now()) {
         return Err(error_with_log_id(
             "security code has expired",
             "Invalid security code",
@@ -97,6 +107,8 @@ pub(crate) async fn create_group(
         ));
     }
 
+    ensure_project_not_frozen(&data.db, security_code.project_id).await?;
+
     // Check if the student already has a group for this project
     let in_project = groups_repository::is_student_in_project(
         &data.db,
@@ -122,6 +134,17 @@ pub(crate) async fn create_group(
         ));
     }
 
+    data.group_name_reservations
+        .check(
+            security_code.project_id,
+            &body.name,
+            user.student_id,
+            body.reservation_token.as_deref(),
+        )
+        .map_err(|_| {
+            "Group name is reserved by another student".to_json_error(StatusCode::CONFLICT)
+        })?;
+
     // Create the group using repository function
     let group = Group {
         group_id: 0,
@@ -130,7 +153,7 @@ pub(crate) async fn create_group(
         created_at: Utc::now(),
     };
 
-    let created_group = groups_repository::create_group(&data.db, group)
+    let created_group = groups_repository::create_group(&unit_of_work, group)
         .await
         .map_err(|e| {
             error_with_log_id(
@@ -152,12 +175,10 @@ pub(crate) async fn create_group(
         joined_at: Utc::now(),
     };
 
-    groups_repository::create_group_member(&data.db, group_member)
+    // On failure the request transaction is rolled back, dropping the group created above
+    groups_repository::create_group_member(&unit_of_work, group_member)
         .await
         .map_err(|e| {
-            // Note: We can't await in map_err, so we'll just log the error
-            // The group will remain in the database but this is acceptable
-            // as it's a rare error case
             error_with_log_id(
                 format!("unable to add student as group member: {}", e),
                 "Database error",
@@ -166,10 +187,34 @@ pub(crate) async fn create_group(
             )
         })?;
 
-    Ok(HttpResponse::Created().json(CreateGroupResponse {
-        group_id: group_data.group_id,
-        name: group_data.name,
-        project_id: group_data.project_id,
-        role: "Group Leader".to_string(),
-    }))
+    audit_log_repository::record(
+        &unit_of_work,
+        AuditLog::by_student(user.student_id, GROUP_CREATED)
+            .project(group_data.project_id)
+            .target("group", group_data.group_id)
+            .details(format!("group {:?}", group_data.name)),
+    )
+    .await
+    .map_err(|e| {
+        error_with_log_id(
+            format!("unable to record the creation of the group: {}", e),
+            "Database error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    data.group_name_reservations
+        .release(group_data.project_id, &group_data.name);
+
+    Ok(created(
+        "/v1/students/groups",
+        group_data.group_id,
+        &CreateGroupResponse {
+            group_id: group_data.group_id,
+            name: group_data.name,
+            project_id: group_data.project_id,
+            role: "Group Leader".to_string(),
+        },
+    ))
 }
@@ -1,5 +1,6 @@
 use crate::app_data::AppData;
 use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::common::project_freeze::ensure_group_not_frozen;
 use crate::database::repositories::groups_repository;
 use crate::jwt::get_user::LoggedUser;
 use actix_web::http::StatusCode;
@@ -19,6 +20,7 @@ pub(crate) struct DeleteGroupResponse {
         (status = 200, description = "Group deleted successfully", body = DeleteGroupResponse),
         (status = 401, description = "Authentication required", body = JsonError),
         (status = 403, description = "Insufficient permissions", body = JsonError),
+        (status = 409, description = "The project is frozen (code PROJECT_FROZEN)", body = JsonError),
         (status = 500, description = "Internal server error", body = JsonError)
     ),
     security(("StudentAuth" = [])),
@@ -70,6 +72,8 @@ pub(crate) async fn delete_group(
         ));
     }
 
+    ensure_group_not_frozen(&data.db, group_id).await?;
+
     // Delete the group and all its members
     groups_repository::delete_group_with_members(&data.db, group_id)
         .await
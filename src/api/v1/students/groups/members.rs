@@ -1,10 +1,13 @@
 use crate::app_data::AppData;
 use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::common::project_freeze::{ensure_group_not_frozen, ensure_not_frozen};
+use crate::database::repositories::groups_repository::MemberRemoval;
 use crate::database::repositories::{
-    groups_repository, projects_repository, student_deliverable_selections_repository,
-    students_repository,
+    audit_log_repository, groups_repository, projects_repository,
+    student_deliverable_selections_repository, students_repository,
 };
 use crate::jwt::get_user::LoggedUser;
+use crate::models::audit_log::{AuditLog, GROUP_MEMBER_ADDED, GROUP_MEMBER_REMOVED};
 use crate::models::group_member::GroupMember;
 use crate::models::student_role::AvailableStudentRole;
 use actix_web::http::StatusCode;
@@ -44,7 +47,7 @@ pub(crate) struct MemberInfo {
         (status = 401, description = "Authentication required", body = JsonError),
         (status = 403, description = "Insufficient permissions", body = JsonError),
         (status = 404, description = "Group or student not found", body = JsonError),
-        (status = 409, description = "Student is already in a group for this project", body = JsonError),
+        (status = 409, description = "Student is already in a group for this project, or the project is frozen (code PROJECT_FROZEN)", body = JsonError),
         (status = 500, description = "Internal server error", body = JsonError)
     ),
     security(("StudentAuth" = [])),
@@ -207,33 +210,9 @@ pub(super) async fn add_member(
         }
     };
 
-    let current_member_count = groups_repository::count_members(&data.db, group_id)
-        .await
-        .map_err(|e| {
-            error_with_log_id(
-                format!("unable to count members for group {}: {}", group_id, e),
-                "Database error",
-                StatusCode::INTERNAL_SERVER_ERROR,
-                log::Level::Error,
-            )
-        })?;
+    ensure_not_frozen(project.frozen)?;
 
-    if current_member_count >= project.max_group_size {
-        return Err(error_with_log_id(
-            format!(
-                "group {} has reached maximum size of {} members",
-                group_id, project.max_group_size
-            ),
-            format!(
-                "Group has reached the maximum size of {} members for this project",
-                project.max_group_size
-            ),
-            StatusCode::BAD_REQUEST,
-            log::Level::Info,
-        ));
-    }
-
-    // Add the student as a group member with Member role using repository function
+    // Add the student as a group member with Member role, checking the size on the locked members
     let group_member = GroupMember {
         group_member_id: 0,
         group_id,
@@ -242,15 +221,16 @@ pub(super) async fn add_member(
         joined_at: Utc::now(),
     };
 
-    match groups_repository::create_group_member(&data.db, group_member).await {
-        Ok(_) => Ok(HttpResponse::Ok().json(MemberInfo {
-            student_id: student.student_id,
-            email: student.email,
-            first_name: student.first_name,
-            last_name: student.last_name,
-            role: "Member".to_string(),
-        })),
-        Err(e) => Err(error_with_log_id(
+    let added = groups_repository::add_members_locked(&data.db, group_id, |members| {
+        if members.len() as i32 >= project.max_group_size {
+            (Vec::new(), false)
+        } else {
+            (vec![group_member], true)
+        }
+    })
+    .await
+    .map_err(|e| {
+        error_with_log_id(
             format!(
                 "unable to add student {} to group: {}",
                 student.student_id, e
@@ -258,8 +238,39 @@ pub(super) async fn add_member(
             "Database error",
             StatusCode::INTERNAL_SERVER_ERROR,
             log::Level::Error,
-        )),
+        )
+    })?;
+
+    if !added {
+        return Err(error_with_log_id(
+            format!(
+                "group {} has reached maximum size of {} members",
+                group_id, project.max_group_size
+            ),
+            format!(
+                "Group has reached the maximum size of {} members for this project",
+                project.max_group_size
+            ),
+            StatusCode::BAD_REQUEST,
+            log::Level::Info,
+        ));
     }
+
+    audit_log_repository::record_or_warn(
+        &data.db,
+        AuditLog::by_student(user.student_id, GROUP_MEMBER_ADDED)
+            .project(group.project_id)
+            .target("student", student.student_id)
+            .details(format!("group {}", group_id)),
+    )
+    .await;
+    Ok(HttpResponse::Ok().json(MemberInfo {
+        student_id: student.student_id,
+        email: student.email,
+        first_name: student.first_name,
+        last_name: student.last_name,
+        role: "Member".to_string(),
+    }))
 }
 
 #[utoipa::path(
@@ -272,6 +283,7 @@ pub(super) async fn add_member(
         (status = 401, description = "Authentication required", body = JsonError),
         (status = 403, description = "Insufficient permissions", body = JsonError),
         (status = 404, description = "Group or member not found", body = JsonError),
+        (status = 409, description = "The project is frozen (code PROJECT_FROZEN)", body = JsonError),
         (status = 500, description = "Internal server error", body = JsonError)
     ),
     security(("StudentAuth" = [])),
@@ -322,33 +334,29 @@ pub(super) async fn remove_member(
         ));
     }
 
-    // Find the group member
-    let member_states = groups_repository::get_members(&data.db, group_id)
-        .await
-        .map_err(|e| {
-            error_with_log_id(
-                format!("unable to find group member: {}", e),
-                "Database error",
-                StatusCode::INTERNAL_SERVER_ERROR,
-                log::Level::Error,
-            )
-        })?;
-
-    // Filter by student_id
-    let mut found_member = None;
-    for member_state in member_states {
-        let member_data = DbState::into_inner(member_state);
-        if member_data.student_id == body.student_id {
-            found_member = Some(member_data);
-            break;
-        }
-    }
+    ensure_group_not_frozen(&data.db, group_id).await?;
 
-    let member = found_member;
+    // Remove the member while holding the group lock, re-checking leadership on the
+    // current members so a concurrent transfer cannot be raced
+    let removal = groups_repository::remove_member_locked(
+        &data.db,
+        group_id,
+        body.student_id,
+        Some(user.student_id),
+    )
+    .await
+    .map_err(|e| {
+        error_with_log_id(
+            format!("unable to remove member from group: {}", e),
+            "Database error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
 
-    let member = match member {
-        Some(member) => member,
-        None => {
+    let member = match removal {
+        MemberRemoval::Removed(member) => member,
+        MemberRemoval::NotMember => {
             return Err(error_with_log_id(
                 format!(
                     "member with student_id {} not found in group {}",
@@ -359,21 +367,31 @@ pub(super) async fn remove_member(
                 log::Level::Info,
             ));
         }
+        // Don't allow removing the GroupLeader
+        MemberRemoval::LeaderProtected => {
+            return Err(error_with_log_id(
+                format!(
+                    "attempt to remove group leader (student_id {}) from group {}",
+                    body.student_id, group_id
+                ),
+                "Cannot remove the group leader",
+                StatusCode::BAD_REQUEST,
+                log::Level::Info,
+            ));
+        }
+        MemberRemoval::NotLeader => {
+            return Err(error_with_log_id(
+                format!(
+                    "user {} is no longer a GroupLeader of group {}",
+                    user.student_id, group_id
+                ),
+                "Insufficient permissions",
+                StatusCode::FORBIDDEN,
+                log::Level::Warn,
+            ));
+        }
     };
 
-    // Don't allow removing the GroupLeader
-    if member.student_role_id == AvailableStudentRole::GroupLeader as i32 {
-        return Err(error_with_log_id(
-            format!(
-                "attempt to remove group leader (student_id {}) from group {}",
-                member.student_id, group_id
-            ),
-            "Cannot remove the group leader",
-            StatusCode::BAD_REQUEST,
-            log::Level::Info,
-        ));
-    }
-
     // Get the group to find the project_id for deliverable selection deletion
     let group_state = groups_repository::get_by_id(&data.db, group_id)
         .await
@@ -398,6 +416,15 @@ pub(super) async fn remove_member(
         }
     };
 
+    audit_log_repository::record_or_warn(
+        &data.db,
+        AuditLog::by_student(user.student_id, GROUP_MEMBER_REMOVED)
+            .project(group.project_id)
+            .target("student", member.student_id)
+            .details(format!("group {}", group_id)),
+    )
+    .await;
+
     // Delete the student's deliverable selection for this project (MANDATORY - Q4)
     if let Err(e) = student_deliverable_selections_repository::delete_by_student_and_project(
         &data.db,
@@ -415,14 +442,5 @@ pub(super) async fn remove_member(
         );
     }
 
-    // Remove the member using repository
-    match groups_repository::delete_member_by_id(&data.db, member.group_member_id).await {
-        Ok(_) => Ok(HttpResponse::NoContent().finish()),
-        Err(e) => Err(error_with_log_id(
-            format!("unable to remove member from group: {}", e),
-            "Database error",
-            StatusCode::INTERNAL_SERVER_ERROR,
-            log::Level::Error,
-        )),
-    }
+    Ok(HttpResponse::NoContent().finish())
 }
@@ -1,13 +1,13 @@
 use crate::app_data::AppData;
 use crate::common::json_error::{error_with_log_id, JsonError};
-use crate::database::repositories::{groups_repository, students_repository};
+use crate::database::repositories::groups_repository;
 use crate::jwt::get_user::LoggedUser;
 use actix_web::http::StatusCode;
 use actix_web::web::{Data, Path};
 use actix_web::{HttpMessage, HttpRequest, HttpResponse};
 use serde::Serialize;
 use utoipa::ToSchema;
-use welds::state::DbState;
+use welds::Client;
 
 #[derive(Debug, Serialize, ToSchema)]
 pub(crate) struct GroupMembersResponse {
@@ -58,80 +58,74 @@ pub(super) async fn list_group_members(
         }
     };
 
-    let group_id = path.into_inner();
+    let group_members = load_group_members(&data.db, path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(group_members))
+}
 
-    // Verify the group exists
-    let group_state = groups_repository::get_by_id(&data.db, group_id)
+/// The group and all its members, in a single database operation whatever the group size
+pub(super) async fn load_group_members(
+    db: &impl Client, group_id: i32,
+) -> Result<GroupMembersResponse, JsonError> {
+    let group = groups_repository::get_with_members(db, group_id)
         .await
         .map_err(|e| {
             error_with_log_id(
-                format!("unable to fetch group: {}", e),
+                format!("unable to fetch group {} with its members: {}", group_id, e),
                 "Database error",
                 StatusCode::INTERNAL_SERVER_ERROR,
                 log::Level::Error,
             )
         })?;
 
-    let group = match group_state {
-        Some(state) => DbState::into_inner(state),
-        None => {
-            return Err(error_with_log_id(
-                format!("group with id {} not found", group_id),
-                "Group not found",
-                StatusCode::NOT_FOUND,
-                log::Level::Warn,
-            ));
-        }
+    let Some((group, members)) = group else {
+        return Err(error_with_log_id(
+            format!("group with id {} not found", group_id),
+            "Group not found",
+            StatusCode::NOT_FOUND,
+            log::Level::Warn,
+        ));
     };
 
-    // Get all group members
-    let group_members = groups_repository::get_group_members(&data.db, group_id)
-        .await
-        .map_err(|e| {
-            error_with_log_id(
-                format!("unable to fetch group members: {}", e),
-                "Database error",
-                StatusCode::INTERNAL_SERVER_ERROR,
-                log::Level::Error,
-            )
-        })?;
-
-    // Get student details for each member
-    let mut members = Vec::new();
-    for group_member in group_members {
-        let student_state = students_repository::get_by_id(&data.db, group_member.student_id)
-            .await
-            .map_err(|e| {
-                error_with_log_id(
-                    format!("unable to fetch student details: {}", e),
-                    "Database error",
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    log::Level::Error,
-                )
-            })?;
-
-        if let Some(student_state) = student_state {
-            let student = DbState::into_inner(student_state);
+    let members = members
+        .into_iter()
+        .map(|(group_member, student)| {
             let role_name = match group_member.student_role_id {
                 1 => "Group Leader",
                 2 => "Member",
                 _ => "Unknown",
             };
 
-            members.push(GroupMemberInfo {
+            GroupMemberInfo {
                 student_id: student.student_id,
                 first_name: student.first_name,
                 last_name: student.last_name,
                 email: student.email,
                 role_id: group_member.student_role_id,
                 role_name: role_name.to_string(),
-            });
-        }
-    }
+            }
+        })
+        .collect();
 
-    Ok(HttpResponse::Ok().json(GroupMembersResponse {
+    Ok(GroupMembersResponse {
         group_id: group.group_id,
         group_name: group.name,
         members,
-    }))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{assert_max_queries, RecordingClient};
+
+    #[actix_web::test]
+    async fn test_group_members_are_listed_in_one_query() {
+        let db = RecordingClient::default();
+
+        let result = assert_max_queries(1, load_group_members(&db, 3)).await;
+
+        // The recording client has no rows, so the group is not found
+        assert!(result.is_err());
+        assert_eq!(db.statements().len(), 1);
+    }
 }
@@ -3,7 +3,9 @@ use crate::api::v1::students::groups::create::create_group;
 use crate::api::v1::students::groups::delete::delete_group;
 use crate::api::v1::students::groups::members::{add_member, remove_member};
 use crate::api::v1::students::groups::members_list::list_group_members;
-use crate::api::v1::students::groups::read::get_groups;
+use crate::api::v1::students::groups::read::{get_group, get_groups};
+use crate::api::v1::students::groups::selection_validation::validate_group_selection;
+use crate::database::unit_of_work::UnitOfWorkMiddleware;
 use actix_web::{web, Scope};
 
 pub(crate) mod check_name;
@@ -12,14 +14,42 @@ pub(crate) mod delete;
 pub(crate) mod members;
 pub(crate) mod members_list;
 pub(crate) mod read;
+pub(crate) mod selection_validation;
 
 pub(super) fn groups_scope() -> Scope {
     web::scope("/groups")
-        .route("", web::post().to(create_group))
-        .route("", web::get().to(get_groups))
+        .service(
+            web::resource("")
+                .wrap(UnitOfWorkMiddleware)
+                .route(web::post().to(create_group))
+                .route(web::get().to(get_groups)),
+        )
         .route("/check-name", web::post().to(check_name))
+        .route("/{group_id}", web::get().to(get_group))
         .route("/{group_id}", web::delete().to(delete_group))
         .route("/{group_id}/members", web::get().to(list_group_members))
         .route("/{group_id}/members", web::post().to(add_member))
         .route("/{group_id}/members", web::delete().to(remove_member))
+        .route(
+            "/{group_id}/selection-validation",
+            web::get().to(validate_group_selection),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::StatusCode;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::App;
+
+    #[actix_web::test]
+    async fn test_created_group_location_is_routed() {
+        let app = init_service(App::new().service(groups_scope())).await;
+
+        // The Location of a created group is /v1/students/groups/{id}, reads of it reach
+        // the protected handler instead of a 404 or 405
+        let res = call_service(&app, TestRequest::get().uri("/groups/7").to_request()).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    }
 }
@@ -1,11 +1,13 @@
+use crate::api::v1::students::groups::members_list::{load_group_members, GroupMembersResponse};
 use crate::app_data::AppData;
 use crate::common::json_error::{error_with_log_id, JsonError};
 use crate::database::repositories::groups_repository;
 use crate::jwt::get_user::LoggedUser;
 use crate::models::group::Group;
+use crate::models::group_member::GroupMember;
 use crate::models::project::Project;
 use actix_web::http::StatusCode;
-use actix_web::web::Data;
+use actix_web::web::{Data, Path};
 use actix_web::{HttpMessage, HttpRequest, HttpResponse};
 use serde::Serialize;
 use utoipa::ToSchema;
@@ -36,6 +38,7 @@ pub(crate) struct GroupWithProject {
 /// Get all groups where the student is a member
 ///
 /// This endpoint allows authenticated students to retrieve all groups they are members of.
+/// A student in no group gets an empty list rather than a 404.
 #[actix_web_grants::protect("ROLE_STUDENT")]
 pub(crate) async fn get_groups(
     req: HttpRequest, data: Data<AppData>,
@@ -68,16 +71,70 @@ pub(crate) async fn get_groups(
                 )
             })?;
 
-    let mut groups_with_projects = Vec::new();
+    Ok(groups_response(groups_and_projects))
+}
 
-    for (_group_member_state, group_state, project_state) in groups_and_projects {
-        let group = DbState::into_inner(group_state);
-        let project = DbState::into_inner(project_state);
+#[utoipa::path(
+    get,
+    path = "/v1/students/groups/{group_id}",
+    responses(
+        (status = 200, description = "The group with its members", body = GroupMembersResponse),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 404, description = "Group not found", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("StudentAuth" = [])),
+    tag = "Groups management",
+)]
+/// Get a group with its members
+///
+/// The resource a created group is found at, answered like its members listing.
+#[actix_web_grants::protect("ROLE_STUDENT")]
+pub(crate) async fn get_group(
+    req: HttpRequest, path: Path<i32>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    req.extensions().get_student().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without a user loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
 
-        groups_with_projects.push(GroupWithProject { group, project });
-    }
+    let group = load_group_members(&data.db, path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(group))
+}
+
+/// `200 OK` listing the groups of a student, with an empty list when they are in none
+fn groups_response(
+    groups_and_projects: Vec<(DbState<GroupMember>, DbState<Group>, DbState<Project>)>,
+) -> HttpResponse {
+    let groups = groups_and_projects
+        .into_iter()
+        .map(
+            |(_group_member_state, group_state, project_state)| GroupWithProject {
+                group: DbState::into_inner(group_state),
+                project: DbState::into_inner(project_state),
+            },
+        )
+        .collect();
 
-    Ok(HttpResponse::Ok().json(GetGroupsResponse {
-        groups: groups_with_projects,
-    }))
+    HttpResponse::Ok().json(GetGroupsResponse { groups })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::body::to_bytes;
+
+    #[actix_web::test]
+    async fn test_student_without_groups_gets_an_empty_list() {
+        let response = groups_response(Vec::new());
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: serde_json::Value =
+            serde_json::from_slice(&to_bytes(response.into_body()).await.unwrap()).unwrap();
+        assert_eq!(body, serde_json::json!({ "groups": [] }));
+    }
 }
@@ -0,0 +1,294 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::repositories::{
+    group_component_implementation_details_repository, group_deliverable_selections_repository,
+    group_deliverables_components_repository, groups_repository,
+};
+use crate::jwt::get_user::LoggedUser;
+use crate::models::group_deliverable_component::GroupDeliverableComponent;
+use crate::models::group_deliverables_component::GroupDeliverablesComponent;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Path};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use serde::Serialize;
+use std::collections::HashSet;
+use utoipa::ToSchema;
+use welds::state::DbState;
+
+/// Problem found in the selections of a group
+#[derive(Debug, PartialEq, Serialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub(crate) enum SelectionIssue {
+    /// The group has not selected a deliverable yet
+    NoDeliverableSelected,
+    /// A component required by the selected deliverable has no implementation details
+    MissingImplementation {
+        group_deliverable_component_id: i32,
+        component_name: String,
+        quantity: i32,
+    },
+    /// Implementation details exist for a component that is not part of the selected deliverable
+    UnexpectedImplementation { group_deliverable_component_id: i32 },
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct SelectionValidationReport {
+    pub group_id: i32,
+    pub group_deliverable_id: Option<i32>,
+    pub valid: bool,
+    /// Sum of the weights of the components with implementation details
+    pub covered_weight: i32,
+    /// Sum of the weights of every component of the selected deliverable
+    pub total_weight: i32,
+    pub issues: Vec<SelectionIssue>,
+}
+
+/// Checks the selection of a group against the components of its deliverable
+///
+/// `links` are the components of the selected deliverable and `implemented` the components
+/// the group wrote implementation details for.
+fn validate_selection(
+    group_id: i32, group_deliverable_id: Option<i32>,
+    links: &[(GroupDeliverablesComponent, GroupDeliverableComponent)], implemented: &HashSet<i32>,
+) -> SelectionValidationReport {
+    let mut issues = Vec::new();
+    if group_deliverable_id.is_none() {
+        issues.push(SelectionIssue::NoDeliverableSelected);
+    }
+
+    let mut covered_weight = 0;
+    let mut total_weight = 0;
+    for (link, component) in links {
+        total_weight += link.weight;
+        if implemented.contains(&link.group_deliverable_component_id) {
+            covered_weight += link.weight;
+        } else {
+            issues.push(SelectionIssue::MissingImplementation {
+                group_deliverable_component_id: link.group_deliverable_component_id,
+                component_name: component.name.clone(),
+                quantity: link.quantity,
+            });
+        }
+    }
+
+    let expected: HashSet<i32> = links
+        .iter()
+        .map(|(link, _)| link.group_deliverable_component_id)
+        .collect();
+    let mut unexpected: Vec<i32> = implemented.difference(&expected).copied().collect();
+    unexpected.sort_unstable();
+    issues.extend(
+        unexpected
+            .into_iter()
+            .map(
+                |group_deliverable_component_id| SelectionIssue::UnexpectedImplementation {
+                    group_deliverable_component_id,
+                },
+            ),
+    );
+
+    SelectionValidationReport {
+        group_id,
+        group_deliverable_id,
+        valid: issues.is_empty(),
+        covered_weight,
+        total_weight,
+        issues,
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/students/groups/{group_id}/selection-validation",
+    params(("group_id" = i32, Path, description = "Group ID")),
+    responses(
+        (status = 200, description = "Report of missing or invalid selections of the group", body = SelectionValidationReport),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 403, description = "Not a member of the group", body = JsonError),
+        (status = 404, description = "Group not found", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError),
+    ),
+    security(("StudentAuth" = [])),
+    tag = "Group management",
+)]
+/// Validate the whole selection set of a group
+///
+/// The report is computed from the components of the selected deliverable and the
+/// implementation details written so far. Only members of the group can request it.
+#[actix_web_grants::protect("ROLE_STUDENT")]
+pub(super) async fn validate_group_selection(
+    req: HttpRequest, path: Path<i32>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let student = req.extensions().get_student().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without a user loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+    let group_id = path.into_inner();
+
+    groups_repository::get_by_id(&data.db, group_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch group {}: {}", group_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?
+        .ok_or_else(|| "Group not found".to_json_error(StatusCode::NOT_FOUND))?;
+
+    let members = groups_repository::get_group_members(&data.db, group_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch members of group {}: {}", group_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+    if !members.iter().any(|m| m.student_id == student.student_id) {
+        return Err("You are not a member of this group".to_json_error(StatusCode::FORBIDDEN));
+    }
+
+    let selection = group_deliverable_selections_repository::get_by_group_id(&data.db, group_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch selection of group {}: {}", group_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?
+        .map(DbState::into_inner);
+
+    let (links, implemented) = match &selection {
+        Some(selection) => {
+            let links = group_deliverables_components_repository::get_components_with_details_for_deliverable(
+                &data.db,
+                selection.group_deliverable_id,
+            )
+            .await
+            .map_err(|e| {
+                error_with_log_id(
+                    format!(
+                        "unable to fetch components of deliverable {}: {}",
+                        selection.group_deliverable_id, e
+                    ),
+                    "Database error",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                )
+            })?
+            .into_iter()
+            .map(|(link, component)| (DbState::into_inner(link), DbState::into_inner(component)))
+            .collect();
+
+            let implemented =
+                group_component_implementation_details_repository::get_by_selection_id(
+                    &data.db,
+                    selection.group_deliverable_selection_id,
+                )
+                .await
+                .map_err(|e| {
+                    error_with_log_id(
+                        format!(
+                            "unable to fetch implementation details of selection {}: {}",
+                            selection.group_deliverable_selection_id, e
+                        ),
+                        "Database error",
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        log::Level::Error,
+                    )
+                })?
+                .iter()
+                .map(|detail| detail.group_deliverable_component_id)
+                .collect();
+
+            (links, implemented)
+        }
+        None => (Vec::new(), HashSet::new()),
+    };
+
+    Ok(HttpResponse::Ok().json(validate_selection(
+        group_id,
+        selection.map(|s| s.group_deliverable_id),
+        &links,
+        &implemented,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(
+        component_id: i32, weight: i32,
+    ) -> (GroupDeliverablesComponent, GroupDeliverableComponent) {
+        (
+            GroupDeliverablesComponent {
+                id: component_id,
+                group_deliverable_id: 1,
+                group_deliverable_component_id: component_id,
+                quantity: 1,
+                weight,
+            },
+            GroupDeliverableComponent {
+                group_deliverable_component_id: component_id,
+                project_id: 1,
+                name: format!("Component {}", component_id),
+                sellable: false,
+            },
+        )
+    }
+
+    #[test]
+    fn test_complete_selection_is_valid() {
+        let links = [link(1, 60), link(2, 40)];
+        let implemented = HashSet::from([1, 2]);
+
+        let report = validate_selection(7, Some(1), &links, &implemented);
+
+        assert!(report.valid);
+        assert!(report.issues.is_empty());
+        assert_eq!(report.covered_weight, 100);
+        assert_eq!(report.total_weight, 100);
+    }
+
+    #[test]
+    fn test_missing_component_is_reported() {
+        let links = [link(1, 60), link(2, 40)];
+        let implemented = HashSet::from([1, 9]);
+
+        let report = validate_selection(7, Some(1), &links, &implemented);
+
+        assert!(!report.valid);
+        assert_eq!(report.covered_weight, 60);
+        assert_eq!(
+            report.issues,
+            vec![
+                SelectionIssue::MissingImplementation {
+                    group_deliverable_component_id: 2,
+                    component_name: "Component 2".to_string(),
+                    quantity: 1,
+                },
+                SelectionIssue::UnexpectedImplementation {
+                    group_deliverable_component_id: 9,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_deliverable_is_reported() {
+        let report = validate_selection(7, None, &[], &HashSet::new());
+
+        assert!(!report.valid);
+        assert_eq!(report.issues, vec![SelectionIssue::NoDeliverableSelected]);
+    }
+}
@@ -9,6 +9,7 @@ use crate::api::v1::students::security_codes::security_codes_scope;
 use crate::api::v1::students::student_deliverable_selections::student_deliverable_selections_scope;
 use crate::api::v1::students::uploads::uploads_scope;
 use crate::api::v1::students::users::users_scope;
+use crate::common::rate_limit::RateLimit;
 use actix_web::{web, Scope};
 
 pub(crate) mod auth;
@@ -29,7 +30,7 @@ pub(super) fn students_scope() -> Scope {
         .service(group_deliverable_selections_scope())
         .service(group_component_implementation_details_scope())
         .service(student_deliverable_selections_scope())
-        .service(auth_scope())
+        .service(auth_scope().wrap(RateLimit))
         .service(projects_scope())
         .service(security_codes_scope())
         .service(groups_scope())
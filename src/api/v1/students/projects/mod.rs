@@ -1,8 +1,15 @@
 use crate::api::v1::students::projects::read::get_student_projects;
+use crate::api::v1::students::projects::requirements::get_project_requirements;
 use actix_web::{web, Scope};
 
 pub(crate) mod read;
+pub(crate) mod requirements;
 
 pub(super) fn projects_scope() -> Scope {
-    web::scope("/projects").route("", web::get().to(get_student_projects))
+    web::scope("/projects")
+        .route("", web::get().to(get_student_projects))
+        .route(
+            "/{id}/requirements",
+            web::get().to(get_project_requirements),
+        )
 }
@@ -1,5 +1,6 @@
 use crate::app_data::AppData;
 use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::common::pagination::{PaginationQuery, TOTAL_COUNT_HEADER};
 use crate::database::repositories::projects_repository;
 use crate::jwt::get_user::LoggedUser;
 use crate::models::group_deliverable::GroupDeliverable;
@@ -8,12 +9,15 @@ use crate::models::project::Project;
 use crate::models::student_deliverable::StudentDeliverable;
 use crate::models::student_deliverable_component::StudentDeliverableComponent;
 use actix_web::http::StatusCode;
-use actix_web::web::Data;
+use actix_web::web::{Data, Query};
 use actix_web::{HttpMessage, HttpRequest, HttpResponse};
 use serde::Serialize;
 use utoipa::ToSchema;
 use welds::state::DbState;
 
+/// Every project comes with all its deliverables and components, so pages are kept small
+const PROJECTS_MAX_PER_PAGE: u32 = 50;
+
 #[derive(Debug, Serialize, ToSchema)]
 pub(crate) struct ProjectWithDetails {
     pub project: Project,
@@ -33,8 +37,16 @@ pub(crate) struct GetStudentProjects {
 #[utoipa::path(
     get,
     path = "/v1/students/projects",
+    params(PaginationQuery),
     responses(
-        (status = 200, description = "Successfully retrieved student's projects with deliverables and components", body = GetStudentProjects),
+        (status = 200, description = "Successfully retrieved the student's projects with deliverables and components, only the requested page of them when `page` or `per_page` is given. On a page, with `envelope=true` the body is `{ data, meta }` with `meta` holding page, per_page, total and total_pages", body = GetStudentProjects,
+            headers(
+                ("X-Total-Count" = u64, description = "Total number of projects visible to the student"),
+                ("X-Page" = u32, description = "Returned page, only on a page"),
+                ("X-Per-Page" = u32, description = "Page size, only on a page"),
+                ("Link" = String, description = "Only on a page, RFC 5988 links to the first, prev, next and last pages"),
+            )
+        ),
         (status = 500, description = "Internal server error during serialization or database query", body = JsonError)
     ),
     security(("StudentAuth" = [])),
@@ -43,10 +55,12 @@ pub(crate) struct GetStudentProjects {
 /// Get all the projects of student with deliverables and components
 ///
 /// This endpoint allows authenticated students to retrieve all the projects in which they have a role,
-/// along with all deliverables and components for each project
+/// along with all deliverables and components for each project. All of them are returned unless
+/// `page` or `per_page` asks for a page, with `envelope=true` the page description is then
+/// returned in the body as well.
 #[actix_web_grants::protect("ROLE_STUDENT")]
 pub(super) async fn get_student_projects(
-    req: HttpRequest, data: Data<AppData>,
+    req: HttpRequest, query: Query<PaginationQuery>, data: Data<AppData>,
 ) -> Result<HttpResponse, JsonError> {
     let user = match req.extensions().get_student() {
         Ok(user) => user,
@@ -60,9 +74,16 @@ pub(super) async fn get_student_projects(
         }
     };
 
-    // Fetch projects with all related entities using repository function
+    let query = query
+        .into_inner()
+        .within(data.page_limits.with_max(PROJECTS_MAX_PER_PAGE));
+    let page = query
+        .is_requested()
+        .then(|| (query.limit(), query.offset()));
+
+    // Fetch the projects, or the requested page of them, with all related entities using repository function
     let projects_with_details_data =
-        projects_repository::get_projects_with_details_for_student(&data.db, user.student_id)
+        projects_repository::get_projects_with_details_for_student(&data.db, user.student_id, page)
             .await
             .map_err(|e| {
                 error_with_log_id(
@@ -76,6 +97,23 @@ pub(super) async fn get_student_projects(
                 )
             })?;
 
+    let total = match page {
+        Some(_) => projects_repository::count_visible_for_student(&data.db, user.student_id)
+            .await
+            .map_err(|e| {
+                error_with_log_id(
+                    format!(
+                        "unable to count student projects from database {}: {}",
+                        user.student_id, e
+                    ),
+                    "Failed to retrieve projects",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                )
+            })?,
+        None => projects_with_details_data.len() as u64,
+    };
+
     let mut projects_with_details = Vec::new();
 
     for (
@@ -115,7 +153,133 @@ pub(super) async fn get_student_projects(
         });
     }
 
-    Ok(HttpResponse::Ok().json(GetStudentProjects {
-        projects: projects_with_details,
-    }))
+    Ok(listing_response(&query, &req, projects_with_details, total))
+}
+
+/// Answers with the requested page of projects, or with all of them when no page was asked for
+fn listing_response(
+    query: &PaginationQuery, req: &HttpRequest, projects: Vec<ProjectWithDetails>, total: u64,
+) -> HttpResponse {
+    if query.is_requested() {
+        query.respond(req, projects, total, |projects| GetStudentProjects {
+            projects,
+        })
+    } else {
+        HttpResponse::Ok()
+            .insert_header((TOTAL_COUNT_HEADER, total.to_string()))
+            .json(GetStudentProjects { projects })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::body::to_bytes;
+    use actix_web::http::header::{HeaderMap, LINK};
+    use actix_web::test::TestRequest;
+    use serde_json::{json, Value};
+
+    async fn listing(envelope: Option<bool>) -> (HeaderMap, Value) {
+        let query = PaginationQuery {
+            page: Some(2),
+            per_page: Some(10),
+            envelope,
+            ..Default::default()
+        };
+        let req = TestRequest::get()
+            .uri("/v1/students/projects?page=2&per_page=10")
+            .to_http_request();
+        let response = listing_response(&query, &req, Vec::new(), 15);
+
+        let headers = response.headers().clone();
+        let body = to_bytes(response.into_body()).await.unwrap();
+        (headers, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[actix_web::test]
+    async fn test_paged_listing_keeps_bare_body_and_headers() {
+        let (headers, body) = listing(None).await;
+
+        assert_eq!(body, json!({ "projects": [] }));
+        assert_eq!(headers.get(TOTAL_COUNT_HEADER).unwrap(), "15");
+        assert_eq!(
+            headers.get(LINK).unwrap(),
+            "</v1/students/projects?page=1&per_page=10>; rel=\"first\", \
+             </v1/students/projects?page=1&per_page=10>; rel=\"prev\", \
+             </v1/students/projects?page=2&per_page=10>; rel=\"last\""
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_enveloped_listing_describes_page_in_body() {
+        let (_, body) = listing(Some(true)).await;
+
+        assert_eq!(
+            body,
+            json!({
+                "data": [],
+                "meta": { "page": 2, "per_page": 10, "total": 15, "total_pages": 2 }
+            })
+        );
+    }
+
+    fn details(project_id: i32, frozen: bool) -> ProjectWithDetails {
+        ProjectWithDetails {
+            project: Project {
+                project_id,
+                name: "Robotics".to_string(),
+                slug: "robotics".to_string(),
+                year: 2026,
+                max_student_uploads: 5,
+                max_group_size: 4,
+                deliverable_selection_deadline: None,
+                upload_deadline: None,
+                active: true,
+                oral_exam_enabled: false,
+                frozen,
+                allowed_signup_domains: None,
+                published: true,
+            },
+            group_deliverables: Vec::new(),
+            group_components: Vec::new(),
+            student_deliverables: Vec::new(),
+            student_components: Vec::new(),
+            fair_id: None,
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_listing_without_page_is_returned_whole() {
+        let query = PaginationQuery {
+            envelope: Some(true),
+            ..Default::default()
+        };
+        let req = TestRequest::get()
+            .uri("/v1/students/projects?envelope=true")
+            .to_http_request();
+        let projects = (1..=30).map(|id| details(id, false)).collect();
+
+        let response = listing_response(&query, &req, projects, 30);
+
+        let headers = response.headers().clone();
+        assert_eq!(headers.get(TOTAL_COUNT_HEADER).unwrap(), "30");
+        assert!(headers.get(LINK).is_none());
+        let body: Value =
+            serde_json::from_slice(&to_bytes(response.into_body()).await.unwrap()).unwrap();
+        assert_eq!(body["projects"].as_array().unwrap().len(), 30);
+    }
+
+    #[actix_web::test]
+    async fn test_frozen_project_stays_readable() {
+        let req = TestRequest::get()
+            .uri("/v1/students/projects")
+            .to_http_request();
+        let response =
+            listing_response(&PaginationQuery::default(), &req, vec![details(3, true)], 1);
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value =
+            serde_json::from_slice(&to_bytes(response.into_body()).await.unwrap()).unwrap();
+        assert_eq!(body["projects"][0]["project"]["frozen"], json!(true));
+    }
 }
@@ -0,0 +1,444 @@
+use crate::app_data::AppData;
+use crate::common::access::ensure_visible;
+use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::database::repositories::{
+    group_component_implementation_details_repository, group_deliverable_selections_repository,
+    group_deliverables_components_repository, group_deliverables_repository, groups_repository,
+    projects_repository, student_deliverable_selections_repository,
+    student_deliverables_components_repository, student_deliverables_repository,
+};
+use crate::jwt::get_user::LoggedUser;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Path};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Arc;
+use utoipa::ToSchema;
+use welds::state::DbState;
+
+const PROJECT_NOT_FOUND: &str = "Project not found";
+
+/// Deliverable that can be selected at one level of the project
+#[derive(Debug, PartialEq, Serialize, ToSchema)]
+pub(crate) struct DeliverableOption {
+    pub deliverable_id: i32,
+    pub name: String,
+    pub selected: bool,
+}
+
+/// Component required by the selected deliverable
+#[derive(Debug, PartialEq, Serialize, ToSchema)]
+pub(crate) struct RequiredComponent {
+    pub component_id: i32,
+    pub name: String,
+    pub quantity: i32,
+    pub weight: i32,
+    pub completed: bool,
+}
+
+/// What has to be done at one level (student or group) of the project
+#[derive(Debug, PartialEq, Serialize, ToSchema)]
+pub(crate) struct LevelRequirements {
+    pub deliverables: Vec<DeliverableOption>,
+    pub selected_deliverable_id: Option<i32>,
+    /// Components of the selected deliverable, empty until one is selected
+    pub components: Vec<RequiredComponent>,
+    /// Sum of the weights of the completed components
+    pub completed_weight: i32,
+    /// Sum of the weights of every component of the selected deliverable
+    pub total_weight: i32,
+    /// A deliverable is selected and all its components are completed
+    pub complete: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ProjectRequirementsResponse {
+    pub project_id: i32,
+    pub student: LevelRequirements,
+    pub group_id: Option<i32>,
+    /// Missing while the student is in no group of the project
+    pub group: Option<LevelRequirements>,
+}
+
+/// Component of a deliverable with its requirements
+#[derive(Debug, Clone)]
+struct ComponentLink {
+    component_id: i32,
+    name: String,
+    quantity: i32,
+    weight: i32,
+}
+
+/// Deliverable of a project with the components it requires
+#[derive(Debug)]
+struct TreeDeliverable {
+    deliverable_id: i32,
+    name: String,
+    components: Vec<ComponentLink>,
+}
+
+impl TreeDeliverable {
+    fn option(&self) -> (i32, String) {
+        (self.deliverable_id, self.name.clone())
+    }
+}
+
+/// Student and group deliverables of a project with their components
+///
+/// The same for every student of the project, so it is kept in `AppData::deliverable_trees`
+/// and only the selections are read on each request.
+#[derive(Debug)]
+pub(crate) struct DeliverableTree {
+    student: Vec<TreeDeliverable>,
+    group: Vec<TreeDeliverable>,
+}
+
+/// Components of `deliverable_id` in `deliverables`, empty when it is not one of them
+fn components_of(deliverables: &[TreeDeliverable], deliverable_id: i32) -> Vec<ComponentLink> {
+    deliverables
+        .iter()
+        .find(|d| d.deliverable_id == deliverable_id)
+        .map(|d| d.components.clone())
+        .unwrap_or_default()
+}
+
+/// Merges the deliverables of a level with the selection made and the components done
+///
+/// `links` are the components of the selected deliverable and `completed` the ones already
+/// done, ignored when nothing is selected.
+fn level_requirements(
+    deliverables: Vec<(i32, String)>, selected: Option<i32>, links: Vec<ComponentLink>,
+    completed: &HashSet<i32>,
+) -> LevelRequirements {
+    let components: Vec<RequiredComponent> = match selected {
+        Some(_) => links
+            .into_iter()
+            .map(|link| RequiredComponent {
+                completed: completed.contains(&link.component_id),
+                component_id: link.component_id,
+                name: link.name,
+                quantity: link.quantity,
+                weight: link.weight,
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let total_weight = components.iter().map(|c| c.weight).sum();
+    let completed_weight = components
+        .iter()
+        .filter(|c| c.completed)
+        .map(|c| c.weight)
+        .sum();
+
+    LevelRequirements {
+        deliverables: deliverables
+            .into_iter()
+            .map(|(deliverable_id, name)| DeliverableOption {
+                selected: selected == Some(deliverable_id),
+                deliverable_id,
+                name,
+            })
+            .collect(),
+        selected_deliverable_id: selected,
+        complete: selected.is_some() && components.iter().all(|c| c.completed),
+        components,
+        completed_weight,
+        total_weight,
+    }
+}
+
+fn db_error(what: String) -> impl FnOnce(welds::WeldsError) -> JsonError {
+    move |e| {
+        error_with_log_id(
+            format!("unable to fetch {}: {}", what, e),
+            "Database error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    }
+}
+
+/// Reads the deliverables of both levels of a project with their components
+async fn load_deliverable_tree(
+    data: &AppData, project_id: i32,
+) -> Result<DeliverableTree, JsonError> {
+    let mut student = Vec::new();
+    let deliverables = student_deliverables_repository::get_by_project_id(&data.db, project_id)
+        .await
+        .map_err(db_error(format!(
+            "student deliverables of project {}",
+            project_id
+        )))?;
+    for deliverable in deliverables.into_iter().map(DbState::into_inner) {
+        let components =
+            student_deliverables_components_repository::get_components_with_details_for_deliverable(
+                &data.db,
+                deliverable.student_deliverable_id,
+            )
+            .await
+            .map_err(db_error(format!(
+                "components of student deliverable {}",
+                deliverable.student_deliverable_id
+            )))?
+            .into_iter()
+            .map(|(link, component)| ComponentLink {
+                component_id: link.student_deliverable_component_id,
+                name: DbState::into_inner(component).name,
+                quantity: link.quantity,
+                weight: link.weight,
+            })
+            .collect();
+        student.push(TreeDeliverable {
+            deliverable_id: deliverable.student_deliverable_id,
+            name: deliverable.name,
+            components,
+        });
+    }
+
+    let mut group = Vec::new();
+    let deliverables = group_deliverables_repository::get_by_project_id(&data.db, project_id)
+        .await
+        .map_err(db_error(format!(
+            "group deliverables of project {}",
+            project_id
+        )))?;
+    for deliverable in deliverables.into_iter().map(DbState::into_inner) {
+        let components =
+            group_deliverables_components_repository::get_components_with_details_for_deliverable(
+                &data.db,
+                deliverable.group_deliverable_id,
+            )
+            .await
+            .map_err(db_error(format!(
+                "components of group deliverable {}",
+                deliverable.group_deliverable_id
+            )))?
+            .into_iter()
+            .map(|(link, component)| ComponentLink {
+                component_id: link.group_deliverable_component_id,
+                name: DbState::into_inner(component).name,
+                quantity: link.quantity,
+                weight: link.weight,
+            })
+            .collect();
+        group.push(TreeDeliverable {
+            deliverable_id: deliverable.group_deliverable_id,
+            name: deliverable.name,
+            components,
+        });
+    }
+
+    Ok(DeliverableTree { student, group })
+}
+
+/// Student level requirements: selecting a deliverable takes on all of its components
+async fn student_requirements(
+    data: &AppData, tree: &DeliverableTree, student_id: i32, project_id: i32,
+) -> Result<LevelRequirements, JsonError> {
+    let selected = student_deliverable_selections_repository::get_by_student_and_project(
+        &data.db, student_id, project_id,
+    )
+    .await
+    .map_err(db_error(format!(
+        "selection of student {} in project {}",
+        student_id, project_id
+    )))?
+    .map(|s| s.student_deliverable_id);
+
+    let links = selected
+        .map(|deliverable_id| components_of(&tree.student, deliverable_id))
+        .unwrap_or_default();
+    let completed = links.iter().map(|l| l.component_id).collect();
+    Ok(level_requirements(
+        tree.student.iter().map(TreeDeliverable::option).collect(),
+        selected,
+        links,
+        &completed,
+    ))
+}
+
+/// Group level requirements: a component is completed once it has implementation details
+async fn group_requirements(
+    data: &AppData, tree: &DeliverableTree, group_id: i32,
+) -> Result<LevelRequirements, JsonError> {
+    let deliverables = tree.group.iter().map(TreeDeliverable::option).collect();
+
+    let selection = group_deliverable_selections_repository::get_by_group_id(&data.db, group_id)
+        .await
+        .map_err(db_error(format!("selection of group {}", group_id)))?
+        .map(DbState::into_inner);
+
+    let Some(selection) = selection else {
+        return Ok(level_requirements(
+            deliverables,
+            None,
+            Vec::new(),
+            &HashSet::new(),
+        ));
+    };
+
+    let implemented = group_component_implementation_details_repository::get_by_selection_id(
+        &data.db,
+        selection.group_deliverable_selection_id,
+    )
+    .await
+    .map_err(db_error(format!(
+        "implementation details of selection {}",
+        selection.group_deliverable_selection_id
+    )))?
+    .iter()
+    .map(|detail| detail.group_deliverable_component_id)
+    .collect();
+
+    Ok(level_requirements(
+        deliverables,
+        Some(selection.group_deliverable_id),
+        components_of(&tree.group, selection.group_deliverable_id),
+        &implemented,
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/students/projects/{id}/requirements",
+    params(("id" = i32, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Deliverables and components required from the student and their group, merged with the selections and completion state", body = ProjectRequirementsResponse),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 404, description = "Project not found or not accessible to the student", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("StudentAuth" = [])),
+    tag = "Projects management",
+)]
+/// Get what the student and their group must complete in a project
+///
+/// Student level components come with the selected student deliverable, group level ones
+/// are completed once the group wrote their implementation details. The group part is
+/// missing while the student is in no group of the project. The deliverables and their
+/// components are cached per project for `deliverable_tree_cache_secs`.
+#[actix_web_grants::protect("ROLE_STUDENT")]
+pub(super) async fn get_project_requirements(
+    req: HttpRequest, path: Path<i32>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let student = req.extensions().get_student().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without a user loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+    let project_id = path.into_inner();
+
+    let visible =
+        projects_repository::is_visible_for_student(&data.db, student.student_id, project_id)
+            .await
+            .map_err(db_error(format!(
+                "access of student {} to project {}",
+                student.student_id, project_id
+            )))?;
+    ensure_visible(visible, PROJECT_NOT_FOUND)?;
+
+    let tree = data
+        .deliverable_trees
+        .get_or_try_load(project_id, || async {
+            load_deliverable_tree(&data, project_id).await.map(Arc::new)
+        })
+        .await?;
+    let student_level = student_requirements(&data, &tree, student.student_id, project_id).await?;
+
+    let group_id =
+        groups_repository::get_student_group_in_project(&data.db, student.student_id, project_id)
+            .await
+            .map_err(db_error(format!(
+                "group of student {} in project {}",
+                student.student_id, project_id
+            )))?
+            .map(|g| g.group_id);
+    let group_level = match group_id {
+        Some(group_id) => Some(group_requirements(&data, &tree, group_id).await?),
+        None => None,
+    };
+
+    Ok(HttpResponse::Ok().json(ProjectRequirementsResponse {
+        project_id,
+        student: student_level,
+        group_id,
+        group: group_level,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(component_id: i32, weight: i32) -> ComponentLink {
+        ComponentLink {
+            component_id,
+            name: format!("Component {}", component_id),
+            quantity: 1,
+            weight,
+        }
+    }
+
+    fn deliverables() -> Vec<(i32, String)> {
+        vec![(1, "Drone".to_string()), (2, "Rover".to_string())]
+    }
+
+    #[test]
+    fn test_partial_group_selection_is_incomplete() {
+        let links = vec![link(10, 60), link(11, 40)];
+
+        let group = level_requirements(deliverables(), Some(2), links, &HashSet::from([10]));
+
+        assert_eq!(
+            group.deliverables,
+            vec![
+                DeliverableOption {
+                    deliverable_id: 1,
+                    name: "Drone".to_string(),
+                    selected: false,
+                },
+                DeliverableOption {
+                    deliverable_id: 2,
+                    name: "Rover".to_string(),
+                    selected: true,
+                },
+            ]
+        );
+        assert_eq!(group.selected_deliverable_id, Some(2));
+        assert_eq!(
+            group
+                .components
+                .iter()
+                .map(|c| (c.component_id, c.completed))
+                .collect::<Vec<_>>(),
+            vec![(10, true), (11, false)]
+        );
+        assert_eq!(group.completed_weight, 60);
+        assert_eq!(group.total_weight, 100);
+        assert!(!group.complete);
+    }
+
+    #[test]
+    fn test_missing_student_selection_requires_a_choice() {
+        let student = level_requirements(deliverables(), None, Vec::new(), &HashSet::new());
+
+        assert!(student.deliverables.iter().all(|d| !d.selected));
+        assert!(student.components.is_empty());
+        assert!(!student.complete);
+    }
+
+    #[test]
+    fn test_selection_with_every_component_done_is_complete() {
+        let links = vec![link(10, 60), link(11, 40)];
+
+        let student = level_requirements(deliverables(), Some(1), links, &HashSet::from([10, 11]));
+
+        assert_eq!(student.completed_weight, 100);
+        assert!(student.complete);
+    }
+}
@@ -1,12 +1,17 @@
 use crate::app_data::AppData;
-use crate::common::json_error::{error_with_log_id, JsonError};
-use crate::database::repositories::{projects_repository, security_codes};
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError, RATE_LIMITED};
+use crate::database::repositories::{
+    projects_repository, security_codes, student_project_access_repository,
+};
 use crate::jwt::get_user::LoggedUser;
+use crate::models::student::Student;
+use actix_web::http::header::{HeaderValue, RETRY_AFTER};
 use actix_web::http::StatusCode;
 use actix_web::web::{Data, Json};
-use actix_web::{HttpMessage, HttpRequest, HttpResponse};
-use chrono::Utc;
+use actix_web::{HttpMessage, HttpRequest, HttpResponse, ResponseError};
+use log::warn;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use utoipa::ToSchema;
 use welds::state::DbState;
 
@@ -18,9 +23,29 @@ pub(crate) struct ValidateCodeRequest {
 #[derive(Debug, Serialize, ToSchema)]
 pub(crate) struct ValidateCodeResponse {
     pub is_valid: bool,
+    /// True when the student had already redeemed a code for this project
+    pub already_redeemed: bool,
     pub project: Option<ProjectInfo>,
 }
 
+impl ValidateCodeResponse {
+    fn invalid() -> Self {
+        Self {
+            is_valid: false,
+            already_redeemed: false,
+            project: None,
+        }
+    }
+
+    fn granted(project: ProjectInfo, newly_granted: bool) -> Self {
+        Self {
+            is_valid: true,
+            already_redeemed: !newly_granted,
+            project: Some(project),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub(crate) struct ProjectInfo {
     pub project_id: i32,
@@ -28,6 +53,31 @@ pub(crate) struct ProjectInfo {
     pub year: i32,
 }
 
+/// Whether the domain of `email` is admitted by the signup domains of the project, every
+/// domain is when the project does not override them
+fn domain_admitted(email: &str, project_domains: Option<&[String]>) -> bool {
+    let Some(domains) = project_domains else {
+        return true;
+    };
+    let Some((_, domain)) = email.rsplit_once('@') else {
+        return false;
+    };
+    domains.iter().any(|d| d.eq_ignore_ascii_case(domain))
+}
+
+/// 429 refusing a validation until the failure window ends, `Retry-After` rounded up
+fn locked_out(retry_after: Duration) -> HttpResponse {
+    let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    let mut response = "Too many invalid security codes, please try again later"
+        .to_json_error(StatusCode::TOO_MANY_REQUESTS)
+        .with_code(RATE_LIMITED)
+        .error_response();
+    response
+        .headers_mut()
+        .insert(RETRY_AFTER, HeaderValue::from(secs.max(1)));
+    response
+}
+
 #[utoipa::path(
     post,
     path = "/v1/students/security-codes/validate",
@@ -35,6 +85,8 @@ pub(crate) struct ProjectInfo {
     responses(
         (status = 200, description = "Security code validation result", body = ValidateCodeResponse),
         (status = 401, description = "Authentication required", body = JsonError),
+        (status = 403, description = "Email domain of the student not in the signup domains of the project", body = JsonError),
+        (status = 429, description = "Too many invalid codes from this address, this student or overall (code RATE_LIMITED), see `Retry-After`", body = JsonError),
         (status = 500, description = "Internal server error", body = JsonError)
     ),
     security(("StudentAuth" = [])),
@@ -43,12 +95,20 @@ pub(crate) struct ProjectInfo {
 /// Validate a security code and return project information
 ///
 /// This endpoint allows students to validate a security code and get information about
-/// the project associated with it. All security codes are for GroupLeader role.
+/// the project associated with it. A valid code grants the student access to the project,
+/// which then shows up in their projects. Redeeming again is an idempotent success.
+/// When the project overrides the signup domains, the email domain of the student must be
+/// one of them.
+/// All security codes are for GroupLeader role.
+///
+/// Invalid codes are counted by client address, by student and overall: past the
+/// configured `code_failures_*` limits validations are refused until the window ends.
+/// A validation counts as soon as it starts and is given back once the code is found valid.
 #[actix_web_grants::protect("ROLE_STUDENT")]
 pub(super) async fn validate_code(
     req: HttpRequest, body: Json<ValidateCodeRequest>, data: Data<AppData>,
 ) -> Result<HttpResponse, JsonError> {
-    let _user = match req.extensions().get_student() {
+    let user = match req.extensions().get_student() {
         Ok(user) => user,
         Err(_) => {
             return Err(error_with_log_id(
@@ -60,8 +120,40 @@ pub(super) async fn validate_code(
         }
     };
 
+    let client = data
+        .trusted_proxies
+        .client_ip(&req)
+        .map_or_else(|| "unknown".to_string(), |ip| ip.to_string());
+    let attempt = match data
+        .code_attempts
+        .reserve(&client, user.student_id, data.clock.now())
+    {
+        Ok(attempt) => attempt,
+        Err(retry_after) => {
+            warn!(
+                "security code validation refused for student {} from {}: too many failures",
+                user.student_id, client
+            );
+            return Ok(locked_out(retry_after));
+        }
+    };
+
+    let outcome = redeem(&data, &user, &body.security_code).await;
+    // Only invalid codes keep counting against the budgets
+    if !matches!(outcome, Ok(None)) {
+        data.code_attempts.release(attempt);
+    }
+
+    let response = outcome?.unwrap_or_else(ValidateCodeResponse::invalid);
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Grants `user` the project of `security_code`, `None` when the code is invalid
+async fn redeem(
+    data: &AppData, user: &Student, security_code: &str,
+) -> Result<Option<ValidateCodeResponse>, JsonError> {
     // Find the security code
-    let security_code_state = security_codes::get_by_code(&data.db, &body.security_code)
+    let security_code_state = security_codes::get_by_code(&data.db, security_code)
         .await
         .map_err(|e| {
             error_with_log_id(
@@ -74,20 +166,12 @@ pub(super) async fn validate_code(
 
     let security_code = match security_code_state {
         Some(state) => DbState::into_inner(state),
-        None => {
-            return Ok(HttpResponse::Ok().json(ValidateCodeResponse {
-                is_valid: false,
-                project: None,
-            }));
-        }
+        None => return Ok(None),
     };
 
     // Check if the security code has expired
-    if security_code.expiration <= Utc::now() {
-        return Ok(HttpResponse::Ok().json(ValidateCodeResponse {
-            is_valid: false,
-            project: None,
-        }));
+    if security_code.is_expired(data.clock.now()) {
+        return Ok(None);
     }
 
     // Get the project information
@@ -105,18 +189,104 @@ pub(super) async fn validate_code(
     let project = match project_state {
         Some(state) => {
             let project_data = DbState::into_inner(state);
-            Some(ProjectInfo {
+            let project_domains = project_data.signup_domain_override();
+            if !domain_admitted(&user.email, project_domains.as_deref()) {
+                return Err("Your email domain is not admitted to this project"
+                    .to_json_error(StatusCode::FORBIDDEN));
+            }
+            ProjectInfo {
                 project_id: project_data.project_id,
                 name: project_data.name,
                 year: project_data.year,
-            })
+            }
         }
-        None => None,
+        None => return Ok(None),
     };
 
+    let newly_granted = student_project_access_repository::grant(
+        &data.db,
+        user.student_id,
+        project.project_id,
+        security_code.security_code_id,
+    )
+    .await
+    .map_err(|e| {
+        error_with_log_id(
+            format!(
+                "unable to grant student {} access to project {}: {}",
+                user.student_id, project.project_id, e
+            ),
+            "Database error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
     // All security codes are for GroupLeader role
-    Ok(HttpResponse::Ok().json(ValidateCodeResponse {
-        is_valid: true,
-        project,
-    }))
+    Ok(Some(ValidateCodeResponse::granted(project, newly_granted)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project_info() -> ProjectInfo {
+        ProjectInfo {
+            project_id: 3,
+            name: "Robotics".to_string(),
+            year: 2026,
+        }
+    }
+
+    #[test]
+    fn test_project_override_admits_an_external_domain() {
+        let project = vec!["studenti.unitn.it".to_string(), "partner.org".to_string()];
+
+        assert!(domain_admitted(
+            "ext.collaborator@Partner.org",
+            Some(&project)
+        ));
+        assert!(domain_admitted(
+            "mario.rossi@studenti.unitn.it",
+            Some(&project)
+        ));
+        assert!(!domain_admitted("someone@elsewhere.org", Some(&project)));
+    }
+
+    #[test]
+    fn test_codes_of_projects_without_override_are_open_to_every_domain() {
+        assert!(domain_admitted("ext.collaborator@partner.org", None));
+        assert!(domain_admitted("mario.rossi@studenti.unitn.it", None));
+    }
+
+    #[actix_web::test]
+    async fn test_lockout_is_refused_with_retry_after() {
+        let response = locked_out(Duration::from_millis(90_500));
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers().get(RETRY_AFTER).unwrap(), "91");
+        let body = actix_web::body::to_bytes(response.into_body())
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], RATE_LIMITED);
+    }
+
+    #[test]
+    fn test_first_redemption_grants_project() {
+        let response = ValidateCodeResponse::granted(project_info(), true);
+
+        assert!(response.is_valid);
+        assert!(!response.already_redeemed);
+        assert_eq!(response.project.unwrap().project_id, 3);
+    }
+
+    #[test]
+    fn test_repeated_redemption_is_idempotent_success() {
+        let response = ValidateCodeResponse::granted(project_info(), false);
+
+        assert!(response.is_valid);
+        assert!(response.already_redeemed);
+        assert_eq!(response.project.unwrap().project_id, 3);
+    }
 }
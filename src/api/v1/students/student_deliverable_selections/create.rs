@@ -1,5 +1,6 @@
 use crate::app_data::AppData;
 use crate::common::json_error::{error_with_log_id, error_with_log_id_and_payload, JsonError};
+use crate::common::project_freeze::ensure_not_frozen;
 use crate::database::repositories::{
     groups_repository, projects_repository, student_deliverable_selections_repository,
     student_deliverables_repository,
@@ -37,7 +38,7 @@ pub(crate) struct CreateStudentDeliverableSelectionResponse {
         (status = 400, description = "Invalid request or deadline passed", body = JsonError),
         (status = 403, description = "Student not in a group for this project", body = JsonError),
         (status = 404, description = "Deliverable or project not found", body = JsonError),
-        (status = 409, description = "Student already has a selection for this project", body = JsonError),
+        (status = 409, description = "Student already has a selection for this project, or the project is frozen (code PROJECT_FROZEN)", body = JsonError),
         (status = 500, description = "Internal server error", body = JsonError)
     ),
     security(("StudentAuth" = [])),
@@ -168,9 +169,10 @@ pub(in crate::api::v1) async fn create_student_deliverable_selection(
             )
         })
         .map(DbState::into_inner)?;
+    ensure_not_frozen(project.frozen)?;
 
     if let Some(deadline) = project.deliverable_selection_deadline {
-        if Utc::now() > deadline {
+        if data.clock.now() > deadline {
             return Err(error_with_log_id(
                 format!(
                     "Deliverable selection deadline {} has passed for project {}",
@@ -1,5 +1,6 @@
 use crate::app_data::AppData;
 use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::common::project_freeze::ensure_project_not_frozen;
 use crate::database::repositories::student_deliverable_selections_repository;
 use crate::jwt::get_user::LoggedUser;
 use actix_web::http::StatusCode;
@@ -19,6 +20,7 @@ pub(crate) struct DeleteStudentDeliverableSelectionResponse {
     responses(
         (status = 200, description = "Selection deleted successfully", body = DeleteStudentDeliverableSelectionResponse),
         (status = 404, description = "No selection found to delete", body = JsonError),
+        (status = 409, description = "The project is frozen (code PROJECT_FROZEN)", body = JsonError),
         (status = 500, description = "Internal server error", body = JsonError)
     ),
     security(("StudentAuth" = [])),
@@ -69,6 +71,8 @@ pub(in crate::api::v1) async fn delete_student_deliverable_selection(
         ));
     }
 
+    ensure_project_not_frozen(&data.db, project_id).await?;
+
     // Delete the selection
     student_deliverable_selections_repository::delete_by_student_and_project(
         &data.db,
@@ -2,17 +2,25 @@ use crate::api::v1::students::student_deliverable_selections::create::create_stu
 use crate::api::v1::students::student_deliverable_selections::delete::delete_student_deliverable_selection;
 use crate::api::v1::students::student_deliverable_selections::read::get_student_deliverable_selection;
 use crate::api::v1::students::student_deliverable_selections::update::update_student_deliverable_selection;
+use crate::api::v1::students::student_deliverable_selections::update_batch::update_student_deliverable_selections_batch;
+use crate::database::unit_of_work::UnitOfWorkMiddleware;
 use actix_web::{web, Scope};
 
 pub(crate) mod create;
 pub(crate) mod delete;
 pub(crate) mod read;
 pub(crate) mod update;
+pub(crate) mod update_batch;
 
 pub(super) fn student_deliverable_selections_scope() -> Scope {
     web::scope("/deliverable-selection")
         .route("", web::post().to(create_student_deliverable_selection))
         .route("", web::patch().to(update_student_deliverable_selection))
+        .service(
+            web::resource("/batch")
+                .wrap(UnitOfWorkMiddleware)
+                .route(web::patch().to(update_student_deliverable_selections_batch)),
+        )
         .route(
             "/project/{project_id}",
             web::get().to(get_student_deliverable_selection),
@@ -0,0 +1,396 @@
+use crate::app_data::AppData;
+use crate::common::batch_result::BatchResult;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::repositories::{
+    projects_repository, student_deliverable_selections_repository, student_deliverables_repository,
+};
+use crate::database::unit_of_work::UnitOfWork;
+use crate::jwt::get_user::LoggedUser;
+use crate::models::project::Project;
+use crate::models::student_deliverable::StudentDeliverable;
+use crate::models::student_deliverable_selection::StudentDeliverableSelection;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Json};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use utoipa::ToSchema;
+use welds::state::DbState;
+
+/// Upper bound on the number of selections accepted in a single batch
+const MAX_BATCH_SIZE: usize = 50;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct SelectionChange {
+    /// Selection to update, must belong to the caller
+    #[schema(example = 3)]
+    pub student_deliverable_selection_id: i32,
+    /// New deliverable, must belong to the same project as the current one
+    #[schema(example = 9)]
+    pub student_deliverable_id: i32,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct BatchUpdateSelectionsRequest {
+    pub changes: Vec<SelectionChange>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct UpdatedSelection {
+    #[schema(example = 3)]
+    pub student_deliverable_selection_id: i32,
+    /// Deliverable now selected
+    #[schema(example = 9)]
+    pub student_deliverable_id: i32,
+}
+
+/// Checks that every requested selection exists and belongs to `student_id`
+///
+/// A single foreign selection rejects the whole batch.
+fn ensure_owned(
+    student_id: i32, changes: &[SelectionChange],
+    selections: &HashMap<i32, StudentDeliverableSelection>,
+) -> Result<(), JsonError> {
+    for change in changes {
+        let id = change.student_deliverable_selection_id;
+        match selections.get(&id) {
+            None => {
+                return Err(
+                    format!("Selection {} not found", id).to_json_error(StatusCode::NOT_FOUND)
+                );
+            }
+            Some(selection) if selection.student_id != student_id => {
+                return Err(error_with_log_id(
+                    format!(
+                        "student {} tried to update selection {} of student {}",
+                        student_id, id, selection.student_id
+                    ),
+                    format!("Selection {} belongs to another student", id),
+                    StatusCode::FORBIDDEN,
+                    log::Level::Warn,
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Decides whether a single change can be applied
+///
+/// # Arguments
+/// * `current_project` - Project of the currently selected deliverable
+/// * `target_project` - Project of the requested deliverable, `None` when it does not exist
+/// * `deadline` - Deliverable selection deadline of the project
+/// * `now` - Time the batch is processed at
+fn check_change(
+    current_project: i32, target_project: Option<i32>, deadline: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Result<(), &'static str> {
+    match target_project {
+        None => return Err("Deliverable not found"),
+        Some(project_id) if project_id != current_project => {
+            return Err("Deliverable does not belong to the selection's project");
+        }
+        Some(_) => {}
+    }
+
+    if deadline.is_some_and(|d| now > d) {
+        return Err("Deliverable selection deadline has passed");
+    }
+
+    Ok(())
+}
+
+/// Reports the outcome of every requested change, in request order
+fn batch_result(
+    changes: &[SelectionChange], outcomes: Vec<Result<(), &'static str>>,
+) -> BatchResult<UpdatedSelection> {
+    changes
+        .iter()
+        .zip(outcomes)
+        .map(|(change, outcome)| {
+            outcome
+                .map(|()| UpdatedSelection {
+                    student_deliverable_selection_id: change.student_deliverable_selection_id,
+                    student_deliverable_id: change.student_deliverable_id,
+                })
+                .map_err(str::to_string)
+        })
+        .collect()
+}
+
+#[utoipa::path(
+    patch,
+    path = "/v1/students/deliverable-selection/batch",
+    request_body = BatchUpdateSelectionsRequest,
+    responses(
+        (status = 200, description = "Every selection updated", body = BatchResult<UpdatedSelection>),
+        (status = 207, description = "Some selections updated, see the failed ones", body = BatchResult<UpdatedSelection>),
+        (status = 400, description = "Empty or oversized batch", body = JsonError),
+        (status = 403, description = "A selection belongs to another student", body = JsonError),
+        (status = 404, description = "A selection does not exist", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError)
+    ),
+    security(("StudentAuth" = [])),
+    tag = "Student Deliverable Selections",
+)]
+/// Update several student deliverable selections at once
+///
+/// Every selection must belong to the caller, otherwise the whole batch is rejected.
+/// Each change is then validated like a single update (same project, deadline not passed),
+/// valid changes are applied together in one statement of the request's transaction and
+/// invalid ones are listed as failed with their index in `changes`.
+#[actix_web_grants::protect("ROLE_STUDENT")]
+pub(in crate::api::v1) async fn update_student_deliverable_selections_batch(
+    req: HttpRequest, body: Json<BatchUpdateSelectionsRequest>, data: Data<AppData>,
+    unit_of_work: UnitOfWork,
+) -> Result<HttpResponse, JsonError> {
+    let user = req.extensions().get_student().map_err(|_| {
+        error_with_log_id(
+            "entered a protected route without a user loaded in the request",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let changes = body.into_inner().changes;
+
+    if changes.is_empty() {
+        return Err("At least one change is required".to_json_error(StatusCode::BAD_REQUEST));
+    }
+
+    if changes.len() > MAX_BATCH_SIZE {
+        return Err(format!(
+            "At most {} selections can be updated at once",
+            MAX_BATCH_SIZE
+        )
+        .to_json_error(StatusCode::BAD_REQUEST));
+    }
+
+    let selection_ids: Vec<i32> = changes
+        .iter()
+        .map(|c| c.student_deliverable_selection_id)
+        .collect();
+
+    let selections: HashMap<i32, StudentDeliverableSelection> =
+        student_deliverable_selections_repository::get_by_ids(&unit_of_work, &selection_ids)
+            .await
+            .map_err(|e| {
+                error_with_log_id(
+                    format!("unable to fetch selections {:?}: {}", selection_ids, e),
+                    "Database error",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                )
+            })?
+            .into_iter()
+            .map(DbState::into_inner)
+            .map(|s| (s.student_deliverable_selection_id, s))
+            .collect();
+
+    ensure_owned(user.student_id, &changes, &selections)?;
+
+    let deliverable_ids: Vec<i32> = changes
+        .iter()
+        .map(|c| c.student_deliverable_id)
+        .chain(selections.values().map(|s| s.student_deliverable_id))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+
+    let deliverables: HashMap<i32, StudentDeliverable> =
+        student_deliverables_repository::get_by_ids(&unit_of_work, &deliverable_ids)
+            .await
+            .map_err(|e| {
+                error_with_log_id(
+                    format!("unable to fetch deliverables {:?}: {}", deliverable_ids, e),
+                    "Database error",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                )
+            })?
+            .into_iter()
+            .map(DbState::into_inner)
+            .map(|d| (d.student_deliverable_id, d))
+            .collect();
+
+    let project_ids: Vec<i32> = deliverables
+        .values()
+        .map(|d| d.project_id)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let projects: HashMap<i32, Project> =
+        projects_repository::get_by_ids(&unit_of_work, &project_ids)
+            .await
+            .map_err(|e| {
+                error_with_log_id(
+                    format!("unable to fetch projects {:?}: {}", project_ids, e),
+                    "Database error",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                )
+            })?
+            .into_iter()
+            .map(DbState::into_inner)
+            .map(|p| (p.project_id, p))
+            .collect();
+
+    let now = data.clock.now();
+    let mut seen = HashSet::new();
+    let outcomes: Vec<Result<(), &'static str>> = changes
+        .iter()
+        .map(|change| {
+            if !seen.insert(change.student_deliverable_selection_id) {
+                return Err("Selection listed more than once");
+            }
+
+            let selection = &selections[&change.student_deliverable_selection_id];
+            let Some(current_project) = deliverables
+                .get(&selection.student_deliverable_id)
+                .map(|d| d.project_id)
+            else {
+                return Err("Current deliverable not found");
+            };
+
+            if projects.get(&current_project).is_some_and(|p| p.frozen) {
+                return Err("Project is frozen");
+            }
+
+            check_change(
+                current_project,
+                deliverables
+                    .get(&change.student_deliverable_id)
+                    .map(|d| d.project_id),
+                projects
+                    .get(&current_project)
+                    .and_then(|p| p.deliverable_selection_deadline),
+                now,
+            )
+        })
+        .collect();
+
+    let to_update: Vec<(i32, i32)> = changes
+        .iter()
+        .zip(&outcomes)
+        .filter(|(_, outcome)| outcome.is_ok())
+        .map(|(change, _)| {
+            (
+                change.student_deliverable_selection_id,
+                change.student_deliverable_id,
+            )
+        })
+        .collect();
+
+    if !to_update.is_empty() {
+        student_deliverable_selections_repository::update_deliverables(
+            &unit_of_work,
+            &to_update,
+            now,
+        )
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!(
+                    "unable to update selections of student {}: {}",
+                    user.student_id, e
+                ),
+                "Failed to update deliverable selections",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+    }
+
+    Ok(batch_result(&changes, outcomes).respond())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::ResponseError;
+
+    fn selection(id: i32, student_id: i32) -> (i32, StudentDeliverableSelection) {
+        (
+            id,
+            StudentDeliverableSelection {
+                student_deliverable_selection_id: id,
+                student_id,
+                student_deliverable_id: 1,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            },
+        )
+    }
+
+    fn change(id: i32, deliverable_id: i32) -> SelectionChange {
+        SelectionChange {
+            student_deliverable_selection_id: id,
+            student_deliverable_id: deliverable_id,
+        }
+    }
+
+    #[test]
+    fn test_valid_batch_is_accepted() {
+        let selections = HashMap::from([selection(1, 10), selection(2, 10)]);
+        let changes = [change(1, 5), change(2, 6)];
+
+        assert!(ensure_owned(10, &changes, &selections).is_ok());
+
+        let now = Utc::now();
+        let deadline = Some(now + chrono::Duration::days(1));
+        assert_eq!(check_change(3, Some(3), deadline, now), Ok(()));
+        assert_eq!(check_change(4, Some(4), None, now), Ok(()));
+    }
+
+    #[test]
+    fn test_batch_with_foreign_selection_is_rejected() {
+        let selections = HashMap::from([selection(1, 10), selection(2, 11)]);
+        let changes = [change(1, 5), change(2, 6)];
+
+        let err = ensure_owned(10, &changes, &selections).unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_invalid_changes_are_reported() {
+        let now = Utc::now();
+
+        assert_eq!(
+            check_change(3, None, None, now),
+            Err("Deliverable not found")
+        );
+        assert_eq!(
+            check_change(3, Some(4), None, now),
+            Err("Deliverable does not belong to the selection's project")
+        );
+        assert_eq!(
+            check_change(3, Some(3), Some(now - chrono::Duration::hours(1)), now),
+            Err("Deliverable selection deadline has passed")
+        );
+    }
+
+    #[test]
+    fn test_mixed_batch_reports_failures_by_index() {
+        let changes = [change(1, 5), change(2, 6), change(3, 7)];
+        let outcomes = vec![Ok(()), Err("Deliverable not found"), Ok(())];
+
+        let result = batch_result(&changes, outcomes);
+
+        assert_eq!(result.status(), StatusCode::MULTI_STATUS);
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({
+                "succeeded": [
+                    {"student_deliverable_selection_id": 1, "student_deliverable_id": 5},
+                    {"student_deliverable_selection_id": 3, "student_deliverable_id": 7}
+                ],
+                "failed": [{"index": 1, "error": "Deliverable not found"}]
+            })
+        );
+    }
+}
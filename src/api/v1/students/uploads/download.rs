@@ -0,0 +1,78 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::repositories::student_uploads_repository;
+use crate::jwt::get_user::LoggedUser;
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Path};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+
+#[utoipa::path(
+    get,
+    path = "/v1/students/uploads/{upload_id}/download",
+    params(
+        ("upload_id" = i32, Path, description = "Upload id")
+    ),
+    responses(
+        (status = 200, description = "ZIP file downloaded", content_type = "application/zip"),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 404, description = "Upload not found", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError),
+    ),
+    security(("StudentAuth" = [])),
+    tag = "Student Uploads",
+)]
+/// Downloads one of the logged student's uploads
+///
+/// Uploads of other students are reported as not found.
+#[actix_web_grants::protect("ROLE_STUDENT")]
+pub(in crate::api::v1) async fn download_own_upload_handler(
+    req: HttpRequest, path: Path<i32>, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let student = req.extensions().get_student().map_err(|_| {
+        error_with_log_id(
+            "entered protected upload download route without loaded student",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let upload_id = path.into_inner();
+    let upload =
+        student_uploads_repository::get_by_id_for_student(&data.db, upload_id, student.student_id)
+            .await
+            .map_err(|e| {
+                error_with_log_id(
+                    format!(
+                        "failed loading upload {} for student {}: {}",
+                        upload_id, student.student_id, e
+                    ),
+                    "Database error",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                )
+            })?
+            .ok_or_else(|| "Upload not found".to_json_error(StatusCode::NOT_FOUND))?;
+
+    let upload_path = upload.as_ref().path.clone();
+    let bytes = tokio::fs::read(&upload_path).await.map_err(|e| {
+        error_with_log_id(
+            format!("failed reading upload file {}: {}", upload_path, e),
+            "Stored upload file not available",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/zip")
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!(
+                "upload_{}.zip",
+                upload_id
+            ))],
+        })
+        .body(bytes))
+}
@@ -0,0 +1,121 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError};
+use crate::database::repositories::student_uploads_repository;
+use crate::jwt::get_user::LoggedUser;
+use crate::models::student_deliverable::StudentDeliverable;
+use crate::models::student_upload::StudentUpload;
+use actix_web::http::StatusCode;
+use actix_web::web::Data;
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct StudentUploadItem {
+    pub upload_id: i32,
+    pub project_id: i32,
+    pub student_deliverable_id: i32,
+    pub deliverable_name: String,
+    pub upload_count: i32,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+    pub timestamp: DateTime<Utc>,
+    /// Route downloading the uploaded ZIP, for the same student only
+    #[schema(example = "/v1/students/uploads/7/download")]
+    pub download_url: String,
+}
+
+impl StudentUploadItem {
+    fn new(upload: StudentUpload, deliverable: StudentDeliverable) -> Self {
+        Self {
+            upload_id: upload.upload_id,
+            project_id: deliverable.project_id,
+            student_deliverable_id: deliverable.student_deliverable_id,
+            deliverable_name: deliverable.name,
+            upload_count: upload.upload_count,
+            timestamp: upload.timestamp,
+            download_url: format!("/v1/students/uploads/{}/download", upload.upload_id),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct ListStudentUploadsResponse {
+    pub uploads: Vec<StudentUploadItem>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/v1/students/uploads",
+    responses(
+        (status = 200, description = "Uploads of the student, newest first", body = ListStudentUploadsResponse),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError),
+    ),
+    security(("StudentAuth" = [])),
+    tag = "Student Uploads",
+)]
+/// Lists the uploads of the logged student, across all projects
+#[actix_web_grants::protect("ROLE_STUDENT")]
+pub(in crate::api::v1) async fn list_student_uploads_handler(
+    req: HttpRequest, data: Data<AppData>,
+) -> Result<HttpResponse, JsonError> {
+    let student = req.extensions().get_student().map_err(|_| {
+        error_with_log_id(
+            "entered protected upload list route without loaded student",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let uploads = student_uploads_repository::get_all_by_student(&data.db, student.student_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!(
+                    "failed loading uploads for student {}: {}",
+                    student.student_id, e
+                ),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+    Ok(HttpResponse::Ok().json(ListStudentUploadsResponse {
+        uploads: uploads
+            .into_iter()
+            .map(|(upload, deliverable)| StudentUploadItem::new(upload, deliverable))
+            .collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_links_to_the_guarded_download() {
+        let upload = StudentUpload {
+            upload_id: 7,
+            student_deliverable_selection_id: 3,
+            path: "/uploads/2/5_v2.zip".to_string(),
+            upload_count: 2,
+            version: 2,
+            timestamp: Utc::now(),
+        };
+        let deliverable = StudentDeliverable {
+            student_deliverable_id: 4,
+            project_id: 2,
+            name: "Backend".to_string(),
+        };
+
+        let item = StudentUploadItem::new(upload, deliverable);
+
+        assert_eq!(item.project_id, 2);
+        assert_eq!(item.download_url, "/v1/students/uploads/7/download");
+        // The storage path stays on the server
+        assert!(!serde_json::to_string(&item).unwrap().contains("5_v2.zip"));
+    }
+}
@@ -1,18 +1,33 @@
+use crate::api::v1::students::uploads::download::download_own_upload_handler;
+use crate::api::v1::students::uploads::list::list_student_uploads_handler;
+use crate::api::v1::students::uploads::replace::replace_upload_handler;
 use crate::api::v1::students::uploads::status::get_upload_status_handler;
 use crate::api::v1::students::uploads::upload::upload_project_zip_handler;
+use crate::database::unit_of_work::UnitOfWorkMiddleware;
 use actix_web::{web, Scope};
 
+pub(crate) mod download;
+pub(crate) mod list;
+pub(crate) mod replace;
 pub(crate) mod status;
 pub(crate) mod upload;
 
 pub(super) fn uploads_scope() -> Scope {
     web::scope("")
-        .route(
-            "/projects/{project_id}/upload",
-            web::post().to(upload_project_zip_handler),
+        .service(
+            web::resource("/projects/{project_id}/upload")
+                .wrap(UnitOfWorkMiddleware)
+                .route(web::post().to(upload_project_zip_handler))
+                .route(web::get().to(get_upload_status_handler)),
+        )
+        .route("/uploads", web::get().to(list_student_uploads_handler))
+        .service(
+            web::resource("/uploads/{upload_id}")
+                .wrap(UnitOfWorkMiddleware)
+                .route(web::put().to(replace_upload_handler)),
         )
         .route(
-            "/projects/{project_id}/upload",
-            web::get().to(get_upload_status_handler),
+            "/uploads/{upload_id}/download",
+            web::get().to(download_own_upload_handler),
         )
 }
@@ -0,0 +1,144 @@
+use crate::api::v1::students::uploads::upload::UploadProjectZipResponse;
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::common::project_freeze::ensure_not_frozen;
+use crate::common::upload_scanner::ensure_clean;
+use crate::common::upload_storage::{ensure_upload_open, read_zip_field, store, version_path};
+use crate::database::repositories::{projects_repository, student_uploads_repository};
+use crate::database::unit_of_work::UnitOfWork;
+use crate::jwt::get_user::LoggedUser;
+use actix_multipart::Multipart;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Path};
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use welds::state::DbState;
+
+#[utoipa::path(
+    put,
+    path = "/v1/students/uploads/{upload_id}",
+    params(
+        ("upload_id" = i32, Path, description = "Upload id")
+    ),
+    request_body(content = String, description = "Multipart form-data with file field named 'file'", content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "File stored as the new current version", body = UploadProjectZipResponse),
+        (status = 400, description = "Validation error", body = JsonError),
+        (status = 401, description = "Authentication required", body = JsonError),
+        (status = 403, description = "Upload deadline reached or attempts exhausted", body = JsonError),
+        (status = 404, description = "Upload not found", body = JsonError),
+        (status = 409, description = "The project is frozen (code PROJECT_FROZEN)", body = JsonError),
+        (status = 413, description = "File too large", body = JsonError),
+        (status = 415, description = "Declared file type not allowed", body = JsonError),
+        (status = 422, description = "File content not matching its declared type, or flagged by the content scanner (code UPLOAD_REJECTED)", body = JsonError),
+        (status = 500, description = "Internal server error", body = JsonError),
+        (status = 503, description = "The content scanner is unavailable", body = JsonError),
+    ),
+    security(("StudentAuth" = [])),
+    tag = "Student Uploads",
+)]
+/// Replaces the file of one of the logged student's uploads with a corrected one
+///
+/// The new file becomes the current version and counts as an upload attempt; the
+/// previous versions stay in storage for the professors. The version is claimed before the
+/// file is written, so concurrent replacements never share one.
+#[actix_web_grants::protect("ROLE_STUDENT")]
+pub(in crate::api::v1) async fn replace_upload_handler(
+    req: HttpRequest, path: Path<i32>, mut payload: Multipart, data: Data<AppData>,
+    unit_of_work: UnitOfWork,
+) -> Result<HttpResponse, JsonError> {
+    let student = req.extensions().get_student().map_err(|_| {
+        error_with_log_id(
+            "entered protected upload replace route without loaded student",
+            "Authentication error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let upload_id = path.into_inner();
+    let db_error = |e: welds::WeldsError| {
+        error_with_log_id(
+            format!(
+                "failed loading upload {} of student {}: {}",
+                upload_id, student.student_id, e
+            ),
+            "Database error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    };
+
+    let upload =
+        student_uploads_repository::get_by_id_for_student(&data.db, upload_id, student.student_id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| "Upload not found".to_json_error(StatusCode::NOT_FOUND))?;
+
+    let project_id = student_uploads_repository::get_project_id(
+        &data.db,
+        upload.as_ref().student_deliverable_selection_id,
+    )
+    .await
+    .map_err(db_error)?
+    .ok_or_else(|| "Upload not found".to_json_error(StatusCode::NOT_FOUND))?;
+    let project = projects_repository::get_by_id(&data.db, project_id)
+        .await
+        .map_err(db_error)?
+        .map(DbState::into_inner)
+        .ok_or_else(|| "Project not found".to_json_error(StatusCode::NOT_FOUND))?;
+
+    ensure_not_frozen(project.frozen)?;
+    ensure_upload_open(project.upload_deadline, data.clock.now())?;
+    if upload.as_ref().upload_count >= project.max_student_uploads {
+        return Err("Upload attempts exhausted".to_json_error(StatusCode::FORBIDDEN));
+    }
+
+    let file_bytes = read_zip_field(&mut payload, data.config.max_upload_size_bytes()).await?;
+    ensure_clean(data.upload_scanner.as_ref(), &file_bytes).await?;
+    let version = student_uploads_repository::claim_version(
+        &unit_of_work,
+        upload_id,
+        project.max_student_uploads,
+    )
+    .await
+    .map_err(|e| {
+        error_with_log_id(
+            format!("failed claiming a version of upload {}: {}", upload_id, e),
+            "Database error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?
+    .ok_or_else(|| "Upload attempts exhausted".to_json_error(StatusCode::FORBIDDEN))?;
+    let file_path = version_path(
+        data.config.uploads_dir(),
+        project_id,
+        student.student_id,
+        version,
+    );
+    store(&file_path, &file_bytes).await?;
+
+    let saved = student_uploads_repository::record_version(
+        &unit_of_work,
+        upload_id,
+        version,
+        file_path,
+        data.clock.now(),
+    )
+    .await
+    .map_err(|e| {
+        error_with_log_id(
+            format!("failed replacing upload {}: {}", upload_id, e),
+            "Failed to store upload metadata",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    Ok(HttpResponse::Ok().json(UploadProjectZipResponse {
+        upload_id: saved.upload_id,
+        version: saved.version,
+        upload_count: saved.upload_count,
+        uploads_remaining: (project.max_student_uploads - saved.upload_count).max(0),
+    }))
+}
@@ -17,7 +17,9 @@ pub(crate) struct StudentUploadStatusResponse {
     pub upload_id: Option<i32>,
     pub upload_count: i32,
     pub uploads_remaining: i32,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339_opt")]
     pub timestamp: Option<DateTime<Utc>>,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339_opt")]
     pub upload_deadline: Option<DateTime<Utc>>,
 }
 
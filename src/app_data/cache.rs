@@ -0,0 +1,175 @@
+use actix_web::http::header::{CacheControl, CacheDirective};
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// How long the allowed signup domains are kept before being read from config again
+pub(crate) const ALLOWED_DOMAINS_TTL: Duration = Duration::from_secs(3600);
+/// How long the admin role list is kept before being read from the database again
+pub(crate) const ADMIN_ROLES_TTL: Duration = Duration::from_secs(3600);
+
+struct CacheEntry<T> {
+    value: T,
+    loaded_at: Instant,
+}
+
+/// Single value in-memory cache with a time to live, shared between workers
+///
+/// Meant for rarely changing reads (config derived lists, lookup tables).
+/// Call `invalidate` after the underlying data changes to force a reload.
+#[derive(Clone)]
+pub(crate) struct TtlCache<T: Clone> {
+    entry: Arc<RwLock<Option<CacheEntry<T>>>>,
+    ttl: Duration,
+}
+
+impl<T: Clone> TtlCache<T> {
+    /// Creates an empty cache, the first read will call the loader
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            entry: Arc::new(RwLock::new(None)),
+            ttl,
+        }
+    }
+
+    /// Creates a cache already holding `value`
+    pub(crate) fn with_value(ttl: Duration, value: T) -> Self {
+        let cache = Self::new(ttl);
+        cache.set(value);
+        cache
+    }
+
+    /// Returns the cached value if present and not expired
+    pub(crate) fn get(&self) -> Option<T> {
+        let guard = self.entry.read().unwrap_or_else(|e| e.into_inner());
+        guard
+            .as_ref()
+            .filter(|entry| entry.loaded_at.elapsed() < self.ttl)
+            .map(|entry| entry.value.clone())
+    }
+
+    /// Replaces the cached value and restarts its time to live
+    pub(crate) fn set(&self, value: T) {
+        let mut guard = self.entry.write().unwrap_or_else(|e| e.into_inner());
+        *guard = Some(CacheEntry {
+            value,
+            loaded_at: Instant::now(),
+        });
+    }
+
+    /// Drops the cached value so the next read goes to the source
+    pub(crate) fn invalidate(&self) {
+        let mut guard = self.entry.write().unwrap_or_else(|e| e.into_inner());
+        *guard = None;
+    }
+
+    /// Returns the cached value, calling `loader` only when it is missing or expired
+    ///
+    /// Errors from the loader are returned as is and nothing is cached.
+    pub(crate) async fn get_or_try_load<E, F, Fut>(&self, loader: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        if let Some(value) = self.get() {
+            return Ok(value);
+        }
+
+        let value = loader().await?;
+        self.set(value.clone());
+        Ok(value)
+    }
+
+    /// `Cache-Control` header letting clients cache the value for the same time to live
+    pub(crate) fn cache_control(&self) -> CacheControl {
+        CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(self.ttl.as_secs() as u32),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::header;
+    use actix_web::HttpResponse;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    async fn load_counting(
+        cache: &TtlCache<Vec<String>>, calls: &AtomicUsize,
+    ) -> Result<Vec<String>, ()> {
+        cache
+            .get_or_try_load(|| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok(vec!["test.com".to_string()])
+            })
+            .await
+    }
+
+    #[actix_web::test]
+    async fn test_repeated_reads_do_not_reload() {
+        let cache = TtlCache::new(Duration::from_secs(60));
+        let calls = AtomicUsize::new(0);
+
+        for _ in 0..3 {
+            let value = load_counting(&cache, &calls).await.unwrap();
+            assert_eq!(value, vec!["test.com".to_string()]);
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[actix_web::test]
+    async fn test_invalidate_forces_reload() {
+        let cache = TtlCache::with_value(Duration::from_secs(60), vec!["old.com".to_string()]);
+        let calls = AtomicUsize::new(0);
+
+        assert_eq!(
+            load_counting(&cache, &calls).await.unwrap(),
+            vec!["old.com".to_string()]
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        cache.invalidate();
+        assert_eq!(
+            load_counting(&cache, &calls).await.unwrap(),
+            vec!["test.com".to_string()]
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[actix_web::test]
+    async fn test_expired_value_is_reloaded() {
+        let cache = TtlCache::with_value(Duration::ZERO, vec!["old.com".to_string()]);
+        let calls = AtomicUsize::new(0);
+
+        assert_eq!(
+            load_counting(&cache, &calls).await.unwrap(),
+            vec!["test.com".to_string()]
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[actix_web::test]
+    async fn test_failed_load_is_not_cached() {
+        let cache: TtlCache<i32> = TtlCache::new(Duration::from_secs(60));
+
+        let result: Result<i32, &str> = cache.get_or_try_load(|| async { Err("db down") }).await;
+        assert!(result.is_err());
+        assert!(cache.get().is_none());
+    }
+
+    #[test]
+    fn test_cache_control_header_is_set() {
+        let cache = TtlCache::with_value(Duration::from_secs(3600), 1);
+        let response = HttpResponse::Ok()
+            .insert_header(cache.cache_control())
+            .finish();
+
+        assert_eq!(
+            response.headers().get(header::CACHE_CONTROL).unwrap(),
+            "public, max-age=3600"
+        );
+    }
+}
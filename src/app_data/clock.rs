@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+#[cfg(test)]
+use std::sync::{Arc, Mutex};
+
+/// Source of the current time for time-dependent logic (token expiry, deadlines, code expiry)
+///
+/// Handlers read the time from `AppData::clock` instead of calling `Utc::now()`,
+/// so tests can pin it with a `MockClock`.
+pub(crate) trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Wall clock used in production
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Clock that only moves when told to, shared between its clones
+#[cfg(test)]
+#[derive(Debug, Clone)]
+pub(crate) struct MockClock {
+    now: Arc<Mutex<DateTime<Utc>>>,
+}
+
+#[cfg(test)]
+impl MockClock {
+    pub(crate) fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Arc::new(Mutex::new(now)),
+        }
+    }
+
+    pub(crate) fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    pub(crate) fn advance(&self, by: chrono::Duration) {
+        *self.now.lock().unwrap() += by;
+    }
+}
+
+#[cfg(test)]
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_mock_clock_only_moves_when_told() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+        let clock = MockClock::new(start);
+        let shared = clock.clone();
+
+        assert_eq!(clock.now(), start);
+        shared.advance(Duration::seconds(30));
+        assert_eq!(clock.now(), start + Duration::seconds(30));
+        shared.set(start);
+        assert_eq!(clock.now(), start);
+    }
+}
@@ -0,0 +1,242 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Who failed a validation, each one with its own budget
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Key {
+    Ip(String),
+    Student(i32),
+    Global,
+}
+
+/// Validations of one key counted in the current window
+struct Failures {
+    started_at: DateTime<Utc>,
+    count: u32,
+}
+
+/// Failures allowed per window before validations are refused
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CodeAttemptLimits {
+    pub per_ip: u32,
+    pub per_student: u32,
+    pub global: u32,
+    pub window: Duration,
+}
+
+/// Validation counted against the budgets of its keys until it is released
+#[must_use]
+pub(crate) struct Attempt {
+    keys: [(Key, DateTime<Utc>); 3],
+}
+
+/// Failed security code validations, shared between workers
+///
+/// Failures are counted by client address, by student and for everyone together, each in
+/// a fixed window starting with its first failure. Once a count reaches its limit every
+/// validation it covers is refused until the window ends, the global count locking out
+/// all students. Expired windows are dropped on every access.
+///
+/// A validation is counted as failed when it starts, so that concurrent guesses cannot all
+/// pass the check before any of them is recorded, and released when the code turns out
+/// valid.
+#[derive(Clone)]
+pub(crate) struct CodeAttempts {
+    failures: Arc<Mutex<HashMap<Key, Failures>>>,
+    limits: CodeAttemptLimits,
+}
+
+impl CodeAttempts {
+    pub(crate) fn new(limits: CodeAttemptLimits) -> Self {
+        Self {
+            failures: Arc::new(Mutex::new(HashMap::new())),
+            limits,
+        }
+    }
+
+    fn keys(ip: &str, student_id: i32) -> [Key; 3] {
+        [
+            Key::Ip(ip.to_string()),
+            Key::Student(student_id),
+            Key::Global,
+        ]
+    }
+
+    fn limit(&self, key: &Key) -> u32 {
+        match key {
+            Key::Ip(_) => self.limits.per_ip,
+            Key::Student(_) => self.limits.per_student,
+            Key::Global => self.limits.global,
+        }
+    }
+
+    fn elapsed(started_at: DateTime<Utc>, now: DateTime<Utc>) -> Duration {
+        (now - started_at).to_std().unwrap_or_default()
+    }
+
+    /// Counts a validation of `student_id` from `ip` as failed, or returns the time until
+    /// they can validate again if they are locked out
+    pub(crate) fn reserve(
+        &self, ip: &str, student_id: i32, now: DateTime<Utc>,
+    ) -> Result<Attempt, Duration> {
+        let mut failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+        failures.retain(|_, f| Self::elapsed(f.started_at, now) < self.limits.window);
+
+        let keys = Self::keys(ip, student_id);
+        let retry_after = keys
+            .iter()
+            .filter_map(|key| {
+                let f = failures.get(key)?;
+                (f.count >= self.limit(key))
+                    .then(|| self.limits.window - Self::elapsed(f.started_at, now))
+            })
+            .max();
+        if let Some(retry_after) = retry_after {
+            return Err(retry_after);
+        }
+
+        Ok(Attempt {
+            keys: keys.map(|key| {
+                let f = failures.entry(key.clone()).or_insert(Failures {
+                    started_at: now,
+                    count: 0,
+                });
+                f.count += 1;
+                (key, f.started_at)
+            }),
+        })
+    }
+
+    /// Takes back a validation that did not fail
+    ///
+    /// Windows that ended since the attempt was reserved are left alone.
+    pub(crate) fn release(&self, attempt: Attempt) {
+        let mut failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+
+        for (key, started_at) in attempt.keys {
+            if let Some(f) = failures.get_mut(&key) {
+                if f.started_at == started_at {
+                    f.count = f.count.saturating_sub(1);
+                }
+                if f.count == 0 {
+                    failures.remove(&key);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn attempts(global: u32) -> CodeAttempts {
+        CodeAttempts::new(CodeAttemptLimits {
+            per_ip: 4,
+            per_student: 3,
+            global,
+            window: Duration::from_secs(600),
+        })
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_burst_of_failures_locks_the_student_out() {
+        let attempts = attempts(100);
+        let now = now();
+
+        for _ in 0..3 {
+            assert!(attempts.reserve("192.0.2.1", 5, now).is_ok());
+        }
+
+        let later = now + chrono::Duration::seconds(100);
+        assert_eq!(
+            attempts.reserve("192.0.2.1", 5, later).err(),
+            Some(Duration::from_secs(500))
+        );
+        // Switching address does not help, other students are unaffected
+        assert!(attempts.reserve("192.0.2.9", 5, later).is_err());
+        assert!(attempts.reserve("192.0.2.9", 6, later).is_ok());
+    }
+
+    #[test]
+    fn test_concurrent_guesses_are_counted_before_they_finish() {
+        let attempts = attempts(100);
+        let now = now();
+
+        // None of the guesses has been answered yet, the fourth is refused anyway
+        let pending: Vec<_> = (0..3)
+            .map(|_| attempts.reserve("192.0.2.1", 5, now).unwrap())
+            .collect();
+        assert!(attempts.reserve("192.0.2.1", 5, now).is_err());
+
+        // Valid codes give their attempt back
+        pending.into_iter().for_each(|a| attempts.release(a));
+        assert!(attempts.reserve("192.0.2.1", 5, now).is_ok());
+    }
+
+    #[test]
+    fn test_failures_across_students_lock_the_address_out() {
+        let attempts = attempts(100);
+        let now = now();
+
+        for student_id in 1..=4 {
+            assert!(attempts.reserve("192.0.2.1", student_id, now).is_ok());
+        }
+
+        assert!(attempts.reserve("192.0.2.1", 10, now).is_err());
+        assert!(attempts.reserve("192.0.2.2", 10, now).is_ok());
+    }
+
+    #[test]
+    fn test_global_failures_lock_everyone_out() {
+        let attempts = attempts(6);
+        let now = now();
+
+        for student_id in 1..=6 {
+            let ip = format!("192.0.2.{}", student_id);
+            assert!(attempts.reserve(&ip, student_id, now).is_ok());
+        }
+
+        assert!(attempts.reserve("198.51.100.1", 99, now).is_err());
+    }
+
+    #[test]
+    fn test_validation_is_allowed_again_after_the_window() {
+        let attempts = attempts(100);
+        let now = now();
+
+        for _ in 0..3 {
+            assert!(attempts.reserve("192.0.2.1", 5, now).is_ok());
+        }
+        assert!(attempts.reserve("192.0.2.1", 5, now).is_err());
+
+        // The budget starts over
+        let elapsed = now + chrono::Duration::seconds(600);
+        let attempt = attempts.reserve("192.0.2.1", 5, elapsed).unwrap();
+        assert!(attempts.reserve("192.0.2.1", 5, elapsed).is_ok());
+        attempts.release(attempt);
+        assert!(attempts.reserve("192.0.2.1", 5, elapsed).is_ok());
+    }
+
+    #[test]
+    fn test_release_leaves_a_later_window_alone() {
+        let attempts = attempts(100);
+        let now = now();
+
+        let stale = attempts.reserve("192.0.2.1", 5, now).unwrap();
+        let later = now + chrono::Duration::seconds(700);
+        for _ in 0..3 {
+            assert!(attempts.reserve("192.0.2.1", 5, later).is_ok());
+        }
+
+        attempts.release(stale);
+        assert!(attempts.reserve("192.0.2.1", 5, later).is_err());
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Last time an email was sent to each address, shared between workers
+///
+/// An address that received an email within the cooldown cannot receive another one.
+/// Addresses are compared case-insensitively and expired entries are dropped on every access.
+#[derive(Clone)]
+pub(crate) struct EmailCooldowns {
+    last_sent: Arc<Mutex<HashMap<String, Instant>>>,
+    cooldown: Duration,
+}
+
+impl EmailCooldowns {
+    pub(crate) fn new(cooldown: Duration) -> Self {
+        Self {
+            last_sent: Arc::new(Mutex::new(HashMap::new())),
+            cooldown,
+        }
+    }
+
+    /// Records a send to `email`, returning false if one happened within the cooldown
+    pub(crate) fn try_acquire(&self, email: &str) -> bool {
+        self.try_acquire_at(email, Instant::now())
+    }
+
+    /// Forgets the last send to `email`, when it did not go through
+    pub(crate) fn release(&self, email: &str) {
+        let mut last_sent = self.last_sent.lock().unwrap_or_else(|e| e.into_inner());
+        last_sent.remove(&email.to_lowercase());
+    }
+
+    fn try_acquire_at(&self, email: &str, now: Instant) -> bool {
+        let mut last_sent = self.last_sent.lock().unwrap_or_else(|e| e.into_inner());
+        last_sent.retain(|_, sent_at| now.duration_since(*sent_at) < self.cooldown);
+
+        let key = email.to_lowercase();
+        if last_sent.contains_key(&key) {
+            return false;
+        }
+        last_sent.insert(key, now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_second_request_within_cooldown_is_suppressed() {
+        let cooldowns = EmailCooldowns::new(Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert!(cooldowns.try_acquire_at("mario.rossi@studenti.unitn.it", now));
+        assert!(!cooldowns.try_acquire_at(
+            "Mario.Rossi@studenti.unitn.it",
+            now + Duration::from_secs(30)
+        ));
+        // Other addresses are unaffected
+        assert!(cooldowns.try_acquire_at("anna.bianchi@studenti.unitn.it", now));
+    }
+
+    #[test]
+    fn test_request_after_cooldown_is_sent() {
+        let cooldowns = EmailCooldowns::new(Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert!(cooldowns.try_acquire_at("mario.rossi@studenti.unitn.it", now));
+        assert!(cooldowns.try_acquire_at(
+            "mario.rossi@studenti.unitn.it",
+            now + Duration::from_secs(60)
+        ));
+    }
+
+    #[test]
+    fn test_released_email_can_be_sent_again() {
+        let cooldowns = EmailCooldowns::new(Duration::from_secs(60));
+
+        assert!(cooldowns.try_acquire("mario.rossi@studenti.unitn.it"));
+        cooldowns.release("mario.rossi@studenti.unitn.it");
+        assert!(cooldowns.try_acquire("mario.rossi@studenti.unitn.it"));
+    }
+}
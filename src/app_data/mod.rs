@@ -1,5 +1,28 @@
+pub(crate) mod cache;
+pub(crate) mod clock;
+pub(crate) mod code_attempts;
+pub(crate) mod email_cooldowns;
+pub(crate) mod name_reservations;
+pub(crate) mod project_cache;
+
+use crate::api::health::DatabaseStatus;
+use crate::api::v1::students::projects::requirements::DeliverableTree;
+use crate::app_data::cache::{TtlCache, ADMIN_ROLES_TTL, ALLOWED_DOMAINS_TTL};
+use crate::app_data::clock::{Clock, SystemClock};
+use crate::app_data::code_attempts::{CodeAttemptLimits, CodeAttempts};
+use crate::app_data::email_cooldowns::EmailCooldowns;
+use crate::app_data::name_reservations::NameReservations;
+use crate::app_data::project_cache::ProjectCache;
+use crate::common::client_ip::{IpRanges, TrustedProxies};
+use crate::common::pagination::PageLimits;
+use crate::common::rate_limit::RateLimiter;
+use crate::common::upload_scanner::{self, UploadScanner};
 use crate::config::Config;
+use crate::database::availability::{DbAvailability, DB_RETRY_AFTER};
 use crate::mail::Mailer;
+use crate::models::admin_role::AdminRole;
+use std::sync::Arc;
+use std::time::Duration;
 use welds::connections::postgres::PostgresClient;
 
 #[derive(Clone)]
@@ -7,10 +30,85 @@ pub(crate) struct AppData {
     pub(crate) config: Config,
     pub(crate) db: PostgresClient,
     pub(crate) mailer: Mailer,
+    /// Email domains accepted at signup, seeded from config at startup
+    pub(crate) allowed_domains_cache: TtlCache<Vec<String>>,
+    /// Admin roles as stored in the database, loaded on first use
+    pub(crate) admin_roles_cache: TtlCache<Vec<AdminRole>>,
+    /// Group names students reserved while checking availability
+    pub(crate) group_name_reservations: NameReservations,
+    /// Addresses that recently received a password reset email
+    pub(crate) reset_email_cooldowns: EmailCooldowns,
+    /// Requests each client address made to the auth routes in the current window
+    pub(crate) auth_rate_limiter: RateLimiter,
+    /// Failed security code validations by address, by student and overall
+    pub(crate) code_attempts: CodeAttempts,
+    /// Deliverables and components of each project, as read by the requirements endpoint
+    pub(crate) deliverable_trees: ProjectCache<Arc<DeliverableTree>>,
+    /// Last database check result of the health endpoint
+    pub(crate) health_cache: TtlCache<DatabaseStatus>,
+    /// Proxies whose forwarded client address is trusted, set from the checked config at startup
+    pub(crate) trusted_proxies: TrustedProxies,
+    /// Client addresses allowed on the admin routes, empty allows any
+    pub(crate) admin_ip_allowlist: IpRanges,
+    /// Page size bounds of list endpoints, from the config
+    pub(crate) page_limits: PageLimits,
+    /// Whether the database answers, requests are turned away with a 503 while it does not
+    pub(crate) db_availability: DbAvailability,
+    /// Current time for expiry and deadline checks, replaced by a mock in tests
+    pub(crate) clock: Arc<dyn Clock>,
+    /// Check run on uploaded files before they are stored, picked from the config
+    pub(crate) upload_scanner: Arc<dyn UploadScanner>,
 }
 
 impl AppData {
     pub(crate) async fn new(config: Config, db: PostgresClient, mailer: Mailer) -> Self {
-        Self { db, config, mailer }
+        let allowed_domains_cache =
+            TtlCache::with_value(ALLOWED_DOMAINS_TTL, config.allowed_signup_domains().clone());
+        let group_name_reservations =
+            NameReservations::new(Duration::from_secs(config.group_name_reservation_seconds()));
+        let reset_email_cooldowns =
+            EmailCooldowns::new(Duration::from_secs(config.reset_email_cooldown_secs()));
+        let auth_rate_limiter = RateLimiter::new(
+            config.auth_rate_limit(),
+            Duration::from_secs(config.auth_rate_limit_window_secs()),
+        );
+        let code_attempts = CodeAttempts::new(CodeAttemptLimits {
+            per_ip: config.code_failures_per_ip(),
+            per_student: config.code_failures_per_student(),
+            global: config.code_failures_global(),
+            window: Duration::from_secs(config.code_failures_window_secs()),
+        });
+        let health_cache = TtlCache::new(Duration::from_millis(config.health_cache_ms()));
+        let deliverable_trees = ProjectCache::new(
+            "deliverable_trees",
+            Duration::from_secs(config.deliverable_tree_cache_secs()),
+        );
+        let page_limits = PageLimits {
+            default_per_page: config.default_per_page(),
+            max_per_page: config.max_per_page(),
+        };
+
+        let db_availability = DbAvailability::new(Arc::new(db.clone()), DB_RETRY_AFTER);
+        let upload_scanner = upload_scanner::from_config(&config);
+
+        Self {
+            db,
+            config,
+            mailer,
+            allowed_domains_cache,
+            admin_roles_cache: TtlCache::new(ADMIN_ROLES_TTL),
+            group_name_reservations,
+            reset_email_cooldowns,
+            auth_rate_limiter,
+            code_attempts,
+            deliverable_trees,
+            health_cache,
+            trusted_proxies: TrustedProxies::default(),
+            admin_ip_allowlist: IpRanges::default(),
+            page_limits,
+            db_availability,
+            clock: Arc::new(SystemClock),
+            upload_scanner,
+        }
     }
 }
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+struct Reservation {
+    token: String,
+    student_id: i32,
+    expires_at: Instant,
+}
+
+/// Reservation held by someone else, returned when a name cannot be reserved or used
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct NameReserved;
+
+/// Short-lived group name reservations, keyed by project and name, shared between workers
+///
+/// A student reserving a name gets a token that `create_group` accepts for the
+/// reservation window. Expired reservations are dropped on every access.
+#[derive(Clone)]
+pub(crate) struct NameReservations {
+    entries: Arc<Mutex<HashMap<(i32, String), Reservation>>>,
+    ttl: Duration,
+}
+
+impl NameReservations {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            ttl,
+        }
+    }
+
+    /// How long a reservation stays valid
+    pub(crate) fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Reserves `name` in `project_id` for `student_id`, returning the reservation token
+    ///
+    /// A student reserving a name they already hold gets the same token with a renewed window.
+    pub(crate) fn reserve(
+        &self, project_id: i32, name: &str, student_id: i32,
+    ) -> Result<String, NameReserved> {
+        self.reserve_at(project_id, name, student_id, Instant::now())
+    }
+
+    /// Checks that `student_id` can create a group named `name` in `project_id`
+    ///
+    /// Names nobody reserved are free, a reserved name requires the holder's token.
+    pub(crate) fn check(
+        &self, project_id: i32, name: &str, student_id: i32, token: Option<&str>,
+    ) -> Result<(), NameReserved> {
+        self.check_at(project_id, name, student_id, token, Instant::now())
+    }
+
+    /// Drops the reservation of `name`, once the group has been created
+    pub(crate) fn release(&self, project_id: i32, name: &str) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.remove(&(project_id, name.to_string()));
+    }
+
+    fn reserve_at(
+        &self, project_id: i32, name: &str, student_id: i32, now: Instant,
+    ) -> Result<String, NameReserved> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|_, r| r.expires_at > now);
+
+        let key = (project_id, name.to_string());
+        let token = match entries.get(&key) {
+            Some(r) if r.student_id != student_id => return Err(NameReserved),
+            Some(r) => r.token.clone(),
+            None => Uuid::new_v4().to_string(),
+        };
+
+        entries.insert(
+            key,
+            Reservation {
+                token: token.clone(),
+                student_id,
+                expires_at: now + self.ttl,
+            },
+        );
+        Ok(token)
+    }
+
+    fn check_at(
+        &self, project_id: i32, name: &str, student_id: i32, token: Option<&str>, now: Instant,
+    ) -> Result<(), NameReserved> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|_, r| r.expires_at > now);
+
+        match entries.get(&(project_id, name.to_string())) {
+            None => Ok(()),
+            Some(r) if r.student_id == student_id && token == Some(r.token.as_str()) => Ok(()),
+            Some(_) => Err(NameReserved),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserve_then_create_succeeds() {
+        let reservations = NameReservations::new(Duration::from_secs(60));
+
+        let token = reservations.reserve(1, "Team Rocket", 10).unwrap();
+
+        assert_eq!(
+            reservations.check(1, "Team Rocket", 10, Some(&token)),
+            Ok(())
+        );
+        reservations.release(1, "Team Rocket");
+        assert_eq!(reservations.check(1, "Team Rocket", 11, None), Ok(()));
+    }
+
+    #[test]
+    fn test_second_student_cannot_reserve_during_window() {
+        let reservations = NameReservations::new(Duration::from_secs(60));
+
+        let token = reservations.reserve(1, "Team Rocket", 10).unwrap();
+
+        assert_eq!(
+            reservations.reserve(1, "Team Rocket", 11),
+            Err(NameReserved)
+        );
+        assert_eq!(
+            reservations.check(1, "Team Rocket", 11, Some(&token)),
+            Err(NameReserved)
+        );
+        // Same name in another project is unaffected
+        assert!(reservations.reserve(2, "Team Rocket", 11).is_ok());
+        // The holder renewing keeps the same token
+        assert_eq!(reservations.reserve(1, "Team Rocket", 10), Ok(token));
+    }
+
+    #[test]
+    fn test_expired_reservation_is_released() {
+        let reservations = NameReservations::new(Duration::from_secs(60));
+        let now = Instant::now();
+
+        let token = reservations.reserve_at(1, "Team Rocket", 10, now).unwrap();
+        let later = now + Duration::from_secs(61);
+
+        assert!(reservations.reserve_at(1, "Team Rocket", 11, later).is_ok());
+        assert_eq!(
+            reservations.check_at(1, "Team Rocket", 10, Some(&token), later),
+            Err(NameReserved)
+        );
+    }
+}
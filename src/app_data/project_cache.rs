@@ -0,0 +1,210 @@
+use log::debug;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use utoipa::ToSchema;
+
+struct Entry<T> {
+    value: T,
+    loaded_at: Instant,
+}
+
+struct Entries<T> {
+    by_project: HashMap<i32, Entry<T>>,
+    /// Bumped on every invalidation, a load started before it is not kept
+    version: u64,
+}
+
+/// Reads served from the cache and from the database since startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+pub(crate) struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// In-memory cache of one value per project with a time to live, shared between workers
+///
+/// Meant for expensive reads assembled from several queries. Every write touching the data
+/// of a project must call `invalidate` for it, or `invalidate_all` when the project is
+/// unknown. A value loaded while an invalidation happens is returned but not kept, so a
+/// slow read never puts back what a write just removed.
+#[derive(Clone)]
+pub(crate) struct ProjectCache<T: Clone> {
+    name: &'static str,
+    entries: Arc<Mutex<Entries<T>>>,
+    ttl: Duration,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
+}
+
+impl<T: Clone> ProjectCache<T> {
+    /// Creates an empty cache, `name` labels its log lines
+    pub(crate) fn new(name: &'static str, ttl: Duration) -> Self {
+        Self {
+            name,
+            entries: Arc::new(Mutex::new(Entries {
+                by_project: HashMap::new(),
+                version: 0,
+            })),
+            ttl,
+            hits: Arc::new(AtomicU64::new(0)),
+            misses: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, Entries<T>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Returns the value of `project_id`, calling `loader` only when it is missing or expired
+    ///
+    /// Errors from the loader are returned as is and nothing is cached.
+    pub(crate) async fn get_or_try_load<E, F, Fut>(
+        &self, project_id: i32, loader: F,
+    ) -> Result<T, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let version = {
+            let mut entries = self.entries();
+            let ttl = self.ttl;
+            entries
+                .by_project
+                .retain(|_, entry| entry.loaded_at.elapsed() < ttl);
+            if let Some(entry) = entries.by_project.get(&project_id) {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                debug!("cache={} project={} hit", self.name, project_id);
+                return Ok(entry.value.clone());
+            }
+            entries.version
+        };
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        debug!("cache={} project={} miss", self.name, project_id);
+        let value = loader().await?;
+
+        let mut entries = self.entries();
+        if entries.version == version {
+            entries.by_project.insert(
+                project_id,
+                Entry {
+                    value: value.clone(),
+                    loaded_at: Instant::now(),
+                },
+            );
+        }
+        Ok(value)
+    }
+
+    /// Drops the value of `project_id` so the next read goes to the database
+    pub(crate) fn invalidate(&self, project_id: i32) {
+        let mut entries = self.entries();
+        entries.version += 1;
+        entries.by_project.remove(&project_id);
+    }
+
+    /// Drops the value of every project
+    pub(crate) fn invalidate_all(&self) {
+        let mut entries = self.entries();
+        entries.version += 1;
+        entries.by_project.clear();
+    }
+
+    pub(crate) fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+    use std::sync::atomic::AtomicUsize;
+
+    /// Cache of the number of database reads made when the value was loaded
+    struct Counted {
+        cache: ProjectCache<usize>,
+        queries: AtomicUsize,
+    }
+
+    impl Counted {
+        fn new(ttl: Duration) -> Self {
+            Self {
+                cache: ProjectCache::new("test", ttl),
+                queries: AtomicUsize::new(0),
+            }
+        }
+
+        async fn read(&self, project_id: i32) -> usize {
+            self.cache
+                .get_or_try_load(project_id, || async {
+                    Ok::<_, Infallible>(self.queries.fetch_add(1, Ordering::SeqCst) + 1)
+                })
+                .await
+                .unwrap()
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_second_read_is_served_from_the_cache() {
+        let counted = Counted::new(Duration::from_secs(60));
+
+        assert_eq!(counted.read(1).await, 1);
+        assert_eq!(counted.read(1).await, 1);
+
+        assert_eq!(counted.queries.load(Ordering::SeqCst), 1);
+        assert_eq!(counted.cache.stats(), CacheStats { hits: 1, misses: 1 });
+    }
+
+    #[actix_web::test]
+    async fn test_mutation_invalidates_only_its_project() {
+        let counted = Counted::new(Duration::from_secs(60));
+        counted.read(1).await;
+        counted.read(2).await;
+
+        counted.cache.invalidate(1);
+
+        assert_eq!(counted.read(1).await, 3);
+        assert_eq!(counted.read(2).await, 2);
+        counted.cache.invalidate_all();
+        assert_eq!(counted.read(2).await, 4);
+    }
+
+    #[actix_web::test]
+    async fn test_expired_value_is_reloaded() {
+        let counted = Counted::new(Duration::ZERO);
+
+        counted.read(1).await;
+        counted.read(1).await;
+
+        assert_eq!(counted.queries.load(Ordering::SeqCst), 2);
+    }
+
+    #[actix_web::test]
+    async fn test_load_racing_an_invalidation_is_not_kept() {
+        let cache = ProjectCache::new("test", Duration::from_secs(60));
+
+        let stale = cache
+            .get_or_try_load(1, || async {
+                // a write lands while the tree is being assembled
+                cache.invalidate(1);
+                Ok::<_, Infallible>("before the write")
+            })
+            .await
+            .unwrap();
+        let fresh = cache
+            .get_or_try_load(1, || async { Ok::<_, Infallible>("after the write") })
+            .await
+            .unwrap();
+
+        assert_eq!(stale, "before the write");
+        assert_eq!(fresh, "after the write");
+    }
+}
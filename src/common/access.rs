@@ -0,0 +1,127 @@
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::repositories::coordinator_projects_repository;
+use crate::models::admin::Admin;
+use crate::models::admin_role::AvailableAdminRole;
+use actix_web::http::StatusCode;
+use log::debug;
+use welds::Client;
+
+/// Builds the response returned when a resource is missing or hidden from the caller.
+///
+/// Both cases share this exact body (no log id) so the response never reveals whether
+/// the resource exists behind a permission check.
+pub(crate) fn not_found(msg: &str) -> JsonError {
+    msg.to_json_error(StatusCode::NOT_FOUND)
+}
+
+/// Unwraps a looked-up resource, mapping a miss to the shared not found response
+pub(crate) fn found_or_not_found<T>(resource: Option<T>, msg: &str) -> Result<T, JsonError> {
+    resource.ok_or_else(|| not_found(msg))
+}
+
+/// Rejects a resource the caller has no visibility on with the shared not found response
+///
+/// # Arguments
+/// * `visible` - Whether the caller is allowed to see the resource
+/// * `msg` - The same message used when the resource does not exist
+pub(crate) fn ensure_visible(visible: bool, msg: &str) -> Result<(), JsonError> {
+    if visible {
+        Ok(())
+    } else {
+        debug!("hiding resource from caller without visibility: {}", msg);
+        Err(not_found(msg))
+    }
+}
+
+/// Root admins are visible only to other Root admins, every other account is visible to all
+pub(crate) fn admin_can_see_admin(caller: &Admin, target: &Admin) -> bool {
+    caller.admin_role_id == AvailableAdminRole::Root as i32
+        || target.admin_role_id != AvailableAdminRole::Root as i32
+}
+
+/// Root and Professors see every project, Coordinators only the ones they are assigned to
+pub(crate) async fn admin_can_see_project(
+    db: &impl Client, admin: &Admin, project_id: i32,
+) -> welds::errors::Result<bool> {
+    if admin.admin_role_id != AvailableAdminRole::Coordinator as i32 {
+        return Ok(true);
+    }
+
+    coordinator_projects_repository::is_assigned(db, admin.admin_id, project_id).await
+}
+
+/// Rejects a project-scoped resource the admin has no visibility on with the shared not found response
+pub(crate) async fn ensure_admin_sees_project(
+    db: &impl Client, admin: &Admin, project_id: i32, msg: &str,
+) -> Result<(), JsonError> {
+    let visible = admin_can_see_project(db, admin, project_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!(
+                    "unable to check visibility of project {} for admin {}: {}",
+                    project_id, admin.admin_id, e
+                ),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+    ensure_visible(visible, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::body::to_bytes;
+    use actix_web::ResponseError;
+
+    fn admin_with_role(role: AvailableAdminRole) -> Admin {
+        Admin {
+            admin_id: 1,
+            first_name: "Test".to_string(),
+            last_name: "Admin".to_string(),
+            email: "admin@test.com".to_string(),
+            password_hash: String::new(),
+            admin_role_id: role as i32,
+        }
+    }
+
+    async fn render(err: JsonError) -> (StatusCode, Vec<u8>) {
+        let response = err.error_response();
+        let status = response.status();
+        let body = to_bytes(response.into_body()).await.unwrap();
+        (status, body.to_vec())
+    }
+
+    #[actix_web::test]
+    async fn test_hidden_resource_is_indistinguishable_from_missing() {
+        let missing = found_or_not_found::<i32>(None, "Project not found").unwrap_err();
+        let hidden = ensure_visible(false, "Project not found").unwrap_err();
+
+        let (missing_status, missing_body) = render(missing).await;
+        let (hidden_status, hidden_body) = render(hidden).await;
+
+        assert_eq!(missing_status, StatusCode::NOT_FOUND);
+        assert_eq!(hidden_status, StatusCode::NOT_FOUND);
+        assert_eq!(missing_body, hidden_body);
+    }
+
+    #[test]
+    fn test_visible_resource_passes() {
+        assert!(ensure_visible(true, "Project not found").is_ok());
+        assert_eq!(found_or_not_found(Some(7), "Project not found").unwrap(), 7);
+    }
+
+    #[test]
+    fn test_root_accounts_hidden_from_non_root() {
+        let root = admin_with_role(AvailableAdminRole::Root);
+        let professor = admin_with_role(AvailableAdminRole::Professor);
+
+        assert!(admin_can_see_admin(&root, &root));
+        assert!(admin_can_see_admin(&root, &professor));
+        assert!(admin_can_see_admin(&professor, &professor));
+        assert!(!admin_can_see_admin(&professor, &root));
+    }
+}
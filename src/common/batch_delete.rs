@@ -0,0 +1,171 @@
+use crate::common::json_error::{JsonError, ToJsonError};
+use actix_web::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use utoipa::ToSchema;
+use welds::Client;
+
+/// Upper bound on the number of ids accepted in a single batch delete
+pub(crate) const MAX_BATCH_DELETE: usize = 100;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub(crate) struct BatchDeleteRequest {
+    /// Ids to delete
+    #[schema(example = json!([4, 7, 9]))]
+    pub ids: Vec<i32>,
+    /// Also delete whatever depends on each id, instead of refusing it
+    #[serde(default)]
+    pub cascade: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct BatchDeleteResult {
+    pub id: i32,
+    pub deleted: bool,
+    /// Rows depending on the id, deleted along with it when cascading
+    #[schema(example = 3)]
+    pub dependents: i64,
+    #[schema(example = "Referenced by 3 selections, retry with cascade=true")]
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct BatchDeleteResponse {
+    /// Number of ids actually deleted
+    pub deleted: usize,
+    /// One entry per requested id, in request order
+    pub results: Vec<BatchDeleteResult>,
+}
+
+impl BatchDeleteResponse {
+    pub(crate) fn new(results: Vec<BatchDeleteResult>) -> Self {
+        Self {
+            deleted: results.iter().filter(|r| r.deleted).count(),
+            results,
+        }
+    }
+
+    /// Ids the plan decided to delete
+    pub(crate) fn deleted_ids(&self) -> Vec<i32> {
+        self.results
+            .iter()
+            .filter(|r| r.deleted)
+            .map(|r| r.id)
+            .collect()
+    }
+}
+
+/// Rejects empty and oversized batches
+pub(crate) fn validate_batch(ids: &[i32]) -> Result<(), JsonError> {
+    if ids.is_empty() {
+        return Err("At least one id is required".to_json_error(StatusCode::BAD_REQUEST));
+    }
+    if ids.len() > MAX_BATCH_DELETE {
+        return Err(
+            format!("At most {} ids can be deleted at once", MAX_BATCH_DELETE)
+                .to_json_error(StatusCode::BAD_REQUEST),
+        );
+    }
+    Ok(())
+}
+
+/// Decides, in request order, which ids are deleted
+///
+/// `existing` maps every id found to the number of rows depending on it, described by
+/// `dependents_noun`. Ids with dependents are only deleted when cascading.
+pub(crate) fn plan_deletes(
+    ids: &[i32], existing: &HashMap<i32, i64>, cascade: bool, dependents_noun: &str,
+) -> Vec<BatchDeleteResult> {
+    let mut seen = HashSet::new();
+
+    ids.iter()
+        .map(|&id| {
+            let result = |deleted: bool, dependents: i64, message: String| BatchDeleteResult {
+                id,
+                deleted,
+                dependents,
+                message,
+            };
+
+            if !seen.insert(id) {
+                return result(false, 0, "Id listed more than once".to_string());
+            }
+            match existing.get(&id) {
+                None => result(false, 0, "Not found".to_string()),
+                Some(&0) => result(true, 0, "Deleted".to_string()),
+                Some(&dependents) if cascade => result(
+                    true,
+                    dependents,
+                    format!("Deleted with {} {}", dependents, dependents_noun),
+                ),
+                Some(&dependents) => result(
+                    false,
+                    dependents,
+                    format!(
+                        "Referenced by {} {}, retry with cascade=true",
+                        dependents, dependents_noun
+                    ),
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Locks the rows selected by a dependents query until the end of the transaction
+///
+/// A row can only gain a dependent through a foreign key, which needs a share lock on it,
+/// so no dependent appears between the count and the delete.
+fn locking(sql: &str) -> String {
+    format!("{} FOR UPDATE", sql.trim_end())
+}
+
+/// Runs a query returning `(id, dependents)` rows for the ids bound as `$1`
+///
+/// Meant to run in the transaction performing the deletes: the counted rows stay locked
+/// until it ends.
+pub(crate) async fn count_dependents(
+    db: &impl Client, sql: &str, ids: &[i32],
+) -> welds::errors::Result<HashMap<i32, i64>> {
+    let ids = ids.to_vec();
+    let rows = db.fetch_rows(&locking(sql), &[&ids]).await?;
+    rows.iter()
+        .map(|row| {
+            Ok((
+                row.get_by_position::<i32>(0)?,
+                row.get_by_position::<i64>(1)?,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_size_is_bounded() {
+        assert!(validate_batch(&[]).is_err());
+        assert!(validate_batch(&[1; MAX_BATCH_DELETE + 1]).is_err());
+        assert!(validate_batch(&[1, 2]).is_ok());
+    }
+
+    #[test]
+    fn test_missing_and_repeated_ids_are_reported() {
+        let existing = HashMap::from([(1, 0)]);
+        let response = BatchDeleteResponse::new(plan_deletes(&[1, 2, 1], &existing, false, "x"));
+
+        assert_eq!(response.deleted_ids(), vec![1]);
+        assert_eq!(response.results[1].message, "Not found");
+        assert_eq!(response.results[2].message, "Id listed more than once");
+    }
+
+    #[test]
+    fn test_dependents_are_counted_on_locked_rows() {
+        let sql = "SELECT d.id, 0 FROM deliverables d WHERE d.id = ANY($1)\n";
+
+        assert_eq!(
+            locking(sql),
+            "SELECT d.id, 0 FROM deliverables d WHERE d.id = ANY($1) FOR UPDATE"
+        );
+    }
+}
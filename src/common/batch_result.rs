@@ -0,0 +1,105 @@
+use actix_web::http::StatusCode;
+use actix_web::HttpResponse;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Item of a batch that was not applied
+#[derive(Debug, Serialize, ToSchema, PartialEq, Eq)]
+pub(crate) struct BatchFailure {
+    /// Position of the item in the request
+    #[schema(example = 2)]
+    pub index: usize,
+    #[schema(example = "Student already in project group")]
+    pub error: String,
+}
+
+/// Outcome of a batch whose items are applied independently
+///
+/// Answered with `200 OK` when every item succeeded and `207 Multi-Status` when some
+/// failed, so a client can tell a partial success from the status alone.
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct BatchResult<T: ToSchema> {
+    /// Applied items, in request order
+    pub succeeded: Vec<T>,
+    /// Items that were not applied, in request order
+    pub failed: Vec<BatchFailure>,
+}
+
+impl<T: ToSchema> Default for BatchResult<T> {
+    fn default() -> Self {
+        Self {
+            succeeded: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+}
+
+impl<T: ToSchema> FromIterator<Result<T, String>> for BatchResult<T> {
+    /// Sorts the outcomes of the items, given in request order
+    fn from_iter<I: IntoIterator<Item = Result<T, String>>>(outcomes: I) -> Self {
+        let mut result = Self::default();
+        for (index, outcome) in outcomes.into_iter().enumerate() {
+            match outcome {
+                Ok(item) => result.succeeded.push(item),
+                Err(error) => result.failed.push(BatchFailure { index, error }),
+            }
+        }
+        result
+    }
+}
+
+impl<T: ToSchema + Serialize> BatchResult<T> {
+    pub(crate) fn status(&self) -> StatusCode {
+        if self.failed.is_empty() {
+            StatusCode::OK
+        } else {
+            StatusCode::MULTI_STATUS
+        }
+    }
+
+    pub(crate) fn respond(self) -> HttpResponse {
+        HttpResponse::build(self.status()).json(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::body::to_bytes;
+    use serde_json::json;
+
+    #[derive(Debug, Serialize, ToSchema)]
+    struct Item {
+        id: i32,
+    }
+
+    #[actix_web::test]
+    async fn test_mixed_batch_is_a_multi_status() {
+        let result: BatchResult<Item> = [
+            Ok(Item { id: 4 }),
+            Err("Not found".to_string()),
+            Ok(Item { id: 9 }),
+        ]
+        .into_iter()
+        .collect();
+
+        let response = result.respond();
+
+        assert_eq!(response.status(), StatusCode::MULTI_STATUS);
+        let bytes = to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&bytes).unwrap(),
+            json!({
+                "succeeded": [{"id": 4}, {"id": 9}],
+                "failed": [{"index": 1, "error": "Not found"}]
+            })
+        );
+    }
+
+    #[test]
+    fn test_batch_without_failures_is_ok() {
+        let result: BatchResult<Item> = [Ok(Item { id: 4 })].into_iter().collect();
+
+        assert_eq!(result.status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,190 @@
+use actix_web::dev::ServiceRequest;
+use actix_web::HttpRequest;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// Address range in CIDR notation, a bare address is a range of one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Cidr {
+    network: IpAddr,
+    prefix: u8,
+}
+
+impl FromStr for Cidr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, prefix) = s.trim().split_once('/').unwrap_or((s.trim(), ""));
+        let network: IpAddr = address
+            .parse()
+            .map_err(|_| format!("invalid address in {:?}", s))?;
+        let max_prefix = if network.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            "" => max_prefix,
+            p => p
+                .parse::<u8>()
+                .ok()
+                .filter(|p| *p <= max_prefix)
+                .ok_or_else(|| format!("invalid prefix length in {:?}", s))?,
+        };
+        Ok(Self { network, prefix })
+    }
+}
+
+impl Cidr {
+    fn contains(&self, ip: IpAddr) -> bool {
+        let ip = match ip {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+            v4 => v4,
+        };
+        let (network, ip, bits) = match (self.network, ip) {
+            (IpAddr::V4(n), IpAddr::V4(a)) => (u32::from(n) as u128, u32::from(a) as u128, 32),
+            (IpAddr::V6(n), IpAddr::V6(a)) => (u128::from(n), u128::from(a), 128),
+            _ => return false,
+        };
+        let host_bits = bits - self.prefix as u32;
+        network.checked_shr(host_bits).unwrap_or(0) == ip.checked_shr(host_bits).unwrap_or(0)
+    }
+}
+
+/// List of address ranges from the config
+#[derive(Debug, Clone, Default)]
+pub(crate) struct IpRanges(Arc<Vec<Cidr>>);
+
+impl IpRanges {
+    pub(crate) fn parse(ranges: &[String]) -> Result<Self, String> {
+        let ranges = ranges
+            .iter()
+            .map(|r| r.parse())
+            .collect::<Result<Vec<Cidr>, _>>()?;
+        Ok(Self(Arc::new(ranges)))
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn contains(&self, ip: IpAddr) -> bool {
+        self.0.iter().any(|range| range.contains(ip))
+    }
+}
+
+/// Reverse proxies allowed to report the client address in `X-Forwarded-For`
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TrustedProxies(IpRanges);
+
+impl From<IpRanges> for TrustedProxies {
+    fn from(ranges: IpRanges) -> Self {
+        Self(ranges)
+    }
+}
+
+impl TrustedProxies {
+    fn trusts(&self, ip: IpAddr) -> bool {
+        self.0.contains(ip)
+    }
+
+    /// Address of the client that sent the request
+    ///
+    /// `X-Forwarded-For` is walked from the closest hop only while the hop that appended
+    /// it is trusted, so clients cannot pick their address by sending the header themselves.
+    fn resolve(&self, peer: Option<IpAddr>, forwarded_for: Option<&str>) -> Option<IpAddr> {
+        let mut client = peer?;
+        let hops = forwarded_for.unwrap_or_default().rsplit(',').map(str::trim);
+        for hop in hops {
+            if !self.trusts(client) {
+                break;
+            }
+            match hop.parse() {
+                Ok(ip) => client = ip,
+                Err(_) => break,
+            }
+        }
+        Some(client)
+    }
+
+    /// Client address of a request, see [`TrustedProxies::resolve`]
+    pub(crate) fn client_ip(&self, req: &HttpRequest) -> Option<IpAddr> {
+        let forwarded_for = req
+            .headers()
+            .get(X_FORWARDED_FOR)
+            .and_then(|v| v.to_str().ok());
+        self.resolve(req.peer_addr().map(|a| a.ip()), forwarded_for)
+    }
+
+    /// Whether the request was sent by a trusted proxy, whose forwarding headers can be used
+    pub(crate) fn is_trusted_peer(&self, req: &HttpRequest) -> bool {
+        req.peer_addr().is_some_and(|peer| self.trusts(peer.ip()))
+    }
+
+    /// Client address of a request seen by a middleware
+    pub(crate) fn service_client_ip(&self, req: &ServiceRequest) -> Option<IpAddr> {
+        self.client_ip(req.request())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    fn proxies() -> TrustedProxies {
+        IpRanges::parse(&["10.0.0.0/8".to_string(), "192.168.1.5".to_string()])
+            .unwrap()
+            .into()
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_spoofed_header_from_untrusted_peer_is_ignored() {
+        let req = TestRequest::default()
+            .peer_addr("203.0.113.7:5000".parse().unwrap())
+            .insert_header((X_FORWARDED_FOR, "1.2.3.4"))
+            .to_http_request();
+
+        assert_eq!(proxies().client_ip(&req), Some(ip("203.0.113.7")));
+        // Nothing is trusted unless configured
+        let req = TestRequest::default()
+            .peer_addr("10.0.0.2:5000".parse().unwrap())
+            .insert_header((X_FORWARDED_FOR, "1.2.3.4"))
+            .to_http_request();
+        assert_eq!(
+            TrustedProxies::default().client_ip(&req),
+            Some(ip("10.0.0.2"))
+        );
+    }
+
+    #[test]
+    fn test_forwarded_chain_is_walked_through_trusted_hops() {
+        let req = TestRequest::default()
+            .peer_addr("10.0.0.2:5000".parse().unwrap())
+            .insert_header((X_FORWARDED_FOR, "6.6.6.6, 198.51.100.20, 192.168.1.5"))
+            .to_http_request();
+
+        // 6.6.6.6 was sent by the client itself, 198.51.100.20 is the first untrusted hop
+        assert_eq!(proxies().client_ip(&req), Some(ip("198.51.100.20")));
+        assert_eq!(
+            proxies().resolve(Some(ip("10.1.2.3")), Some("garbage, 10.0.0.9")),
+            Some(ip("10.0.0.9"))
+        );
+        assert_eq!(proxies().resolve(None, Some("1.2.3.4")), None);
+    }
+
+    #[test]
+    fn test_ranges_are_parsed_and_matched() {
+        let range: Cidr = "2001:db8::/32".parse().unwrap();
+        assert!(range.contains(ip("2001:db8::1")));
+        assert!(!range.contains(ip("2001:db9::1")));
+        assert!("0.0.0.0/0".parse::<Cidr>().unwrap().contains(ip("8.8.8.8")));
+        assert!(proxies().trusts(ip("::ffff:10.2.3.4")));
+
+        assert!("10.0.0.0/33".parse::<Cidr>().is_err());
+        assert!("proxy.local".parse::<Cidr>().is_err());
+    }
+}
@@ -0,0 +1,53 @@
+use actix_web::http::header::LOCATION;
+use actix_web::HttpResponse;
+use serde::Serialize;
+use std::fmt::Display;
+
+/// `201 Created` response with the new resource in the body and its URL in `Location`
+///
+/// `collection` is the path the resource was posted to, the `Location` is that path
+/// followed by the id of the resource.
+pub(crate) fn created<T: Serialize>(collection: &str, id: impl Display, body: &T) -> HttpResponse {
+    HttpResponse::Created()
+        .insert_header((
+            LOCATION,
+            format!("{}/{}", collection.trim_end_matches('/'), id),
+        ))
+        .json(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::body::to_bytes;
+    use actix_web::http::StatusCode;
+    use serde_json::json;
+
+    #[actix_web::test]
+    async fn test_created_project_points_at_the_project() {
+        let body = json!({"project_id": 7, "slug": "rusty-robots"});
+        let response = created("/v1/admins/projects", 7, &body);
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            response.headers().get(LOCATION).unwrap(),
+            "/v1/admins/projects/7"
+        );
+        let bytes = to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&bytes).unwrap(),
+            body
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_created_admin_ignores_a_trailing_slash() {
+        let response = created("/v1/admins/users/", 12, &json!({"admin_id": 12}));
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            response.headers().get(LOCATION).unwrap(),
+            "/v1/admins/users/12"
+        );
+    }
+}
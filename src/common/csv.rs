@@ -0,0 +1,114 @@
+/// Characters that make spreadsheets read a cell as a formula when it starts with them
+const FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+/// Quotes a CSV field when it contains a separator, a quote or a line break
+///
+/// Values a spreadsheet would run as a formula are prefixed with `'`, so they are shown
+/// as text instead.
+pub(crate) fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(FORMULA_PREFIXES) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// Splits a CSV document into records of fields, undoing the quoting of [`csv_field`]
+///
+/// Lines may end with `\n` or `\r\n`, blank lines are skipped.
+pub(crate) fn parse_csv(text: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => {
+                if c == '\n' {
+                    line += 1;
+                }
+                field.push(c);
+            }
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                line += 1;
+                record.push(std::mem::take(&mut field));
+                if record.iter().any(|f| !f.is_empty()) || record.len() > 1 {
+                    records.push(std::mem::take(&mut record));
+                } else {
+                    record.clear();
+                }
+            }
+            (false, c) => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(format!("Unterminated quoted field on line {}", line));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_field_doubles_quotes() {
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("plain"), "plain");
+    }
+
+    #[test]
+    fn test_csv_field_neutralises_formulas() {
+        assert_eq!(
+            csv_field("=HYPERLINK(\"http://x\")"),
+            "\"'=HYPERLINK(\"\"http://x\"\")\""
+        );
+        assert_eq!(csv_field("+1"), "'+1");
+        assert_eq!(csv_field("-2+3"), "'-2+3");
+        assert_eq!(csv_field("@SUM(A1)"), "'@SUM(A1)");
+        assert_eq!(csv_field("a=b"), "a=b");
+        assert_eq!(csv_field(""), "");
+    }
+
+    #[test]
+    fn test_parse_csv_reads_back_quoted_fields() {
+        let text = format!(
+            "type,name\r\ngroup,{}\n\nstudent,\"two\nlines\"\n",
+            csv_field("say \"hi\", then leave")
+        );
+
+        assert_eq!(
+            parse_csv(&text).unwrap(),
+            vec![
+                vec!["type", "name"],
+                vec!["group", "say \"hi\", then leave"],
+                vec!["student", "two\nlines"],
+            ]
+        );
+        assert_eq!(
+            parse_csv("type\n\"open").unwrap_err(),
+            "Unterminated quoted field on line 2"
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use crate::common::json_error::{ToJsonError, INVALID_PARAM};
+use crate::common::timestamps::{with_format, TimestampFormat};
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::HeaderName;
+use actix_web::http::StatusCode;
+use actix_web::web::Query;
+use actix_web::{Error, HttpRequest, ResponseError};
+use futures_util::future::{ready, LocalBoxFuture, Ready};
+use serde::Deserialize;
+use std::rc::Rc;
+
+pub(crate) const ACCEPT_VERSION: HeaderName = HeaderName::from_static("accept-version");
+
+/// API version of the legacy clients reading timestamps as Unix seconds
+const LEGACY_VERSION: &str = "1";
+
+#[derive(Deserialize)]
+struct DateFormatQuery {
+    date_format: Option<String>,
+}
+
+/// Timestamp format asked for by the client, `date_format` taking precedence over
+/// `Accept-Version`
+fn requested_format(req: &HttpRequest) -> Result<TimestampFormat, String> {
+    let date_format = Query::<DateFormatQuery>::from_query(req.query_string())
+        .ok()
+        .and_then(|q| q.into_inner().date_format);
+    match date_format.as_deref().map(str::trim) {
+        Some("rfc3339") => return Ok(TimestampFormat::Rfc3339),
+        Some("unix") => return Ok(TimestampFormat::Unix),
+        Some(other) => {
+            return Err(format!(
+                "Invalid date_format `{}`, expected `rfc3339` or `unix`",
+                other
+            ))
+        }
+        None => {}
+    }
+
+    let legacy = req
+        .headers()
+        .get(ACCEPT_VERSION)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.trim() == LEGACY_VERSION);
+    Ok(if legacy {
+        TimestampFormat::Unix
+    } else {
+        TimestampFormat::Rfc3339
+    })
+}
+
+/// Serializes the timestamps of a response as Unix seconds for legacy clients
+///
+/// Timestamps are RFC3339 strings by default. Clients opt in to integer seconds per
+/// request with `?date_format=unix`, or with `Accept-Version: 1` for the old client that
+/// cannot change its URLs. Only fields serialized through
+/// [`rfc3339`](crate::common::timestamps::rfc3339) are affected.
+pub(crate) struct DateFormatNegotiation;
+
+impl<S, B> Transform<S, ServiceRequest> for DateFormatNegotiation
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = DateFormatNegotiationService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(DateFormatNegotiationService {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub(crate) struct DateFormatNegotiationService<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for DateFormatNegotiationService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        Box::pin(async move {
+            let format = match requested_format(req.request()) {
+                Ok(format) => format,
+                Err(message) => {
+                    let error = message
+                        .to_json_error(StatusCode::BAD_REQUEST)
+                        .with_code(INVALID_PARAM);
+                    let res = error.error_response();
+                    return Ok(req.into_response(res).map_into_right_body());
+                }
+            };
+
+            let res = with_format(format, service.call(req)).await?;
+            Ok(res.map_into_left_body())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::{call_service, init_service, read_body, TestRequest};
+    use actix_web::{web, App, HttpResponse};
+    use chrono::{DateTime, TimeZone, Utc};
+    use serde::Serialize;
+    use serde_json::{json, Value};
+
+    #[derive(Serialize)]
+    struct Deadline {
+        project_id: i32,
+        #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
+        upload_deadline: DateTime<Utc>,
+    }
+
+    async fn deadline() -> HttpResponse {
+        HttpResponse::Ok().json(Deadline {
+            project_id: 3,
+            upload_deadline: Utc.with_ymd_and_hms(2026, 6, 10, 10, 0, 0).unwrap(),
+        })
+    }
+
+    async fn get(request: TestRequest) -> (StatusCode, Value) {
+        let app = init_service(
+            App::new()
+                .wrap(DateFormatNegotiation)
+                .route("/deadline", web::get().to(deadline)),
+        )
+        .await;
+        let res = call_service(&app, request.to_request()).await;
+        let status = res.status();
+        (
+            status,
+            serde_json::from_slice(&read_body(res).await).unwrap(),
+        )
+    }
+
+    #[actix_web::test]
+    async fn test_timestamps_are_rfc3339_by_default() {
+        let (status, body) = get(TestRequest::get().uri("/deadline")).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            json!({"project_id": 3, "upload_deadline": "2026-06-10T10:00:00.000Z"})
+        );
+
+        let (_, body) = get(TestRequest::get()
+            .uri("/deadline")
+            .insert_header((ACCEPT_VERSION, "2")))
+        .await;
+        assert_eq!(body["upload_deadline"], "2026-06-10T10:00:00.000Z");
+    }
+
+    #[actix_web::test]
+    async fn test_legacy_clients_get_unix_seconds() {
+        let expected = json!({"project_id": 3, "upload_deadline": 1781085600});
+
+        let (_, by_query) = get(TestRequest::get().uri("/deadline?date_format=unix")).await;
+        assert_eq!(by_query, expected);
+
+        let (_, by_header) = get(TestRequest::get()
+            .uri("/deadline")
+            .insert_header((ACCEPT_VERSION, "1")))
+        .await;
+        assert_eq!(by_header, expected);
+
+        // the query parameter wins over the header
+        let (_, body) = get(TestRequest::get()
+            .uri("/deadline?date_format=rfc3339")
+            .insert_header((ACCEPT_VERSION, "1")))
+        .await;
+        assert_eq!(body["upload_deadline"], "2026-06-10T10:00:00.000Z");
+    }
+
+    #[actix_web::test]
+    async fn test_unknown_date_format_is_rejected() {
+        let (status, body) = get(TestRequest::get().uri("/deadline?date_format=iso")).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], INVALID_PARAM);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::common::json_error::{JsonError, ToJsonError};
+use actix_web::http::StatusCode;
+use chrono::{DateTime, Duration, SubsecRound, Utc};
+
+/// How far in the past a deadline of a new project can be, to absorb clock and timezone slips
+pub(crate) const PAST_DEADLINE_TOLERANCE: Duration = Duration::days(1);
+
+/// Deadlines of a project, in the order they are expected to fall
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ProjectDeadlines {
+    pub deliverable_selection_deadline: Option<DateTime<Utc>>,
+    pub upload_deadline: Option<DateTime<Utc>>,
+}
+
+impl ProjectDeadlines {
+    /// Named deadlines, earliest first
+    fn ordered(&self) -> [(&'static str, Option<DateTime<Utc>>); 2] {
+        [
+            (
+                "deliverable_selection_deadline",
+                self.deliverable_selection_deadline,
+            ),
+            ("upload_deadline", self.upload_deadline),
+        ]
+    }
+
+    /// Drops sub-second precision so stored deadlines compare as clients send them
+    pub(crate) fn normalized(self) -> Self {
+        Self {
+            deliverable_selection_deadline: self.deliverable_selection_deadline.map(normalize),
+            upload_deadline: self.upload_deadline.map(normalize),
+        }
+    }
+
+    /// Checks that the set deadlines are in order, unset ones are skipped
+    pub(crate) fn validate_order(&self) -> Result<(), JsonError> {
+        let set: Vec<(&str, DateTime<Utc>)> = self
+            .ordered()
+            .into_iter()
+            .filter_map(|(name, value)| value.map(|v| (name, v)))
+            .collect();
+
+        for pair in set.windows(2) {
+            let (earlier_name, earlier) = pair[0];
+            let (later_name, later) = pair[1];
+            if earlier > later {
+                return Err(format!("{} must not be after {}", earlier_name, later_name)
+                    .to_json_error(StatusCode::UNPROCESSABLE_ENTITY));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that no deadline is further in the past than `PAST_DEADLINE_TOLERANCE`
+    pub(crate) fn validate_not_past(&self, now: DateTime<Utc>) -> Result<(), JsonError> {
+        for (name, value) in self.ordered() {
+            if value.is_some_and(|v| v < now - PAST_DEADLINE_TOLERANCE) {
+                return Err(format!("{} is in the past", name)
+                    .to_json_error(StatusCode::UNPROCESSABLE_ENTITY));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn normalize(value: DateTime<Utc>) -> DateTime<Utc> {
+    value.trunc_subsecs(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::ResponseError;
+    use chrono::TimeZone;
+
+    fn at(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 12, day, 23, 59, 59).unwrap()
+    }
+
+    #[test]
+    fn test_out_of_order_deadlines_are_rejected() {
+        let deadlines = ProjectDeadlines {
+            deliverable_selection_deadline: Some(at(20)),
+            upload_deadline: Some(at(15)),
+        };
+
+        let err = deadlines.validate_order().unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(err
+            .to_string()
+            .contains("deliverable_selection_deadline must not be after upload_deadline"));
+    }
+
+    #[test]
+    fn test_valid_deadlines_are_accepted() {
+        let deadlines = ProjectDeadlines {
+            deliverable_selection_deadline: Some(at(15)),
+            upload_deadline: Some(at(20)),
+        };
+
+        assert!(deadlines.validate_order().is_ok());
+        assert!(deadlines.validate_not_past(at(1)).is_ok());
+        assert!(ProjectDeadlines::default().validate_order().is_ok());
+    }
+
+    #[test]
+    fn test_past_deadline_is_rejected_for_new_projects() {
+        let deadlines = ProjectDeadlines {
+            deliverable_selection_deadline: None,
+            upload_deadline: Some(at(1)),
+        };
+
+        assert!(deadlines
+            .validate_not_past(at(1) + Duration::hours(12))
+            .is_ok());
+        let err = deadlines.validate_not_past(at(3)).unwrap_err();
+        assert!(err.to_string().contains("upload_deadline is in the past"));
+    }
+
+    #[test]
+    fn test_normalized_drops_sub_seconds() {
+        let deadlines = ProjectDeadlines {
+            deliverable_selection_deadline: Some(at(15) + Duration::milliseconds(250)),
+            upload_deadline: None,
+        }
+        .normalized();
+
+        assert_eq!(deadlines.deliverable_selection_deadline, Some(at(15)));
+    }
+}
@@ -0,0 +1,137 @@
+use crate::common::json_error::{JsonError, ToJsonError, INVALID_PARAM};
+use actix_web::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use utoipa::IntoParams;
+
+/// Sparse fieldset query parameter of read endpoints
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub(crate) struct FieldsQuery {
+    /// Comma separated fields to return for each item, all of them when missing
+    #[param(example = "project_id,name")]
+    pub fields: Option<String>,
+}
+
+/// Fields a client asked for, checked against the fields of the entity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FieldSet(Vec<String>);
+
+impl FieldsQuery {
+    /// Requested fields, `None` when every field is wanted
+    ///
+    /// Fields outside `allowed` are a 400 `INVALID_PARAM`, blank entries and duplicates are
+    /// ignored.
+    pub(crate) fn parse(&self, allowed: &[&str]) -> Result<Option<FieldSet>, JsonError> {
+        let Some(fields) = &self.fields else {
+            return Ok(None);
+        };
+
+        let mut selected: Vec<String> = Vec::new();
+        for field in fields.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+            if !allowed.contains(&field) {
+                return Err(format!(
+                    "Unknown field `{}`, expected some of: {}",
+                    field,
+                    allowed.join(", ")
+                )
+                .to_json_error(StatusCode::BAD_REQUEST)
+                .with_code(INVALID_PARAM));
+            }
+            if !selected.iter().any(|f| f == field) {
+                selected.push(field.to_string());
+            }
+        }
+
+        if selected.is_empty() {
+            return Err("No field requested in `fields`"
+                .to_json_error(StatusCode::BAD_REQUEST)
+                .with_code(INVALID_PARAM));
+        }
+        Ok(Some(FieldSet(selected)))
+    }
+}
+
+/// Serializes `items`, keeping only the requested fields of each when there is a selection
+pub(crate) fn select_fields<T: Serialize>(items: Vec<T>, fields: Option<&FieldSet>) -> Vec<Value> {
+    items
+        .into_iter()
+        .map(|item| {
+            let value = serde_json::to_value(item).unwrap_or(Value::Null);
+            match (fields, value) {
+                (Some(FieldSet(fields)), Value::Object(mut object)) => Value::Object(
+                    fields
+                        .iter()
+                        .filter_map(|f| object.remove_entry(f))
+                        .collect::<Map<_, _>>(),
+                ),
+                (_, value) => value,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::ResponseError;
+    use serde_json::json;
+
+    const ALLOWED: &[&str] = &["project_id", "name", "year"];
+
+    #[derive(Serialize)]
+    struct Item {
+        project_id: i32,
+        name: &'static str,
+        year: i32,
+    }
+
+    fn query(fields: &str) -> FieldsQuery {
+        FieldsQuery {
+            fields: Some(fields.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_subset_keeps_only_the_requested_fields() {
+        let fields = query("project_id, name,name").parse(ALLOWED).unwrap();
+        let items = vec![Item {
+            project_id: 1,
+            name: "Rusty Robots",
+            year: 2026,
+        }];
+
+        let projected = select_fields(items, fields.as_ref());
+        assert_eq!(
+            projected,
+            vec![json!({"project_id": 1, "name": "Rusty Robots"})]
+        );
+    }
+
+    #[test]
+    fn test_missing_fields_keep_every_field() {
+        let fields = FieldsQuery::default().parse(ALLOWED).unwrap();
+        let items = vec![Item {
+            project_id: 1,
+            name: "Rusty Robots",
+            year: 2026,
+        }];
+
+        assert_eq!(fields, None);
+        assert_eq!(select_fields(items, fields.as_ref())[0]["year"], 2026);
+    }
+
+    #[test]
+    fn test_unknown_field_is_rejected() {
+        let err = query("project_id,password").parse(ALLOWED).unwrap_err();
+
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+        assert!(err.to_string().contains("password"));
+    }
+
+    #[test]
+    fn test_empty_selection_is_rejected() {
+        let err = query(" , ").parse(ALLOWED).unwrap_err();
+
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+    }
+}
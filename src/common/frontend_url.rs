@@ -0,0 +1,80 @@
+use actix_web::http::header::{ORIGIN, REFERER};
+use actix_web::HttpRequest;
+use url::{Origin, Url};
+
+/// Frontend base url the email links sent for `req` should point to
+///
+/// The first of `bases` whose origin matches the request `Origin` header, or else its
+/// `Referer`, is picked. Requests from unknown frontends get the first (default) one.
+pub(crate) fn link_base_for<'a>(req: &HttpRequest, bases: &'a [String]) -> &'a str {
+    let header = |name| req.headers().get(name).and_then(|v| v.to_str().ok());
+    pick_base(bases, header(ORIGIN), header(REFERER))
+}
+
+fn pick_base<'a>(bases: &'a [String], origin: Option<&str>, referer: Option<&str>) -> &'a str {
+    let request_origin = origin
+        .or(referer)
+        .and_then(|value| Url::parse(value).ok())
+        .map(|url| url.origin())
+        .filter(Origin::is_tuple);
+
+    request_origin
+        .and_then(|request_origin| {
+            bases.iter().find(|base| {
+                Url::parse(base).is_ok_and(|base_url| base_url.origin() == request_origin)
+            })
+        })
+        .or(bases.first())
+        .map_or("", String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    fn bases() -> Vec<String> {
+        vec![
+            "https://ap.example.com".to_string(),
+            "https://staging.example.com/app".to_string(),
+        ]
+    }
+
+    #[test]
+    fn test_matching_origin_picks_its_base() {
+        let bases = bases();
+        let req = TestRequest::default()
+            .insert_header((ORIGIN, "https://staging.example.com"))
+            .to_http_request();
+
+        assert_eq!(
+            link_base_for(&req, &bases),
+            "https://staging.example.com/app"
+        );
+        // Without an Origin header the Referer is used
+        assert_eq!(
+            pick_base(&bases, None, Some("https://staging.example.com/login?x=1")),
+            "https://staging.example.com/app"
+        );
+    }
+
+    #[test]
+    fn test_unknown_origin_uses_default() {
+        let bases = bases();
+        let req = TestRequest::default()
+            .insert_header((ORIGIN, "https://evil.example.org"))
+            .to_http_request();
+
+        assert_eq!(link_base_for(&req, &bases), "https://ap.example.com");
+        assert_eq!(pick_base(&bases, None, None), "https://ap.example.com");
+        // Same host on another scheme is a different origin
+        assert_eq!(
+            pick_base(&bases, Some("http://staging.example.com"), None),
+            "https://ap.example.com"
+        );
+        assert_eq!(
+            pick_base(&bases, Some("null"), None),
+            "https://ap.example.com"
+        );
+    }
+}
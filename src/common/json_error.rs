@@ -1,5 +1,6 @@
 use actix_web::{http::StatusCode, HttpResponse, ResponseError};
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 use utoipa::ToSchema;
 use uuid::Uuid;
@@ -7,6 +8,7 @@ use uuid::Uuid;
 /// Custom error type for generating JSON error responses
 ///
 /// - `error`: Human-readable error message
+/// - `code`: Optional machine-readable code for errors the frontend handles specifically
 /// - `log_id`: Unique identifier included in console logs for frontend tracking
 /// - `status`: HTTP status code (not included in JSON response)
 ///
@@ -15,6 +17,8 @@ use uuid::Uuid;
 pub struct JsonError {
     error: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     log_id: Option<String>,
     #[serde(skip)]
     status: StatusCode,
@@ -29,6 +33,7 @@ impl JsonError {
     pub fn new(msg: impl Into<String>, status: StatusCode) -> Self {
         JsonError {
             error: msg.into(),
+            code: None,
             log_id: None,
             status,
         }
@@ -43,12 +48,29 @@ impl JsonError {
     fn new_with_log_id(msg: impl Into<String>, status: StatusCode, log_id: Uuid) -> Self {
         JsonError {
             error: msg.into(),
+            code: None,
             log_id: Some(log_id.to_string()),
             status,
         }
     }
 }
 
+impl JsonError {
+    /// Human-readable message of the error
+    pub(crate) fn message(&self) -> &str {
+        &self.error
+    }
+
+    /// Attaches a machine-readable code to the error
+    ///
+    /// # Arguments
+    /// * `code` - One of the error code constants defined in this module
+    pub(crate) fn with_code(mut self, code: &str) -> Self {
+        self.code = Some(code.to_string());
+        self
+    }
+}
+
 impl Display for JsonError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{} ({})", self.error, self.status)
@@ -70,6 +92,91 @@ impl ResponseError for JsonError {
     }
 }
 
+/// Field errors of a request body that failed validation
+///
+/// - `error`: Message of the first invalid field, so clients reading only `error` keep working
+/// - `code`: Always `VALIDATION_FAILED`
+/// - `fields`: Every invalid field mapped to its messages
+///
+/// Returned with a 400 status, like the other invalid request errors, unless another one is set
+#[derive(Serialize, Debug, Default, ToSchema)]
+pub struct ValidationError {
+    #[schema(example = "First name cannot be empty")]
+    error: String,
+    #[schema(example = "VALIDATION_FAILED")]
+    code: String,
+    #[schema(example = json!({"first_name": ["First name cannot be empty"], "email": ["Email cannot be empty"]}))]
+    fields: BTreeMap<String, Vec<String>>,
+    #[serde(skip)]
+    status: Option<StatusCode>,
+}
+
+impl ValidationError {
+    /// Records a message for `field`
+    pub(crate) fn add(&mut self, field: &str, msg: impl Into<String>) {
+        let msg = msg.into();
+        if self.error.is_empty() {
+            self.error = msg.clone();
+            self.code = VALIDATION_FAILED.to_string();
+        }
+        self.fields.entry(field.to_string()).or_default().push(msg);
+    }
+
+    /// Answers with `status` instead of 400
+    pub(crate) fn with_status(mut self, status: StatusCode) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Fails with the collected field errors, if there are any
+    pub(crate) fn into_result(self) -> Result<(), ValidationError> {
+        if self.fields.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({} invalid fields)", self.error, self.fields.len())
+    }
+}
+
+impl ResponseError for ValidationError {
+    fn status_code(&self) -> StatusCode {
+        self.status.unwrap_or(StatusCode::BAD_REQUEST)
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code()).json(self)
+    }
+}
+
+/// Error code returned when a deliverable-component link already exists
+pub(crate) const LINK_EXISTS: &str = "LINK_EXISTS";
+/// Error code returned when student self-signup is turned off
+pub(crate) const SIGNUP_DISABLED: &str = "SIGNUP_DISABLED";
+/// Error code returned when a mutation targets a frozen project
+pub(crate) const PROJECT_FROZEN: &str = "PROJECT_FROZEN";
+/// Error code returned when a path or query parameter cannot be parsed
+pub(crate) const INVALID_PARAM: &str = "INVALID_PARAM";
+/// Error code returned with the field errors of a request body
+pub(crate) const VALIDATION_FAILED: &str = "VALIDATION_FAILED";
+/// Error code returned while the database cannot be reached
+pub(crate) const DB_UNAVAILABLE: &str = "DB_UNAVAILABLE";
+/// Error code returned when a client made too many requests
+pub(crate) const RATE_LIMITED: &str = "RATE_LIMITED";
+/// Error code returned when a change would leave no Root admin
+pub(crate) const LAST_ROOT: &str = "LAST_ROOT";
+/// Error code returned when an admin is assigned to a project they already coordinate
+pub(crate) const ALREADY_COORDINATOR: &str = "ALREADY_COORDINATOR";
+/// Error code returned when the upload scanner flags an uploaded file
+pub(crate) const UPLOAD_REJECTED: &str = "UPLOAD_REJECTED";
+/// Error code returned when a deleted row is still referenced by another one
+pub(crate) const RESOURCE_IN_USE: &str = "RESOURCE_IN_USE";
+
 /// Convenience trait for converting Display types to JsonError
 pub(crate) trait ToJsonError {
     /// Converts self into a JsonError with specified status code
@@ -116,3 +223,51 @@ pub(crate) fn error_with_log_id_and_payload<T>(
 ) -> JsonError {
     error_with_log_id(detailed_msg, user_msg, status, log_level)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_code_is_serialized_only_when_set() {
+        let plain = "Not found".to_json_error(StatusCode::NOT_FOUND);
+        assert_eq!(
+            serde_json::to_value(&plain).unwrap(),
+            json!({"error": "Not found"})
+        );
+
+        let coded = "Relationship already exists"
+            .to_json_error(StatusCode::CONFLICT)
+            .with_code(LINK_EXISTS);
+        assert_eq!(
+            serde_json::to_value(&coded).unwrap(),
+            json!({"error": "Relationship already exists", "code": "LINK_EXISTS"})
+        );
+        assert_eq!(coded.status_code(), StatusCode::CONFLICT);
+    }
+
+    #[test]
+    fn test_validation_error_collects_every_field() {
+        let mut errors = ValidationError::default();
+        assert!(std::mem::take(&mut errors).into_result().is_ok());
+
+        errors.add("first_name", "First name cannot be empty");
+        errors.add("email", "Email cannot be empty");
+        errors.add("email", "Invalid email format");
+        let err = errors.into_result().unwrap_err();
+
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            json!({
+                "error": "First name cannot be empty",
+                "code": "VALIDATION_FAILED",
+                "fields": {
+                    "email": ["Email cannot be empty", "Invalid email format"],
+                    "first_name": ["First name cannot be empty"]
+                }
+            })
+        );
+    }
+}
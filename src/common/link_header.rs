@@ -0,0 +1,139 @@
+use actix_web::http::Uri;
+use url::form_urlencoded;
+
+/// Query parameter holding the page number
+const PAGE_PARAM: &str = "page";
+
+/// Request path with its query, `page` set to the given value and the rest kept in order
+fn page_uri(uri: &Uri, page: u64) -> String {
+    let mut query = form_urlencoded::Serializer::new(String::new());
+    let mut has_page = false;
+    for (key, value) in form_urlencoded::parse(uri.query().unwrap_or_default().as_bytes()) {
+        if key == PAGE_PARAM {
+            if !has_page {
+                query.append_pair(PAGE_PARAM, &page.to_string());
+                has_page = true;
+            }
+        } else {
+            query.append_pair(&key, &value);
+        }
+    }
+    if !has_page {
+        query.append_pair(PAGE_PARAM, &page.to_string());
+    }
+    format!("{}?{}", uri.path(), query.finish())
+}
+
+/// Value of the RFC 5988 `Link` header of a page of a list endpoint
+///
+/// Links are relative to the host and keep the other query parameters of the request, so
+/// filters and page size carry over. `first` and `last` are always present, `prev` and
+/// `next` only when such a page exists; an empty list has a single page.
+pub(crate) fn page_links(uri: &Uri, page: u32, total_pages: u64) -> String {
+    let page = page as u64;
+    let last = total_pages.max(1);
+
+    let mut links = vec![(1, "first")];
+    if page > 1 {
+        links.push(((page - 1).min(last), "prev"));
+    }
+    if page < last {
+        links.push((page + 1, "next"));
+    }
+    links.push((last, "last"));
+
+    links
+        .into_iter()
+        .map(|(target, rel)| format!("<{}>; rel=\"{}\"", page_uri(uri, target), rel))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rels(links: &str) -> Vec<(&str, &str)> {
+        links
+            .split(", ")
+            .map(|link| {
+                let (target, rel) = link.split_once("; ").unwrap();
+                (
+                    rel.trim_start_matches("rel=\"").trim_end_matches('"'),
+                    target.trim_matches(|c| c == '<' || c == '>'),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_first_page_links_forward_only() {
+        let uri: Uri = "/v1/admins/complaints?per_page=10&status=open"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            rels(&page_links(&uri, 1, 3)),
+            vec![
+                (
+                    "first",
+                    "/v1/admins/complaints?per_page=10&status=open&page=1"
+                ),
+                (
+                    "next",
+                    "/v1/admins/complaints?per_page=10&status=open&page=2"
+                ),
+                (
+                    "last",
+                    "/v1/admins/complaints?per_page=10&status=open&page=3"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_middle_page_links_both_ways() {
+        let uri: Uri = "/v1/students/projects?page=2&per_page=5".parse().unwrap();
+        let links = page_links(&uri, 2, 3);
+
+        assert_eq!(
+            rels(&links),
+            vec![
+                ("first", "/v1/students/projects?page=1&per_page=5"),
+                ("prev", "/v1/students/projects?page=1&per_page=5"),
+                ("next", "/v1/students/projects?page=3&per_page=5"),
+                ("last", "/v1/students/projects?page=3&per_page=5"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_last_page_links_backward_only() {
+        let uri: Uri = "/v1/admins/projects/4/members?search=ro%20ss&page=3"
+            .parse()
+            .unwrap();
+        let header = page_links(&uri, 3, 3);
+        let links = rels(&header);
+
+        assert_eq!(
+            links.iter().map(|(rel, _)| *rel).collect::<Vec<_>>(),
+            vec!["first", "prev", "last"]
+        );
+        assert_eq!(
+            links[1].1,
+            "/v1/admins/projects/4/members?search=ro+ss&page=2"
+        );
+        // Past the end, prev goes back to the last page; an empty list has one page
+        assert_eq!(
+            rels(&page_links(&uri, 9, 3))[1].1.rsplit('=').next(),
+            Some("3")
+        );
+        assert_eq!(
+            rels(&page_links(&uri, 1, 0)),
+            vec![
+                ("first", "/v1/admins/projects/4/members?search=ro+ss&page=1"),
+                ("last", "/v1/admins/projects/4/members?search=ro+ss&page=1"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use crate::common::json_error::{JsonError, ToJsonError};
+use actix_web::http::StatusCode;
+
+/// Highest total weight the components of a single deliverable can reach
+pub(crate) const MAX_TOTAL_WEIGHT: i32 = 100;
+
+/// Validates quantity and weight of a deliverable-component link
+///
+/// # Arguments
+/// * `quantity` - Required quantity of the component, at least 1
+/// * `weight` - Grading weight of the component, between 0 and `MAX_TOTAL_WEIGHT`
+/// * `other_weights` - Sum of the weights of the other links of the same deliverable
+pub(crate) fn validate_link(
+    quantity: i32, weight: i32, other_weights: i32,
+) -> Result<(), JsonError> {
+    if quantity < 1 {
+        return Err("Quantity must be at least 1".to_json_error(StatusCode::UNPROCESSABLE_ENTITY));
+    }
+
+    if !(0..=MAX_TOTAL_WEIGHT).contains(&weight) {
+        return Err(format!("Weight must be between 0 and {}", MAX_TOTAL_WEIGHT)
+            .to_json_error(StatusCode::UNPROCESSABLE_ENTITY));
+    }
+
+    if other_weights + weight > MAX_TOTAL_WEIGHT {
+        return Err(format!(
+            "Component weights of a deliverable cannot exceed {} (already assigned: {})",
+            MAX_TOTAL_WEIGHT, other_weights
+        )
+        .to_json_error(StatusCode::UNPROCESSABLE_ENTITY));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::ResponseError;
+
+    #[test]
+    fn test_valid_weight_set_is_accepted() {
+        // 30 + 30 already assigned, adding the last 40
+        assert!(validate_link(1, 40, 60).is_ok());
+        assert!(validate_link(3, 0, 100).is_ok());
+    }
+
+    #[test]
+    fn test_weights_over_100_are_rejected() {
+        let err = validate_link(1, 50, 60).unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let err = validate_link(1, 101, 0).unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let err = validate_link(1, -1, 0).unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[test]
+    fn test_quantity_must_be_positive() {
+        let err = validate_link(0, 10, 0).unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
@@ -0,0 +1,407 @@
+use crate::app_data::AppData;
+use crate::common::fields::{select_fields, FieldSet, FieldsQuery};
+use crate::common::json_error::{JsonError, ToJsonError, INVALID_PARAM};
+use crate::common::pagination::{PageLimits, PaginationQuery};
+use crate::common::params::invalid_query;
+use actix_web::dev::Payload;
+use actix_web::error::QueryPayloadError;
+use actix_web::http::StatusCode;
+use actix_web::web::{Data, Query};
+use actix_web::{FromRequest, HttpRequest, HttpResponse};
+use futures_util::future::{ready, Ready};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use utoipa::IntoParams;
+
+/// What a list endpoint lets clients sort, filter and select
+pub(crate) trait ListSpec {
+    /// Fields `sort` accepts
+    const SORTABLE: &'static [&'static str];
+    /// Fields `filter` accepts, none when the endpoint has dedicated filters
+    const FILTERABLE: &'static [&'static str];
+    /// Fields `fields` accepts
+    const FIELDS: &'static [&'static str];
+    /// Largest page of the endpoint, the configured one when `None`
+    const MAX_PER_PAGE: Option<u32> = None;
+}
+
+/// Query parameters shared by list endpoints, read by [`ListParams`]
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub(crate) struct ListQuery {
+    /// Page number, starting from 1
+    #[param(value_type = Option<u32>)]
+    page: Option<String>,
+    /// Number of items per page
+    #[param(value_type = Option<u32>)]
+    per_page: Option<String>,
+    /// Wrap the items in a `{ data, meta }` body instead of the default one
+    #[param(value_type = Option<bool>)]
+    envelope: Option<String>,
+    /// Comma separated fields to sort by, each optionally followed by `:asc` or `:desc`
+    #[param(example = "year:desc,name")]
+    sort: Option<String>,
+    /// Comma separated `field:value` pairs the items must all match
+    #[param(example = "active:true")]
+    filter: Option<String>,
+    /// Comma separated fields to return for each item, all of them when missing
+    #[param(example = "project_id,name")]
+    fields: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// One field of the requested order, earlier keys take precedence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SortKey {
+    pub field: &'static str,
+    pub direction: SortDirection,
+}
+
+/// Value one field of the items must have
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Filter {
+    pub field: &'static str,
+    pub value: String,
+}
+
+/// Pagination, sorting, filtering and field selection of a list endpoint
+///
+/// Extracted from the query string and checked against the [`ListSpec`] of the endpoint,
+/// so every list rejects the same mistakes with the same 400 `INVALID_PARAM` body. Other
+/// query parameters are left to the endpoint's own extractors.
+#[derive(Debug)]
+pub(crate) struct ListParams<S> {
+    pub pagination: PaginationQuery,
+    pub sort: Vec<SortKey>,
+    pub filters: Vec<Filter>,
+    pub fields: Option<FieldSet>,
+    spec: PhantomData<S>,
+}
+
+fn invalid(message: String) -> JsonError {
+    message
+        .to_json_error(StatusCode::BAD_REQUEST)
+        .with_code(INVALID_PARAM)
+}
+
+/// Comma separated entries of a parameter, blank ones skipped
+fn entries(value: &Option<String>) -> impl Iterator<Item = &str> {
+    value
+        .iter()
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+}
+
+/// Parsed value of the `name` parameter, read as text so a bad one can be named
+fn typed<T: FromStr>(
+    name: &str, value: Option<String>, expected: &str,
+) -> Result<Option<T>, JsonError> {
+    value
+        .map(|v| {
+            v.trim().parse().map_err(|_| {
+                invalid(format!(
+                    "Invalid value `{}` for `{}`, expected {}",
+                    v, name, expected
+                ))
+            })
+        })
+        .transpose()
+}
+
+/// The `allowed` field named `field`, with its static lifetime
+fn known_field(
+    allowed: &[&'static str], field: &str, what: &str,
+) -> Result<&'static str, JsonError> {
+    allowed
+        .iter()
+        .find(|f| **f == field)
+        .copied()
+        .ok_or_else(|| {
+            if allowed.is_empty() {
+                invalid(format!("This list cannot be {}", what))
+            } else {
+                invalid(format!(
+                    "Cannot {} by `{}`, expected one of: {}",
+                    what.trim_end_matches("ed"),
+                    field,
+                    allowed.join(", ")
+                ))
+            }
+        })
+}
+
+impl<S: ListSpec> ListParams<S> {
+    fn parse(query: ListQuery, limits: PageLimits) -> Result<Self, JsonError> {
+        let limits = S::MAX_PER_PAGE.map_or(limits, |max| limits.with_max(max));
+
+        let sort = entries(&query.sort)
+            .map(|entry| {
+                let (field, direction) = entry.split_once(':').unwrap_or((entry, "asc"));
+                let field = known_field(S::SORTABLE, field.trim(), "sorted")?;
+                let direction = match direction.trim().to_ascii_lowercase().as_str() {
+                    "asc" => SortDirection::Asc,
+                    "desc" => SortDirection::Desc,
+                    other => {
+                        return Err(invalid(format!(
+                            "Invalid sort direction `{}` for `{}`, expected `asc` or `desc`",
+                            other, field
+                        )))
+                    }
+                };
+                Ok(SortKey { field, direction })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let filters = entries(&query.filter)
+            .map(|entry| {
+                let (field, value) = entry.split_once(':').ok_or_else(|| {
+                    invalid(format!(
+                        "Invalid filter `{}`, expected `field:value`",
+                        entry
+                    ))
+                })?;
+                Ok(Filter {
+                    field: known_field(S::FILTERABLE, field.trim(), "filtered")?,
+                    value: value.trim().to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let fields = FieldsQuery {
+            fields: query.fields,
+        }
+        .parse(S::FIELDS)?;
+
+        Ok(Self {
+            pagination: PaginationQuery {
+                page: typed("page", query.page, "a positive integer")?,
+                per_page: typed("per_page", query.per_page, "a positive integer")?,
+                envelope: typed("envelope", query.envelope, "`true` or `false`")?,
+                limits: PageLimits::default(),
+            }
+            .within(limits),
+            sort,
+            filters,
+            fields,
+            spec: PhantomData,
+        })
+    }
+
+    /// Value the items must have for `field`, parsed, `None` when not filtered on
+    pub(crate) fn filter<T: FromStr>(&self, field: &str) -> Result<Option<T>, JsonError> {
+        let Some(filter) = self.filters.iter().find(|f| f.field == field) else {
+            return Ok(None);
+        };
+        filter.value.parse().map(Some).map_err(|_| {
+            invalid(format!(
+                "Invalid value `{}` for filter `{}`",
+                filter.value, filter.field
+            ))
+        })
+    }
+
+    /// Sorts `items` in the requested order, `compare` comparing two items on one field
+    ///
+    /// The sort is stable, items equal on every key keep their order.
+    pub(crate) fn sort_items<T>(
+        &self, items: &mut [T], compare: impl Fn(&str, &T, &T) -> Ordering,
+    ) {
+        items.sort_by(|a, b| {
+            self.sort
+                .iter()
+                .map(|key| match key.direction {
+                    SortDirection::Asc => compare(key.field, a, b),
+                    SortDirection::Desc => compare(key.field, b, a),
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+    }
+
+    /// Whether the client asked for a page rather than the whole list
+    fn is_paged(&self) -> bool {
+        let q = &self.pagination;
+        q.page.is_some() || q.per_page.is_some() || q.envelope.is_some()
+    }
+
+    /// Response of a list returning every item unless the client asked for a page
+    ///
+    /// Items keep only the requested fields. When paged, the response is built by
+    /// [`PaginationQuery::respond`] with the headers of every paginated list.
+    pub(crate) fn respond_all<T, B>(
+        &self, req: &HttpRequest, items: Vec<T>, bare: impl FnOnce(Vec<Value>) -> B,
+    ) -> HttpResponse
+    where
+        T: Serialize,
+        B: Serialize,
+    {
+        let items = select_fields(items, self.fields.as_ref());
+        if !self.is_paged() {
+            return HttpResponse::Ok().json(bare(items));
+        }
+
+        let total = items.len() as u64;
+        let page: Vec<Value> = items
+            .into_iter()
+            .skip(self.pagination.offset() as usize)
+            .take(self.pagination.limit() as usize)
+            .collect();
+        self.pagination.respond(req, page, total, bare)
+    }
+}
+
+impl<S: ListSpec> FromRequest for ListParams<S> {
+    type Error = JsonError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let limits = req
+            .app_data::<Data<AppData>>()
+            .map_or_else(PageLimits::default, |data| data.page_limits);
+        let parsed = Query::<ListQuery>::from_query(req.query_string())
+            .map_err(|e| match e {
+                QueryPayloadError::Deserialize(e) => invalid_query(e),
+                other => invalid_query(other),
+            })
+            .and_then(|query| Self::parse(query.into_inner(), limits));
+        ready(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+    use actix_web::ResponseError;
+
+    #[derive(Debug)]
+    struct Projects;
+
+    impl ListSpec for Projects {
+        const SORTABLE: &'static [&'static str] = &["name", "year"];
+        const FILTERABLE: &'static [&'static str] = &["active"];
+        const FIELDS: &'static [&'static str] = &["project_id", "name", "year"];
+    }
+
+    async fn extract(query: &str) -> Result<ListParams<Projects>, JsonError> {
+        let req = TestRequest::get()
+            .uri(&format!("/v1/admins/projects?{}", query))
+            .to_http_request();
+        ListParams::<Projects>::extract(&req).await
+    }
+
+    fn message(err: &JsonError) -> String {
+        serde_json::to_value(err).unwrap()["error"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[actix_web::test]
+    async fn test_non_numeric_per_page_is_rejected() {
+        let err = extract("per_page=ten").await.unwrap_err();
+
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+        let body = serde_json::to_value(&err).unwrap();
+        assert_eq!(body["code"], INVALID_PARAM);
+        assert_eq!(
+            message(&err),
+            "Invalid value `ten` for `per_page`, expected a positive integer"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_invalid_sort_direction_is_rejected() {
+        let err = extract("sort=name:up").await.unwrap_err();
+
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+        assert_eq!(serde_json::to_value(&err).unwrap()["code"], INVALID_PARAM);
+        assert_eq!(
+            message(&err),
+            "Invalid sort direction `up` for `name`, expected `asc` or `desc`"
+        );
+
+        let err = extract("sort=password").await.unwrap_err();
+        assert_eq!(
+            message(&err),
+            "Cannot sort by `password`, expected one of: name, year"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_combined_query_is_parsed() {
+        let params = extract(
+            "page=2&per_page=5&sort=year:DESC,name&filter=active:true&fields=name,year&other=1",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(params.pagination.page(), 2);
+        assert_eq!(params.pagination.per_page(), 5);
+        assert_eq!(
+            params.sort,
+            vec![
+                SortKey {
+                    field: "year",
+                    direction: SortDirection::Desc
+                },
+                SortKey {
+                    field: "name",
+                    direction: SortDirection::Asc
+                },
+            ]
+        );
+        assert_eq!(params.filter::<bool>("active").unwrap(), Some(true));
+        assert!(params.fields.is_some());
+
+        let mut items = vec![("b", 2025), ("a", 2026), ("c", 2026)];
+        params.sort_items(&mut items, |field, x, y| match field {
+            "year" => x.1.cmp(&y.1),
+            _ => x.0.cmp(y.0),
+        });
+        assert_eq!(items, vec![("a", 2026), ("c", 2026), ("b", 2025)]);
+    }
+
+    #[actix_web::test]
+    async fn test_malformed_filters_are_rejected() {
+        assert!(extract("filter=active").await.is_err());
+        assert!(extract("filter=year:2026").await.is_err());
+
+        let params = extract("filter=active:maybe").await.unwrap();
+        let err = params.filter::<bool>("active").unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_whole_list_unless_a_page_is_asked_for() {
+        let req = TestRequest::get().to_http_request();
+        let items: Vec<i32> = (1..=30).collect();
+
+        let all = extract("")
+            .await
+            .unwrap()
+            .respond_all(&req, items.clone(), |v| v);
+        let body: Vec<Value> =
+            serde_json::from_slice(&actix_web::body::to_bytes(all.into_body()).await.unwrap())
+                .unwrap();
+        assert_eq!(body.len(), 30);
+
+        let paged = extract("per_page=10&page=3")
+            .await
+            .unwrap()
+            .respond_all(&req, items, |v| v);
+        assert_eq!(paged.headers().get("X-Total-Count").unwrap(), "30");
+        let body: Vec<Value> =
+            serde_json::from_slice(&actix_web::body::to_bytes(paged.into_body()).await.unwrap())
+                .unwrap();
+        assert_eq!(body, (21..=30).map(Value::from).collect::<Vec<_>>());
+    }
+}
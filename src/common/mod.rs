@@ -1 +1,25 @@
+pub mod access;
+pub mod batch_delete;
+pub mod batch_result;
+pub mod client_ip;
+pub mod created;
+pub mod csv;
+pub mod date_format;
+pub mod deadlines;
+pub mod fields;
+pub mod frontend_url;
 pub mod json_error;
+pub mod link_header;
+pub mod link_weights;
+pub mod list_params;
+pub mod negotiation;
+pub mod pagination;
+pub mod params;
+pub mod project_freeze;
+pub mod rate_limit;
+pub mod reset_token;
+pub mod signed_url;
+pub mod slug;
+pub mod timestamps;
+pub mod upload_scanner;
+pub mod upload_storage;
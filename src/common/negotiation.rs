@@ -0,0 +1,273 @@
+use crate::common::json_error::error_with_log_id;
+use actix_web::body::{to_bytes, BoxBody, EitherBody, MessageBody};
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{self, Accept, Header, HeaderValue};
+use actix_web::http::StatusCode;
+use actix_web::mime;
+use actix_web::{Error, HttpRequest};
+use futures_util::future::{ready, LocalBoxFuture, Ready};
+use serde_json::Value;
+use std::rc::Rc;
+
+/// Whether the client prefers a plain text body over JSON
+///
+/// The most preferred media type of `Accept` decides: `text/plain` and `text/*` pick text,
+/// anything else, a missing or an unparsable header keep the JSON default.
+fn prefers_plain_text(req: &HttpRequest) -> bool {
+    let Ok(accept) = Accept::parse(req) else {
+        return false;
+    };
+    accept
+        .ranked()
+        .into_iter()
+        .find_map(
+            |mime| match (mime.type_().as_str(), mime.subtype().as_str()) {
+                ("text", "plain") | ("text", "*") => Some(true),
+                ("application", "json") | ("application", "*") | ("*", "*") => Some(false),
+                _ => None,
+            },
+        )
+        .unwrap_or(false)
+}
+
+/// Plain text version of a `JsonError` or `ValidationError` body: the message, then the
+/// code and log id on their own lines when present
+fn plain_text(body: &[u8]) -> Option<String> {
+    let body: Value = serde_json::from_slice(body).ok()?;
+    let mut text = body.get("error")?.as_str()?.to_string();
+    for key in ["code", "log_id"] {
+        if let Some(value) = body.get(key).and_then(Value::as_str) {
+            text.push_str(&format!("\n{}: {}", key, value));
+        }
+    }
+    if let Some(fields) = body.get("fields").and_then(Value::as_object) {
+        for (field, messages) in fields {
+            for message in messages.as_array().into_iter().flatten() {
+                if let Some(message) = message.as_str() {
+                    text.push_str(&format!("\n{}: {}", field, message));
+                }
+            }
+        }
+    }
+    text.push('\n');
+    Some(text)
+}
+
+/// Whether `res` carries a JSON error body
+fn is_json_error<B>(res: &ServiceResponse<B>) -> bool {
+    (res.status().is_client_error() || res.status().is_server_error())
+        && res
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<mime::Mime>().ok())
+            .is_some_and(|m| m.essence_str() == mime::APPLICATION_JSON.essence_str())
+}
+
+/// Replaces a JSON error body by its plain text version, left as is if it is not one
+async fn rewrite_as_text<B: MessageBody>(
+    res: ServiceResponse<B>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let (req, res) = res.into_parts();
+    let (res, body) = res.into_parts();
+    let body = to_bytes(body).await.map_err(|e| {
+        error_with_log_id(
+            format!("unable to read an error body to negotiate: {}", e.into()),
+            "Internal server error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })?;
+
+    let res = match plain_text(&body) {
+        Some(text) => {
+            let mut res = res.set_body(text);
+            res.headers_mut().insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("text/plain; charset=utf-8"),
+            );
+            res.map_into_boxed_body()
+        }
+        None => res.set_body(body).map_into_boxed_body(),
+    };
+    Ok(ServiceResponse::new(req, res))
+}
+
+/// Answers errors in plain text to clients that ask for it with `Accept: text/plain`
+///
+/// Error bodies are JSON by default. When the client prefers `text/plain`, the JSON error
+/// of a response is rewritten as text, keeping its status and headers such as
+/// `Retry-After`. Successful responses are never touched.
+pub(crate) struct ErrorNegotiation;
+
+impl<S, B> Transform<S, ServiceRequest> for ErrorNegotiation
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = ErrorNegotiationService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ErrorNegotiationService {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub(crate) struct ErrorNegotiationService<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for ErrorNegotiationService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        Box::pin(async move {
+            let plain_text = prefers_plain_text(req.request());
+            let res = service.call(req).await?;
+            if !plain_text || !is_json_error(&res) {
+                return Ok(res.map_into_left_body());
+            }
+            Ok(rewrite_as_text(res).await?.map_into_right_body())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::not_found;
+    use crate::common::json_error::{JsonError, ToJsonError, RATE_LIMITED};
+    use actix_web::http::header::RETRY_AFTER;
+    use actix_web::test::{call_service, init_service, read_body, TestRequest};
+    use actix_web::{web, App, HttpResponse, ResponseError};
+
+    async fn limited() -> HttpResponse {
+        let mut response = "Too many requests, please try again later"
+            .to_json_error(StatusCode::TOO_MANY_REQUESTS)
+            .with_code(RATE_LIMITED)
+            .error_response();
+        response
+            .headers_mut()
+            .insert(RETRY_AFTER, HeaderValue::from(30));
+        response
+    }
+
+    async fn failing() -> Result<HttpResponse, JsonError> {
+        Err("Group not found".to_json_error(StatusCode::NOT_FOUND))
+    }
+
+    async fn ok() -> HttpResponse {
+        HttpResponse::Ok().json(serde_json::json!({"status": "ok"}))
+    }
+
+    fn request(uri: &str, accept: Option<&str>) -> TestRequest {
+        let request = TestRequest::get().uri(uri);
+        match accept {
+            Some(accept) => request.insert_header((header::ACCEPT, accept)),
+            None => request,
+        }
+    }
+
+    macro_rules! app {
+        () => {
+            init_service(
+                App::new()
+                    .wrap(ErrorNegotiation)
+                    .route("/limited", web::get().to(limited))
+                    .route("/failing", web::get().to(failing))
+                    .route("/ok", web::get().to(ok))
+                    .default_service(web::to(not_found)),
+            )
+            .await
+        };
+    }
+
+    fn content_type<B>(res: &ServiceResponse<B>) -> &str {
+        res.headers()
+            .get(header::CONTENT_TYPE)
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+
+    #[actix_web::test]
+    async fn test_errors_are_json_by_default() {
+        let app = app!();
+
+        for accept in [None, Some("application/json"), Some("*/*")] {
+            let res = call_service(&app, request("/failing", accept).to_request()).await;
+            assert_eq!(res.status(), StatusCode::NOT_FOUND);
+            assert_eq!(content_type(&res), "application/json");
+            let body: Value = serde_json::from_slice(&read_body(res).await).unwrap();
+            assert_eq!(body["error"], "Group not found");
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_errors_are_plain_text_when_requested() {
+        let app = app!();
+
+        let res = call_service(&app, request("/limited", Some("text/plain")).to_request()).await;
+
+        assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(content_type(&res).starts_with("text/plain"));
+        assert_eq!(res.headers().get(RETRY_AFTER).unwrap(), "30");
+        assert_eq!(
+            read_body(res).await,
+            "Too many requests, please try again later\ncode: RATE_LIMITED\n"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_unknown_routes_are_negotiated() {
+        let app = app!();
+
+        let json = call_service(&app, request("/missing", None).to_request()).await;
+        assert_eq!(json.status(), StatusCode::NOT_FOUND);
+        assert_eq!(content_type(&json), "application/json");
+
+        let text = call_service(
+            &app,
+            request("/missing", Some("text/plain;q=0.9, application/json;q=0.5")).to_request(),
+        )
+        .await;
+        assert_eq!(text.status(), StatusCode::NOT_FOUND);
+        assert_eq!(read_body(text).await, "Resource not found\n");
+    }
+
+    #[actix_web::test]
+    async fn test_successful_responses_are_untouched() {
+        let app = app!();
+
+        let res = call_service(&app, request("/ok", Some("text/plain")).to_request()).await;
+
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(content_type(&res), "application/json");
+    }
+
+    #[test]
+    fn test_validation_fields_are_listed() {
+        let body = br#"{"error":"Name cannot be empty","code":"VALIDATION_FAILED","fields":{"name":["Name cannot be empty"]}}"#;
+
+        assert_eq!(
+            plain_text(body).unwrap(),
+            "Name cannot be empty\ncode: VALIDATION_FAILED\nname: Name cannot be empty\n"
+        );
+        assert_eq!(plain_text(b"not json"), None);
+    }
+}
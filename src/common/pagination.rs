@@ -0,0 +1,368 @@
+use crate::common::link_header::page_links;
+use actix_web::http::header::{LINK, WARNING};
+use actix_web::{HttpRequest, HttpResponse, HttpResponseBuilder};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Page size used when the client does not request one, unless configured otherwise
+pub(crate) const DEFAULT_PER_PAGE: u32 = 20;
+/// Largest page size a client can request, unless configured otherwise
+pub(crate) const MAX_PER_PAGE: u32 = 100;
+
+pub(crate) const TOTAL_COUNT_HEADER: &str = "X-Total-Count";
+pub(crate) const PAGE_HEADER: &str = "X-Page";
+pub(crate) const PER_PAGE_HEADER: &str = "X-Per-Page";
+
+/// Page size bounds of a list endpoint
+///
+/// The configured limits apply everywhere; an endpoint whose pages are cheaper or more
+/// expensive than average derives its own with [`PageLimits::with_max`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PageLimits {
+    pub default_per_page: u32,
+    pub max_per_page: u32,
+}
+
+impl Default for PageLimits {
+    fn default() -> Self {
+        Self {
+            default_per_page: DEFAULT_PER_PAGE,
+            max_per_page: MAX_PER_PAGE,
+        }
+    }
+}
+
+impl PageLimits {
+    /// Same limits with another largest page size, lowering the default if it no longer fits
+    pub(crate) fn with_max(self, max_per_page: u32) -> Self {
+        Self {
+            default_per_page: self.default_per_page.min(max_per_page),
+            max_per_page,
+        }
+    }
+}
+
+/// Pagination query parameters shared by list endpoints
+///
+/// Pages are 1-based; missing or out of range values fall back to sane defaults. Page sizes
+/// above the maximum are clamped and reported with a `Warning` header.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub(crate) struct PaginationQuery {
+    /// Page number, starting from 1
+    pub page: Option<u32>,
+    /// Number of items per page
+    pub per_page: Option<u32>,
+    /// Wrap the items in a `{ data, meta }` body instead of the default one
+    pub envelope: Option<bool>,
+    /// Page size bounds of the endpoint, set with [`PaginationQuery::within`]
+    #[serde(skip)]
+    #[param(ignore)]
+    pub limits: PageLimits,
+}
+
+/// `count_only` flag of list endpoints, for clients that only need the total
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub(crate) struct CountQuery {
+    /// Only count the matching items, answering `{ total }` without any of them
+    pub count_only: Option<bool>,
+}
+
+impl CountQuery {
+    pub(crate) fn is_set(&self) -> bool {
+        self.count_only.unwrap_or(false)
+    }
+}
+
+/// Body of a list endpoint called with `count_only=true`
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct CountResponse {
+    #[schema(example = 45)]
+    pub total: u64,
+}
+
+/// Builds the `200 OK` response of a list asked only for its total
+///
+/// The total is also in `X-Total-Count`, as on the paginated responses.
+pub(crate) fn count_response(total: u64) -> HttpResponse {
+    HttpResponse::Ok()
+        .insert_header((TOTAL_COUNT_HEADER, total.to_string()))
+        .json(CountResponse { total })
+}
+
+/// Description of the returned page, used in enveloped responses
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct PageMeta {
+    #[schema(example = 2)]
+    pub page: u32,
+    #[schema(example = 20)]
+    pub per_page: u32,
+    #[schema(example = 45)]
+    pub total: u64,
+    #[schema(example = 3)]
+    pub total_pages: u64,
+}
+
+/// Enveloped list response, returned when `envelope=true`
+#[derive(Debug, Serialize)]
+pub(crate) struct Page<T> {
+    pub data: Vec<T>,
+    pub meta: PageMeta,
+}
+
+impl PaginationQuery {
+    /// Applies the page size bounds of the endpoint, the defaults are used otherwise
+    pub(crate) fn within(self, limits: PageLimits) -> Self {
+        Self { limits, ..self }
+    }
+
+    /// Whether the client asked for a page at all, listings that are returned whole by
+    /// default only paginate when it did
+    pub(crate) fn is_requested(&self) -> bool {
+        self.page.is_some() || self.per_page.is_some()
+    }
+
+    /// Requested page, never lower than 1
+    pub(crate) fn page(&self) -> u32 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    /// Requested page size, clamped to `1..=max_per_page`
+    pub(crate) fn per_page(&self) -> u32 {
+        self.per_page
+            .unwrap_or(self.limits.default_per_page)
+            .clamp(1, self.limits.max_per_page.max(1))
+    }
+
+    /// Whether the client asked for more items per page than the endpoint allows
+    pub(crate) fn is_clamped(&self) -> bool {
+        self.per_page
+            .is_some_and(|per_page| per_page > self.limits.max_per_page)
+    }
+
+    /// Value for the `LIMIT` clause of the page query
+    pub(crate) fn limit(&self) -> i64 {
+        self.per_page() as i64
+    }
+
+    /// Value for the `OFFSET` clause of the page query
+    pub(crate) fn offset(&self) -> i64 {
+        (self.page() as i64 - 1) * self.per_page() as i64
+    }
+
+    /// Adds the pagination headers describing the returned page to a response
+    pub(crate) fn insert_headers(&self, builder: &mut HttpResponseBuilder, total: u64) {
+        builder
+            .insert_header((TOTAL_COUNT_HEADER, total.to_string()))
+            .insert_header((PAGE_HEADER, self.page().to_string()))
+            .insert_header((PER_PAGE_HEADER, self.per_page().to_string()));
+        if self.is_clamped() {
+            builder.insert_header((
+                WARNING,
+                format!(
+                    "299 - \"per_page reduced to the maximum of {}\"",
+                    self.per_page()
+                ),
+            ));
+        }
+    }
+
+    /// Metadata of the returned page given the total number of items
+    pub(crate) fn meta(&self, total: u64) -> PageMeta {
+        let per_page = self.per_page();
+        PageMeta {
+            page: self.page(),
+            per_page,
+            total,
+            total_pages: total.div_ceil(per_page as u64),
+        }
+    }
+
+    /// Builds the `200 OK` response of a list endpoint
+    ///
+    /// The pagination headers are always set, along with a `Link` header pointing at the
+    /// neighbouring pages of `req`. The body is `{ data, meta }` when the client asked for
+    /// an envelope, otherwise the endpoint's own body built by `bare`. An empty collection,
+    /// or a page past the last one, is still a `200 OK` with no items: `404` is kept for a
+    /// single resource that does not exist.
+    pub(crate) fn respond<T, B>(
+        &self, req: &HttpRequest, items: Vec<T>, total: u64, bare: impl FnOnce(Vec<T>) -> B,
+    ) -> HttpResponse
+    where
+        T: Serialize,
+        B: Serialize,
+    {
+        let mut builder = HttpResponse::Ok();
+        self.insert_headers(&mut builder, total);
+        builder.insert_header((
+            LINK,
+            page_links(req.uri(), self.page(), self.meta(total).total_pages),
+        ));
+
+        if self.envelope.unwrap_or(false) {
+            builder.json(Page {
+                data: items,
+                meta: self.meta(total),
+            })
+        } else {
+            builder.json(bare(items))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::body::to_bytes;
+    use actix_web::web::Query;
+
+    fn query(page: Option<u32>, per_page: Option<u32>) -> PaginationQuery {
+        PaginationQuery {
+            page,
+            per_page,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_defaults_when_missing() {
+        let q = query(None, None);
+        assert_eq!(q.page(), 1);
+        assert_eq!(q.per_page(), DEFAULT_PER_PAGE);
+        assert_eq!(q.offset(), 0);
+    }
+
+    #[test]
+    fn test_page_is_requested_by_page_or_size() {
+        assert!(!query(None, None).is_requested());
+        assert!(query(Some(2), None).is_requested());
+        assert!(query(None, Some(10)).is_requested());
+    }
+
+    #[test]
+    fn test_out_of_range_values_are_clamped() {
+        let q = query(Some(0), Some(0));
+        assert_eq!(q.page(), 1);
+        assert_eq!(q.per_page(), 1);
+
+        let q = query(Some(2), Some(10_000));
+        assert_eq!(q.per_page(), MAX_PER_PAGE);
+        assert_eq!(q.limit(), MAX_PER_PAGE as i64);
+    }
+
+    #[test]
+    fn test_offset_follows_page() {
+        let q = query(Some(3), Some(25));
+        assert_eq!(q.limit(), 25);
+        assert_eq!(q.offset(), 50);
+    }
+
+    #[test]
+    fn test_headers_describe_page() {
+        let q = query(Some(2), Some(5));
+        let mut builder = HttpResponse::Ok();
+        q.insert_headers(&mut builder, 12);
+        let response = builder.finish();
+
+        assert_eq!(response.headers().get(TOTAL_COUNT_HEADER).unwrap(), "12");
+        assert_eq!(response.headers().get(PAGE_HEADER).unwrap(), "2");
+        assert_eq!(response.headers().get(PER_PAGE_HEADER).unwrap(), "5");
+    }
+
+    #[test]
+    fn test_meta_counts_partial_last_page() {
+        let meta = query(Some(3), Some(20)).meta(45);
+        assert_eq!(meta.page, 3);
+        assert_eq!(meta.per_page, 20);
+        assert_eq!(meta.total, 45);
+        assert_eq!(meta.total_pages, 3);
+
+        assert_eq!(query(None, None).meta(0).total_pages, 0);
+    }
+
+    #[test]
+    fn test_oversized_page_is_clamped_with_a_warning() {
+        let q = query(Some(1), Some(500));
+        assert!(q.is_clamped());
+        assert_eq!(q.per_page(), MAX_PER_PAGE);
+
+        let mut builder = HttpResponse::Ok();
+        q.insert_headers(&mut builder, 1000);
+        let response = builder.finish();
+        assert_eq!(response.headers().get(PER_PAGE_HEADER).unwrap(), "100");
+        assert_eq!(
+            response.headers().get(WARNING).unwrap(),
+            "299 - \"per_page reduced to the maximum of 100\""
+        );
+
+        let mut builder = HttpResponse::Ok();
+        query(Some(1), Some(100)).insert_headers(&mut builder, 1000);
+        assert!(builder.finish().headers().get(WARNING).is_none());
+    }
+
+    #[test]
+    fn test_endpoint_limits_override_the_configured_ones() {
+        let configured = PageLimits {
+            default_per_page: 30,
+            max_per_page: 200,
+        };
+        assert_eq!(query(None, None).within(configured).per_page(), 30);
+        assert_eq!(query(None, Some(150)).within(configured).per_page(), 150);
+
+        // A heavy endpoint lowers the maximum, and the default with it
+        let heavy = configured.with_max(10);
+        assert_eq!(heavy.default_per_page, 10);
+        let q = query(None, Some(150)).within(heavy);
+        assert_eq!(q.per_page(), 10);
+        assert!(q.is_clamped());
+
+        let log = configured.with_max(500);
+        assert_eq!(query(None, None).within(log).per_page(), 30);
+        assert!(!query(None, Some(400)).within(log).is_clamped());
+    }
+
+    #[actix_web::test]
+    async fn test_count_only_answers_the_total_without_items() {
+        let query = Query::<CountQuery>::from_query("count_only=true&page=2")
+            .unwrap()
+            .into_inner();
+        assert!(query.is_set());
+        assert!(!CountQuery::default().is_set());
+
+        let response = count_response(12);
+
+        assert_eq!(response.headers().get(TOTAL_COUNT_HEADER).unwrap(), "12");
+        let body: serde_json::Value =
+            serde_json::from_slice(&to_bytes(response.into_body()).await.unwrap()).unwrap();
+        assert_eq!(body, serde_json::json!({ "total": 12 }));
+    }
+
+    #[actix_web::test]
+    async fn test_empty_collection_is_ok_with_no_items() {
+        let req = actix_web::test::TestRequest::get()
+            .uri("/v1/admins/complaints")
+            .to_http_request();
+        let body = |response: HttpResponse| async move {
+            assert_eq!(response.status(), actix_web::http::StatusCode::OK);
+            serde_json::from_slice::<serde_json::Value>(
+                &to_bytes(response.into_body()).await.unwrap(),
+            )
+            .unwrap()
+        };
+
+        let bare = query(None, None).respond(&req, Vec::<i32>::new(), 0, |items| items);
+        assert_eq!(body(bare).await, serde_json::json!([]));
+
+        let enveloped = PaginationQuery {
+            envelope: Some(true),
+            ..query(Some(3), None)
+        }
+        .respond(&req, Vec::<i32>::new(), 0, |items| items);
+        assert_eq!(
+            body(enveloped).await,
+            serde_json::json!({
+                "data": [],
+                "meta": { "page": 3, "per_page": DEFAULT_PER_PAGE, "total": 0, "total_pages": 0 }
+            })
+        );
+    }
+}
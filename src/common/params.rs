@@ -0,0 +1,120 @@
+use crate::common::json_error::{JsonError, ToJsonError, INVALID_PARAM};
+use actix_web::dev::Path as MatchInfo;
+use actix_web::error::{PathError, QueryPayloadError};
+use actix_web::http::StatusCode;
+use actix_web::web::{PathConfig, QueryConfig};
+use actix_web::HttpRequest;
+use std::fmt::Display;
+
+/// Names the path parameter the typed extractor failed on
+///
+/// The deserializer only reports the rejected value, so the parameter is found by matching
+/// it against the values of the route.
+fn invalid_path_message<T: actix_web::dev::ResourcePath>(
+    params: &MatchInfo<T>, error: &PathError,
+) -> String {
+    let PathError::Deserialize(e) = error else {
+        return format!("Invalid path parameter: {}", error);
+    };
+    let reason = e.to_string();
+
+    let param = params.iter().find(|(_, value)| {
+        reason
+            .strip_prefix("can not parse ")
+            .is_some_and(|rest| rest.starts_with(&format!("{:?}", value)))
+    });
+    match param {
+        Some((name, value)) => format!("Invalid path parameter `{}`: {:?}", name, value),
+        None => format!("Invalid path parameter: {}", reason),
+    }
+}
+
+/// Path extractor configuration, bad parameters are a 400 `INVALID_PARAM` instead of a 404
+pub(crate) fn path_config() -> PathConfig {
+    PathConfig::default().error_handler(|error, req: &HttpRequest| {
+        invalid_path_message(req.match_info(), &error)
+            .to_json_error(StatusCode::BAD_REQUEST)
+            .with_code(INVALID_PARAM)
+            .into()
+    })
+}
+
+/// Error of a malformed query string, a 400 `INVALID_PARAM`
+pub(crate) fn invalid_query(reason: impl Display) -> JsonError {
+    format!("Invalid query parameters: {}", reason)
+        .to_json_error(StatusCode::BAD_REQUEST)
+        .with_code(INVALID_PARAM)
+}
+
+/// Query extractor configuration, bad parameters are a 400 `INVALID_PARAM` in the usual JSON body
+pub(crate) fn query_config() -> QueryConfig {
+    QueryConfig::default().error_handler(|error, _req: &HttpRequest| {
+        match &error {
+            QueryPayloadError::Deserialize(e) => invalid_query(e),
+            other => invalid_query(other),
+        }
+        .into()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::web::{self, Path, Query};
+    use actix_web::{test, App, HttpResponse};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct PageQuery {
+        #[allow(dead_code)]
+        page: Option<u32>,
+    }
+
+    async fn project(_id: Path<i32>) -> HttpResponse {
+        HttpResponse::Ok().finish()
+    }
+
+    async fn coordinator(_ids: Path<(i32, i32)>, _query: Query<PageQuery>) -> HttpResponse {
+        HttpResponse::Ok().finish()
+    }
+
+    async fn call(uri: &str) -> (StatusCode, serde_json::Value) {
+        let app = test::init_service(
+            App::new()
+                .app_data(path_config())
+                .app_data(query_config())
+                .route("/projects/{id}", web::get().to(project))
+                .route(
+                    "/projects/{project_id}/coordinators/{admin_id}",
+                    web::get().to(coordinator),
+                ),
+        )
+        .await;
+        let response =
+            test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+        let status = response.status();
+        let body = test::read_body(response).await;
+        (status, serde_json::from_slice(&body).unwrap_or_default())
+    }
+
+    #[actix_web::test]
+    async fn test_non_numeric_id_is_a_bad_request() {
+        let (status, body) = call("/projects/abc").await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], INVALID_PARAM);
+        assert!(body["error"].as_str().unwrap().contains("`id`"));
+        assert_eq!(call("/projects/3").await.0, StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn test_the_failing_parameter_is_named() {
+        let (status, body) = call("/projects/3/coordinators/x1").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body["error"].as_str().unwrap().contains("`admin_id`"));
+
+        let (status, body) = call("/projects/3/coordinators/4?page=first").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], INVALID_PARAM);
+    }
+}
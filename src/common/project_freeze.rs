@@ -0,0 +1,78 @@
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError, PROJECT_FROZEN};
+use crate::database::repositories::{groups_repository, projects_repository};
+use actix_web::http::StatusCode;
+use welds::connections::postgres::PostgresClient;
+
+/// Rejects a mutation of a frozen project, reads never go through this check
+pub(crate) fn ensure_not_frozen(frozen: bool) -> Result<(), JsonError> {
+    if frozen {
+        Err("The project is frozen"
+            .to_json_error(StatusCode::CONFLICT)
+            .with_code(PROJECT_FROZEN))
+    } else {
+        Ok(())
+    }
+}
+
+/// Loads the project and rejects the mutation when it is frozen
+///
+/// A missing project passes, so the handler reports it with its own message.
+pub(crate) async fn ensure_project_not_frozen(
+    db: &PostgresClient, project_id: i32,
+) -> Result<(), JsonError> {
+    let project = projects_repository::get_by_id(db, project_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch project {}: {}", project_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+    ensure_not_frozen(project.is_some_and(|p| p.frozen))
+}
+
+/// Rejects the mutation when the project of the group is frozen, a missing group passes
+pub(crate) async fn ensure_group_not_frozen(
+    db: &PostgresClient, group_id: i32,
+) -> Result<(), JsonError> {
+    let group = groups_repository::get_by_id(db, group_id)
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to fetch group {}: {}", group_id, e),
+                "Database error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+    match group {
+        Some(group) => ensure_project_not_frozen(db, group.project_id).await,
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::ResponseError;
+
+    #[test]
+    fn test_frozen_project_rejects_mutations() {
+        let err = ensure_not_frozen(true).unwrap_err();
+
+        assert_eq!(err.status_code(), StatusCode::CONFLICT);
+        assert_eq!(
+            serde_json::to_value(&err).unwrap()["code"],
+            serde_json::json!(PROJECT_FROZEN)
+        );
+    }
+
+    #[test]
+    fn test_unfrozen_project_accepts_mutations() {
+        assert!(ensure_not_frozen(false).is_ok());
+    }
+}
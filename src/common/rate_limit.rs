@@ -0,0 +1,256 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, ToJsonError, RATE_LIMITED};
+use actix_web::body::EitherBody;
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{HeaderMap, HeaderName, HeaderValue, RETRY_AFTER};
+use actix_web::http::StatusCode;
+use actix_web::web::Data;
+use actix_web::{Error, ResponseError};
+use futures_util::future::{ready, LocalBoxFuture, Ready};
+use log::warn;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub(crate) const LIMIT_HEADER: HeaderName = HeaderName::from_static("x-ratelimit-limit");
+pub(crate) const REMAINING_HEADER: HeaderName = HeaderName::from_static("x-ratelimit-remaining");
+pub(crate) const RESET_HEADER: HeaderName = HeaderName::from_static("x-ratelimit-reset");
+
+/// Budget of a client after one of its requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RateLimitStatus {
+    pub limit: u32,
+    pub remaining: u32,
+    /// Time until the window ends and the budget is restored
+    pub reset_after: Duration,
+    /// Whether the request fits in the budget
+    pub allowed: bool,
+}
+
+impl RateLimitStatus {
+    /// Whole seconds until the reset, rounded up so clients never retry too early
+    fn reset_secs(&self) -> u64 {
+        let secs = self.reset_after.as_secs();
+        if self.reset_after.subsec_nanos() > 0 {
+            secs + 1
+        } else {
+            secs
+        }
+    }
+
+    /// Adds the `X-RateLimit-*` headers, and `Retry-After` when the request was refused
+    fn insert_headers(&self, headers: &mut HeaderMap) {
+        let reset = self.reset_secs();
+        headers.insert(LIMIT_HEADER, HeaderValue::from(self.limit));
+        headers.insert(REMAINING_HEADER, HeaderValue::from(self.remaining));
+        headers.insert(RESET_HEADER, HeaderValue::from(reset));
+        if !self.allowed {
+            headers.insert(RETRY_AFTER, HeaderValue::from(reset.max(1)));
+        }
+    }
+}
+
+/// Requests of one client in the current window
+struct Window {
+    started_at: Instant,
+    count: u32,
+}
+
+/// Number of requests each client made in a fixed window, shared between workers
+///
+/// Every client gets `limit` requests per `window`, the window starting with its first
+/// request. Expired windows are dropped on every access.
+#[derive(Clone)]
+pub(crate) struct RateLimiter {
+    windows: Arc<Mutex<HashMap<String, Window>>>,
+    limit: u32,
+    window: Duration,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            windows: Arc::new(Mutex::new(HashMap::new())),
+            limit,
+            window,
+        }
+    }
+
+    /// Counts a request of `client` and returns what is left of its budget
+    pub(crate) fn hit(&self, client: &str) -> RateLimitStatus {
+        self.hit_at(client, Instant::now())
+    }
+
+    fn hit_at(&self, client: &str, now: Instant) -> RateLimitStatus {
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        windows.retain(|_, w| now.duration_since(w.started_at) < self.window);
+
+        let window = windows.entry(client.to_string()).or_insert(Window {
+            started_at: now,
+            count: 0,
+        });
+        let allowed = window.count < self.limit;
+        if allowed {
+            window.count += 1;
+        }
+
+        RateLimitStatus {
+            limit: self.limit,
+            remaining: self.limit - window.count,
+            reset_after: self.window - now.duration_since(window.started_at),
+            allowed,
+        }
+    }
+}
+
+/// Limits the requests each client address makes to the wrapped scope
+///
+/// Every response carries the budget of the client in `X-RateLimit-Limit`,
+/// `X-RateLimit-Remaining` and `X-RateLimit-Reset` (seconds), so clients can back off
+/// before being refused. Over budget requests get a 429 `RATE_LIMITED` with `Retry-After`.
+pub(crate) struct RateLimit;
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimit
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = RateLimitService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RateLimitService {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub(crate) struct RateLimitService<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimitService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        Box::pin(async move {
+            let Some(data) = req.app_data::<Data<AppData>>().cloned() else {
+                let error = error_with_log_id(
+                    "RateLimit used without application data",
+                    "Internal server error",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                );
+                return Ok(req.error_response(error).map_into_right_body());
+            };
+
+            let client = data
+                .trusted_proxies
+                .service_client_ip(&req)
+                .map_or_else(|| "unknown".to_string(), |ip| ip.to_string());
+            let status = data.auth_rate_limiter.hit(&client);
+
+            if !status.allowed {
+                warn!(
+                    "rate limit exceeded by {}: {} {}",
+                    client,
+                    req.method(),
+                    req.path()
+                );
+                let mut response = "Too many requests, please try again later"
+                    .to_json_error(StatusCode::TOO_MANY_REQUESTS)
+                    .with_code(RATE_LIMITED)
+                    .error_response();
+                status.insert_headers(response.headers_mut());
+                return Ok(req.into_response(response).map_into_right_body());
+            }
+
+            let mut res = service.call(req).await?;
+            status.insert_headers(res.headers_mut());
+            Ok(res.map_into_left_body())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_app_data;
+    use actix_web::test::{call_service, init_service, read_body_json, TestRequest};
+    use actix_web::{web, App, HttpResponse};
+
+    async fn ok() -> HttpResponse {
+        HttpResponse::Ok().finish()
+    }
+
+    #[test]
+    fn test_window_restores_the_budget() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert_eq!(limiter.hit_at("192.0.2.1", now).remaining, 1);
+        assert_eq!(limiter.hit_at("192.0.2.1", now).remaining, 0);
+        let refused = limiter.hit_at("192.0.2.1", now + Duration::from_secs(20));
+        assert!(!refused.allowed);
+        assert_eq!(refused.reset_after, Duration::from_secs(40));
+        // Other clients have their own budget
+        assert!(limiter.hit_at("192.0.2.2", now).allowed);
+
+        let later = limiter.hit_at("192.0.2.1", now + Duration::from_secs(60));
+        assert!(later.allowed);
+        assert_eq!(later.remaining, 1);
+    }
+
+    #[actix_web::test]
+    async fn test_remaining_decrements_until_refused() {
+        let mut data = create_test_app_data().await;
+        data.auth_rate_limiter = RateLimiter::new(2, Duration::from_secs(60));
+        let app = init_service(
+            App::new().app_data(Data::new(data)).service(
+                web::scope("/auth")
+                    .wrap(RateLimit)
+                    .route("/login", web::post().to(ok)),
+            ),
+        )
+        .await;
+        let login = || {
+            TestRequest::post()
+                .uri("/auth/login")
+                .peer_addr("192.0.2.1:4000".parse().unwrap())
+                .to_request()
+        };
+
+        let mut remaining = Vec::new();
+        for _ in 0..2 {
+            let res = call_service(&app, login()).await;
+            assert_eq!(res.status(), StatusCode::OK);
+            assert_eq!(res.headers().get(LIMIT_HEADER).unwrap(), "2");
+            assert_eq!(res.headers().get(RESET_HEADER).unwrap(), "60");
+            assert!(!res.headers().contains_key(RETRY_AFTER));
+            remaining.push(res.headers().get(REMAINING_HEADER).unwrap().clone());
+        }
+        assert_eq!(remaining, vec!["1", "0"]);
+
+        let res = call_service(&app, login()).await;
+        assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(res.headers().get(REMAINING_HEADER).unwrap(), "0");
+        assert!(res.headers().contains_key(RESET_HEADER));
+        assert!(res.headers().contains_key(RETRY_AFTER));
+        let body: serde_json::Value = read_body_json(res).await;
+        assert_eq!(body["code"], RATE_LIMITED);
+    }
+}
@@ -0,0 +1,147 @@
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use crate::database::repositories::used_reset_tokens_repository;
+use actix_web::http::StatusCode;
+use confirm_email::error::Error as TokenError;
+use confirm_email::validate_token;
+use log::error;
+use sha2::{Digest, Sha256};
+use welds::connections::postgres::PostgresClient;
+use welds::Client;
+
+/// Hash under which a used reset token is stored
+pub(crate) fn hash_reset_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Turns the outcome of decrypting a reset token into its email, or the error to answer with
+///
+/// Forged or malformed tokens are bad requests, expired and used ones are gone.
+fn token_state(validated: Result<String, TokenError>, used: bool) -> Result<String, JsonError> {
+    match validated {
+        Ok(_) if used => {
+            Err("Password reset token was already used".to_json_error(StatusCode::GONE))
+        }
+        Ok(email) => Ok(email),
+        Err(TokenError::Expired(at)) => {
+            error!("expired password reset token, expired at {}", at);
+            Err("Password reset token has expired".to_json_error(StatusCode::GONE))
+        }
+        Err(e) => {
+            error!("invalid password reset token: {}", e);
+            Err("Invalid password reset token".to_json_error(StatusCode::BAD_REQUEST))
+        }
+    }
+}
+
+/// Checks the signature, expiry and prior use of a reset token without using it
+///
+/// Returns the email the token was issued for.
+pub(crate) async fn check_reset_token(
+    db: &PostgresClient, token: &str, secret: &str,
+) -> Result<String, JsonError> {
+    let validated = validate_token(token.to_string(), secret.to_string());
+    let used = match validated {
+        Ok(_) => used_reset_tokens_repository::is_used(db, &hash_reset_token(token))
+            .await
+            .map_err(|e| {
+                error_with_log_id(
+                    format!("unable to check password reset token: {}", e),
+                    "Password reset failed",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                )
+            })?,
+        Err(_) => false,
+    };
+
+    token_state(validated, used)
+}
+
+/// Whether recording a reset token as used claimed it for this request
+fn claimed(rows_affected: u64) -> Result<(), JsonError> {
+    if rows_affected == 0 {
+        return Err("Password reset token was already used".to_json_error(StatusCode::GONE));
+    }
+    Ok(())
+}
+
+/// Records a reset token as used, so that it cannot reset the password again
+///
+/// Must run before the password is written and in the same transaction: a concurrent request
+/// with the same token inserts nothing and is answered with a 410.
+pub(crate) async fn consume_reset_token(db: &impl Client, token: &str) -> Result<(), JsonError> {
+    let rows_affected = used_reset_tokens_repository::mark_used(db, &hash_reset_token(token))
+        .await
+        .map_err(|e| {
+            error_with_log_id(
+                format!("unable to record used password reset token: {}", e),
+                "Password reset failed",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+
+    claimed(rows_affected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::ResponseError;
+    use chrono::Utc;
+    use confirm_email::generate_token;
+
+    const SECRET: &str = "test-email-token-secret";
+    const EMAIL: &str = "student@test.com";
+
+    fn token() -> String {
+        generate_token(EMAIL.to_string(), SECRET.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_valid_token_returns_its_email() {
+        let validated = validate_token(token(), SECRET.to_string());
+
+        assert_eq!(token_state(validated, false).unwrap(), EMAIL);
+    }
+
+    #[test]
+    fn test_expired_token_is_gone() {
+        let err = token_state(Err(TokenError::Expired(Utc::now())), false).unwrap_err();
+
+        assert_eq!(err.status_code(), StatusCode::GONE);
+    }
+
+    #[test]
+    fn test_used_token_is_gone() {
+        let validated = validate_token(token(), SECRET.to_string());
+
+        let err = token_state(validated, true).unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::GONE);
+    }
+
+    #[test]
+    fn test_token_signed_with_another_secret_is_invalid() {
+        let validated = validate_token(token(), "another-secret".to_string());
+
+        let err = token_state(validated, false).unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_token_claimed_by_another_request_is_gone() {
+        assert!(claimed(1).is_ok());
+
+        let err = claimed(0).unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::GONE);
+    }
+
+    #[test]
+    fn test_hash_is_stable_and_hides_the_token() {
+        let token = token();
+
+        assert_eq!(hash_reset_token(&token), hash_reset_token(&token));
+        assert_ne!(hash_reset_token(&token), token);
+        assert_eq!(hash_reset_token(&token).len(), 64);
+    }
+}
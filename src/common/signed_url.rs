@@ -0,0 +1,141 @@
+use crate::common::json_error::{JsonError, ToJsonError};
+use actix_web::http::StatusCode;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+use utoipa::IntoParams;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Query of a signed download link, everything but the signature is covered by it
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct SignedDownloadQuery {
+    /// Version of the file, the current one when omitted
+    pub version: Option<i32>,
+    /// Unix time after which the link no longer works
+    pub expires: i64,
+    /// Hex encoded HMAC-SHA256 of the upload, version and expiry
+    pub signature: String,
+}
+
+/// Secret signing the links, links answer as if they did not exist when none is configured
+pub(crate) fn download_secret(secret: Option<&str>) -> Result<&str, JsonError> {
+    secret
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| "Signed download links are not enabled".to_json_error(StatusCode::NOT_FOUND))
+}
+
+/// Text signed for a link to `version` of an upload, `0` standing for the current one
+fn signed_text(upload_id: i32, version: Option<i32>, expires: i64) -> String {
+    format!("upload:{}:{}:{}", upload_id, version.unwrap_or(0), expires)
+}
+
+fn mac(secret: &str, upload_id: i32, version: Option<i32>, expires: i64) -> HmacSha256 {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(signed_text(upload_id, version, expires).as_bytes());
+    mac
+}
+
+/// Signature of a link to `version` of an upload, valid until `expires`
+pub(crate) fn sign_download(
+    secret: &str, upload_id: i32, version: Option<i32>, expires: DateTime<Utc>,
+) -> String {
+    hex::encode(
+        mac(secret, upload_id, version, expires.timestamp())
+            .finalize()
+            .into_bytes(),
+    )
+}
+
+/// Query string of a link to `version` of an upload, valid until `expires`
+pub(crate) fn download_query(
+    secret: &str, upload_id: i32, version: Option<i32>, expires: DateTime<Utc>,
+) -> String {
+    let signature = sign_download(secret, upload_id, version, expires);
+    match version {
+        Some(version) => format!(
+            "version={}&expires={}&signature={}",
+            version,
+            expires.timestamp(),
+            signature
+        ),
+        None => format!("expires={}&signature={}", expires.timestamp(), signature),
+    }
+}
+
+/// Checks a signed link to an upload, in constant time
+///
+/// Tampered links are forbidden, expired ones are gone. The signature is checked first so
+/// that a forged link never learns whether its expiry would have passed.
+pub(crate) fn verify_download(
+    secret: &str, upload_id: i32, query: &SignedDownloadQuery, now: DateTime<Utc>,
+) -> Result<(), JsonError> {
+    let valid = hex::decode(&query.signature).is_ok_and(|signature| {
+        mac(secret, upload_id, query.version, query.expires)
+            .verify_slice(&signature)
+            .is_ok()
+    });
+    if !valid {
+        return Err("Invalid download link".to_json_error(StatusCode::FORBIDDEN));
+    }
+    if now.timestamp() > query.expires {
+        return Err("This download link has expired".to_json_error(StatusCode::GONE));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::web::Query;
+    use actix_web::ResponseError;
+    use chrono::TimeDelta;
+
+    const SECRET: &str = "download_secret";
+
+    fn query(text: &str) -> SignedDownloadQuery {
+        Query::<SignedDownloadQuery>::from_query(text)
+            .unwrap()
+            .into_inner()
+    }
+
+    #[test]
+    fn test_signed_link_is_accepted_until_it_expires() {
+        let now = Utc::now();
+        let expires = now + TimeDelta::minutes(30);
+        let link = query(&download_query(SECRET, 12, Some(2), expires));
+
+        assert!(verify_download(SECRET, 12, &link, now).is_ok());
+        assert!(verify_download(SECRET, 12, &link, expires).is_ok());
+
+        let err = verify_download(SECRET, 12, &link, expires + TimeDelta::seconds(1)).unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::GONE);
+    }
+
+    #[test]
+    fn test_tampered_link_is_rejected() {
+        let now = Utc::now();
+        let link = download_query(SECRET, 12, None, now + TimeDelta::minutes(30));
+
+        let forbidden = |upload_id: i32, text: &str, secret: &str| {
+            verify_download(secret, upload_id, &query(text), now)
+                .unwrap_err()
+                .status_code()
+                == StatusCode::FORBIDDEN
+        };
+        assert!(forbidden(13, &link, SECRET));
+        assert!(forbidden(12, &link, "another_secret"));
+        assert!(forbidden(12, &format!("version=1&{}", link), SECRET));
+
+        let extended = link.replacen(
+            &format!("expires={}", (now + TimeDelta::minutes(30)).timestamp()),
+            &format!("expires={}", (now + TimeDelta::days(30)).timestamp()),
+            1,
+        );
+        assert_ne!(extended, link);
+        assert!(forbidden(12, &extended, SECRET));
+        assert!(forbidden(12, "expires=1&signature=not-hex", SECRET));
+    }
+}
@@ -0,0 +1,156 @@
+use crate::common::json_error::{JsonError, ToJsonError};
+use actix_web::http::StatusCode;
+
+/// Longest slug accepted or generated
+pub(crate) const MAX_SLUG_LEN: usize = 64;
+/// Slug used when a name has no usable characters
+const FALLBACK_SLUG: &str = "project";
+
+/// Turns a name into a slug: lowercase ascii letters and digits joined by single hyphens
+///
+/// Numeric results are prefixed so that they are never mistaken for an id.
+pub(crate) fn slugify(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.truncate(MAX_SLUG_LEN);
+    let slug = slug.trim_end_matches('-');
+
+    if slug.is_empty() {
+        FALLBACK_SLUG.to_string()
+    } else if slug.bytes().all(|b| b.is_ascii_digit()) {
+        format!("{}-{}", FALLBACK_SLUG, slug)
+    } else {
+        slug.to_string()
+    }
+}
+
+/// Picks `base`, or the first of `base-2`, `base-3`, ... not in `taken`
+pub(crate) fn unique_slug(base: &str, taken: &[String]) -> String {
+    if !taken.iter().any(|t| t == base) {
+        return base.to_string();
+    }
+
+    (2..)
+        .map(|n| {
+            let suffix = format!("-{}", n);
+            let stem = base[..base.len().min(MAX_SLUG_LEN - suffix.len())].trim_end_matches('-');
+            format!("{}{}", stem, suffix)
+        })
+        .find(|candidate| !taken.contains(candidate))
+        .expect("the suffixes never run out")
+}
+
+/// Checks a slug chosen by a client
+pub(crate) fn validate_slug(slug: &str) -> Result<(), JsonError> {
+    let well_formed = !slug.is_empty()
+        && slug.len() <= MAX_SLUG_LEN
+        && slug.split('-').all(|part| {
+            !part.is_empty()
+                && part
+                    .bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+        });
+
+    if !well_formed {
+        return Err(format!(
+            "Slug must be at most {} lowercase letters and digits, separated by single hyphens",
+            MAX_SLUG_LEN
+        )
+        .to_json_error(StatusCode::BAD_REQUEST));
+    }
+    if slug.parse::<i32>().is_ok() {
+        return Err("Slug must not be a number".to_json_error(StatusCode::BAD_REQUEST));
+    }
+    Ok(())
+}
+
+/// A project addressed in a path either by id or by slug
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ProjectRef {
+    Id(i32),
+    Slug(String),
+}
+
+impl From<String> for ProjectRef {
+    fn from(value: String) -> Self {
+        match value.parse() {
+            Ok(id) => ProjectRef::Id(id),
+            Err(_) => ProjectRef::Slug(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify_lowercases_and_hyphenates() {
+        assert_eq!(
+            slugify("Advanced Programming 2025"),
+            "advanced-programming-2025"
+        );
+        assert_eq!(slugify("  Rust -- Fair!  "), "rust-fair");
+        assert_eq!(slugify("Café Ü"), "caf");
+    }
+
+    #[test]
+    fn test_slugify_falls_back_and_avoids_numbers() {
+        assert_eq!(slugify("!!!"), "project");
+        assert_eq!(slugify("2025"), "project-2025");
+        assert_eq!(slugify(&"a".repeat(100)).len(), MAX_SLUG_LEN);
+    }
+
+    #[test]
+    fn test_generated_slugs_are_valid() {
+        for name in [
+            "Advanced Programming",
+            "2025",
+            "",
+            "a b-c_d",
+            &"x y ".repeat(40),
+        ] {
+            assert!(validate_slug(&slugify(name)).is_ok(), "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn test_unique_slug_suffixes_collisions() {
+        let taken = vec!["fair".to_string(), "fair-2".to_string()];
+
+        assert_eq!(unique_slug("other", &taken), "other");
+        assert_eq!(unique_slug("fair", &taken), "fair-3");
+
+        let long = "a".repeat(MAX_SLUG_LEN);
+        let suffixed = unique_slug(&long, std::slice::from_ref(&long));
+        assert_eq!(suffixed.len(), MAX_SLUG_LEN);
+        assert!(suffixed.ends_with("-2"));
+    }
+
+    #[test]
+    fn test_validate_slug() {
+        assert!(validate_slug("rust-fair-2025").is_ok());
+        assert!(validate_slug("").is_err());
+        assert!(validate_slug("Rust").is_err());
+        assert!(validate_slug("rust--fair").is_err());
+        assert!(validate_slug("-rust").is_err());
+        assert!(validate_slug("rust_fair").is_err());
+        assert!(validate_slug("42").is_err());
+        assert!(validate_slug(&"a".repeat(MAX_SLUG_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_project_ref_parses_ids_and_slugs() {
+        assert_eq!(ProjectRef::from("42".to_string()), ProjectRef::Id(42));
+        assert_eq!(
+            ProjectRef::from("rust-fair".to_string()),
+            ProjectRef::Slug("rust-fair".to_string())
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serializer;
+use std::future::Future;
+
+/// How [`rfc3339`] and [`rfc3339_opt`] serialize timestamps in the current response
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum TimestampFormat {
+    /// RFC3339 strings, see [`format`]
+    #[default]
+    Rfc3339,
+    /// Integer seconds since the Unix epoch, for legacy clients
+    Unix,
+}
+
+tokio::task_local! {
+    static TIMESTAMP_FORMAT: TimestampFormat;
+}
+
+/// Format of the timestamps serialized now, RFC3339 outside of [`with_format`]
+pub(crate) fn current_format() -> TimestampFormat {
+    TIMESTAMP_FORMAT.try_with(|f| *f).unwrap_or_default()
+}
+
+/// Runs `fut` with its timestamps serialized in `format`
+pub(crate) async fn with_format<T>(format: TimestampFormat, fut: impl Future<Output = T>) -> T {
+    TIMESTAMP_FORMAT.scope(format, fut).await
+}
+
+/// Formats a timestamp as RFC3339 in UTC with millisecond precision and a `Z` suffix
+pub(crate) fn format(timestamp: &DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// Serializes a timestamp field with [`format`], for `#[serde(serialize_with = ..)]`
+///
+/// Legacy clients asking for it through [`with_format`] get Unix seconds instead.
+pub(crate) fn rfc3339<S: Serializer>(
+    timestamp: &DateTime<Utc>, serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match current_format() {
+        TimestampFormat::Rfc3339 => serializer.serialize_str(&format(timestamp)),
+        TimestampFormat::Unix => serializer.serialize_i64(timestamp.timestamp()),
+    }
+}
+
+/// Same as [`rfc3339`] for optional timestamps, `None` is serialized as `null`
+pub(crate) fn rfc3339_opt<S: Serializer>(
+    timestamp: &Option<DateTime<Utc>>, serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match timestamp {
+        Some(timestamp) => rfc3339(timestamp, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Event {
+        #[serde(serialize_with = "rfc3339")]
+        created_at: DateTime<Utc>,
+        #[serde(serialize_with = "rfc3339_opt")]
+        closed_at: Option<DateTime<Utc>>,
+    }
+
+    #[test]
+    fn test_known_timestamp_serializes_to_exact_string() {
+        let created_at = Utc.with_ymd_and_hms(2026, 6, 10, 10, 0, 0).unwrap()
+            + chrono::Duration::microseconds(123_456);
+        let event = Event {
+            created_at,
+            closed_at: None,
+        };
+
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"created_at":"2026-06-10T10:00:00.123Z","closed_at":null}"#
+        );
+        let whole_second = Utc.with_ymd_and_hms(2026, 1, 2, 3, 4, 5).unwrap();
+        assert_eq!(format(&whole_second), "2026-01-02T03:04:05.000Z");
+    }
+
+    #[actix_web::test]
+    async fn test_unix_format_serializes_seconds() {
+        let event = Event {
+            created_at: Utc.with_ymd_and_hms(2026, 6, 10, 10, 0, 0).unwrap()
+                + chrono::Duration::milliseconds(999),
+            closed_at: Some(Utc.with_ymd_and_hms(2026, 6, 11, 0, 0, 0).unwrap()),
+        };
+
+        let unix = with_format(TimestampFormat::Unix, async {
+            serde_json::to_string(&event).unwrap()
+        })
+        .await;
+
+        assert_eq!(unix, r#"{"created_at":1781085600,"closed_at":1781136000}"#);
+        assert_eq!(current_format(), TimestampFormat::Rfc3339);
+    }
+}
@@ -0,0 +1,223 @@
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError, UPLOAD_REJECTED};
+use crate::config::Config;
+use actix_web::http::StatusCode;
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// How long a scan may take, connecting included, before the scanner counts as unavailable
+const SCAN_TIMEOUT: Duration = Duration::from_secs(30);
+/// Size of the chunks streamed to clamd
+const CLAMD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Verdict of a scanner on an uploaded file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ScanResult {
+    Clean,
+    /// The file is flagged, with the reason given by the scanner
+    Flagged(String),
+    /// The file could not be scanned, with the reason
+    Unavailable(String),
+}
+
+/// Check run on every uploaded file before it is stored
+#[async_trait]
+pub(crate) trait UploadScanner: Send + Sync {
+    async fn scan(&self, bytes: &[u8]) -> ScanResult;
+}
+
+/// Scanner accepting every file, used when no external scanner is configured
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct NoScanner;
+
+#[async_trait]
+impl UploadScanner for NoScanner {
+    async fn scan(&self, _bytes: &[u8]) -> ScanResult {
+        ScanResult::Clean
+    }
+}
+
+/// Scanner sending files to a ClamAV daemon over TCP, with its `INSTREAM` command
+#[derive(Debug, Clone)]
+pub(crate) struct ClamdScanner {
+    address: String,
+}
+
+impl ClamdScanner {
+    pub(crate) fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+        }
+    }
+
+    async fn instream(&self, bytes: &[u8]) -> std::io::Result<String> {
+        let mut stream = TcpStream::connect(&self.address).await?;
+        stream.write_all(b"zINSTREAM\0").await?;
+        for chunk in bytes.chunks(CLAMD_CHUNK_SIZE) {
+            stream
+                .write_all(&(chunk.len() as u32).to_be_bytes())
+                .await?;
+            stream.write_all(chunk).await?;
+        }
+        stream.write_all(&0u32.to_be_bytes()).await?;
+
+        let mut reply = Vec::new();
+        stream.read_to_end(&mut reply).await?;
+        Ok(String::from_utf8_lossy(&reply)
+            .trim_end_matches(['\0', '\n'])
+            .to_string())
+    }
+}
+
+/// Verdict in a clamd reply, like `stream: OK` or `stream: Eicar-Signature FOUND`
+fn parse_clamd_reply(reply: &str) -> ScanResult {
+    let verdict = reply.strip_prefix("stream: ").unwrap_or(reply);
+    if verdict == "OK" {
+        ScanResult::Clean
+    } else if let Some(signature) = verdict.strip_suffix(" FOUND") {
+        ScanResult::Flagged(signature.to_string())
+    } else {
+        ScanResult::Unavailable(format!("unexpected clamd reply {:?}", reply))
+    }
+}
+
+#[async_trait]
+impl UploadScanner for ClamdScanner {
+    async fn scan(&self, bytes: &[u8]) -> ScanResult {
+        match tokio::time::timeout(SCAN_TIMEOUT, self.instream(bytes)).await {
+            Ok(Ok(reply)) => parse_clamd_reply(&reply),
+            Ok(Err(e)) => ScanResult::Unavailable(format!("clamd at {}: {}", self.address, e)),
+            Err(_) => ScanResult::Unavailable(format!("clamd at {} timed out", self.address)),
+        }
+    }
+}
+
+/// Scanner selected by the config, ClamAV when `clamav_address` is set
+pub(crate) fn from_config(config: &Config) -> Arc<dyn UploadScanner> {
+    match config.clamav_address() {
+        Some(address) => Arc::new(ClamdScanner::new(address)),
+        None => Arc::new(NoScanner),
+    }
+}
+
+/// Scans an uploaded file, rejecting it with 422 `UPLOAD_REJECTED` when flagged
+///
+/// Files that cannot be scanned are refused too, rather than stored unchecked.
+pub(crate) async fn ensure_clean(
+    scanner: &dyn UploadScanner, bytes: &[u8],
+) -> Result<(), JsonError> {
+    match scanner.scan(bytes).await {
+        ScanResult::Clean => Ok(()),
+        ScanResult::Flagged(reason) => {
+            log::warn!("rejected an upload flagged by the scanner: {}", reason);
+            Err("The uploaded file was rejected by the content scanner"
+                .to_json_error(StatusCode::UNPROCESSABLE_ENTITY)
+                .with_code(UPLOAD_REJECTED))
+        }
+        ScanResult::Unavailable(reason) => Err(error_with_log_id(
+            format!("unable to scan an upload: {}", reason),
+            "Uploads cannot be checked right now, please try again later",
+            StatusCode::SERVICE_UNAVAILABLE,
+            log::Level::Error,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::ResponseError;
+    use tokio::net::TcpListener;
+
+    const FLAGGED_PAYLOAD: &[u8] = b"PK\x03\x04 known bad payload";
+
+    /// Flags the files containing `FLAGGED_PAYLOAD`
+    struct StubScanner;
+
+    #[async_trait]
+    impl UploadScanner for StubScanner {
+        async fn scan(&self, bytes: &[u8]) -> ScanResult {
+            if bytes == FLAGGED_PAYLOAD {
+                ScanResult::Flagged("Stub-Signature".to_string())
+            } else {
+                ScanResult::Clean
+            }
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_flagged_upload_is_rejected() {
+        let err = ensure_clean(&StubScanner, FLAGGED_PAYLOAD)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(serde_json::to_value(&err).unwrap()["code"], UPLOAD_REJECTED);
+    }
+
+    #[actix_web::test]
+    async fn test_clean_upload_passes() {
+        assert!(ensure_clean(&StubScanner, b"PK\x03\x04 homework")
+            .await
+            .is_ok());
+        assert!(ensure_clean(&NoScanner, FLAGGED_PAYLOAD).await.is_ok());
+    }
+
+    #[test]
+    fn test_clamd_replies_are_parsed() {
+        assert_eq!(parse_clamd_reply("stream: OK"), ScanResult::Clean);
+        assert_eq!(
+            parse_clamd_reply("stream: Eicar-Signature FOUND"),
+            ScanResult::Flagged("Eicar-Signature".to_string())
+        );
+        assert!(matches!(
+            parse_clamd_reply("INSTREAM size limit exceeded. ERROR"),
+            ScanResult::Unavailable(_)
+        ));
+    }
+
+    #[actix_web::test]
+    async fn test_clamd_scanner_streams_the_file() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let daemon = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut command = [0u8; 10];
+            socket.read_exact(&mut command).await.unwrap();
+            assert_eq!(&command, b"zINSTREAM\0");
+
+            let mut received = Vec::new();
+            loop {
+                let length = socket.read_u32().await.unwrap() as usize;
+                if length == 0 {
+                    break;
+                }
+                let mut chunk = vec![0u8; length];
+                socket.read_exact(&mut chunk).await.unwrap();
+                received.extend(chunk);
+            }
+            socket.write_all(b"stream: OK\0").await.unwrap();
+            received
+        });
+
+        let result = ClamdScanner::new(address).scan(FLAGGED_PAYLOAD).await;
+
+        assert_eq!(result, ScanResult::Clean);
+        assert_eq!(daemon.await.unwrap(), FLAGGED_PAYLOAD);
+    }
+
+    #[actix_web::test]
+    async fn test_unreachable_scanner_refuses_the_upload() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        drop(listener);
+
+        let err = ensure_clean(&ClamdScanner::new(address), b"PK\x03\x04")
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
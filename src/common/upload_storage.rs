@@ -0,0 +1,226 @@
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError};
+use actix_multipart::Multipart;
+use actix_web::http::StatusCode;
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+
+/// Content types a file can be uploaded as, each with the type its content must actually have
+///
+/// Browsers label ZIP archives differently by platform, and tools without a type for the
+/// extension send `application/octet-stream`; the sniffed content decides in every case.
+const ALLOWED_CONTENT_TYPES: [(&str, &str); 4] = [
+    ("application/zip", "application/zip"),
+    ("application/x-zip-compressed", "application/zip"),
+    ("application/x-zip", "application/zip"),
+    ("application/octet-stream", "application/zip"),
+];
+
+/// Content type a file sent without one is taken as
+const DEFAULT_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// Type the content of a file uploaded as `declared` must have, if it is allowed at all
+fn expected_content_type(declared: &str) -> Result<&'static str, JsonError> {
+    ALLOWED_CONTENT_TYPES
+        .iter()
+        .find(|(allowed, _)| allowed.eq_ignore_ascii_case(declared))
+        .map(|(_, actual)| *actual)
+        .ok_or_else(|| {
+            format!(
+                "Files of type {} cannot be uploaded, send a ZIP archive",
+                declared
+            )
+            .to_json_error(StatusCode::UNSUPPORTED_MEDIA_TYPE)
+        })
+}
+
+/// Checks, from its magic bytes, that the content of a file has the type its declared one maps to
+fn verify_content_type(declared: &str, bytes: &[u8]) -> Result<(), JsonError> {
+    let expected = expected_content_type(declared)?;
+    let actual = infer::get(bytes).map(|kind| kind.mime_type());
+    if actual == Some(expected) {
+        return Ok(());
+    }
+
+    log::warn!(
+        "rejected an upload declared as {} whose content is {}",
+        declared,
+        actual.unwrap_or("of an unknown type")
+    );
+    Err(
+        "The content of the uploaded file does not match its declared type"
+            .to_json_error(StatusCode::UNPROCESSABLE_ENTITY),
+    )
+}
+
+/// Reads the ZIP archive sent in the multipart field named `file`
+///
+/// Other fields are skipped. The declared content type of the field must be allowed, then
+/// files larger than `max_size` bytes are refused without being read to the end, and the
+/// content itself must be of the type the declared one maps to.
+pub(crate) async fn read_zip_field(
+    payload: &mut Multipart, max_size: u64,
+) -> Result<Vec<u8>, JsonError> {
+    let mut file_bytes: Option<Vec<u8>> = None;
+    while let Some(field_result) = payload.next().await {
+        let mut field = field_result.map_err(|e| {
+            error_with_log_id(
+                format!("failed reading multipart field: {}", e),
+                "Invalid multipart data",
+                StatusCode::BAD_REQUEST,
+                log::Level::Warn,
+            )
+        })?;
+        if field.name() != Some("file") {
+            continue;
+        }
+        let declared = field
+            .content_type()
+            .map_or(DEFAULT_CONTENT_TYPE.to_string(), |mime| {
+                mime.essence_str().to_string()
+            });
+        expected_content_type(&declared)?;
+
+        let mut bytes = Vec::new();
+        let mut current_size: u64 = 0;
+        while let Some(chunk_result) = field.next().await {
+            let chunk = chunk_result.map_err(|e| {
+                error_with_log_id(
+                    format!("failed reading multipart chunk: {}", e),
+                    "Invalid multipart data",
+                    StatusCode::BAD_REQUEST,
+                    log::Level::Warn,
+                )
+            })?;
+            current_size += chunk.len() as u64;
+            if current_size > max_size {
+                return Err("File size exceeds configured maximum"
+                    .to_json_error(StatusCode::PAYLOAD_TOO_LARGE));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        verify_content_type(&declared, &bytes)?;
+        file_bytes = Some(bytes);
+        break;
+    }
+
+    file_bytes
+        .ok_or_else(|| "Multipart field 'file' is required".to_json_error(StatusCode::BAD_REQUEST))
+}
+
+/// Rejects uploads once the upload deadline of the project has passed
+pub(crate) fn ensure_upload_open(
+    upload_deadline: Option<DateTime<Utc>>, now: DateTime<Utc>,
+) -> Result<(), JsonError> {
+    match upload_deadline {
+        Some(deadline) if now > deadline => {
+            Err("Upload deadline has passed".to_json_error(StatusCode::FORBIDDEN))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Where a version of a student's upload for a project is stored
+///
+/// The first version keeps the name uploads had before versioning, so their files are
+/// still found; later ones get their own file and never overwrite an earlier version.
+pub(crate) fn version_path(
+    uploads_dir: &str, project_id: i32, student_id: i32, version: i32,
+) -> String {
+    let dir = uploads_dir.trim_end_matches('/');
+    if version <= 1 {
+        format!("{}/{}/{}.zip", dir, project_id, student_id)
+    } else {
+        format!("{}/{}/{}_v{}.zip", dir, project_id, student_id, version)
+    }
+}
+
+/// Writes an uploaded file to `path`, creating its directory when missing
+pub(crate) async fn store(path: &str, bytes: &[u8]) -> Result<(), JsonError> {
+    if let Some(dir) = std::path::Path::new(path).parent() {
+        tokio::fs::create_dir_all(dir).await.map_err(|e| {
+            error_with_log_id(
+                format!("failed creating upload directory {}: {}", dir.display(), e),
+                "Unable to store upload",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        })?;
+    }
+
+    tokio::fs::write(path, bytes).await.map_err(|e| {
+        error_with_log_id(
+            format!("failed writing upload file {}: {}", path, e),
+            "Unable to store upload",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            log::Level::Error,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::ResponseError;
+    use chrono::TimeZone;
+
+    const ZIP: &[u8] = b"PK\x03\x04\x14\x00\x00\x00\x08\x00";
+    const ELF: &[u8] = b"\x7fELF\x02\x01\x01\x00\x00\x00\x00\x00";
+    const PDF: &[u8] = b"%PDF-1.7\n%\xe2\xe3\xcf\xd3\n";
+
+    #[test]
+    fn test_correctly_typed_file_is_accepted() {
+        for declared in [
+            "application/zip",
+            "application/x-zip-compressed",
+            "Application/Zip",
+        ] {
+            assert!(verify_content_type(declared, ZIP).is_ok(), "{}", declared);
+        }
+        assert!(verify_content_type(DEFAULT_CONTENT_TYPE, ZIP).is_ok());
+    }
+
+    #[test]
+    fn test_mislabeled_file_is_rejected() {
+        for content in [ELF, PDF, b"just some text".as_slice()] {
+            let err = verify_content_type("application/zip", content).unwrap_err();
+            assert_eq!(err.status_code(), StatusCode::UNPROCESSABLE_ENTITY);
+        }
+        // An executable sent without a usable type is still sniffed
+        assert!(verify_content_type(DEFAULT_CONTENT_TYPE, ELF).is_err());
+    }
+
+    #[test]
+    fn test_types_outside_the_allowlist_are_refused() {
+        let err = verify_content_type("application/pdf", PDF).unwrap_err();
+
+        assert_eq!(err.status_code(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[test]
+    fn test_versions_never_share_a_file() {
+        assert_eq!(
+            version_path("/data/uploads/", 3, 5, 1),
+            "/data/uploads/3/5.zip"
+        );
+        assert_eq!(
+            version_path("/data/uploads", 3, 5, 2),
+            "/data/uploads/3/5_v2.zip"
+        );
+        assert_ne!(
+            version_path("/data/uploads", 3, 5, 2),
+            version_path("/data/uploads", 3, 5, 3)
+        );
+    }
+
+    #[test]
+    fn test_uploads_are_rejected_after_the_deadline() {
+        let deadline = Utc.with_ymd_and_hms(2026, 6, 30, 23, 59, 59).unwrap();
+
+        assert!(ensure_upload_open(Some(deadline), deadline).is_ok());
+        assert!(ensure_upload_open(None, deadline).is_ok());
+        let err = ensure_upload_open(Some(deadline), deadline + chrono::Duration::seconds(1))
+            .unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,290 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, ToJsonError, DB_UNAVAILABLE};
+use actix_web::body::EitherBody;
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::RETRY_AFTER;
+use actix_web::http::StatusCode;
+use actix_web::web::Data;
+use actix_web::{Error, HttpResponse, ResponseError};
+use async_trait::async_trait;
+use futures_util::future::{ready, LocalBoxFuture, Ready};
+use log::{info, warn};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use welds::connections::postgres::PostgresClient;
+
+/// How long requests are turned away after a failed probe before the database is tried again
+pub(crate) const DB_RETRY_AFTER: Duration = Duration::from_secs(1);
+/// How long a probe waits for the database to answer
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Check of whether the database currently answers queries
+#[async_trait]
+pub(crate) trait DbProbe: Send + Sync {
+    async fn is_reachable(&self) -> bool;
+}
+
+#[async_trait]
+impl DbProbe for PostgresClient {
+    async fn is_reachable(&self) -> bool {
+        let query = sqlx::query("SELECT 1").fetch_one(self.as_sqlx_pool());
+        matches!(tokio::time::timeout(PROBE_TIMEOUT, query).await, Ok(Ok(_)))
+    }
+}
+
+/// Whether the database is reachable, shared between workers
+///
+/// The database is assumed up until a failed request is confirmed by a probe. While it is
+/// down, requests are answered right away and the database is probed again at most once
+/// every `retry_after`, the pool replacing its broken connections in the meantime.
+#[derive(Clone)]
+pub(crate) struct DbAvailability {
+    probe: Arc<dyn DbProbe>,
+    /// Time of the last failed probe, `None` while the database is up
+    down_since_probe: Arc<Mutex<Option<Instant>>>,
+    retry_after: Duration,
+}
+
+impl DbAvailability {
+    pub(crate) fn new(probe: Arc<dyn DbProbe>, retry_after: Duration) -> Self {
+        Self {
+            probe,
+            down_since_probe: Arc::new(Mutex::new(None)),
+            retry_after,
+        }
+    }
+
+    fn last_failed_probe(&self) -> MutexGuard<'_, Option<Instant>> {
+        self.down_since_probe
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Whether a request can go through, probing the database when an outage is due a retry
+    pub(crate) async fn admits(&self) -> bool {
+        {
+            let mut last = self.last_failed_probe();
+            match *last {
+                None => return true,
+                Some(at) if at.elapsed() < self.retry_after => return false,
+                // claim the retry, so concurrent requests keep being turned away meanwhile
+                Some(_) => *last = Some(Instant::now()),
+            }
+        }
+
+        if self.probe.is_reachable().await {
+            *self.last_failed_probe() = None;
+            info!("database reachable again, accepting requests");
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Whether a failed request was caused by the database being unreachable
+    ///
+    /// A failed probe starts an outage, so the following requests are short circuited.
+    pub(crate) async fn confirms_outage(&self) -> bool {
+        if self.probe.is_reachable().await {
+            return false;
+        }
+
+        let mut last = self.last_failed_probe();
+        if last.is_none() {
+            warn!("database unreachable, rejecting requests until it recovers");
+        }
+        *last = Some(Instant::now());
+        true
+    }
+}
+
+/// Answer given while the database is unreachable
+fn unavailable_response(retry_after: Duration) -> HttpResponse {
+    let error = "Database unavailable, please try again shortly"
+        .to_json_error(StatusCode::SERVICE_UNAVAILABLE)
+        .with_code(DB_UNAVAILABLE);
+    let mut response = error.error_response();
+    response.headers_mut().insert(
+        RETRY_AFTER,
+        retry_after.as_secs().max(1).to_string().parse().unwrap(),
+    );
+    response
+}
+
+/// Answers 503 `DB_UNAVAILABLE` instead of 500 while the database is unreachable
+///
+/// Requests are short circuited during an outage, and a 500 of the wrapped handlers is
+/// turned into a 503 when a probe shows the database went away.
+pub(crate) struct DbAvailabilityGuard;
+
+impl<S, B> Transform<S, ServiceRequest> for DbAvailabilityGuard
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = DbAvailabilityGuardService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(DbAvailabilityGuardService {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub(crate) struct DbAvailabilityGuardService<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for DbAvailabilityGuardService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        Box::pin(async move {
+            let Some(data) = req.app_data::<Data<AppData>>().cloned() else {
+                let error = error_with_log_id(
+                    "DbAvailabilityGuard used without application data",
+                    "Internal server error",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                );
+                return Ok(req.error_response(error).map_into_right_body());
+            };
+            let availability = &data.db_availability;
+
+            if !availability.admits().await {
+                let response = unavailable_response(availability.retry_after);
+                return Ok(req.into_response(response).map_into_right_body());
+            }
+
+            let res = service.call(req).await?;
+            if res.status() != StatusCode::INTERNAL_SERVER_ERROR
+                || !availability.confirms_outage().await
+            {
+                return Ok(res.map_into_left_body());
+            }
+
+            let (req, _) = res.into_parts();
+            let response = unavailable_response(availability.retry_after);
+            Ok(ServiceResponse::new(req, response).map_into_right_body())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_app_data;
+    use actix_web::test::{call_service, init_service, read_body_json, TestRequest};
+    use actix_web::{web, App};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    /// Database that can be taken down and brought back by the test
+    #[derive(Default)]
+    struct FakeDb {
+        down: AtomicBool,
+        queries: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl DbProbe for FakeDb {
+        async fn is_reachable(&self) -> bool {
+            !self.down.load(Ordering::SeqCst)
+        }
+    }
+
+    /// Handler failing with a generic error while the fake database is down
+    async fn query(db: Data<Arc<FakeDb>>) -> HttpResponse {
+        db.queries.fetch_add(1, Ordering::SeqCst);
+        if db.down.load(Ordering::SeqCst) {
+            HttpResponse::InternalServerError().finish()
+        } else {
+            HttpResponse::Ok().finish()
+        }
+    }
+
+    async fn app_data(db: Arc<FakeDb>, retry_after: Duration) -> AppData {
+        let mut data = create_test_app_data().await;
+        data.db_availability = DbAvailability::new(db, retry_after);
+        data
+    }
+
+    #[actix_web::test]
+    async fn test_dropped_connection_is_unavailable_then_recovers() {
+        let db = Arc::new(FakeDb::default());
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(app_data(db.clone(), Duration::ZERO).await))
+                .app_data(Data::new(db.clone()))
+                .service(
+                    web::scope("/v1")
+                        .wrap(DbAvailabilityGuard)
+                        .route("/query", web::get().to(query)),
+                ),
+        )
+        .await;
+
+        db.down.store(true, Ordering::SeqCst);
+        let res = call_service(&app, TestRequest::get().uri("/v1/query").to_request()).await;
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert!(res.headers().contains_key(RETRY_AFTER));
+        let body: serde_json::Value = read_body_json(res).await;
+        assert_eq!(body["code"], DB_UNAVAILABLE);
+
+        db.down.store(false, Ordering::SeqCst);
+        let res = call_service(&app, TestRequest::get().uri("/v1/query").to_request()).await;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn test_requests_are_short_circuited_during_an_outage() {
+        let db = Arc::new(FakeDb::default());
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(
+                    app_data(db.clone(), Duration::from_secs(60)).await,
+                ))
+                .app_data(Data::new(db.clone()))
+                .service(
+                    web::scope("/v1")
+                        .wrap(DbAvailabilityGuard)
+                        .route("/query", web::get().to(query)),
+                ),
+        )
+        .await;
+
+        db.down.store(true, Ordering::SeqCst);
+        call_service(&app, TestRequest::get().uri("/v1/query").to_request()).await;
+        let res = call_service(&app, TestRequest::get().uri("/v1/query").to_request()).await;
+
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(db.queries.load(Ordering::SeqCst), 1);
+    }
+
+    #[actix_web::test]
+    async fn test_errors_with_a_reachable_database_stay_internal() {
+        let db = Arc::new(FakeDb::default());
+        let availability = DbAvailability::new(db.clone(), Duration::ZERO);
+
+        assert!(!availability.confirms_outage().await);
+        assert!(availability.admits().await);
+
+        db.down.store(true, Ordering::SeqCst);
+        assert!(availability.confirms_outage().await);
+        assert!(!availability.admits().await);
+    }
+}
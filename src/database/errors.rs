@@ -0,0 +1,148 @@
+use crate::common::json_error::{error_with_log_id, JsonError, ToJsonError, RESOURCE_IN_USE};
+use actix_web::http::StatusCode;
+use sqlx::error::DatabaseError;
+use welds::errors::{ConnError, WeldsError};
+
+fn database_error(err: &WeldsError) -> Option<&dyn DatabaseError> {
+    match err {
+        WeldsError::Database(ConnError::Sqlx(sqlx::Error::Database(db_err))) => {
+            Some(db_err.as_ref())
+        }
+        _ => None,
+    }
+}
+
+/// Returns true when the error comes from a violated unique constraint
+///
+/// Lets handlers turn races that slip past their existence pre-checks into a conflict
+/// instead of a generic database error.
+pub(crate) fn is_unique_violation(err: &WeldsError) -> bool {
+    database_error(err).is_some_and(|e| e.is_unique_violation())
+}
+
+/// Returns true when the error comes from a violated foreign key (SQLSTATE 23503)
+pub(crate) fn is_foreign_key_violation(err: &WeldsError) -> bool {
+    database_error(err).is_some_and(|e| e.is_foreign_key_violation())
+}
+
+/// Error of a failed delete of `what`, described to the client as `user_msg`
+///
+/// A row still referenced by another table is a 409 `RESOURCE_IN_USE` naming that table,
+/// which for a delete is the table holding the violated foreign key. Any other failure is
+/// a logged 500.
+pub(crate) fn delete_error(err: WeldsError, what: &str, user_msg: &str) -> JsonError {
+    if is_foreign_key_violation(&err) {
+        let referenced_by = database_error(&err)
+            .and_then(|e| e.table())
+            .map_or_else(|| "other records".to_string(), |t| t.replace('_', " "));
+        return format!(
+            "Cannot delete the {}, it is still referenced by {}",
+            what, referenced_by
+        )
+        .to_json_error(StatusCode::CONFLICT)
+        .with_code(RESOURCE_IN_USE);
+    }
+    error_with_log_id(
+        format!("unable to delete {}: {}", what, err),
+        user_msg,
+        StatusCode::INTERNAL_SERVER_ERROR,
+        log::Level::Error,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::ResponseError;
+    use sqlx::error::ErrorKind;
+    use std::error::Error as StdError;
+    use std::fmt;
+
+    /// Database error as Postgres reports a delete of a still referenced row
+    #[derive(Debug)]
+    struct ForeignKeyViolation {
+        table: Option<&'static str>,
+    }
+
+    impl fmt::Display for ForeignKeyViolation {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(self.message())
+        }
+    }
+
+    impl StdError for ForeignKeyViolation {}
+
+    impl DatabaseError for ForeignKeyViolation {
+        fn message(&self) -> &str {
+            "update or delete on table violates foreign key constraint"
+        }
+
+        fn as_error(&self) -> &(dyn StdError + Send + Sync + 'static) {
+            self
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn StdError + Send + Sync + 'static) {
+            self
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn StdError + Send + Sync + 'static> {
+            self
+        }
+
+        fn table(&self) -> Option<&str> {
+            self.table
+        }
+
+        fn kind(&self) -> ErrorKind {
+            ErrorKind::ForeignKeyViolation
+        }
+    }
+
+    fn violation(table: Option<&'static str>) -> WeldsError {
+        WeldsError::Database(ConnError::Sqlx(sqlx::Error::Database(Box::new(
+            ForeignKeyViolation { table },
+        ))))
+    }
+
+    #[test]
+    fn test_deleting_a_referenced_deliverable_is_a_conflict() {
+        let err = delete_error(
+            violation(Some("student_deliverable_selections")),
+            "student deliverable",
+            "Failed to delete deliverable",
+        );
+
+        assert_eq!(err.status_code(), StatusCode::CONFLICT);
+        let body = serde_json::to_value(&err).unwrap();
+        assert_eq!(body["code"], RESOURCE_IN_USE);
+        assert_eq!(
+            body["error"],
+            "Cannot delete the student deliverable, it is still referenced by student deliverable selections"
+        );
+        assert!(!is_unique_violation(&violation(None)));
+    }
+
+    #[test]
+    fn test_other_delete_failures_stay_internal_errors() {
+        let err = delete_error(
+            WeldsError::RowNotFound,
+            "security code",
+            "Failed to delete security code",
+        );
+
+        assert_eq!(err.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        let unnamed = delete_error(violation(None), "security code", "Failed");
+        assert_eq!(
+            serde_json::to_value(&unnamed).unwrap()["error"],
+            "Cannot delete the security code, it is still referenced by other records"
+        );
+    }
+
+    #[test]
+    fn test_non_database_errors_are_not_unique_violations() {
+        assert!(!is_unique_violation(&WeldsError::RowNotFound));
+        assert!(!is_unique_violation(&WeldsError::Database(
+            ConnError::Sqlx(sqlx::Error::RowNotFound)
+        )));
+    }
+}
@@ -1,2 +1,7 @@
+pub(crate) mod availability;
+pub(crate) mod errors;
+pub(crate) mod query_budget;
 pub(crate) mod repositories;
 pub(crate) mod seed;
+pub(crate) mod timing;
+pub(crate) mod unit_of_work;
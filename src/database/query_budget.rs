@@ -0,0 +1,202 @@
+use actix_web::body::MessageBody;
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::Error;
+use futures_util::future::{ready, LocalBoxFuture, Ready};
+use log::warn;
+use std::cell::Cell;
+use std::future::Future;
+use std::rc::Rc;
+
+pub(crate) const QUERY_COUNT_HEADER: HeaderName = HeaderName::from_static("x-query-count");
+
+/// Database operations run by the current request
+#[derive(Default)]
+struct QueryCount {
+    count: Cell<u32>,
+    /// Operations currently running, those started inside another one are not counted
+    depth: Cell<u32>,
+}
+
+tokio::task_local! {
+    static QUERIES: Rc<QueryCount>;
+}
+
+/// Counts one database operation of the current request until dropped
+///
+/// Only operations started outside any other are counted, so a repository function
+/// calling another one counts once. Outside of [`counting`] nothing is recorded.
+///
+/// The depth is shared by the whole request, so it cannot tell nesting from concurrency:
+/// operations run together, e.g. in a `join!`, count once while they overlap. The count is
+/// a lower bound for requests that run operations concurrently.
+pub(crate) struct Operation(());
+
+impl Operation {
+    pub(crate) fn start() -> Self {
+        let _ = QUERIES.try_with(|q| {
+            if q.depth.get() == 0 {
+                q.count.set(q.count.get() + 1);
+            }
+            q.depth.set(q.depth.get() + 1);
+        });
+        Operation(())
+    }
+}
+
+impl Drop for Operation {
+    fn drop(&mut self) {
+        let _ = QUERIES.try_with(|q| q.depth.set(q.depth.get().saturating_sub(1)));
+    }
+}
+
+/// Runs `fut`, returning its output and the number of database operations it ran
+pub(crate) async fn counting<T>(fut: impl Future<Output = T>) -> (T, u32) {
+    let queries = Rc::new(QueryCount::default());
+    let output = QUERIES.scope(Rc::clone(&queries), fut).await;
+    (output, queries.count.get())
+}
+
+/// Counts the database operations of every request, to catch accidental N+1s
+///
+/// Meant for debug builds. Requests running more operations than `budget` are logged,
+/// and with `expose_count` every response carries the count in `X-Query-Count`.
+/// Operations are the repository calls measured by [`timed`](crate::database::timing::timed).
+#[derive(Clone, Copy, Default)]
+pub(crate) struct QueryBudget {
+    /// Operations a request may run before it is logged
+    pub budget: Option<u32>,
+    /// Whether responses carry `X-Query-Count`
+    pub expose_count: bool,
+}
+
+impl<S, B> Transform<S, ServiceRequest> for QueryBudget
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = QueryBudgetService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(QueryBudgetService {
+            service: Rc::new(service),
+            settings: *self,
+        }))
+    }
+}
+
+pub(crate) struct QueryBudgetService<S> {
+    service: Rc<S>,
+    settings: QueryBudget,
+}
+
+impl<S, B> Service<ServiceRequest> for QueryBudgetService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let settings = self.settings;
+
+        Box::pin(async move {
+            let route = format!("{} {}", req.method(), req.path());
+
+            let (res, count) = counting(service.call(req)).await;
+            let mut res = res?;
+
+            if let Some(budget) = settings.budget {
+                if count > budget {
+                    warn!(
+                        "{} ran {} database operations, over the budget of {}",
+                        route, count, budget
+                    );
+                }
+            }
+            if settings.expose_count {
+                res.headers_mut()
+                    .insert(QUERY_COUNT_HEADER, HeaderValue::from(count));
+            }
+            Ok(res)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::timing::timed;
+    use crate::test_utils::assert_max_queries;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::{web, App, HttpResponse};
+
+    async fn three_operations() -> HttpResponse {
+        for _ in 0..3 {
+            timed("students.get_by_id", async {}).await;
+        }
+        HttpResponse::Ok().finish()
+    }
+
+    #[actix_web::test]
+    async fn test_nested_operations_count_once() {
+        let ((), count) = counting(async {
+            timed("groups.count_members", async {
+                timed("groups.get_members", async {}).await;
+            })
+            .await;
+            timed("students.get_by_id", async {}).await;
+        })
+        .await;
+
+        assert_eq!(count, 2);
+    }
+
+    #[actix_web::test]
+    async fn test_operations_outside_a_request_are_not_counted() {
+        timed("students.get_by_id", async {}).await;
+
+        let ((), count) = counting(async {}).await;
+        assert_eq!(count, 0);
+    }
+
+    #[actix_web::test]
+    #[should_panic(expected = "at most 2 database operations")]
+    async fn test_budget_catches_a_loop_of_queries() {
+        assert_max_queries(2, three_operations()).await;
+    }
+
+    #[actix_web::test]
+    async fn test_count_is_exposed_when_asked() {
+        let app = |settings: QueryBudget| async move {
+            init_service(
+                App::new()
+                    .wrap(settings)
+                    .route("/", web::get().to(three_operations)),
+            )
+            .await
+        };
+
+        let hidden = app(QueryBudget::default()).await;
+        let res = call_service(&hidden, TestRequest::get().uri("/").to_request()).await;
+        assert!(!res.headers().contains_key(QUERY_COUNT_HEADER));
+
+        let exposed = app(QueryBudget {
+            budget: Some(2),
+            expose_count: true,
+        })
+        .await;
+        let res = call_service(&exposed, TestRequest::get().uri("/").to_request()).await;
+        assert_eq!(res.status(), actix_web::http::StatusCode::OK);
+        assert_eq!(res.headers().get(QUERY_COUNT_HEADER).unwrap(), "3");
+    }
+}
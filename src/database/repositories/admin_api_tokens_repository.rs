@@ -0,0 +1,83 @@
+use crate::database::timing::timed;
+use crate::models::admin_api_token::AdminApiToken;
+use chrono::Utc;
+use welds::connections::postgres::PostgresClient;
+use welds::state::DbState;
+
+/// Create a new API token
+pub(crate) async fn create(
+    db: &PostgresClient, token: AdminApiToken,
+) -> welds::errors::Result<DbState<AdminApiToken>> {
+    timed("admin_api_tokens.create", async move {
+        let mut state = DbState::new_uncreated(token);
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
+}
+
+/// Get all the API tokens of an admin, revoked ones included
+pub(crate) async fn get_by_admin(
+    db: &PostgresClient, admin_id: i32,
+) -> welds::errors::Result<Vec<DbState<AdminApiToken>>> {
+    timed("admin_api_tokens.get_by_admin", async move {
+        AdminApiToken::where_col(|t| t.admin_id.equal(admin_id))
+            .order_by_asc(|t| t.api_token_id)
+            .run(db)
+            .await
+    })
+    .await
+}
+
+/// Get an API token by the hash of its value
+pub(crate) async fn get_by_hash(
+    db: &PostgresClient, token_hash: &str,
+) -> welds::errors::Result<Option<DbState<AdminApiToken>>> {
+    timed("admin_api_tokens.get_by_hash", async move {
+        let mut rows = AdminApiToken::where_col(|t| t.token_hash.equal(token_hash))
+            .limit(1)
+            .run(db)
+            .await?;
+
+        Ok(rows.pop())
+    })
+    .await
+}
+
+/// Check if an admin already has a token with the given name
+pub(crate) async fn name_exists(
+    db: &PostgresClient, admin_id: i32, name: &str,
+) -> welds::errors::Result<bool> {
+    timed("admin_api_tokens.name_exists", async move {
+        let count = AdminApiToken::where_col(|t| t.admin_id.equal(admin_id))
+            .where_col(|t| t.name.equal(name))
+            .count(db)
+            .await?;
+
+        Ok(count > 0)
+    })
+    .await
+}
+
+/// Revoke an API token of an admin
+/// Returns false if the admin has no such token or it was already revoked
+pub(crate) async fn revoke(
+    db: &PostgresClient, admin_id: i32, api_token_id: i32,
+) -> welds::errors::Result<bool> {
+    timed("admin_api_tokens.revoke", async move {
+        let mut rows = AdminApiToken::where_col(|t| t.api_token_id.equal(api_token_id))
+            .where_col(|t| t.admin_id.equal(admin_id))
+            .run(db)
+            .await?;
+
+        match rows.pop() {
+            Some(mut state) if state.is_active() => {
+                state.revoked_at = Some(Utc::now());
+                state.save(db).await?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    })
+    .await
+}
@@ -0,0 +1,15 @@
+use crate::database::timing::timed;
+use crate::models::admin_role::AdminRole;
+use welds::connections::postgres::PostgresClient;
+use welds::state::DbState;
+
+/// Get all admin roles ordered by id.
+pub(crate) async fn get_all(db: &PostgresClient) -> welds::errors::Result<Vec<DbState<AdminRole>>> {
+    timed("admin_roles.get_all", async move {
+        AdminRole::all()
+            .order_by_asc(|r| r.admin_role_id)
+            .run(db)
+            .await
+    })
+    .await
+}
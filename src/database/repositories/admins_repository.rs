@@ -1,70 +1,84 @@
 use crate::database::seed::seed_all_roles;
+use crate::database::timing::timed;
 use crate::models::admin::Admin;
 use crate::models::admin_role::AvailableAdminRole;
 use log::{error, info};
 use password_auth::generate_hash;
 use welds::connections::postgres::PostgresClient;
+use welds::connections::Transaction;
 use welds::state::DbState;
+use welds::{Client, TransactStart};
 
 pub(crate) async fn get_all(db: &PostgresClient) -> welds::errors::Result<Vec<DbState<Admin>>> {
-    Admin::all().run(db).await
+    timed("admins.get_all", async move { Admin::all().run(db).await }).await
 }
 
 /// Get an admin by email
 pub(crate) async fn get_by_email(
     db: &PostgresClient, email: &str,
 ) -> welds::errors::Result<Option<DbState<Admin>>> {
-    let mut rows = Admin::where_col(|a| a.email.equal(email)).run(db).await?;
+    timed("admins.get_by_email", async move {
+        let mut rows = Admin::where_col(|a| a.email.equal(email)).run(db).await?;
 
-    Ok(rows.pop())
+        Ok(rows.pop())
+    })
+    .await
 }
 
 /// Get an admin by ID
 pub(crate) async fn get_by_id(
     db: &PostgresClient, admin_id: i32,
 ) -> welds::errors::Result<Option<DbState<Admin>>> {
-    let mut rows = Admin::where_col(|a| a.admin_id.equal(admin_id))
-        .run(db)
-        .await?;
+    timed("admins.get_by_id", async move {
+        let mut rows = Admin::where_col(|a| a.admin_id.equal(admin_id))
+            .run(db)
+            .await?;
 
-    Ok(rows.pop())
+        Ok(rows.pop())
+    })
+    .await
 }
 
-/// Delete an admin by ID
-/// Returns true if the admin was deleted, false if not found
-pub(crate) async fn delete_by_id(
-    db: &PostgresClient, admin_id: i32,
-) -> welds::errors::Result<bool> {
-    let mut rows = Admin::where_col(|a| a.admin_id.equal(admin_id))
-        .run(db)
-        .await?;
+/// Get all admins whose ID is in the given list
+pub(crate) async fn get_by_ids(
+    db: &PostgresClient, admin_ids: &[i32],
+) -> welds::errors::Result<Vec<DbState<Admin>>> {
+    timed("admins.get_by_ids", async move {
+        if admin_ids.is_empty() {
+            return Ok(Vec::new());
+        }
 
-    if let Some(mut state) = rows.pop() {
-        state.delete(db).await?;
-        Ok(true)
-    } else {
-        Ok(false)
-    }
+        Admin::where_col(|a| a.admin_id.in_list(admin_ids))
+            .run(db)
+            .await
+    })
+    .await
 }
 
 /// Update an admin's password by email
 pub(crate) async fn update_password_by_email(
-    db: &PostgresClient, email: &str, password_hash: String,
+    db: &impl Client, email: &str, password_hash: String,
 ) -> welds::errors::Result<()> {
-    Admin::where_col(|a| a.email.equal(email))
-        .set(|a| a.password_hash, password_hash)
-        .run(db)
-        .await?;
-    Ok(())
+    timed("admins.update_password_by_email", async move {
+        Admin::where_col(|a| a.email.equal(email))
+            .set(|a| a.password_hash, password_hash)
+            .run(db)
+            .await?;
+        Ok(())
+    })
+    .await
 }
 
 /// Create a new admin
 pub(crate) async fn create(
     db: &PostgresClient, admin: Admin,
 ) -> welds::errors::Result<DbState<Admin>> {
-    let mut state = DbState::new_uncreated(admin);
-    state.save(db).await?;
-    Ok(state)
+    timed("admins.create", async move {
+        let mut state = DbState::new_uncreated(admin);
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
 }
 
 /// Update an admin by ID
@@ -72,65 +86,549 @@ pub(crate) async fn update_by_id(
     db: &PostgresClient, admin_id: i32, first_name: Option<String>, last_name: Option<String>,
     email: Option<String>, password_hash: Option<String>,
 ) -> welds::errors::Result<()> {
-    if let Some(name) = first_name {
-        Admin::where_col(|a| a.admin_id.equal(admin_id))
-            .set(|a| a.first_name, name)
-            .run(db)
+    timed("admins.update_by_id", async move {
+        if let Some(name) = first_name {
+            Admin::where_col(|a| a.admin_id.equal(admin_id))
+                .set(|a| a.first_name, name)
+                .run(db)
+                .await?;
+        }
+        if let Some(name) = last_name {
+            Admin::where_col(|a| a.admin_id.equal(admin_id))
+                .set(|a| a.last_name, name)
+                .run(db)
+                .await?;
+        }
+        if let Some(email) = email {
+            Admin::where_col(|a| a.admin_id.equal(admin_id))
+                .set(|a| a.email, email)
+                .run(db)
+                .await?;
+        }
+        if let Some(hash) = password_hash {
+            Admin::where_col(|a| a.admin_id.equal(admin_id))
+                .set(|a| a.password_hash, hash)
+                .run(db)
+                .await?;
+        }
+
+        Ok(())
+    })
+    .await
+}
+
+/// Key of the advisory lock held while a Root admin may be removed
+const ROOT_ADMINS_LOCK_KEY: i64 = 0x006c_6173_7472_6f6f;
+
+/// Outcome of a change that could remove the last Root admin
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum RootGuarded {
+    Applied,
+    NotFound,
+    /// Refused, the admin is the only Root left
+    LastRoot,
+}
+
+/// Outcome of a role change that could demote the last Root admin
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum RoleChange {
+    /// Applied, `old_role` is the role replaced as read under the lock
+    Changed {
+        old_role: i32,
+    },
+    NotFound,
+    /// Refused, the admin is the only Root left
+    LastRoot,
+}
+
+/// Steps of a change guarded against removing the last Root admin
+trait RootAdminStore {
+    /// Waits until no other change of a Root admin is in progress
+    async fn lock(&self) -> welds::errors::Result<()>;
+    /// Role of the admin, whose row stays locked until the change ends
+    async fn role_of(&self, admin_id: i32) -> welds::errors::Result<Option<i32>>;
+    async fn root_count(&self) -> welds::errors::Result<u64>;
+    async fn delete_admin(&self, admin_id: i32) -> welds::errors::Result<()>;
+    async fn set_role(&self, admin_id: i32, admin_role_id: i32) -> welds::errors::Result<()>;
+}
+
+impl RootAdminStore for Transaction<'_> {
+    async fn lock(&self) -> welds::errors::Result<()> {
+        // released with the transaction
+        self.fetch_rows("SELECT pg_advisory_xact_lock($1)", &[&ROOT_ADMINS_LOCK_KEY])
             .await?;
+        Ok(())
     }
-    if let Some(name) = last_name {
-        Admin::where_col(|a| a.admin_id.equal(admin_id))
-            .set(|a| a.last_name, name)
-            .run(db)
+
+    async fn role_of(&self, admin_id: i32) -> welds::errors::Result<Option<i32>> {
+        let rows = self
+            .fetch_rows(
+                "SELECT admin_role_id FROM admins WHERE admin_id = $1 FOR UPDATE",
+                &[&admin_id],
+            )
             .await?;
+        Ok(rows
+            .first()
+            .map(|row| row.get("admin_role_id"))
+            .transpose()?)
     }
-    if let Some(email) = email {
+
+    async fn root_count(&self) -> welds::errors::Result<u64> {
+        Admin::where_col(|a| a.admin_role_id.equal(AvailableAdminRole::Root as i32))
+            .count(self)
+            .await
+    }
+
+    async fn delete_admin(&self, admin_id: i32) -> welds::errors::Result<()> {
         Admin::where_col(|a| a.admin_id.equal(admin_id))
-            .set(|a| a.email, email)
-            .run(db)
+            .delete(self)
             .await?;
+        Ok(())
     }
-    if let Some(hash) = password_hash {
+
+    async fn set_role(&self, admin_id: i32, admin_role_id: i32) -> welds::errors::Result<()> {
         Admin::where_col(|a| a.admin_id.equal(admin_id))
-            .set(|a| a.password_hash, hash)
-            .run(db)
+            .set(|a| a.admin_role_id, admin_role_id)
+            .run(self)
             .await?;
+        Ok(())
     }
+}
 
-    Ok(())
+/// Deletes an admin unless it is the last Root
+///
+/// The Roots are counted under the store lock, so two deletions running together cannot
+/// both see another Root left and remove the last two.
+async fn delete_guarding_last_root(
+    store: &impl RootAdminStore, admin_id: i32,
+) -> welds::errors::Result<RootGuarded> {
+    store.lock().await?;
+    let Some(role) = store.role_of(admin_id).await? else {
+        return Ok(RootGuarded::NotFound);
+    };
+    if role == AvailableAdminRole::Root as i32 && store.root_count().await? <= 1 {
+        return Ok(RootGuarded::LastRoot);
+    }
+
+    store.delete_admin(admin_id).await?;
+    Ok(RootGuarded::Applied)
 }
 
-pub(crate) async fn create_default_admin(db: &PostgresClient, email: String, password: String) {
-    let found = match get_all(db).await {
-        Ok(v) => v.len(),
-        Err(e) => {
-            error!("unable to find admins: {}", e);
-            0
+/// Delete an admin by ID, refusing to delete the last Root admin
+pub(crate) async fn delete_unless_last_root(
+    db: &PostgresClient, admin_id: i32,
+) -> welds::errors::Result<RootGuarded> {
+    timed("admins.delete_unless_last_root", async move {
+        let transaction = db.begin().await?;
+        match delete_guarding_last_root(&transaction, admin_id).await {
+            Ok(outcome) => {
+                transaction.commit().await?;
+                Ok(outcome)
+            }
+            Err(e) => {
+                transaction.rollback().await?;
+                Err(e)
+            }
         }
+    })
+    .await
+}
+
+/// Changes the role of an admin unless it demotes the last Root
+///
+/// The old role is read under the same lock as the write, so it is the role actually
+/// replaced even when another change of the admin runs at the same time.
+async fn set_role_guarding_last_root(
+    store: &impl RootAdminStore, admin_id: i32, admin_role_id: i32,
+) -> welds::errors::Result<RoleChange> {
+    let root = AvailableAdminRole::Root as i32;
+    store.lock().await?;
+    let Some(old_role) = store.role_of(admin_id).await? else {
+        return Ok(RoleChange::NotFound);
     };
-    if found > 0 {
-        return;
+    if old_role == root && admin_role_id != root && store.root_count().await? <= 1 {
+        return Ok(RoleChange::LastRoot);
     }
 
-    match seed_all_roles(db).await {
-        Ok(_) => {}
-        Err(e) => {
-            panic!("unable to seed roles: {e}");
+    store.set_role(admin_id, admin_role_id).await?;
+    Ok(RoleChange::Changed { old_role })
+}
+
+/// Change the role of an admin, refusing to demote the last Root admin
+pub(crate) async fn set_role_unless_last_root(
+    db: &PostgresClient, admin_id: i32, admin_role_id: i32,
+) -> welds::errors::Result<RoleChange> {
+    timed("admins.set_role_unless_last_root", async move {
+        let transaction = db.begin().await?;
+        match set_role_guarding_last_root(&transaction, admin_id, admin_role_id).await {
+            Ok(outcome) => {
+                transaction.commit().await?;
+                Ok(outcome)
+            }
+            Err(e) => {
+                transaction.rollback().await?;
+                Err(e)
+            }
         }
-    };
+    })
+    .await
+}
+
+/// Key of the advisory lock held while the default admin is created
+const DEFAULT_ADMIN_LOCK_KEY: i64 = 0x0064_6566_6175_6c74;
+
+/// Steps of the default admin creation, run by every instance starting against the database
+trait DefaultAdminStore {
+    /// Waits until no other instance is creating the default admin
+    async fn lock(&self) -> welds::errors::Result<()>;
+    async fn admin_count(&self) -> welds::errors::Result<usize>;
+    async fn seed_roles(&self) -> welds::errors::Result<()>;
+    async fn insert_admin(&self, admin: Admin) -> welds::errors::Result<()>;
+}
+
+impl DefaultAdminStore for Transaction<'_> {
+    async fn lock(&self) -> welds::errors::Result<()> {
+        // released with the transaction
+        self.fetch_rows(
+            "SELECT pg_advisory_xact_lock($1)",
+            &[&DEFAULT_ADMIN_LOCK_KEY],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn admin_count(&self) -> welds::errors::Result<usize> {
+        Ok(Admin::all().run(self).await?.len())
+    }
+
+    async fn seed_roles(&self) -> welds::errors::Result<()> {
+        seed_all_roles(self).await
+    }
+
+    async fn insert_admin(&self, admin: Admin) -> welds::errors::Result<()> {
+        DbState::new_uncreated(admin).save(self).await
+    }
+}
+
+/// Creates the root admin and seeds the roles when there are no admins yet
+///
+/// Returns whether the admin was created. The check and the insert happen under the store
+/// lock, so instances starting together create a single admin.
+async fn ensure_default_admin(
+    store: &impl DefaultAdminStore, email: String, password: String,
+) -> welds::errors::Result<bool> {
+    store.lock().await?;
+    if store.admin_count().await? > 0 {
+        return Ok(false);
+    }
+
+    store.seed_roles().await?;
 
     let mut admin = Admin::new();
     admin.admin_role_id = AvailableAdminRole::Root.into();
-    admin.email = email.clone();
+    admin.email = email;
     admin.password_hash = generate_hash(password);
     admin.first_name = "root".to_string();
     admin.last_name = String::new();
 
     info!("creating default admin");
-    match admin.save(db).await {
-        Ok(_) => {}
-        Err(e) => {
-            panic!("unable to create default admin {:?} error: {}", admin, e)
+    store.insert_admin(DbState::into_inner(admin)).await?;
+    Ok(true)
+}
+
+/// Creates the default admin, meant to run once at startup before the workers are spawned
+pub(crate) async fn create_default_admin(db: &PostgresClient, email: String, password: String) {
+    timed("admins.create_default_admin", async move {
+        let transaction = match db.begin().await {
+            Ok(t) => t,
+            Err(e) => panic!("unable to start the default admin transaction: {e}"),
+        };
+
+        match ensure_default_admin(&transaction, email, password).await {
+            Ok(_) => {
+                if let Err(e) = transaction.commit().await {
+                    panic!("unable to create default admin: {e}");
+                }
+            }
+            Err(e) => {
+                if let Err(rollback) = transaction.rollback().await {
+                    error!(
+                        "unable to roll back the default admin creation: {}",
+                        rollback
+                    );
+                }
+                panic!("unable to create default admin: {e}");
+            }
+        }
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::future::join_all;
+    use std::sync::{Arc, Mutex as StdMutex};
+    use tokio::sync::{Mutex, OwnedMutexGuard};
+
+    /// Admins table shared by every instance, guarded by a lock standing in for the advisory one
+    #[derive(Default)]
+    struct Database {
+        lock: Arc<Mutex<()>>,
+        admins: StdMutex<Vec<Admin>>,
+    }
+
+    /// Connection of one starting instance, the lock is held until it is dropped
+    struct Instance {
+        database: Arc<Database>,
+        guard: StdMutex<Option<OwnedMutexGuard<()>>>,
+    }
+
+    impl Instance {
+        fn new(database: &Arc<Database>) -> Self {
+            Self {
+                database: database.clone(),
+                guard: StdMutex::new(None),
+            }
+        }
+    }
+
+    impl DefaultAdminStore for Instance {
+        async fn lock(&self) -> welds::errors::Result<()> {
+            let guard = self.database.lock.clone().lock_owned().await;
+            *self.guard.lock().unwrap() = Some(guard);
+            Ok(())
+        }
+
+        async fn admin_count(&self) -> welds::errors::Result<usize> {
+            let count = self.database.admins.lock().unwrap().len();
+            // let the other instances run between the check and the insert
+            tokio::task::yield_now().await;
+            Ok(count)
+        }
+
+        async fn seed_roles(&self) -> welds::errors::Result<()> {
+            Ok(())
+        }
+
+        async fn insert_admin(&self, admin: Admin) -> welds::errors::Result<()> {
+            self.database.admins.lock().unwrap().push(admin);
+            Ok(())
         }
     }
+
+    /// Admins table of the Root guard, the lock is held by the change running at the moment
+    #[derive(Default)]
+    struct Roles {
+        lock: Arc<Mutex<()>>,
+        /// Admin id and role id of each admin
+        admins: StdMutex<Vec<(i32, i32)>>,
+    }
+
+    struct RolesConnection {
+        roles: Arc<Roles>,
+        guard: StdMutex<Option<OwnedMutexGuard<()>>>,
+    }
+
+    impl RootAdminStore for RolesConnection {
+        async fn lock(&self) -> welds::errors::Result<()> {
+            let guard = self.roles.lock.clone().lock_owned().await;
+            *self.guard.lock().unwrap() = Some(guard);
+            Ok(())
+        }
+
+        async fn role_of(&self, admin_id: i32) -> welds::errors::Result<Option<i32>> {
+            let admins = self.roles.admins.lock().unwrap();
+            Ok(admins.iter().find(|(id, _)| *id == admin_id).map(|a| a.1))
+        }
+
+        async fn root_count(&self) -> welds::errors::Result<u64> {
+            let count = self
+                .roles
+                .admins
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(_, role)| *role == AvailableAdminRole::Root as i32)
+                .count();
+            // let the other changes run between the count and the write
+            tokio::task::yield_now().await;
+            Ok(count as u64)
+        }
+
+        async fn delete_admin(&self, admin_id: i32) -> welds::errors::Result<()> {
+            self.roles
+                .admins
+                .lock()
+                .unwrap()
+                .retain(|(id, _)| *id != admin_id);
+            Ok(())
+        }
+
+        async fn set_role(&self, admin_id: i32, admin_role_id: i32) -> welds::errors::Result<()> {
+            let mut admins = self.roles.admins.lock().unwrap();
+            if let Some(admin) = admins.iter_mut().find(|(id, _)| *id == admin_id) {
+                admin.1 = admin_role_id;
+            }
+            Ok(())
+        }
+    }
+
+    fn roles(admins: &[(i32, AvailableAdminRole)]) -> Arc<Roles> {
+        let roles = Roles::default();
+        *roles.admins.lock().unwrap() = admins
+            .iter()
+            .map(|(id, role)| (*id, *role as i32))
+            .collect();
+        Arc::new(roles)
+    }
+
+    async fn delete(roles: Arc<Roles>, admin_id: i32) -> RootGuarded {
+        let connection = RolesConnection {
+            roles,
+            guard: StdMutex::new(None),
+        };
+        delete_guarding_last_root(&connection, admin_id)
+            .await
+            .unwrap()
+    }
+
+    async fn set_role(roles: Arc<Roles>, admin_id: i32, role: AvailableAdminRole) -> RoleChange {
+        let connection = RolesConnection {
+            roles,
+            guard: StdMutex::new(None),
+        };
+        set_role_guarding_last_root(&connection, admin_id, role as i32)
+            .await
+            .unwrap()
+    }
+
+    #[actix_web::test]
+    async fn test_root_with_another_root_left_is_deleted() {
+        let roles = roles(&[(1, AvailableAdminRole::Root), (2, AvailableAdminRole::Root)]);
+
+        assert_eq!(delete(roles.clone(), 2).await, RootGuarded::Applied);
+        assert_eq!(*roles.admins.lock().unwrap(), vec![(1, 1)]);
+    }
+
+    #[actix_web::test]
+    async fn test_last_root_is_kept() {
+        let roles = roles(&[
+            (1, AvailableAdminRole::Root),
+            (2, AvailableAdminRole::Professor),
+        ]);
+
+        assert_eq!(delete(roles.clone(), 1).await, RootGuarded::LastRoot);
+        assert_eq!(delete(roles.clone(), 2).await, RootGuarded::Applied);
+        assert_eq!(delete(roles.clone(), 9).await, RootGuarded::NotFound);
+        assert_eq!(*roles.admins.lock().unwrap(), vec![(1, 1)]);
+    }
+
+    #[actix_web::test]
+    async fn test_role_change_is_applied() {
+        let roles = roles(&[
+            (1, AvailableAdminRole::Root),
+            (2, AvailableAdminRole::Professor),
+        ]);
+
+        let promoted = set_role(roles.clone(), 2, AvailableAdminRole::Root).await;
+        assert_eq!(promoted, RoleChange::Changed { old_role: 2 });
+        let demoted = set_role(roles.clone(), 1, AvailableAdminRole::Coordinator).await;
+        assert_eq!(demoted, RoleChange::Changed { old_role: 1 });
+        assert_eq!(*roles.admins.lock().unwrap(), vec![(1, 3), (2, 1)]);
+    }
+
+    #[actix_web::test]
+    async fn test_last_root_is_not_demoted() {
+        let roles = roles(&[
+            (1, AvailableAdminRole::Root),
+            (2, AvailableAdminRole::Professor),
+        ]);
+
+        let demoted = set_role(roles.clone(), 1, AvailableAdminRole::Professor).await;
+        assert_eq!(demoted, RoleChange::LastRoot);
+        // keeping the last Root a Root is harmless
+        let kept = set_role(roles.clone(), 1, AvailableAdminRole::Root).await;
+        assert_eq!(kept, RoleChange::Changed { old_role: 1 });
+        let missing = set_role(roles.clone(), 9, AvailableAdminRole::Root).await;
+        assert_eq!(missing, RoleChange::NotFound);
+        assert_eq!(*roles.admins.lock().unwrap(), vec![(1, 1), (2, 2)]);
+    }
+
+    #[actix_web::test]
+    async fn test_concurrent_deletions_keep_one_root() {
+        let roles = roles(&[(1, AvailableAdminRole::Root), (2, AvailableAdminRole::Root)]);
+
+        let outcomes = join_all([1, 2].map(|id| tokio::spawn(delete(roles.clone(), id)))).await;
+
+        let applied = outcomes
+            .into_iter()
+            .filter(|o| *o.as_ref().unwrap() == RootGuarded::Applied)
+            .count();
+        assert_eq!(applied, 1);
+        assert_eq!(roles.admins.lock().unwrap().len(), 1);
+    }
+
+    #[actix_web::test]
+    async fn test_concurrent_role_changes_report_the_role_they_replaced() {
+        let roles = roles(&[
+            (1, AvailableAdminRole::Root),
+            (2, AvailableAdminRole::Professor),
+        ]);
+
+        let requested = [AvailableAdminRole::Coordinator, AvailableAdminRole::Root];
+        let outcomes =
+            join_all(requested.map(|role| tokio::spawn(set_role(roles.clone(), 2, role)))).await;
+
+        // The change that ran second replaced the role set by the first one, not the
+        // Professor role both could have read before
+        let final_role = roles.admins.lock().unwrap()[1].1;
+        let first_role = requested
+            .map(|role| role as i32)
+            .into_iter()
+            .find(|role| *role != final_role)
+            .unwrap();
+        let mut old_roles: Vec<i32> = outcomes
+            .into_iter()
+            .map(|o| match o.unwrap() {
+                RoleChange::Changed { old_role } => old_role,
+                other => panic!("unexpected outcome {:?}", other),
+            })
+            .collect();
+        old_roles.sort();
+        let mut expected = vec![AvailableAdminRole::Professor as i32, first_role];
+        expected.sort();
+        assert_eq!(old_roles, expected);
+    }
+
+    async fn start(database: Arc<Database>) -> bool {
+        let instance = Instance::new(&database);
+        ensure_default_admin(&instance, "root@example.com".into(), "root".into())
+            .await
+            .unwrap()
+    }
+
+    #[actix_web::test]
+    async fn test_concurrent_startups_create_a_single_admin() {
+        let database = Arc::new(Database::default());
+
+        let created = join_all((0..8).map(|_| tokio::spawn(start(database.clone())))).await;
+
+        let created = created.into_iter().filter(|c| *c.as_ref().unwrap()).count();
+        assert_eq!(created, 1);
+        assert_eq!(database.admins.lock().unwrap().len(), 1);
+    }
+
+    #[actix_web::test]
+    async fn test_existing_admin_is_kept() {
+        let database = Arc::new(Database::default());
+        database
+            .admins
+            .lock()
+            .unwrap()
+            .push(DbState::into_inner(Admin::new()));
+
+        assert!(!start(database.clone()).await);
+        assert_eq!(database.admins.lock().unwrap().len(), 1);
+    }
 }
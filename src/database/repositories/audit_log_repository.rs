@@ -0,0 +1,79 @@
+use crate::database::timing::timed;
+use crate::models::audit_log::AuditLog;
+use log::warn;
+use welds::state::DbState;
+use welds::Client;
+
+/// Record an entry in the audit log
+pub(crate) async fn record(db: &impl Client, entry: AuditLog) -> welds::errors::Result<()> {
+    timed("audit_log.record", async move {
+        let mut state = DbState::new_uncreated(entry);
+        state.save(db).await
+    })
+    .await
+}
+
+/// Record an entry for an action that already happened, a failure is only logged
+pub(crate) async fn record_or_warn(db: &impl Client, entry: AuditLog) {
+    timed("audit_log.record_or_warn", async move {
+        let action = entry.action.clone();
+        if let Err(e) = record(db, entry).await {
+            warn!("unable to record {} in the audit log: {}", action, e);
+        }
+    })
+    .await
+}
+
+/// Count the entries related to a project
+pub(crate) async fn count_for_project(
+    db: &impl Client, project_id: i32,
+) -> welds::errors::Result<u64> {
+    timed("audit_log.count_for_project", async move {
+        AuditLog::where_col(|a| a.project_id.equal(project_id))
+            .count(db)
+            .await
+    })
+    .await
+}
+
+/// Get one page of the entries related to a project, most recent first
+pub(crate) async fn get_for_project(
+    db: &impl Client, project_id: i32, limit: i64, offset: i64,
+) -> welds::errors::Result<Vec<DbState<AuditLog>>> {
+    timed("audit_log.get_for_project", async move {
+        AuditLog::where_col(|a| a.project_id.equal(project_id))
+            .order_by_desc(|a| a.created_at)
+            .order_by_desc(|a| a.audit_log_id)
+            .limit(limit)
+            .offset(offset)
+            .run(db)
+            .await
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RecordingClient;
+
+    #[actix_web::test]
+    async fn test_project_activity_is_scoped_and_most_recent_first() {
+        let db = RecordingClient::default();
+
+        // The stub returns no row to read the count from, only the statement matters
+        let _ = count_for_project(&db, 4).await;
+        get_for_project(&db, 4, 20, 40).await.unwrap();
+
+        let statements = db.statements();
+        assert_eq!(statements.len(), 2);
+        for sql in &statements {
+            assert!(sql.contains(r#"t1."project_id" = $1"#), "{}", sql);
+        }
+        assert!(
+            statements[1].contains(r#"ORDER BY t1."created_at" DESC, t1."audit_log_id" DESC"#),
+            "{}",
+            statements[1]
+        );
+    }
+}
@@ -1,27 +1,155 @@
-use crate::models::complaint::Complaint;
+use crate::database::timing::timed;
+use crate::models::complaint::{Complaint, STATUS_OPEN};
+use chrono::{DateTime, Utc};
 use welds::connections::postgres::PostgresClient;
+use welds::query::builder::{ManualParam, QueryBuilder};
 use welds::state::DbState;
 
 pub(crate) async fn create(
     db: &PostgresClient, complaint: Complaint,
 ) -> welds::errors::Result<DbState<Complaint>> {
-    let mut state = DbState::new_uncreated(complaint);
-    state.save(db).await?;
-    Ok(state)
+    timed("complaints.create", async move {
+        let mut state = DbState::new_uncreated(complaint);
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
+}
+
+/// Get a complaint by id
+pub(crate) async fn get_by_id(
+    db: &PostgresClient, complaint_id: i32,
+) -> welds::errors::Result<Option<DbState<Complaint>>> {
+    timed("complaints.get_by_id", async move {
+        let mut rows = Complaint::where_col(|c| c.complaint_id.equal(complaint_id))
+            .run(db)
+            .await?;
+        Ok(rows.pop())
+    })
+    .await
+}
+
+/// Save the changes made to a complaint
+pub(crate) async fn update(
+    db: &PostgresClient, mut state: DbState<Complaint>,
+) -> welds::errors::Result<DbState<Complaint>> {
+    timed("complaints.update", async move {
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
 }
 
 pub(crate) async fn get_filed_by_group(
     db: &PostgresClient, group_id: i32,
 ) -> welds::errors::Result<Vec<DbState<Complaint>>> {
-    Complaint::where_col(|c| c.from_group_id.equal(group_id))
-        .run(db)
-        .await
+    timed("complaints.get_filed_by_group", async move {
+        Complaint::where_col(|c| c.from_group_id.equal(group_id))
+            .run(db)
+            .await
+    })
+    .await
 }
 
 pub(crate) async fn get_received_by_group(
     db: &PostgresClient, group_id: i32,
 ) -> welds::errors::Result<Vec<DbState<Complaint>>> {
-    Complaint::where_col(|c| c.to_group_id.equal(group_id))
-        .run(db)
-        .await
+    timed("complaints.get_received_by_group", async move {
+        Complaint::where_col(|c| c.to_group_id.equal(group_id))
+            .run(db)
+            .await
+    })
+    .await
+}
+
+/// Filters of the complaints feeds, every unset field matches everything
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ComplaintsFilter {
+    pub status: Option<&'static str>,
+    pub project_id: Option<i32>,
+    /// Group that filed the complaint
+    pub from_group_id: Option<i32>,
+    /// Student who filed the complaint
+    pub filed_by_student_id: Option<i32>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    /// Restricts the feed to the projects assigned to this coordinator
+    pub coordinator_id: Option<i32>,
+}
+
+/// SQL filter matching complaints filed in a project
+const IN_PROJECT: &str = "EXISTS (SELECT 1 FROM groups g \
+     WHERE g.group_id = $.from_group_id AND g.project_id = ?)";
+
+/// SQL filter matching complaints filed in a project assigned to a coordinator
+const IN_COORDINATOR_PROJECTS: &str = "EXISTS (SELECT 1 FROM groups g \
+     JOIN coordinator_projects cp ON cp.project_id = g.project_id \
+     WHERE g.group_id = $.from_group_id AND cp.admin_id = ?)";
+
+impl ComplaintsFilter {
+    /// Same filter restricted to complaints not resolved yet
+    pub(crate) fn unresolved(&self) -> Self {
+        Self {
+            status: Some(STATUS_OPEN),
+            ..self.clone()
+        }
+    }
+
+    fn apply(&self, mut query: QueryBuilder<Complaint>) -> QueryBuilder<Complaint> {
+        if let Some(status) = self.status {
+            query = query.where_col(|c| c.status.equal(status));
+        }
+        if let Some(from_group_id) = self.from_group_id {
+            query = query.where_col(|c| c.from_group_id.equal(from_group_id));
+        }
+        if let Some(student_id) = self.filed_by_student_id {
+            query = query.where_col(|c| c.filed_by_student_id.equal(student_id));
+        }
+        if let Some(after) = self.created_after {
+            query = query.where_col(|c| c.created_at.gte(after));
+        }
+        if let Some(before) = self.created_before {
+            query = query.where_col(|c| c.created_at.lte(before));
+        }
+        if let Some(project_id) = self.project_id {
+            query = query.where_manual2(IN_PROJECT, ManualParam::new().with(project_id));
+        }
+        if let Some(coordinator_id) = self.coordinator_id {
+            query = query.where_manual2(
+                IN_COORDINATOR_PROJECTS,
+                ManualParam::new().with(coordinator_id),
+            );
+        }
+        query
+    }
+}
+
+/// Count the complaints matching a filter
+pub(crate) async fn count_filtered(
+    db: &PostgresClient, filter: &ComplaintsFilter,
+) -> welds::errors::Result<u64> {
+    timed("complaints.count_filtered", async move {
+        filter.apply(Complaint::all()).count(db).await
+    })
+    .await
+}
+
+/// Get one page of the complaints matching a filter, most recent first unless `oldest_first`
+pub(crate) async fn get_filtered(
+    db: &PostgresClient, filter: &ComplaintsFilter, oldest_first: bool, limit: i64, offset: i64,
+) -> welds::errors::Result<Vec<DbState<Complaint>>> {
+    timed("complaints.get_filtered", async move {
+        let query = filter.apply(Complaint::all());
+        let query = if oldest_first {
+            query
+                .order_by_asc(|c| c.created_at)
+                .order_by_asc(|c| c.complaint_id)
+        } else {
+            query
+                .order_by_desc(|c| c.created_at)
+                .order_by_desc(|c| c.complaint_id)
+        };
+        query.limit(limit).offset(offset).run(db).await
+    })
+    .await
 }
@@ -1,65 +1,214 @@
+use crate::database::timing::timed;
 use crate::models::coordinator_project::CoordinatorProject;
+use chrono::{DateTime, Utc};
 use welds::connections::postgres::PostgresClient;
 use welds::state::DbState;
+use welds::{Client, TransactStart};
 
 /// Create a coordinator-project assignment
 pub(crate) async fn create(
     db: &PostgresClient, admin_id: i32, project_id: i32,
 ) -> welds::errors::Result<DbState<CoordinatorProject>> {
-    let mut coordinator_project = DbState::new_uncreated(CoordinatorProject {
-        coordinator_project_id: 0,
-        admin_id,
-        project_id,
-        assigned_at: chrono::Utc::now(),
-    });
-
-    coordinator_project.save(db).await?;
-    Ok(coordinator_project)
+    timed("coordinator_projects.create", async move {
+        let mut coordinator_project = DbState::new_uncreated(CoordinatorProject {
+            coordinator_project_id: 0,
+            admin_id,
+            project_id,
+            assigned_at: chrono::Utc::now(),
+        });
+
+        coordinator_project.save(db).await?;
+        Ok(coordinator_project)
+    })
+    .await
 }
 
 /// Get all coordinators for a project
 pub(crate) async fn get_by_project_id(
     db: &PostgresClient, project_id: i32,
 ) -> welds::errors::Result<Vec<DbState<CoordinatorProject>>> {
-    CoordinatorProject::where_col(|cp| cp.project_id.equal(project_id))
-        .run(db)
-        .await
+    timed("coordinator_projects.get_by_project_id", async move {
+        CoordinatorProject::where_col(|cp| cp.project_id.equal(project_id))
+            .run(db)
+            .await
+    })
+    .await
 }
 
 /// Get all projects assigned to a coordinator
 pub(crate) async fn get_projects_by_coordinator(
     db: &PostgresClient, admin_id: i32,
 ) -> welds::errors::Result<Vec<i32>> {
-    let assignments = CoordinatorProject::where_col(|cp| cp.admin_id.equal(admin_id))
-        .run(db)
-        .await?;
-
-    Ok(assignments
-        .into_iter()
-        .map(|state| state.project_id)
-        .collect())
+    timed(
+        "coordinator_projects.get_projects_by_coordinator",
+        async move {
+            let assignments = CoordinatorProject::where_col(|cp| cp.admin_id.equal(admin_id))
+                .run(db)
+                .await?;
+
+            Ok(assignments
+                .into_iter()
+                .map(|state| state.project_id)
+                .collect())
+        },
+    )
+    .await
 }
 
 /// Check if a coordinator is assigned to a project
 pub(crate) async fn is_assigned(
-    db: &PostgresClient, admin_id: i32, project_id: i32,
+    db: &impl Client, admin_id: i32, project_id: i32,
 ) -> welds::errors::Result<bool> {
-    let assignments = CoordinatorProject::where_col(|cp| cp.admin_id.equal(admin_id))
-        .where_col(|cp| cp.project_id.equal(project_id))
-        .run(db)
-        .await?;
+    timed("coordinator_projects.is_assigned", async move {
+        let assignments = CoordinatorProject::where_col(|cp| cp.admin_id.equal(admin_id))
+            .where_col(|cp| cp.project_id.equal(project_id))
+            .run(db)
+            .await?;
 
-    Ok(!assignments.is_empty())
+        Ok(!assignments.is_empty())
+    })
+    .await
 }
 
 /// Delete a coordinator-project assignment
 pub(crate) async fn delete(
     db: &PostgresClient, admin_id: i32, project_id: i32,
 ) -> welds::errors::Result<()> {
-    CoordinatorProject::where_col(|cp| cp.admin_id.equal(admin_id))
-        .where_col(|cp| cp.project_id.equal(project_id))
-        .delete(db)
-        .await?;
+    timed("coordinator_projects.delete", async move {
+        CoordinatorProject::where_col(|cp| cp.admin_id.equal(admin_id))
+            .where_col(|cp| cp.project_id.equal(project_id))
+            .delete(db)
+            .await?;
+
+        Ok(())
+    })
+    .await
+}
+
+/// Projects of a reassignment of coordinations, by id
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct Reassignment {
+    /// Assignments given to the target
+    pub moved: Vec<i32>,
+    /// Projects the target already coordinated, the source's assignment is dropped
+    pub skipped: Vec<i32>,
+}
+
+/// Decides which `source` assignments move to an admin already coordinating `target_projects`
+pub(crate) fn plan_reassignment(
+    source: &[CoordinatorProject], target_projects: &[i32],
+) -> Reassignment {
+    let mut reassignment = Reassignment::default();
+    for assignment in source {
+        if target_projects.contains(&assignment.project_id) {
+            reassignment.skipped.push(assignment.project_id);
+        } else {
+            reassignment.moved.push(assignment.project_id);
+        }
+    }
+    reassignment
+}
+
+/// Move every assignment of `source_id` to `target_id` in one transaction
+///
+/// Projects the target already coordinates are skipped, and the source's assignment to
+/// them is dropped, so the source coordinates nothing afterwards. Moved assignments are
+/// dated `now`.
+pub(crate) async fn reassign_all(
+    db: &PostgresClient, source_id: i32, target_id: i32, now: DateTime<Utc>,
+) -> welds::errors::Result<Reassignment> {
+    timed("coordinator_projects.reassign_all", async move {
+        let transaction = db.begin().await?;
+
+        let result = async {
+            // lock the assignments of both admins against concurrent changes
+            transaction
+                .fetch_rows(
+                    "SELECT coordinator_project_id FROM coordinator_projects \
+                     WHERE admin_id = $1 OR admin_id = $2 FOR UPDATE",
+                    &[&source_id, &target_id],
+                )
+                .await?;
+
+            let source: Vec<CoordinatorProject> =
+                CoordinatorProject::where_col(|cp| cp.admin_id.equal(source_id))
+                    .order_by_asc(|cp| cp.project_id)
+                    .run(&transaction)
+                    .await?
+                    .into_iter()
+                    .map(DbState::into_inner)
+                    .collect();
+            let target_projects: Vec<i32> =
+                CoordinatorProject::where_col(|cp| cp.admin_id.equal(target_id))
+                    .run(&transaction)
+                    .await?
+                    .into_iter()
+                    .map(|state| state.project_id)
+                    .collect();
+
+            let reassignment = plan_reassignment(&source, &target_projects);
+            for assignment in source {
+                if reassignment.skipped.contains(&assignment.project_id) {
+                    let id = assignment.coordinator_project_id;
+                    CoordinatorProject::where_col(|cp| cp.coordinator_project_id.equal(id))
+                        .delete(&transaction)
+                        .await?;
+                } else {
+                    let mut state = DbState::db_loaded(assignment);
+                    state.as_mut().admin_id = target_id;
+                    state.as_mut().assigned_at = now;
+                    state.save(&transaction).await?;
+                }
+            }
+
+            Ok(reassignment)
+        }
+        .await;
+
+        match result {
+            Ok(reassignment) => {
+                transaction.commit().await?;
+                Ok(reassignment)
+            }
+            Err(e) => {
+                transaction.rollback().await?;
+                Err(e)
+            }
+        }
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assignment(admin_id: i32, project_id: i32) -> CoordinatorProject {
+        CoordinatorProject {
+            coordinator_project_id: admin_id * 100 + project_id,
+            admin_id,
+            project_id,
+            assigned_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_coordinations_move_to_the_target() {
+        let source = vec![assignment(4, 1), assignment(4, 2)];
+
+        let reassignment = plan_reassignment(&source, &[]);
+
+        assert_eq!(reassignment.moved, vec![1, 2]);
+        assert!(reassignment.skipped.is_empty());
+    }
+
+    #[test]
+    fn test_projects_already_coordinated_by_the_target_are_skipped() {
+        let source = vec![assignment(4, 1), assignment(4, 2), assignment(4, 3)];
+
+        let reassignment = plan_reassignment(&source, &[2, 7]);
 
-    Ok(())
+        assert_eq!(reassignment.moved, vec![1, 3]);
+        assert_eq!(reassignment.skipped, vec![2]);
+    }
 }
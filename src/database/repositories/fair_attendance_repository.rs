@@ -0,0 +1,78 @@
+use crate::database::timing::timed;
+use crate::models::fair_attendance::FairAttendance;
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use welds::connections::postgres::PostgresClient;
+use welds::state::DbState;
+use welds::TransactStart;
+
+/// Get the attendance recorded for a fair, ordered by group and student
+pub(crate) async fn get_by_fair(
+    db: &PostgresClient, fair_id: i32,
+) -> welds::errors::Result<Vec<DbState<FairAttendance>>> {
+    timed("fair_attendance.get_by_fair", async move {
+        FairAttendance::where_col(|a| a.fair_id.equal(fair_id))
+            .order_by_asc(|a| a.group_id)
+            .order_by_asc(|a| a.student_id)
+            .run(db)
+            .await
+    })
+    .await
+}
+
+/// Count the attendance entries recorded for a fair
+pub(crate) async fn count_by_fair(db: &PostgresClient, fair_id: i32) -> welds::errors::Result<u64> {
+    timed("fair_attendance.count_by_fair", async move {
+        FairAttendance::where_col(|a| a.fair_id.equal(fair_id))
+            .count(db)
+            .await
+    })
+    .await
+}
+
+/// Record the given students of a group as present at a fair, atomically.
+/// Students already recorded for the fair are left untouched; returns the new entries
+pub(crate) async fn record(
+    db: &PostgresClient, fair_id: i32, group_id: i32, student_ids: Vec<i32>, admin_id: i32,
+    now: DateTime<Utc>,
+) -> welds::errors::Result<Vec<DbState<FairAttendance>>> {
+    timed("fair_attendance.record", async move {
+        let transaction = db.begin().await?;
+
+        let recorded: HashSet<i32> = match FairAttendance::where_col(|a| a.fair_id.equal(fair_id))
+            .where_col(|a| a.student_id.in_list(&student_ids))
+            .run(&transaction)
+            .await
+        {
+            Ok(rows) => rows.iter().map(|a| a.student_id).collect(),
+            Err(e) => {
+                transaction.rollback().await?;
+                return Err(e);
+            }
+        };
+
+        let mut states = Vec::new();
+        for student_id in student_ids {
+            if recorded.contains(&student_id) {
+                continue;
+            }
+            let mut state = DbState::new_uncreated(FairAttendance {
+                fair_attendance_id: 0,
+                fair_id,
+                group_id,
+                student_id,
+                recorded_by_admin_id: Some(admin_id),
+                recorded_at: now,
+            });
+            if let Err(e) = state.save(&transaction).await {
+                transaction.rollback().await?;
+                return Err(e);
+            }
+            states.push(state);
+        }
+
+        transaction.commit().await?;
+        Ok(states)
+    })
+    .await
+}
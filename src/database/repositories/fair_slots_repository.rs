@@ -0,0 +1,234 @@
+use crate::database::timing::timed;
+use crate::models::fair::Fair;
+use crate::models::fair_slot::FairSlot;
+use chrono::{DateTime, Utc};
+use welds::connections::postgres::PostgresClient;
+use welds::connections::Transaction;
+use welds::state::DbState;
+use welds::{Client, TransactStart};
+
+/// Outcome of assigning a slot, decided on the locked slots of the fair
+#[derive(Debug, Clone)]
+pub(crate) enum SlotAssignment {
+    Assigned(FairSlot),
+    /// The slot does not end after it starts
+    InvalidRange,
+    /// The slot is not within the fair's start and end dates
+    OutsideFair,
+    /// The slot overlaps the slot of another group
+    Overlaps {
+        group_id: i32,
+    },
+}
+
+/// Decides whether `group_id` can present between `starts_at` and `ends_at`
+///
+/// The current slot of the group itself is ignored, so a group can be rescheduled
+/// to a window overlapping its old one.
+pub(crate) fn plan_slot_assignment(
+    fair: &Fair, slots: &[FairSlot], group_id: i32, starts_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+) -> SlotAssignment {
+    if ends_at <= starts_at {
+        return SlotAssignment::InvalidRange;
+    }
+    if starts_at < fair.start_date || ends_at > fair.end_date {
+        return SlotAssignment::OutsideFair;
+    }
+    if let Some(other) = slots
+        .iter()
+        .find(|s| s.group_id != group_id && s.overlaps(starts_at, ends_at))
+    {
+        return SlotAssignment::Overlaps {
+            group_id: other.group_id,
+        };
+    }
+
+    let fair_slot_id = slots
+        .iter()
+        .find(|s| s.group_id == group_id)
+        .map_or(0, |s| s.fair_slot_id);
+    SlotAssignment::Assigned(FairSlot {
+        fair_slot_id,
+        fair_id: fair.fair_id,
+        group_id,
+        starts_at,
+        ends_at,
+    })
+}
+
+/// Locks the fair row for the rest of the transaction and returns its current slots
+///
+/// Concurrent assignments on the same fair are serialized, so two groups can never
+/// be given overlapping slots.
+async fn lock_slots(
+    transaction: &Transaction<'_>, fair_id: i32,
+) -> welds::errors::Result<Vec<FairSlot>> {
+    transaction
+        .fetch_rows(
+            "SELECT fair_id FROM fairs WHERE fair_id = $1 FOR UPDATE",
+            &[&fair_id],
+        )
+        .await?;
+
+    Ok(FairSlot::where_col(|s| s.fair_id.equal(fair_id))
+        .run(transaction)
+        .await?
+        .into_iter()
+        .map(DbState::into_inner)
+        .collect())
+}
+
+/// Assign or reschedule the slot of a group while holding the fair lock
+pub(crate) async fn assign_locked(
+    db: &PostgresClient, fair: &Fair, group_id: i32, starts_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+) -> welds::errors::Result<SlotAssignment> {
+    timed("fair_slots.assign_locked", async move {
+        let transaction = db.begin().await?;
+
+        let result = async {
+            let slots = lock_slots(&transaction, fair.fair_id).await?;
+            let assignment = plan_slot_assignment(fair, &slots, group_id, starts_at, ends_at);
+
+            if let SlotAssignment::Assigned(slot) = &assignment {
+                let slot_id = slot.fair_slot_id;
+                match FairSlot::where_col(|s| s.fair_slot_id.equal(slot_id))
+                    .run(&transaction)
+                    .await?
+                    .pop()
+                {
+                    Some(mut existing) => {
+                        existing.starts_at = starts_at;
+                        existing.ends_at = ends_at;
+                        existing.save(&transaction).await?;
+                    }
+                    None => {
+                        DbState::new_uncreated(slot.clone())
+                            .save(&transaction)
+                            .await?;
+                    }
+                }
+            }
+
+            Ok(assignment)
+        }
+        .await;
+
+        match result {
+            Ok(assignment) => {
+                transaction.commit().await?;
+                Ok(assignment)
+            }
+            Err(e) => {
+                transaction.rollback().await?;
+                Err(e)
+            }
+        }
+    })
+    .await
+}
+
+/// Get the slots of several groups, across all of their fairs
+pub(crate) async fn get_for_groups(
+    db: &PostgresClient, group_ids: &[i32],
+) -> welds::errors::Result<Vec<DbState<FairSlot>>> {
+    if group_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    timed("fair_slots.get_for_groups", async move {
+        FairSlot::where_col(|s| s.group_id.in_list(group_ids))
+            .run(db)
+            .await
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn fair() -> Fair {
+        let start_date = Utc.with_ymd_and_hms(2026, 6, 1, 9, 0, 0).unwrap();
+        Fair {
+            fair_id: 1,
+            project_id: 1,
+            details: String::new(),
+            start_date,
+            end_date: start_date + Duration::hours(8),
+            min_purchases: 1,
+        }
+    }
+
+    /// Slot starting `from` hours after the fair opens and lasting `hours`
+    fn at(from: i64, hours: i64) -> (DateTime<Utc>, DateTime<Utc>) {
+        let starts_at = fair().start_date + Duration::hours(from);
+        (starts_at, starts_at + Duration::hours(hours))
+    }
+
+    fn slot(fair_slot_id: i32, group_id: i32, from: i64, hours: i64) -> FairSlot {
+        let (starts_at, ends_at) = at(from, hours);
+        FairSlot {
+            fair_slot_id,
+            fair_id: 1,
+            group_id,
+            starts_at,
+            ends_at,
+        }
+    }
+
+    #[test]
+    fn test_overlapping_slot_is_rejected() {
+        let slots = [slot(1, 10, 1, 2)];
+        let (starts_at, ends_at) = at(2, 1);
+
+        let assignment = plan_slot_assignment(&fair(), &slots, 20, starts_at, ends_at);
+
+        assert!(matches!(
+            assignment,
+            SlotAssignment::Overlaps { group_id: 10 }
+        ));
+    }
+
+    #[test]
+    fn test_adjacent_slot_is_assigned() {
+        let slots = [slot(1, 10, 1, 2)];
+        let (starts_at, ends_at) = at(3, 1);
+
+        match plan_slot_assignment(&fair(), &slots, 20, starts_at, ends_at) {
+            SlotAssignment::Assigned(new_slot) => {
+                assert_eq!(new_slot.fair_slot_id, 0);
+                assert_eq!(new_slot.group_id, 20);
+                assert_eq!(new_slot.starts_at, starts_at);
+            }
+            other => panic!("expected an assignment, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rescheduling_ignores_the_group_own_slot() {
+        let slots = [slot(1, 10, 1, 2), slot(2, 20, 4, 1)];
+        let (starts_at, ends_at) = at(2, 2);
+
+        match plan_slot_assignment(&fair(), &slots, 10, starts_at, ends_at) {
+            SlotAssignment::Assigned(moved) => assert_eq!(moved.fair_slot_id, 1),
+            other => panic!("expected an assignment, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_slot_outside_fair_or_empty_is_rejected() {
+        let (starts_at, ends_at) = at(7, 2);
+        assert!(matches!(
+            plan_slot_assignment(&fair(), &[], 10, starts_at, ends_at),
+            SlotAssignment::OutsideFair
+        ));
+
+        let (starts_at, _) = at(2, 1);
+        assert!(matches!(
+            plan_slot_assignment(&fair(), &[], 10, starts_at, starts_at),
+            SlotAssignment::InvalidRange
+        ));
+    }
+}
@@ -1,71 +1,116 @@
+use crate::database::timing::timed;
 use crate::models::fair::Fair;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use welds::connections::postgres::PostgresClient;
 use welds::state::DbState;
 
 pub(crate) async fn create(
     db: &PostgresClient, fair: Fair,
 ) -> welds::errors::Result<DbState<Fair>> {
-    let mut state = DbState::new_uncreated(fair);
-    state.save(db).await?;
-    Ok(state)
+    timed("fairs.create", async move {
+        let mut state = DbState::new_uncreated(fair);
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
 }
 
 pub(crate) async fn get_by_id(
     db: &PostgresClient, fair_id: i32,
 ) -> welds::errors::Result<Option<DbState<Fair>>> {
-    let mut rows = Fair::where_col(|f| f.fair_id.equal(fair_id))
-        .run(db)
-        .await?;
-    Ok(rows.pop())
+    timed("fairs.get_by_id", async move {
+        let mut rows = Fair::where_col(|f| f.fair_id.equal(fair_id))
+            .run(db)
+            .await?;
+        Ok(rows.pop())
+    })
+    .await
 }
 
 pub(crate) async fn get_by_project_id(
     db: &PostgresClient, project_id: i32,
 ) -> welds::errors::Result<Option<DbState<Fair>>> {
-    let mut rows = Fair::where_col(|f| f.project_id.equal(project_id))
-        .run(db)
-        .await?;
-    Ok(rows.pop())
+    timed("fairs.get_by_project_id", async move {
+        let mut rows = Fair::where_col(|f| f.project_id.equal(project_id))
+            .run(db)
+            .await?;
+        Ok(rows.pop())
+    })
+    .await
+}
+
+/// Get the fairs of several projects, projects without a fair are skipped
+pub(crate) async fn get_by_project_ids(
+    db: &PostgresClient, project_ids: &[i32],
+) -> welds::errors::Result<Vec<DbState<Fair>>> {
+    timed("fairs.get_by_project_ids", async move {
+        if project_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        Fair::where_col(|f| f.project_id.in_list(project_ids))
+            .order_by_asc(|f| f.start_date)
+            .run(db)
+            .await
+    })
+    .await
 }
 
 pub(crate) async fn update(
     db: &PostgresClient, state: &mut DbState<Fair>,
 ) -> welds::errors::Result<()> {
-    state.save(db).await
+    timed("fairs.update", async move { state.save(db).await }).await
 }
 
+/// Start a fair at `now`
 pub(crate) async fn enable(
-    db: &PostgresClient, fair_id: i32,
+    db: &PostgresClient, fair_id: i32, now: DateTime<Utc>,
 ) -> welds::errors::Result<Option<DbState<Fair>>> {
-    let mut rows = Fair::where_col(|f| f.fair_id.equal(fair_id))
-        .run(db)
-        .await?;
-    if let Some(mut state) = rows.pop() {
-        state.start_date = Utc::now();
-        state.save(db).await?;
-        Ok(Some(state))
-    } else {
-        Ok(None)
-    }
+    timed("fairs.enable", async move {
+        let mut rows = Fair::where_col(|f| f.fair_id.equal(fair_id))
+            .run(db)
+            .await?;
+        if let Some(mut state) = rows.pop() {
+            state.start_date = now;
+            state.save(db).await?;
+            Ok(Some(state))
+        } else {
+            Ok(None)
+        }
+    })
+    .await
 }
 
+/// End a fair at `now`
 pub(crate) async fn disable(
-    db: &PostgresClient, fair_id: i32,
+    db: &PostgresClient, fair_id: i32, now: DateTime<Utc>,
 ) -> welds::errors::Result<Option<DbState<Fair>>> {
-    let mut rows = Fair::where_col(|f| f.fair_id.equal(fair_id))
-        .run(db)
-        .await?;
-    if let Some(mut state) = rows.pop() {
-        state.end_date = Utc::now();
-        state.save(db).await?;
-        Ok(Some(state))
-    } else {
-        Ok(None)
-    }
+    timed("fairs.disable", async move {
+        let mut rows = Fair::where_col(|f| f.fair_id.equal(fair_id))
+            .run(db)
+            .await?;
+        if let Some(mut state) = rows.pop() {
+            state.end_date = now;
+            state.save(db).await?;
+            Ok(Some(state))
+        } else {
+            Ok(None)
+        }
+    })
+    .await
+}
+
+/// Delete a fair, its transactions and attendance are removed with it
+pub(crate) async fn delete(db: &PostgresClient, fair_id: i32) -> welds::errors::Result<()> {
+    timed("fairs.delete", async move {
+        Fair::where_col(|f| f.fair_id.equal(fair_id))
+            .delete(db)
+            .await?;
+        Ok(())
+    })
+    .await
 }
 
-pub(crate) fn is_active(fair: &Fair) -> bool {
-    let now = Utc::now();
+/// Whether the fair is running at `now`
+pub(crate) fn is_active(fair: &Fair, now: DateTime<Utc>) -> bool {
     fair.start_date <= now && now <= fair.end_date
 }
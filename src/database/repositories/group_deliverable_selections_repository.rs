@@ -1,3 +1,4 @@
+use crate::database::timing::timed;
 use crate::models::group_deliverable_selection::GroupDeliverableSelection;
 use welds::connections::postgres::PostgresClient;
 use welds::state::DbState;
@@ -6,38 +7,70 @@ use welds::state::DbState;
 pub(crate) async fn get_by_group_deliverable_selection_id(
     db: &PostgresClient, selection_id: i32,
 ) -> welds::errors::Result<Option<DbState<GroupDeliverableSelection>>> {
-    let mut rows = GroupDeliverableSelection::where_col(|gds| {
-        gds.group_deliverable_selection_id.equal(selection_id)
-    })
-    .run(db)
-    .await?;
-    Ok(rows.pop())
+    timed(
+        "group_deliverable_selections.get_by_group_deliverable_selection_id",
+        async move {
+            let mut rows = GroupDeliverableSelection::where_col(|gds| {
+                gds.group_deliverable_selection_id.equal(selection_id)
+            })
+            .run(db)
+            .await?;
+            Ok(rows.pop())
+        },
+    )
+    .await
+}
+
+/// Get every selection of a group deliverable
+pub(crate) async fn get_by_deliverable_id(
+    db: &PostgresClient, group_deliverable_id: i32,
+) -> welds::errors::Result<Vec<DbState<GroupDeliverableSelection>>> {
+    timed(
+        "group_deliverable_selections.get_by_deliverable_id",
+        async move {
+            GroupDeliverableSelection::where_col(|gds| {
+                gds.group_deliverable_id.equal(group_deliverable_id)
+            })
+            .run(db)
+            .await
+        },
+    )
+    .await
 }
 
 /// Get a group deliverable selection by group ID
 pub(crate) async fn get_by_group_id(
     db: &PostgresClient, group_id: i32,
 ) -> welds::errors::Result<Option<DbState<GroupDeliverableSelection>>> {
-    let mut rows = GroupDeliverableSelection::where_col(|gds| gds.group_id.equal(group_id))
-        .run(db)
-        .await?;
+    timed("group_deliverable_selections.get_by_group_id", async move {
+        let mut rows = GroupDeliverableSelection::where_col(|gds| gds.group_id.equal(group_id))
+            .run(db)
+            .await?;
 
-    Ok(rows.pop())
+        Ok(rows.pop())
+    })
+    .await
 }
 
 /// Check if a group has already selected a deliverable
 pub(crate) async fn has_selection(
     db: &PostgresClient, group_id: i32,
 ) -> welds::errors::Result<bool> {
-    let selection = get_by_group_id(db, group_id).await?;
-    Ok(selection.is_some())
+    timed("group_deliverable_selections.has_selection", async move {
+        let selection = get_by_group_id(db, group_id).await?;
+        Ok(selection.is_some())
+    })
+    .await
 }
 
 /// Create a new group deliverable selection
 pub(crate) async fn create(
     db: &PostgresClient, group_deliverable_selection: GroupDeliverableSelection,
 ) -> welds::errors::Result<DbState<GroupDeliverableSelection>> {
-    let mut state = DbState::new_uncreated(group_deliverable_selection);
-    state.save(db).await?;
-    Ok(state)
+    timed("group_deliverable_selections.create", async move {
+        let mut state = DbState::new_uncreated(group_deliverable_selection);
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
 }
@@ -1,48 +1,101 @@
+use crate::database::timing::timed;
 use crate::models::group_deliverable::GroupDeliverable;
 use crate::models::group_deliverable_component::GroupDeliverableComponent;
 use crate::models::group_deliverables_component::GroupDeliverablesComponent;
 use welds::connections::postgres::PostgresClient;
 use welds::state::DbState;
+use welds::Client;
+
+/// Get the component links of several group deliverables
+pub(crate) async fn get_by_deliverable_ids(
+    db: &impl Client, deliverable_ids: &[i32],
+) -> welds::errors::Result<Vec<DbState<GroupDeliverablesComponent>>> {
+    timed(
+        "group_deliverables_components.get_by_deliverable_ids",
+        async move {
+            GroupDeliverablesComponent::where_col(|gdc| {
+                gdc.group_deliverable_id.in_list(deliverable_ids)
+            })
+            .run(db)
+            .await
+        },
+    )
+    .await
+}
 
 /// Get a group deliverables component relationship by its ID
 pub(crate) async fn get_by_id(
     db: &PostgresClient, id: i32,
 ) -> welds::errors::Result<Option<DbState<GroupDeliverablesComponent>>> {
-    let mut rows = GroupDeliverablesComponent::where_col(|gdc| gdc.id.equal(id))
-        .run(db)
-        .await?;
+    timed("group_deliverables_components.get_by_id", async move {
+        let mut rows = GroupDeliverablesComponent::where_col(|gdc| gdc.id.equal(id))
+            .run(db)
+            .await?;
 
-    Ok(rows.pop())
+        Ok(rows.pop())
+    })
+    .await
 }
 
 /// Check if a relationship exists between a deliverable and component
 pub(crate) async fn relationship_exists(
     db: &PostgresClient, deliverable_id: i32, component_id: i32,
 ) -> welds::errors::Result<bool> {
-    let rows =
-        GroupDeliverablesComponent::where_col(|gdc| gdc.group_deliverable_id.equal(deliverable_id))
+    timed(
+        "group_deliverables_components.relationship_exists",
+        async move {
+            let rows = GroupDeliverablesComponent::where_col(|gdc| {
+                gdc.group_deliverable_id.equal(deliverable_id)
+            })
             .where_col(|gdc| gdc.group_deliverable_component_id.equal(component_id))
             .limit(1)
             .run(db)
             .await?;
 
-    Ok(!rows.is_empty())
+            Ok(!rows.is_empty())
+        },
+    )
+    .await
 }
 
 /// Check if component is part of a deliverable
 pub(crate) async fn is_component_in_deliverable(
     db: &PostgresClient, deliverable_id: i32, component_id: i32,
 ) -> welds::errors::Result<bool> {
-    relationship_exists(db, deliverable_id, component_id).await
+    timed(
+        "group_deliverables_components.is_component_in_deliverable",
+        async move { relationship_exists(db, deliverable_id, component_id).await },
+    )
+    .await
 }
 
 /// Create a new group deliverables component relationship
 pub(crate) async fn create(
     db: &PostgresClient, group_deliverables_component: GroupDeliverablesComponent,
 ) -> welds::errors::Result<DbState<GroupDeliverablesComponent>> {
-    let mut state = DbState::new_uncreated(group_deliverables_component);
-    state.save(db).await?;
-    Ok(state)
+    timed("group_deliverables_components.create", async move {
+        let mut state = DbState::new_uncreated(group_deliverables_component);
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
+}
+
+/// Get the component links of a group deliverable
+pub(crate) async fn get_by_deliverable_id(
+    db: &PostgresClient, deliverable_id: i32,
+) -> welds::errors::Result<Vec<DbState<GroupDeliverablesComponent>>> {
+    timed(
+        "group_deliverables_components.get_by_deliverable_id",
+        async move {
+            GroupDeliverablesComponent::where_col(|gdc| {
+                gdc.group_deliverable_id.equal(deliverable_id)
+            })
+            .run(db)
+            .await
+        },
+    )
+    .await
 }
 
 /// Get components with their details for a specific group deliverable
@@ -54,26 +107,56 @@ pub(crate) async fn get_components_with_details_for_deliverable(
         DbState<GroupDeliverableComponent>,
     )>,
 > {
-    let relationships =
-        GroupDeliverablesComponent::where_col(|gdc| gdc.group_deliverable_id.equal(deliverable_id))
+    timed(
+        "group_deliverables_components.get_components_with_details_for_deliverable",
+        async move {
+            let relationships = GroupDeliverablesComponent::where_col(|gdc| {
+                gdc.group_deliverable_id.equal(deliverable_id)
+            })
             .run(db)
             .await?;
 
-    let mut result = Vec::new();
-    for relationship in relationships {
-        let mut components = GroupDeliverableComponent::where_col(|gc| {
-            gc.group_deliverable_component_id
-                .equal(relationship.group_deliverable_component_id)
-        })
-        .run(db)
-        .await?;
-
-        if let Some(component) = components.pop() {
-            result.push((relationship, component));
-        }
-    }
-
-    Ok(result)
+            let mut result = Vec::new();
+            for relationship in relationships {
+                let mut components = GroupDeliverableComponent::where_col(|gc| {
+                    gc.group_deliverable_component_id
+                        .equal(relationship.group_deliverable_component_id)
+                })
+                .run(db)
+                .await?;
+
+                if let Some(component) = components.pop() {
+                    result.push((relationship, component));
+                }
+            }
+
+            Ok(result)
+        },
+    )
+    .await
+}
+
+/// Sum the weights of the component links of a deliverable, optionally skipping one link
+pub(crate) async fn total_weight_for_deliverable(
+    db: &PostgresClient, deliverable_id: i32, excluding_id: Option<i32>,
+) -> welds::errors::Result<i32> {
+    timed(
+        "group_deliverables_components.total_weight_for_deliverable",
+        async move {
+            let rows = GroupDeliverablesComponent::where_col(|gdc| {
+                gdc.group_deliverable_id.equal(deliverable_id)
+            })
+            .run(db)
+            .await?;
+
+            Ok(rows
+                .iter()
+                .filter(|link| Some(link.id) != excluding_id)
+                .map(|link| link.weight)
+                .sum())
+        },
+    )
+    .await
 }
 
 /// Get deliverables with their details for a specific group component
@@ -85,42 +168,54 @@ pub(crate) async fn get_deliverables_with_details_for_component(
         DbState<GroupDeliverable>,
     )>,
 > {
-    let relationships = GroupDeliverablesComponent::where_col(|gdc| {
-        gdc.group_deliverable_component_id.equal(component_id)
-    })
-    .run(db)
-    .await?;
-
-    let mut result = Vec::new();
-    for relationship in relationships {
-        let mut deliverables = GroupDeliverable::where_col(|gd| {
-            gd.group_deliverable_id
-                .equal(relationship.group_deliverable_id)
-        })
-        .run(db)
-        .await?;
-
-        if let Some(deliverable) = deliverables.pop() {
-            result.push((relationship, deliverable));
-        }
-    }
-
-    Ok(result)
+    timed(
+        "group_deliverables_components.get_deliverables_with_details_for_component",
+        async move {
+            let relationships = GroupDeliverablesComponent::where_col(|gdc| {
+                gdc.group_deliverable_component_id.equal(component_id)
+            })
+            .run(db)
+            .await?;
+
+            let mut result = Vec::new();
+            for relationship in relationships {
+                let mut deliverables = GroupDeliverable::where_col(|gd| {
+                    gd.group_deliverable_id
+                        .equal(relationship.group_deliverable_id)
+                })
+                .run(db)
+                .await?;
+
+                if let Some(deliverable) = deliverables.pop() {
+                    result.push((relationship, deliverable));
+                }
+            }
+
+            Ok(result)
+        },
+    )
+    .await
 }
 /// Delete a group deliverables component relationship by ID
 pub(crate) async fn delete_by_id(
     db: &PostgresClient, relationship_id: i32,
 ) -> welds::errors::Result<()> {
-    GroupDeliverablesComponent::where_col(|gdc| gdc.id.equal(relationship_id))
-        .delete(db)
-        .await?;
-    Ok(())
+    timed("group_deliverables_components.delete_by_id", async move {
+        GroupDeliverablesComponent::where_col(|gdc| gdc.id.equal(relationship_id))
+            .delete(db)
+            .await?;
+        Ok(())
+    })
+    .await
 }
 
 /// Update a group deliverables component relationship
 pub(crate) async fn update(
     db: &PostgresClient, mut state: DbState<GroupDeliverablesComponent>,
 ) -> welds::errors::Result<DbState<GroupDeliverablesComponent>> {
-    state.save(db).await?;
-    Ok(state)
+    timed("group_deliverables_components.update", async move {
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
 }
@@ -1,88 +1,133 @@
+use crate::database::timing::timed;
 use crate::models::group_deliverable::GroupDeliverable;
 use welds::connections::postgres::PostgresClient;
 use welds::state::DbState;
+use welds::Client;
 
 /// Get all group deliverables
 pub(crate) async fn get_all(
     db: &PostgresClient,
 ) -> welds::errors::Result<Vec<DbState<GroupDeliverable>>> {
-    GroupDeliverable::all().run(db).await
+    timed("group_deliverables.get_all", async move {
+        GroupDeliverable::all().run(db).await
+    })
+    .await
 }
 
 /// Get a group deliverable by its ID
 pub(crate) async fn get_by_id(
     db: &PostgresClient, group_deliverable_id: i32,
 ) -> welds::errors::Result<Option<DbState<GroupDeliverable>>> {
-    let mut rows =
-        GroupDeliverable::where_col(|gd| gd.group_deliverable_id.equal(group_deliverable_id))
-            .run(db)
-            .await?;
+    timed("group_deliverables.get_by_id", async move {
+        let mut rows =
+            GroupDeliverable::where_col(|gd| gd.group_deliverable_id.equal(group_deliverable_id))
+                .run(db)
+                .await?;
 
-    Ok(rows.pop())
+        Ok(rows.pop())
+    })
+    .await
 }
 
 /// Get all group deliverables for a specific project
 pub(crate) async fn get_by_project_id(
-    db: &PostgresClient, project_id: i32,
+    db: &impl Client, project_id: i32,
 ) -> welds::errors::Result<Vec<DbState<GroupDeliverable>>> {
-    GroupDeliverable::where_col(|gd| gd.project_id.equal(project_id))
-        .run(db)
-        .await
+    timed("group_deliverables.get_by_project_id", async move {
+        GroupDeliverable::where_col(|gd| gd.project_id.equal(project_id))
+            .run(db)
+            .await
+    })
+    .await
 }
 
 /// Check if a group deliverable with the same name exists in a project (excluding a specific ID)
 pub(crate) async fn check_name_exists_excluding(
     db: &PostgresClient, project_id: i32, name: &str, excluding_id: i32,
 ) -> welds::errors::Result<bool> {
-    let rows = GroupDeliverable::where_col(|gd| gd.project_id.equal(project_id))
-        .where_col(|gd| gd.name.equal(name))
-        .where_col(|gd| gd.group_deliverable_id.not_equal(excluding_id))
-        .limit(1)
-        .run(db)
-        .await?;
+    timed(
+        "group_deliverables.check_name_exists_excluding",
+        async move {
+            let rows = GroupDeliverable::where_col(|gd| gd.project_id.equal(project_id))
+                .where_col(|gd| gd.name.equal(name))
+                .where_col(|gd| gd.group_deliverable_id.not_equal(excluding_id))
+                .limit(1)
+                .run(db)
+                .await?;
 
-    Ok(!rows.is_empty())
+            Ok(!rows.is_empty())
+        },
+    )
+    .await
 }
 
 /// Check if a group deliverable with the same name exists in a project
 pub(crate) async fn check_name_exists(
     db: &PostgresClient, project_id: i32, name: &str,
 ) -> welds::errors::Result<bool> {
-    let rows = GroupDeliverable::where_col(|gd| gd.project_id.equal(project_id))
-        .where_col(|gd| gd.name.equal(name))
-        .limit(1)
-        .run(db)
-        .await?;
+    timed("group_deliverables.check_name_exists", async move {
+        let rows = GroupDeliverable::where_col(|gd| gd.project_id.equal(project_id))
+            .where_col(|gd| gd.name.equal(name))
+            .limit(1)
+            .run(db)
+            .await?;
 
-    Ok(!rows.is_empty())
+        Ok(!rows.is_empty())
+    })
+    .await
 }
 
 /// Create a new group deliverable
 pub(crate) async fn create(
     db: &PostgresClient, group_deliverable: GroupDeliverable,
 ) -> welds::errors::Result<DbState<GroupDeliverable>> {
-    let mut state = DbState::new_uncreated(group_deliverable);
-    state.save(db).await?;
-    Ok(state)
+    timed("group_deliverables.create", async move {
+        let mut state = DbState::new_uncreated(group_deliverable);
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
 }
 
 /// Delete a group deliverable by ID
 pub(crate) async fn delete_by_id(
     db: &PostgresClient, group_deliverable_id: i32,
 ) -> welds::errors::Result<()> {
-    GroupDeliverable::where_col(|gd| gd.group_deliverable_id.equal(group_deliverable_id))
-        .delete(db)
-        .await?;
-    Ok(())
+    timed("group_deliverables.delete_by_id", async move {
+        GroupDeliverable::where_col(|gd| gd.group_deliverable_id.equal(group_deliverable_id))
+            .delete(db)
+            .await?;
+        Ok(())
+    })
+    .await
+}
+
+/// Delete several group deliverables at once, either directly or inside a request unit of work
+///
+/// Rows referencing them are removed by the cascading foreign keys.
+pub(crate) async fn delete_by_ids(db: &impl Client, ids: &[i32]) -> welds::errors::Result<()> {
+    timed("group_deliverables.delete_by_ids", async move {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        GroupDeliverable::where_col(|gd| gd.group_deliverable_id.in_list(ids))
+            .delete(db)
+            .await?;
+        Ok(())
+    })
+    .await
 }
 
 /// Update a group deliverable by ID
 pub(crate) async fn update_by_id(
     db: &PostgresClient, group_deliverable_id: i32, name: &str,
 ) -> welds::errors::Result<()> {
-    GroupDeliverable::where_col(|gd| gd.group_deliverable_id.equal(group_deliverable_id))
-        .set(|gd| gd.name, name)
-        .run(db)
-        .await?;
-    Ok(())
+    timed("group_deliverables.update_by_id", async move {
+        GroupDeliverable::where_col(|gd| gd.group_deliverable_id.equal(group_deliverable_id))
+            .set(|gd| gd.name, name)
+            .run(db)
+            .await?;
+        Ok(())
+    })
+    .await
 }
@@ -1,185 +1,900 @@
+use crate::database::timing::timed;
 use crate::models::group::Group;
 use crate::models::group_member::GroupMember;
 use crate::models::project::Project;
+use crate::models::student::Student;
 use crate::models::student_role::AvailableStudentRole;
 use welds::connections::postgres::PostgresClient;
 use welds::state::DbState;
+use welds::{Client, TransactStart};
 
-/// Create a new group
+/// Create a new group, either directly or inside a request unit of work
 pub(crate) async fn create_group(
-    db: &PostgresClient, group: Group,
+    db: &impl Client, group: Group,
 ) -> welds::errors::Result<DbState<Group>> {
-    let mut state = DbState::new_uncreated(group);
-    state.save(db).await?;
-    Ok(state)
+    timed("groups.create_group", async move {
+        let mut state = DbState::new_uncreated(group);
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
 }
 
-/// Create a new group member
+/// Create a new group member, either directly or inside a request unit of work
 pub(crate) async fn create_group_member(
-    db: &PostgresClient, group_member: GroupMember,
+    db: &impl Client, group_member: GroupMember,
 ) -> welds::errors::Result<DbState<GroupMember>> {
-    let mut state = DbState::new_uncreated(group_member);
-    state.save(db).await?;
-    Ok(state)
+    timed("groups.create_group_member", async move {
+        let mut state = DbState::new_uncreated(group_member);
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
+}
+
+/// Add members to a group while holding the group lock
+///
+/// `plan` is given the current members and returns the members to insert with its outcome,
+/// so the group cannot grow between the size check and the insert. Concurrent additions
+/// are serialized with each other and with removals and transfers on the same group.
+pub(crate) async fn add_members_locked<T>(
+    db: &PostgresClient, group_id: i32, plan: impl FnOnce(&[GroupMember]) -> (Vec<GroupMember>, T),
+) -> welds::errors::Result<T> {
+    timed("groups.add_members_locked", async move {
+        let transaction = db.begin().await?;
+
+        match insert_planned_members(&transaction, group_id, plan).await {
+            Ok(outcome) => {
+                transaction.commit().await?;
+                Ok(outcome)
+            }
+            Err(e) => {
+                transaction.rollback().await?;
+                Err(e)
+            }
+        }
+    })
+    .await
+}
+
+/// Locks the group and inserts the members `plan` picks from the locked members
+async fn insert_planned_members<T>(
+    db: &impl Client, group_id: i32, plan: impl FnOnce(&[GroupMember]) -> (Vec<GroupMember>, T),
+) -> welds::errors::Result<T> {
+    let members = lock_members(db, group_id).await?;
+    let (new_members, outcome) = plan(&members);
+
+    for group_member in new_members {
+        DbState::new_uncreated(group_member).save(db).await?;
+    }
+    Ok(outcome)
+}
+
+/// Get several groups by their IDs, missing ones are skipped
+pub(crate) async fn get_by_ids(
+    db: &PostgresClient, group_ids: &[i32],
+) -> welds::errors::Result<Vec<DbState<Group>>> {
+    timed("groups.get_by_ids", async move {
+        Group::where_col(|g| g.group_id.in_list(group_ids))
+            .run(db)
+            .await
+    })
+    .await
 }
 
 /// Get a group by its ID
 pub(crate) async fn get_by_id(
     db: &PostgresClient, group_id: i32,
 ) -> welds::errors::Result<Option<DbState<Group>>> {
-    let mut rows = Group::where_col(|g| g.group_id.equal(group_id))
-        .run(db)
-        .await?;
+    timed("groups.get_by_id", async move {
+        let mut rows = Group::where_col(|g| g.group_id.equal(group_id))
+            .run(db)
+            .await?;
 
-    Ok(rows.pop())
+        Ok(rows.pop())
+    })
+    .await
 }
 
 /// Get all groups for a specific project
 pub(crate) async fn get_by_project_id(
     db: &PostgresClient, project_id: i32,
 ) -> welds::errors::Result<Vec<DbState<Group>>> {
-    Group::where_col(|g| g.project_id.equal(project_id))
-        .run(db)
-        .await
+    timed("groups.get_by_project_id", async move {
+        Group::where_col(|g| g.project_id.equal(project_id))
+            .run(db)
+            .await
+    })
+    .await
 }
 
 /// Get all members of a group
 pub(crate) async fn get_members(
     db: &PostgresClient, group_id: i32,
 ) -> welds::errors::Result<Vec<DbState<GroupMember>>> {
-    GroupMember::where_col(|gm| gm.group_id.equal(group_id))
-        .run(db)
-        .await
+    timed("groups.get_members", async move {
+        GroupMember::where_col(|gm| gm.group_id.equal(group_id))
+            .run(db)
+            .await
+    })
+    .await
+}
+
+/// Get a group with all its members and their student, in a single query
+///
+/// Leaders come first, then members in the order they joined. `None` when the group does
+/// not exist.
+pub(crate) async fn get_with_members(
+    db: &impl Client, group_id: i32,
+) -> welds::errors::Result<Option<(Group, Vec<(GroupMember, Student)>)>> {
+    timed("groups.get_with_members", async move {
+        let rows = db
+            .fetch_rows(
+                "SELECT g.group_id, g.project_id, g.name, g.created_at, \
+                        gm.group_member_id, gm.student_id, gm.student_role_id, gm.joined_at, \
+                        s.first_name, s.last_name, s.email, s.university_id, \
+                        s.password_hash, s.is_pending, s.sessions_revoked_at \
+                 FROM groups g \
+                 LEFT JOIN group_members gm ON gm.group_id = g.group_id \
+                 LEFT JOIN students s ON s.student_id = gm.student_id \
+                 WHERE g.group_id = $1 \
+                 ORDER BY gm.student_role_id, gm.joined_at, gm.group_member_id",
+                &[&group_id],
+            )
+            .await?;
+
+        let Some(first) = rows.first() else {
+            return Ok(None);
+        };
+        let group = Group {
+            group_id: first.get("group_id")?,
+            project_id: first.get("project_id")?,
+            name: first.get("name")?,
+            created_at: first.get("created_at")?,
+        };
+
+        let mut members = Vec::with_capacity(rows.len());
+        for row in &rows {
+            // A group without members comes back as a single row of nulls past the group
+            let Some(group_member_id) = row.get::<Option<i32>>("group_member_id")? else {
+                continue;
+            };
+            let member = GroupMember {
+                group_member_id,
+                group_id: group.group_id,
+                student_id: row.get("student_id")?,
+                student_role_id: row.get("student_role_id")?,
+                joined_at: row.get("joined_at")?,
+            };
+            let student = Student {
+                student_id: member.student_id,
+                first_name: row.get("first_name")?,
+                last_name: row.get("last_name")?,
+                email: row.get("email")?,
+                university_id: row.get("university_id")?,
+                password_hash: row.get("password_hash")?,
+                is_pending: row.get("is_pending")?,
+                sessions_revoked_at: row.get("sessions_revoked_at")?,
+            };
+            members.push((member, student));
+        }
+        Ok(Some((group, members)))
+    })
+    .await
 }
 
 /// Get all members of a group (alias for get_members)
 pub(crate) async fn get_group_members(
     db: &PostgresClient, group_id: i32,
 ) -> welds::errors::Result<Vec<DbState<GroupMember>>> {
-    get_members(db, group_id).await
-}
-
-/// Count the number of members in a group
-pub(crate) async fn count_members(
-    db: &PostgresClient, group_id: i32,
-) -> welds::errors::Result<i32> {
-    let members = get_members(db, group_id).await?;
-    Ok(members.len() as i32)
+    timed("groups.get_group_members", async move {
+        get_members(db, group_id).await
+    })
+    .await
 }
 
 /// Check if a student is a group leader of a specific group
 pub(crate) async fn is_group_leader(
     db: &PostgresClient, student_id: i32, group_id: i32,
 ) -> welds::errors::Result<bool> {
-    let members = get_members(db, group_id).await?;
+    timed("groups.is_group_leader", async move {
+        let members = get_members(db, group_id).await?;
 
-    for member_state in members {
-        let member = DbState::into_inner(member_state);
-        if member.student_id == student_id
-            && member.student_role_id == AvailableStudentRole::GroupLeader as i32
-        {
-            return Ok(true);
+        for member_state in members {
+            let member = DbState::into_inner(member_state);
+            if member.student_id == student_id
+                && member.student_role_id == AvailableStudentRole::GroupLeader as i32
+            {
+                return Ok(true);
+            }
         }
-    }
 
-    Ok(false)
+        Ok(false)
+    })
+    .await
 }
 
 /// Check if a student is in any group for a specific project
 pub(crate) async fn is_student_in_project(
     db: &PostgresClient, student_id: i32, project_id: i32,
 ) -> welds::errors::Result<bool> {
-    let existing_membership = GroupMember::where_col(|gm| gm.student_id.equal(student_id))
-        .run(db)
-        .await?;
-
-    for membership in existing_membership {
-        let membership_data = DbState::into_inner(membership);
-        let group_states = Group::where_col(|g| g.group_id.equal(membership_data.group_id))
+    timed("groups.is_student_in_project", async move {
+        let existing_membership = GroupMember::where_col(|gm| gm.student_id.equal(student_id))
             .run(db)
-            .await
-            .unwrap_or_default();
+            .await?;
 
-        if let Some(group_state) = group_states.into_iter().next() {
-            let group = DbState::into_inner(group_state);
-            if group.project_id == project_id {
-                return Ok(true);
+        for membership in existing_membership {
+            let membership_data = DbState::into_inner(membership);
+            let group_states = Group::where_col(|g| g.group_id.equal(membership_data.group_id))
+                .run(db)
+                .await
+                .unwrap_or_default();
+
+            if let Some(group_state) = group_states.into_iter().next() {
+                let group = DbState::into_inner(group_state);
+                if group.project_id == project_id {
+                    return Ok(true);
+                }
             }
         }
-    }
 
-    Ok(false)
+        Ok(false)
+    })
+    .await
+}
+
+/// Return which of the given students already belong to a group of the project
+pub(crate) async fn students_in_project(
+    db: &PostgresClient, student_ids: &[i32], project_id: i32,
+) -> welds::errors::Result<Vec<i32>> {
+    timed("groups.students_in_project", async move {
+        if student_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let group_ids: Vec<i32> = get_by_project_id(db, project_id)
+            .await?
+            .into_iter()
+            .map(|g| g.group_id)
+            .collect();
+
+        if group_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows = GroupMember::where_col(|gm| gm.group_id.in_list(&group_ids))
+            .where_col(|gm| gm.student_id.in_list(student_ids))
+            .run(db)
+            .await?;
+
+        Ok(rows.into_iter().map(|gm| gm.student_id).collect())
+    })
+    .await
+}
+
+/// Get the group a student belongs to in a project, if any
+pub(crate) async fn get_student_group_in_project(
+    db: &PostgresClient, student_id: i32, project_id: i32,
+) -> welds::errors::Result<Option<DbState<Group>>> {
+    timed("groups.get_student_group_in_project", async move {
+        let groups = get_by_project_id(db, project_id).await?;
+        let group_ids: Vec<i32> = groups.iter().map(|g| g.group_id).collect();
+        if group_ids.is_empty() {
+            return Ok(None);
+        }
+
+        let membership = GroupMember::where_col(|gm| gm.group_id.in_list(&group_ids))
+            .where_col(|gm| gm.student_id.equal(student_id))
+            .limit(1)
+            .run(db)
+            .await?
+            .pop();
+
+        Ok(membership.and_then(|m| groups.into_iter().find(|g| g.group_id == m.group_id)))
+    })
+    .await
 }
 
 /// Delete a group and all its members
 pub(crate) async fn delete_group_with_members(
     db: &PostgresClient, group_id: i32,
 ) -> welds::errors::Result<()> {
-    // Delete all group members first
-    GroupMember::where_col(|gm| gm.group_id.equal(group_id))
-        .delete(db)
-        .await?;
+    timed("groups.delete_group_with_members", async move {
+        // Delete all group members first
+        GroupMember::where_col(|gm| gm.group_id.equal(group_id))
+            .delete(db)
+            .await?;
 
-    // Delete the group
-    Group::where_col(|g| g.group_id.equal(group_id))
-        .delete(db)
-        .await?;
+        // Delete the group
+        Group::where_col(|g| g.group_id.equal(group_id))
+            .delete(db)
+            .await?;
 
-    Ok(())
+        Ok(())
+    })
+    .await
 }
 
 /// Check if a group name already exists for a project
 pub(crate) async fn name_exists_for_project(
     db: &PostgresClient, project_id: i32, name: &str,
 ) -> welds::errors::Result<bool> {
-    let rows = Group::where_col(|g| g.project_id.equal(project_id))
-        .run(db)
-        .await?;
+    timed("groups.name_exists_for_project", async move {
+        let rows = Group::where_col(|g| g.project_id.equal(project_id))
+            .run(db)
+            .await?;
 
-    for group_state in rows {
-        let group = DbState::into_inner(group_state);
-        if group.name == name {
-            return Ok(true);
+        for group_state in rows {
+            let group = DbState::into_inner(group_state);
+            if group.name == name {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    })
+    .await
+}
+
+/// Outcome of removing a member, decided on the locked members of the group
+#[derive(Debug, Clone)]
+pub(crate) enum MemberRemoval {
+    Removed(GroupMember),
+    NotMember,
+    /// The target is the group leader and the caller is not allowed to remove it
+    LeaderProtected,
+    /// The acting student is no longer the leader of the group
+    NotLeader,
+}
+
+/// Outcome of a leadership transfer, decided on the locked members of the group
+#[derive(Debug, Clone)]
+pub(crate) enum LeadershipTransfer {
+    Transferred {
+        old_leader: GroupMember,
+        new_leader: GroupMember,
+    },
+    NoLeader,
+    /// The new leader is not a (non leader) member of the group
+    NotMember,
+}
+
+/// Decides the removal of `student_id` from `members`
+///
+/// Admins pass `acting_leader: None` and can remove anyone, leaders included.
+/// A student leader passes their own ID: they must still lead the group and
+/// cannot remove the leader.
+pub(crate) fn plan_member_removal(
+    members: &[GroupMember], student_id: i32, acting_leader: Option<i32>,
+) -> MemberRemoval {
+    let is_leader = |m: &GroupMember| m.student_role_id == AvailableStudentRole::GroupLeader as i32;
+
+    if let Some(leader_id) = acting_leader {
+        if !members
+            .iter()
+            .any(|m| m.student_id == leader_id && is_leader(m))
+        {
+            return MemberRemoval::NotLeader;
         }
     }
 
-    Ok(false)
+    match members.iter().find(|m| m.student_id == student_id) {
+        None => MemberRemoval::NotMember,
+        Some(member) if acting_leader.is_some() && is_leader(member) => {
+            MemberRemoval::LeaderProtected
+        }
+        Some(member) => MemberRemoval::Removed(member.clone()),
+    }
 }
 
-/// Delete a group member by ID
-pub(crate) async fn delete_member_by_id(
-    db: &PostgresClient, group_member_id: i32,
-) -> welds::errors::Result<()> {
-    GroupMember::where_col(|gm| gm.group_member_id.equal(group_member_id))
-        .delete(db)
+/// Decides the transfer of the leadership of `members` to `new_leader_student_id`
+pub(crate) fn plan_leadership_transfer(
+    members: &[GroupMember], new_leader_student_id: i32,
+) -> LeadershipTransfer {
+    let leader_role = AvailableStudentRole::GroupLeader as i32;
+
+    let Some(old_leader) = members.iter().find(|m| m.student_role_id == leader_role) else {
+        return LeadershipTransfer::NoLeader;
+    };
+
+    match members
+        .iter()
+        .find(|m| m.student_id == new_leader_student_id && m.student_role_id != leader_role)
+    {
+        Some(new_leader) => LeadershipTransfer::Transferred {
+            old_leader: old_leader.clone(),
+            new_leader: new_leader.clone(),
+        },
+        None => LeadershipTransfer::NotMember,
+    }
+}
+
+/// Outcome of a group split, decided on the locked members of the group
+#[derive(Debug, Clone)]
+pub(crate) enum GroupSplit {
+    /// `moved` now belong to `group`, its leader first
+    Split {
+        group: Group,
+        moved: Vec<GroupMember>,
+    },
+    /// The student is not a member of the group
+    NotMember(i32),
+    /// The new leader is not among the moved members
+    LeaderNotMoved,
+    /// No member would move, or none would stay
+    EmptyGroup,
+    /// The leader would move, leaving the group without one
+    Leaderless,
+}
+
+/// Decides the split of `members`, moving `moved_ids` to `new_group` led by `new_leader_id`
+///
+/// Both groups must keep at least one member and their own leader: the current leader
+/// stays, and the new one is picked among the moved members.
+pub(crate) fn plan_group_split(
+    members: &[GroupMember], new_group: Group, moved_ids: &[i32], new_leader_id: i32,
+) -> GroupSplit {
+    let leader_role = AvailableStudentRole::GroupLeader as i32;
+
+    let mut moved: Vec<GroupMember> = Vec::new();
+    for &student_id in moved_ids {
+        if moved.iter().any(|m| m.student_id == student_id) {
+            continue;
+        }
+        match members.iter().find(|m| m.student_id == student_id) {
+            Some(member) => moved.push(member.clone()),
+            None => return GroupSplit::NotMember(student_id),
+        }
+    }
+
+    if moved.is_empty() || moved.len() == members.len() {
+        return GroupSplit::EmptyGroup;
+    }
+    if moved.iter().any(|m| m.student_role_id == leader_role) {
+        return GroupSplit::Leaderless;
+    }
+    let Some(position) = moved.iter().position(|m| m.student_id == new_leader_id) else {
+        return GroupSplit::LeaderNotMoved;
+    };
+    moved.swap(0, position);
+
+    GroupSplit::Split {
+        group: new_group,
+        moved,
+    }
+}
+
+/// Locks the group row for the rest of the transaction and returns its current members
+///
+/// Every addition and leadership-affecting mutation goes through this, so concurrent
+/// additions, removals and transfers on the same group are serialized and always see the
+/// latest members.
+async fn lock_members(
+    transaction: &impl Client, group_id: i32,
+) -> welds::errors::Result<Vec<GroupMember>> {
+    transaction
+        .fetch_rows(
+            "SELECT group_id FROM groups WHERE group_id = $1 FOR UPDATE",
+            &[&group_id],
+        )
         .await?;
-    Ok(())
+
+    Ok(GroupMember::where_col(|gm| gm.group_id.equal(group_id))
+        .run(transaction)
+        .await?
+        .into_iter()
+        .map(DbState::into_inner)
+        .collect())
+}
+
+/// Remove a member from a group while holding the group lock
+///
+/// See `plan_member_removal` for the meaning of `acting_leader`.
+pub(crate) async fn remove_member_locked(
+    db: &PostgresClient, group_id: i32, student_id: i32, acting_leader: Option<i32>,
+) -> welds::errors::Result<MemberRemoval> {
+    timed("groups.remove_member_locked", async move {
+        let transaction = db.begin().await?;
+
+        let result = async {
+            let members = lock_members(&transaction, group_id).await?;
+            let removal = plan_member_removal(&members, student_id, acting_leader);
+
+            if let MemberRemoval::Removed(member) = &removal {
+                let member_id = member.group_member_id;
+                GroupMember::where_col(|gm| gm.group_member_id.equal(member_id))
+                    .delete(&transaction)
+                    .await?;
+            }
+
+            Ok(removal)
+        }
+        .await;
+
+        match result {
+            Ok(removal) => {
+                transaction.commit().await?;
+                Ok(removal)
+            }
+            Err(e) => {
+                transaction.rollback().await?;
+                Err(e)
+            }
+        }
+    })
+    .await
+}
+
+/// Transfer the leadership of a group while holding the group lock
+///
+/// The old leader is demoted to member, or removed when `remove_old_leader` is set.
+pub(crate) async fn transfer_leadership_locked(
+    db: &PostgresClient, group_id: i32, new_leader_student_id: i32, remove_old_leader: bool,
+) -> welds::errors::Result<LeadershipTransfer> {
+    timed("groups.transfer_leadership_locked", async move {
+        let transaction = db.begin().await?;
+
+        let result = async {
+            let members = lock_members(&transaction, group_id).await?;
+            let transfer = plan_leadership_transfer(&members, new_leader_student_id);
+
+            if let LeadershipTransfer::Transferred {
+                old_leader,
+                new_leader,
+            } = &transfer
+            {
+                if remove_old_leader {
+                    let old_id = old_leader.group_member_id;
+                    GroupMember::where_col(|gm| gm.group_member_id.equal(old_id))
+                        .delete(&transaction)
+                        .await?;
+                } else {
+                    let mut state = DbState::db_loaded(old_leader.clone());
+                    state.as_mut().student_role_id = AvailableStudentRole::Member as i32;
+                    state.save(&transaction).await?;
+                }
+
+                let mut state = DbState::db_loaded(new_leader.clone());
+                state.as_mut().student_role_id = AvailableStudentRole::GroupLeader as i32;
+                state.save(&transaction).await?;
+            }
+
+            Ok(transfer)
+        }
+        .await;
+
+        match result {
+            Ok(transfer) => {
+                transaction.commit().await?;
+                Ok(transfer)
+            }
+            Err(e) => {
+                transaction.rollback().await?;
+                Err(e)
+            }
+        }
+    })
+    .await
+}
+
+/// Split a group in two while holding the group lock
+///
+/// See `plan_group_split`. On success `new_group` is created and the moved members join
+/// it, the first one as its leader, all in one transaction.
+pub(crate) async fn split_group_locked(
+    db: &PostgresClient, group_id: i32, new_group: Group, moved_ids: &[i32], new_leader_id: i32,
+) -> welds::errors::Result<GroupSplit> {
+    timed("groups.split_group_locked", async move {
+        let transaction = db.begin().await?;
+
+        let result = async {
+            let members = lock_members(&transaction, group_id).await?;
+            let split = plan_group_split(&members, new_group, moved_ids, new_leader_id);
+
+            let GroupSplit::Split { group, moved } = split else {
+                return Ok(split);
+            };
+
+            let mut state = DbState::new_uncreated(group);
+            state.save(&transaction).await?;
+            let group = DbState::into_inner(state);
+
+            let mut moved_members = Vec::with_capacity(moved.len());
+            for (i, member) in moved.into_iter().enumerate() {
+                let mut state = DbState::db_loaded(member);
+                state.as_mut().group_id = group.group_id;
+                state.as_mut().student_role_id = if i == 0 {
+                    AvailableStudentRole::GroupLeader as i32
+                } else {
+                    AvailableStudentRole::Member as i32
+                };
+                state.save(&transaction).await?;
+                moved_members.push(DbState::into_inner(state));
+            }
+
+            Ok(GroupSplit::Split {
+                group,
+                moved: moved_members,
+            })
+        }
+        .await;
+
+        match result {
+            Ok(split) => {
+                transaction.commit().await?;
+                Ok(split)
+            }
+            Err(e) => {
+                transaction.rollback().await?;
+                Err(e)
+            }
+        }
+    })
+    .await
 }
 
 /// Get all groups for a student with their projects (returns GroupMember -> Group -> Project tuples)
 pub(crate) async fn get_groups_with_projects_for_student(
     db: &PostgresClient, student_id: i32,
 ) -> welds::errors::Result<Vec<(DbState<GroupMember>, DbState<Group>, DbState<Project>)>> {
-    let group_members = GroupMember::where_col(|gm| gm.student_id.equal(student_id))
-        .run(db)
-        .await?;
-
-    let mut result = Vec::new();
-    for gm in group_members {
-        let mut groups = Group::where_col(|g| g.group_id.equal(gm.group_id))
+    timed("groups.get_groups_with_projects_for_student", async move {
+        let group_members = GroupMember::where_col(|gm| gm.student_id.equal(student_id))
             .run(db)
             .await?;
 
-        if let Some(group) = groups.pop() {
-            let mut projects = Project::where_col(|p| p.project_id.equal(group.project_id))
+        let mut result = Vec::new();
+        for gm in group_members {
+            let mut groups = Group::where_col(|g| g.group_id.equal(gm.group_id))
                 .run(db)
                 .await?;
 
-            if let Some(project) = projects.pop() {
-                result.push((gm, group, project));
+            if let Some(group) = groups.pop() {
+                let mut projects = Project::where_col(|p| p.project_id.equal(group.project_id))
+                    .run(db)
+                    .await?;
+
+                if let Some(project) = projects.pop() {
+                    result.push((gm, group, project));
+                }
+            }
+        }
+
+        Ok(result)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RecordingClient;
+    use chrono::Utc;
+
+    fn member(student_id: i32, role: AvailableStudentRole) -> GroupMember {
+        GroupMember {
+            group_member_id: student_id * 10,
+            group_id: 1,
+            student_id,
+            student_role_id: role as i32,
+            joined_at: Utc::now(),
+        }
+    }
+
+    fn leaders(members: &[GroupMember]) -> Vec<i32> {
+        members
+            .iter()
+            .filter(|m| m.student_role_id == AvailableStudentRole::GroupLeader as i32)
+            .map(|m| m.student_id)
+            .collect()
+    }
+
+    /// Applies a removal the way `remove_member_locked` does, on the current members
+    fn remove(members: &mut Vec<GroupMember>, student_id: i32) -> MemberRemoval {
+        let removal = plan_member_removal(members, student_id, None);
+        if let MemberRemoval::Removed(removed) = &removal {
+            members.retain(|m| m.group_member_id != removed.group_member_id);
+        }
+        removal
+    }
+
+    /// Applies a transfer the way `transfer_leadership_locked` does, on the current members
+    fn transfer(members: &mut [GroupMember], new_leader_student_id: i32) -> LeadershipTransfer {
+        let transfer = plan_leadership_transfer(members, new_leader_student_id);
+        if let LeadershipTransfer::Transferred {
+            old_leader,
+            new_leader,
+        } = &transfer
+        {
+            for m in members.iter_mut() {
+                if m.group_member_id == old_leader.group_member_id {
+                    m.student_role_id = AvailableStudentRole::Member as i32;
+                } else if m.group_member_id == new_leader.group_member_id {
+                    m.student_role_id = AvailableStudentRole::GroupLeader as i32;
+                }
             }
         }
+        transfer
+    }
+
+    #[test]
+    fn test_concurrent_transfer_and_removal_of_target_stay_consistent() {
+        let initial = vec![
+            member(1, AvailableStudentRole::GroupLeader),
+            member(2, AvailableStudentRole::Member),
+            member(3, AvailableStudentRole::Member),
+        ];
+
+        // The group lock serializes the two calls, whichever wins the race
+        // the loser sees the winner's result instead of a stale member list.
+
+        // Transfer first: the removal then removes the freshly promoted leader
+        // and reports it, the group is left without a leader but never with two
+        let mut members = initial.clone();
+        assert!(matches!(
+            transfer(&mut members, 2),
+            LeadershipTransfer::Transferred { .. }
+        ));
+        match remove(&mut members, 2) {
+            MemberRemoval::Removed(removed) => assert_eq!(
+                removed.student_role_id,
+                AvailableStudentRole::GroupLeader as i32
+            ),
+            other => panic!("unexpected removal outcome: {:?}", other),
+        }
+        assert!(leaders(&members).is_empty());
+        assert!(members.iter().all(|m| m.student_id != 2));
+
+        // Removal first: the transfer no longer finds its target and the
+        // old leader keeps the group
+        let mut members = initial.clone();
+        assert!(matches!(remove(&mut members, 2), MemberRemoval::Removed(_)));
+        assert!(matches!(
+            transfer(&mut members, 2),
+            LeadershipTransfer::NotMember
+        ));
+        assert_eq!(leaders(&members), vec![1]);
     }
 
-    Ok(result)
+    #[test]
+    fn test_student_leader_cannot_remove_promoted_member() {
+        let mut members = vec![
+            member(1, AvailableStudentRole::GroupLeader),
+            member(2, AvailableStudentRole::Member),
+        ];
+
+        transfer(&mut members, 2);
+
+        // The old leader acting on a stale view is rejected on the locked members
+        assert!(matches!(
+            plan_member_removal(&members, 2, Some(1)),
+            MemberRemoval::NotLeader
+        ));
+        assert!(matches!(
+            plan_member_removal(&members, 2, Some(2)),
+            MemberRemoval::LeaderProtected
+        ));
+    }
+
+    #[test]
+    fn test_transfer_requires_leader_and_non_leader_target() {
+        let members = vec![
+            member(1, AvailableStudentRole::GroupLeader),
+            member(2, AvailableStudentRole::Member),
+        ];
+
+        assert!(matches!(
+            plan_leadership_transfer(&members, 1),
+            LeadershipTransfer::NotMember
+        ));
+        assert!(matches!(
+            plan_leadership_transfer(&members[1..], 2),
+            LeadershipTransfer::NoLeader
+        ));
+    }
+
+    fn new_group() -> Group {
+        Group {
+            group_id: 0,
+            project_id: 3,
+            name: "Rusty Robots B".to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_split_moves_members_under_their_new_leader() {
+        let members = vec![
+            member(1, AvailableStudentRole::GroupLeader),
+            member(2, AvailableStudentRole::Member),
+            member(3, AvailableStudentRole::Member),
+            member(4, AvailableStudentRole::Member),
+        ];
+
+        let GroupSplit::Split { group, moved } =
+            plan_group_split(&members, new_group(), &[3, 4, 3], 4)
+        else {
+            panic!("the split should be accepted");
+        };
+
+        assert_eq!(group.name, "Rusty Robots B");
+        let moved_ids: Vec<i32> = moved.iter().map(|m| m.student_id).collect();
+        assert_eq!(moved_ids, vec![4, 3]);
+        let stays: Vec<GroupMember> = members
+            .into_iter()
+            .filter(|m| !moved_ids.contains(&m.student_id))
+            .collect();
+        assert_eq!(leaders(&stays), vec![1]);
+    }
+
+    #[test]
+    fn test_split_leaving_a_group_empty_or_leaderless_is_rejected() {
+        let members = vec![
+            member(1, AvailableStudentRole::GroupLeader),
+            member(2, AvailableStudentRole::Member),
+        ];
+
+        assert!(matches!(
+            plan_group_split(&members, new_group(), &[1, 2], 2),
+            GroupSplit::EmptyGroup
+        ));
+        assert!(matches!(
+            plan_group_split(&members, new_group(), &[], 2),
+            GroupSplit::EmptyGroup
+        ));
+        assert!(matches!(
+            plan_group_split(&members, new_group(), &[1], 1),
+            GroupSplit::Leaderless
+        ));
+        assert!(matches!(
+            plan_group_split(&members, new_group(), &[2], 1),
+            GroupSplit::LeaderNotMoved
+        ));
+        assert!(matches!(
+            plan_group_split(&members, new_group(), &[9], 9),
+            GroupSplit::NotMember(9)
+        ));
+    }
+
+    #[actix_web::test]
+    async fn test_group_members_and_students_are_loaded_in_one_query() {
+        let db = RecordingClient::default();
+
+        assert!(get_with_members(&db, 3).await.unwrap().is_none());
+
+        let statements = db.statements();
+        assert_eq!(statements.len(), 1);
+        // Groups without members are still found
+        assert!(
+            statements[0].contains(
+                "FROM groups g LEFT JOIN group_members gm ON gm.group_id = g.group_id \
+                 LEFT JOIN students s ON s.student_id = gm.student_id WHERE g.group_id = $1"
+            ),
+            "{}",
+            statements[0]
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_members_are_added_after_locking_the_group() {
+        let db = RecordingClient::default();
+
+        let planned_on = insert_planned_members(&db, 1, |members| {
+            (vec![member(5, AvailableStudentRole::Member)], members.len())
+        })
+        .await;
+
+        let statements = db.statements();
+        assert_eq!(
+            statements[0],
+            "SELECT group_id FROM groups WHERE group_id = $1 FOR UPDATE"
+        );
+        assert!(statements[1].contains("group_members"), "{}", statements[1]);
+        // Planned on the members read under the lock, then inserted
+        assert_eq!(planned_on.ok(), Some(0));
+        assert!(
+            statements[2].starts_with("INSERT INTO"),
+            "{}",
+            statements[2]
+        );
+    }
 }
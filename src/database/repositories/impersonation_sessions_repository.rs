@@ -0,0 +1,108 @@
+use crate::database::timing::timed;
+use crate::models::audit_log::{AuditLog, IMPERSONATION_ENDED, IMPERSONATION_STARTED};
+use crate::models::impersonation_session::ImpersonationSession;
+use chrono::Utc;
+use welds::connections::postgres::PostgresClient;
+use welds::state::DbState;
+use welds::TransactStart;
+
+/// Start an impersonation session, recording it in the audit log in the same transaction
+pub(crate) async fn start(
+    db: &PostgresClient, session: ImpersonationSession,
+) -> welds::errors::Result<DbState<ImpersonationSession>> {
+    timed("impersonation_sessions.start", async move {
+        let transaction = db.begin().await?;
+
+        let mut state = DbState::new_uncreated(session);
+        if let Err(e) = state.save(&transaction).await {
+            transaction.rollback().await?;
+            return Err(e);
+        }
+
+        let mut entry = DbState::new_uncreated(start_entry(&state));
+        if let Err(e) = entry.save(&transaction).await {
+            transaction.rollback().await?;
+            return Err(e);
+        }
+
+        transaction.commit().await?;
+        Ok(state)
+    })
+    .await
+}
+
+/// Get an impersonation session by its ID
+pub(crate) async fn get_by_id(
+    db: &PostgresClient, impersonation_session_id: i32,
+) -> welds::errors::Result<Option<DbState<ImpersonationSession>>> {
+    timed("impersonation_sessions.get_by_id", async move {
+        let mut rows = ImpersonationSession::where_col(|s| {
+            s.impersonation_session_id.equal(impersonation_session_id)
+        })
+        .limit(1)
+        .run(db)
+        .await?;
+
+        Ok(rows.pop())
+    })
+    .await
+}
+
+/// End an active session started by `admin_id`, recording it in the audit log
+///
+/// Returns `false` when the session does not exist, belongs to another admin or already ended.
+pub(crate) async fn end(
+    db: &PostgresClient, admin_id: i32, impersonation_session_id: i32,
+) -> welds::errors::Result<bool> {
+    timed("impersonation_sessions.end", async move {
+        let transaction = db.begin().await?;
+
+        let mut rows = ImpersonationSession::where_col(|s| {
+            s.impersonation_session_id.equal(impersonation_session_id)
+        })
+        .where_col(|s| s.admin_id.equal(admin_id))
+        .where_col(|s| s.ended_at.equal(None))
+        .limit(1)
+        .run(&transaction)
+        .await?;
+
+        let Some(mut state) = rows.pop() else {
+            transaction.rollback().await?;
+            return Ok(false);
+        };
+
+        state.ended_at = Some(Utc::now());
+        if let Err(e) = state.save(&transaction).await {
+            transaction.rollback().await?;
+            return Err(e);
+        }
+
+        let mut entry = DbState::new_uncreated(end_entry(&state));
+        if let Err(e) = entry.save(&transaction).await {
+            transaction.rollback().await?;
+            return Err(e);
+        }
+
+        transaction.commit().await?;
+        Ok(true)
+    })
+    .await
+}
+
+/// Audit entry recorded when `session` starts
+pub(crate) fn start_entry(session: &ImpersonationSession) -> AuditLog {
+    AuditLog::by_admin(session.admin_id, IMPERSONATION_STARTED)
+        .target("student", session.student_id)
+        .details(format!(
+            "session {} valid until {}",
+            session.impersonation_session_id,
+            session.expires_at.to_rfc3339()
+        ))
+}
+
+/// Audit entry recorded when `session` is ended by its admin
+fn end_entry(session: &ImpersonationSession) -> AuditLog {
+    AuditLog::by_admin(session.admin_id, IMPERSONATION_ENDED)
+        .target("student", session.student_id)
+        .details(format!("session {}", session.impersonation_session_id))
+}
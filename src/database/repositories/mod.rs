@@ -1,7 +1,12 @@
+pub(crate) mod admin_api_tokens_repository;
+pub(crate) mod admin_roles_repository;
 pub(crate) mod admins_repository;
+pub(crate) mod audit_log_repository;
 pub(crate) mod blacklist_repository;
 pub(crate) mod complaints_repository;
 pub(crate) mod coordinator_projects_repository;
+pub(crate) mod fair_attendance_repository;
+pub(crate) mod fair_slots_repository;
 pub(crate) mod fairs_repository;
 pub(crate) mod group_component_implementation_details_repository;
 pub(crate) mod group_deliverable_components_repository;
@@ -9,13 +14,17 @@ pub(crate) mod group_deliverable_selections_repository;
 pub(crate) mod group_deliverables_components_repository;
 pub(crate) mod group_deliverables_repository;
 pub(crate) mod groups_repository;
+pub(crate) mod impersonation_sessions_repository;
 pub(crate) mod oral_exam_repository;
+pub(crate) mod orphans_repository;
 pub(crate) mod projects_repository;
 pub(crate) mod security_codes;
 pub(crate) mod student_deliverable_components_repository;
 pub(crate) mod student_deliverable_selections_repository;
 pub(crate) mod student_deliverables_components_repository;
 pub(crate) mod student_deliverables_repository;
+pub(crate) mod student_project_access_repository;
 pub(crate) mod student_uploads_repository;
 pub(crate) mod students_repository;
 pub(crate) mod transactions_repository;
+pub(crate) mod used_reset_tokens_repository;
@@ -1,3 +1,4 @@
+use crate::database::timing::timed;
 use crate::models::oral_exam_completion::OralExamCompletion;
 use crate::models::oral_exam_note::OralExamNote;
 use chrono::{DateTime, Utc};
@@ -9,54 +10,63 @@ use welds::state::DbState;
 pub(crate) async fn get_note(
     db: &PostgresClient, student_id: i32, project_id: i32,
 ) -> welds::errors::Result<Option<DbState<OralExamNote>>> {
-    let mut rows = OralExamNote::where_col(|n| n.student_id.equal(student_id))
-        .where_col(|n| n.project_id.equal(project_id))
-        .limit(1)
-        .run(db)
-        .await?;
-    Ok(rows.pop())
+    timed("oral_exam.get_note", async move {
+        let mut rows = OralExamNote::where_col(|n| n.student_id.equal(student_id))
+            .where_col(|n| n.project_id.equal(project_id))
+            .limit(1)
+            .run(db)
+            .await?;
+        Ok(rows.pop())
+    })
+    .await
 }
 
 pub(crate) async fn upsert_note(
     db: &PostgresClient, student_id: i32, project_id: i32, note_text: String,
     updated_by_admin_id: i32, now: DateTime<Utc>,
 ) -> welds::errors::Result<DbState<OralExamNote>> {
-    if let Some(mut existing) = get_note(db, student_id, project_id).await? {
-        existing.as_mut().note_text = note_text;
-        existing.as_mut().updated_at = now;
-        existing.as_mut().updated_by_admin_id = Some(updated_by_admin_id);
-        existing.save(db).await?;
-        Ok(existing)
-    } else {
-        let note = OralExamNote {
-            note_id: 0,
-            student_id,
-            project_id,
-            note_text,
-            updated_at: now,
-            updated_by_admin_id: Some(updated_by_admin_id),
-        };
-        let mut state = DbState::new_uncreated(note);
-        state.save(db).await?;
-        Ok(state)
-    }
+    timed("oral_exam.upsert_note", async move {
+        if let Some(mut existing) = get_note(db, student_id, project_id).await? {
+            existing.as_mut().note_text = note_text;
+            existing.as_mut().updated_at = now;
+            existing.as_mut().updated_by_admin_id = Some(updated_by_admin_id);
+            existing.save(db).await?;
+            Ok(existing)
+        } else {
+            let note = OralExamNote {
+                note_id: 0,
+                student_id,
+                project_id,
+                note_text,
+                updated_at: now,
+                updated_by_admin_id: Some(updated_by_admin_id),
+            };
+            let mut state = DbState::new_uncreated(note);
+            state.save(db).await?;
+            Ok(state)
+        }
+    })
+    .await
 }
 
 pub(crate) async fn delete_note(
     db: &PostgresClient, student_id: i32, project_id: i32,
 ) -> welds::errors::Result<bool> {
-    let rows = OralExamNote::where_col(|n| n.student_id.equal(student_id))
-        .where_col(|n| n.project_id.equal(project_id))
-        .run(db)
-        .await?;
-    if rows.is_empty() {
-        return Ok(false);
-    }
-    OralExamNote::where_col(|n| n.student_id.equal(student_id))
-        .where_col(|n| n.project_id.equal(project_id))
-        .delete(db)
-        .await?;
-    Ok(true)
+    timed("oral_exam.delete_note", async move {
+        let rows = OralExamNote::where_col(|n| n.student_id.equal(student_id))
+            .where_col(|n| n.project_id.equal(project_id))
+            .run(db)
+            .await?;
+        if rows.is_empty() {
+            return Ok(false);
+        }
+        OralExamNote::where_col(|n| n.student_id.equal(student_id))
+            .where_col(|n| n.project_id.equal(project_id))
+            .delete(db)
+            .await?;
+        Ok(true)
+    })
+    .await
 }
 
 // ── Completions ────────────────────────────────────────────────────────────
@@ -64,20 +74,26 @@ pub(crate) async fn delete_note(
 pub(crate) async fn get_completion(
     db: &PostgresClient, student_id: i32, project_id: i32,
 ) -> welds::errors::Result<Option<DbState<OralExamCompletion>>> {
-    let mut rows = OralExamCompletion::where_col(|c| c.student_id.equal(student_id))
-        .where_col(|c| c.project_id.equal(project_id))
-        .limit(1)
-        .run(db)
-        .await?;
-    Ok(rows.pop())
+    timed("oral_exam.get_completion", async move {
+        let mut rows = OralExamCompletion::where_col(|c| c.student_id.equal(student_id))
+            .where_col(|c| c.project_id.equal(project_id))
+            .limit(1)
+            .run(db)
+            .await?;
+        Ok(rows.pop())
+    })
+    .await
 }
 
 pub(crate) async fn get_completions_for_project(
     db: &PostgresClient, project_id: i32,
 ) -> welds::errors::Result<Vec<DbState<OralExamCompletion>>> {
-    OralExamCompletion::where_col(|c| c.project_id.equal(project_id))
-        .run(db)
-        .await
+    timed("oral_exam.get_completions_for_project", async move {
+        OralExamCompletion::where_col(|c| c.project_id.equal(project_id))
+            .run(db)
+            .await
+    })
+    .await
 }
 
 /// Mark student as completed. If already completed, updates timestamp and admin.
@@ -85,38 +101,44 @@ pub(crate) async fn mark_completed(
     db: &PostgresClient, student_id: i32, project_id: i32, completed_by_admin_id: i32,
     now: DateTime<Utc>,
 ) -> welds::errors::Result<DbState<OralExamCompletion>> {
-    if let Some(mut existing) = get_completion(db, student_id, project_id).await? {
-        existing.as_mut().completed_at = now;
-        existing.as_mut().completed_by_admin_id = Some(completed_by_admin_id);
-        existing.save(db).await?;
-        Ok(existing)
-    } else {
-        let completion = OralExamCompletion {
-            completion_id: 0,
-            student_id,
-            project_id,
-            completed_at: now,
-            completed_by_admin_id: Some(completed_by_admin_id),
-        };
-        let mut state = DbState::new_uncreated(completion);
-        state.save(db).await?;
-        Ok(state)
-    }
+    timed("oral_exam.mark_completed", async move {
+        if let Some(mut existing) = get_completion(db, student_id, project_id).await? {
+            existing.as_mut().completed_at = now;
+            existing.as_mut().completed_by_admin_id = Some(completed_by_admin_id);
+            existing.save(db).await?;
+            Ok(existing)
+        } else {
+            let completion = OralExamCompletion {
+                completion_id: 0,
+                student_id,
+                project_id,
+                completed_at: now,
+                completed_by_admin_id: Some(completed_by_admin_id),
+            };
+            let mut state = DbState::new_uncreated(completion);
+            state.save(db).await?;
+            Ok(state)
+        }
+    })
+    .await
 }
 
 pub(crate) async fn mark_incomplete(
     db: &PostgresClient, student_id: i32, project_id: i32,
 ) -> welds::errors::Result<bool> {
-    let rows = OralExamCompletion::where_col(|c| c.student_id.equal(student_id))
-        .where_col(|c| c.project_id.equal(project_id))
-        .run(db)
-        .await?;
-    if rows.is_empty() {
-        return Ok(false);
-    }
-    OralExamCompletion::where_col(|c| c.student_id.equal(student_id))
-        .where_col(|c| c.project_id.equal(project_id))
-        .delete(db)
-        .await?;
-    Ok(true)
+    timed("oral_exam.mark_incomplete", async move {
+        let rows = OralExamCompletion::where_col(|c| c.student_id.equal(student_id))
+            .where_col(|c| c.project_id.equal(project_id))
+            .run(db)
+            .await?;
+        if rows.is_empty() {
+            return Ok(false);
+        }
+        OralExamCompletion::where_col(|c| c.student_id.equal(student_id))
+            .where_col(|c| c.project_id.equal(project_id))
+            .delete(db)
+            .await?;
+        Ok(true)
+    })
+    .await
 }
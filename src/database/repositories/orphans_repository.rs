@@ -0,0 +1,240 @@
+use crate::database::timing::timed;
+use serde::Serialize;
+use utoipa::ToSchema;
+use welds::connections::postgres::PostgresClient;
+use welds::connections::Row;
+use welds::{Client, TransactStart};
+
+/// Kind of row left inconsistent by earlier changes
+///
+/// Foreign keys cascade, so no row points to a deleted one. These rows point to rows that
+/// still exist but no longer fit together, such as a selection of a student who left every
+/// group of the project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum OrphanKind {
+    /// Link between a group deliverable and a component of another project
+    GroupComponentLink,
+    /// Link between a student deliverable and a component of another project
+    StudentComponentLink,
+    /// Group selection of a deliverable of another project than the group's
+    GroupSelection,
+    /// Implementation details of a component no longer part of the selected deliverable
+    ImplementationDetail,
+    /// Student selection of a student no longer in a group of the project
+    StudentSelection,
+}
+
+impl OrphanKind {
+    /// Every kind, in report order
+    pub(crate) const ALL: [OrphanKind; 5] = [
+        OrphanKind::GroupComponentLink,
+        OrphanKind::StudentComponentLink,
+        OrphanKind::GroupSelection,
+        OrphanKind::ImplementationDetail,
+        OrphanKind::StudentSelection,
+    ];
+
+    /// Kinds the cleanup deletes, in order: removing a link can orphan implementation details
+    ///
+    /// Selections are only reported. Deleting a group selection would cascade into the
+    /// append-only transactions bought for it, and a student moving between two groups of
+    /// the project briefly looks like one who left it.
+    pub(crate) const CLEANED_UP: [OrphanKind; 3] = [
+        OrphanKind::GroupComponentLink,
+        OrphanKind::StudentComponentLink,
+        OrphanKind::ImplementationDetail,
+    ];
+
+    fn table(self) -> &'static str {
+        match self {
+            OrphanKind::GroupComponentLink => "group_deliverables_components",
+            OrphanKind::StudentComponentLink => "student_deliverables_components",
+            OrphanKind::GroupSelection => "group_deliverable_selections",
+            OrphanKind::ImplementationDetail => "group_component_implementation_details",
+            OrphanKind::StudentSelection => "student_deliverable_selections",
+        }
+    }
+
+    fn id_column(self) -> &'static str {
+        match self {
+            OrphanKind::GroupSelection => "group_deliverable_selection_id",
+            OrphanKind::StudentSelection => "student_deliverable_selection_id",
+            _ => "id",
+        }
+    }
+
+    /// Query returning the `id` and `project_id` of every orphan of this kind, `o` being
+    /// the orphan row
+    pub(crate) fn find_sql(self) -> String {
+        let (joins, condition) = match self {
+            OrphanKind::GroupComponentLink => (
+                "JOIN group_deliverables d ON d.group_deliverable_id = o.group_deliverable_id \
+                 JOIN group_deliverable_components c \
+                   ON c.group_deliverable_component_id = o.group_deliverable_component_id",
+                "c.project_id <> d.project_id",
+            ),
+            OrphanKind::StudentComponentLink => (
+                "JOIN student_deliverables d ON d.student_deliverable_id = o.student_deliverable_id \
+                 JOIN student_deliverable_components c \
+                   ON c.student_deliverable_component_id = o.student_deliverable_component_id",
+                "c.project_id <> d.project_id",
+            ),
+            OrphanKind::GroupSelection => (
+                "JOIN group_deliverables d ON d.group_deliverable_id = o.group_deliverable_id \
+                 JOIN groups g ON g.group_id = o.group_id",
+                "g.project_id <> d.project_id",
+            ),
+            OrphanKind::ImplementationDetail => (
+                "JOIN group_deliverable_selections s \
+                   ON s.group_deliverable_selection_id = o.group_deliverable_selection_id \
+                 JOIN group_deliverables d ON d.group_deliverable_id = s.group_deliverable_id",
+                "NOT EXISTS (SELECT 1 FROM group_deliverables_components l \
+                   WHERE l.group_deliverable_id = s.group_deliverable_id \
+                   AND l.group_deliverable_component_id = o.group_deliverable_component_id)",
+            ),
+            OrphanKind::StudentSelection => (
+                "JOIN student_deliverables d ON d.student_deliverable_id = o.student_deliverable_id",
+                "NOT EXISTS (SELECT 1 FROM group_members gm \
+                   JOIN groups g ON g.group_id = gm.group_id \
+                   WHERE gm.student_id = o.student_id AND g.project_id = d.project_id)",
+            ),
+        };
+        format!(
+            "SELECT o.{id} AS id, d.project_id AS project_id FROM {table} o {joins} WHERE {condition}",
+            id = self.id_column(),
+            table = self.table(),
+        )
+    }
+
+    /// Statement deleting every orphan of this kind, returning what [`Self::find_sql`] does
+    pub(crate) fn delete_sql(self) -> String {
+        format!(
+            "WITH orphans AS ({find}) \
+             DELETE FROM {table} t USING orphans WHERE t.{id} = orphans.id \
+             RETURNING orphans.id AS id, orphans.project_id AS project_id",
+            find = self.find_sql(),
+            table = self.table(),
+            id = self.id_column(),
+        )
+    }
+}
+
+/// One inconsistent row, with the project it belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Orphan {
+    pub kind: OrphanKind,
+    pub id: i32,
+    pub project_id: i32,
+}
+
+fn orphans_of(kind: OrphanKind, rows: Vec<Row>) -> welds::errors::Result<Vec<Orphan>> {
+    rows.iter()
+        .map(|row| {
+            Ok(Orphan {
+                kind,
+                id: row.get("id")?,
+                project_id: row.get("project_id")?,
+            })
+        })
+        .collect()
+}
+
+/// Get every orphaned row, kind by kind in report order
+pub(crate) async fn find_all(db: &impl Client) -> welds::errors::Result<Vec<Orphan>> {
+    timed("orphans.find_all", async move {
+        let mut orphans = Vec::new();
+        for kind in OrphanKind::ALL {
+            let rows = db.fetch_rows(&kind.find_sql(), &[]).await?;
+            orphans.extend(orphans_of(kind, rows)?);
+        }
+        Ok(orphans)
+    })
+    .await
+}
+
+/// Delete the orphaned rows of every kind the cleanup handles, returning the deleted ones
+async fn delete_cleaned_up(db: &impl Client) -> welds::errors::Result<Vec<Orphan>> {
+    let mut orphans = Vec::new();
+    for kind in OrphanKind::CLEANED_UP {
+        let rows = db.fetch_rows(&kind.delete_sql(), &[]).await?;
+        orphans.extend(orphans_of(kind, rows)?);
+    }
+    Ok(orphans)
+}
+
+/// Delete the orphaned links and implementation details in one transaction, returning the
+/// deleted rows
+///
+/// With `dry_run` the transaction is rolled back, so the result is exactly what a real
+/// cleanup would delete at this time. Orphaned selections are left alone, see
+/// [`OrphanKind::CLEANED_UP`].
+pub(crate) async fn delete_all(
+    db: &PostgresClient, dry_run: bool,
+) -> welds::errors::Result<Vec<Orphan>> {
+    timed("orphans.delete_all", async move {
+        let transaction = db.begin().await?;
+
+        match delete_cleaned_up(&transaction).await {
+            Ok(orphans) if !dry_run => {
+                transaction.commit().await?;
+                Ok(orphans)
+            }
+            Ok(orphans) => {
+                transaction.rollback().await?;
+                Ok(orphans)
+            }
+            Err(e) => {
+                transaction.rollback().await?;
+                Err(e)
+            }
+        }
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RecordingClient;
+
+    #[actix_web::test]
+    async fn test_every_kind_is_reported() {
+        let client = RecordingClient::default();
+
+        assert!(find_all(&client).await.unwrap().is_empty());
+
+        let statements = client.statements();
+        assert_eq!(statements.len(), OrphanKind::ALL.len());
+        for (kind, sql) in OrphanKind::ALL.into_iter().zip(&statements) {
+            assert!(
+                sql.starts_with(&format!("SELECT o.{} AS id", kind.id_column())),
+                "{:?}: {}",
+                kind,
+                sql
+            );
+            assert!(sql.contains(&format!("FROM {} o", kind.table())));
+        }
+        assert!(statements[4]
+            .contains("WHERE gm.student_id = o.student_id AND g.project_id = d.project_id"));
+    }
+
+    #[actix_web::test]
+    async fn test_cleanup_leaves_selections_alone() {
+        let client = RecordingClient::default();
+
+        assert!(delete_cleaned_up(&client).await.unwrap().is_empty());
+
+        let statements = client.statements();
+        assert_eq!(statements.len(), 3, "links and implementation details only");
+        for (kind, sql) in OrphanKind::CLEANED_UP.into_iter().zip(&statements) {
+            // Deletes what detection reports
+            assert!(sql.contains(&kind.find_sql()), "{:?}", kind);
+            assert!(sql.contains(&format!("DELETE FROM {} t", kind.table())));
+        }
+        for sql in &statements {
+            assert!(!sql.contains("DELETE FROM group_deliverable_selections"));
+            assert!(!sql.contains("DELETE FROM student_deliverable_selections"));
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use crate::database::timing::timed;
+use crate::models::fair::Fair;
 use crate::models::group_deliverable::GroupDeliverable;
 use crate::models::group_deliverable_component::GroupDeliverableComponent;
 use crate::models::project::Project;
@@ -5,22 +7,144 @@ use crate::models::student_deliverable::StudentDeliverable;
 use crate::models::student_deliverable_component::StudentDeliverableComponent;
 use chrono::{DateTime, Utc};
 use welds::connections::postgres::PostgresClient;
+use welds::query::builder::{ManualParam, QueryBuilder};
 use welds::state::DbState;
+use welds::Client;
 
 /// Get all projects from the database
 pub(crate) async fn get_all(db: &PostgresClient) -> welds::errors::Result<Vec<DbState<Project>>> {
-    Project::all().run(db).await
+    timed(
+        "projects.get_all",
+        async move { Project::all().run(db).await },
+    )
+    .await
+}
+
+/// Count all projects
+pub(crate) async fn count(db: &PostgresClient) -> welds::errors::Result<u64> {
+    timed(
+        "projects.count",
+        async move { Project::all().count(db).await },
+    )
+    .await
+}
+
+/// Filters of the admin projects listing, every unset field matches everything
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct ProjectsFilter {
+    pub year: Option<i32>,
+    pub active: Option<bool>,
+    pub frozen: Option<bool>,
+    /// Restricts the listing to the projects assigned to this coordinator
+    pub coordinator_id: Option<i32>,
+}
+
+/// SQL filter matching projects assigned to a coordinator
+const ASSIGNED_TO_COORDINATOR: &str = "EXISTS (SELECT 1 FROM coordinator_projects cp \
+     WHERE cp.project_id = $.project_id AND cp.admin_id = ?)";
+
+impl ProjectsFilter {
+    fn apply(&self, mut query: QueryBuilder<Project>) -> QueryBuilder<Project> {
+        if let Some(year) = self.year {
+            query = query.where_col(|p| p.year.equal(year));
+        }
+        if let Some(active) = self.active {
+            query = query.where_col(|p| p.active.equal(active));
+        }
+        if let Some(frozen) = self.frozen {
+            query = query.where_col(|p| p.frozen.equal(frozen));
+        }
+        if let Some(coordinator_id) = self.coordinator_id {
+            query = query.where_manual2(
+                ASSIGNED_TO_COORDINATOR,
+                ManualParam::new().with(coordinator_id),
+            );
+        }
+        query
+    }
+}
+
+/// Count the projects matching a filter
+pub(crate) async fn count_filtered(
+    db: &PostgresClient, filter: &ProjectsFilter,
+) -> welds::errors::Result<u64> {
+    timed("projects.count_filtered", async move {
+        filter.apply(Project::all()).count(db).await
+    })
+    .await
 }
 
 /// Get a project by its ID
 pub(crate) async fn get_by_id(
-    db: &PostgresClient, project_id: i32,
+    db: &impl Client, project_id: i32,
+) -> welds::errors::Result<Option<DbState<Project>>> {
+    timed("projects.get_by_id", async move {
+        let mut rows = Project::where_col(|p| p.project_id.equal(project_id))
+            .run(db)
+            .await?;
+
+        Ok(rows.pop())
+    })
+    .await
+}
+
+/// Get several projects by id, in one query
+pub(crate) async fn get_by_ids(
+    db: &impl Client, project_ids: &[i32],
+) -> welds::errors::Result<Vec<DbState<Project>>> {
+    if project_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    timed("projects.get_by_ids", async move {
+        Project::where_col(|p| p.project_id.in_list(project_ids))
+            .run(db)
+            .await
+    })
+    .await
+}
+
+/// Get a project by its slug
+pub(crate) async fn get_by_slug(
+    db: &PostgresClient, slug: &str,
 ) -> welds::errors::Result<Option<DbState<Project>>> {
-    let mut rows = Project::where_col(|p| p.project_id.equal(project_id))
-        .run(db)
-        .await?;
+    timed("projects.get_by_slug", async move {
+        let mut rows = Project::where_col(|p| p.slug.equal(slug)).run(db).await?;
+
+        Ok(rows.pop())
+    })
+    .await
+}
 
-    Ok(rows.pop())
+/// Get the slugs starting with `base`, the candidates a new slug could collide with
+pub(crate) async fn slugs_starting_with(
+    db: &impl Client, base: &str,
+) -> welds::errors::Result<Vec<String>> {
+    timed("projects.slugs_starting_with", async move {
+        let rows = Project::where_col(|p| p.slug.like(format!("{}%", base)))
+            .run(db)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|p| DbState::into_inner(p).slug)
+            .collect())
+    })
+    .await
+}
+
+/// Change the slug of a project
+pub(crate) async fn update_slug(
+    db: &PostgresClient, project_id: i32, slug: String,
+) -> welds::errors::Result<()> {
+    timed("projects.update_slug", async move {
+        Project::where_col(|p| p.project_id.equal(project_id))
+            .set(|p| p.slug, slug)
+            .run(db)
+            .await?;
+        Ok(())
+    })
+    .await
 }
 
 /// Delete a project by its ID
@@ -28,25 +152,31 @@ pub(crate) async fn get_by_id(
 pub(crate) async fn delete_by_id(
     db: &PostgresClient, project_id: i32,
 ) -> welds::errors::Result<bool> {
-    let mut rows = Project::where_col(|p| p.project_id.equal(project_id))
-        .run(db)
-        .await?;
-
-    if let Some(mut state) = rows.pop() {
-        state.delete(db).await?;
-        Ok(true)
-    } else {
-        Ok(false)
-    }
+    timed("projects.delete_by_id", async move {
+        let mut rows = Project::where_col(|p| p.project_id.equal(project_id))
+            .run(db)
+            .await?;
+
+        if let Some(mut state) = rows.pop() {
+            state.delete(db).await?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    })
+    .await
 }
 
 /// Create a new project
 pub(crate) async fn create(
     db: &PostgresClient, project: Project,
 ) -> welds::errors::Result<DbState<Project>> {
-    let mut state = DbState::new_uncreated(project);
-    state.save(db).await?;
-    Ok(state)
+    timed("projects.create", async move {
+        let mut state = DbState::new_uncreated(project);
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
 }
 
 /// Update a project by ID
@@ -54,42 +184,45 @@ pub(crate) async fn update_by_id(
     db: &PostgresClient, project_id: i32, name: Option<String>, max_student_uploads: Option<i32>,
     max_group_size: Option<i32>, upload_deadline: Option<DateTime<Utc>>, active: Option<bool>,
 ) -> welds::errors::Result<()> {
-    if let Some(name) = name {
-        Project::where_col(|p| p.project_id.equal(project_id))
-            .set(|p| p.name, name)
-            .run(db)
-            .await?;
-    }
-    if let Some(uploads) = max_student_uploads {
-        Project::where_col(|p| p.project_id.equal(project_id))
-            .set(|p| p.max_student_uploads, uploads)
-            .run(db)
-            .await?;
-    }
-    if let Some(size) = max_group_size {
-        Project::where_col(|p| p.project_id.equal(project_id))
-            .set(|p| p.max_group_size, size)
-            .run(db)
-            .await?;
-    }
-    if let Some(upload_deadline) = upload_deadline {
-        Project::where_col(|p| p.project_id.equal(project_id))
-            .set(|p| p.upload_deadline, upload_deadline)
-            .run(db)
-            .await?;
-    }
-    if let Some(active) = active {
-        Project::where_col(|p| p.project_id.equal(project_id))
-            .set(|p| p.active, active)
-            .run(db)
-            .await?;
-    }
-    Ok(())
+    timed("projects.update_by_id", async move {
+        if let Some(name) = name {
+            Project::where_col(|p| p.project_id.equal(project_id))
+                .set(|p| p.name, name)
+                .run(db)
+                .await?;
+        }
+        if let Some(uploads) = max_student_uploads {
+            Project::where_col(|p| p.project_id.equal(project_id))
+                .set(|p| p.max_student_uploads, uploads)
+                .run(db)
+                .await?;
+        }
+        if let Some(size) = max_group_size {
+            Project::where_col(|p| p.project_id.equal(project_id))
+                .set(|p| p.max_group_size, size)
+                .run(db)
+                .await?;
+        }
+        if let Some(upload_deadline) = upload_deadline {
+            Project::where_col(|p| p.project_id.equal(project_id))
+                .set(|p| p.upload_deadline, upload_deadline)
+                .run(db)
+                .await?;
+        }
+        if let Some(active) = active {
+            Project::where_col(|p| p.project_id.equal(project_id))
+                .set(|p| p.active, active)
+                .run(db)
+                .await?;
+        }
+        Ok(())
+    })
+    .await
 }
 
 /// Get project details with all related entities
 pub(crate) async fn get_project_details(
-    db: &PostgresClient, project_id: i32,
+    db: &impl Client, project_id: i32,
 ) -> welds::errors::Result<
     Option<(
         DbState<Project>,
@@ -99,72 +232,12 @@ pub(crate) async fn get_project_details(
         Vec<DbState<StudentDeliverableComponent>>,
     )>,
 > {
-    // Get the project
-    let project_state = match get_by_id(db, project_id).await? {
-        Some(state) => state,
-        None => return Ok(None),
-    };
-
-    // Get group deliverables
-    let group_deliverables = Project::where_col(|p| p.project_id.equal(project_id))
-        .map_query(|p| p.group_deliverables)
-        .run(db)
-        .await?;
-
-    // Get group components
-    let group_components = Project::where_col(|p| p.project_id.equal(project_id))
-        .map_query(|p| p.group_deliverable_components)
-        .run(db)
-        .await?;
-
-    // Get student deliverables
-    let student_deliverables = Project::where_col(|p| p.project_id.equal(project_id))
-        .map_query(|p| p.student_deliverables)
-        .run(db)
-        .await?;
-
-    // Get student components
-    let student_components = Project::where_col(|p| p.project_id.equal(project_id))
-        .map_query(|p| p.student_deliverable_components)
-        .run(db)
-        .await?;
-
-    Ok(Some((
-        project_state,
-        group_deliverables,
-        group_components,
-        student_deliverables,
-        student_components,
-    )))
-}
-
-/// Get projects with all related entities for a student
-pub(crate) async fn get_projects_with_details_for_student(
-    db: &PostgresClient, student_id: i32,
-) -> welds::errors::Result<
-    Vec<(
-        DbState<Project>,
-        Vec<DbState<GroupDeliverable>>,
-        Vec<DbState<GroupDeliverableComponent>>,
-        Vec<DbState<StudentDeliverable>>,
-        Vec<DbState<StudentDeliverableComponent>>,
-        Option<i32>,
-    )>,
-> {
-    use crate::database::repositories::fairs_repository;
-    use crate::models::group_member::GroupMember;
-
-    // Get projects through group membership
-    let projects = GroupMember::where_col(|gm| gm.student_id.equal(student_id))
-        .map_query(|gm| gm.group)
-        .map_query(|g| g.project)
-        .run(db)
-        .await?;
-
-    let mut result = Vec::new();
-
-    for project in projects {
-        let project_id = project.project_id;
+    timed("projects.get_project_details", async move {
+        // Get the project
+        let project_state = match get_by_id(db, project_id).await? {
+            Some(state) => state,
+            None => return Ok(None),
+        };
 
         // Get group deliverables
         let group_deliverables = Project::where_col(|p| p.project_id.equal(project_id))
@@ -190,19 +263,494 @@ pub(crate) async fn get_projects_with_details_for_student(
             .run(db)
             .await?;
 
-        let fair_id = fairs_repository::get_by_project_id(db, project_id)
-            .await?
-            .map(|fair| fair.fair_id);
-
-        result.push((
-            project,
+        Ok(Some((
+            project_state,
             group_deliverables,
             group_components,
             student_deliverables,
             student_components,
-            fair_id,
-        ));
+        )))
+    })
+    .await
+}
+
+/// SQL filter matching the published projects a student has access to, through a group membership
+/// or a redeemed security code
+const STUDENT_VISIBLE_PROJECT: &str = "$.published AND (EXISTS (SELECT 1 FROM group_members gm \
+     JOIN groups g ON g.group_id = gm.group_id \
+     WHERE gm.student_id = ? AND g.project_id = $.project_id) \
+     OR EXISTS (SELECT 1 FROM student_project_access spa \
+     WHERE spa.student_id = ? AND spa.project_id = $.project_id))";
+
+/// Projects a student has access to, binding the student for both branches of
+/// `STUDENT_VISIBLE_PROJECT`
+fn visible_for_student(student_id: i32) -> QueryBuilder<Project> {
+    Project::all().where_manual2(
+        STUDENT_VISIBLE_PROJECT,
+        ManualParam::new().with(student_id).with(student_id),
+    )
+}
+
+/// Count the projects a student has access to
+pub(crate) async fn count_visible_for_student(
+    db: &PostgresClient, student_id: i32,
+) -> welds::errors::Result<u64> {
+    timed("projects.count_visible_for_student", async move {
+        visible_for_student(student_id).count(db).await
+    })
+    .await
+}
+
+/// Check whether a student has access to a project
+pub(crate) async fn is_visible_for_student(
+    db: &PostgresClient, student_id: i32, project_id: i32,
+) -> welds::errors::Result<bool> {
+    timed("projects.is_visible_for_student", async move {
+        let count = visible_for_student(student_id)
+            .where_col(|p| p.project_id.equal(project_id))
+            .count(db)
+            .await?;
+        Ok(count > 0)
+    })
+    .await
+}
+
+/// Get the projects a student has access to in a single query, only the `(limit, offset)`
+/// page of them when one is given
+pub(crate) async fn get_visible_for_student(
+    db: &PostgresClient, student_id: i32, page: Option<(i64, i64)>,
+) -> welds::errors::Result<Vec<DbState<Project>>> {
+    timed("projects.get_visible_for_student", async move {
+        let query = visible_for_student(student_id).order_by_asc(|p| p.project_id);
+        match page {
+            Some((limit, offset)) => query.limit(limit).offset(offset).run(db).await,
+            None => query.run(db).await,
+        }
+    })
+    .await
+}
+
+/// Get the projects of a student, or one `(limit, offset)` page of them, with all related entities
+///
+/// Related entities are loaded with one query per entity type for all the returned projects,
+/// so the number of queries does not grow with the number of projects
+pub(crate) async fn get_projects_with_details_for_student(
+    db: &PostgresClient, student_id: i32, page: Option<(i64, i64)>,
+) -> welds::errors::Result<
+    Vec<(
+        DbState<Project>,
+        Vec<DbState<GroupDeliverable>>,
+        Vec<DbState<GroupDeliverableComponent>>,
+        Vec<DbState<StudentDeliverable>>,
+        Vec<DbState<StudentDeliverableComponent>>,
+        Option<i32>,
+    )>,
+> {
+    timed(
+        "projects.get_projects_with_details_for_student",
+        async move {
+            let projects = get_visible_for_student(db, student_id, page).await?;
+            if projects.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            let project_ids: Vec<i32> = projects.iter().map(|p| p.project_id).collect();
+
+            let mut group_deliverables =
+                GroupDeliverable::where_col(|d| d.project_id.in_list(&project_ids))
+                    .run(db)
+                    .await?;
+            let mut group_components =
+                GroupDeliverableComponent::where_col(|c| c.project_id.in_list(&project_ids))
+                    .run(db)
+                    .await?;
+            let mut student_deliverables =
+                StudentDeliverable::where_col(|d| d.project_id.in_list(&project_ids))
+                    .run(db)
+                    .await?;
+            let mut student_components =
+                StudentDeliverableComponent::where_col(|c| c.project_id.in_list(&project_ids))
+                    .run(db)
+                    .await?;
+            let fairs = Fair::where_col(|f| f.project_id.in_list(&project_ids))
+                .run(db)
+                .await?;
+
+            let result = projects
+                .into_iter()
+                .map(|project| {
+                    let project_id = project.project_id;
+                    let fair_id = fairs
+                        .iter()
+                        .find(|f| f.project_id == project_id)
+                        .map(|f| f.fair_id);
+
+                    (
+                        project,
+                        take_for_project(&mut group_deliverables, project_id, |d| d.project_id),
+                        take_for_project(&mut group_components, project_id, |c| c.project_id),
+                        take_for_project(&mut student_deliverables, project_id, |d| d.project_id),
+                        take_for_project(&mut student_components, project_id, |c| c.project_id),
+                        fair_id,
+                    )
+                })
+                .collect();
+
+            Ok(result)
+        },
+    )
+    .await
+}
+
+/// A student in one of the groups of a project
+#[derive(Debug, Clone)]
+pub(crate) struct ProjectMemberRow {
+    pub student_id: i32,
+    pub first_name: String,
+    pub last_name: String,
+    pub email: String,
+    pub group_id: i32,
+    pub group_name: String,
+    pub student_role_id: i32,
+}
+
+/// Members of every group of the project bound to `$1`, matching the `ILIKE` pattern bound
+/// to `$2` on the name or email when it is not null
+const PROJECT_MEMBERS_FILTER: &str = "FROM group_members gm \
+     JOIN groups g ON g.group_id = gm.group_id \
+     JOIN students s ON s.student_id = gm.student_id \
+     WHERE g.project_id = $1 \
+       AND ($2::TEXT IS NULL \
+            OR s.first_name ILIKE $2 \
+            OR s.last_name ILIKE $2 \
+            OR s.first_name || ' ' || s.last_name ILIKE $2 \
+            OR s.email ILIKE $2)";
+
+/// Count the members of all the groups of a project matching `pattern`
+pub(crate) async fn count_members(
+    db: &impl Client, project_id: i32, pattern: Option<String>,
+) -> welds::errors::Result<u64> {
+    timed("projects.count_members", async move {
+        let rows = db
+            .fetch_rows(
+                &format!("SELECT COUNT(*) AS total {}", PROJECT_MEMBERS_FILTER),
+                &[&project_id, &pattern],
+            )
+            .await?;
+
+        let mut total: i64 = 0;
+        for row in rows {
+            total = row.get("total")?;
+        }
+        Ok(total as u64)
+    })
+    .await
+}
+
+/// Get one page of the members of all the groups of a project matching `pattern`, sorted by
+/// name
+pub(crate) async fn get_members(
+    db: &impl Client, project_id: i32, pattern: Option<String>, limit: i64, offset: i64,
+) -> welds::errors::Result<Vec<ProjectMemberRow>> {
+    timed("projects.get_members", async move {
+        let rows = db
+            .fetch_rows(
+                &format!(
+                    "SELECT s.student_id, s.first_name, s.last_name, s.email, \
+                            g.group_id, g.name AS group_name, gm.student_role_id \
+                     {} \
+                     ORDER BY s.last_name, s.first_name, s.student_id \
+                     LIMIT $3 OFFSET $4",
+                    PROJECT_MEMBERS_FILTER
+                ),
+                &[&project_id, &pattern, &limit, &offset],
+            )
+            .await?;
+
+        let mut members = Vec::with_capacity(rows.len());
+        for row in rows {
+            members.push(ProjectMemberRow {
+                student_id: row.get("student_id")?,
+                first_name: row.get("first_name")?,
+                last_name: row.get("last_name")?,
+                email: row.get("email")?,
+                group_id: row.get("group_id")?,
+                group_name: row.get("group_name")?,
+                student_role_id: row.get("student_role_id")?,
+            });
+        }
+        Ok(members)
+    })
+    .await
+}
+
+/// A student with access to a project who is in none of its groups
+#[derive(Debug, Clone)]
+pub(crate) struct UngroupedStudentRow {
+    pub student_id: i32,
+    pub first_name: String,
+    pub last_name: String,
+    pub email: String,
+    pub university_id: i32,
+    pub granted_at: DateTime<Utc>,
+}
+
+/// Students with access to the project bound to `$1` that are in none of its groups
+const UNGROUPED_FILTER: &str = "FROM student_project_access spa \
+     JOIN students s ON s.student_id = spa.student_id \
+     WHERE spa.project_id = $1 \
+       AND NOT EXISTS (SELECT 1 \
+                       FROM group_members gm \
+                       JOIN groups g ON g.group_id = gm.group_id \
+                       WHERE gm.student_id = spa.student_id \
+                         AND g.project_id = spa.project_id)";
+
+/// Count the students with access to a project that are in none of its groups
+pub(crate) async fn count_ungrouped(
+    db: &impl Client, project_id: i32,
+) -> welds::errors::Result<u64> {
+    timed("projects.count_ungrouped", async move {
+        let rows = db
+            .fetch_rows(
+                &format!("SELECT COUNT(*) AS total {}", UNGROUPED_FILTER),
+                &[&project_id],
+            )
+            .await?;
+
+        let mut total: i64 = 0;
+        for row in rows {
+            total = row.get("total")?;
+        }
+        Ok(total as u64)
+    })
+    .await
+}
+
+/// Get one page of the students with access to a project that are in none of its groups,
+/// sorted by name
+pub(crate) async fn get_ungrouped(
+    db: &impl Client, project_id: i32, limit: i64, offset: i64,
+) -> welds::errors::Result<Vec<UngroupedStudentRow>> {
+    timed("projects.get_ungrouped", async move {
+        let rows = db
+            .fetch_rows(
+                &format!(
+                    "SELECT s.student_id, s.first_name, s.last_name, s.email, s.university_id, \
+                            spa.granted_at \
+                     {} \
+                     ORDER BY s.last_name, s.first_name, s.student_id \
+                     LIMIT $2 OFFSET $3",
+                    UNGROUPED_FILTER
+                ),
+                &[&project_id, &limit, &offset],
+            )
+            .await?;
+
+        let mut students = Vec::with_capacity(rows.len());
+        for row in rows {
+            students.push(UngroupedStudentRow {
+                student_id: row.get("student_id")?,
+                first_name: row.get("first_name")?,
+                last_name: row.get("last_name")?,
+                email: row.get("email")?,
+                university_id: row.get("university_id")?,
+                granted_at: row.get("granted_at")?,
+            });
+        }
+        Ok(students)
+    })
+    .await
+}
+
+/// Components a group has to provide and has provided for its selected deliverable
+#[derive(Debug, Clone)]
+pub(crate) struct GroupProgressRow {
+    pub group_id: i32,
+    pub group_name: String,
+    /// Deliverable selected by the group, if any
+    pub group_deliverable_id: Option<i32>,
+    pub required_components: i64,
+    pub completed_components: i64,
+}
+
+/// Get the progress of every group of a project, by group name
+///
+/// Groups without a selected deliverable are kept, with no required component.
+pub(crate) async fn progress(
+    db: &impl Client, project_id: i32,
+) -> welds::errors::Result<Vec<GroupProgressRow>> {
+    timed("projects.progress", async move {
+        let rows = db
+            .fetch_rows(
+                "SELECT g.group_id, g.name AS group_name, gds.group_deliverable_id, \
+                        COUNT(DISTINCT gdc.group_deliverable_component_id) AS required_components, \
+                        COUNT(DISTINCT gcid.group_deliverable_component_id) AS completed_components \
+                 FROM groups g \
+                 LEFT JOIN group_deliverable_selections gds ON gds.group_id = g.group_id \
+                 LEFT JOIN group_deliverables_components gdc \
+                   ON gdc.group_deliverable_id = gds.group_deliverable_id \
+                 LEFT JOIN group_component_implementation_details gcid \
+                   ON gcid.group_deliverable_selection_id = gds.group_deliverable_selection_id \
+                   AND gcid.group_deliverable_component_id = gdc.group_deliverable_component_id \
+                 WHERE g.project_id = $1 \
+                 GROUP BY g.group_id, g.name, gds.group_deliverable_id \
+                 ORDER BY g.name",
+                &[&project_id],
+            )
+            .await?;
+
+        let mut groups = Vec::with_capacity(rows.len());
+        for row in rows {
+            groups.push(GroupProgressRow {
+                group_id: row.get("group_id")?,
+                group_name: row.get("group_name")?,
+                group_deliverable_id: row.get("group_deliverable_id")?,
+                required_components: row.get("required_components")?,
+                completed_components: row.get("completed_components")?,
+            });
+        }
+        Ok(groups)
+    })
+    .await
+}
+
+/// Moves the rows belonging to `project_id` out of `rows`
+fn take_for_project<T>(
+    rows: &mut Vec<DbState<T>>, project_id: i32, project_of: impl Fn(&T) -> i32,
+) -> Vec<DbState<T>> {
+    let (matching, rest) = std::mem::take(rows)
+        .into_iter()
+        .partition(|row| project_of(row) == project_id);
+    *rows = rest;
+    matching
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RecordingClient;
+    use welds::Syntax;
+
+    #[test]
+    fn test_students_only_see_projects_they_are_linked_to() {
+        let sql = visible_for_student(4).to_sql(Syntax::Postgres);
+
+        // Both ways in are scoped to the student and to the listed project
+        assert!(
+            sql.contains("WHERE gm.student_id = $1 AND g.project_id = t1.project_id"),
+            "{}",
+            sql
+        );
+        assert!(
+            sql.contains("WHERE spa.student_id = $2 AND spa.project_id = t1.project_id"),
+            "{}",
+            sql
+        );
+        assert_eq!(sql.matches("EXISTS").count(), 2, "{}", sql);
+    }
+    #[test]
+    fn test_students_only_see_published_projects() {
+        let sql = visible_for_student(4).to_sql(Syntax::Postgres);
+
+        assert!(sql.contains(".published AND (EXISTS"), "{}", sql);
+    }
+
+    #[actix_web::test]
+    async fn test_members_are_searched_and_paged_across_every_group() {
+        let db = RecordingClient::default();
+
+        count_members(&db, 4, Some("%rossi%".to_string()))
+            .await
+            .unwrap();
+        get_members(&db, 4, Some("%rossi%".to_string()), 20, 40)
+            .await
+            .unwrap();
+
+        let statements = db.statements();
+        assert_eq!(statements.len(), 2);
+        for sql in &statements {
+            // Scoped to the project, not to one of its groups
+            assert!(sql.contains("WHERE g.project_id = $1"), "{}", sql);
+            assert!(!sql.contains("gm.group_id = $"), "{}", sql);
+            for column in [
+                "s.first_name ILIKE $2",
+                "s.last_name ILIKE $2",
+                "s.first_name || ' ' || s.last_name ILIKE $2",
+                "s.email ILIKE $2",
+            ] {
+                assert!(sql.contains(column), "{}", sql);
+            }
+        }
+        // Pages follow one order over all the groups, ties broken by id
+        assert!(
+            statements[1]
+                .contains("ORDER BY s.last_name, s.first_name, s.student_id LIMIT $3 OFFSET $4"),
+            "{}",
+            statements[1]
+        );
     }
 
-    Ok(result)
+    #[actix_web::test]
+    async fn test_only_students_outside_every_group_are_ungrouped() {
+        let db = RecordingClient::default();
+
+        count_ungrouped(&db, 4).await.unwrap();
+        get_ungrouped(&db, 4, 20, 40).await.unwrap();
+
+        let statements = db.statements();
+        assert_eq!(statements.len(), 2);
+        for sql in &statements {
+            // Students with access to the project, minus those in any of its groups
+            assert!(sql.contains("WHERE spa.project_id = $1"), "{}", sql);
+            assert!(
+                sql.contains(
+                    "AND NOT EXISTS (SELECT 1 FROM group_members gm \
+                     JOIN groups g ON g.group_id = gm.group_id \
+                     WHERE gm.student_id = spa.student_id AND g.project_id = spa.project_id)"
+                ),
+                "{}",
+                sql
+            );
+        }
+        assert!(
+            statements[1]
+                .contains("ORDER BY s.last_name, s.first_name, s.student_id LIMIT $2 OFFSET $3"),
+            "{}",
+            statements[1]
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_progress_counts_components_per_group() {
+        let db = RecordingClient::default();
+
+        progress(&db, 4).await.unwrap();
+
+        let statements = db.statements();
+        assert_eq!(statements.len(), 1);
+        let sql = &statements[0];
+        // Groups without a selection, or with nothing implemented yet, are kept
+        assert!(sql.contains("FROM groups g LEFT JOIN group_deliverable_selections gds ON gds.group_id = g.group_id"), "{}", sql);
+        assert!(
+            sql.contains("LEFT JOIN group_deliverables_components gdc"),
+            "{}",
+            sql
+        );
+        assert!(
+            sql.contains(
+                "LEFT JOIN group_component_implementation_details gcid \
+                 ON gcid.group_deliverable_selection_id = gds.group_deliverable_selection_id \
+                 AND gcid.group_deliverable_component_id = gdc.group_deliverable_component_id"
+            ),
+            "{}",
+            sql
+        );
+        assert!(
+            sql.contains(
+                "WHERE g.project_id = $1 GROUP BY g.group_id, g.name, gds.group_deliverable_id"
+            ),
+            "{}",
+            sql
+        );
+    }
 }
@@ -1,39 +1,51 @@
+use crate::database::timing::timed;
 use crate::models::project::Project;
 use crate::models::security_code::SecurityCode;
+use chrono::{DateTime, Utc};
 use welds::connections::postgres::PostgresClient;
 use welds::state::DbState;
+use welds::{Client, TransactStart};
 
 /// Check if a security code exists
 pub(crate) async fn security_code_exists(
     db: &PostgresClient, code: &str,
 ) -> welds::errors::Result<bool> {
-    let rows = SecurityCode::where_col(|sc| sc.code.equal(code))
-        .limit(1)
-        .run(db)
-        .await?;
-    Ok(!rows.is_empty())
+    timed("security_codes.security_code_exists", async move {
+        let rows = SecurityCode::where_col(|sc| sc.code.equal(code))
+            .limit(1)
+            .run(db)
+            .await?;
+        Ok(!rows.is_empty())
+    })
+    .await
 }
 
 /// Get a security code by its code string
 pub(crate) async fn get_by_code(
     db: &PostgresClient, code: &str,
 ) -> welds::errors::Result<Option<DbState<SecurityCode>>> {
-    let mut rows = SecurityCode::where_col(|sc| sc.code.equal(code))
-        .run(db)
-        .await?;
+    timed("security_codes.get_by_code", async move {
+        let mut rows = SecurityCode::where_col(|sc| sc.code.equal(code))
+            .run(db)
+            .await?;
 
-    Ok(rows.pop())
+        Ok(rows.pop())
+    })
+    .await
 }
 
 /// Get a security code by its ID
 pub(crate) async fn get_by_id(
     db: &PostgresClient, security_code_id: i32,
 ) -> welds::errors::Result<Option<DbState<SecurityCode>>> {
-    let mut rows = SecurityCode::where_col(|sc| sc.security_code_id.equal(security_code_id))
-        .run(db)
-        .await?;
+    timed("security_codes.get_by_id", async move {
+        let mut rows = SecurityCode::where_col(|sc| sc.security_code_id.equal(security_code_id))
+            .run(db)
+            .await?;
 
-    Ok(rows.pop())
+        Ok(rows.pop())
+    })
+    .await
 }
 
 /// Update a security code
@@ -41,60 +53,234 @@ pub(crate) async fn update(
     db: &PostgresClient, security_code_id: i32, code: String,
     expiration: chrono::DateTime<chrono::Utc>,
 ) -> welds::errors::Result<Option<DbState<SecurityCode>>> {
-    let mut security_code =
-        SecurityCode::where_col(|sc| sc.security_code_id.equal(security_code_id))
-            .run(db)
-            .await?;
+    timed("security_codes.update", async move {
+        let mut security_code =
+            SecurityCode::where_col(|sc| sc.security_code_id.equal(security_code_id))
+                .run(db)
+                .await?;
 
-    if let Some(mut code_state) = security_code.pop() {
-        code_state.code = code;
-        code_state.expiration = expiration;
-        code_state.save(db).await?;
-        Ok(Some(code_state))
-    } else {
-        Ok(None)
-    }
+        if let Some(mut code_state) = security_code.pop() {
+            code_state.code = code;
+            code_state.expiration = expiration;
+            code_state.save(db).await?;
+            Ok(Some(code_state))
+        } else {
+            Ok(None)
+        }
+    })
+    .await
 }
 
 /// Delete a security code
 pub(crate) async fn delete(
     db: &PostgresClient, security_code_id: i32,
 ) -> welds::errors::Result<()> {
-    SecurityCode::where_col(|sc| sc.security_code_id.equal(security_code_id))
-        .delete(db)
-        .await?;
+    timed("security_codes.delete", async move {
+        SecurityCode::where_col(|sc| sc.security_code_id.equal(security_code_id))
+            .delete(db)
+            .await?;
 
-    Ok(())
+        Ok(())
+    })
+    .await
 }
 
 /// Create a new security code
 pub(crate) async fn create(
     db: &PostgresClient, security_code: SecurityCode,
 ) -> welds::errors::Result<DbState<SecurityCode>> {
-    let mut state = DbState::new_uncreated(security_code);
-    state.save(db).await?;
-    Ok(state)
+    timed("security_codes.create", async move {
+        let mut state = DbState::new_uncreated(security_code);
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
 }
 
 /// Get all security codes with their projects
 pub(crate) async fn get_all_with_projects(
     db: &PostgresClient,
 ) -> welds::errors::Result<Vec<(DbState<SecurityCode>, DbState<Project>)>> {
-    let security_codes = SecurityCode::all()
-        .order_by_asc(|sc| sc.security_code_id)
-        .run(db)
-        .await?;
-
-    let mut result = Vec::new();
-    for sc in security_codes {
-        let mut projects = Project::where_col(|p| p.project_id.equal(sc.project_id))
+    timed("security_codes.get_all_with_projects", async move {
+        let security_codes = SecurityCode::all()
+            .order_by_asc(|sc| sc.security_code_id)
             .run(db)
             .await?;
 
-        if let Some(project) = projects.pop() {
-            result.push((sc, project));
+        let mut result = Vec::new();
+        for sc in security_codes {
+            let mut projects = Project::where_col(|p| p.project_id.equal(sc.project_id))
+                .run(db)
+                .await?;
+
+            if let Some(project) = projects.pop() {
+                result.push((sc, project));
+            }
+        }
+
+        Ok(result)
+    })
+    .await
+}
+
+/// Codes touched by the rotation of a project
+#[derive(Debug)]
+pub(crate) struct Rotation {
+    /// Codes that could still be redeemed, now expiring at the rotation instant
+    pub invalidated: Vec<SecurityCode>,
+    /// Replacements, one per invalidated code and with the same expiration
+    pub created: Vec<SecurityCode>,
+}
+
+/// Plans the rotation of the codes of a project
+///
+/// Only codes that can still be redeemed are invalidated, by expiring them now. Rows are
+/// kept so the accesses already granted through them stay intact.
+pub(crate) fn plan_rotation(
+    codes: Vec<SecurityCode>, now: DateTime<Utc>, regenerate: bool,
+    mut new_code: impl FnMut() -> String,
+) -> Rotation {
+    let mut invalidated = Vec::new();
+    let mut created = Vec::new();
+    for code in codes.into_iter().filter(|c| !c.is_expired(now)) {
+        if regenerate {
+            created.push(SecurityCode {
+                security_code_id: 0,
+                project_id: code.project_id,
+                code: new_code(),
+                expiration: code.expiration,
+            });
+        }
+        invalidated.push(SecurityCode {
+            expiration: now,
+            ..code
+        });
+    }
+    Rotation {
+        invalidated,
+        created,
+    }
+}
+
+/// Rotate the codes of a project in a single transaction
+///
+/// The project row is locked so concurrent rotations of the same project are serialized.
+/// Returns `None` when the project does not exist.
+pub(crate) async fn rotate_for_project(
+    db: &PostgresClient, project_id: i32, now: DateTime<Utc>, regenerate: bool,
+    mut new_code: impl FnMut() -> String,
+) -> welds::errors::Result<Option<Rotation>> {
+    timed("security_codes.rotate_for_project", async move {
+        let transaction = db.begin().await?;
+
+        let result = async {
+            let locked = transaction
+                .fetch_rows(
+                    "SELECT project_id FROM projects WHERE project_id = $1 FOR UPDATE",
+                    &[&project_id],
+                )
+                .await?;
+            if locked.is_empty() {
+                return Ok(None);
+            }
+
+            let codes = SecurityCode::where_col(|sc| sc.project_id.equal(project_id))
+                .order_by_asc(|sc| sc.security_code_id)
+                .run(&transaction)
+                .await?
+                .into_iter()
+                .map(DbState::into_inner)
+                .collect();
+
+            let mut rotation = plan_rotation(codes, now, regenerate, &mut new_code);
+            for code in &rotation.invalidated {
+                SecurityCode::where_col(|sc| sc.security_code_id.equal(code.security_code_id))
+                    .set(|sc| sc.expiration, now)
+                    .run(&transaction)
+                    .await?;
+            }
+            for code in &mut rotation.created {
+                while !SecurityCode::where_col(|sc| sc.code.equal(code.code.as_str()))
+                    .limit(1)
+                    .run(&transaction)
+                    .await?
+                    .is_empty()
+                {
+                    code.code = new_code();
+                }
+                let mut state = DbState::new_uncreated(code.clone());
+                state.save(&transaction).await?;
+                *code = DbState::into_inner(state);
+            }
+            Ok(Some(rotation))
+        }
+        .await;
+
+        match result {
+            Ok(rotation) => {
+                transaction.commit().await?;
+                Ok(rotation)
+            }
+            Err(e) => {
+                transaction.rollback().await?;
+                Err(e)
+            }
+        }
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 6, 1, 9, 0, 0).unwrap()
+    }
+
+    fn code(security_code_id: i32, code: &str, expiration: DateTime<Utc>) -> SecurityCode {
+        SecurityCode {
+            security_code_id,
+            project_id: 3,
+            code: code.to_string(),
+            expiration,
         }
     }
 
-    Ok(result)
+    #[test]
+    fn test_rotated_codes_stop_validating_and_replacements_work() {
+        let expiration = now() + Duration::days(7);
+        let codes = vec![
+            code(1, "AAA-111", expiration),
+            code(2, "BBB-222", now() - Duration::days(1)),
+        ];
+
+        let rotation = plan_rotation(codes, now(), true, || "NEW-333".to_string());
+
+        assert_eq!(rotation.invalidated.len(), 1);
+        assert_eq!(rotation.invalidated[0].security_code_id, 1);
+        assert!(rotation.invalidated[0].is_expired(now()));
+
+        assert_eq!(rotation.created.len(), 1);
+        let created = &rotation.created[0];
+        assert_eq!(created.code, "NEW-333");
+        assert_eq!(created.project_id, 3);
+        assert_eq!(created.expiration, expiration);
+        assert!(!created.is_expired(now()));
+    }
+
+    #[test]
+    fn test_rotation_without_regeneration_only_invalidates() {
+        let codes = vec![
+            code(1, "AAA-111", now() + Duration::days(1)),
+            code(2, "BBB-222", now() + Duration::days(2)),
+        ];
+
+        let rotation = plan_rotation(codes, now(), false, || unreachable!());
+
+        assert_eq!(rotation.invalidated.len(), 2);
+        assert!(rotation.invalidated.iter().all(|c| c.is_expired(now())));
+        assert!(rotation.created.is_empty());
+    }
 }
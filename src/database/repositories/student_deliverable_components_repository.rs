@@ -1,88 +1,143 @@
+use crate::database::timing::timed;
 use crate::models::student_deliverable_component::StudentDeliverableComponent;
 use welds::connections::postgres::PostgresClient;
 use welds::state::DbState;
+use welds::Client;
 
 /// Get all student deliverable components
 pub(crate) async fn get_all(
     db: &PostgresClient,
 ) -> welds::errors::Result<Vec<DbState<StudentDeliverableComponent>>> {
-    StudentDeliverableComponent::all().run(db).await
+    timed("student_deliverable_components.get_all", async move {
+        StudentDeliverableComponent::all().run(db).await
+    })
+    .await
 }
 
 /// Get a student deliverable component by its ID
 pub(crate) async fn get_by_id(
     db: &PostgresClient, component_id: i32,
 ) -> welds::errors::Result<Option<DbState<StudentDeliverableComponent>>> {
-    let mut rows = StudentDeliverableComponent::where_col(|sdc| {
-        sdc.student_deliverable_component_id.equal(component_id)
-    })
-    .run(db)
-    .await?;
+    timed("student_deliverable_components.get_by_id", async move {
+        let mut rows = StudentDeliverableComponent::where_col(|sdc| {
+            sdc.student_deliverable_component_id.equal(component_id)
+        })
+        .run(db)
+        .await?;
 
-    Ok(rows.pop())
+        Ok(rows.pop())
+    })
+    .await
 }
 
 /// Get all student deliverable components for a specific project
 pub(crate) async fn get_by_project_id(
-    db: &PostgresClient, project_id: i32,
+    db: &impl Client, project_id: i32,
 ) -> welds::errors::Result<Vec<DbState<StudentDeliverableComponent>>> {
-    StudentDeliverableComponent::where_col(|sdc| sdc.project_id.equal(project_id))
-        .run(db)
-        .await
+    timed(
+        "student_deliverable_components.get_by_project_id",
+        async move {
+            StudentDeliverableComponent::where_col(|sdc| sdc.project_id.equal(project_id))
+                .run(db)
+                .await
+        },
+    )
+    .await
 }
 
 /// Check if a student component with the same name exists in a project (excluding a specific ID)
 pub(crate) async fn check_name_exists_excluding(
     db: &PostgresClient, project_id: i32, name: &str, excluding_id: i32,
 ) -> welds::errors::Result<bool> {
-    let rows = StudentDeliverableComponent::where_col(|sdc| sdc.project_id.equal(project_id))
-        .where_col(|sdc| sdc.name.equal(name))
-        .where_col(|sdc| sdc.student_deliverable_component_id.not_equal(excluding_id))
-        .limit(1)
-        .run(db)
-        .await?;
+    timed(
+        "student_deliverable_components.check_name_exists_excluding",
+        async move {
+            let rows =
+                StudentDeliverableComponent::where_col(|sdc| sdc.project_id.equal(project_id))
+                    .where_col(|sdc| sdc.name.equal(name))
+                    .where_col(|sdc| sdc.student_deliverable_component_id.not_equal(excluding_id))
+                    .limit(1)
+                    .run(db)
+                    .await?;
 
-    Ok(!rows.is_empty())
+            Ok(!rows.is_empty())
+        },
+    )
+    .await
 }
 
 /// Check if a student component with the same name exists in a project
 pub(crate) async fn check_name_exists(
     db: &PostgresClient, project_id: i32, name: &str,
 ) -> welds::errors::Result<bool> {
-    let rows = StudentDeliverableComponent::where_col(|sdc| sdc.project_id.equal(project_id))
-        .where_col(|sdc| sdc.name.equal(name))
-        .limit(1)
-        .run(db)
-        .await?;
+    timed(
+        "student_deliverable_components.check_name_exists",
+        async move {
+            let rows =
+                StudentDeliverableComponent::where_col(|sdc| sdc.project_id.equal(project_id))
+                    .where_col(|sdc| sdc.name.equal(name))
+                    .limit(1)
+                    .run(db)
+                    .await?;
 
-    Ok(!rows.is_empty())
+            Ok(!rows.is_empty())
+        },
+    )
+    .await
 }
 
 /// Delete a student deliverable component by ID
 pub(crate) async fn delete_by_id(
     db: &PostgresClient, component_id: i32,
 ) -> welds::errors::Result<()> {
-    StudentDeliverableComponent::where_col(|sdc| {
-        sdc.student_deliverable_component_id.equal(component_id)
+    timed("student_deliverable_components.delete_by_id", async move {
+        StudentDeliverableComponent::where_col(|sdc| {
+            sdc.student_deliverable_component_id.equal(component_id)
+        })
+        .delete(db)
+        .await?;
+        Ok(())
+    })
+    .await
+}
+
+/// Delete several student deliverable components at once, either directly or inside a request unit of work
+///
+/// Rows referencing them are removed by the cascading foreign keys.
+pub(crate) async fn delete_by_ids(db: &impl Client, ids: &[i32]) -> welds::errors::Result<()> {
+    timed("student_deliverable_components.delete_by_ids", async move {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        StudentDeliverableComponent::where_col(|sdc| {
+            sdc.student_deliverable_component_id.in_list(ids)
+        })
+        .delete(db)
+        .await?;
+        Ok(())
     })
-    .delete(db)
-    .await?;
-    Ok(())
+    .await
 }
 
 /// Create a new student deliverable component
 pub(crate) async fn create(
     db: &PostgresClient, student_deliverable_component: StudentDeliverableComponent,
 ) -> welds::errors::Result<DbState<StudentDeliverableComponent>> {
-    let mut state = DbState::new_uncreated(student_deliverable_component);
-    state.save(db).await?;
-    Ok(state)
+    timed("student_deliverable_components.create", async move {
+        let mut state = DbState::new_uncreated(student_deliverable_component);
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
 }
 
 /// Update a student deliverable component
 pub(crate) async fn update(
     db: &PostgresClient, mut state: DbState<StudentDeliverableComponent>,
 ) -> welds::errors::Result<DbState<StudentDeliverableComponent>> {
-    state.save(db).await?;
-    Ok(state)
+    timed("student_deliverable_components.update", async move {
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
 }
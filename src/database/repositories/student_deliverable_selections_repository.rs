@@ -1,108 +1,203 @@
+use crate::database::timing::timed;
 use crate::models::student_deliverable_selection::StudentDeliverableSelection;
+use chrono::{DateTime, Utc};
 use welds::connections::postgres::PostgresClient;
 use welds::state::DbState;
+use welds::Client;
 
 /// Get a student deliverable selection by student ID and project ID
 pub(crate) async fn get_by_student_and_project(
     db: &PostgresClient, student_id: i32, project_id: i32,
 ) -> welds::errors::Result<Option<DbState<StudentDeliverableSelection>>> {
-    // First, get all student deliverables for the project
-    let student_deliverables =
-        crate::models::student_deliverable::StudentDeliverable::where_col(|sd| {
-            sd.project_id.equal(project_id)
-        })
-        .run(db)
-        .await?;
+    timed(
+        "student_deliverable_selections.get_by_student_and_project",
+        async move {
+            // First, get all student deliverables for the project
+            let student_deliverables =
+                crate::models::student_deliverable::StudentDeliverable::where_col(|sd| {
+                    sd.project_id.equal(project_id)
+                })
+                .run(db)
+                .await?;
 
-    let deliverable_ids: Vec<i32> = student_deliverables
-        .into_iter()
-        .map(|state| DbState::into_inner(state).student_deliverable_id)
-        .collect();
+            let deliverable_ids: Vec<i32> = student_deliverables
+                .into_iter()
+                .map(|state| DbState::into_inner(state).student_deliverable_id)
+                .collect();
 
-    // Get all selections for this student
-    let selections = StudentDeliverableSelection::where_col(|sds| sds.student_id.equal(student_id))
-        .run(db)
-        .await?;
-
-    // Find the selection that matches one of the deliverable IDs for this project
-    for selection_state in selections {
-        let selection_id = selection_state.as_ref().student_deliverable_id;
-        if deliverable_ids.contains(&selection_id) {
-            return Ok(Some(selection_state));
-        }
-    }
+            // Get all selections for this student
+            let selections =
+                StudentDeliverableSelection::where_col(|sds| sds.student_id.equal(student_id))
+                    .run(db)
+                    .await?;
+
+            // Find the selection that matches one of the deliverable IDs for this project
+            for selection_state in selections {
+                let selection_id = selection_state.as_ref().student_deliverable_id;
+                if deliverable_ids.contains(&selection_id) {
+                    return Ok(Some(selection_state));
+                }
+            }
+
+            Ok(None)
+        },
+    )
+    .await
+}
 
-    Ok(None)
+/// Count the students who selected a student deliverable
+pub(crate) async fn count_by_deliverable_id(
+    db: &PostgresClient, student_deliverable_id: i32,
+) -> welds::errors::Result<u64> {
+    timed(
+        "student_deliverable_selections.count_by_deliverable_id",
+        async move {
+            StudentDeliverableSelection::where_col(|sds| {
+                sds.student_deliverable_id.equal(student_deliverable_id)
+            })
+            .count(db)
+            .await
+        },
+    )
+    .await
 }
 
 /// Check if a student has already selected a deliverable for a project
 pub(crate) async fn has_selection_for_project(
     db: &PostgresClient, student_id: i32, project_id: i32,
 ) -> welds::errors::Result<bool> {
-    let selection = get_by_student_and_project(db, student_id, project_id).await?;
-    Ok(selection.is_some())
+    timed(
+        "student_deliverable_selections.has_selection_for_project",
+        async move {
+            let selection = get_by_student_and_project(db, student_id, project_id).await?;
+            Ok(selection.is_some())
+        },
+    )
+    .await
 }
 
 /// Create a new student deliverable selection
 pub(crate) async fn create(
     db: &PostgresClient, selection: StudentDeliverableSelection,
 ) -> welds::errors::Result<DbState<StudentDeliverableSelection>> {
-    let mut state = DbState::new_uncreated(selection);
-    state.save(db).await?;
-    Ok(state)
+    timed("student_deliverable_selections.create", async move {
+        let mut state = DbState::new_uncreated(selection);
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
 }
 
 /// Update a student deliverable selection
 pub(crate) async fn update(
     db: &PostgresClient, mut state: DbState<StudentDeliverableSelection>,
 ) -> welds::errors::Result<DbState<StudentDeliverableSelection>> {
-    state.save(db).await?;
-    Ok(state)
+    timed("student_deliverable_selections.update", async move {
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
+}
+
+/// Get the selections with the given IDs, missing ones are skipped
+pub(crate) async fn get_by_ids(
+    db: &impl Client, selection_ids: &[i32],
+) -> welds::errors::Result<Vec<DbState<StudentDeliverableSelection>>> {
+    if selection_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    timed("student_deliverable_selections.get_by_ids", async move {
+        StudentDeliverableSelection::where_col(|sds| {
+            sds.student_deliverable_selection_id.in_list(selection_ids)
+        })
+        .run(db)
+        .await
+    })
+    .await
+}
+
+/// Point several selections to new deliverables in a single statement
+///
+/// Each change is a `(student_deliverable_selection_id, student_deliverable_id)` pair.
+pub(crate) async fn update_deliverables(
+    db: &impl Client, changes: &[(i32, i32)], now: DateTime<Utc>,
+) -> welds::errors::Result<()> {
+    let (selection_ids, deliverable_ids): (Vec<i32>, Vec<i32>) = changes.iter().copied().unzip();
+    timed(
+        "student_deliverable_selections.update_deliverables",
+        async move {
+            db.execute(
+                "UPDATE student_deliverable_selections AS sds \
+             SET student_deliverable_id = c.student_deliverable_id, updated_at = $3 \
+             FROM UNNEST($1::int[], $2::int[]) AS c(selection_id, student_deliverable_id) \
+             WHERE sds.student_deliverable_selection_id = c.selection_id",
+                &[&selection_ids, &deliverable_ids, &now],
+            )
+            .await?;
+            Ok(())
+        },
+    )
+    .await
 }
 
 /// Delete a student's deliverable selection for a specific project
 pub(crate) async fn delete_by_student_and_project(
     db: &PostgresClient, student_id: i32, project_id: i32,
 ) -> welds::errors::Result<()> {
-    if let Some(selection_state) = get_by_student_and_project(db, student_id, project_id).await? {
-        let selection = DbState::into_inner(selection_state);
-        StudentDeliverableSelection::where_col(|sds| {
-            sds.student_deliverable_selection_id
-                .equal(selection.student_deliverable_selection_id)
-        })
-        .delete(db)
-        .await?;
-    }
-    Ok(())
+    timed(
+        "student_deliverable_selections.delete_by_student_and_project",
+        async move {
+            if let Some(selection_state) =
+                get_by_student_and_project(db, student_id, project_id).await?
+            {
+                let selection = DbState::into_inner(selection_state);
+                StudentDeliverableSelection::where_col(|sds| {
+                    sds.student_deliverable_selection_id
+                        .equal(selection.student_deliverable_selection_id)
+                })
+                .delete(db)
+                .await?;
+            }
+            Ok(())
+        },
+    )
+    .await
 }
 
 /// Get all student deliverable selections for a project
 pub(crate) async fn get_by_project_id(
     db: &PostgresClient, project_id: i32,
 ) -> welds::errors::Result<Vec<DbState<StudentDeliverableSelection>>> {
-    // First, get all student deliverables for the project
-    let student_deliverables =
-        crate::models::student_deliverable::StudentDeliverable::where_col(|sd| {
-            sd.project_id.equal(project_id)
-        })
-        .run(db)
-        .await?;
-
-    let deliverable_ids: Vec<i32> = student_deliverables
-        .into_iter()
-        .map(|state| DbState::into_inner(state).student_deliverable_id)
-        .collect();
-
-    // Get all selections that match these deliverable IDs
-    let all_selections = StudentDeliverableSelection::all().run(db).await?;
-
-    let mut result = Vec::new();
-    for selection_state in all_selections {
-        let selection_id = selection_state.as_ref().student_deliverable_id;
-        if deliverable_ids.contains(&selection_id) {
-            result.push(selection_state);
-        }
-    }
+    timed(
+        "student_deliverable_selections.get_by_project_id",
+        async move {
+            // First, get all student deliverables for the project
+            let student_deliverables =
+                crate::models::student_deliverable::StudentDeliverable::where_col(|sd| {
+                    sd.project_id.equal(project_id)
+                })
+                .run(db)
+                .await?;
+
+            let deliverable_ids: Vec<i32> = student_deliverables
+                .into_iter()
+                .map(|state| DbState::into_inner(state).student_deliverable_id)
+                .collect();
+
+            // Get all selections that match these deliverable IDs
+            let all_selections = StudentDeliverableSelection::all().run(db).await?;
+
+            let mut result = Vec::new();
+            for selection_state in all_selections {
+                let selection_id = selection_state.as_ref().student_deliverable_id;
+                if deliverable_ids.contains(&selection_id) {
+                    result.push(selection_state);
+                }
+            }
 
-    Ok(result)
+            Ok(result)
+        },
+    )
+    .await
 }
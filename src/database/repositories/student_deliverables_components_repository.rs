@@ -1,33 +1,101 @@
+use crate::database::timing::timed;
 use crate::models::student_deliverable::StudentDeliverable;
 use crate::models::student_deliverable_component::StudentDeliverableComponent;
 use crate::models::student_deliverables_component::StudentDeliverablesComponent;
 use welds::connections::postgres::PostgresClient;
 use welds::state::DbState;
+use welds::Client;
+
+/// Get the component links of several student deliverables
+pub(crate) async fn get_by_deliverable_ids(
+    db: &impl Client, deliverable_ids: &[i32],
+) -> welds::errors::Result<Vec<DbState<StudentDeliverablesComponent>>> {
+    timed(
+        "student_deliverables_components.get_by_deliverable_ids",
+        async move {
+            StudentDeliverablesComponent::where_col(|sdc| {
+                sdc.student_deliverable_id.in_list(deliverable_ids)
+            })
+            .run(db)
+            .await
+        },
+    )
+    .await
+}
+
+/// Get the component links of a student deliverable
+pub(crate) async fn get_by_deliverable_id(
+    db: &PostgresClient, student_deliverable_id: i32,
+) -> welds::errors::Result<Vec<DbState<StudentDeliverablesComponent>>> {
+    timed(
+        "student_deliverables_components.get_by_deliverable_id",
+        async move {
+            StudentDeliverablesComponent::where_col(|sdc| {
+                sdc.student_deliverable_id.equal(student_deliverable_id)
+            })
+            .run(db)
+            .await
+        },
+    )
+    .await
+}
 
 /// Get a student deliverables component relationship by its ID
 pub(crate) async fn get_by_id(
     db: &PostgresClient, id: i32,
 ) -> welds::errors::Result<Option<DbState<StudentDeliverablesComponent>>> {
-    let mut rows = StudentDeliverablesComponent::where_col(|sdc| sdc.id.equal(id))
-        .run(db)
-        .await?;
+    timed("student_deliverables_components.get_by_id", async move {
+        let mut rows = StudentDeliverablesComponent::where_col(|sdc| sdc.id.equal(id))
+            .run(db)
+            .await?;
 
-    Ok(rows.pop())
+        Ok(rows.pop())
+    })
+    .await
 }
 
 /// Check if a relationship exists between a deliverable and component
 pub(crate) async fn relationship_exists(
     db: &PostgresClient, deliverable_id: i32, component_id: i32,
 ) -> welds::errors::Result<bool> {
-    let rows = StudentDeliverablesComponent::where_col(|sdc| {
-        sdc.student_deliverable_id.equal(deliverable_id)
-    })
-    .where_col(|sdc| sdc.student_deliverable_component_id.equal(component_id))
-    .limit(1)
-    .run(db)
-    .await?;
+    timed(
+        "student_deliverables_components.relationship_exists",
+        async move {
+            let rows = StudentDeliverablesComponent::where_col(|sdc| {
+                sdc.student_deliverable_id.equal(deliverable_id)
+            })
+            .where_col(|sdc| sdc.student_deliverable_component_id.equal(component_id))
+            .limit(1)
+            .run(db)
+            .await?;
+
+            Ok(!rows.is_empty())
+        },
+    )
+    .await
+}
+
+/// Sum the weights of the component links of a deliverable, optionally skipping one link
+pub(crate) async fn total_weight_for_deliverable(
+    db: &PostgresClient, deliverable_id: i32, excluding_id: Option<i32>,
+) -> welds::errors::Result<i32> {
+    timed(
+        "student_deliverables_components.total_weight_for_deliverable",
+        async move {
+            let rows = StudentDeliverablesComponent::where_col(|sdc| {
+                sdc.student_deliverable_id.equal(deliverable_id)
+            })
+            .run(db)
+            .await?;
 
-    Ok(!rows.is_empty())
+            Ok(rows
+                .iter()
+                .filter(|link| Some(link.id) != excluding_id)
+                .map(|link| link.weight)
+                .sum())
+        },
+    )
+    .await
 }
 
 /// Get deliverables with their details for a specific student component
@@ -39,27 +107,33 @@ pub(crate) async fn get_deliverables_with_details_for_component(
         DbState<StudentDeliverable>,
     )>,
 > {
-    let relationships = StudentDeliverablesComponent::where_col(|sdc| {
-        sdc.student_deliverable_component_id.equal(component_id)
-    })
-    .run(db)
-    .await?;
-
-    let mut result = Vec::new();
-    for relationship in relationships {
-        let mut deliverables = StudentDeliverable::where_col(|sd| {
-            sd.student_deliverable_id
-                .equal(relationship.student_deliverable_id)
-        })
-        .run(db)
-        .await?;
-
-        if let Some(deliverable) = deliverables.pop() {
-            result.push((relationship, deliverable));
-        }
-    }
-
-    Ok(result)
+    timed(
+        "student_deliverables_components.get_deliverables_with_details_for_component",
+        async move {
+            let relationships = StudentDeliverablesComponent::where_col(|sdc| {
+                sdc.student_deliverable_component_id.equal(component_id)
+            })
+            .run(db)
+            .await?;
+
+            let mut result = Vec::new();
+            for relationship in relationships {
+                let mut deliverables = StudentDeliverable::where_col(|sd| {
+                    sd.student_deliverable_id
+                        .equal(relationship.student_deliverable_id)
+                })
+                .run(db)
+                .await?;
+
+                if let Some(deliverable) = deliverables.pop() {
+                    result.push((relationship, deliverable));
+                }
+            }
+
+            Ok(result)
+        },
+    )
+    .await
 }
 
 /// Get components with their details for a specific student deliverable
@@ -71,60 +145,83 @@ pub(crate) async fn get_components_with_details_for_deliverable(
         DbState<StudentDeliverableComponent>,
     )>,
 > {
-    let relationships = StudentDeliverablesComponent::where_col(|sdc| {
-        sdc.student_deliverable_id.equal(deliverable_id)
-    })
-    .run(db)
-    .await?;
-
-    let mut result = Vec::new();
-    for relationship in relationships {
-        let mut components = StudentDeliverableComponent::where_col(|sc| {
-            sc.student_deliverable_component_id
-                .equal(relationship.student_deliverable_component_id)
-        })
-        .run(db)
-        .await?;
-
-        if let Some(component) = components.pop() {
-            result.push((relationship, component));
-        }
-    }
-    Ok(result)
+    timed(
+        "student_deliverables_components.get_components_with_details_for_deliverable",
+        async move {
+            let relationships = StudentDeliverablesComponent::where_col(|sdc| {
+                sdc.student_deliverable_id.equal(deliverable_id)
+            })
+            .run(db)
+            .await?;
+
+            let mut result = Vec::new();
+            for relationship in relationships {
+                let mut components = StudentDeliverableComponent::where_col(|sc| {
+                    sc.student_deliverable_component_id
+                        .equal(relationship.student_deliverable_component_id)
+                })
+                .run(db)
+                .await?;
+
+                if let Some(component) = components.pop() {
+                    result.push((relationship, component));
+                }
+            }
+            Ok(result)
+        },
+    )
+    .await
 }
 
 /// Delete a student deliverables component relationship by ID
 pub(crate) async fn delete_by_id(
     db: &PostgresClient, relationship_id: i32,
 ) -> welds::errors::Result<()> {
-    StudentDeliverablesComponent::where_col(|sdc| sdc.id.equal(relationship_id))
-        .delete(db)
-        .await?;
-    Ok(())
+    timed("student_deliverables_components.delete_by_id", async move {
+        StudentDeliverablesComponent::where_col(|sdc| sdc.id.equal(relationship_id))
+            .delete(db)
+            .await?;
+        Ok(())
+    })
+    .await
 }
 
 /// Create a new student deliverables component relationship
 pub(crate) async fn create(
     db: &PostgresClient, student_deliverables_component: StudentDeliverablesComponent,
 ) -> welds::errors::Result<DbState<StudentDeliverablesComponent>> {
-    let mut state = DbState::new_uncreated(student_deliverables_component);
-    state.save(db).await?;
-    Ok(state)
+    timed("student_deliverables_components.create", async move {
+        let mut state = DbState::new_uncreated(student_deliverables_component);
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
 }
 
 /// Update a student deliverables component relationship
 pub(crate) async fn update(
     db: &PostgresClient, mut state: DbState<StudentDeliverablesComponent>,
 ) -> welds::errors::Result<DbState<StudentDeliverablesComponent>> {
-    state.save(db).await?;
-    Ok(state)
+    timed("student_deliverables_components.update", async move {
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
 }
 
 /// Get components for a specific student deliverable
 pub(crate) async fn get_components_for_deliverable(
     db: &PostgresClient, deliverable_id: i32,
 ) -> welds::errors::Result<Vec<DbState<StudentDeliverablesComponent>>> {
-    StudentDeliverablesComponent::where_col(|sdc| sdc.student_deliverable_id.equal(deliverable_id))
-        .run(db)
-        .await
+    timed(
+        "student_deliverables_components.get_components_for_deliverable",
+        async move {
+            StudentDeliverablesComponent::where_col(|sdc| {
+                sdc.student_deliverable_id.equal(deliverable_id)
+            })
+            .run(db)
+            .await
+        },
+    )
+    .await
 }
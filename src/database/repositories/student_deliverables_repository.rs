@@ -1,88 +1,152 @@
+use crate::database::timing::timed;
 use crate::models::student_deliverable::StudentDeliverable;
 use welds::connections::postgres::PostgresClient;
 use welds::state::DbState;
+use welds::Client;
 
 /// Get all student deliverables
 pub(crate) async fn get_all(
     db: &PostgresClient,
 ) -> welds::errors::Result<Vec<DbState<StudentDeliverable>>> {
-    StudentDeliverable::all().run(db).await
+    timed("student_deliverables.get_all", async move {
+        StudentDeliverable::all().run(db).await
+    })
+    .await
 }
 
 /// Get a student deliverable by its ID
 pub(crate) async fn get_by_id(
     db: &PostgresClient, student_deliverable_id: i32,
 ) -> welds::errors::Result<Option<DbState<StudentDeliverable>>> {
-    let mut rows =
-        StudentDeliverable::where_col(|sd| sd.student_deliverable_id.equal(student_deliverable_id))
-            .run(db)
-            .await?;
+    timed("student_deliverables.get_by_id", async move {
+        let mut rows = StudentDeliverable::where_col(|sd| {
+            sd.student_deliverable_id.equal(student_deliverable_id)
+        })
+        .run(db)
+        .await?;
 
-    Ok(rows.pop())
+        Ok(rows.pop())
+    })
+    .await
 }
 
-/// Get all student deliverables for a specific project
-pub(crate) async fn get_by_project_id(
-    db: &PostgresClient, project_id: i32,
+/// Get the student deliverables with the given IDs, missing ones are skipped
+pub(crate) async fn get_by_ids(
+    db: &impl Client, student_deliverable_ids: &[i32],
 ) -> welds::errors::Result<Vec<DbState<StudentDeliverable>>> {
-    StudentDeliverable::where_col(|sd| sd.project_id.equal(project_id))
+    if student_deliverable_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    timed("student_deliverables.get_by_ids", async move {
+        StudentDeliverable::where_col(|sd| {
+            sd.student_deliverable_id.in_list(student_deliverable_ids)
+        })
         .run(db)
         .await
+    })
+    .await
+}
+
+/// Get all student deliverables for a specific project
+pub(crate) async fn get_by_project_id(
+    db: &impl Client, project_id: i32,
+) -> welds::errors::Result<Vec<DbState<StudentDeliverable>>> {
+    timed("student_deliverables.get_by_project_id", async move {
+        StudentDeliverable::where_col(|sd| sd.project_id.equal(project_id))
+            .run(db)
+            .await
+    })
+    .await
 }
 
 /// Check if a student deliverable with the same name exists in a project (excluding a specific ID)
 pub(crate) async fn check_name_exists_excluding(
     db: &PostgresClient, project_id: i32, name: &str, excluding_id: i32,
 ) -> welds::errors::Result<bool> {
-    let rows = StudentDeliverable::where_col(|sd| sd.project_id.equal(project_id))
-        .where_col(|sd| sd.name.equal(name))
-        .where_col(|sd| sd.student_deliverable_id.not_equal(excluding_id))
-        .limit(1)
-        .run(db)
-        .await?;
+    timed(
+        "student_deliverables.check_name_exists_excluding",
+        async move {
+            let rows = StudentDeliverable::where_col(|sd| sd.project_id.equal(project_id))
+                .where_col(|sd| sd.name.equal(name))
+                .where_col(|sd| sd.student_deliverable_id.not_equal(excluding_id))
+                .limit(1)
+                .run(db)
+                .await?;
 
-    Ok(!rows.is_empty())
+            Ok(!rows.is_empty())
+        },
+    )
+    .await
 }
 
 /// Check if a student deliverable with the same name exists in a project
 pub(crate) async fn check_name_exists(
     db: &PostgresClient, project_id: i32, name: &str,
 ) -> welds::errors::Result<bool> {
-    let rows = StudentDeliverable::where_col(|sd| sd.project_id.equal(project_id))
-        .where_col(|sd| sd.name.equal(name))
-        .limit(1)
-        .run(db)
-        .await?;
+    timed("student_deliverables.check_name_exists", async move {
+        let rows = StudentDeliverable::where_col(|sd| sd.project_id.equal(project_id))
+            .where_col(|sd| sd.name.equal(name))
+            .limit(1)
+            .run(db)
+            .await?;
 
-    Ok(!rows.is_empty())
+        Ok(!rows.is_empty())
+    })
+    .await
 }
 
 /// Delete a student deliverable by ID
 pub(crate) async fn delete_by_id(
     db: &PostgresClient, student_deliverable_id: i32,
 ) -> welds::errors::Result<()> {
-    StudentDeliverable::where_col(|sd| sd.student_deliverable_id.equal(student_deliverable_id))
-        .delete(db)
-        .await?;
-    Ok(())
+    timed("student_deliverables.delete_by_id", async move {
+        StudentDeliverable::where_col(|sd| sd.student_deliverable_id.equal(student_deliverable_id))
+            .delete(db)
+            .await?;
+        Ok(())
+    })
+    .await
+}
+
+/// Delete several student deliverables at once, either directly or inside a request unit of work
+///
+/// Rows referencing them are removed by the cascading foreign keys.
+pub(crate) async fn delete_by_ids(db: &impl Client, ids: &[i32]) -> welds::errors::Result<()> {
+    timed("student_deliverables.delete_by_ids", async move {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        StudentDeliverable::where_col(|sd| sd.student_deliverable_id.in_list(ids))
+            .delete(db)
+            .await?;
+        Ok(())
+    })
+    .await
 }
 
 /// Create a new student deliverable
 pub(crate) async fn create(
     db: &PostgresClient, student_deliverable: StudentDeliverable,
 ) -> welds::errors::Result<DbState<StudentDeliverable>> {
-    let mut state = DbState::new_uncreated(student_deliverable);
-    state.save(db).await?;
-    Ok(state)
+    timed("student_deliverables.create", async move {
+        let mut state = DbState::new_uncreated(student_deliverable);
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
 }
 
 /// Update a student deliverable by ID
 pub(crate) async fn update_by_id(
     db: &PostgresClient, student_deliverable_id: i32, name: &str,
 ) -> welds::errors::Result<()> {
-    StudentDeliverable::where_col(|sd| sd.student_deliverable_id.equal(student_deliverable_id))
-        .set(|sd| sd.name, name)
-        .run(db)
-        .await?;
-    Ok(())
+    timed("student_deliverables.update_by_id", async move {
+        StudentDeliverable::where_col(|sd| sd.student_deliverable_id.equal(student_deliverable_id))
+            .set(|sd| sd.name, name)
+            .run(db)
+            .await?;
+        Ok(())
+    })
+    .await
 }
@@ -0,0 +1,55 @@
+use crate::database::errors::is_unique_violation;
+use crate::database::timing::timed;
+use crate::models::student_project_access::StudentProjectAccess;
+use chrono::Utc;
+use welds::connections::postgres::PostgresClient;
+use welds::state::DbState;
+use welds::TransactStart;
+
+/// Grant a student access to a project through a security code
+///
+/// The lookup and the insert run in one transaction. Returns `true` when the access
+/// was created by this call and `false` when the student already had it.
+pub(crate) async fn grant(
+    db: &PostgresClient, student_id: i32, project_id: i32, security_code_id: i32,
+) -> welds::errors::Result<bool> {
+    timed("student_project_access.grant", async move {
+        let transaction = db.begin().await?;
+
+        let existing = StudentProjectAccess::where_col(|spa| spa.student_id.equal(student_id))
+            .where_col(|spa| spa.project_id.equal(project_id))
+            .limit(1)
+            .run(&transaction)
+            .await?;
+
+        if !existing.is_empty() {
+            transaction.rollback().await?;
+            return Ok(false);
+        }
+
+        let mut state = DbState::new_uncreated(StudentProjectAccess {
+            student_project_access_id: 0,
+            student_id,
+            project_id,
+            security_code_id: Some(security_code_id),
+            granted_at: Utc::now(),
+        });
+
+        match state.save(&transaction).await {
+            Ok(()) => {
+                transaction.commit().await?;
+                Ok(true)
+            }
+            // a concurrent redemption by the same student won the race
+            Err(e) if is_unique_violation(&e) => {
+                transaction.rollback().await?;
+                Ok(false)
+            }
+            Err(e) => {
+                transaction.rollback().await?;
+                Err(e)
+            }
+        }
+    })
+    .await
+}
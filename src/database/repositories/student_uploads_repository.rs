@@ -1,105 +1,390 @@
+use crate::database::timing::timed;
 use crate::models::student::Student;
 use crate::models::student_deliverable::StudentDeliverable;
 use crate::models::student_deliverable_selection::StudentDeliverableSelection;
 use crate::models::student_upload::StudentUpload;
 use chrono::{DateTime, Utc};
 use welds::connections::postgres::PostgresClient;
+use welds::query::builder::QueryBuilder;
 use welds::state::DbState;
+use welds::{Client, WeldsError};
+
+/// SQL filter matching the uploads made through one of the student's deliverable selections
+const OWNED_BY_STUDENT: &str = "EXISTS (SELECT 1 FROM student_deliverable_selections sds \
+     WHERE sds.student_deliverable_selection_id = $.student_deliverable_selection_id \
+     AND sds.student_id = ?)";
+
+/// Uploads belonging to a student
+fn owned_by(student_id: i32) -> QueryBuilder<StudentUpload> {
+    StudentUpload::all().where_manual2(OWNED_BY_STUDENT, (student_id,))
+}
 
 pub(crate) async fn get_by_selection_id(
     db: &PostgresClient, student_deliverable_selection_id: i32,
 ) -> welds::errors::Result<Option<DbState<StudentUpload>>> {
-    let mut rows = StudentUpload::where_col(|upload| {
-        upload
-            .student_deliverable_selection_id
-            .equal(student_deliverable_selection_id)
+    timed("student_uploads.get_by_selection_id", async move {
+        let mut rows = StudentUpload::where_col(|upload| {
+            upload
+                .student_deliverable_selection_id
+                .equal(student_deliverable_selection_id)
+        })
+        .limit(1)
+        .run(db)
+        .await?;
+        Ok(rows.pop())
+    })
+    .await
+}
+
+/// Claims the next version of the upload of a selection, creating the upload on first use
+///
+/// The first file goes in as version 1 at `first_path`. Later ones bump the version and the
+/// upload count of the existing row, which stays locked until the transaction ends, so
+/// concurrent uploads get distinct versions. Nothing is claimed once `max_uploads` is reached.
+const CLAIM_FOR_SELECTION: &str = "INSERT INTO student_uploads \
+     (student_deliverable_selection_id, path, upload_count, version, timestamp) \
+     VALUES ($1, $2, 1, 1, $3) \
+     ON CONFLICT (student_deliverable_selection_id) DO UPDATE \
+     SET version = student_uploads.version + 1, upload_count = student_uploads.upload_count + 1 \
+     WHERE student_uploads.upload_count < $4 \
+     RETURNING upload_id, version";
+
+/// Claims the next version of an existing upload, see [`CLAIM_FOR_SELECTION`]
+const CLAIM_FOR_UPLOAD: &str = "UPDATE student_uploads \
+     SET version = version + 1, upload_count = upload_count + 1 \
+     WHERE upload_id = $1 AND upload_count < $2 \
+     RETURNING upload_id, version";
+
+/// Upload id and version of the first claimed row, `None` when attempts are exhausted
+fn claimed(rows: &[welds::Row]) -> welds::errors::Result<Option<(i32, i32)>> {
+    let Some(row) = rows.first() else {
+        return Ok(None);
+    };
+    Ok(Some((
+        row.get_by_position::<i32>(0)?,
+        row.get_by_position::<i32>(1)?,
+    )))
+}
+
+/// Claim the next version of the upload of a selection, see [`CLAIM_FOR_SELECTION`]
+pub(crate) async fn claim_version_for_selection(
+    db: &impl Client, student_deliverable_selection_id: i32, first_path: String,
+    now: DateTime<Utc>, max_uploads: i32,
+) -> welds::errors::Result<Option<(i32, i32)>> {
+    timed("student_uploads.claim_version_for_selection", async move {
+        let rows = db
+            .fetch_rows(
+                CLAIM_FOR_SELECTION,
+                &[
+                    &student_deliverable_selection_id,
+                    &first_path,
+                    &now,
+                    &max_uploads,
+                ],
+            )
+            .await?;
+        claimed(&rows)
+    })
+    .await
+}
+
+/// Claim the next version of an upload, `None` when its attempts are exhausted
+pub(crate) async fn claim_version(
+    db: &impl Client, upload_id: i32, max_uploads: i32,
+) -> welds::errors::Result<Option<i32>> {
+    timed("student_uploads.claim_version", async move {
+        let rows = db
+            .fetch_rows(CLAIM_FOR_UPLOAD, &[&upload_id, &max_uploads])
+            .await?;
+        Ok(claimed(&rows)?.map(|(_, version)| version))
+    })
+    .await
+}
+
+/// Store the file at `path` as a claimed version of an upload, making it the current one
+pub(crate) async fn record_version(
+    db: &impl Client, upload_id: i32, version: i32, path: String, now: DateTime<Utc>,
+) -> welds::errors::Result<StudentUpload> {
+    timed("student_uploads.record_version", async move {
+        db.execute(
+            "UPDATE student_uploads SET path = $2, timestamp = $3 WHERE upload_id = $1",
+            &[&upload_id, &path, &now],
+        )
+        .await?;
+        db.execute(
+            "INSERT INTO student_upload_versions (upload_id, version, path, uploaded_at) \
+             VALUES ($1, $2, $3, $4)",
+            &[&upload_id, &version, &path, &now],
+        )
+        .await?;
+
+        StudentUpload::where_col(|upload| upload.upload_id.equal(upload_id))
+            .limit(1)
+            .run(db)
+            .await?
+            .pop()
+            .map(DbState::into_inner)
+            .ok_or(WeldsError::RowNotFound)
+    })
+    .await
+}
+
+/// Get where a version of an upload is stored
+pub(crate) async fn get_version_path(
+    db: &PostgresClient, upload_id: i32, version: i32,
+) -> welds::errors::Result<Option<String>> {
+    timed("student_uploads.get_version_path", async move {
+        let rows = db
+            .fetch_rows(
+                "SELECT path FROM student_upload_versions WHERE upload_id = $1 AND version = $2",
+                &[&upload_id, &version],
+            )
+            .await?;
+        rows.first()
+            .map(|row| row.get_by_position::<String>(0))
+            .transpose()
+            .map_err(Into::into)
     })
-    .limit(1)
-    .run(db)
-    .await?;
-    Ok(rows.pop())
+    .await
 }
 
-pub(crate) async fn upsert(
-    db: &PostgresClient, student_deliverable_selection_id: i32, path: String, now: DateTime<Utc>,
-) -> welds::errors::Result<DbState<StudentUpload>> {
-    if let Some(mut existing) = get_by_selection_id(db, student_deliverable_selection_id).await? {
-        let current_count = existing.as_ref().upload_count;
-        existing.as_mut().path = path;
-        existing.as_mut().timestamp = now;
-        existing.as_mut().upload_count = current_count + 1;
-        existing.save(db).await?;
-        Ok(existing)
-    } else {
-        let upload = StudentUpload {
-            upload_id: 0,
-            student_deliverable_selection_id,
-            path,
-            upload_count: 1,
-            timestamp: now,
+/// Get the project an upload was made for, through its deliverable selection
+pub(crate) async fn get_project_id(
+    db: &PostgresClient, student_deliverable_selection_id: i32,
+) -> welds::errors::Result<Option<i32>> {
+    timed("student_uploads.get_project_id", async move {
+        let Some(selection) = StudentDeliverableSelection::where_col(|sds| {
+            sds.student_deliverable_selection_id
+                .equal(student_deliverable_selection_id)
+        })
+        .limit(1)
+        .run(db)
+        .await?
+        .pop() else {
+            return Ok(None);
         };
-        let mut state = DbState::new_uncreated(upload);
-        state.save(db).await?;
-        Ok(state)
-    }
+
+        let deliverable_id = selection.as_ref().student_deliverable_id;
+        Ok(
+            StudentDeliverable::where_col(|sd| sd.student_deliverable_id.equal(deliverable_id))
+                .limit(1)
+                .run(db)
+                .await?
+                .pop()
+                .map(|deliverable| deliverable.as_ref().project_id),
+        )
+    })
+    .await
 }
 
 pub(crate) async fn get_all_by_project(
     db: &PostgresClient, project_id: i32,
 ) -> welds::errors::Result<Vec<(StudentUpload, Student)>> {
-    let deliverables =
-        StudentDeliverable::where_col(|deliverable| deliverable.project_id.equal(project_id))
-            .run(db)
-            .await?;
-    let deliverable_ids: Vec<i32> = deliverables
-        .into_iter()
-        .map(|state| state.as_ref().student_deliverable_id)
-        .collect();
+    timed("student_uploads.get_all_by_project", async move {
+        let deliverables =
+            StudentDeliverable::where_col(|deliverable| deliverable.project_id.equal(project_id))
+                .run(db)
+                .await?;
+        let deliverable_ids: Vec<i32> = deliverables
+            .into_iter()
+            .map(|state| state.as_ref().student_deliverable_id)
+            .collect();
 
-    if deliverable_ids.is_empty() {
-        return Ok(Vec::new());
-    }
+        if deliverable_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let selections = StudentDeliverableSelection::all().run(db).await?;
+        let project_selections: Vec<(i32, i32)> = selections
+            .into_iter()
+            .filter_map(|selection| {
+                let selection_ref = selection.as_ref();
+                if deliverable_ids.contains(&selection_ref.student_deliverable_id) {
+                    Some((
+                        selection_ref.student_deliverable_selection_id,
+                        selection_ref.student_id,
+                    ))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        if project_selections.is_empty() {
+            return Ok(Vec::new());
+        }
 
-    let selections = StudentDeliverableSelection::all().run(db).await?;
-    let project_selections: Vec<(i32, i32)> = selections
-        .into_iter()
-        .filter_map(|selection| {
-            let selection_ref = selection.as_ref();
-            if deliverable_ids.contains(&selection_ref.student_deliverable_id) {
-                Some((
-                    selection_ref.student_deliverable_selection_id,
-                    selection_ref.student_id,
-                ))
-            } else {
-                None
+        let uploads = StudentUpload::all().run(db).await?;
+        let mut result = Vec::new();
+        for upload_state in uploads {
+            let upload = upload_state.as_ref();
+            let selection_match = project_selections
+                .iter()
+                .find(|(selection_id, _)| *selection_id == upload.student_deliverable_selection_id);
+            let Some((_, student_id)) = selection_match else {
+                continue;
+            };
+
+            let mut students = Student::where_col(|student| student.student_id.equal(*student_id))
+                .limit(1)
+                .run(db)
+                .await?;
+
+            if let Some(student_state) = students.pop() {
+                result.push((upload.clone(), DbState::into_inner(student_state)));
             }
-        })
-        .collect();
+        }
 
-    if project_selections.is_empty() {
-        return Ok(Vec::new());
-    }
+        Ok(result)
+    })
+    .await
+}
 
-    let uploads = StudentUpload::all().run(db).await?;
-    let mut result = Vec::new();
-    for upload_state in uploads {
-        let upload = upload_state.as_ref();
-        let selection_match = project_selections
+/// Get all uploads of a student with the deliverable each one was made for, newest first
+pub(crate) async fn get_all_by_student(
+    db: &PostgresClient, student_id: i32,
+) -> welds::errors::Result<Vec<(StudentUpload, StudentDeliverable)>> {
+    timed("student_uploads.get_all_by_student", async move {
+        let uploads = owned_by(student_id)
+            .order_by_desc(|upload| upload.timestamp)
+            .run(db)
+            .await?;
+        if uploads.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let selections =
+            StudentDeliverableSelection::where_col(|sds| sds.student_id.equal(student_id))
+                .run(db)
+                .await?;
+        let deliverable_ids: Vec<i32> = selections
             .iter()
-            .find(|(selection_id, _)| *selection_id == upload.student_deliverable_selection_id);
-        let Some((_, student_id)) = selection_match else {
-            continue;
-        };
+            .map(|selection| selection.as_ref().student_deliverable_id)
+            .collect();
+        let deliverables = StudentDeliverable::where_col(|deliverable| {
+            deliverable.student_deliverable_id.in_list(&deliverable_ids)
+        })
+        .run(db)
+        .await?;
 
-        let mut students = Student::where_col(|student| student.student_id.equal(*student_id))
+        Ok(uploads
+            .into_iter()
+            .filter_map(|upload_state| {
+                let upload = DbState::into_inner(upload_state);
+                let selection = selections.iter().find(|selection| {
+                    selection.as_ref().student_deliverable_selection_id
+                        == upload.student_deliverable_selection_id
+                })?;
+                let deliverable = deliverables.iter().find(|deliverable| {
+                    deliverable.as_ref().student_deliverable_id
+                        == selection.as_ref().student_deliverable_id
+                })?;
+                Some((upload, deliverable.as_ref().clone()))
+            })
+            .collect())
+    })
+    .await
+}
+
+/// Get an upload by id, only if it belongs to the student
+pub(crate) async fn get_by_id_for_student(
+    db: &PostgresClient, upload_id: i32, student_id: i32,
+) -> welds::errors::Result<Option<DbState<StudentUpload>>> {
+    timed("student_uploads.get_by_id_for_student", async move {
+        let mut rows = owned_by(student_id)
+            .where_col(|upload| upload.upload_id.equal(upload_id))
             .limit(1)
             .run(db)
             .await?;
+        Ok(rows.pop())
+    })
+    .await
+}
 
-        if let Some(student_state) = students.pop() {
-            result.push((upload.clone(), DbState::into_inner(student_state)));
+/// Upload with the project and the student it was made for
+#[derive(Debug, Clone)]
+pub(crate) struct OwnedUpload {
+    pub upload: StudentUpload,
+    pub project_id: i32,
+    pub student_id: i32,
+}
+
+/// Get an upload by id with the project and student owning it
+pub(crate) async fn get_by_id_with_owner(
+    db: &PostgresClient, upload_id: i32,
+) -> welds::errors::Result<Option<OwnedUpload>> {
+    timed("student_uploads.get_by_id_with_owner", async move {
+        let Some(upload) = StudentUpload::where_col(|upload| upload.upload_id.equal(upload_id))
+            .limit(1)
+            .run(db)
+            .await?
+            .pop()
+        else {
+            return Ok(None);
+        };
+        let upload = DbState::into_inner(upload);
+
+        let selection_id = upload.student_deliverable_selection_id;
+        let Some(selection) = StudentDeliverableSelection::where_col(|sds| {
+            sds.student_deliverable_selection_id.equal(selection_id)
+        })
+        .limit(1)
+        .run(db)
+        .await?
+        .pop() else {
+            return Ok(None);
+        };
+        let Some(project_id) = get_project_id(db, selection_id).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(OwnedUpload {
+            upload,
+            project_id,
+            student_id: selection.as_ref().student_id,
+        }))
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use welds::Syntax;
+
+    #[test]
+    fn test_students_only_read_their_own_uploads() {
+        let sql = owned_by(4).to_sql(Syntax::Postgres);
+
+        assert!(
+            sql.contains(
+                "sds.student_deliverable_selection_id = t1.student_deliverable_selection_id"
+            ),
+            "{}",
+            sql
+        );
+        assert!(sql.contains("sds.student_id = $1"), "{}", sql);
+    }
+
+    #[test]
+    fn test_versions_are_claimed_in_the_guarded_write() {
+        for sql in [CLAIM_FOR_SELECTION, CLAIM_FOR_UPLOAD] {
+            assert!(sql.contains("version + 1"), "{}", sql);
+            assert!(sql.contains("upload_count < $"), "{}", sql);
+            assert!(sql.ends_with("RETURNING upload_id, version"), "{}", sql);
         }
     }
 
-    Ok(result)
+    #[test]
+    fn test_single_upload_is_scoped_to_the_student() {
+        let sql = owned_by(4)
+            .where_col(|upload| upload.upload_id.equal(9))
+            .to_sql(Syntax::Postgres);
+
+        assert!(
+            sql.contains("sds.student_id = $1) AND t1.\"upload_id\" = $2"),
+            "{}",
+            sql
+        );
+    }
 }
@@ -1,87 +1,227 @@
+use crate::database::timing::timed;
 use crate::models::student::Student;
+use chrono::{DateTime, Utc};
 use welds::connections::postgres::PostgresClient;
 use welds::state::DbState;
+use welds::Client;
 
 /// Get a student by email
 pub(crate) async fn get_by_email(
     db: &PostgresClient, email: &str,
 ) -> welds::errors::Result<Option<DbState<Student>>> {
-    let mut rows = Student::where_col(|s| s.email.equal(email)).run(db).await?;
+    timed("students.get_by_email", async move {
+        let mut rows = Student::where_col(|s| s.email.equal(email)).run(db).await?;
 
-    Ok(rows.pop())
+        Ok(rows.pop())
+    })
+    .await
 }
 
 /// Get a student by student ID
 pub(crate) async fn get_by_id(
     db: &PostgresClient, student_id: i32,
 ) -> welds::errors::Result<Option<DbState<Student>>> {
-    let mut rows = Student::where_col(|s| s.student_id.equal(student_id))
-        .run(db)
-        .await?;
+    timed("students.get_by_id", async move {
+        let mut rows = Student::where_col(|s| s.student_id.equal(student_id))
+            .run(db)
+            .await?;
 
-    Ok(rows.pop())
+        Ok(rows.pop())
+    })
+    .await
+}
+
+/// Get all students whose ID is in the given list
+pub(crate) async fn get_by_ids(
+    db: &PostgresClient, student_ids: &[i32],
+) -> welds::errors::Result<Vec<DbState<Student>>> {
+    timed("students.get_by_ids", async move {
+        if student_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Student::where_col(|s| s.student_id.in_list(student_ids))
+            .run(db)
+            .await
+    })
+    .await
+}
+
+/// Get all students whose email is in the given list
+pub(crate) async fn get_by_emails(
+    db: &PostgresClient, emails: &[String],
+) -> welds::errors::Result<Vec<DbState<Student>>> {
+    timed("students.get_by_emails", async move {
+        if emails.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Student::where_col(|s| s.email.in_list(emails))
+            .run(db)
+            .await
+    })
+    .await
 }
 
 /// Get a student by university ID
 pub(crate) async fn get_by_university_id(
     db: &PostgresClient, university_id: i32,
 ) -> welds::errors::Result<Option<DbState<Student>>> {
-    let mut rows = Student::where_col(|s| s.university_id.equal(university_id))
-        .run(db)
-        .await?;
+    timed("students.get_by_university_id", async move {
+        let mut rows = Student::where_col(|s| s.university_id.equal(university_id))
+            .run(db)
+            .await?;
 
-    Ok(rows.pop())
-}
-
-/// Check if an email already exists
-pub(crate) async fn email_exists(db: &PostgresClient, email: &str) -> welds::errors::Result<bool> {
-    let result = get_by_email(db, email).await?;
-    Ok(result.is_some())
+        Ok(rows.pop())
+    })
+    .await
 }
 
 /// Check if a university ID already exists
 pub(crate) async fn university_id_exists(
     db: &PostgresClient, university_id: i32,
 ) -> welds::errors::Result<bool> {
-    let result = get_by_university_id(db, university_id).await?;
-    Ok(result.is_some())
+    timed("students.university_id_exists", async move {
+        let result = get_by_university_id(db, university_id).await?;
+        Ok(result.is_some())
+    })
+    .await
+}
+
+/// Count all students
+pub(crate) async fn count(db: &PostgresClient) -> welds::errors::Result<u64> {
+    timed(
+        "students.count",
+        async move { Student::all().count(db).await },
+    )
+    .await
 }
 
 /// Create a new student
 pub(crate) async fn create(
     db: &PostgresClient, student: Student,
 ) -> welds::errors::Result<DbState<Student>> {
-    let mut state = DbState::new_uncreated(student);
-    state.save(db).await?;
-    Ok(state)
+    timed("students.create", async move {
+        let mut state = DbState::new_uncreated(student);
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
 }
 
 /// Update student confirmation status by email
 pub(crate) async fn update_confirmation_by_email(
     db: &PostgresClient, email: &str, is_pending: bool,
 ) -> welds::errors::Result<()> {
-    Student::where_col(|s| s.email.equal(email))
-        .set(|s| s.is_pending, is_pending)
-        .run(db)
-        .await?;
-    Ok(())
+    timed("students.update_confirmation_by_email", async move {
+        Student::where_col(|s| s.email.equal(email))
+            .set(|s| s.is_pending, is_pending)
+            .run(db)
+            .await?;
+        Ok(())
+    })
+    .await
+}
+
+/// Mark all students whose ID is in the given list as confirmed
+pub(crate) async fn confirm_by_ids(
+    db: &impl Client, student_ids: &[i32],
+) -> welds::errors::Result<()> {
+    timed("students.confirm_by_ids", async move {
+        if student_ids.is_empty() {
+            return Ok(());
+        }
+
+        Student::where_col(|s| s.student_id.in_list(student_ids))
+            .set(|s| s.is_pending, false)
+            .run(db)
+            .await?;
+        Ok(())
+    })
+    .await
 }
 
 /// Update student password by email
 pub(crate) async fn update_password_by_email(
-    db: &PostgresClient, email: &str, password_hash: String,
+    db: &impl Client, email: &str, password_hash: String,
 ) -> welds::errors::Result<()> {
-    Student::where_col(|s| s.email.equal(email))
-        .set(|s| s.password_hash, password_hash)
-        .run(db)
-        .await?;
-    Ok(())
+    timed("students.update_password_by_email", async move {
+        Student::where_col(|s| s.email.equal(email))
+            .set(|s| s.password_hash, password_hash)
+            .run(db)
+            .await?;
+        Ok(())
+    })
+    .await
 }
 
 /// Update a student
 pub(crate) async fn update(
     db: &PostgresClient, mut state: DbState<Student>,
 ) -> welds::errors::Result<DbState<Student>> {
-    state.save(db).await?;
-    Ok(state)
+    timed("students.update", async move {
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
+}
+
+/// Students with access to project `$1`, through a group or a redeemed security code
+const STUDENTS_OF_PROJECT: &str = "SELECT gm.student_id FROM group_members gm \
+     JOIN groups g ON g.group_id = gm.group_id WHERE g.project_id = $1 \
+     UNION SELECT spa.student_id FROM student_project_access spa WHERE spa.project_id = $1";
+
+/// Revoke the sessions of every student with access to a project, returning their IDs
+///
+/// Tokens issued up to `now` are rejected afterward, whatever project they are used for.
+pub(crate) async fn revoke_sessions_by_project(
+    db: &impl Client, project_id: i32, now: DateTime<Utc>,
+) -> welds::errors::Result<Vec<i32>> {
+    timed("students.revoke_sessions_by_project", async move {
+        let rows = db
+            .fetch_rows(
+                &format!(
+                    "UPDATE students SET sessions_revoked_at = $2 \
+                     WHERE student_id IN ({}) RETURNING student_id",
+                    STUDENTS_OF_PROJECT
+                ),
+                &[&project_id, &now],
+            )
+            .await?;
+
+        let mut student_ids = Vec::with_capacity(rows.len());
+        for row in rows {
+            student_ids.push(row.get("student_id")?);
+        }
+        Ok(student_ids)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RecordingClient;
+
+    #[actix_web::test]
+    async fn test_only_students_of_the_project_are_revoked() {
+        let db = RecordingClient::default();
+
+        revoke_sessions_by_project(&db, 4, Utc::now())
+            .await
+            .unwrap();
+
+        let statements = db.statements();
+        assert_eq!(statements.len(), 1);
+        let sql = &statements[0];
+        // Members of its groups and students who redeemed one of its codes, nobody else
+        assert!(
+            sql.contains("WHERE student_id IN (SELECT gm.student_id FROM group_members gm"),
+            "{}",
+            sql
+        );
+        assert!(sql.contains("WHERE g.project_id = $1 UNION"), "{}", sql);
+        assert!(sql.contains("WHERE spa.project_id = $1)"), "{}", sql);
+        assert_eq!(sql.matches("WHERE").count(), 3, "{}", sql);
+    }
 }
@@ -1,48 +1,241 @@
+use crate::database::timing::timed;
 use crate::models::transaction::Transaction;
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
 use welds::connections::postgres::PostgresClient;
 use welds::state::DbState;
+use welds::{Client, TransactStart};
 
 pub(crate) async fn create(
     db: &PostgresClient, transaction: Transaction,
 ) -> welds::errors::Result<DbState<Transaction>> {
-    let mut state = DbState::new_uncreated(transaction);
-    state.save(db).await?;
-    Ok(state)
+    timed("transactions.create", async move {
+        let mut state = DbState::new_uncreated(transaction);
+        state.save(db).await?;
+        Ok(state)
+    })
+    .await
 }
 
 pub(crate) async fn get_by_id(
     db: &PostgresClient, transaction_id: i32,
 ) -> welds::errors::Result<Option<DbState<Transaction>>> {
-    let mut rows = Transaction::where_col(|t| t.transaction_id.equal(transaction_id))
-        .run(db)
-        .await?;
-    Ok(rows.pop())
+    timed("transactions.get_by_id", async move {
+        let mut rows = Transaction::where_col(|t| t.transaction_id.equal(transaction_id))
+            .run(db)
+            .await?;
+        Ok(rows.pop())
+    })
+    .await
 }
 
+/// Purchases of a group in a fair, reversed ones left out
 pub(crate) async fn get_by_fair_and_buyer(
     db: &PostgresClient, fair_id: i32, buyer_group_id: i32,
 ) -> welds::errors::Result<Vec<DbState<Transaction>>> {
-    Transaction::where_col(|t| t.fair_id.equal(fair_id))
-        .where_col(|t| t.buyer_group_id.equal(buyer_group_id))
-        .run(db)
-        .await
+    timed("transactions.get_by_fair_and_buyer", async move {
+        let rows = Transaction::where_col(|t| t.fair_id.equal(fair_id))
+            .where_col(|t| t.buyer_group_id.equal(buyer_group_id))
+            .run(db)
+            .await?;
+        Ok(without_reversed(rows))
+    })
+    .await
 }
 
 /// Check whether a specific (buyer, seller_selection, component) purchase already exists.
+/// A reversed purchase still exists, so it cannot be bought again.
 pub(crate) async fn purchase_exists(
     db: &PostgresClient, buyer_group_id: i32, group_deliverable_selection_id: i32,
     group_deliverable_component_id: i32,
 ) -> welds::errors::Result<bool> {
-    let rows = Transaction::where_col(|t| t.buyer_group_id.equal(buyer_group_id))
-        .where_col(|t| {
-            t.group_deliverable_selection_id
-                .equal(group_deliverable_selection_id)
-        })
-        .where_col(|t| {
-            t.group_deliverable_component_id
-                .equal(group_deliverable_component_id)
-        })
-        .run(db)
-        .await?;
-    Ok(!rows.is_empty())
+    timed("transactions.purchase_exists", async move {
+        let rows = Transaction::where_col(|t| t.buyer_group_id.equal(buyer_group_id))
+            .where_col(|t| t.reverses_transaction_id.equal(None))
+            .where_col(|t| {
+                t.group_deliverable_selection_id
+                    .equal(group_deliverable_selection_id)
+            })
+            .where_col(|t| {
+                t.group_deliverable_component_id
+                    .equal(group_deliverable_component_id)
+            })
+            .run(db)
+            .await?;
+        Ok(!rows.is_empty())
+    })
+    .await
+}
+
+/// Outcome of reversing a transaction, decided on the locked original
+#[derive(Debug, Clone)]
+pub(crate) enum Reversal {
+    Reversed(Transaction),
+    NotFound,
+    /// The transaction already has a reversing entry
+    AlreadyReversed {
+        reversing_transaction_id: i32,
+    },
+    /// The transaction is itself a reversing entry
+    ReversingEntry,
+}
+
+/// Decides whether `original` can be reversed, building the reversing entry
+///
+/// `existing` is the reversing entry already linked to `original`, if any.
+pub(crate) fn plan_reversal(
+    original: Option<&Transaction>, existing: Option<&Transaction>, admin_id: i32,
+    now: DateTime<Utc>,
+) -> Reversal {
+    let Some(original) = original else {
+        return Reversal::NotFound;
+    };
+    if original.reverses_transaction_id.is_some() {
+        return Reversal::ReversingEntry;
+    }
+    if let Some(existing) = existing {
+        return Reversal::AlreadyReversed {
+            reversing_transaction_id: existing.transaction_id,
+        };
+    }
+
+    Reversal::Reversed(Transaction {
+        transaction_id: 0,
+        timestamp: now,
+        reverses_transaction_id: Some(original.transaction_id),
+        reversed_by_admin_id: Some(admin_id),
+        ..original.clone()
+    })
+}
+
+/// Reverse a transaction while holding a lock on it
+///
+/// Concurrent reversals of the same transaction are serialized, and the unique link to the
+/// original rejects any that would slip through.
+pub(crate) async fn reverse_locked(
+    db: &PostgresClient, transaction_id: i32, admin_id: i32, now: DateTime<Utc>,
+) -> welds::errors::Result<Reversal> {
+    timed("transactions.reverse_locked", async move {
+        let transaction = db.begin().await?;
+
+        let result = async {
+            transaction
+                .fetch_rows(
+                    "SELECT transaction_id FROM transactions WHERE transaction_id = $1 FOR UPDATE",
+                    &[&transaction_id],
+                )
+                .await?;
+
+            let original = Transaction::where_col(|t| t.transaction_id.equal(transaction_id))
+                .run(&transaction)
+                .await?
+                .pop()
+                .map(DbState::into_inner);
+            let existing =
+                Transaction::where_col(|t| t.reverses_transaction_id.equal(transaction_id))
+                    .run(&transaction)
+                    .await?
+                    .pop()
+                    .map(DbState::into_inner);
+
+            let reversal = plan_reversal(original.as_ref(), existing.as_ref(), admin_id, now);
+            if let Reversal::Reversed(entry) = reversal {
+                let mut state = DbState::new_uncreated(entry);
+                state.save(&transaction).await?;
+                return Ok(Reversal::Reversed(DbState::into_inner(state)));
+            }
+            Ok(reversal)
+        }
+        .await;
+
+        match result {
+            Ok(reversal) => {
+                transaction.commit().await?;
+                Ok(reversal)
+            }
+            Err(e) => {
+                transaction.rollback().await?;
+                Err(e)
+            }
+        }
+    })
+    .await
+}
+
+/// Drops reversing entries and the transactions they reverse, leaving the purchases that count
+///
+/// Both sides of a reversal must be in `transactions`, as they are when fetching by buyer,
+/// fair or seller selection.
+pub(crate) fn without_reversed(
+    transactions: Vec<DbState<Transaction>>,
+) -> Vec<DbState<Transaction>> {
+    let reversed: HashSet<i32> = transactions
+        .iter()
+        .filter_map(|t| t.reverses_transaction_id)
+        .collect();
+    transactions
+        .into_iter()
+        .filter(|t| t.reverses_transaction_id.is_none() && !reversed.contains(&t.transaction_id))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn purchase(transaction_id: i32) -> Transaction {
+        Transaction {
+            transaction_id,
+            buyer_group_id: 1,
+            group_deliverable_selection_id: 2,
+            group_deliverable_component_id: 3,
+            fair_id: 4,
+            timestamp: Utc.with_ymd_and_hms(2026, 6, 1, 10, 0, 0).unwrap(),
+            reverses_transaction_id: None,
+            reversed_by_admin_id: None,
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 6, 2, 10, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_first_reversal_links_the_original() {
+        match plan_reversal(Some(&purchase(10)), None, 5, now()) {
+            Reversal::Reversed(entry) => {
+                assert_eq!(entry.reverses_transaction_id, Some(10));
+                assert_eq!(entry.reversed_by_admin_id, Some(5));
+                assert_eq!(entry.buyer_group_id, 1);
+                assert_eq!(entry.group_deliverable_component_id, 3);
+                assert_eq!(entry.timestamp, now());
+            }
+            other => panic!("expected a reversal, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_second_reversal_is_rejected() {
+        let reversing = Transaction {
+            reverses_transaction_id: Some(10),
+            reversed_by_admin_id: Some(5),
+            ..purchase(11)
+        };
+
+        assert!(matches!(
+            plan_reversal(Some(&purchase(10)), Some(&reversing), 6, now()),
+            Reversal::AlreadyReversed {
+                reversing_transaction_id: 11
+            }
+        ));
+        assert!(matches!(
+            plan_reversal(Some(&reversing), None, 6, now()),
+            Reversal::ReversingEntry
+        ));
+        assert!(matches!(
+            plan_reversal(None, None, 6, now()),
+            Reversal::NotFound
+        ));
+    }
 }
@@ -0,0 +1,33 @@
+use crate::database::timing::timed;
+use crate::models::used_reset_token::UsedResetToken;
+use welds::connections::postgres::PostgresClient;
+use welds::Client;
+
+/// Check whether the reset token with this hash was already used
+pub(crate) async fn is_used(db: &PostgresClient, token_hash: &str) -> welds::errors::Result<bool> {
+    timed("used_reset_tokens.is_used", async move {
+        let count = UsedResetToken::where_col(|t| t.token_hash.equal(token_hash))
+            .count(db)
+            .await?;
+
+        Ok(count > 0)
+    })
+    .await
+}
+
+/// Record the reset token with this hash as used, returning how many rows were inserted
+///
+/// Recording it twice inserts nothing, so 0 means another request already used the token.
+pub(crate) async fn mark_used(db: &impl Client, token_hash: &str) -> welds::errors::Result<u64> {
+    let token_hash = token_hash.to_string();
+    timed("used_reset_tokens.mark_used", async move {
+        let result = db
+            .execute(
+                "INSERT INTO used_reset_tokens (token_hash) VALUES ($1) ON CONFLICT DO NOTHING",
+                &[&token_hash],
+            )
+            .await?;
+        Ok(result.rows_affected())
+    })
+    .await
+}
@@ -1,5 +1,21 @@
+use crate::common::slug::{slugify, unique_slug};
+use crate::database::repositories::projects_repository;
 use crate::models::admin_role::{AdminRole, AvailableAdminRole};
+use crate::models::group::Group;
+use crate::models::group_deliverable::GroupDeliverable;
+use crate::models::group_deliverable_component::GroupDeliverableComponent;
+use crate::models::group_deliverable_selection::GroupDeliverableSelection;
+use crate::models::group_deliverables_component::GroupDeliverablesComponent;
+use crate::models::group_member::GroupMember;
+use crate::models::project::Project;
+use crate::models::student::Student;
+use crate::models::student_deliverable::StudentDeliverable;
+use crate::models::student_deliverable_component::StudentDeliverableComponent;
+use crate::models::student_deliverable_selection::StudentDeliverableSelection;
+use crate::models::student_deliverables_component::StudentDeliverablesComponent;
+use crate::models::student_project_access::StudentProjectAccess;
 use crate::models::student_role::{AvailableStudentRole, StudentRole};
+use chrono::Utc;
 use welds::state::DbState;
 
 /// Seeds the admin roles table with the default roles
@@ -71,3 +87,344 @@ pub(crate) async fn seed_all_roles(db: &impl welds::Client) -> welds::errors::Re
     seed_student_roles(db).await?;
     Ok(())
 }
+
+/// Password of every student created by [`seed_demo_data`]
+pub(crate) const DEMO_STUDENT_PASSWORD: &str = "demo-password";
+
+/// Components required by a deliverable, as (component index, quantity, weight)
+type DemoLinks = Vec<(usize, i32, i32)>;
+
+/// Student account of the demo data
+#[derive(Debug)]
+pub(crate) struct DemoStudent {
+    pub first_name: &'static str,
+    pub last_name: &'static str,
+    pub email: String,
+    pub university_id: i32,
+}
+
+/// Group of the demo data, the first member is the leader
+#[derive(Debug)]
+pub(crate) struct DemoGroup {
+    pub name: &'static str,
+    pub members: Vec<usize>,
+    pub deliverable: usize,
+}
+
+/// Sample project with everything students and admins interact with
+///
+/// Entities refer to each other by their index in the plan, ids are only known once
+/// [`seed_demo_data`] inserts them.
+#[derive(Debug)]
+pub(crate) struct DemoData {
+    pub project_name: String,
+    pub year: i32,
+    pub group_components: Vec<(&'static str, bool)>,
+    pub group_deliverables: Vec<(&'static str, DemoLinks)>,
+    pub student_components: Vec<&'static str>,
+    pub student_deliverables: Vec<(&'static str, DemoLinks)>,
+    pub students: Vec<DemoStudent>,
+    pub groups: Vec<DemoGroup>,
+    /// (student index, student deliverable index)
+    pub student_selections: Vec<(usize, usize)>,
+}
+
+impl DemoData {
+    /// Demo plan whose names, emails and university ids are made unique by `tag`
+    pub(crate) fn sample(tag: u32, year: i32) -> Self {
+        let names = [
+            ("Mario", "Rossi"),
+            ("Anna", "Bianchi"),
+            ("Luca", "Verdi"),
+            ("Giulia", "Neri"),
+            ("Marco", "Russo"),
+            ("Sara", "Gallo"),
+        ];
+        let students = names
+            .iter()
+            .enumerate()
+            .map(|(i, (first_name, last_name))| DemoStudent {
+                first_name,
+                last_name,
+                email: format!(
+                    "{}.{}.{}@demo.example.com",
+                    first_name.to_lowercase(),
+                    last_name.to_lowercase(),
+                    tag
+                ),
+                university_id: 900_000_000 + (tag % 10_000_000) as i32 * 10 + i as i32,
+            })
+            .collect();
+
+        Self {
+            project_name: format!("Demo project {}", tag),
+            year,
+            group_components: vec![("Engine", true), ("Wheels", true), ("Dashboard", false)],
+            group_deliverables: vec![
+                ("Car", vec![(0, 1, 50), (1, 4, 30), (2, 1, 20)]),
+                ("Bike", vec![(1, 2, 60), (2, 1, 40)]),
+            ],
+            student_components: vec!["Report", "Presentation"],
+            student_deliverables: vec![
+                ("Written exam", vec![(0, 1, 100)]),
+                ("Talk", vec![(0, 1, 40), (1, 1, 60)]),
+            ],
+            students,
+            groups: vec![
+                DemoGroup {
+                    name: "Demo group A",
+                    members: vec![0, 1, 2],
+                    deliverable: 0,
+                },
+                DemoGroup {
+                    name: "Demo group B",
+                    members: vec![3, 4, 5],
+                    deliverable: 1,
+                },
+            ],
+            student_selections: (0..names.len()).map(|i| (i, i % 2)).collect(),
+        }
+    }
+}
+
+/// Ids of the rows created by [`seed_demo_data`], in the order of the plan
+#[derive(Debug, Default)]
+pub(crate) struct SeededDemo {
+    pub project_id: i32,
+    pub group_deliverable_ids: Vec<i32>,
+    pub group_deliverable_component_ids: Vec<i32>,
+    pub student_deliverable_ids: Vec<i32>,
+    pub student_deliverable_component_ids: Vec<i32>,
+    pub student_ids: Vec<i32>,
+    pub group_ids: Vec<i32>,
+}
+
+/// Inserts the demo plan, pass a transaction to keep either all of it or nothing
+///
+/// Students are created already confirmed, with `password_hash` as their password.
+pub(crate) async fn seed_demo_data(
+    db: &impl welds::Client, plan: &DemoData, password_hash: &str,
+) -> welds::errors::Result<SeededDemo> {
+    let now = Utc::now();
+    let mut seeded = SeededDemo::default();
+
+    let slug = slugify(&plan.project_name);
+    let taken = projects_repository::slugs_starting_with(db, &slug).await?;
+    let mut project = DbState::new_uncreated(Project {
+        project_id: 0,
+        name: plan.project_name.clone(),
+        slug: unique_slug(&slug, &taken),
+        year: plan.year,
+        max_student_uploads: 5,
+        max_group_size: 4,
+        deliverable_selection_deadline: None,
+        upload_deadline: None,
+        active: true,
+        oral_exam_enabled: false,
+        frozen: false,
+        allowed_signup_domains: None,
+        published: true,
+    });
+    project.save(db).await?;
+    seeded.project_id = project.project_id;
+
+    for (name, sellable) in &plan.group_components {
+        let mut state = DbState::new_uncreated(GroupDeliverableComponent {
+            group_deliverable_component_id: 0,
+            project_id: seeded.project_id,
+            name: (*name).to_string(),
+            sellable: *sellable,
+        });
+        state.save(db).await?;
+        seeded
+            .group_deliverable_component_ids
+            .push(state.group_deliverable_component_id);
+    }
+
+    for (name, links) in &plan.group_deliverables {
+        let mut state = DbState::new_uncreated(GroupDeliverable {
+            group_deliverable_id: 0,
+            project_id: seeded.project_id,
+            name: (*name).to_string(),
+        });
+        state.save(db).await?;
+        for (component, quantity, weight) in links {
+            let mut link = DbState::new_uncreated(GroupDeliverablesComponent {
+                id: 0,
+                group_deliverable_id: state.group_deliverable_id,
+                group_deliverable_component_id: seeded.group_deliverable_component_ids[*component],
+                quantity: *quantity,
+                weight: *weight,
+            });
+            link.save(db).await?;
+        }
+        seeded
+            .group_deliverable_ids
+            .push(state.group_deliverable_id);
+    }
+
+    for name in &plan.student_components {
+        let mut state = DbState::new_uncreated(StudentDeliverableComponent {
+            student_deliverable_component_id: 0,
+            project_id: seeded.project_id,
+            name: (*name).to_string(),
+        });
+        state.save(db).await?;
+        seeded
+            .student_deliverable_component_ids
+            .push(state.student_deliverable_component_id);
+    }
+
+    for (name, links) in &plan.student_deliverables {
+        let mut state = DbState::new_uncreated(StudentDeliverable {
+            student_deliverable_id: 0,
+            project_id: seeded.project_id,
+            name: (*name).to_string(),
+        });
+        state.save(db).await?;
+        for (component, quantity, weight) in links {
+            let mut link = DbState::new_uncreated(StudentDeliverablesComponent {
+                id: 0,
+                student_deliverable_id: state.student_deliverable_id,
+                student_deliverable_component_id: seeded.student_deliverable_component_ids
+                    [*component],
+                quantity: *quantity,
+                weight: *weight,
+            });
+            link.save(db).await?;
+        }
+        seeded
+            .student_deliverable_ids
+            .push(state.student_deliverable_id);
+    }
+
+    for student in &plan.students {
+        let mut state = DbState::new_uncreated(Student {
+            student_id: 0,
+            first_name: student.first_name.to_string(),
+            last_name: student.last_name.to_string(),
+            email: student.email.clone(),
+            university_id: student.university_id,
+            password_hash: password_hash.to_string(),
+            is_pending: false,
+            sessions_revoked_at: None,
+        });
+        state.save(db).await?;
+
+        let mut access = DbState::new_uncreated(StudentProjectAccess {
+            student_project_access_id: 0,
+            student_id: state.student_id,
+            project_id: seeded.project_id,
+            security_code_id: None,
+            granted_at: now,
+        });
+        access.save(db).await?;
+        seeded.student_ids.push(state.student_id);
+    }
+
+    for group in &plan.groups {
+        let mut state = DbState::new_uncreated(Group {
+            group_id: 0,
+            project_id: seeded.project_id,
+            name: group.name.to_string(),
+            created_at: now,
+        });
+        state.save(db).await?;
+
+        for (position, member) in group.members.iter().enumerate() {
+            let role = if position == 0 {
+                AvailableStudentRole::GroupLeader
+            } else {
+                AvailableStudentRole::Member
+            };
+            let mut state = DbState::new_uncreated(GroupMember {
+                group_member_id: 0,
+                group_id: state.group_id,
+                student_id: seeded.student_ids[*member],
+                student_role_id: role as i32,
+                joined_at: now,
+            });
+            state.save(db).await?;
+        }
+
+        let mut selection = DbState::new_uncreated(GroupDeliverableSelection {
+            group_deliverable_selection_id: 0,
+            group_id: state.group_id,
+            group_deliverable_id: seeded.group_deliverable_ids[group.deliverable],
+            created_at: now,
+            updated_at: now,
+        });
+        selection.save(db).await?;
+        seeded.group_ids.push(state.group_id);
+    }
+
+    for (student, deliverable) in &plan.student_selections {
+        let mut state = DbState::new_uncreated(StudentDeliverableSelection {
+            student_deliverable_selection_id: 0,
+            student_id: seeded.student_ids[*student],
+            student_deliverable_id: seeded.student_deliverable_ids[*deliverable],
+            created_at: now,
+            updated_at: now,
+        });
+        state.save(db).await?;
+    }
+
+    Ok(seeded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::link_weights::MAX_TOTAL_WEIGHT;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_demo_plan_has_the_expected_entities() {
+        let plan = DemoData::sample(42, 2026);
+
+        assert_eq!(plan.group_components.len(), 3);
+        assert_eq!(plan.group_deliverables.len(), 2);
+        assert_eq!(plan.student_components.len(), 2);
+        assert_eq!(plan.student_deliverables.len(), 2);
+        assert_eq!(plan.students.len(), 6);
+        assert_eq!(plan.groups.len(), 2);
+        assert_eq!(plan.student_selections.len(), 6);
+
+        // Every student is in exactly one group and references stay inside the plan
+        let members: Vec<usize> = plan.groups.iter().flat_map(|g| g.members.clone()).collect();
+        assert_eq!(members.len(), plan.students.len());
+        assert_eq!(members.iter().collect::<HashSet<_>>().len(), members.len());
+        assert!(plan
+            .groups
+            .iter()
+            .all(|g| g.deliverable < plan.group_deliverables.len()));
+        for (_, links) in plan
+            .group_deliverables
+            .iter()
+            .chain(&plan.student_deliverables)
+        {
+            assert!(links.iter().map(|(_, _, w)| w).sum::<i32>() <= MAX_TOTAL_WEIGHT);
+        }
+    }
+
+    #[test]
+    fn test_demo_plans_with_different_tags_do_not_collide() {
+        let (a, b) = (DemoData::sample(1, 2026), DemoData::sample(2, 2026));
+
+        let emails: HashSet<&str> = a
+            .students
+            .iter()
+            .chain(&b.students)
+            .map(|s| s.email.as_str())
+            .collect();
+        let ids: HashSet<i32> = a
+            .students
+            .iter()
+            .chain(&b.students)
+            .map(|s| s.university_id)
+            .collect();
+        assert_eq!(emails.len(), 12);
+        assert_eq!(ids.len(), 12);
+        assert_ne!(a.project_name, b.project_name);
+    }
+}
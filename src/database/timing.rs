@@ -0,0 +1,128 @@
+use crate::database::query_budget::Operation;
+use log::{debug, warn};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Threshold used until the configured one is installed at startup
+const DEFAULT_SLOW_QUERY_MS: u64 = 200;
+
+static SLOW_QUERY_MS: AtomicU64 = AtomicU64::new(DEFAULT_SLOW_QUERY_MS);
+
+/// Sets the duration above which database operations are logged as slow
+pub(crate) fn set_slow_query_threshold(ms: u64) {
+    SLOW_QUERY_MS.store(ms, Ordering::Relaxed);
+}
+
+fn slow_query_threshold() -> Duration {
+    Duration::from_millis(SLOW_QUERY_MS.load(Ordering::Relaxed))
+}
+
+/// Database operation that took longer than the slow query threshold
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct SlowQuery {
+    pub operation: &'static str,
+    pub duration_ms: u128,
+}
+
+/// Logs the duration of an operation, returning the slow query entry when over `threshold`
+fn record(operation: &'static str, elapsed: Duration, threshold: Duration) -> Option<SlowQuery> {
+    let duration_ms = elapsed.as_millis();
+    debug!("db op={} duration_ms={}", operation, duration_ms);
+
+    if elapsed < threshold {
+        return None;
+    }
+
+    warn!(
+        "slow db op={} duration_ms={} threshold_ms={}",
+        operation,
+        duration_ms,
+        threshold.as_millis()
+    );
+    Some(SlowQuery {
+        operation,
+        duration_ms,
+    })
+}
+
+/// Runs a repository operation measuring how long it takes
+///
+/// The operation also counts towards the query budget of the current request.
+///
+/// # Arguments
+/// * `operation` - Label in the form `repository.function`, e.g. `projects.get_all`
+/// * `fut` - The database operation to run
+pub(crate) async fn timed<T>(operation: &'static str, fut: impl Future<Output = T>) -> T {
+    let _counted = Operation::start();
+    let start = Instant::now();
+    let output = fut.await;
+    record(operation, start.elapsed(), slow_query_threshold());
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[actix_web::test]
+    async fn test_slow_operation_produces_slow_query_entry() {
+        let start = Instant::now();
+        actix_web::rt::time::sleep(Duration::from_millis(20)).await;
+
+        let entry = record(
+            "projects.get_all",
+            start.elapsed(),
+            Duration::from_millis(10),
+        );
+
+        let entry = entry.expect("operation should be reported as slow");
+        assert_eq!(entry.operation, "projects.get_all");
+        assert!(entry.duration_ms >= 20);
+    }
+
+    #[test]
+    fn test_fast_operation_is_not_reported() {
+        assert!(record(
+            "projects.get_by_id",
+            Duration::from_millis(1),
+            Duration::from_millis(10)
+        )
+        .is_none());
+    }
+
+    #[actix_web::test]
+    async fn test_timed_returns_operation_output() {
+        assert_eq!(timed("projects.count", async { 42 }).await, 42);
+    }
+
+    #[test]
+    fn test_every_repository_function_is_timed() {
+        let dir =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/database/repositories");
+        let mut untimed = Vec::new();
+
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let stem = path.file_stem().unwrap().to_str().unwrap().to_string();
+            if stem == "mod" {
+                continue;
+            }
+            let source = std::fs::read_to_string(&path).unwrap();
+            let source = source.split("#[cfg(test)]").next().unwrap();
+            let repository = stem.trim_end_matches("_repository");
+
+            // Each function runs up to the closing brace at the start of a line
+            for function in source.split("pub(crate) async fn ").skip(1) {
+                let name = function.split(['(', '<']).next().unwrap();
+                let body = function.split("\n}").next().unwrap();
+                let label = format!("\"{}.{}\"", repository, name);
+                if !body.contains("timed(") || !body.contains(&label) {
+                    untimed.push(format!("{}::{}", stem, name));
+                }
+            }
+        }
+
+        assert!(untimed.is_empty(), "not timed: {:?}", untimed);
+    }
+}
@@ -0,0 +1,418 @@
+use crate::app_data::AppData;
+use crate::common::json_error::{error_with_log_id, JsonError};
+use actix_web::body::EitherBody;
+use actix_web::dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::{Method, StatusCode};
+use actix_web::web::Data;
+use actix_web::{Error, FromRequest, HttpMessage, HttpRequest, ResponseError};
+use async_trait::async_trait;
+use futures_util::future::{ready, LocalBoxFuture, Ready};
+use log::warn;
+use sqlx::{PgPool, Postgres};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex as StdMutex};
+use tokio::sync::Mutex;
+use welds::connections::errors::{Error as ConnectionError, Result as ConnectionResult};
+use welds::connections::postgres::PostgresParam;
+use welds::connections::{ExecuteResult, Fetch, Param};
+use welds::{Client, Row, Syntax};
+
+type PgTransaction = sqlx::Transaction<'static, Postgres>;
+
+/// Work deferred until the writes of a request are committed
+type AfterCommit = Box<dyn FnOnce() + Send>;
+
+/// Whether requests with this method run inside a transaction
+fn opens_transaction(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    )
+}
+
+/// Whether a response with this status keeps the writes made by the handler
+fn commits(status: StatusCode) -> bool {
+    status.is_success()
+}
+
+/// How the transaction of a request ended
+#[derive(Debug, PartialEq, Eq)]
+enum Settled {
+    Committed,
+    RolledBack,
+}
+
+/// Transaction the middleware ends once the handler has answered
+trait Settle {
+    async fn commit(self) -> Result<(), sqlx::Error>;
+    async fn rollback(self) -> Result<(), sqlx::Error>;
+}
+
+impl Settle for PgTransaction {
+    async fn commit(self) -> Result<(), sqlx::Error> {
+        sqlx::Transaction::commit(self).await
+    }
+
+    async fn rollback(self) -> Result<(), sqlx::Error> {
+        sqlx::Transaction::rollback(self).await
+    }
+}
+
+/// Commits the transaction on a 2xx response and rolls it back otherwise
+async fn settle<T: Settle>(tx: T, status: StatusCode) -> Result<Settled, sqlx::Error> {
+    if commits(status) {
+        tx.commit().await?;
+        Ok(Settled::Committed)
+    } else {
+        tx.rollback().await?;
+        Ok(Settled::RolledBack)
+    }
+}
+
+/// Transaction of a request, begun by its first query
+enum TxState {
+    Pending(PgPool),
+    Open(PgTransaction),
+    Closed,
+}
+
+impl TxState {
+    /// The transaction, begun on first use
+    async fn open(&mut self) -> ConnectionResult<&mut PgTransaction> {
+        if let TxState::Pending(pool) = self {
+            let tx = pool.begin().await?;
+            *self = TxState::Open(tx);
+        }
+        match self {
+            TxState::Open(tx) => Ok(tx),
+            _ => Err(ConnectionError::ClosedTransaction),
+        }
+    }
+}
+
+/// Database transaction shared by every write of a request
+///
+/// Opened by [`UnitOfWorkMiddleware`] and extracted by handlers; it can be passed to
+/// welds queries in place of the pool, so their writes are committed only if the
+/// handler answers with a 2xx status. The transaction begins with the first query, so a
+/// request rejected before touching the database never holds a connection.
+#[derive(Clone)]
+pub(crate) struct UnitOfWork {
+    state: Arc<Mutex<TxState>>,
+    after_commit: Arc<StdMutex<Vec<AfterCommit>>>,
+}
+
+impl UnitOfWork {
+    fn new(pool: PgPool) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(TxState::Pending(pool))),
+            after_commit: Arc::new(StdMutex::new(Vec::new())),
+        }
+    }
+
+    /// Runs `hook` once the writes of the request are committed, never if they are rolled back
+    ///
+    /// Meant for caches of what the handler writes: cleared before the commit, a concurrent
+    /// read could load the old rows and keep them.
+    pub(crate) fn after_commit(&self, hook: impl FnOnce() + Send + 'static) {
+        self.after_commit
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Box::new(hook));
+    }
+
+    fn run_after_commit(&self) {
+        let hooks =
+            std::mem::take(&mut *self.after_commit.lock().unwrap_or_else(|e| e.into_inner()));
+        for hook in hooks {
+            hook();
+        }
+    }
+
+    /// Takes the transaction out, later queries fail as on a closed transaction
+    ///
+    /// Returns `None` when no query was run, so there is nothing to settle.
+    async fn take(&self) -> Option<PgTransaction> {
+        match std::mem::replace(&mut *self.state.lock().await, TxState::Closed) {
+            TxState::Open(tx) => Some(tx),
+            _ => None,
+        }
+    }
+}
+
+impl FromRequest for UnitOfWork {
+    type Error = JsonError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(req.extensions().get::<UnitOfWork>().cloned().ok_or_else(|| {
+            error_with_log_id(
+                format!(
+                    "{} {} expects a unit of work but its scope is not wrapped in UnitOfWorkMiddleware",
+                    req.method(),
+                    req.path()
+                ),
+                "Internal server error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+                log::Level::Error,
+            )
+        }))
+    }
+}
+
+#[async_trait]
+impl Client for UnitOfWork {
+    async fn execute(
+        &self, sql: &str, params: &[&(dyn Param + Sync)],
+    ) -> ConnectionResult<ExecuteResult> {
+        let mut state = self.state.lock().await;
+        let tx = state.open().await?;
+        let mut query = sqlx::query::<Postgres>(sql);
+        for param in params {
+            query = PostgresParam::add_param(*param, query);
+        }
+        let result = query.execute(&mut **tx).await?;
+        Ok(ExecuteResult::new(result.rows_affected()))
+    }
+
+    async fn fetch_rows(
+        &self, sql: &str, params: &[&(dyn Param + Sync)],
+    ) -> ConnectionResult<Vec<Row>> {
+        let mut state = self.state.lock().await;
+        let tx = state.open().await?;
+        let mut query = sqlx::query::<Postgres>(sql);
+        for param in params {
+            query = PostgresParam::add_param(*param, query);
+        }
+        let rows = query.fetch_all(&mut **tx).await?;
+        Ok(rows.into_iter().map(Row::from).collect())
+    }
+
+    async fn fetch_many<'s, 'args, 't>(
+        &self, fetches: &[Fetch<'s, 'args, 't>],
+    ) -> ConnectionResult<Vec<Vec<Row>>> {
+        let mut state = self.state.lock().await;
+        let tx = state.open().await?;
+        let mut datasets = Vec::with_capacity(fetches.len());
+        for fetch in fetches {
+            let mut query = sqlx::query::<Postgres>(fetch.sql);
+            for param in fetch.params {
+                query = PostgresParam::add_param(*param, query);
+            }
+            let rows = query.fetch_all(&mut **tx).await?;
+            datasets.push(rows.into_iter().map(Row::from).collect());
+        }
+        Ok(datasets)
+    }
+
+    fn syntax(&self) -> Syntax {
+        Syntax::Postgres
+    }
+}
+
+/// Runs mutating requests of the wrapped scope inside a [`UnitOfWork`]
+///
+/// Opt-in per scope or resource with `.wrap(UnitOfWorkMiddleware)`: reads pass through
+/// untouched so long listings do not hold a connection for their whole duration
+pub(crate) struct UnitOfWorkMiddleware;
+
+impl<S, B> Transform<S, ServiceRequest> for UnitOfWorkMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = UnitOfWorkService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(UnitOfWorkService {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub(crate) struct UnitOfWorkService<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for UnitOfWorkService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        Box::pin(async move {
+            if !opens_transaction(req.method()) {
+                return service.call(req).await.map(|res| res.map_into_left_body());
+            }
+
+            let Some(data) = req.app_data::<Data<AppData>>().cloned() else {
+                let error = error_with_log_id(
+                    "UnitOfWorkMiddleware used without application data",
+                    "Internal server error",
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    log::Level::Error,
+                );
+                return Ok(req.error_response(error).map_into_right_body());
+            };
+
+            let unit_of_work = UnitOfWork::new(data.db.as_sqlx_pool().clone());
+            req.extensions_mut().insert(unit_of_work.clone());
+
+            let result = service.call(req).await;
+            let Some(tx) = unit_of_work.take().await else {
+                // Nothing to commit, a successful answer still means the handler is done
+                if result.as_ref().is_ok_and(|res| commits(res.status())) {
+                    unit_of_work.run_after_commit();
+                }
+                return result.map(|res| res.map_into_left_body());
+            };
+
+            let res = match result {
+                Ok(res) => res,
+                Err(e) => {
+                    if let Err(rollback_error) = tx.rollback().await {
+                        warn!(
+                            "unable to roll back request transaction: {}",
+                            rollback_error
+                        );
+                    }
+                    return Err(e);
+                }
+            };
+
+            let status = res.status();
+            match settle(tx, status).await {
+                Ok(Settled::Committed) => {
+                    unit_of_work.run_after_commit();
+                    Ok(res.map_into_left_body())
+                }
+                Ok(Settled::RolledBack) => Ok(res.map_into_left_body()),
+                Err(e) if commits(status) => {
+                    let error = error_with_log_id(
+                        format!("unable to commit request transaction: {}", e),
+                        "Database error",
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        log::Level::Error,
+                    );
+                    let (req, _) = res.into_parts();
+                    let response = error.error_response();
+                    Ok(ServiceResponse::new(req, response).map_into_right_body())
+                }
+                Err(e) => {
+                    warn!("unable to roll back request transaction: {}", e);
+                    Ok(res.map_into_left_body())
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_app_data;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::{web, App, HttpResponse};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Records how the middleware ended the transaction instead of touching a database
+    struct RecordingTx {
+        outcome: Arc<StdMutex<Option<Settled>>>,
+    }
+
+    impl Settle for RecordingTx {
+        async fn commit(self) -> Result<(), sqlx::Error> {
+            *self.outcome.lock().unwrap() = Some(Settled::Committed);
+            Ok(())
+        }
+
+        async fn rollback(self) -> Result<(), sqlx::Error> {
+            *self.outcome.lock().unwrap() = Some(Settled::RolledBack);
+            Ok(())
+        }
+    }
+
+    async fn settle_recording(status: StatusCode) -> Option<Settled> {
+        let outcome = Arc::new(StdMutex::new(None));
+        let tx = RecordingTx {
+            outcome: outcome.clone(),
+        };
+
+        settle(tx, status).await.unwrap();
+        let settled = outcome.lock().unwrap().take();
+        settled
+    }
+
+    #[actix_web::test]
+    async fn test_error_response_rolls_back_writes() {
+        assert_eq!(
+            settle_recording(StatusCode::CONFLICT).await,
+            Some(Settled::RolledBack)
+        );
+        assert_eq!(
+            settle_recording(StatusCode::INTERNAL_SERVER_ERROR).await,
+            Some(Settled::RolledBack)
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_success_response_commits_writes() {
+        assert_eq!(
+            settle_recording(StatusCode::CREATED).await,
+            Some(Settled::Committed)
+        );
+    }
+
+    #[test]
+    fn test_only_mutating_methods_open_a_transaction() {
+        assert!(opens_transaction(&Method::POST));
+        assert!(opens_transaction(&Method::DELETE));
+        assert!(!opens_transaction(&Method::GET));
+        assert!(!opens_transaction(&Method::HEAD));
+    }
+
+    /// Handler answering with the status in its path after counting a hook for after commit
+    async fn hooked(
+        unit_of_work: UnitOfWork, path: web::Path<u16>, runs: Data<AtomicUsize>,
+    ) -> HttpResponse {
+        let runs = runs.into_inner();
+        unit_of_work.after_commit(move || {
+            runs.fetch_add(1, Ordering::SeqCst);
+        });
+        HttpResponse::build(StatusCode::from_u16(path.into_inner()).unwrap()).finish()
+    }
+
+    #[actix_web::test]
+    async fn test_after_commit_hooks_only_run_on_success() {
+        let runs = Data::new(AtomicUsize::new(0));
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(create_test_app_data().await))
+                .app_data(runs.clone())
+                .service(
+                    web::resource("/{status}")
+                        .wrap(UnitOfWorkMiddleware)
+                        .route(web::post().to(hooked)),
+                ),
+        )
+        .await;
+
+        call_service(&app, TestRequest::post().uri("/409").to_request()).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+
+        call_service(&app, TestRequest::post().uri("/201").to_request()).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,189 @@
+use crate::jwt::grants_extractor::{ROLE_ADMIN_COORDINATOR, ROLE_ADMIN_PROFESSOR, ROLE_ADMIN_ROOT};
+use crate::models::admin_role::AvailableAdminRole;
+use rand::RngExt;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+
+/// Prefix distinguishing API tokens from JWTs in the admin token header
+pub(crate) const API_TOKEN_PREFIX: &str = "apt_";
+/// Number of random characters following the prefix
+const API_TOKEN_LENGTH: usize = 40;
+
+/// Authorities an API token can be scoped to
+pub(crate) const API_TOKEN_PERMISSIONS: [&str; 3] = [
+    ROLE_ADMIN_ROOT,
+    ROLE_ADMIN_PROFESSOR,
+    ROLE_ADMIN_COORDINATOR,
+];
+
+/// Generates a new random API token value
+pub(crate) fn generate_api_token() -> String {
+    const CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+    let mut rng = rand::rng();
+    let secret: String = (0..API_TOKEN_LENGTH)
+        .map(|_| CHARS[rng.random_range(0..CHARS.len())] as char)
+        .collect();
+
+    format!("{}{}", API_TOKEN_PREFIX, secret)
+}
+
+/// Hash under which an API token is stored and looked up
+pub(crate) fn hash_api_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Whether a value sent in the admin token header is an API token rather than a JWT
+pub(crate) fn is_api_token(token: &str) -> bool {
+    token.starts_with(API_TOKEN_PREFIX)
+}
+
+/// Checks the requested permissions, returning them deduplicated in a stable order
+pub(crate) fn validate_permissions(permissions: &[String]) -> Result<Vec<String>, String> {
+    if permissions.is_empty() {
+        return Err("At least one permission is required".to_string());
+    }
+
+    if let Some(unknown) = permissions
+        .iter()
+        .find(|p| !API_TOKEN_PERMISSIONS.contains(&p.as_str()))
+    {
+        return Err(format!("Unknown permission '{}'", unknown));
+    }
+
+    Ok(API_TOKEN_PERMISSIONS
+        .iter()
+        .filter(|known| permissions.iter().any(|p| p == *known))
+        .map(|p| p.to_string())
+        .collect())
+}
+
+/// Permissions of a token that the current role of its admin still allows
+///
+/// Checked on every request rather than at issue time, so the tokens of an admin who is
+/// demoted lose what the new role no longer grants. A role allows its own authority and
+/// the ones below it.
+pub(crate) fn permissions_within_role(
+    permissions: Vec<String>, role: AvailableAdminRole,
+) -> HashSet<String> {
+    let allowed = match role {
+        AvailableAdminRole::Root => &API_TOKEN_PERMISSIONS[..],
+        AvailableAdminRole::Professor => &API_TOKEN_PERMISSIONS[1..],
+        AvailableAdminRole::Coordinator => &API_TOKEN_PERMISSIONS[2..],
+    };
+
+    permissions
+        .into_iter()
+        .filter(|p| allowed.contains(&p.as_str()))
+        .collect()
+}
+
+/// Role a token acts with when data is scoped to the caller
+///
+/// The highest role among the permissions of the token, so a token scoped below the role
+/// of its admin only sees what an admin of that scope would. A token left without any
+/// permission acts as a coordinator, the narrowest scope.
+pub(crate) fn scoped_role(permissions: &HashSet<String>) -> AvailableAdminRole {
+    if permissions.contains(ROLE_ADMIN_ROOT) {
+        AvailableAdminRole::Root
+    } else if permissions.contains(ROLE_ADMIN_PROFESSOR) {
+        AvailableAdminRole::Professor
+    } else {
+        AvailableAdminRole::Coordinator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issued_tokens_are_prefixed_and_unique() {
+        let first = generate_api_token();
+        let second = generate_api_token();
+
+        assert!(is_api_token(&first));
+        assert_eq!(first.len(), API_TOKEN_PREFIX.len() + API_TOKEN_LENGTH);
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_hash_is_stable_and_hides_the_token() {
+        let token = generate_api_token();
+
+        assert_eq!(hash_api_token(&token), hash_api_token(&token));
+        assert_ne!(hash_api_token(&token), token);
+        assert_ne!(
+            hash_api_token(&token),
+            hash_api_token(&generate_api_token())
+        );
+    }
+
+    #[test]
+    fn test_jwt_is_not_an_api_token() {
+        assert!(!is_api_token("eyJhbGciOiJIUzI1NiJ9.e30.sig"));
+    }
+
+    #[test]
+    fn test_permissions_are_validated() {
+        let requested = vec![
+            ROLE_ADMIN_PROFESSOR.to_string(),
+            ROLE_ADMIN_ROOT.to_string(),
+            ROLE_ADMIN_PROFESSOR.to_string(),
+        ];
+        assert_eq!(
+            validate_permissions(&requested).unwrap(),
+            vec![
+                ROLE_ADMIN_ROOT.to_string(),
+                ROLE_ADMIN_PROFESSOR.to_string()
+            ]
+        );
+
+        assert!(validate_permissions(&[]).is_err());
+        assert!(validate_permissions(&["ROLE_STUDENT".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_demoted_admin_token_loses_the_higher_permissions() {
+        let permissions = || {
+            vec![
+                ROLE_ADMIN_PROFESSOR.to_string(),
+                ROLE_ADMIN_COORDINATOR.to_string(),
+            ]
+        };
+
+        assert_eq!(
+            permissions_within_role(permissions(), AvailableAdminRole::Root),
+            permissions().into_iter().collect()
+        );
+        assert_eq!(
+            permissions_within_role(permissions(), AvailableAdminRole::Coordinator),
+            HashSet::from([ROLE_ADMIN_COORDINATOR.to_string()])
+        );
+        assert!(permissions_within_role(
+            vec![ROLE_ADMIN_ROOT.to_string()],
+            AvailableAdminRole::Professor
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_scoped_role_is_the_highest_permission() {
+        let role = |permissions: &[&str]| {
+            scoped_role(&permissions.iter().map(|p| p.to_string()).collect())
+        };
+
+        assert_eq!(
+            role(&[ROLE_ADMIN_PROFESSOR, ROLE_ADMIN_ROOT]),
+            AvailableAdminRole::Root
+        );
+        assert_eq!(
+            role(&[ROLE_ADMIN_COORDINATOR, ROLE_ADMIN_PROFESSOR]),
+            AvailableAdminRole::Professor
+        );
+        assert_eq!(
+            role(&[ROLE_ADMIN_COORDINATOR]),
+            AvailableAdminRole::Coordinator
+        );
+        assert_eq!(role(&[]), AvailableAdminRole::Coordinator);
+    }
+}
@@ -1,13 +1,17 @@
 use crate::app_data::AppData;
-use crate::common::json_error::ToJsonError;
-use crate::jwt::token::decode_token;
+use crate::common::json_error::{JsonError, ToJsonError};
+use crate::database::repositories::{
+    admin_api_tokens_repository, impersonation_sessions_repository,
+};
+use crate::jwt::api_token::{hash_api_token, is_api_token, permissions_within_role, scoped_role};
+use crate::jwt::token::{decode_token, Token, TokenKeys};
 use crate::models::admin::Admin;
 use crate::models::admin_role::AvailableAdminRole;
 use crate::models::student::Student;
 use actix_web::dev::ServiceRequest;
 use actix_web::http::StatusCode;
 use actix_web::{web, Error, HttpMessage};
-use log::{error, warn};
+use log::{error, info, warn};
 use std::collections::HashSet;
 use welds::state::DbState;
 
@@ -20,6 +24,8 @@ pub(crate) const ROLE_ADMIN_PROFESSOR: &str = "ROLE_ADMIN_PROFESSOR";
 pub(crate) const ROLE_ADMIN_COORDINATOR: &str = "ROLE_ADMIN_COORDINATOR";
 pub(crate) const ROLE_STUDENT: &str = "ROLE_STUDENT";
 
+const INVALID_TOKEN: &str = "Invalid token";
+
 /// Extracts authorities from the request for actix-web-grants.
 /// This function:
 /// 1. Extracts JWT token from request headers
@@ -28,18 +34,17 @@ pub(crate) const ROLE_STUDENT: &str = "ROLE_STUDENT";
 /// 4. Stores the user in request extensions
 /// 5. Returns a HashSet of role authorities
 pub async fn extract(req: &ServiceRequest) -> Result<HashSet<String>, Error> {
-    const INVALID_TOKEN: &str = "Invalid token";
-
-    // Try to extract token from either header
+    // Try to extract token from either header, remembering which one it came from
     let token = req
         .headers()
         .get(ADMIN_HEADER_NAME)
-        .or_else(|| req.headers().get(STUDENT_HEADER_NAME))
-        .and_then(|h| h.to_str().ok())
-        .map(|s| s.to_string());
+        .map(|h| (h, true))
+        .or_else(|| req.headers().get(STUDENT_HEADER_NAME).map(|h| (h, false)))
+        .and_then(|(h, from_admin_header)| Some((h.to_str().ok()?, from_admin_header)))
+        .map(|(s, from_admin_header)| (s.to_string(), from_admin_header));
 
     // Return empty authorities if no token found (public endpoints)
-    let Some(token) = token else {
+    let Some((token, from_admin_header)) = token else {
         return Ok(HashSet::new());
     };
 
@@ -53,9 +58,21 @@ pub async fn extract(req: &ServiceRequest) -> Result<HashSet<String>, Error> {
                 .into()
         })?;
 
+    // Long-lived API tokens carry their own permissions, and are only accepted as admin tokens
+    if from_admin_header && is_api_token(&token) {
+        return extract_api_token(req, app_state, &token).await;
+    }
+
+    // Admin tokens are only verified with the admin keys and student tokens with the student ones
+    let keys = if from_admin_header {
+        TokenKeys::admin(&app_state.config)
+    } else {
+        TokenKeys::student(&app_state.config)
+    };
+
     // Decode token
     let decoded_token =
-        decode_token(token, app_state.config.jwt_secret().as_bytes()).map_err(|e| -> Error {
+        decode_token(token, &keys, app_state.clock.now()).map_err(|e| -> Error {
             warn!("unable to decode jwt token: {}", e);
             INVALID_TOKEN.to_json_error(StatusCode::UNAUTHORIZED).into()
         })?;
@@ -63,20 +80,6 @@ pub async fn extract(req: &ServiceRequest) -> Result<HashSet<String>, Error> {
     let mut authorities = HashSet::new();
 
     if decoded_token.adm {
-        // Admin processing
-        let role: AvailableAdminRole = decoded_token.rl.try_into().map_err(|_| {
-            warn!("invalid admin role in token: {}", decoded_token.rl);
-            INVALID_TOKEN.to_json_error(StatusCode::UNAUTHORIZED)
-        })?;
-
-        // Add role-specific authority
-        let authority = match role {
-            AvailableAdminRole::Root => ROLE_ADMIN_ROOT,
-            AvailableAdminRole::Professor => ROLE_ADMIN_PROFESSOR,
-            AvailableAdminRole::Coordinator => ROLE_ADMIN_COORDINATOR,
-        };
-        authorities.insert(authority.to_string());
-
         // Load admin from database
         let admin = Admin::where_col(|a| a.admin_id.equal(decoded_token.sub))
             .run(&app_state.db)
@@ -94,6 +97,9 @@ pub async fn extract(req: &ServiceRequest) -> Result<HashSet<String>, Error> {
 
         let admin = DbState::into_inner(admin);
 
+        // Add role-specific authority
+        authorities.insert(admin_authority(&decoded_token, &admin)?.to_string());
+
         // Store admin in request extensions
         req.extensions_mut().insert::<Admin>(admin);
     } else {
@@ -117,9 +123,226 @@ pub async fn extract(req: &ServiceRequest) -> Result<HashSet<String>, Error> {
 
         let student = DbState::into_inner(student);
 
+        if decoded_token.is_revoked(student.sessions_revoked_at) {
+            warn!(
+                "request with a token of student {} issued before its sessions were revoked",
+                student.student_id
+            );
+            return Err(INVALID_TOKEN.to_json_error(StatusCode::UNAUTHORIZED).into());
+        }
+
+        if let Some(session_id) = decoded_token.imp {
+            check_impersonation(req, app_state, session_id, &student).await?;
+        }
+
         // Store student in request extensions
         req.extensions_mut().insert::<Student>(student);
     }
 
     Ok(authorities)
 }
+
+/// Authority of an admin, from its current role rather than the one its token was issued with
+///
+/// An admin whose role changed gets the authority of the new role on the next request
+/// instead of keeping the old one until the token expires.
+fn admin_authority(token: &Token, admin: &Admin) -> Result<&'static str, JsonError> {
+    let role: AvailableAdminRole = admin.admin_role_id.try_into().map_err(|_| {
+        warn!(
+            "admin {} with invalid role {}",
+            admin.admin_id, admin.admin_role_id
+        );
+        INVALID_TOKEN.to_json_error(StatusCode::UNAUTHORIZED)
+    })?;
+    if token.rl != admin.admin_role_id {
+        info!(
+            "token of admin {} issued with role {}, using the current role {}",
+            admin.admin_id, token.rl, admin.admin_role_id
+        );
+    }
+
+    Ok(match role {
+        AvailableAdminRole::Root => ROLE_ADMIN_ROOT,
+        AvailableAdminRole::Professor => ROLE_ADMIN_PROFESSOR,
+        AvailableAdminRole::Coordinator => ROLE_ADMIN_COORDINATOR,
+    })
+}
+
+/// Validates the impersonation session of a student token and logs the request it is used for
+async fn check_impersonation(
+    req: &ServiceRequest, app_state: &web::Data<AppData>, session_id: i32, student: &Student,
+) -> Result<(), Error> {
+    let session = impersonation_sessions_repository::get_by_id(&app_state.db, session_id)
+        .await
+        .map_err(|e| {
+            error!("unable to fetch impersonation session from database: {}", e);
+            "unable to fetch impersonation session from database"
+                .to_json_error(StatusCode::INTERNAL_SERVER_ERROR)
+        })?
+        .map(DbState::into_inner)
+        .filter(|s| s.student_id == student.student_id && s.is_active(app_state.clock.now()))
+        .ok_or_else(|| {
+            warn!(
+                "request with ended or unknown impersonation session {}",
+                session_id
+            );
+            INVALID_TOKEN.to_json_error(StatusCode::UNAUTHORIZED)
+        })?;
+
+    info!(
+        "admin {} impersonating student {} (session {}) from {}: {} {}",
+        session.admin_id,
+        student.student_id,
+        session_id,
+        app_state
+            .trusted_proxies
+            .service_client_ip(req)
+            .map_or_else(|| "unknown address".to_string(), |ip| ip.to_string()),
+        req.method(),
+        req.path()
+    );
+
+    Ok(())
+}
+
+/// Authenticates an admin API token, returning the permissions it was scoped to
+///
+/// Permissions above the current role of the admin are dropped, see
+/// [`permissions_within_role`].
+async fn extract_api_token(
+    req: &ServiceRequest, app_state: &web::Data<AppData>, token: &str,
+) -> Result<HashSet<String>, Error> {
+    let api_token = admin_api_tokens_repository::get_by_hash(&app_state.db, &hash_api_token(token))
+        .await
+        .map_err(|e| {
+            error!("unable to fetch api token from database: {}", e);
+            "unable to fetch api token from database"
+                .to_json_error(StatusCode::INTERNAL_SERVER_ERROR)
+        })?
+        .map(DbState::into_inner)
+        .filter(|t| t.is_active())
+        .ok_or_else(|| {
+            warn!("request with unknown or revoked api token");
+            INVALID_TOKEN.to_json_error(StatusCode::UNAUTHORIZED)
+        })?;
+
+    let admin = Admin::where_col(|a| a.admin_id.equal(api_token.admin_id))
+        .run(&app_state.db)
+        .await
+        .map_err(|e| {
+            error!("unable to fetch admin from database: {}", e);
+            "unable to fetch admin from database".to_json_error(StatusCode::INTERNAL_SERVER_ERROR)
+        })?
+        .pop()
+        .ok_or_else(|| {
+            warn!(
+                "api token {} of a non-existing admin",
+                api_token.api_token_id
+            );
+            INVALID_TOKEN.to_json_error(StatusCode::UNAUTHORIZED)
+        })?;
+
+    let admin = DbState::into_inner(admin);
+    let role: AvailableAdminRole = admin.admin_role_id.try_into().map_err(|_| {
+        warn!(
+            "api token {} of admin {} with invalid role {}",
+            api_token.api_token_id, admin.admin_id, admin.admin_role_id
+        );
+        INVALID_TOKEN.to_json_error(StatusCode::UNAUTHORIZED)
+    })?;
+
+    let permissions = permissions_within_role(api_token.permission_list(), role);
+
+    // Data is scoped by the role of the stored admin, which the token may narrow
+    req.extensions_mut()
+        .insert::<Admin>(admin_with_token_scope(admin, &permissions));
+
+    Ok(permissions)
+}
+
+/// The admin owning an API token, acting with the scope of the token rather than its own role
+fn admin_with_token_scope(admin: Admin, permissions: &HashSet<String>) -> Admin {
+    Admin {
+        admin_role_id: scoped_role(permissions) as i32,
+        ..admin
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::access::admin_can_see_project;
+    use crate::jwt::token::create_admin_token;
+    use crate::test_utils::{create_test_app_data, RecordingClient};
+    use crate::test_utils::{TEST_ADMIN_ID, TEST_ADMIN_JWT_KEYS, TEST_JWT_VALIDITY_SECONDS};
+    use actix_web::test::TestRequest;
+    use chrono::Utc;
+
+    fn admin(role: AvailableAdminRole) -> Admin {
+        Admin {
+            admin_id: TEST_ADMIN_ID,
+            first_name: "Test".to_string(),
+            last_name: "Admin".to_string(),
+            email: "admin@test.com".to_string(),
+            password_hash: String::new(),
+            admin_role_id: role as i32,
+        }
+    }
+
+    #[test]
+    fn test_token_issued_before_a_demotion_loses_root() {
+        let now = Utc::now();
+        let token = create_admin_token(
+            TEST_ADMIN_ID,
+            AvailableAdminRole::Root as i32,
+            &TEST_ADMIN_JWT_KEYS,
+            TEST_JWT_VALIDITY_SECONDS,
+            now,
+        )
+        .unwrap();
+        let token = decode_token(&token, &TEST_ADMIN_JWT_KEYS, now).unwrap();
+
+        assert_eq!(
+            admin_authority(&token, &admin(AvailableAdminRole::Root)).unwrap(),
+            ROLE_ADMIN_ROOT
+        );
+        // Demoted after the token was issued
+        assert_eq!(
+            admin_authority(&token, &admin(AvailableAdminRole::Professor)).unwrap(),
+            ROLE_ADMIN_PROFESSOR
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_api_tokens_are_not_accepted_as_student_tokens() {
+        let req = TestRequest::default()
+            .insert_header((STUDENT_HEADER_NAME, "apt_not-a-student-token"))
+            .app_data(web::Data::new(create_test_app_data().await))
+            .to_srv_request();
+
+        // Rejected as an invalid student jwt, without looking the token up
+        let err = extract(&req).await.unwrap_err();
+        assert_eq!(
+            err.as_response_error().status_code(),
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_root_api_token_scoped_to_coordinator_does_not_see_every_project() {
+        let permissions = permissions_within_role(
+            vec![ROLE_ADMIN_COORDINATOR.to_string()],
+            AvailableAdminRole::Root,
+        );
+        let admin = admin_with_token_scope(admin(AvailableAdminRole::Root), &permissions);
+        assert_eq!(admin.admin_id, TEST_ADMIN_ID);
+        assert_eq!(admin.admin_role_id, AvailableAdminRole::Coordinator as i32);
+
+        // The project is only visible through a coordinator assignment
+        let client = RecordingClient::default();
+        let visible = admin_can_see_project(&client, &admin, 1).await;
+        assert!(!visible.unwrap_or(false));
+        assert_eq!(client.statements().len(), 1);
+        assert!(client.statements()[0].contains("coordinator_projects"));
+    }
+}
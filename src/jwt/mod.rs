@@ -1,3 +1,5 @@
+pub(crate) mod api_token;
 pub(crate) mod get_user;
 pub(crate) mod grants_extractor;
+pub(crate) mod permissions;
 pub(crate) mod token;
@@ -0,0 +1,81 @@
+use crate::jwt::grants_extractor::{ROLE_ADMIN_COORDINATOR, ROLE_ADMIN_PROFESSOR, ROLE_ADMIN_ROOT};
+use std::collections::HashSet;
+
+const ALL_ADMINS: &[&str] = &[
+    ROLE_ADMIN_ROOT,
+    ROLE_ADMIN_PROFESSOR,
+    ROLE_ADMIN_COORDINATOR,
+];
+const ROOT_AND_PROFESSORS: &[&str] = &[ROLE_ADMIN_ROOT, ROLE_ADMIN_PROFESSOR];
+const ROOT_ONLY: &[&str] = &[ROLE_ADMIN_ROOT];
+
+/// Admin capabilities exposed to clients and the authorities granting them
+///
+/// Mirrors the `protect` attributes of the admin endpoints, keep the two in sync
+/// when an endpoint changes the roles it accepts.
+const CAPABILITIES: &[(&str, &[&str])] = &[
+    ("projects:read", ALL_ADMINS),
+    ("projects:manage", ROOT_AND_PROFESSORS),
+    ("deliverables:manage", ROOT_AND_PROFESSORS),
+    ("coordinators:manage", ROOT_AND_PROFESSORS),
+    ("groups:read", ALL_ADMINS),
+    ("groups:manage_members", ALL_ADMINS),
+    ("complaints:read", ALL_ADMINS),
+    ("complaints:manage", ROOT_AND_PROFESSORS),
+    ("security_codes:manage", ALL_ADMINS),
+    ("security_codes:rotate", ROOT_AND_PROFESSORS),
+    ("fairs:read", ALL_ADMINS),
+    ("fairs:manage", ROOT_AND_PROFESSORS),
+    ("transactions:read", ROOT_AND_PROFESSORS),
+    ("transactions:reverse", ROOT_AND_PROFESSORS),
+    ("uploads:read", ROOT_AND_PROFESSORS),
+    ("oral_exam:manage", ROOT_AND_PROFESSORS),
+    ("blacklist:manage", ROOT_AND_PROFESSORS),
+    ("admins:manage", ROOT_AND_PROFESSORS),
+    ("api_tokens:manage", ROOT_ONLY),
+    ("students:impersonate", ROOT_ONLY),
+    ("email:test", ROOT_ONLY),
+];
+
+/// Capabilities granted by a set of authorities, in a stable order
+///
+/// The authorities are the ones resolved for the request, so the result already
+/// reflects the scoping of API tokens.
+pub(crate) fn effective_permissions(authorities: &HashSet<String>) -> Vec<&'static str> {
+    CAPABILITIES
+        .iter()
+        .filter(|(_, granted_by)| granted_by.iter().any(|a| authorities.contains(*a)))
+        .map(|(capability, _)| *capability)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn authorities(roles: &[&str]) -> HashSet<String> {
+        roles.iter().map(|r| r.to_string()).collect()
+    }
+
+    #[test]
+    fn test_coordinator_permissions_differ_from_professor() {
+        let coordinator = effective_permissions(&authorities(&[ROLE_ADMIN_COORDINATOR]));
+        let professor = effective_permissions(&authorities(&[ROLE_ADMIN_PROFESSOR]));
+
+        assert_ne!(coordinator, professor);
+        assert!(coordinator.contains(&"groups:manage_members"));
+        assert!(!coordinator.contains(&"projects:manage"));
+        assert!(professor.contains(&"projects:manage"));
+        assert!(!professor.contains(&"students:impersonate"));
+        // Everything a coordinator can do, a professor can do as well
+        assert!(coordinator.iter().all(|p| professor.contains(p)));
+    }
+
+    #[test]
+    fn test_root_has_every_permission_and_students_none() {
+        let root = effective_permissions(&authorities(&[ROLE_ADMIN_ROOT]));
+
+        assert_eq!(root.len(), CAPABILITIES.len());
+        assert!(effective_permissions(&authorities(&["ROLE_STUDENT"])).is_empty());
+    }
+}
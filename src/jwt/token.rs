@@ -1,8 +1,12 @@
+use crate::config::Config;
 use actix_web::{error, Error};
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
 
+/// Seconds a token is still accepted after its expiry, to absorb clock skew between servers
+const EXPIRY_LEEWAY_SECONDS: i64 = 60;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(super) struct Token {
     pub(super) sub: i32,
@@ -10,16 +14,63 @@ pub(super) struct Token {
     pub(super) adm: bool,
     pub(super) rl: i32,
     pub(super) exp: usize,
+    /// Impersonation session the student token was issued for, absent on regular tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) imp: Option<i32>,
+    pub(super) iss: String,
+    pub(super) aud: String,
+}
+
+impl Token {
+    /// Whether the token was issued before the sessions of its user were revoked
+    ///
+    /// Issue times have second precision, so a token issued in the second of the revocation
+    /// is revoked too.
+    pub(super) fn is_revoked(&self, sessions_revoked_at: Option<DateTime<Utc>>) -> bool {
+        sessions_revoked_at.is_some_and(|revoked_at| self.iat as i64 <= revoked_at.timestamp())
+    }
+}
+
+/// Secret and claims identifying the deployment and the kind of user a token belongs to
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TokenKeys<'a> {
+    pub(crate) secret: &'a [u8],
+    pub(crate) issuer: &'a str,
+    pub(crate) audience: &'a str,
+    /// Whether the keys sign admin tokens, tokens of the other kind are rejected
+    pub(crate) admin: bool,
+}
+
+impl<'a> TokenKeys<'a> {
+    /// Keys of admin tokens
+    pub(crate) fn admin(config: &'a Config) -> Self {
+        Self {
+            secret: config.admin_jwt_secret().as_bytes(),
+            issuer: config.jwt_issuer(),
+            audience: config.jwt_audience(),
+            admin: true,
+        }
+    }
+
+    /// Keys of student tokens, impersonation tokens included
+    pub(crate) fn student(config: &'a Config) -> Self {
+        Self {
+            secret: config.student_jwt_secret().as_bytes(),
+            issuer: config.jwt_issuer(),
+            audience: config.jwt_audience(),
+            admin: false,
+        }
+    }
 }
 
 fn create_token(
-    user_id: i32, is_admin: bool, admin_role: i32, secret: &[u8], expires_in_seconds: i64,
+    user_id: i32, is_admin: bool, admin_role: i32, keys: &TokenKeys, expires_in_seconds: i64,
+    impersonation_session_id: Option<i32>, now: DateTime<Utc>,
 ) -> Result<String, jsonwebtoken::errors::Error> {
     if user_id < 1 {
         return Err(jsonwebtoken::errors::ErrorKind::InvalidSubject.into());
     }
 
-    let now = Utc::now();
     let iat = now.timestamp() as usize;
     let exp = (now + Duration::minutes(expires_in_seconds)).timestamp() as usize;
     let claims: Token = Token {
@@ -28,50 +79,101 @@ fn create_token(
         adm: is_admin,
         exp,
         iat,
+        imp: impersonation_session_id,
+        iss: keys.issuer.to_string(),
+        aud: keys.audience.to_string(),
     };
 
     encode(
         &Header::default(),
         &claims,
-        &EncodingKey::from_secret(secret),
+        &EncodingKey::from_secret(keys.secret),
     )
 }
 #[inline(always)]
 pub(crate) fn create_admin_token(
-    user_id: i32, admin_role_id: i32, secret: &[u8], expires_in_seconds: i64,
+    user_id: i32, admin_role_id: i32, keys: &TokenKeys, expires_in_seconds: i64, now: DateTime<Utc>,
 ) -> Result<String, jsonwebtoken::errors::Error> {
-    create_token(user_id, true, admin_role_id, secret, expires_in_seconds)
+    create_token(
+        user_id,
+        true,
+        admin_role_id,
+        keys,
+        expires_in_seconds,
+        None,
+        now,
+    )
 }
 #[inline(always)]
 pub(crate) fn create_student_token(
-    user_id: i32, secret: &[u8], expires_in_seconds: i64,
+    user_id: i32, keys: &TokenKeys, expires_in_seconds: i64, now: DateTime<Utc>,
+) -> Result<String, jsonwebtoken::errors::Error> {
+    create_token(user_id, false, 0, keys, expires_in_seconds, None, now)
+}
+#[inline(always)]
+pub(crate) fn create_impersonation_token(
+    student_id: i32, impersonation_session_id: i32, keys: &TokenKeys, expires_in_minutes: i64,
+    now: DateTime<Utc>,
 ) -> Result<String, jsonwebtoken::errors::Error> {
-    create_token(user_id, false, 0, secret, expires_in_seconds)
+    create_token(
+        student_id,
+        false,
+        0,
+        keys,
+        expires_in_minutes,
+        Some(impersonation_session_id),
+        now,
+    )
 }
 
-pub(super) fn decode_token<T: Into<String>>(token: T, secret: &[u8]) -> Result<Token, Error> {
-    let decoded = decode::<Token>(
+/// Decodes a token and checks that it has not expired at `now`
+///
+/// Issuer and audience must match `keys`, so a token signed by another deployment with the
+/// same secret is rejected, and so must the kind of user the token was issued to.
+/// Expiry is checked here rather than by `jsonwebtoken`, so it follows the application clock.
+pub(super) fn decode_token<T: Into<String>>(
+    token: T, keys: &TokenKeys, now: DateTime<Utc>,
+) -> Result<Token, Error> {
+    let mut validation = Validation::new(Algorithm::HS256);
+    validation.validate_exp = false;
+    validation.set_required_spec_claims(&["exp", "iss", "aud"]);
+    validation.set_issuer(&[keys.issuer]);
+    validation.set_audience(&[keys.audience]);
+
+    let claims = decode::<Token>(
         &token.into(),
-        &DecodingKey::from_secret(secret),
-        &Validation::new(Algorithm::HS256),
-    );
-    decoded
-        .map_err(|_| error::ErrorUnauthorized("Invalid token"))
-        .map(|token| token.claims)
+        &DecodingKey::from_secret(keys.secret),
+        &validation,
+    )
+    .map_err(|_| error::ErrorUnauthorized("Invalid token"))?
+    .claims;
+
+    if claims.adm != keys.admin {
+        return Err(error::ErrorUnauthorized("Invalid token"));
+    }
+
+    if (claims.exp as i64) < now.timestamp() - EXPIRY_LEEWAY_SECONDS {
+        return Err(error::ErrorUnauthorized("Invalid token"));
+    }
+
+    Ok(claims)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app_data::clock::{Clock, MockClock};
     use crate::test_utils::*;
+    use chrono::TimeZone;
 
     #[test]
     fn test_create_admin_token_success() {
         let result = create_admin_token(
             TEST_ADMIN_ID,
             TEST_ADMIN_ROLE_ID,
-            TEST_JWT_SECRET,
+            &TEST_ADMIN_JWT_KEYS,
             TEST_JWT_VALIDITY_SECONDS,
+            Utc::now(),
         );
 
         assert!(result.is_ok());
@@ -79,7 +181,7 @@ mod tests {
         assert!(!token.is_empty());
 
         // Verify token can be decoded
-        let decoded = decode_token(&token, TEST_JWT_SECRET);
+        let decoded = decode_token(&token, &TEST_ADMIN_JWT_KEYS, Utc::now());
         assert!(decoded.is_ok());
         let claims = decoded.unwrap();
         assert_eq!(claims.sub, TEST_ADMIN_ID);
@@ -89,15 +191,19 @@ mod tests {
 
     #[test]
     fn test_create_student_token_success() {
-        let result =
-            create_student_token(TEST_STUDENT_ID, TEST_JWT_SECRET, TEST_JWT_VALIDITY_SECONDS);
+        let result = create_student_token(
+            TEST_STUDENT_ID,
+            &TEST_STUDENT_JWT_KEYS,
+            TEST_JWT_VALIDITY_SECONDS,
+            Utc::now(),
+        );
 
         assert!(result.is_ok());
         let token = result.unwrap();
         assert!(!token.is_empty());
 
         // Verify token can be decoded
-        let decoded = decode_token(&token, TEST_JWT_SECRET);
+        let decoded = decode_token(&token, &TEST_STUDENT_JWT_KEYS, Utc::now());
         assert!(decoded.is_ok());
         let claims = decoded.unwrap();
         assert_eq!(claims.sub, TEST_STUDENT_ID);
@@ -110,8 +216,9 @@ mod tests {
         let result = create_admin_token(
             0, // Invalid user ID
             TEST_ADMIN_ROLE_ID,
-            TEST_JWT_SECRET,
+            &TEST_ADMIN_JWT_KEYS,
             TEST_JWT_VALIDITY_SECONDS,
+            Utc::now(),
         );
 
         assert!(result.is_err());
@@ -122,8 +229,9 @@ mod tests {
         let result = create_admin_token(
             -1, // Invalid user ID
             TEST_ADMIN_ROLE_ID,
-            TEST_JWT_SECRET,
+            &TEST_ADMIN_JWT_KEYS,
             TEST_JWT_VALIDITY_SECONDS,
+            Utc::now(),
         );
 
         assert!(result.is_err());
@@ -134,12 +242,13 @@ mod tests {
         let token = create_admin_token(
             TEST_ADMIN_ID,
             TEST_ADMIN_ROLE_ID,
-            TEST_JWT_SECRET,
+            &TEST_ADMIN_JWT_KEYS,
             TEST_JWT_VALIDITY_SECONDS,
+            Utc::now(),
         )
         .unwrap();
 
-        let result = decode_token(&token, TEST_JWT_SECRET);
+        let result = decode_token(&token, &TEST_ADMIN_JWT_KEYS, Utc::now());
         assert!(result.is_ok());
 
         let claims = result.unwrap();
@@ -155,26 +264,30 @@ mod tests {
         let token = create_admin_token(
             TEST_ADMIN_ID,
             TEST_ADMIN_ROLE_ID,
-            TEST_JWT_SECRET,
+            &TEST_ADMIN_JWT_KEYS,
             TEST_JWT_VALIDITY_SECONDS,
+            Utc::now(),
         )
         .unwrap();
 
-        let wrong_secret = b"wrong-secret-key-for-jwt-tokens-32-chars";
-        let result = decode_token(&token, wrong_secret);
+        let wrong_keys = TokenKeys {
+            secret: b"wrong-secret-key-for-jwt-tokens-32-chars",
+            ..TEST_ADMIN_JWT_KEYS
+        };
+        let result = decode_token(&token, &wrong_keys, Utc::now());
         assert!(result.is_err());
     }
 
     #[test]
     fn test_decode_token_malformed() {
         let malformed_token = "not.a.valid.jwt.token";
-        let result = decode_token(malformed_token, TEST_JWT_SECRET);
+        let result = decode_token(malformed_token, &TEST_STUDENT_JWT_KEYS, Utc::now());
         assert!(result.is_err());
     }
 
     #[test]
     fn test_decode_token_empty() {
-        let result = decode_token("", TEST_JWT_SECRET);
+        let result = decode_token("", &TEST_STUDENT_JWT_KEYS, Utc::now());
         assert!(result.is_err());
     }
 
@@ -184,12 +297,13 @@ mod tests {
         let token = create_admin_token(
             TEST_ADMIN_ID,
             TEST_ADMIN_ROLE_ID,
-            TEST_JWT_SECRET,
+            &TEST_ADMIN_JWT_KEYS,
             60, // 1 minute
+            now,
         )
         .unwrap();
 
-        let claims = decode_token(&token, TEST_JWT_SECRET).unwrap();
+        let claims = decode_token(&token, &TEST_ADMIN_JWT_KEYS, Utc::now()).unwrap();
 
         // Check that expiration is approximately 1 minute from now
         let expected_exp = (now + Duration::minutes(60)).timestamp() as usize;
@@ -205,13 +319,14 @@ mod tests {
         let token = create_admin_token(
             TEST_ADMIN_ID,
             TEST_ADMIN_ROLE_ID,
-            TEST_JWT_SECRET,
+            &TEST_ADMIN_JWT_KEYS,
             TEST_JWT_VALIDITY_SECONDS,
+            Utc::now(),
         )
         .unwrap();
         let after_creation = Utc::now().timestamp() as usize;
 
-        let claims = decode_token(&token, TEST_JWT_SECRET).unwrap();
+        let claims = decode_token(&token, &TEST_ADMIN_JWT_KEYS, Utc::now()).unwrap();
 
         // IAT should be between before and after creation
         assert!(claims.iat >= before_creation);
@@ -220,28 +335,204 @@ mod tests {
 
     #[test]
     fn test_student_token_has_zero_role() {
+        let token = create_student_token(
+            TEST_STUDENT_ID,
+            &TEST_STUDENT_JWT_KEYS,
+            TEST_JWT_VALIDITY_SECONDS,
+            Utc::now(),
+        )
+        .unwrap();
+
+        let claims = decode_token(&token, &TEST_STUDENT_JWT_KEYS, Utc::now()).unwrap();
+        assert_eq!(claims.rl, 0);
+        assert!(!claims.adm);
+    }
+
+    #[test]
+    fn test_impersonation_token_carries_session() {
         let token =
-            create_student_token(TEST_STUDENT_ID, TEST_JWT_SECRET, TEST_JWT_VALIDITY_SECONDS)
+            create_impersonation_token(TEST_STUDENT_ID, 42, &TEST_STUDENT_JWT_KEYS, 15, Utc::now())
                 .unwrap();
 
-        let claims = decode_token(&token, TEST_JWT_SECRET).unwrap();
-        assert_eq!(claims.rl, 0);
+        let claims = decode_token(&token, &TEST_STUDENT_JWT_KEYS, Utc::now()).unwrap();
+        assert_eq!(claims.sub, TEST_STUDENT_ID);
+        assert_eq!(claims.imp, Some(42));
         assert!(!claims.adm);
     }
 
+    #[test]
+    fn test_regular_tokens_are_not_impersonations() {
+        let token = create_student_token(
+            TEST_STUDENT_ID,
+            &TEST_STUDENT_JWT_KEYS,
+            TEST_JWT_VALIDITY_SECONDS,
+            Utc::now(),
+        )
+        .unwrap();
+
+        let claims = decode_token(&token, &TEST_STUDENT_JWT_KEYS, Utc::now()).unwrap();
+        assert_eq!(claims.imp, None);
+    }
+
     #[test]
     fn test_admin_token_has_correct_role() {
         let role_id = 2; // Different role
         let token = create_admin_token(
             TEST_ADMIN_ID,
             role_id,
-            TEST_JWT_SECRET,
+            &TEST_ADMIN_JWT_KEYS,
             TEST_JWT_VALIDITY_SECONDS,
+            Utc::now(),
         )
         .unwrap();
 
-        let claims = decode_token(&token, TEST_JWT_SECRET).unwrap();
+        let claims = decode_token(&token, &TEST_ADMIN_JWT_KEYS, Utc::now()).unwrap();
         assert_eq!(claims.rl, role_id);
         assert!(claims.adm);
     }
+
+    #[test]
+    fn test_token_is_rejected_just_after_expiry() {
+        let clock = MockClock::new(Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap());
+        let token =
+            create_student_token(TEST_STUDENT_ID, &TEST_STUDENT_JWT_KEYS, 1, clock.now()).unwrap();
+        let exp = decode_token(&token, &TEST_STUDENT_JWT_KEYS, clock.now())
+            .unwrap()
+            .exp as i64;
+
+        clock.set(Utc.timestamp_opt(exp + EXPIRY_LEEWAY_SECONDS, 0).unwrap());
+        assert!(decode_token(&token, &TEST_STUDENT_JWT_KEYS, clock.now()).is_ok());
+
+        clock.advance(Duration::seconds(1));
+        assert!(decode_token(&token, &TEST_STUDENT_JWT_KEYS, clock.now()).is_err());
+    }
+
+    #[test]
+    fn test_expiry_follows_the_given_clock() {
+        // A token issued long ago by the clock is already expired, regardless of the wall clock
+        let issued_at = Utc::now() - Duration::days(30);
+        let token = create_admin_token(
+            TEST_ADMIN_ID,
+            TEST_ADMIN_ROLE_ID,
+            &TEST_ADMIN_JWT_KEYS,
+            TEST_JWT_VALIDITY_SECONDS,
+            issued_at,
+        )
+        .unwrap();
+
+        assert!(decode_token(&token, &TEST_ADMIN_JWT_KEYS, issued_at).is_ok());
+        assert!(decode_token(&token, &TEST_ADMIN_JWT_KEYS, Utc::now()).is_err());
+    }
+
+    #[test]
+    fn test_token_for_another_audience_is_rejected() {
+        let token = create_student_token(
+            TEST_STUDENT_ID,
+            &TEST_STUDENT_JWT_KEYS,
+            TEST_JWT_VALIDITY_SECONDS,
+            Utc::now(),
+        )
+        .unwrap();
+
+        let other_deployment = TokenKeys {
+            audience: "another-deployment",
+            ..TEST_STUDENT_JWT_KEYS
+        };
+        assert!(decode_token(&token, &other_deployment, Utc::now()).is_err());
+
+        let claims = decode_token(&token, &TEST_STUDENT_JWT_KEYS, Utc::now()).unwrap();
+        assert_eq!(claims.aud, TEST_STUDENT_JWT_KEYS.audience);
+        assert_eq!(claims.iss, TEST_STUDENT_JWT_KEYS.issuer);
+    }
+
+    #[test]
+    fn test_token_from_another_issuer_is_rejected() {
+        let other_issuer = TokenKeys {
+            issuer: "another-backend",
+            ..TEST_ADMIN_JWT_KEYS
+        };
+        let token = create_admin_token(
+            TEST_ADMIN_ID,
+            TEST_ADMIN_ROLE_ID,
+            &other_issuer,
+            TEST_JWT_VALIDITY_SECONDS,
+            Utc::now(),
+        )
+        .unwrap();
+
+        assert!(decode_token(&token, &TEST_ADMIN_JWT_KEYS, Utc::now()).is_err());
+    }
+
+    #[test]
+    fn test_admin_token_fails_student_verification() {
+        let token = create_admin_token(
+            TEST_ADMIN_ID,
+            TEST_ADMIN_ROLE_ID,
+            &TEST_ADMIN_JWT_KEYS,
+            TEST_JWT_VALIDITY_SECONDS,
+            Utc::now(),
+        )
+        .unwrap();
+
+        assert!(decode_token(&token, &TEST_STUDENT_JWT_KEYS, Utc::now()).is_err());
+        assert!(decode_token(&token, &TEST_ADMIN_JWT_KEYS, Utc::now()).is_ok());
+    }
+
+    #[test]
+    fn test_student_token_fails_admin_verification() {
+        let token = create_student_token(
+            TEST_STUDENT_ID,
+            &TEST_STUDENT_JWT_KEYS,
+            TEST_JWT_VALIDITY_SECONDS,
+            Utc::now(),
+        )
+        .unwrap();
+
+        assert!(decode_token(&token, &TEST_ADMIN_JWT_KEYS, Utc::now()).is_err());
+        assert!(decode_token(&token, &TEST_STUDENT_JWT_KEYS, Utc::now()).is_ok());
+    }
+
+    #[test]
+    fn test_shared_secret_still_separates_admin_and_student_tokens() {
+        // Deployments falling back to jwt_secret sign both kinds with the same secret
+        let shared_admin_keys = TokenKeys {
+            secret: TEST_STUDENT_JWT_KEYS.secret,
+            ..TEST_ADMIN_JWT_KEYS
+        };
+        let token = create_student_token(
+            TEST_STUDENT_ID,
+            &TEST_STUDENT_JWT_KEYS,
+            TEST_JWT_VALIDITY_SECONDS,
+            Utc::now(),
+        )
+        .unwrap();
+
+        assert!(decode_token(&token, &shared_admin_keys, Utc::now()).is_err());
+    }
+
+    #[test]
+    fn test_tokens_issued_before_the_revocation_are_rejected() {
+        let issued_at = Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+        let revoked_at = issued_at + Duration::minutes(5);
+        let token_of = |now: DateTime<Utc>| {
+            let token = create_student_token(
+                TEST_STUDENT_ID,
+                &TEST_STUDENT_JWT_KEYS,
+                TEST_JWT_VALIDITY_SECONDS,
+                now,
+            )
+            .unwrap();
+            decode_token(&token, &TEST_STUDENT_JWT_KEYS, revoked_at).unwrap()
+        };
+
+        let before = token_of(issued_at);
+        assert!(before.is_revoked(Some(revoked_at)));
+        // Issue times have second precision, the second of the revocation is revoked too
+        let same_second = token_of(revoked_at + Duration::milliseconds(500));
+        assert!(same_second.is_revoked(Some(revoked_at)));
+
+        // Logging in again after the revocation gives a working token
+        let renewed = token_of(revoked_at + Duration::seconds(1));
+        assert!(!renewed.is_revoked(Some(revoked_at)));
+    }
 }
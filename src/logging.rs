@@ -1,11 +1,91 @@
 use chrono::Utc;
 use log::{Level, LevelFilter, Metadata, Record, SetLoggerError};
+use std::sync::RwLock;
 
-struct ConsoleLogger;
+/// Verbosity of every module, parsed from `RUST_LOG`-style directives such as
+/// `warn,backend::database=debug`
+///
+/// A bare level sets the default, `module=level` applies to the module and its children;
+/// the most specific module wins.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LogFilter {
+    default: LevelFilter,
+    modules: Vec<(String, LevelFilter)>,
+}
+
+impl LogFilter {
+    const INFO: LogFilter = LogFilter {
+        default: LevelFilter::Info,
+        modules: Vec::new(),
+    };
+
+    pub(crate) fn parse(spec: &str) -> Result<Self, String> {
+        let mut filter = Self::INFO;
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            let level = |name: &str| {
+                name.trim()
+                    .parse::<LevelFilter>()
+                    .map_err(|_| format!("unknown log level {:?} in {:?}", name, directive))
+            };
+            match directive.split_once('=') {
+                Some((module, name)) if !module.trim().is_empty() => filter
+                    .modules
+                    .push((module.trim().to_string(), level(name)?)),
+                Some(_) => return Err(format!("missing module name in {:?}", directive)),
+                None => filter.default = level(directive)?,
+            }
+        }
+        Ok(filter)
+    }
+
+    fn level_for(&self, target: &str) -> LevelFilter {
+        self.modules
+            .iter()
+            .filter(|(module, _)| {
+                target == module
+                    || target
+                        .strip_prefix(module.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .max_by_key(|(module, _)| module.len())
+            .map_or(self.default, |(_, level)| *level)
+    }
+
+    /// Most verbose level any module can log at
+    fn max_level(&self) -> LevelFilter {
+        self.modules
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.default, Ord::max)
+    }
+}
+
+struct ConsoleLogger {
+    filter: RwLock<LogFilter>,
+    write: fn(String),
+}
+
+fn print_line(line: String) {
+    println!("{}", line);
+}
+
+static LOGGER: ConsoleLogger = ConsoleLogger {
+    filter: RwLock::new(LogFilter::INFO),
+    write: print_line,
+};
+
+impl ConsoleLogger {
+    fn set_filter(&self, filter: LogFilter) -> LevelFilter {
+        let max_level = filter.max_level();
+        *self.filter.write().unwrap_or_else(|e| e.into_inner()) = filter;
+        max_level
+    }
+}
 
 impl log::Log for ConsoleLogger {
     fn enabled(&self, metadata: &Metadata) -> bool {
-        metadata.level() <= Level::Info
+        let filter = self.filter.read().unwrap_or_else(|e| e.into_inner());
+        metadata.level() <= filter.level_for(metadata.target())
     }
 
     fn log(&self, record: &Record) {
@@ -22,20 +102,98 @@ impl log::Log for ConsoleLogger {
             Level::Trace => "\x1b[37mTRACE\x1b[0m",
         };
 
-        println!(
+        (self.write)(format!(
             "[{}] {} [{}] {}",
             timestamp,
             level,
             record.target(),
             record.args()
-        );
+        ));
     }
 
     fn flush(&self) {}
 }
 
+/// Installs the console logger at info level, until the configured filter is applied
 pub(crate) fn init_console_logger() -> Result<(), SetLoggerError> {
-    log::set_boxed_logger(Box::new(ConsoleLogger))?;
+    log::set_logger(&LOGGER)?;
     log::set_max_level(LevelFilter::Info);
     Ok(())
 }
+
+/// Replaces the verbosity of the console logger
+pub(crate) fn apply_log_filter(filter: LogFilter) {
+    log::set_max_level(LOGGER.set_filter(filter));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use log::Log;
+    use std::sync::Mutex;
+
+    static LINES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    fn record_line(line: String) {
+        LINES.lock().unwrap().push(line);
+    }
+
+    fn log_at(logger: &ConsoleLogger, level: Level, target: &str, message: &str) {
+        logger.log(
+            &Record::builder()
+                .level(level)
+                .target(target)
+                .args(format_args!("{}", message))
+                .build(),
+        );
+    }
+
+    #[test]
+    fn test_below_threshold_records_are_filtered_out() {
+        let logger = ConsoleLogger {
+            filter: RwLock::new(LogFilter::parse("warn,backend::database=debug").unwrap()),
+            write: record_line,
+        };
+
+        log_at(&logger, Level::Info, "backend::api", "filtered info");
+        log_at(&logger, Level::Warn, "backend::api", "kept warn");
+        log_at(
+            &logger,
+            Level::Debug,
+            "backend::database::timing",
+            "kept debug",
+        );
+        log_at(
+            &logger,
+            Level::Debug,
+            "backend::databases",
+            "filtered debug",
+        );
+
+        let lines = LINES.lock().unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("kept warn"));
+        assert!(lines[1].ends_with("kept debug"));
+    }
+
+    #[test]
+    fn test_most_specific_module_wins() {
+        let filter = LogFilter::parse("info, actix_web=warn, actix_web::middleware=debug").unwrap();
+
+        assert_eq!(filter.level_for("backend"), LevelFilter::Info);
+        assert_eq!(filter.level_for("actix_web::server"), LevelFilter::Warn);
+        assert_eq!(
+            filter.level_for("actix_web::middleware::logger"),
+            LevelFilter::Debug
+        );
+        assert_eq!(filter.max_level(), LevelFilter::Debug);
+    }
+
+    #[test]
+    fn test_invalid_directives_are_rejected() {
+        assert!(LogFilter::parse("loud").is_err());
+        assert!(LogFilter::parse("backend=loud").is_err());
+        assert!(LogFilter::parse("=debug").is_err());
+        assert_eq!(LogFilter::parse("").unwrap(), LogFilter::INFO);
+    }
+}
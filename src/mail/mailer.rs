@@ -3,6 +3,8 @@ use lettre::message::{
     header::{ContentTransferEncoding, ContentType},
     Mailbox, Message, MultiPart, SinglePart,
 };
+#[cfg(test)]
+use lettre::transport::stub::AsyncStubTransport;
 use lettre::{
     transport::smtp::authentication::Credentials, AsyncSmtpTransport, AsyncTransport,
     Tokio1Executor,
@@ -10,18 +12,41 @@ use lettre::{
 use url::Url;
 use uuid::Uuid;
 
-use super::template::TemplateEngine;
+use super::template::{PreviewError, RenderedEmail, TemplateEngine};
 use crate::config::Config;
 use minijinja::Value as JinjaValue;
+use std::collections::BTreeMap;
 
 type DynError = Box<dyn std::error::Error + Send + Sync + 'static>;
 type Result<T> = std::result::Result<T, DynError>;
 
-const CONFIRMATION_URL: &str = "/confirm";
+const CONFIRMATION_URL: &str = "confirm";
+
+/// Parses the base of the frontend links, as a directory so that joining keeps its path
+///
+/// `Url::join` replaces the last segment of a base without a trailing slash, which would
+/// drop `app` from `https://example.org/app`.
+fn frontend_base(frontend_base_url: &str) -> Result<Url> {
+    let mut url = Url::parse(frontend_base_url)?;
+    if !url.path().ends_with('/') {
+        let path = format!("{}/", url.path());
+        url.set_path(&path);
+    }
+    Ok(url)
+}
+
+/// Where the emails go once built
+#[derive(Clone)]
+enum MailTransport {
+    Smtp(AsyncSmtpTransport<Tokio1Executor>),
+    /// Keeps the emails in memory instead of delivering them
+    #[cfg(test)]
+    Memory(AsyncStubTransport),
+}
 
 #[derive(Clone)]
 pub struct Mailer {
-    transport: AsyncSmtpTransport<Tokio1Executor>,
+    transport: MailTransport,
     from: Mailbox,
     frontend_base_url: Url,
     templates: TemplateEngine,
@@ -45,10 +70,21 @@ impl Mailer {
             config.smtp_use_tls(),
             config.email_from(),
             from_email,
-            config.frontend_base_url(),
+            &config.frontend_base_urls()[0],
         )
     }
 
+    /// Checks that the smtp server accepts connections
+    pub async fn test_connection(&self) -> Result<()> {
+        match &self.transport {
+            MailTransport::Smtp(transport) if transport.test_connection().await? => Ok(()),
+            MailTransport::Smtp(_) => Err("smtp server did not answer the NOOP command".into()),
+            #[cfg(test)]
+            MailTransport::Memory(_) => Ok(()),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         smtp_host: &str, port: u16, username: Option<&str>, password: Option<&str>, use_tls: bool,
         from_name: &str, from_email: &str, frontend_base_url: &str,
@@ -73,10 +109,10 @@ impl Mailer {
         // Set connection timeout (30 seconds) for reliable delivery
         builder = builder.timeout(Some(std::time::Duration::from_secs(30)));
 
-        let transport = builder.build();
+        let transport = MailTransport::Smtp(builder.build());
 
         let from = Mailbox::new(Some(from_name.to_owned()), from_email.parse()?);
-        let frontend_base_url = Url::parse(frontend_base_url)?;
+        let frontend_base_url = frontend_base(frontend_base_url)?;
 
         Ok(Self {
             transport,
@@ -86,10 +122,64 @@ impl Mailer {
         })
     }
 
+    /// Mailer keeping the emails it sends in memory, see `Mailer::sent`
+    #[cfg(test)]
+    pub fn in_memory(from_name: &str, from_email: &str, frontend_base_url: &str) -> Result<Self> {
+        Ok(Self {
+            transport: MailTransport::Memory(AsyncStubTransport::new_ok()),
+            from: Mailbox::new(Some(from_name.to_owned()), from_email.parse()?),
+            frontend_base_url: frontend_base(frontend_base_url)?,
+            templates: TemplateEngine::new()?,
+        })
+    }
+
+    /// Recipients and raw content of the emails sent by an in-memory mailer
+    #[cfg(test)]
+    pub async fn sent(&self) -> Vec<(Vec<String>, String)> {
+        match &self.transport {
+            MailTransport::Memory(transport) => transport
+                .messages()
+                .await
+                .into_iter()
+                .map(|(envelope, raw)| {
+                    let to = envelope.to().iter().map(|a| a.to_string()).collect();
+                    (to, raw)
+                })
+                .collect(),
+            MailTransport::Smtp(_) => Vec::new(),
+        }
+    }
+
+    async fn deliver(&self, email: Message) -> Result<()> {
+        match &self.transport {
+            MailTransport::Smtp(transport) => {
+                transport.send(email).await?;
+            }
+            #[cfg(test)]
+            MailTransport::Memory(transport) => {
+                transport.send(email).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Same mailer with email links pointing to another frontend
+    pub fn with_frontend_base_url(&self, frontend_base_url: &str) -> Result<Self> {
+        Ok(Self {
+            frontend_base_url: frontend_base(frontend_base_url)?,
+            ..self.clone()
+        })
+    }
+
+    /// Link to `path` of the frontend, relative to its base
+    fn frontend_link(&self, path: &str) -> Result<Url> {
+        Ok(self.frontend_base_url.join(path.trim_start_matches('/'))?)
+    }
+
     fn confirmation_link(&self, email: String, key: String) -> Result<Url> {
         let token = generate_token(email, key)?;
 
-        let mut url = self.frontend_base_url.join(CONFIRMATION_URL)?;
+        let mut url = self.frontend_link(CONFIRMATION_URL)?;
         url.query_pairs_mut().append_pair("t", token.as_str());
         Ok(url)
     }
@@ -145,8 +235,7 @@ impl Mailer {
                     ),
             )?;
 
-        self.transport.send(email).await?;
-        Ok(())
+        self.deliver(email).await
     }
 
     pub async fn send_account_confirmation(
@@ -192,7 +281,7 @@ impl Mailer {
     pub async fn send_admin_welcome(
         &self, to_email: String, to_name: String, password: String,
     ) -> Result<()> {
-        let login_url = self.frontend_base_url.join("/admin/login")?.to_string();
+        let login_url = self.frontend_link("admin/login")?.to_string();
 
         let ctx = minijinja::context! {
             user_name => to_name,
@@ -212,6 +301,107 @@ impl Mailer {
         .await
     }
 
+    /// Tells the owner of an account that someone tried to sign up again with their email
+    pub async fn send_existing_account_notice(
+        &self, to_email: String, to_name: String,
+    ) -> Result<()> {
+        let login_url = self.frontend_link("login")?.to_string();
+
+        let ctx = minijinja::context! {
+            user_name => to_name,
+            login_url => login_url,
+        };
+
+        self.send_templated(
+            to_email,
+            to_name,
+            "You already have an account",
+            "existing_account.html",
+            "existing_account.txt",
+            ctx,
+        )
+        .await
+    }
+
+    /// Tells someone signing up that the university ID they entered belongs to another account
+    pub async fn send_university_id_taken_notice(
+        &self, to_email: String, to_name: String,
+    ) -> Result<()> {
+        let login_url = self.frontend_link("login")?.to_string();
+
+        let ctx = minijinja::context! {
+            user_name => to_name,
+            login_url => login_url,
+        };
+
+        self.send_templated(
+            to_email,
+            to_name,
+            "Your university ID already has an account",
+            "university_id_taken.html",
+            "university_id_taken.txt",
+            ctx,
+        )
+        .await
+    }
+
+    /// Reminds a student of something still missing in a project
+    pub async fn send_project_nudge(
+        &self, to_email: String, to_name: String, project_name: &str, reminder: &str,
+    ) -> Result<()> {
+        let login_url = self.frontend_link("login")?.to_string();
+
+        let ctx = minijinja::context! {
+            user_name => to_name,
+            project_name => project_name,
+            reminder => reminder,
+            url => login_url,
+        };
+
+        self.send_templated(
+            to_email,
+            to_name,
+            &format!("Reminder: {}", project_name),
+            "project_nudge.html",
+            "project_nudge.txt",
+            ctx,
+        )
+        .await
+    }
+
+    /// Tells a student that an admin changed their group, with the reason given if any
+    pub async fn send_group_change_notice(
+        &self, to_email: String, to_name: String, group_name: &str, change: &str,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        let login_url = self.frontend_link("login")?.to_string();
+
+        let ctx = minijinja::context! {
+            user_name => to_name,
+            group_name => group_name,
+            change => change,
+            reason => reason,
+            url => login_url,
+        };
+
+        self.send_templated(
+            to_email,
+            to_name,
+            &format!("Your group {} changed", group_name),
+            "group_change.html",
+            "group_change.txt",
+            ctx,
+        )
+        .await
+    }
+
+    /// Render a template with a sample context, see `TemplateEngine::preview`
+    pub fn preview(
+        &self, template: &str, sample: &BTreeMap<String, String>,
+    ) -> std::result::Result<RenderedEmail, PreviewError> {
+        self.templates.preview(template, sample)
+    }
+
     /// Send a simple test email without templates
     /// This is useful for testing SMTP configuration
     pub async fn send_test_email(
@@ -235,8 +425,7 @@ impl Mailer {
                     .body(body),
             )?;
 
-        self.transport.send(email).await?;
-        Ok(())
+        self.deliver(email).await
     }
 }
 
@@ -264,6 +453,34 @@ mod tests {
         assert!(query_pairs.contains_key("t"));
     }
 
+    #[test]
+    fn test_links_keep_the_path_of_the_frontend_base() {
+        for base in ["https://example.org/app", "https://example.org/app/"] {
+            let mailer = create_test_mailer()
+                .unwrap()
+                .with_frontend_base_url(base)
+                .unwrap();
+
+            let url = mailer
+                .confirmation_link(TEST_STUDENT_EMAIL.to_string(), "key".to_string())
+                .unwrap();
+            assert_eq!(url.path(), "/app/confirm", "{}", base);
+            assert_eq!(
+                mailer.frontend_link("/admin/login").unwrap().as_str(),
+                "https://example.org/app/admin/login"
+            );
+        }
+
+        let mailer = create_test_mailer()
+            .unwrap()
+            .with_frontend_base_url("https://example.org")
+            .unwrap();
+        assert_eq!(
+            mailer.frontend_link("login").unwrap().as_str(),
+            "https://example.org/login"
+        );
+    }
+
     #[test]
     fn test_confirmation_link_with_different_emails() {
         let mailer = create_test_mailer().unwrap();
@@ -2,3 +2,4 @@ mod mailer;
 mod template;
 
 pub use mailer::Mailer;
+pub use template::PreviewError;
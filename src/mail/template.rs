@@ -1,4 +1,7 @@
 use minijinja::{Environment, Value as JinjaValue};
+use std::collections::BTreeMap;
+use std::fmt;
+use url::Url;
 
 type DynError = Box<dyn std::error::Error + Send + Sync + 'static>;
 type Result<T> = std::result::Result<T, DynError>;
@@ -26,6 +29,143 @@ const ADMIN_WELCOME_TEXT_TMPL: &str = include_str!(concat!(
     "/templates/admin_welcome.txt"
 ));
 
+const EXISTING_ACCOUNT_HTML_TMPL: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/templates/existing_account.html"
+));
+const EXISTING_ACCOUNT_TEXT_TMPL: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/templates/existing_account.txt"
+));
+
+const UNIVERSITY_ID_TAKEN_HTML_TMPL: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/templates/university_id_taken.html"
+));
+const UNIVERSITY_ID_TAKEN_TEXT_TMPL: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/templates/university_id_taken.txt"
+));
+
+const PROJECT_NUDGE_HTML_TMPL: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/templates/project_nudge.html"
+));
+const PROJECT_NUDGE_TEXT_TMPL: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/templates/project_nudge.txt"
+));
+
+const GROUP_CHANGE_HTML_TMPL: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/templates/group_change.html"
+));
+const GROUP_CHANGE_TEXT_TMPL: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/templates/group_change.txt"
+));
+
+/// Longest sample value accepted when previewing a template
+const MAX_PREVIEW_VALUE_LEN: usize = 500;
+
+/// Templates that can be previewed, with the variables they use and the value shown when
+/// the sample context leaves one out
+const PREVIEWABLE: &[(&str, &[(&str, &str)])] = &[
+    (
+        "confirm",
+        &[
+            ("user_name", "Jane Doe"),
+            ("url", "https://example.com/confirm?t=preview-token"),
+        ],
+    ),
+    (
+        "reset",
+        &[
+            ("user_name", "Jane Doe"),
+            ("url", "https://example.com/reset?t=preview-token"),
+        ],
+    ),
+    (
+        "admin_welcome",
+        &[
+            ("user_name", "Jane Doe"),
+            ("email", "jane.doe@example.com"),
+            ("password", "preview-password"),
+            ("login_url", "https://example.com/login"),
+        ],
+    ),
+    (
+        "existing_account",
+        &[
+            ("user_name", "Jane Doe"),
+            ("login_url", "https://example.com/login"),
+        ],
+    ),
+    (
+        "university_id_taken",
+        &[
+            ("user_name", "Jane Doe"),
+            ("login_url", "https://example.com/login"),
+        ],
+    ),
+    (
+        "project_nudge",
+        &[
+            ("user_name", "Jane Doe"),
+            ("project_name", "Rusty Robots"),
+            ("reminder", "you are not in a group yet"),
+            ("url", "https://example.com/login"),
+        ],
+    ),
+    (
+        "group_change",
+        &[
+            ("user_name", "Jane Doe"),
+            ("group_name", "Rustaceans"),
+            ("change", "you were removed from the group"),
+            ("reason", "You asked to move to another group"),
+            ("url", "https://example.com/login"),
+        ],
+    ),
+];
+
+/// Both bodies of a rendered email
+#[derive(Debug)]
+pub struct RenderedEmail {
+    pub html: String,
+    pub text: String,
+}
+
+/// Reason a template preview was refused
+#[derive(Debug, PartialEq, Eq)]
+pub enum PreviewError {
+    UnknownTemplate(String),
+    /// The sample context sets a variable the template does not use
+    UnknownVariable(String),
+    ValueTooLong(String),
+    /// A link variable is not an http(s) URL
+    UnsafeUrl(String),
+    Render(String),
+}
+
+impl fmt::Display for PreviewError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PreviewError::UnknownTemplate(name) => write!(f, "Unknown template {}", name),
+            PreviewError::UnknownVariable(name) => {
+                write!(f, "Variable {} is not used by this template", name)
+            }
+            PreviewError::ValueTooLong(name) => write!(
+                f,
+                "Value of {} is longer than {} characters",
+                name, MAX_PREVIEW_VALUE_LEN
+            ),
+            PreviewError::UnsafeUrl(name) => write!(f, "Value of {} must be an http(s) URL", name),
+            PreviewError::Render(e) => write!(f, "Unable to render template: {}", e),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct TemplateEngine {
     env: Environment<'static>,
@@ -44,6 +184,18 @@ impl TemplateEngine {
         env.add_template("admin_welcome.html", ADMIN_WELCOME_HTML_TMPL)?;
         env.add_template("admin_welcome.txt", ADMIN_WELCOME_TEXT_TMPL)?;
 
+        env.add_template("existing_account.html", EXISTING_ACCOUNT_HTML_TMPL)?;
+        env.add_template("existing_account.txt", EXISTING_ACCOUNT_TEXT_TMPL)?;
+
+        env.add_template("university_id_taken.html", UNIVERSITY_ID_TAKEN_HTML_TMPL)?;
+        env.add_template("university_id_taken.txt", UNIVERSITY_ID_TAKEN_TEXT_TMPL)?;
+
+        env.add_template("project_nudge.html", PROJECT_NUDGE_HTML_TMPL)?;
+        env.add_template("project_nudge.txt", PROJECT_NUDGE_TEXT_TMPL)?;
+
+        env.add_template("group_change.html", GROUP_CHANGE_HTML_TMPL)?;
+        env.add_template("group_change.txt", GROUP_CHANGE_TEXT_TMPL)?;
+
         Ok(Self { env })
     }
 
@@ -51,6 +203,53 @@ impl TemplateEngine {
         let tmpl = self.env.get_template(name)?;
         Ok(tmpl.render(data)?)
     }
+
+    /// Renders both bodies of a template with a sample context, without sending anything
+    ///
+    /// Only the string variables the template uses are accepted, link variables must be
+    /// http(s) URLs and the HTML body is auto-escaped as in real emails.
+    pub fn preview(
+        &self, name: &str, sample: &BTreeMap<String, String>,
+    ) -> std::result::Result<RenderedEmail, PreviewError> {
+        let (_, variables) = PREVIEWABLE
+            .iter()
+            .find(|(template, _)| *template == name)
+            .ok_or_else(|| PreviewError::UnknownTemplate(name.to_string()))?;
+
+        if let Some(key) = sample
+            .keys()
+            .find(|key| !variables.iter().any(|(v, _)| v == key))
+        {
+            return Err(PreviewError::UnknownVariable(key.clone()));
+        }
+
+        let mut ctx = BTreeMap::new();
+        for (variable, default) in variables.iter() {
+            let value = sample.get(*variable).map_or(*default, String::as_str);
+
+            if value.chars().count() > MAX_PREVIEW_VALUE_LEN {
+                return Err(PreviewError::ValueTooLong(variable.to_string()));
+            }
+            if variable.ends_with("url")
+                && !Url::parse(value).is_ok_and(|u| matches!(u.scheme(), "http" | "https"))
+            {
+                return Err(PreviewError::UnsafeUrl(variable.to_string()));
+            }
+
+            ctx.insert(*variable, value);
+        }
+
+        let ctx = JinjaValue::from_serialize(&ctx);
+        let render = |file: String| {
+            self.render(&file, ctx.clone())
+                .map_err(|e| PreviewError::Render(e.to_string()))
+        };
+
+        Ok(RenderedEmail {
+            html: render(format!("{}.html", name))?,
+            text: render(format!("{}.txt", name))?,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -178,6 +377,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_render_existing_account_text() {
+        let engine = TemplateEngine::new().unwrap();
+        let ctx = minijinja::context! {
+            user_name => "Test User",
+            login_url => "https://test.example.com/login"
+        };
+
+        let text = engine.render("existing_account.txt", ctx).unwrap();
+        assert!(text.contains("Test User"));
+        assert!(text.contains("already exists"));
+        assert!(text.contains("https://test.example.com/login"));
+    }
+
+    #[test]
+    fn test_render_university_id_taken_text() {
+        let engine = TemplateEngine::new().unwrap();
+        let ctx = minijinja::context! {
+            user_name => "Test User",
+            login_url => "https://test.example.com/login"
+        };
+
+        let text = engine.render("university_id_taken.txt", ctx).unwrap();
+        assert!(text.contains("Test User"));
+        assert!(text.contains("university ID"));
+        assert!(text.contains("https://test.example.com/login"));
+    }
+
     #[test]
     fn test_render_nonexistent_template() {
         let engine = TemplateEngine::new().unwrap();
@@ -287,4 +514,67 @@ mod tests {
         assert!(result2.is_ok());
         assert_eq!(result1.unwrap(), result2.unwrap());
     }
+
+    fn sample(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_preview_confirm_fills_link() {
+        let engine = TemplateEngine::new().unwrap();
+        let link = "https://test.example.com/confirm?t=sample-token";
+
+        let preview = engine
+            .preview("confirm", &sample(&[("url", link)]))
+            .unwrap();
+
+        // The HTML body escapes the link like real emails do, the token shows through
+        assert!(preview.html.contains("t=sample-token"));
+        assert!(!preview.html.contains("preview-token"));
+        assert!(preview.text.contains(link));
+        // Variables left out fall back to their sample value
+        assert!(preview.text.contains("Jane Doe"));
+        assert!(!preview.html.contains("{{"));
+    }
+
+    #[test]
+    fn test_preview_rejects_unsafe_context() {
+        let engine = TemplateEngine::new().unwrap();
+
+        assert_eq!(
+            engine
+                .preview("confirm", &sample(&[("url", "javascript:alert(1)")]))
+                .unwrap_err(),
+            PreviewError::UnsafeUrl("url".to_string())
+        );
+        assert_eq!(
+            engine
+                .preview("confirm", &sample(&[("password", "x")]))
+                .unwrap_err(),
+            PreviewError::UnknownVariable("password".to_string())
+        );
+        assert_eq!(
+            engine.preview("welcome", &sample(&[])).unwrap_err(),
+            PreviewError::UnknownTemplate("welcome".to_string())
+        );
+        assert!(matches!(
+            engine.preview("reset", &sample(&[("user_name", &"a".repeat(501))])),
+            Err(PreviewError::ValueTooLong(_))
+        ));
+    }
+
+    #[test]
+    fn test_preview_escapes_html() {
+        let engine = TemplateEngine::new().unwrap();
+
+        let preview = engine
+            .preview("confirm", &sample(&[("user_name", "<script>x</script>")]))
+            .unwrap();
+
+        assert!(!preview.html.contains("<script>"));
+        assert!(preview.html.contains("&lt;script&gt;"));
+    }
 }
@@ -1,16 +1,19 @@
 use crate::api::configure_endpoints;
 use crate::app_data::AppData;
-use crate::config::Config;
+use crate::common::client_ip::TrustedProxies;
+use crate::common::date_format::DateFormatNegotiation;
+use crate::common::negotiation::ErrorNegotiation;
+use crate::database::query_budget::QueryBudget;
 use crate::database::repositories::admins_repository::create_default_admin;
+use crate::database::timing::set_slow_query_threshold;
 use crate::jwt::grants_extractor::extract;
-use crate::logging::init_console_logger;
-use crate::mail::Mailer;
-use actix_web::middleware::Logger;
+use crate::logging::{apply_log_filter, init_console_logger};
+use crate::preflight::{migrate_on_boot, preflight, FailureClass, Ready};
+use actix_web::middleware::{Condition, Logger};
 use actix_web::web::Data;
 use actix_web::{App, HttpServer};
 use actix_web_grants::GrantsMiddleware;
 use log::{error, info};
-use welds::connections::postgres::connect;
 
 mod api;
 mod app_data;
@@ -21,6 +24,7 @@ mod jwt;
 mod logging;
 mod mail;
 mod models;
+mod preflight;
 
 #[cfg(test)]
 mod test_utils;
@@ -28,36 +32,51 @@ mod test_utils;
 #[cfg(test)]
 mod password_tests;
 
+/// Default access log, with the client address resolved through the trusted proxies
+fn access_logger(trusted_proxies: TrustedProxies) -> Logger {
+    Logger::new(r#"%{client_ip}xi "%r" %s %b "%{Referer}i" "%{User-Agent}i" %T"#)
+        .custom_request_replace("client_ip", move |req| {
+            trusted_proxies
+                .service_client_ip(req)
+                .map_or_else(|| "-".to_string(), |ip| ip.to_string())
+        })
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    // load config from env or file
-    let app_config = Config::load();
-
     if let Err(e) = init_console_logger() {
         eprintln!("failed to initialize console logger: {}", e);
         std::process::exit(1);
     }
 
-    let client = match connect(app_config.db_url()).await {
-        Ok(client) => client,
-        Err(e) => {
-            error!("failed to connect to DB: {}", e);
-            std::process::exit(1);
+    // check config, database and migrations before touching anything
+    let Ready {
+        config: app_config,
+        client,
+        mailer,
+        log_filter,
+        trusted_proxies,
+        admin_ip_allowlist,
+    } = match preflight().await {
+        Ok(ready) => ready,
+        Err(report) => {
+            report.log();
+            std::process::exit(report.exit_code());
         }
     };
+    apply_log_filter(log_filter);
 
-    let mailer = match Mailer::from_config(&app_config) {
-        Ok(mailer) => mailer,
-        Err(e) => {
-            error!("failed to initialize mailer: {}", e);
-            std::process::exit(1);
-        }
-    };
+    set_slow_query_threshold(app_config.slow_query_ms());
 
-    let app_data = AppData::new(app_config.clone(), client.clone(), mailer).await;
+    let mut app_data = AppData::new(app_config.clone(), client.clone(), mailer).await;
+    app_data.trusted_proxies = trusted_proxies.clone();
+    app_data.admin_ip_allowlist = admin_ip_allowlist;
 
-    info!("migrating database schema");
-    sqlx::migrate!().run(client.as_sqlx_pool()).await.expect("");
+    // one-time setup, done before the workers are spawned so they never race on it
+    if let Err(e) = migrate_on_boot(app_config.run_migrations_on_boot(), &client).await {
+        error!("unable to migrate the database schema: {}", e);
+        std::process::exit(FailureClass::Migrations.exit_code());
+    }
 
     create_default_admin(
         &client,
@@ -65,13 +84,23 @@ async fn main() -> std::io::Result<()> {
         app_config.default_admin_password().clone(),
     )
     .await;
+    // roles may have just been seeded, drop anything read before
+    app_data.admin_roles_cache.invalidate();
+
+    let query_budget = QueryBudget {
+        budget: *app_config.query_budget(),
+        expose_count: app_config.expose_query_count(),
+    };
 
     info!("starting server");
     HttpServer::new(move || {
         App::new()
             .app_data(Data::new(app_data.clone())) //add application state with repositories and config
-            .wrap(Logger::default()) // add logging middleware
+            .wrap(Condition::new(cfg!(debug_assertions), query_budget)) // count database operations of debug builds
+            .wrap(access_logger(trusted_proxies.clone())) // add logging middleware
             .wrap(GrantsMiddleware::with_extractor(extract)) // add grants middleware for authorization
+            .wrap(DateFormatNegotiation) // serialize timestamps as Unix seconds for legacy clients
+            .wrap(ErrorNegotiation) // answer errors in plain text to clients asking for it
             .configure(configure_endpoints) // add scopes and routes
     })
     .workers(app_config.workers()) // normally 1 worker per thread
@@ -0,0 +1,39 @@
+use crate::models::admin::Admin;
+use chrono::{DateTime, Utc};
+use welds::WeldsModel;
+
+/// Long-lived token letting automation act as an admin with a restricted set of permissions
+///
+/// Only the SHA-256 hash of the token is stored, the plain value is shown once at creation.
+#[derive(Debug, Clone, WeldsModel)]
+#[welds(schema = "public", table = "admin_api_tokens")]
+#[welds(BelongsTo(admin, Admin, "admin_id"))]
+pub struct AdminApiToken {
+    #[welds(primary_key)]
+    pub api_token_id: i32,
+    #[welds(foreign_key = "admins.admin_id")]
+    pub admin_id: i32,
+    pub name: String,
+    pub token_hash: String,
+    /// Comma separated authorities, e.g. `ROLE_ADMIN_PROFESSOR`
+    pub permissions: String,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl AdminApiToken {
+    /// Authorities granted to requests authenticated with this token
+    pub(crate) fn permission_list(&self) -> Vec<String> {
+        self.permissions
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// A revoked token is kept for the listing but no longer authenticates
+    pub(crate) fn is_active(&self) -> bool {
+        self.revoked_at.is_none()
+    }
+}
@@ -11,7 +11,7 @@ pub struct AdminRole {
     pub name: String,
 }
 
-#[derive(PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
 #[repr(i32)]
 pub(crate) enum AvailableAdminRole {
     Root = 1,
@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use welds::WeldsModel;
+
+/// An admin started impersonating a student
+pub(crate) const IMPERSONATION_STARTED: &str = "impersonation_started";
+/// An admin ended an impersonation session
+pub(crate) const IMPERSONATION_ENDED: &str = "impersonation_ended";
+/// A Root admin changed the role of an admin
+pub(crate) const ADMIN_ROLE_CHANGED: &str = "admin_role_changed";
+/// An admin confirmed students in bulk, in place of their confirmation email
+pub(crate) const STUDENTS_CONFIRMED: &str = "students_confirmed";
+/// A student formed a group
+pub(crate) const GROUP_CREATED: &str = "group_created";
+/// A group leader added a member to their group
+pub(crate) const GROUP_MEMBER_ADDED: &str = "group_member_added";
+/// A group leader removed a member from their group
+pub(crate) const GROUP_MEMBER_REMOVED: &str = "group_member_removed";
+/// An admin removed a member from a group
+pub(crate) const ADMIN_GROUP_MEMBER_REMOVED: &str = "admin_group_member_removed";
+/// An admin transferred the leadership of a group
+pub(crate) const GROUP_LEADERSHIP_TRANSFERRED: &str = "group_leadership_transferred";
+/// An admin split a group in two
+pub(crate) const GROUP_SPLIT: &str = "group_split";
+/// A Root admin deleted orphaned links and implementation details
+pub(crate) const ORPHANS_CLEANED: &str = "orphans_cleaned";
+/// A Root admin moved the coordinations of an admin to another one
+pub(crate) const COORDINATIONS_REASSIGNED: &str = "coordinations_reassigned";
+/// An admin created a shareable download link to an upload
+pub(crate) const UPLOAD_LINK_SIGNED: &str = "upload_link_signed";
+/// An admin revoked the sessions of every student of a project
+pub(crate) const STUDENT_SESSIONS_REVOKED: &str = "student_sessions_revoked";
+/// A group selected its deliverable
+pub(crate) const GROUP_DELIVERABLE_SELECTED: &str = "group_deliverable_selected";
+/// An admin resolved or reopened a complaint
+pub(crate) const COMPLAINT_STATUS_CHANGED: &str = "complaint_status_changed";
+
+/// Record of a sensitive action, kept for accountability
+#[derive(Debug, Clone, WeldsModel)]
+#[welds(schema = "public", table = "audit_log")]
+pub struct AuditLog {
+    #[welds(primary_key)]
+    pub audit_log_id: i32,
+    #[welds(foreign_key = "admins.admin_id")]
+    pub actor_admin_id: Option<i32>,
+    #[welds(foreign_key = "students.student_id")]
+    pub actor_student_id: Option<i32>,
+    /// One of the action constants defined in this module
+    pub action: String,
+    #[welds(foreign_key = "projects.project_id")]
+    pub project_id: Option<i32>,
+    /// Kind of entity the action was performed on, e.g. `student`
+    pub target_type: Option<String>,
+    pub target_id: Option<i32>,
+    pub details: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditLog {
+    /// Entry for an action performed by an admin
+    pub(crate) fn by_admin(admin_id: i32, action: &str) -> Self {
+        Self {
+            audit_log_id: 0,
+            actor_admin_id: Some(admin_id),
+            actor_student_id: None,
+            action: action.to_string(),
+            project_id: None,
+            target_type: None,
+            target_id: None,
+            details: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Entry for an action performed by a student
+    pub(crate) fn by_student(student_id: i32, action: &str) -> Self {
+        Self {
+            actor_admin_id: None,
+            actor_student_id: Some(student_id),
+            ..Self::by_admin(0, action)
+        }
+    }
+
+    /// Sets the project the action relates to
+    pub(crate) fn project(mut self, project_id: i32) -> Self {
+        self.project_id = Some(project_id);
+        self
+    }
+
+    /// Sets the entity the action was performed on
+    pub(crate) fn target(mut self, target_type: &str, target_id: i32) -> Self {
+        self.target_type = Some(target_type.to_string());
+        self.target_id = Some(target_id);
+        self
+    }
+
+    /// Attaches free-form details to the entry
+    pub(crate) fn details(mut self, details: impl Into<String>) -> Self {
+        self.details = Some(details.into());
+        self
+    }
+}
@@ -12,5 +12,6 @@ pub struct Blacklist {
     pub description: String,
     pub first_name: String,
     pub last_name: String,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub banned_at: DateTime<Utc>,
 }
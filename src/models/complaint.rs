@@ -3,6 +3,11 @@ use serde::Serialize;
 use utoipa::ToSchema;
 use welds::WeldsModel;
 
+/// Complaint waiting for an admin to look at it
+pub(crate) const STATUS_OPEN: &str = "open";
+/// Complaint handled by an admin
+pub(crate) const STATUS_RESOLVED: &str = "resolved";
+
 #[derive(Debug, Clone, Serialize, ToSchema, WeldsModel)]
 #[welds(schema = "public", table = "complaints")]
 pub struct Complaint {
@@ -15,5 +20,14 @@ pub struct Complaint {
     #[welds(foreign_key = "groups.group_id")]
     pub to_group_id: i32,
     pub text: String,
+    /// One of the status constants defined in this module
+    #[schema(example = "open")]
+    pub status: String,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub created_at: DateTime<Utc>,
+    /// Group leader who filed the complaint, missing if the student was deleted
+    #[welds(foreign_key = "students.student_id")]
+    pub filed_by_student_id: Option<i32>,
+    /// Notes left by the admin who resolved the complaint
+    pub resolution_notes: Option<String>,
 }
@@ -11,7 +11,9 @@ pub struct Fair {
     #[welds(foreign_key = "projects.project_id")]
     pub project_id: i32,
     pub details: String,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub start_date: DateTime<Utc>,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub end_date: DateTime<Utc>,
     pub min_purchases: i32,
 }
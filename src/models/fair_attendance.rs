@@ -0,0 +1,24 @@
+use crate::models::fair::Fair;
+use crate::models::group::Group;
+use crate::models::student::Student;
+use chrono::{DateTime, Utc};
+use welds::WeldsModel;
+
+/// A student of a group recorded as present at a fair
+#[derive(Debug, Clone, WeldsModel)]
+#[welds(schema = "public", table = "fair_attendance")]
+#[welds(BelongsTo(fair, Fair, "fair_id"))]
+#[welds(BelongsTo(group, Group, "group_id"))]
+#[welds(BelongsTo(student, Student, "student_id"))]
+pub struct FairAttendance {
+    #[welds(primary_key)]
+    pub fair_attendance_id: i32,
+    #[welds(foreign_key = "fairs.fair_id")]
+    pub fair_id: i32,
+    #[welds(foreign_key = "groups.group_id")]
+    pub group_id: i32,
+    #[welds(foreign_key = "students.student_id")]
+    pub student_id: i32,
+    pub recorded_by_admin_id: Option<i32>,
+    pub recorded_at: DateTime<Utc>,
+}
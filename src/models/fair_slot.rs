@@ -0,0 +1,27 @@
+use crate::models::fair::Fair;
+use crate::models::group::Group;
+use chrono::{DateTime, Utc};
+use welds::WeldsModel;
+
+/// Time window in which a group presents at a fair
+#[derive(Debug, Clone, WeldsModel)]
+#[welds(schema = "public", table = "fair_slots")]
+#[welds(BelongsTo(fair, Fair, "fair_id"))]
+#[welds(BelongsTo(group, Group, "group_id"))]
+pub struct FairSlot {
+    #[welds(primary_key)]
+    pub fair_slot_id: i32,
+    #[welds(foreign_key = "fairs.fair_id")]
+    pub fair_id: i32,
+    #[welds(foreign_key = "groups.group_id")]
+    pub group_id: i32,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+impl FairSlot {
+    /// Slots overlap when each starts before the other ends, touching slots do not
+    pub(crate) fn overlaps(&self, starts_at: DateTime<Utc>, ends_at: DateTime<Utc>) -> bool {
+        self.starts_at < ends_at && starts_at < self.ends_at
+    }
+}
@@ -13,5 +13,6 @@ pub struct Group {
     #[welds(foreign_key = "projects.project_id")]
     pub project_id: i32,
     pub name: String,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub created_at: DateTime<Utc>,
 }
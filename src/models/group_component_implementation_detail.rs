@@ -26,6 +26,8 @@ pub struct GroupComponentImplementationDetail {
     pub group_deliverable_component_id: i32,
     pub markdown_description: String,
     pub repository_link: String,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub created_at: DateTime<Utc>,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub updated_at: DateTime<Utc>,
 }
@@ -18,4 +18,5 @@ pub struct GroupDeliverablesComponent {
     #[welds(foreign_key = "group_deliverable_components.group_deliverable_component_id")]
     pub group_deliverable_component_id: i32,
     pub quantity: i32,
+    pub weight: i32,
 }
@@ -0,0 +1,28 @@
+use crate::models::admin::Admin;
+use crate::models::student::Student;
+use chrono::{DateTime, Utc};
+use welds::WeldsModel;
+
+/// Time window in which a Root admin can act as a student for support
+#[derive(Debug, Clone, WeldsModel)]
+#[welds(schema = "public", table = "impersonation_sessions")]
+#[welds(BelongsTo(admin, Admin, "admin_id"))]
+#[welds(BelongsTo(student, Student, "student_id"))]
+pub struct ImpersonationSession {
+    #[welds(primary_key)]
+    pub impersonation_session_id: i32,
+    #[welds(foreign_key = "admins.admin_id")]
+    pub admin_id: i32,
+    #[welds(foreign_key = "students.student_id")]
+    pub student_id: i32,
+    pub started_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl ImpersonationSession {
+    /// A session authenticates requests until it is ended or expires
+    pub(crate) fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.ended_at.is_none() && now < self.expires_at
+    }
+}
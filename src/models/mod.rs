@@ -3,14 +3,19 @@ pub mod project;
 
 // Admin related models
 pub mod admin;
+pub mod admin_api_token;
 pub mod admin_role;
+pub mod audit_log;
 pub mod coordinator_project;
 
 // Student related models
 pub mod blacklist;
+pub mod impersonation_session;
 pub mod security_code;
 pub mod student;
+pub mod student_project_access;
 pub mod student_role;
+pub mod used_reset_token;
 
 // Group related models
 pub mod complaint;
@@ -19,6 +24,8 @@ pub mod group_member;
 
 // Fair related models
 pub mod fair;
+pub mod fair_attendance;
+pub mod fair_slot;
 
 // Group deliverables and components
 pub mod group_component_implementation_detail;
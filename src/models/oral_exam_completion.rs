@@ -12,6 +12,7 @@ pub struct OralExamCompletion {
     pub student_id: i32,
     #[welds(foreign_key = "projects.project_id")]
     pub project_id: i32,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub completed_at: DateTime<Utc>,
     pub completed_by_admin_id: Option<i32>,
 }
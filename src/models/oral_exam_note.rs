@@ -13,6 +13,7 @@ pub struct OralExamNote {
     #[welds(foreign_key = "projects.project_id")]
     pub project_id: i32,
     pub note_text: String,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub updated_at: DateTime<Utc>,
     pub updated_by_admin_id: Option<i32>,
 }
@@ -21,11 +21,37 @@ pub struct Project {
     #[welds(primary_key)]
     pub project_id: i32,
     pub name: String,
+    /// Unique, URL friendly identifier generated from the name
+    pub slug: String,
     pub year: i32,
     pub max_student_uploads: i32,
     pub max_group_size: i32,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339_opt")]
     pub deliverable_selection_deadline: Option<DateTime<Utc>>,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339_opt")]
     pub upload_deadline: Option<DateTime<Utc>>,
     pub active: bool,
     pub oral_exam_enabled: bool,
+    /// Students cannot change groups, selections or uploads of a frozen project
+    pub frozen: bool,
+    /// Comma separated email domains admitted when redeeming the codes of the project,
+    /// every domain is when not set
+    pub allowed_signup_domains: Option<String>,
+    /// Students only see published projects, drafts are for admins only
+    pub published: bool,
+}
+
+impl Project {
+    /// Email domains admitted when redeeming the codes of the project, `None` when every
+    /// domain is
+    pub(crate) fn signup_domain_override(&self) -> Option<Vec<String>> {
+        self.allowed_signup_domains.as_ref().map(|domains| {
+            domains
+                .split(',')
+                .map(str::trim)
+                .filter(|d| !d.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+    }
 }
@@ -13,3 +13,33 @@ pub struct SecurityCode {
     pub code: String,
     pub expiration: DateTime<Utc>,
 }
+
+impl SecurityCode {
+    /// A code can no longer be redeemed from its expiration instant on
+    pub(crate) fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expiration <= now
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_data::clock::{Clock, MockClock};
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_code_expires_exactly_at_expiration() {
+        let expiration = Utc.with_ymd_and_hms(2026, 5, 10, 18, 0, 0).unwrap();
+        let code = SecurityCode {
+            security_code_id: 1,
+            project_id: 1,
+            code: "ABC123".to_string(),
+            expiration,
+        };
+        let clock = MockClock::new(expiration - Duration::milliseconds(1));
+
+        assert!(!code.is_expired(clock.now()));
+        clock.advance(Duration::milliseconds(1));
+        assert!(code.is_expired(clock.now()));
+    }
+}
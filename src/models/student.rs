@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use welds::WeldsModel;
 
 #[derive(Debug, Clone, WeldsModel)]
@@ -11,4 +12,6 @@ pub struct Student {
     pub university_id: i32,
     pub password_hash: String,
     pub is_pending: bool,
+    /// Tokens issued up to this time are no longer accepted
+    pub sessions_revoked_at: Option<DateTime<Utc>>,
 }
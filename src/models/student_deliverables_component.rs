@@ -18,4 +18,5 @@ pub struct StudentDeliverablesComponent {
     #[welds(foreign_key = "student_deliverable_components.student_deliverable_component_id")]
     pub student_deliverable_component_id: i32,
     pub quantity: i32,
+    pub weight: i32,
 }
@@ -0,0 +1,17 @@
+use chrono::{DateTime, Utc};
+use welds::WeldsModel;
+
+/// Access to a project granted to a student by redeeming one of its security codes
+#[derive(Debug, Clone, WeldsModel)]
+#[welds(schema = "public", table = "student_project_access")]
+pub struct StudentProjectAccess {
+    #[welds(primary_key)]
+    pub student_project_access_id: i32,
+    #[welds(foreign_key = "students.student_id")]
+    pub student_id: i32,
+    #[welds(foreign_key = "projects.project_id")]
+    pub project_id: i32,
+    #[welds(foreign_key = "security_codes.security_code_id")]
+    pub security_code_id: Option<i32>,
+    pub granted_at: DateTime<Utc>,
+}
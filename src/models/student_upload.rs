@@ -12,5 +12,8 @@ pub struct StudentUpload {
     pub student_deliverable_selection_id: i32,
     pub path: String,
     pub upload_count: i32,
+    /// Version of the current file, earlier versions stay in storage
+    pub version: i32,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub timestamp: DateTime<Utc>,
 }
@@ -16,5 +16,12 @@ pub struct Transaction {
     pub group_deliverable_component_id: i32,
     #[welds(foreign_key = "fairs.fair_id")]
     pub fair_id: i32,
+    #[serde(serialize_with = "crate::common::timestamps::rfc3339")]
     pub timestamp: DateTime<Utc>,
+    /// Transaction cancelled by this reversing entry, absent on purchases
+    #[welds(foreign_key = "transactions.transaction_id")]
+    pub reverses_transaction_id: Option<i32>,
+    /// Admin who reversed the purchase, set on reversing entries
+    #[welds(foreign_key = "admins.admin_id")]
+    pub reversed_by_admin_id: Option<i32>,
 }
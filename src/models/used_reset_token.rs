@@ -0,0 +1,11 @@
+use chrono::{DateTime, Utc};
+use welds::WeldsModel;
+
+/// Password reset token that was already used, stored as the SHA-256 hash of its value
+#[derive(Debug, Clone, WeldsModel)]
+#[welds(schema = "public", table = "used_reset_tokens")]
+pub struct UsedResetToken {
+    #[welds(primary_key)]
+    pub token_hash: String,
+    pub used_at: DateTime<Utc>,
+}
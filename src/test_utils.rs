@@ -1,10 +1,33 @@
 //! Test utilities and common test data for unit tests
 
+use crate::app_data::AppData;
 use crate::config::Config;
+use crate::database::query_budget::counting;
+use crate::jwt::token::TokenKeys;
+use crate::mail::Mailer;
+use async_trait::async_trait;
+use sqlx::postgres::PgPoolOptions;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use welds::connections::errors::Result as ConnectionResult;
+use welds::connections::postgres::PostgresClient;
+use welds::connections::{ExecuteResult, Fetch, Param};
+use welds::{Client, Row, Syntax};
 
 /// Test constants for consistent testing
 pub const TEST_JWT_SECRET: &[u8] = b"test-secret-key-for-jwt-tokens-32-chars";
+pub const TEST_ADMIN_JWT_KEYS: TokenKeys<'static> = TokenKeys {
+    secret: b"test-admin-secret-key-for-jwt-tokens",
+    issuer: "test-backend",
+    audience: "test-frontend",
+    admin: true,
+};
+pub const TEST_STUDENT_JWT_KEYS: TokenKeys<'static> = TokenKeys {
+    secret: b"test-student-secret-key-for-jwt-tokens",
+    issuer: "test-backend",
+    audience: "test-frontend",
+    admin: false,
+};
 pub const TEST_EMAIL_TOKEN_SECRET: &str = "test-email-token-secret";
 pub const TEST_ADMIN_EMAIL: &str = "admin@test.com";
 pub const TEST_STUDENT_EMAIL: &str = "student@test.com";
@@ -124,6 +147,83 @@ pub fn create_minimal_test_config() -> Config {
     Config::load()
 }
 
+/// Runs `fut`, failing the test if it runs more than `max` database operations
+///
+/// Operations are the repository calls measured by `timed`, so a loop querying once per
+/// item shows up here as soon as there are more items than the budget.
+pub async fn assert_max_queries<T>(max: u32, fut: impl std::future::Future<Output = T>) -> T {
+    let (output, count) = counting(fut).await;
+    assert!(
+        count <= max,
+        "expected at most {} database operations, ran {}",
+        max,
+        count
+    );
+    output
+}
+
+/// Database client that records the statements it is given instead of running them
+///
+/// Every statement affects and returns no rows, enough to count the statements a repository
+/// call issues without a database.
+#[derive(Clone, Default)]
+pub struct RecordingClient {
+    statements: Arc<Mutex<Vec<String>>>,
+}
+
+impl RecordingClient {
+    /// SQL of every statement run so far, in order
+    pub fn statements(&self) -> Vec<String> {
+        self.statements.lock().unwrap().clone()
+    }
+
+    fn record(&self, sql: &str) {
+        self.statements.lock().unwrap().push(sql.to_string());
+    }
+}
+
+#[async_trait]
+impl Client for RecordingClient {
+    async fn execute(
+        &self, sql: &str, _params: &[&(dyn Param + Sync)],
+    ) -> ConnectionResult<ExecuteResult> {
+        self.record(sql);
+        Ok(ExecuteResult::new(0))
+    }
+
+    async fn fetch_rows(
+        &self, sql: &str, _params: &[&(dyn Param + Sync)],
+    ) -> ConnectionResult<Vec<Row>> {
+        self.record(sql);
+        Ok(Vec::new())
+    }
+
+    async fn fetch_many<'s, 'args, 't>(
+        &self, fetches: &[Fetch<'s, 'args, 't>],
+    ) -> ConnectionResult<Vec<Vec<Row>>> {
+        fetches.iter().for_each(|fetch| self.record(fetch.sql));
+        Ok(fetches.iter().map(|_| Vec::new()).collect())
+    }
+
+    fn syntax(&self) -> Syntax {
+        Syntax::Postgres
+    }
+}
+
+/// Creates application state for handler tests
+///
+/// The database pool connects lazily, so handlers that return before querying
+/// (e.g. on authorization failures) can be called without a database.
+pub async fn create_test_app_data() -> AppData {
+    let config = create_test_config();
+    let pool = PgPoolOptions::new()
+        .connect_lazy(&config.db_url().expect("test database url should be set"))
+        .expect("test database url should be valid");
+    let mailer = Mailer::from_config(&config).expect("test mailer config should be valid");
+
+    AppData::new(config, PostgresClient::from(pool), mailer).await
+}
+
 /// Helper to create test email data for templates
 pub fn create_test_email_context() -> minijinja::Value {
     minijinja::context! {